@@ -0,0 +1,68 @@
+//! Benchmarks for formatting large result sets
+//!
+//! Run with `cargo bench --bench formatter`.
+
+use std::hint::black_box;
+
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use mongodb::bson::{DateTime, Document, oid::ObjectId};
+use mongosh::config::{DisplayConfig, OutputFormat};
+use mongosh::executor::{ExecutionResult, ExecutionStats, ResultData};
+use mongosh::formatter::Formatter;
+
+/// Documents shaped like typical application data: scalars, a nested
+/// document, an array and BSON types with shell wrappers
+fn documents(count: usize) -> Vec<Document> {
+    (0..count)
+        .map(|i| {
+            mongodb::bson::doc! {
+                "_id": ObjectId::new(),
+                "name": format!("user-{}", i),
+                "age": (i % 90) as i32,
+                "balance": i as f64 * 1.25,
+                "active": i % 2 == 0,
+                "created": DateTime::from_millis(1_700_000_000_000 + i as i64),
+                "address": { "city": "Oslo", "zip": format!("{:04}", i % 10_000) },
+                "tags": ["alpha", "beta", format!("t{}", i % 7)],
+            }
+        })
+        .collect()
+}
+
+fn bench_format(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Formatter::format");
+
+    for count in [100, 1_000, 10_000] {
+        let result = ExecutionResult {
+            success: true,
+            data: ResultData::Documents(documents(count)),
+            stats: ExecutionStats::default(),
+            error: None,
+        };
+        group.throughput(Throughput::Elements(count as u64));
+
+        for format in [
+            OutputFormat::Shell,
+            OutputFormat::Json,
+            OutputFormat::JsonPretty,
+            OutputFormat::Table,
+        ] {
+            let formatter = Formatter::from_config(&DisplayConfig {
+                format,
+                color_output: false,
+                show_timing: false,
+                ..Default::default()
+            });
+            group.bench_with_input(
+                BenchmarkId::new(format.as_str(), count),
+                &result,
+                |b, result| b.iter(|| formatter.format(black_box(result)).unwrap()),
+            );
+        }
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_format);
+criterion_main!(benches);
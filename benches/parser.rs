@@ -0,0 +1,77 @@
+//! Benchmarks for the shell and SQL parsers
+//!
+//! Run with `cargo bench --bench parser`.
+
+use std::hint::black_box;
+
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use mongosh::parser::{MongoParser, Parser, SqlLexer};
+
+/// A filter nested `depth` levels deep through `$and`/`$or`, with a few
+/// comparison operators at every level
+fn nested_filter(depth: usize) -> String {
+    let mut filter = "{ status: 'active', age: { $gte: 18, $lt: 65 } }".to_string();
+    for level in 0..depth {
+        let op = if level % 2 == 0 { "$and" } else { "$or" };
+        filter = format!(
+            "{{ {}: [{}, {{ score_{}: {{ $in: [1, 2, 3] }}, name: /^a/i }}] }}",
+            op, filter, level
+        );
+    }
+    filter
+}
+
+/// A SELECT with `columns` projected fields and as many WHERE conditions
+fn sql_query(columns: usize) -> String {
+    let fields: Vec<String> = (0..columns).map(|i| format!("field_{}", i)).collect();
+    let conditions: Vec<String> = (0..columns)
+        .map(|i| format!("field_{} >= {} AND name_{} LIKE 'a%'", i, i, i))
+        .collect();
+    format!(
+        "SELECT {} FROM users WHERE {} ORDER BY field_0 DESC LIMIT 100",
+        fields.join(", "),
+        conditions.join(" OR ")
+    )
+}
+
+fn bench_mongo_parser(c: &mut Criterion) {
+    let mut group = c.benchmark_group("MongoParser::parse");
+    for depth in [1, 8, 32] {
+        let input = nested_filter(depth);
+        group.throughput(Throughput::Bytes(input.len() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(depth), &input, |b, input| {
+            b.iter(|| MongoParser::parse(black_box(input)).unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn bench_parser(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Parser::parse");
+    for depth in [1, 8, 32] {
+        let input = format!(
+            "db.users.find({}).sort({{ age: -1 }}).limit(20)",
+            nested_filter(depth)
+        );
+        group.throughput(Throughput::Bytes(input.len() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(depth), &input, |b, input| {
+            b.iter(|| Parser::new().parse(black_box(input)).unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn bench_sql_lexer(c: &mut Criterion) {
+    let mut group = c.benchmark_group("SqlLexer::tokenize");
+    for columns in [4, 32, 256] {
+        let input = sql_query(columns);
+        group.throughput(Throughput::Bytes(input.len() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(columns), &input, |b, input| {
+            b.iter(|| SqlLexer::tokenize(black_box(input)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_mongo_parser, bench_parser, bench_sql_lexer);
+criterion_main!(benches);
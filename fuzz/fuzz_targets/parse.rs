@@ -0,0 +1,9 @@
+//! Fuzz the shell and SQL parsers: `cargo +nightly fuzz run parse`
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    mongosh::parser::fuzz_entry(data);
+});
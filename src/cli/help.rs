@@ -0,0 +1,1810 @@
+//! Command reference for mongosh
+//!
+//! This module holds the structured registry behind `help <command>` in the
+//! REPL and the `mongosh help --format markdown|json` subcommand, so the
+//! generated reference docs and the in-shell help never drift apart.
+//!
+//! Topics are matched case-insensitively, through aliases such as `sql`, and
+//! by edit distance, so `help agregate` still finds `aggregate`.
+
+use serde::Serialize;
+
+use crate::error::{ConfigError, MongoshError, Result};
+use crate::formatter::AnsiColors;
+
+/// A documented option of a command
+#[derive(Debug, Clone, Serialize)]
+pub struct HelpOption {
+    /// Option as typed, e.g. `--watch [seconds]`
+    pub name: &'static str,
+
+    /// What the option does
+    pub description: &'static str,
+}
+
+/// A documented command
+#[derive(Debug, Clone, Serialize)]
+pub struct CommandHelp {
+    /// Command name used to look up help, e.g. `show log`
+    pub name: &'static str,
+
+    /// Section the command is listed under
+    pub category: &'static str,
+
+    /// Usage syntax
+    pub syntax: &'static str,
+
+    /// One-line description
+    pub description: &'static str,
+
+    /// Supported options
+    pub options: &'static [HelpOption],
+
+    /// Example invocations
+    pub examples: &'static [&'static str],
+}
+
+/// Output format of the command reference
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HelpFormat {
+    /// Plain text, as shown in the REPL
+    Text,
+    /// Markdown document
+    Markdown,
+    /// JSON array of commands
+    Json,
+}
+
+impl HelpFormat {
+    /// Parse a format name
+    ///
+    /// # Arguments
+    /// * `name` - Format name (text, markdown/md, json)
+    ///
+    /// # Returns
+    /// * `Result<Self>` - Parsed format or error
+    pub fn parse(name: &str) -> Result<Self> {
+        match name.to_lowercase().as_str() {
+            "text" | "txt" => Ok(Self::Text),
+            "markdown" | "md" => Ok(Self::Markdown),
+            "json" => Ok(Self::Json),
+            _ => Err(MongoshError::Config(ConfigError::Generic(format!(
+                "Unsupported help format: {}. Supported formats: text, markdown, json",
+                name
+            )))),
+        }
+    }
+}
+
+/// All commands known to the shell, in display order
+pub const COMMANDS: &[CommandHelp] = &[
+    CommandHelp {
+        name: "show dbs",
+        category: "Shell",
+        syntax: "show dbs | show databases",
+        description: "List databases with their sizes",
+        options: &[],
+        examples: &["show dbs"],
+    },
+    CommandHelp {
+        name: "show collections",
+        category: "Shell",
+        syntax: "show collections | show tables",
+        description: "List collections in the current database",
+        options: &[],
+        examples: &["show collections"],
+    },
+    CommandHelp {
+        name: "show users",
+        category: "Shell",
+        syntax: "show users",
+        description: "List users of the current database",
+        options: &[],
+        examples: &["show users"],
+    },
+    CommandHelp {
+        name: "show roles",
+        category: "Shell",
+        syntax: "show roles",
+        description: "List roles of the current database",
+        options: &[],
+        examples: &["show roles"],
+    },
+    CommandHelp {
+        name: "show profile",
+        category: "Shell",
+        syntax: "show profile",
+        description: "Show the most recent profiler entries",
+        options: &[],
+        examples: &["show profile"],
+    },
+    CommandHelp {
+        name: "show logs",
+        category: "Shell",
+        syntax: "show logs",
+        description: "List the available server log names",
+        options: &[],
+        examples: &["show logs"],
+    },
+    CommandHelp {
+        name: "show log",
+        category: "Shell",
+        syntax: "show log <name> [--grep <text>] [--since <duration>] [--follow [seconds]]",
+        description: "Show recent entries of a server log",
+        options: &[
+            HelpOption {
+                name: "--grep <text>",
+                description: "Only show lines containing the text",
+            },
+            HelpOption {
+                name: "--since <duration>",
+                description: "Only show entries newer than e.g. 30s, 10m, 2h or 1d",
+            },
+            HelpOption {
+                name: "--follow, -f [seconds]",
+                description: "Keep printing new entries, polling every few seconds (default 2), until Ctrl+C",
+            },
+        ],
+        examples: &[
+            "show log global",
+            "show log global --grep slow --since 10m",
+            "show log global --follow --grep error",
+        ],
+    },
+    CommandHelp {
+        name: "use",
+        category: "Shell",
+        syntax: "use <database>",
+        description: "Switch the current database",
+        options: &[],
+        examples: &["use test"],
+    },
+    CommandHelp {
+        name: "top",
+        category: "Shell",
+        syntax: "top [--watch [seconds]] [--limit <n>]",
+        description: "Show per-namespace read and write activity",
+        options: &[
+            HelpOption {
+                name: "--watch, -w [seconds]",
+                description: "Refresh continuously at the given interval",
+            },
+            HelpOption {
+                name: "--limit, -n <n>",
+                description: "Number of namespaces to show",
+            },
+        ],
+        examples: &["top", "top --watch 5 --limit 20"],
+    },
+    CommandHelp {
+        name: "currentOp",
+        category: "Shell",
+        syntax: "currentOp [--watch [seconds]] [--all]",
+        description: "Show in-progress operations with their duration and plan summary, longest running first",
+        options: &[
+            HelpOption {
+                name: "--watch, -w [seconds]",
+                description: "Open a monitor refreshing every N seconds (default 1); ↑/↓ select, x kills the selected operation, q quits",
+            },
+            HelpOption {
+                name: "--all, -a",
+                description: "Include idle connections and sessions",
+            },
+        ],
+        examples: &[
+            "currentOp",
+            "currentOp --watch",
+            "currentOp --watch 5 --all",
+        ],
+    },
+    CommandHelp {
+        name: "rs.lag",
+        category: "Shell",
+        syntax: "rs.lag [--watch [seconds]] [--max-lag <duration>]",
+        description: "Show each replica set member's state and lag behind the primary from replSetGetStatus, colored green, yellow (10s or more) or red (60s or more, or unhealthy)",
+        options: &[
+            HelpOption {
+                name: "--watch, -w [seconds]",
+                description: "Print the table every N seconds (default 5) until Ctrl+C",
+            },
+            HelpOption {
+                name: "--max-lag <duration>",
+                description: "Fail once a member is further behind, e.g. 30 or 2m; a script run with --file then exits non-zero",
+            },
+        ],
+        examples: &["rs.lag", "rs.lag --watch 10", "rs.lag --max-lag 30s"],
+    },
+    CommandHelp {
+        name: "oplog tail",
+        category: "Shell",
+        syntax: "oplog tail [filter]",
+        description: "Print new oplog entries as they are written, one line each with time, operation, namespace and the changed document, until Ctrl+C. The filter is matched against local.oplog.rs entries; add a ts condition to start from an earlier point",
+        options: &[],
+        examples: &[
+            "oplog tail",
+            "oplog tail {ns: 'mydb.users'}",
+            "oplog tail {ns: /^mydb\\./, op: {$in: ['u', 'd']}}",
+        ],
+    },
+    CommandHelp {
+        name: "dump",
+        category: "Shell",
+        syntax: "dump [dir] [--collection <name>]...",
+        description: "Write the current database to <dir>/<db>/ in mongodump format (default dir: dump)",
+        options: &[HelpOption {
+            name: "--collection, -c <name>",
+            description: "Only dump this collection; may be repeated",
+        }],
+        examples: &["dump", "dump backups --collection orders"],
+    },
+    CommandHelp {
+        name: "restore",
+        category: "Shell",
+        syntax: "restore [dir] [--drop] [--collection <name>]...",
+        description: "Load a dump written by dump or mongodump into the current database",
+        options: &[
+            HelpOption {
+                name: "--drop",
+                description: "Drop each collection before restoring it",
+            },
+            HelpOption {
+                name: "--collection, -c <name>",
+                description: "Only restore this collection; may be repeated",
+            },
+        ],
+        examples: &["restore", "restore backups/shop --drop"],
+    },
+    CommandHelp {
+        name: "import",
+        category: "Shell",
+        syntax: "import <file.csv> [--collection <name>] [--locale <tag>] [--delimiter <char>] [--decimal-separator <.|,>]",
+        description: "Insert the rows of a CSV file with a header line into a collection of the current database (default: the file name). Numbers written in the chosen locale become integers or doubles, true/false become booleans, empty fields are left out and numbers with leading zeros stay strings",
+        options: &[
+            HelpOption {
+                name: "--collection, -c <name>",
+                description: "Target collection",
+            },
+            HelpOption {
+                name: "--locale <tag>",
+                description: "Number format of a language or region, e.g. de, fr-FR or de-CH; decimal-comma locales also default to ';' as delimiter",
+            },
+            HelpOption {
+                name: "--delimiter, -d <char>",
+                description: "Field separator; use tab for tab-separated files (default: ,)",
+            },
+            HelpOption {
+                name: "--decimal-separator <.|,>",
+                description: "Decimal separator, overriding the locale",
+            },
+        ],
+        examples: &[
+            "import orders.csv",
+            "import export_2024.csv --collection orders --locale de",
+            "import prices.tsv -d tab --decimal-separator ,",
+        ],
+    },
+    CommandHelp {
+        name: "undo last-delete",
+        category: "Shell",
+        syntax: "undo last-delete",
+        description: "Put the documents removed by the most recent deleteOne or deleteMany back into their collections. Needs safety.trash = true, which copies deleted documents to the __mongosh_trash collection first; documents whose _id exists again are skipped",
+        options: &[],
+        examples: &[
+            "config set safety.trash true",
+            "db.orders.deleteMany({status: 'cancelled'})",
+            "undo last-delete",
+        ],
+    },
+    CommandHelp {
+        name: "backups",
+        category: "Shell",
+        syntax: "backups [cleanup [--older-than <duration>]]",
+        description: "List the <collection>.__backup_YYYYMMDD copies taken before drop() and renameCollection(), or drop the ones older than safety.backup_retention_days. Backups are taken when safety.backup_before_drop is on or the call passes { backup: true }",
+        options: &[HelpOption {
+            name: "--older-than <duration>",
+            description: "Drop backups older than this instead, e.g. 12h or 30d",
+        }],
+        examples: &[
+            "backups",
+            "backups cleanup",
+            "backups cleanup --older-than 30d",
+        ],
+    },
+    CommandHelp {
+        name: "lookup wizard",
+        category: "Shell",
+        syntax: "lookup wizard",
+        description: "Build a $lookup stage step by step, with collection and field completion, then print or run it",
+        options: &[],
+        examples: &["lookup wizard"],
+    },
+    CommandHelp {
+        name: "watch",
+        category: "Shell",
+        syntax: "watch [seconds] <command>",
+        description: "Re-run a command every few seconds (default 2), highlighting what changed, until Ctrl+C",
+        options: &[],
+        examples: &[
+            "watch 5 db.orders.countDocuments({ status: \"pending\" })",
+            "watch 10 currentOp",
+        ],
+    },
+    CommandHelp {
+        name: "translate",
+        category: "Shell",
+        syntax: "translate <rust|node|python|go|java> <command>",
+        description: "Show the driver code for a query (find, aggregate, count, distinct, insert, update, replace, delete) without running it",
+        options: &[],
+        examples: &[
+            "translate rust db.users.find({ age: { $gt: 18 } }).limit(5)",
+            "translate python SELECT name FROM users WHERE age > 18",
+        ],
+    },
+    CommandHelp {
+        name: "describe",
+        category: "Shell",
+        syntax: "describe <command>",
+        description: "Explain in plain words what a query, write or drop would do, with a count of the documents it would touch, without running it",
+        options: &[],
+        examples: &[
+            "describe db.orders.deleteMany({ status: \"cancelled\" })",
+            "describe db.users.find({ age: { $gt: 18 } }).limit(10)",
+        ],
+    },
+    CommandHelp {
+        name: "it",
+        category: "Shell",
+        syntax: "it",
+        description: "Show the next page of the last cursor",
+        options: &[],
+        examples: &["it"],
+    },
+    CommandHelp {
+        name: "retry",
+        category: "Shell",
+        syntax: "retry",
+        description: "Run the last failed command again; writes that are not safe to repeat (inserts, $inc, deleteOne by filter, ...) ask for confirmation first",
+        options: &[],
+        examples: &["retry"],
+    },
+    CommandHelp {
+        name: "output last",
+        category: "Shell",
+        syntax: "output last > <file>",
+        description: "Write the previous result to a file in the current output format, without colors",
+        options: &[],
+        examples: &["output last > users.json"],
+    },
+    CommandHelp {
+        name: "copy",
+        category: "Shell",
+        syntax: "copy | db.<collection>.find(...).copy()",
+        description: "Put the previous result, or a query's results, on the system clipboard as pretty-printed JSON (up to 1 MiB)",
+        options: &[],
+        examples: &["copy", "db.orders.find({status: 'failed'}).pluck('_id', 'error').copy()"],
+    },
+    CommandHelp {
+        name: "expand",
+        category: "Shell",
+        syntax: "expand <path>",
+        description: "Show a field of the previous result that shell output collapsed to {…} (N keys) or [… N items]. With several documents the path starts with the document's position; an expanded document becomes the previous result, so expand again to drill further. Collapsing is set by display.collapse_depth and display.collapse_width",
+        options: &[],
+        examples: &[
+            "expand address",
+            "expand 2.items",
+            "expand items.499",
+            "config set display.collapse_width 20",
+        ],
+    },
+    CommandHelp {
+        name: "help",
+        category: "Shell",
+        syntax: "help [command]",
+        description: "Show general help or help for a specific command",
+        options: &[],
+        examples: &["help", "help find", "help sql", "help agregate"],
+    },
+    CommandHelp {
+        name: "exit",
+        category: "Shell",
+        syntax: "exit | quit",
+        description: "Exit the shell",
+        options: &[],
+        examples: &["exit"],
+    },
+    CommandHelp {
+        name: "format",
+        category: "Configuration",
+        syntax: "format [shell|json|json-pretty|table|compact|extjson|extjson-canonical|chart|map]",
+        description: "Set or show the output format; `.format(...)`, `|> format <name>` or a trailing `--format=<name>` applies to one command",
+        options: &[],
+        examples: &[
+            "format table",
+            "db.users.find().format('json')",
+            "show collections --format=table",
+        ],
+    },
+    CommandHelp {
+        name: "color",
+        category: "Configuration",
+        syntax: "color [on|off]",
+        description: "Enable, disable or show colored output",
+        options: &[],
+        examples: &["color off"],
+    },
+    CommandHelp {
+        name: "config",
+        category: "Configuration",
+        syntax: "config",
+        description: "Show the current shell configuration",
+        options: &[],
+        examples: &["config"],
+    },
+    CommandHelp {
+        name: "config get",
+        category: "Configuration",
+        syntax: "config get <key>",
+        description: "Show a setting or section by its dotted key, including changes made in this session",
+        options: &[],
+        examples: &["config get display.format", "config get query"],
+    },
+    CommandHelp {
+        name: "config set",
+        category: "Configuration",
+        syntax: "config set <key> <value> [--save]",
+        description: "Change a setting for this session; display, query, safety and redaction settings apply immediately",
+        options: &[HelpOption {
+            name: "--save",
+            description: "Also write the setting to the config file, keeping its comments",
+        }],
+        examples: &[
+            "config set display.page_size 50",
+            "config set safety.max_results 1000 --save",
+            "config set safety.preview_counts true",
+        ],
+    },
+    CommandHelp {
+        name: "session save",
+        category: "Configuration",
+        syntax: "session save <file>",
+        description: "Save the datasource, database, settings, previous result, named queries and bookmarks to a file",
+        options: &[],
+        examples: &["session save incident-42.toml"],
+    },
+    CommandHelp {
+        name: "session load",
+        category: "Configuration",
+        syntax: "session load <file>",
+        description: "Restore a saved session, switching datasource if needed and adding its named queries and bookmarks",
+        options: &[],
+        examples: &["session load incident-42.toml"],
+    },
+    CommandHelp {
+        name: "record start",
+        category: "Configuration",
+        syntax: "record start <file>",
+        description: "Write every command and its output, with timestamps, to a transcript; re-run it with `mongosh replay <file>`",
+        options: &[],
+        examples: &["record start incident-42.log"],
+    },
+    CommandHelp {
+        name: "record stop",
+        category: "Configuration",
+        syntax: "record stop",
+        description: "Stop recording and close the transcript",
+        options: &[],
+        examples: &["record stop"],
+    },
+    CommandHelp {
+        name: "report start",
+        category: "Configuration",
+        syntax: "report start <file>",
+        description: "Append each command and its result to a Markdown report as fenced code blocks",
+        options: &[],
+        examples: &["report start incident-42.md"],
+    },
+    CommandHelp {
+        name: "report stop",
+        category: "Configuration",
+        syntax: "report stop",
+        description: "Close the Markdown report",
+        options: &[],
+        examples: &["report stop"],
+    },
+    CommandHelp {
+        name: "query",
+        category: "Named Queries",
+        syntax: "query [<name> [args...]]",
+        description: "List named queries, or execute one with arguments",
+        options: &[],
+        examples: &["query", "query user John 25"],
+    },
+    CommandHelp {
+        name: "query save",
+        category: "Named Queries",
+        syntax: "query save <name> <query>",
+        description: "Save a named query; $1, $2... are replaced by arguments",
+        options: &[
+            HelpOption {
+                name: "'$1', '$2'...",
+                description: "String parameters (quoted in the template)",
+            },
+            HelpOption {
+                name: "$1, $2...",
+                description: "Numeric or raw parameters (unquoted in the template)",
+            },
+            HelpOption {
+                name: "$*",
+                description: "All arguments, raw: 18, 25, 30",
+            },
+            HelpOption {
+                name: "$@",
+                description: "All arguments, quoted: 'admin', 'user'",
+            },
+        ],
+        examples: &["query save user \"db.users.find({name: '$1', age: $2})\""],
+    },
+    CommandHelp {
+        name: "query delete",
+        category: "Named Queries",
+        syntax: "query delete <name>",
+        description: "Delete a named query",
+        options: &[],
+        examples: &["query delete user"],
+    },
+    CommandHelp {
+        name: "bookmark save",
+        category: "Named Queries",
+        syntax: "bookmark save <name> <query>",
+        description: "Bookmark a query in ~/.mongosh/bookmarks.toml; {{name}} placeholders are asked for when it runs",
+        options: &[],
+        examples: &[
+            "bookmark save slow-orders db.orders.find({status:'pending'}).sort({ts:-1})",
+            "bookmark save by-status db.orders.find({status:'{{status}}'}).limit({{n}})",
+        ],
+    },
+    CommandHelp {
+        name: "bookmark run",
+        category: "Named Queries",
+        syntax: "bookmark run <name>",
+        description: "Run a bookmarked query, prompting for each placeholder value",
+        options: &[],
+        examples: &["bookmark run slow-orders"],
+    },
+    CommandHelp {
+        name: "bookmark list",
+        category: "Named Queries",
+        syntax: "bookmark [list]",
+        description: "List bookmarked queries",
+        options: &[],
+        examples: &["bookmark list"],
+    },
+    CommandHelp {
+        name: "bookmark delete",
+        category: "Named Queries",
+        syntax: "bookmark delete <name>",
+        description: "Delete a bookmarked query",
+        options: &[],
+        examples: &["bookmark delete slow-orders"],
+    },
+    CommandHelp {
+        name: "plugin list",
+        category: "Configuration",
+        syntax: "plugin [list]",
+        description: "List loaded plugins with their formats, commands and status",
+        options: &[],
+        examples: &["plugin list"],
+    },
+    CommandHelp {
+        name: "plugin info",
+        category: "Configuration",
+        syntax: "plugin info <name>",
+        description: "Show a plugin's formats, commands and [plugins.config.<name>] settings",
+        options: &[],
+        examples: &["plugin info graph"],
+    },
+    CommandHelp {
+        name: "plugin enable",
+        category: "Configuration",
+        syntax: "plugin enable <name> | plugin disable <name>",
+        description: "Turn a plugin's formats and commands on or off for this session; plugins.disabled sets the startup state",
+        options: &[],
+        examples: &["plugin disable graph", "plugin enable graph"],
+    },
+    CommandHelp {
+        name: "cache",
+        category: "Configuration",
+        syntax: "cache on | off | clear | stats",
+        description: "Answer repeated identical reads from an in-memory cache (query.result_cache, with TTL and LRU size limit); writes clear it",
+        options: &[],
+        examples: &["cache on", "cache stats", "cache clear"],
+    },
+    CommandHelp {
+        name: "ai",
+        category: "AI",
+        syntax: "ai <description>",
+        description: "Generate a query from a natural language description",
+        options: &[],
+        examples: &["ai users older than 30 sorted by name"],
+    },
+    CommandHelp {
+        name: ":ai-gen",
+        category: "AI",
+        syntax: ":ai-gen [collection] [--force]",
+        description: "Generate AI context for collections",
+        options: &[HelpOption {
+            name: "--force",
+            description: "Regenerate context that already exists",
+        }],
+        examples: &[":ai-gen", ":ai-gen users --force"],
+    },
+    CommandHelp {
+        name: ":ai-status",
+        category: "AI",
+        syntax: ":ai-status",
+        description: "Show the status of generated AI context",
+        options: &[],
+        examples: &[":ai-status"],
+    },
+    CommandHelp {
+        name: "print",
+        category: "Globals",
+        syntax: "print(value, ...)",
+        description: "Print values separated by spaces",
+        options: &[],
+        examples: &["print('count:', 42)"],
+    },
+    CommandHelp {
+        name: "printjson",
+        category: "Globals",
+        syntax: "printjson(value)",
+        description: "Print a value or a query result as indented extended JSON",
+        options: &[],
+        examples: &["printjson({a: 1})", "printjson(db.users.findOne())"],
+    },
+    CommandHelp {
+        name: "tojson",
+        category: "Globals",
+        syntax: "tojson(value)",
+        description: "Convert a value to an extended JSON string",
+        options: &[],
+        examples: &["tojson({a: 1})"],
+    },
+    CommandHelp {
+        name: "sleep",
+        category: "Globals",
+        syntax: "sleep(ms)",
+        description: "Pause for the given number of milliseconds",
+        options: &[],
+        examples: &["sleep(500)"],
+    },
+    CommandHelp {
+        name: "JSON.stringify",
+        category: "Globals",
+        syntax: "JSON.stringify(value)",
+        description: "Convert a value to a JSON string",
+        options: &[],
+        examples: &["JSON.stringify({a: 1})"],
+    },
+    CommandHelp {
+        name: "JSON.parse",
+        category: "Globals",
+        syntax: "JSON.parse(text)",
+        description: "Parse a JSON string into a value",
+        options: &[],
+        examples: &["JSON.parse('{\"a\": 1}')"],
+    },
+    CommandHelp {
+        name: "Object.keys",
+        category: "Globals",
+        syntax: "Object.keys(document)",
+        description: "List the field names of a document or a findOne() result",
+        options: &[],
+        examples: &["Object.keys({a: 1, b: 2})", "Object.keys(db.users.findOne())"],
+    },
+    CommandHelp {
+        name: "load",
+        category: "Globals",
+        syntax: "load(path)",
+        description: "Run a file of shell statements, or a Lua script (.lua, `lua` feature); nested loads resolve relative to the file",
+        options: &[],
+        examples: &["load('scripts/setup.js')"],
+    },
+    CommandHelp {
+        name: "parallel",
+        category: "Globals",
+        syntax: "parallel([() => command, ...], { concurrency: n })",
+        description: "Run commands concurrently and print one summary per command",
+        options: &[HelpOption {
+            name: "concurrency",
+            description: "Maximum number of commands in flight (default 4)",
+        }],
+        examples: &[
+            "parallel([() => db.a.updateMany({}, { $set: { v: 2 } }), () => db.b.updateMany({}, { $set: { v: 2 } })], { concurrency: 2 })",
+        ],
+    },
+    CommandHelp {
+        name: "bulkUpdateWithRateLimit",
+        category: "Globals",
+        syntax: "bulkUpdateWithRateLimit(collection, filter, update, { batchSize, opsPerSecond })",
+        description: "Update matching documents in _id batches, throttled to a write rate",
+        options: &[
+            HelpOption {
+                name: "batchSize",
+                description: "Documents updated per batch (default 1000)",
+            },
+            HelpOption {
+                name: "opsPerSecond",
+                description: "Maximum documents updated per second (default 1000)",
+            },
+        ],
+        examples: &[
+            "bulkUpdateWithRateLimit('users', { plan: 'legacy' }, { $set: { plan: 'basic' } }, { batchSize: 500, opsPerSecond: 200 })",
+        ],
+    },
+    CommandHelp {
+        name: "storageReport",
+        category: "Globals",
+        syntax: "storageReport()",
+        description: "Show data, index and storage sizes of every database and collection, largest first",
+        options: &[],
+        examples: &["storageReport()"],
+    },
+    CommandHelp {
+        name: "clusterReport",
+        category: "Globals",
+        syntax: "clusterReport()",
+        description: "One-page health report for on-call use: WiredTiger cache fill and dirty share, replica set member states and lag, connection usage and pool, collection scans since startup and disk usage, each rated OK, WARN or CRIT. Built from serverStatus, replSetGetStatus, dbStats and connPoolStats; checks whose command fails show the error",
+        options: &[],
+        examples: &["clusterReport()"],
+    },
+    CommandHelp {
+        name: "rs.printOplogInfo",
+        category: "Globals",
+        syntax: "rs.printOplogInfo()",
+        description: "Show the configured and used oplog size, the time between the first and last entry (the replication window) and their times",
+        options: &[],
+        examples: &["rs.printOplogInfo()"],
+    },
+    CommandHelp {
+        name: "env",
+        category: "Globals",
+        syntax: "env(name, [default])",
+        description: "Read an environment variable; process.env.NAME also works. Only in the shell and scripts: serve, --rpc and embedded sessions reject it",
+        options: &[],
+        examples: &["db.jobs.find({ region: env('REGION', 'eu') })"],
+    },
+    CommandHelp {
+        name: "args",
+        category: "Globals",
+        syntax: "args | args[i] | process.argv",
+        description: "Arguments given after -- to a script run with --file",
+        options: &[],
+        examples: &[
+            "mongosh -f purge.js -- 2024-01-01",
+            "db.sessions.deleteMany({ day: { $lt: ISODate(args[0]) } })",
+        ],
+    },
+    CommandHelp {
+        name: "_",
+        category: "Globals",
+        syntax: "_ | _[i] | _[i].field | _.length | _.map(d => d.field)",
+        description: "The previous result: an array of documents, a document or a count",
+        options: &[],
+        examples: &[
+            "db.users.find({ plan: 'pro' })",
+            "db.orders.find({ userId: { $in: _.map(d => d._id) } })",
+        ],
+    },
+    CommandHelp {
+        name: "find",
+        category: "Collection",
+        syntax: "db.<collection>.find([filter], [projection])",
+        description: "Query documents; chain .sort(), .skip(), .limit() or .noLimit()",
+        options: &[],
+        examples: &["db.users.find({age: {$gt: 30}}).sort({name: 1}).limit(10)"],
+    },
+    CommandHelp {
+        name: "export",
+        category: "Collection",
+        syntax: "db.<collection>.find(...).export(<target>, [options])",
+        description: "Stream query results to a file, stdout (-) or a shell command (!cmd)",
+        options: &[
+            HelpOption {
+                name: "format",
+                description: "jsonl, csv, xlsx or html (default: from the file extension, else jsonl)",
+            },
+            HelpOption {
+                name: "compression",
+                description: "gzip or zstd (default: from a .gz or .zst extension)",
+            },
+        ],
+        examples: &[
+            "db.users.find().export('-', { format: 'jsonl' })",
+            "db.users.find().export('!jq -c .email')",
+            "db.users.find() |> export csv -",
+            "db.orders.find({status: 'late'}).export('report.html', { format: 'html' })",
+        ],
+    },
+    CommandHelp {
+        name: "pluck",
+        category: "Collection",
+        syntax: "db.<collection>.find(...).pluck(<field>, ...) | <command> :: .<field>, ...",
+        description: "Show only the listed fields of each result; the query sent to the server is unchanged",
+        options: &[],
+        examples: &[
+            "db.users.find().pluck('name', 'age')",
+            "db.users.find({active: true}) :: .name, .profile.city",
+            "db.users.find() |> pluck name, email",
+        ],
+    },
+    CommandHelp {
+        name: "chart",
+        category: "Collection",
+        syntax: "db.<collection>.aggregate(...).chart() | <command> --format chart",
+        description: "Draw {_id, count} style results, such as the output of a $group stage, as an ASCII bar chart with each value and its share of the total",
+        options: &[],
+        examples: &[
+            "db.orders.aggregate([{$group: {_id: '$status', count: {$sum: 1}}}]).chart()",
+            "db.orders.aggregate([{$sortByCount: '$country'}]) --format chart",
+        ],
+    },
+    CommandHelp {
+        name: "geo",
+        category: "Collection",
+        syntax: "db.<collection>.find(...).geo() | <command> --format map",
+        description: "Check the GeoJSON given to $near, $nearSphere, $geoWithin, $geoIntersects and $geoNear before sending the query, then plot the positions in the results as an ASCII map followed by an OpenStreetMap link to their bounding box",
+        options: &[],
+        examples: &[
+            "db.places.find({location: {$near: {$geometry: {type: 'Point', coordinates: [13.4, 52.5]}, $maxDistance: 500}}}).geo()",
+            "db.places.find({area: {$geoWithin: {$geometry: {type: 'Polygon', coordinates: [[[0, 0], [3, 6], [6, 1], [0, 0]]]}}}}).geo()",
+            "db.places.find().limit(50) --format map",
+        ],
+    },
+    CommandHelp {
+        name: "table",
+        category: "Collection",
+        syntax: "db.<collection>.find(...).table([<field>, ...]) | <command> --columns <field>,...",
+        description: "Show the results as a table with these columns in this order instead of every field; nested paths such as address.city become columns of their own",
+        options: &[],
+        examples: &[
+            "db.users.find().table(['name', 'age', 'email'])",
+            "db.users.find() --columns name,address.city --format table",
+        ],
+    },
+    CommandHelp {
+        name: "full",
+        category: "Collection",
+        syntax: "db.<collection>.find(...).full() | <command> |> full | show full",
+        description: "Show whole values: long strings are no longer cut off at display.max_string_length characters, BinData larger than display.max_binary_length bytes shows its content instead of its size, and nested values are not collapsed. .full() applies to one query; show full switches it on or off for the session",
+        options: &[],
+        examples: &[
+            "db.files.findOne({name: 'logo.png'}).full()",
+            "db.logs.find() |> full",
+            "show full",
+        ],
+    },
+    CommandHelp {
+        name: "save",
+        category: "Collection",
+        syntax: "db.<collection>.find(...).save(<file>)",
+        description: "Write the shown results to a file in the current output format instead of displaying them; use export for whole result sets",
+        options: &[],
+        examples: &[
+            "db.users.find({active: true}).save('active.json')",
+            "db.users.find().pluck('name').format('table').save('names.txt')",
+        ],
+    },
+    CommandHelp {
+        name: "asOf",
+        category: "Collection",
+        syntax: "db.<collection>.asOf(timestamp, [filter], [projection]) | db.<collection>.find(...).asOf(timestamp)",
+        description: "Read documents as they were at a past cluster time; falls back to current documents reconciled with the oplog when the snapshot has expired",
+        options: &[],
+        examples: &[
+            "db.orders.asOf(Timestamp(1700000000, 1), {status: 'paid'})",
+            "db.orders.find({_id: 42}).asOf(ISODate('2024-05-01T10:00:00Z'))",
+        ],
+    },
+    CommandHelp {
+        name: "parallel",
+        category: "Collection",
+        syntax: "db.<collection>.find(...).parallel(n) | db.<collection>.aggregate([...]).parallel(n)",
+        description: "Read n _id ranges of the collection concurrently and merge the results (unordered); for finds without sort/skip and pipelines of per-document stages, optionally ending in $count",
+        options: &[],
+        examples: &[
+            "db.events.find({type: 'click'}).parallel(8).export('clicks.jsonl')",
+            "db.events.aggregate([{$match: {type: 'click'}}, {$count: 'clicks'}]).parallel(8)",
+        ],
+    },
+    CommandHelp {
+        name: "diff",
+        category: "Collection",
+        syntax: "db.<collection>.diff(other, [options])",
+        description: "Compare two collections matched on _id: documents only in either one and documents with differing fields",
+        options: &[
+            HelpOption {
+                name: "filter",
+                description: "Only compare documents matching this filter",
+            },
+            HelpOption {
+                name: "db",
+                description: "Database of the other collection",
+            },
+            HelpOption {
+                name: "datasource",
+                description: "Configured datasource holding the other collection",
+            },
+            HelpOption {
+                name: "out",
+                description: "Write every difference to this JSON Lines file",
+            },
+        ],
+        examples: &[
+            "db.orders.diff('orders_backup')",
+            "db.orders.diff('orders', {datasource: 'staging', out: 'orders-diff.jsonl'})",
+        ],
+    },
+    CommandHelp {
+        name: "findOne",
+        category: "Collection",
+        syntax: "db.<collection>.findOne([filter], [projection])",
+        description: "Return the first matching document",
+        options: &[],
+        examples: &["db.users.findOne({name: 'John'})"],
+    },
+    CommandHelp {
+        name: "countDocuments",
+        category: "Collection",
+        syntax: "db.<collection>.countDocuments([filter])",
+        description: "Count matching documents",
+        options: &[],
+        examples: &["db.users.countDocuments({active: true})"],
+    },
+    CommandHelp {
+        name: "distinct",
+        category: "Collection",
+        syntax: "db.<collection>.distinct(field, [filter])",
+        description: "Return the distinct values of a field",
+        options: &[],
+        examples: &["db.users.distinct('city')"],
+    },
+    CommandHelp {
+        name: "estimatedDocumentCount",
+        category: "Collection",
+        syntax: "db.<collection>.estimatedDocumentCount()",
+        description: "Count all documents quickly from collection metadata",
+        options: &[],
+        examples: &["db.users.estimatedDocumentCount()"],
+    },
+    CommandHelp {
+        name: "explain",
+        category: "Collection",
+        syntax: "db.<collection>.explain([verbosity]).<query> | db.<collection>.<query>.explain([verbosity])",
+        description: "Show the query plan of find, findOne, aggregate, count or distinct",
+        options: &[
+            HelpOption {
+                name: "queryPlanner",
+                description: "Chosen plan only (default)",
+            },
+            HelpOption {
+                name: "executionStats",
+                description: "Plan with documents and keys examined and time taken",
+            },
+            HelpOption {
+                name: "allPlansExecution",
+                description: "Statistics for every candidate plan",
+            },
+        ],
+        examples: &[
+            "db.users.explain('executionStats').find({age: {$gt: 30}})",
+            "db.orders.find({status: 'open'}).explain()",
+        ],
+    },
+    CommandHelp {
+        name: "docSize",
+        category: "Collection",
+        syntax: "db.<collection>.docSize([filter], [options])",
+        description: "BSON size of matching documents, largest first",
+        options: &[HelpOption {
+            name: "limit",
+            description: "Number of documents to show",
+        }],
+        examples: &["db.orders.docSize({status: 'open'}, {limit: 5})"],
+    },
+    CommandHelp {
+        name: "largestDocuments",
+        category: "Collection",
+        syntax: "db.<collection>.largestDocuments([n])",
+        description: "The n largest documents in the collection",
+        options: &[],
+        examples: &["db.orders.largestDocuments(10)"],
+    },
+    CommandHelp {
+        name: "search",
+        category: "Collection",
+        syntax: "db.<collection>.search(spec, [options])",
+        description: "Atlas Search query with score and highlights",
+        options: &[HelpOption {
+            name: "limit",
+            description: "Maximum number of results",
+        }],
+        examples: &["db.movies.search({ text: { query: 'coffee', path: 'title' } }, { limit: 5 })"],
+    },
+    CommandHelp {
+        name: "vectorSearch",
+        category: "Collection",
+        syntax: "db.<collection>.vectorSearch({ queryVector, path, [limit], [numCandidates], [index], [filter] })",
+        description: "Atlas Vector Search with similarity scores",
+        options: &[],
+        examples: &[
+            "db.movies.vectorSearch({ queryVector: [0.1, 0.2], path: 'embedding', limit: 5 })",
+        ],
+    },
+    CommandHelp {
+        name: "aggregate",
+        category: "Collection",
+        syntax: "db.<collection>.aggregate(pipeline)",
+        description: "Run an aggregation pipeline",
+        options: &[],
+        examples: &["db.orders.aggregate([{$group: {_id: '$status', n: {$sum: 1}}}])"],
+    },
+    CommandHelp {
+        name: "insertOne",
+        category: "Collection",
+        syntax: "db.<collection>.insertOne(document)",
+        description: "Insert a document",
+        options: &[],
+        examples: &["db.users.insertOne({name: 'John'})"],
+    },
+    CommandHelp {
+        name: "insertMany",
+        category: "Collection",
+        syntax: "db.<collection>.insertMany(documents)",
+        description: "Insert several documents",
+        options: &[],
+        examples: &["db.users.insertMany([{name: 'A'}, {name: 'B'}])"],
+    },
+    CommandHelp {
+        name: "updateOne",
+        category: "Collection",
+        syntax: "db.<collection>.updateOne(filter, update, [options])",
+        description: "Update the first matching document",
+        options: &[],
+        examples: &["db.users.updateOne({name: 'John'}, {$set: {age: 31}})"],
+    },
+    CommandHelp {
+        name: "updateMany",
+        category: "Collection",
+        syntax: "db.<collection>.updateMany(filter, update, [options])",
+        description: "Update all matching documents",
+        options: &[],
+        examples: &["db.users.updateMany({}, {$set: {active: true}})"],
+    },
+    CommandHelp {
+        name: "replaceOne",
+        category: "Collection",
+        syntax: "db.<collection>.replaceOne(filter, replacement, [options])",
+        description: "Replace the first matching document",
+        options: &[],
+        examples: &["db.users.replaceOne({name: 'John'}, {name: 'John', age: 31})"],
+    },
+    CommandHelp {
+        name: "deleteOne",
+        category: "Collection",
+        syntax: "db.<collection>.deleteOne(filter)",
+        description: "Delete the first matching document",
+        options: &[],
+        examples: &["db.users.deleteOne({name: 'John'})"],
+    },
+    CommandHelp {
+        name: "deleteMany",
+        category: "Collection",
+        syntax: "db.<collection>.deleteMany(filter)",
+        description: "Delete all matching documents",
+        options: &[],
+        examples: &["db.users.deleteMany({active: false})"],
+    },
+    CommandHelp {
+        name: "findOneAndUpdate",
+        category: "Collection",
+        syntax: "db.<collection>.findOneAndUpdate(filter, update, [options])",
+        description: "Update the first matching document and return it",
+        options: &[],
+        examples: &[
+            "db.counters.findOneAndUpdate({_id: 'orders'}, {$inc: {seq: 1}}, {returnDocument: 'after'})",
+        ],
+    },
+    CommandHelp {
+        name: "findOneAndReplace",
+        category: "Collection",
+        syntax: "db.<collection>.findOneAndReplace(filter, replacement, [options])",
+        description: "Replace the first matching document and return it",
+        options: &[],
+        examples: &["db.users.findOneAndReplace({name: 'John'}, {name: 'John', age: 31})"],
+    },
+    CommandHelp {
+        name: "findOneAndDelete",
+        category: "Collection",
+        syntax: "db.<collection>.findOneAndDelete(filter, [options])",
+        description: "Delete the first matching document and return it",
+        options: &[],
+        examples: &["db.jobs.findOneAndDelete({status: 'queued'}, {sort: {createdAt: 1}})"],
+    },
+    CommandHelp {
+        name: "findAndModify",
+        category: "Collection",
+        syntax: "db.<collection>.findAndModify({ query, [sort], [update], [remove], [new], [upsert] })",
+        description: "Update or remove a single document and return it",
+        options: &[],
+        examples: &[
+            "db.jobs.findAndModify({query: {status: 'queued'}, update: {$set: {status: 'running'}}, new: true})",
+        ],
+    },
+    CommandHelp {
+        name: "bulkWrite",
+        category: "Collection",
+        syntax: "db.<collection>.bulkWrite(operations)",
+        description: "Run several inserts, updates and deletes in one ordered batch",
+        options: &[],
+        examples: &[
+            "db.users.bulkWrite([{insertOne: {document: {name: 'A'}}}, {deleteOne: {filter: {name: 'B'}}}])",
+        ],
+    },
+    CommandHelp {
+        name: "createIndex",
+        category: "Collection",
+        syntax: "db.<collection>.createIndex(keys, [options])",
+        description: "Build an index",
+        options: &[],
+        examples: &["db.users.createIndex({email: 1}, {unique: true})"],
+    },
+    CommandHelp {
+        name: "createIndexes",
+        category: "Collection",
+        syntax: "db.<collection>.createIndexes(specs)",
+        description: "Build several indexes; each spec has a key and optional name and options",
+        options: &[],
+        examples: &["db.users.createIndexes([{key: {email: 1}, unique: true}, {key: {age: 1}}])"],
+    },
+    CommandHelp {
+        name: "getIndexes",
+        category: "Collection",
+        syntax: "db.<collection>.getIndexes()",
+        description: "List the indexes of a collection",
+        options: &[],
+        examples: &["db.users.getIndexes()"],
+    },
+    CommandHelp {
+        name: "dropIndex",
+        category: "Collection",
+        syntax: "db.<collection>.dropIndex(name)",
+        description: "Drop an index",
+        options: &[],
+        examples: &["db.users.dropIndex('email_1')"],
+    },
+    CommandHelp {
+        name: "dropIndexes",
+        category: "Collection",
+        syntax: "db.<collection>.dropIndexes([names])",
+        description: "Drop several indexes, picking which from a checkbox list",
+        options: &[],
+        examples: &[
+            "db.users.dropIndexes()",
+            "db.users.dropIndexes(['email_1', 'age_1'])",
+        ],
+    },
+    CommandHelp {
+        name: "dropCollections",
+        category: "Collection",
+        syntax: "db.dropCollections(/pattern/ | [names])",
+        description: "Drop matching collections, picking which from a checkbox list",
+        options: &[],
+        examples: &["db.dropCollections(/^tmp_/)"],
+    },
+    CommandHelp {
+        name: "indexStats",
+        category: "Collection",
+        syntax: "db.<collection>.indexStats()",
+        description: "Show how many operations used each index, from $indexStats",
+        options: &[],
+        examples: &["db.orders.indexStats()"],
+    },
+    CommandHelp {
+        name: "indexReport",
+        category: "Collection",
+        syntax: "db.indexReport([{ since: <date> }])",
+        description: "Table of index usage across all collections, unused indexes first",
+        options: &[HelpOption {
+            name: "since",
+            description: "Flag indexes with no operations since this date (default: since counting started)",
+        }],
+        examples: &[
+            "db.indexReport()",
+            "db.indexReport({ since: ISODate(\"2026-01-01\") })",
+        ],
+    },
+    CommandHelp {
+        name: "stats",
+        category: "Collection",
+        syntax: "db.<collection>.stats([scale | options])",
+        description: "Show document count, sizes and index sizes of a collection",
+        options: &[],
+        examples: &["db.orders.stats()", "db.orders.stats({scale: 1024 * 1024})"],
+    },
+    CommandHelp {
+        name: "validate",
+        category: "Collection",
+        syntax: "db.<collection>.validate([{ full: true }])",
+        description: "Check a collection and its indexes for corruption, with a readable report",
+        options: &[],
+        examples: &["db.orders.validate({full: true})"],
+    },
+    CommandHelp {
+        name: "getValidator",
+        category: "Collection",
+        syntax: "db.<collection>.getValidator()",
+        description: "Show the validator, validation level and action",
+        options: &[],
+        examples: &["db.users.getValidator()"],
+    },
+    CommandHelp {
+        name: "setValidator",
+        category: "Collection",
+        syntax: "db.<collection>.setValidator(schema, [options])",
+        description: "Set the validator, validation level and action",
+        options: &[
+            HelpOption {
+                name: "level",
+                description: "off, strict or moderate",
+            },
+            HelpOption {
+                name: "action",
+                description: "error or warn",
+            },
+        ],
+        examples: &[
+            "db.users.setValidator({$jsonSchema: {required: ['email']}}, {level: 'moderate', action: 'warn'})",
+        ],
+    },
+    CommandHelp {
+        name: "renameCollection",
+        category: "Collection",
+        syntax: "db.<collection>.renameCollection(target, [dropTarget], [{ dropTarget, backup }])",
+        description: "Rename a collection, optionally replacing an existing target. With backup (default safety.backup_before_drop) the source and a replaced target are copied to dated backups first",
+        options: &[],
+        examples: &[
+            "db.orders_tmp.renameCollection('orders', true)",
+            "db.orders_tmp.renameCollection('orders', { dropTarget: true, backup: true })",
+        ],
+    },
+    CommandHelp {
+        name: "convertToCapped",
+        category: "Collection",
+        syntax: "db.<collection>.convertToCapped(sizeInBytes)",
+        description: "Convert a collection to a capped collection",
+        options: &[],
+        examples: &["db.logs.convertToCapped(100 * 1024 * 1024)"],
+    },
+    CommandHelp {
+        name: "drop",
+        category: "Collection",
+        syntax: "db.<collection>.drop([{ backup }])",
+        description: "Drop a collection. With backup (default safety.backup_before_drop) it is first copied to <collection>.__backup_YYYYMMDD",
+        options: &[],
+        examples: &["db.tmp_import.drop()", "db.orders.drop({ backup: true })"],
+    },
+    CommandHelp {
+        name: "createCollection",
+        category: "Database",
+        syntax: "db.createCollection(name, [options])",
+        description: "Create a collection or a view",
+        options: &[
+            HelpOption {
+                name: "capped",
+                description: "Create a capped collection; requires size",
+            },
+            HelpOption {
+                name: "size",
+                description: "Maximum size of a capped collection in bytes",
+            },
+            HelpOption {
+                name: "max",
+                description: "Maximum number of documents in a capped collection",
+            },
+        ],
+        examples: &["db.createCollection('logs', {capped: true, size: 1048576})"],
+    },
+    CommandHelp {
+        name: "runCommand",
+        category: "Database",
+        syntax: "db.runCommand(command)",
+        description: "Run a database command against the current database",
+        options: &[],
+        examples: &["db.runCommand({ connectionStatus: 1 })"],
+    },
+    CommandHelp {
+        name: "adminCommand",
+        category: "Database",
+        syntax: "db.adminCommand(command)",
+        description: "Run a database command against the admin database",
+        options: &[],
+        examples: &["db.adminCommand({ replSetGetStatus: 1 })"],
+    },
+    CommandHelp {
+        name: "getSiblingDB",
+        category: "Database",
+        syntax: "db.getSiblingDB(name).<collection>.<method>(...)",
+        description: "Run a command against another database without switching to it",
+        options: &[],
+        examples: &["db.getSiblingDB('reporting').daily.find()"],
+    },
+    CommandHelp {
+        name: "SELECT",
+        category: "SQL",
+        syntax: "SELECT <columns> FROM <collection> [WHERE ...] [GROUP BY ...] [ORDER BY ...] [LIMIT n] [OFFSET n]",
+        description: "Query a collection with SQL, translated to find or aggregate",
+        options: &[
+            HelpOption {
+                name: "DISTINCT",
+                description: "Return each combination of the selected columns once",
+            },
+            HelpOption {
+                name: "COUNT, SUM, AVG, MIN, MAX",
+                description: "Aggregate functions, per group with GROUP BY",
+            },
+            HelpOption {
+                name: "=, !=, <, <=, >, >=",
+                description: "Comparisons, combined with AND, OR and NOT",
+            },
+            HelpOption {
+                name: "IS [NOT] NULL, IS [NOT] MISSING",
+                description: "Test for null values and for fields that are absent",
+            },
+            HelpOption {
+                name: "tags[0], tags[0:5]",
+                description: "Array element and slice access",
+            },
+            HelpOption {
+                name: "+, -, *, /, %",
+                description: "Arithmetic on fields and numbers",
+            },
+            HelpOption {
+                name: "CURRENT_DATE, NOW()",
+                description: "Current date and time; DATE '2024-01-01' for date literals",
+            },
+        ],
+        examples: &[
+            "SELECT name, age FROM users WHERE age > 30 ORDER BY name LIMIT 10",
+            "SELECT status, COUNT(*) AS n FROM orders GROUP BY status",
+            "SELECT name, tags[0:3] FROM posts WHERE deleted_at IS MISSING",
+        ],
+    },
+    CommandHelp {
+        name: "EXPLAIN",
+        category: "SQL",
+        syntax: "EXPLAIN [queryPlanner | executionStats | allPlansExecution] SELECT ...",
+        description: "Show the query plan of the find or aggregate a SELECT translates to",
+        options: &[],
+        examples: &["EXPLAIN executionStats SELECT * FROM orders WHERE status = 'open'"],
+    },
+    CommandHelp {
+        name: "sql explain",
+        category: "SQL",
+        syntax: "sql explain SELECT ...",
+        description: "Show the find or aggregate a SELECT translates to, as a shell query, without running it",
+        options: &[],
+        examples: &["sql explain SELECT status, COUNT(*) AS n FROM orders GROUP BY status"],
+    },
+];
+
+/// Topics that name a group of commands rather than a single command
+const TOPIC_ALIASES: &[(&str, &str)] = &[("sql", "SELECT"), ("count", "countDocuments")];
+
+/// Longest edit distance at which an unknown topic still counts as a typo
+const MAX_TOPIC_DISTANCE: usize = 2;
+
+/// Number of suggestions offered for an unknown topic
+const MAX_SUGGESTIONS: usize = 3;
+
+/// Look up a command by name
+///
+/// Matching is case-insensitive and ignores a trailing `()`, so both
+/// `help findOne` and `help findone()` work.
+///
+/// # Arguments
+/// * `topic` - Command name
+///
+/// # Returns
+/// * `Option<&'static CommandHelp>` - The command, if documented
+pub fn find(topic: &str) -> Option<&'static CommandHelp> {
+    let topic = normalize_topic(topic);
+    let topic = TOPIC_ALIASES
+        .iter()
+        .find(|(alias, _)| alias.eq_ignore_ascii_case(topic))
+        .map_or(topic, |(_, name)| name);
+    COMMANDS
+        .iter()
+        .find(|cmd| cmd.name.eq_ignore_ascii_case(topic))
+        .or_else(|| {
+            // "quit" and "show databases" are aliases listed in the syntax
+            COMMANDS.iter().find(|cmd| {
+                cmd.syntax
+                    .split(" | ")
+                    .any(|alias| alias.eq_ignore_ascii_case(topic))
+            })
+        })
+}
+
+/// Look up a command, falling back to fuzzy matching
+///
+/// An exact name or alias wins. Otherwise commands within a small edit
+/// distance, or starting with the topic, are candidates; a single candidate
+/// is returned directly.
+///
+/// # Arguments
+/// * `topic` - Command name, possibly misspelled or abbreviated
+///
+/// # Returns
+/// * `std::result::Result<&'static CommandHelp, Vec<&'static str>>` - The
+///   command, or the names of the closest commands (possibly none)
+pub fn lookup(topic: &str) -> std::result::Result<&'static CommandHelp, Vec<&'static str>> {
+    if let Some(cmd) = find(topic) {
+        return Ok(cmd);
+    }
+
+    let suggestions = suggest(topic);
+    if let [name] = suggestions[..]
+        && let Some(cmd) = find(name)
+    {
+        return Ok(cmd);
+    }
+    Err(suggestions)
+}
+
+/// Names of the commands closest to an unknown topic, best match first
+///
+/// # Arguments
+/// * `topic` - Unknown command name
+///
+/// # Returns
+/// * `Vec<&'static str>` - Up to three command names
+pub fn suggest(topic: &str) -> Vec<&'static str> {
+    let topic = normalize_topic(topic).to_lowercase();
+    if topic.is_empty() {
+        return Vec::new();
+    }
+
+    let mut scored: Vec<(usize, &'static str)> = COMMANDS
+        .iter()
+        .filter_map(|cmd| {
+            let name = cmd.name.to_lowercase();
+            let distance = edit_distance(&topic, &name);
+            if distance <= MAX_TOPIC_DISTANCE.min(topic.chars().count() / 2) {
+                Some((distance, cmd.name))
+            } else if topic.chars().count() >= 3 && name.starts_with(&topic) {
+                // Rank prefix matches after typos, shortest first
+                Some((MAX_TOPIC_DISTANCE + name.len(), cmd.name))
+            } else {
+                None
+            }
+        })
+        .collect();
+    scored.sort();
+    scored
+        .into_iter()
+        .map(|(_, name)| name)
+        .take(MAX_SUGGESTIONS)
+        .collect()
+}
+
+/// Message for a topic with no exact match
+///
+/// # Arguments
+/// * `topic` - Unknown command name
+/// * `suggestions` - Closest command names
+///
+/// # Returns
+/// * `String` - Message naming the suggestions, if any
+pub fn unknown_topic_message(topic: &str, suggestions: &[&str]) -> String {
+    if suggestions.is_empty() {
+        format!("No help for: {}\nType 'help' to list commands", topic)
+    } else {
+        format!(
+            "No help for: {}\nDid you mean: {}?\nType 'help' to list commands",
+            topic,
+            suggestions.join(", ")
+        )
+    }
+}
+
+/// Strip whitespace and a trailing `()` from a topic
+fn normalize_topic(topic: &str) -> &str {
+    let topic = topic.trim();
+    topic.strip_suffix("()").unwrap_or(topic)
+}
+
+/// Levenshtein distance between two strings, in characters
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// Render the whole command reference
+///
+/// # Arguments
+/// * `format` - Output format
+///
+/// # Returns
+/// * `Result<String>` - Rendered reference
+pub fn render(format: HelpFormat) -> Result<String> {
+    match format {
+        HelpFormat::Text => Ok(COMMANDS
+            .iter()
+            .map(render_text)
+            .collect::<Vec<_>>()
+            .join("\n\n")),
+        HelpFormat::Markdown => Ok(render_markdown()),
+        HelpFormat::Json => serde_json::to_string_pretty(COMMANDS).map_err(|e| {
+            MongoshError::Generic(format!("Failed to serialize command reference: {}", e))
+        }),
+    }
+}
+
+/// Render a single command as plain text
+///
+/// # Arguments
+/// * `cmd` - Command to render
+///
+/// # Returns
+/// * `String` - Rendered help
+pub fn render_text(cmd: &CommandHelp) -> String {
+    render_styled(cmd, false)
+}
+
+/// Render a single command, optionally colored
+///
+/// The syntax is bold, option names yellow and examples green.
+///
+/// # Arguments
+/// * `cmd` - Command to render
+/// * `use_colors` - Add ANSI colors
+///
+/// # Returns
+/// * `String` - Rendered help
+pub fn render_styled(cmd: &CommandHelp, use_colors: bool) -> String {
+    let style = Style::new(use_colors);
+    let mut out = format!(
+        "{}\n\n  {}\n\n",
+        style.paint(AnsiColors::BOLD, cmd.syntax),
+        cmd.description
+    );
+    if !cmd.options.is_empty() {
+        out.push_str(&style.heading("Options:"));
+        for opt in cmd.options {
+            let name = format!("{:<24}", opt.name);
+            out.push_str(&format!(
+                "  {} {}\n",
+                style.paint(AnsiColors::YELLOW, &name),
+                opt.description
+            ));
+        }
+        out.push('\n');
+    }
+    if !cmd.examples.is_empty() {
+        out.push_str(&style.heading("Examples:"));
+        for example in cmd.examples {
+            out.push_str(&format!("  {}\n", style.paint(AnsiColors::GREEN, example)));
+        }
+    }
+    out.trim_end().to_string()
+}
+
+/// Render the list of all commands by category, one line each
+///
+/// # Arguments
+/// * `use_colors` - Add ANSI colors
+///
+/// # Returns
+/// * `String` - Overview shown by `help` without a topic
+pub fn render_overview(use_colors: bool) -> String {
+    let style = Style::new(use_colors);
+    let width = COMMANDS.iter().map(|cmd| cmd.name.len()).max().unwrap_or(0);
+
+    let mut out = String::from("MongoDB Shell Commands:\n");
+    let mut category = "";
+    for cmd in COMMANDS {
+        if cmd.category != category {
+            category = cmd.category;
+            out.push('\n');
+            out.push_str(&style.heading(&format!("{}:", category)));
+        }
+        let name = format!("{:<width$}", cmd.name, width = width);
+        out.push_str(&format!(
+            "  {}  {}\n",
+            style.paint(AnsiColors::CYAN, &name),
+            cmd.description
+        ));
+    }
+    out.push_str("\nType 'help <command>' for syntax, options and examples, e.g. help find, help aggregate, help sql");
+    out
+}
+
+/// ANSI styling that is a no-op when colors are off
+struct Style {
+    use_colors: bool,
+}
+
+impl Style {
+    fn new(use_colors: bool) -> Self {
+        Self { use_colors }
+    }
+
+    /// Wrap text in a color code
+    fn paint(&self, color: &str, text: &str) -> String {
+        if self.use_colors {
+            format!("{}{}{}", color, text, AnsiColors::RESET)
+        } else {
+            text.to_string()
+        }
+    }
+
+    /// Bold section heading followed by a newline
+    fn heading(&self, text: &str) -> String {
+        format!("{}\n", self.paint(AnsiColors::BOLD, text))
+    }
+}
+
+/// Render the command reference as a Markdown document
+fn render_markdown() -> String {
+    let mut out = String::from("# mongosh Command Reference\n");
+    let mut category = "";
+    for cmd in COMMANDS {
+        if cmd.category != category {
+            category = cmd.category;
+            out.push_str(&format!("\n## {}\n", category));
+        }
+
+        out.push_str(&format!("\n### `{}`\n\n", cmd.name));
+        out.push_str(&render_markdown_body(cmd));
+    }
+    out
+}
+
+/// Render a single command as Markdown, without a heading
+///
+/// Used by the reference and by editor hovers (`mongosh lsp`).
+///
+/// # Arguments
+/// * `cmd` - Command to render
+///
+/// # Returns
+/// * `String` - Description, syntax, options and examples
+pub fn render_markdown_body(cmd: &CommandHelp) -> String {
+    let mut out = format!("{}\n\n```\n{}\n```\n", cmd.description, cmd.syntax);
+    if !cmd.options.is_empty() {
+        out.push_str("\n| Option | Description |\n| ------ | ----------- |\n");
+        for opt in cmd.options {
+            out.push_str(&format!("| `{}` | {} |\n", opt.name, opt.description));
+        }
+    }
+    if !cmd.examples.is_empty() {
+        out.push_str("\nExamples:\n\n```\n");
+        for example in cmd.examples {
+            out.push_str(example);
+            out.push('\n');
+        }
+        out.push_str("```\n");
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find() {
+        assert_eq!(find("top").unwrap().name, "top");
+        assert_eq!(find("FINDONE()").unwrap().name, "findOne");
+        assert_eq!(find("quit").unwrap().name, "exit");
+        assert_eq!(find("show databases").unwrap().name, "show dbs");
+        assert!(find("nope").is_none());
+        assert_eq!(find("SQL").unwrap().name, "SELECT");
+        assert_eq!(find("count").unwrap().name, "countDocuments");
+    }
+
+    #[test]
+    fn test_lookup_fuzzy() {
+        assert_eq!(lookup("agregate").unwrap().name, "aggregate");
+        assert_eq!(lookup("estimated").unwrap().name, "estimatedDocumentCount");
+        assert_eq!(lookup("findone").unwrap().name, "findOne");
+
+        let suggestions = lookup("findOneAnd").unwrap_err();
+        assert_eq!(suggestions.len(), 3);
+        assert!(
+            suggestions
+                .iter()
+                .all(|name| name.starts_with("findOneAnd"))
+        );
+        assert!(lookup("zzzzzz").unwrap_err().is_empty());
+
+        assert_eq!(
+            unknown_topic_message("findOneAnd", &suggestions[..1]),
+            format!(
+                "No help for: findOneAnd\nDid you mean: {}?\nType 'help' to list commands",
+                suggestions[0]
+            )
+        );
+    }
+
+    #[test]
+    fn test_render_styled() {
+        let plain = render_text(find("show log").unwrap());
+        assert!(plain.starts_with("show log <name>"));
+        assert!(!plain.contains('\x1b'));
+
+        let colored = render_styled(find("show log").unwrap(), true);
+        assert!(colored.contains(&format!(
+            "{}Examples:{}",
+            AnsiColors::BOLD,
+            AnsiColors::RESET
+        )));
+        assert!(colored.contains(AnsiColors::GREEN));
+    }
+
+    #[test]
+    fn test_render_overview_lists_every_command() {
+        let overview = render_overview(false);
+        for cmd in COMMANDS {
+            assert!(overview.contains(cmd.name), "missing {}", cmd.name);
+        }
+        assert!(overview.contains("\nSQL:\n"));
+    }
+
+    #[test]
+    fn test_names_are_unique() {
+        for (i, cmd) in COMMANDS.iter().enumerate() {
+            assert!(
+                COMMANDS[i + 1..].iter().all(|other| other.name != cmd.name),
+                "duplicate command {}",
+                cmd.name
+            );
+        }
+    }
+
+    #[test]
+    fn test_render_markdown() {
+        let markdown = render(HelpFormat::Markdown).unwrap();
+        assert!(markdown.starts_with("# mongosh Command Reference\n"));
+        assert_eq!(markdown.matches("\n## Shell\n").count(), 1);
+        assert!(markdown.contains("### `show log`"));
+        assert!(markdown.contains("| `--grep <text>` | Only show lines containing the text |"));
+    }
+
+    #[test]
+    fn test_render_json() {
+        let json = render(HelpFormat::Json).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let commands = value.as_array().unwrap();
+        assert_eq!(commands.len(), COMMANDS.len());
+        assert_eq!(commands[0]["name"], "show dbs");
+        assert!(commands[0]["examples"].is_array());
+    }
+
+    #[test]
+    fn test_help_format_parse() {
+        assert_eq!(HelpFormat::parse("md").unwrap(), HelpFormat::Markdown);
+        assert_eq!(HelpFormat::parse("JSON").unwrap(), HelpFormat::Json);
+        assert!(HelpFormat::parse("html").is_err());
+    }
+}
@@ -0,0 +1,338 @@
+//! First-run setup wizard for `mongosh init`
+//!
+//! Asks for the default connection URI, output format, history file and
+//! color preference, writes the answers to the configuration file and then
+//! checks that the server is reachable with the new URI.
+
+use std::path::{Path, PathBuf};
+
+use crate::cli::prompt;
+use crate::config::{Config, OutputFormat};
+use crate::connection::ConnectionManager;
+use crate::error::{ConfigError, MongoshError, Result};
+
+/// Datasource name used when the configuration does not name a default yet
+const DEFAULT_DATASOURCE_NAME: &str = "default";
+
+/// URI suggested when the configuration has no datasource
+const DEFAULT_URI: &str = "mongodb://localhost:27017";
+
+/// Output formats accepted by the format question
+const FORMAT_CHOICES: &str =
+    "shell, json, json-pretty, table, compact, extjson, extjson-canonical, chart, map";
+
+/// Answers collected by the wizard
+#[derive(Debug, Clone, PartialEq)]
+pub struct InitAnswers {
+    /// Default connection URI
+    pub uri: String,
+
+    /// Preferred output format
+    pub format: OutputFormat,
+
+    /// History file location
+    pub history_file: PathBuf,
+
+    /// Whether colored output is enabled
+    pub color_output: bool,
+}
+
+impl InitAnswers {
+    /// Answers matching the current configuration, offered as prompt defaults
+    ///
+    /// # Arguments
+    /// * `config` - Configuration loaded from the file being initialized
+    ///
+    /// # Returns
+    /// * `Self` - Default answers
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            uri: config
+                .connection
+                .get_datasource(None)
+                .unwrap_or_else(|| DEFAULT_URI.to_string()),
+            format: config.display.format,
+            history_file: config.history.file_path.clone(),
+            color_output: config.display.color_output,
+        }
+    }
+
+    /// Store the answers in a configuration
+    ///
+    /// The URI becomes the default datasource, keeping the name of the
+    /// current default when there is one.
+    ///
+    /// # Arguments
+    /// * `config` - Configuration to update
+    pub fn apply(&self, config: &mut Config) {
+        let name = config
+            .connection
+            .default_datasource
+            .clone()
+            .unwrap_or_else(|| DEFAULT_DATASOURCE_NAME.to_string());
+        config
+            .connection
+            .datasources
+            .insert(name.clone(), self.uri.clone());
+        config.connection.default_datasource = Some(name);
+        config.connection.default_uri = None;
+
+        config.display.format = self.format;
+        config.display.color_output = self.color_output;
+        config.history.file_path = self.history_file.clone();
+    }
+}
+
+/// Run the setup wizard
+///
+/// # Arguments
+/// * `config_path` - Configuration file to write (None for the default path)
+/// * `skip_check` - Do not try to connect with the new URI
+///
+/// # Returns
+/// * `Result<()>` - Success or error
+pub async fn run(config_path: Option<&Path>, skip_check: bool) -> Result<()> {
+    if !prompt::is_interactive() {
+        return Err(MongoshError::Config(ConfigError::Generic(
+            "mongosh init needs an interactive terminal".to_string(),
+        )));
+    }
+
+    let path = config_path
+        .map(Path::to_path_buf)
+        .unwrap_or_else(Config::default_config_path);
+    let mut config = if path.exists() {
+        Config::load_from_file(Some(&path))?
+    } else {
+        Config::default()
+    };
+
+    println!("Setting up {}", path.display());
+    println!("Press Enter to keep the value in brackets.\n");
+
+    let answers = ask(&InitAnswers::from_config(&config))?;
+    answers.apply(&mut config);
+    config.validate()?;
+    config.save_to_file(Some(&path))?;
+    println!("\nConfiguration written to {}", path.display());
+
+    if !skip_check {
+        check_connection(&answers.uri, &config).await;
+    }
+
+    Ok(())
+}
+
+/// Ask every question, re-asking until each answer is valid
+///
+/// # Arguments
+/// * `defaults` - Values used for empty answers
+///
+/// # Returns
+/// * `Result<InitAnswers>` - Collected answers
+fn ask(defaults: &InitAnswers) -> Result<InitAnswers> {
+    let uri = ask_until(
+        &format!("Default connection URI [{}]: ", defaults.uri),
+        |answer| parse_uri(answer, &defaults.uri),
+    )?;
+    let format = ask_until(
+        &format!(
+            "Output format ({}) [{}]: ",
+            FORMAT_CHOICES,
+            defaults.format.as_str()
+        ),
+        |answer| parse_format(answer, defaults.format),
+    )?;
+    let history_file = ask_until(
+        &format!("History file [{}]: ", defaults.history_file.display()),
+        |answer| Ok(parse_path(answer, &defaults.history_file)),
+    )?;
+    let color_output = ask_until(
+        &format!(
+            "Colored output (y/n) [{}]: ",
+            if defaults.color_output { "y" } else { "n" }
+        ),
+        |answer| parse_yes_no(answer, defaults.color_output),
+    )?;
+
+    Ok(InitAnswers {
+        uri,
+        format,
+        history_file,
+        color_output,
+    })
+}
+
+/// Prompt repeatedly until `parse` accepts the answer
+fn ask_until<T>(label: &str, parse: impl Fn(&str) -> Result<T>) -> Result<T> {
+    loop {
+        match parse(&prompt::read_line(label)?) {
+            Ok(value) => return Ok(value),
+            Err(e) => eprintln!("{}", e),
+        }
+    }
+}
+
+/// Try the new URI and report whether the server answered
+async fn check_connection(uri: &str, config: &Config) {
+    println!("Checking connection...");
+    let mut manager = ConnectionManager::new(uri.to_string(), config.connection.clone());
+    let result = match manager.connect().await {
+        Ok(()) => manager.verify_authentication().await,
+        Err(e) => Err(e),
+    };
+
+    match result {
+        Ok(()) => println!("Connected successfully."),
+        Err(e) => {
+            eprintln!("Warning: could not connect: {}", e);
+            eprintln!("The configuration was saved; run `mongosh init` again to change it.");
+        }
+    }
+}
+
+/// Parse the URI answer
+///
+/// # Arguments
+/// * `answer` - Trimmed user input
+/// * `default` - Value used for an empty answer
+///
+/// # Returns
+/// * `Result<String>` - URI or error for a missing scheme
+fn parse_uri(answer: &str, default: &str) -> Result<String> {
+    if answer.is_empty() {
+        return Ok(default.to_string());
+    }
+    if answer.starts_with("mongodb://") || answer.starts_with("mongodb+srv://") {
+        Ok(answer.to_string())
+    } else {
+        Err(MongoshError::Config(ConfigError::Generic(
+            "URI must start with mongodb:// or mongodb+srv://".to_string(),
+        )))
+    }
+}
+
+/// Parse the output format answer
+///
+/// # Arguments
+/// * `answer` - Trimmed user input
+/// * `default` - Value used for an empty answer
+///
+/// # Returns
+/// * `Result<OutputFormat>` - Format or error for an unknown name
+fn parse_format(answer: &str, default: OutputFormat) -> Result<OutputFormat> {
+    match answer.to_lowercase().as_str() {
+        "" => Ok(default),
+        "shell" => Ok(OutputFormat::Shell),
+        "json" => Ok(OutputFormat::Json),
+        "json-pretty" | "jsonpretty" => Ok(OutputFormat::JsonPretty),
+        "table" => Ok(OutputFormat::Table),
+        "compact" => Ok(OutputFormat::Compact),
+        "extjson" | "extjson-relaxed" => Ok(OutputFormat::ExtJsonRelaxed),
+        "extjson-canonical" => Ok(OutputFormat::ExtJsonCanonical),
+        "chart" => Ok(OutputFormat::Chart),
+        "map" => Ok(OutputFormat::Map),
+        _ => Err(MongoshError::Config(ConfigError::Generic(format!(
+            "Unknown format '{}', expected one of: {}",
+            answer, FORMAT_CHOICES
+        )))),
+    }
+}
+
+/// Parse the history file answer, expanding a leading `~/`
+///
+/// # Arguments
+/// * `answer` - Trimmed user input
+/// * `default` - Value used for an empty answer
+///
+/// # Returns
+/// * `PathBuf` - History file path
+fn parse_path(answer: &str, default: &Path) -> PathBuf {
+    if answer.is_empty() {
+        return default.to_path_buf();
+    }
+    match (answer.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(answer),
+    }
+}
+
+/// Parse a yes/no answer
+///
+/// # Arguments
+/// * `answer` - Trimmed user input
+/// * `default` - Value used for an empty answer
+///
+/// # Returns
+/// * `Result<bool>` - Choice or error for anything else
+fn parse_yes_no(answer: &str, default: bool) -> Result<bool> {
+    match answer.to_lowercase().as_str() {
+        "" => Ok(default),
+        "y" | "yes" => Ok(true),
+        "n" | "no" => Ok(false),
+        _ => Err(MongoshError::Config(ConfigError::Generic(
+            "Please answer y or n".to_string(),
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_answers_keep_defaults() {
+        assert_eq!(
+            parse_uri("", "mongodb://db:27017").unwrap(),
+            "mongodb://db:27017"
+        );
+        assert_eq!(
+            parse_format("", OutputFormat::Table).unwrap(),
+            OutputFormat::Table
+        );
+        assert_eq!(
+            parse_path("", Path::new("/tmp/history")),
+            PathBuf::from("/tmp/history")
+        );
+        assert!(!parse_yes_no("", false).unwrap());
+    }
+
+    #[test]
+    fn test_invalid_answers_are_rejected() {
+        assert!(parse_uri("localhost:27017", DEFAULT_URI).is_err());
+        assert!(parse_format("yaml", OutputFormat::Shell).is_err());
+        assert!(parse_yes_no("maybe", true).is_err());
+        assert_eq!(
+            parse_format("JSON-Pretty", OutputFormat::Shell).unwrap(),
+            OutputFormat::JsonPretty
+        );
+        assert!(parse_yes_no("Yes", false).unwrap());
+    }
+
+    #[test]
+    fn test_apply_sets_default_datasource() {
+        let mut config = Config::default();
+        config.connection.datasources.clear();
+        config.connection.default_datasource = None;
+
+        let answers = InitAnswers {
+            uri: "mongodb://prod:27017".to_string(),
+            format: OutputFormat::Json,
+            history_file: PathBuf::from("/tmp/history"),
+            color_output: false,
+        };
+        answers.apply(&mut config);
+
+        assert_eq!(
+            config.connection.get_datasource(None).as_deref(),
+            Some("mongodb://prod:27017")
+        );
+        assert_eq!(
+            config.connection.default_datasource.as_deref(),
+            Some(DEFAULT_DATASOURCE_NAME)
+        );
+        assert_eq!(config.display.format, OutputFormat::Json);
+        assert!(!config.display.color_output);
+        assert_eq!(InitAnswers::from_config(&config), answers);
+    }
+}
@@ -8,12 +8,18 @@
 //! - Connection string parsing
 
 mod completion;
+pub mod help;
+mod init;
+pub mod prompt;
 
 use clap::{Parser, Subcommand};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use crate::config::{Config, OutputFormat};
 use crate::error::Result;
+use crate::parser::{AdminCommand, Command};
+use crate::repl::recording::{self, EntryKind};
+use crate::serve::ServeOptions;
 
 /// Extract database name from MongoDB connection URI
 ///
@@ -22,7 +28,7 @@ use crate::error::Result;
 ///
 /// # Returns
 /// * `Option<String>` - Database name if found in URI
-fn extract_database_from_uri(uri: &str) -> Option<String> {
+pub(crate) fn extract_database_from_uri(uri: &str) -> Option<String> {
     // Parse URI to extract database name
     // Format: mongodb://[username:password@]host[:port][/database][?options]
 
@@ -47,7 +53,8 @@ fn extract_database_from_uri(uri: &str) -> Option<String> {
     version,
     about = "MongoDB Shell written in Rust",
     long_about = "A high-performance MongoDB Shell implementation in Rust with support for
-interactive REPL, script execution, and all MongoDB operations."
+interactive REPL, script execution, and all MongoDB operations.",
+    disable_help_subcommand = true
 )]
 pub struct CliArgs {
     /// MongoDB connection URI
@@ -91,7 +98,8 @@ pub struct CliArgs {
     #[arg(short = 'c', long = "config", value_name = "FILE")]
     pub config_file: Option<PathBuf>,
 
-    /// Output format (json, json-pretty, table, compact)
+    /// Output format (json, json-pretty, table, compact, extjson, extjson-canonical, chart,
+    /// map)
     #[arg(long, value_name = "FORMAT")]
     pub format: Option<String>,
 
@@ -99,6 +107,14 @@ pub struct CliArgs {
     #[arg(long = "no-color")]
     pub no_color: bool,
 
+    /// Screen reader friendly output (no tables, spinners or colors)
+    #[arg(long)]
+    pub accessible: bool,
+
+    /// Show fields masked by the [redaction] config section
+    #[arg(long)]
+    pub show_sensitive: bool,
+
     /// Quiet mode (minimal output)
     #[arg(short = 'q', long)]
     pub quiet: bool,
@@ -135,10 +151,56 @@ pub struct CliArgs {
     #[arg(long)]
     pub tls_insecure: bool,
 
+    /// Override a configuration setting, e.g. `-o display.page_size=50 -o safety.read_only=true`
+    ///
+    /// Can be repeated. Applied after the config file and other options.
+    #[arg(short = 'o', long = "set", value_name = "KEY=VALUE")]
+    pub overrides: Vec<String>,
+
+    /// Continue skip/OFFSET pages from where the previous page ended (keyset pagination)
+    #[arg(long)]
+    pub keyset: bool,
+
+    /// Run a script file and exit
+    ///
+    /// Arguments after `--` are available to the script as `args`.
+    /// Example: mongosh -f cleanup.js -- orders 30
+    #[arg(short = 'f', long, value_name = "FILE")]
+    pub file: Option<PathBuf>,
+
+    /// Run shell commands and exit
+    ///
+    /// Statements are separated by `;` or newlines, as in a script.
+    /// Example: mongosh --eval 'printjson(db.users.findOne())'
+    #[arg(long, value_name = "CODE", conflicts_with = "file")]
+    pub eval: Option<String>,
+
+    /// Arguments passed to the script
+    #[arg(last = true, value_name = "ARGS")]
+    pub script_args: Vec<String>,
+
     /// Enable MCP (Model Context Protocol) server mode
     #[arg(long)]
     pub mcp: bool,
 
+    /// Answer JSON-RPC requests on stdin for editor integrations
+    ///
+    /// Methods: complete, execute, cancel and listCollections, one message per line.
+    #[arg(long, conflicts_with = "mcp")]
+    pub rpc: bool,
+
+    /// Serve Prometheus metrics at http://<host>:<PORT>/metrics while running
+    ///
+    /// Exposes command counters, a latency histogram and connection pool gauges.
+    #[arg(long, value_name = "PORT")]
+    pub metrics_port: Option<u16>,
+
+    /// Print a crash report for sharing (the latest one if no file is given)
+    ///
+    /// Crash reports are written to ~/.mongosh/crash/ and never sent anywhere.
+    #[arg(long, value_name = "FILE", num_args = 0..=1, default_missing_value = "")]
+    pub report: Option<String>,
+
     /// Subcommands
     #[command(subcommand)]
     pub command: Option<Commands>,
@@ -157,6 +219,13 @@ pub enum Commands {
         shell: String,
     },
 
+    /// Interactively create the configuration file
+    Init {
+        /// Do not test the connection after saving
+        #[arg(long)]
+        skip_check: bool,
+    },
+
     /// Show configuration
     Config {
         /// Show effective configuration
@@ -171,6 +240,80 @@ pub enum Commands {
         #[arg(long)]
         list_datasources: bool,
     },
+
+    /// Print the command reference, e.g. `mongosh help --format markdown > COMMANDS.md`
+    Help {
+        /// Command to describe (all commands when omitted)
+        #[arg(value_name = "COMMAND")]
+        topic: Option<String>,
+
+        /// Output format (text, markdown, json)
+        #[arg(long, value_name = "FORMAT", default_value = "text")]
+        format: String,
+    },
+
+    /// Dump a database to <OUT>/<db>/ as mongodump-style BSON and metadata files
+    Dump {
+        /// Dump directory
+        #[arg(long, value_name = "DIR", default_value = "dump")]
+        out: String,
+
+        /// Only dump this collection (may be repeated)
+        #[arg(long = "collection", value_name = "NAME")]
+        collections: Vec<String>,
+    },
+
+    /// Restore a dump written by `mongosh dump` or mongodump into a database
+    Restore {
+        /// Dump root or database folder
+        #[arg(value_name = "DIR", default_value = "dump")]
+        dir: String,
+
+        /// Only restore this collection (may be repeated)
+        #[arg(long = "collection", value_name = "NAME")]
+        collections: Vec<String>,
+
+        /// Drop each collection before restoring it
+        #[arg(long)]
+        drop: bool,
+    },
+
+    /// Answer shell and SQL commands over HTTP: POST /query returns the result as JSON
+    Serve {
+        /// Port to listen on
+        #[arg(long, value_name = "PORT", default_value_t = 8080)]
+        port: u16,
+
+        /// Address to listen on; use 0.0.0.0 to accept remote clients
+        #[arg(long, value_name = "ADDR", default_value = "127.0.0.1")]
+        bind: String,
+
+        /// Run commands that modify data (read-only by default)
+        #[arg(long)]
+        allow_writes: bool,
+
+        /// Require `Authorization: Bearer <SECRET>` on POST /query; defaults to
+        /// the MONGOSH_SERVE_TOKEN environment variable
+        #[arg(long, value_name = "SECRET")]
+        token: Option<String>,
+    },
+
+    /// Run a language server on stdio for .mongodb and .js shell scripts
+    ///
+    /// Provides completion, operator and method hovers, parser diagnostics and
+    /// formatting. `mongosh --no-connect lsp` skips completing collection names.
+    Lsp,
+
+    /// Re-run the commands of a session recorded with `record start <file>`
+    Replay {
+        /// Recording file
+        #[arg(value_name = "FILE")]
+        file: PathBuf,
+
+        /// Show the recorded commands and their output without running them
+        #[arg(long)]
+        dry_run: bool,
+    },
 }
 
 /// CLI interface handler
@@ -195,11 +338,13 @@ impl CliInterface {
         let config = Self::load_config(&args)?;
         let connection_uri = Self::resolve_connection_uri(&args, &config)?;
 
-        Ok(Self {
+        let mut cli = Self {
             args,
             config,
             connection_uri,
-        })
+        };
+        cli.args.quiet |= cli.stdout_is_protocol();
+        Ok(cli)
     }
 
     /// Load configuration from file and merge with arguments
@@ -224,6 +369,13 @@ impl CliInterface {
         // Apply CLI arguments to override config values
         Self::apply_args_to_config(&mut config, args);
 
+        if !args.overrides.is_empty() {
+            for assignment in &args.overrides {
+                config.apply_override(assignment)?;
+            }
+            config.validate()?;
+        }
+
         Ok(config)
     }
 
@@ -309,7 +461,7 @@ impl CliInterface {
     ///
     /// # Returns
     /// * `String` - Sanitized URI
-    fn sanitize_uri(uri: &str) -> String {
+    pub(crate) fn sanitize_uri(uri: &str) -> String {
         // Hide everything between :// and @
         if let Some(proto_end) = uri.find("://") {
             if let Some(host_start) = uri.find('@') {
@@ -467,6 +619,10 @@ impl CliInterface {
         Self::apply_display_args(config, args);
         Self::apply_logging_args(config, args);
         Self::apply_connection_args(config, args);
+
+        if args.keyset {
+            config.query.keyset_pagination = true;
+        }
     }
 
     /// Apply display-related CLI arguments to configuration
@@ -478,6 +634,14 @@ impl CliInterface {
         if args.no_color {
             config.display.color_output = false;
         }
+
+        if args.accessible {
+            config.display.accessible = true;
+        }
+
+        if args.show_sensitive {
+            config.redaction.enabled = false;
+        }
     }
 
     /// Apply logging-related CLI arguments to configuration
@@ -510,6 +674,10 @@ impl CliInterface {
             "json-pretty" | "jsonpretty" => OutputFormat::JsonPretty,
             "table" => OutputFormat::Table,
             "compact" => OutputFormat::Compact,
+            "extjson" | "extjson-relaxed" => OutputFormat::ExtJsonRelaxed,
+            "extjson-canonical" => OutputFormat::ExtJsonCanonical,
+            "chart" => OutputFormat::Chart,
+            "map" => OutputFormat::Map,
             _ => {
                 eprintln!("Warning: Unknown format '{}', using default", format_str);
                 OutputFormat::Shell
@@ -541,7 +709,151 @@ impl CliInterface {
                 self.handle_config_command(*show, *validate, *list_datasources)?;
                 Ok(true)
             }
-            None => Ok(false),
+            Some(Commands::Help { topic, format }) => {
+                self.show_help(topic.as_deref(), format)?;
+                Ok(true)
+            }
+            Some(Commands::Init { skip_check }) => {
+                init::run(self.args.config_file.as_deref(), *skip_check).await?;
+                Ok(true)
+            }
+            Some(Commands::Replay {
+                file,
+                dry_run: true,
+            }) => {
+                Self::show_recording(file)?;
+                Ok(true)
+            }
+            // Need a connection, run from main via `backup_command`, `replay_file`,
+            // `serve_options` and `is_lsp`
+            Some(
+                Commands::Dump { .. }
+                | Commands::Restore { .. }
+                | Commands::Replay { .. }
+                | Commands::Serve { .. }
+                | Commands::Lsp,
+            )
+            | None => Ok(false),
+        }
+    }
+
+    /// Listening options for the `serve` subcommand
+    ///
+    /// # Returns
+    /// * `Option<ServeOptions>` - Options, `None` for other subcommands
+    pub fn serve_options(&self) -> Option<ServeOptions> {
+        match &self.args.command {
+            Some(Commands::Serve {
+                port,
+                bind,
+                allow_writes,
+                token,
+            }) => Some(ServeOptions {
+                bind: bind.clone(),
+                port: *port,
+                allow_writes: *allow_writes,
+                token: token
+                    .clone()
+                    .or_else(|| std::env::var("MONGOSH_SERVE_TOKEN").ok())
+                    .filter(|token| !token.is_empty()),
+            }),
+            _ => None,
+        }
+    }
+
+    /// Whether the `lsp` subcommand was given
+    pub fn is_lsp(&self) -> bool {
+        matches!(self.args.command, Some(Commands::Lsp))
+    }
+
+    /// Whether stdout carries protocol messages (`--rpc`, `lsp`)
+    ///
+    /// Banners, connection info and logs must then stay off stdout.
+    pub fn stdout_is_protocol(&self) -> bool {
+        self.args.rpc || self.is_lsp()
+    }
+
+    /// Recording to re-run for the `replay` subcommand
+    ///
+    /// # Returns
+    /// * `Option<&Path>` - Recording file, `None` for other subcommands and
+    ///   `--dry-run`
+    pub fn replay_file(&self) -> Option<&Path> {
+        match &self.args.command {
+            Some(Commands::Replay {
+                file,
+                dry_run: false,
+            }) => Some(file),
+            _ => None,
+        }
+    }
+
+    /// Print the commands of a recording with their recorded output (`replay --dry-run`)
+    ///
+    /// # Arguments
+    /// * `file` - Recording file
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or error
+    fn show_recording(file: &Path) -> Result<()> {
+        let entries = recording::load(file)?;
+        let commands = entries
+            .iter()
+            .filter(|entry| entry.kind == EntryKind::Input)
+            .count();
+        println!(
+            "{}: {} command(s), not executed (--dry-run)",
+            file.display(),
+            commands
+        );
+
+        let mut number = 0;
+        for entry in &entries {
+            match entry.kind {
+                EntryKind::Input => {
+                    number += 1;
+                    println!("\n[{}] {}", number, entry.time);
+                    for line in entry.text.lines() {
+                        println!("> {}", line);
+                    }
+                }
+                EntryKind::Output | EntryKind::Error => {
+                    let prefix = if entry.kind == EntryKind::Error {
+                        "! "
+                    } else {
+                        "  "
+                    };
+                    for line in entry.text.lines() {
+                        println!("{}{}", prefix, line);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Shell command for the `dump` or `restore` subcommand
+    ///
+    /// Both act on the database selected by the URI or `--database`.
+    ///
+    /// # Returns
+    /// * `Option<Command>` - Admin command to run, `None` for other subcommands
+    pub fn backup_command(&self) -> Option<Command> {
+        match &self.args.command {
+            Some(Commands::Dump { out, collections }) => Some(Command::Admin(AdminCommand::Dump {
+                dir: out.clone(),
+                collections: collections.clone(),
+            })),
+            Some(Commands::Restore {
+                dir,
+                collections,
+                drop,
+            }) => Some(Command::Admin(AdminCommand::Restore {
+                dir: dir.clone(),
+                collections: collections.clone(),
+                drop: *drop,
+            })),
+            _ => None,
         }
     }
 
@@ -562,6 +874,37 @@ impl CliInterface {
         completion::generate_completion(shell)
     }
 
+    /// Print the command reference
+    ///
+    /// # Arguments
+    /// * `topic` - Single command to describe, or all commands
+    /// * `format` - Output format (text, markdown, json)
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or error
+    fn show_help(&self, topic: Option<&str>, format: &str) -> Result<()> {
+        let format = help::HelpFormat::parse(format)?;
+        let Some(topic) = topic else {
+            println!("{}", help::render(format)?);
+            return Ok(());
+        };
+
+        let cmd = help::lookup(topic).map_err(|suggestions| {
+            crate::error::MongoshError::Config(crate::error::ConfigError::Generic(
+                help::unknown_topic_message(topic, &suggestions),
+            ))
+        })?;
+        match format {
+            help::HelpFormat::Json => println!(
+                "{}",
+                serde_json::to_string_pretty(cmd)
+                    .map_err(|e| crate::error::MongoshError::Generic(e.to_string()))?
+            ),
+            _ => println!("{}", help::render_text(cmd)),
+        }
+        Ok(())
+    }
+
     /// Handle config subcommand
     ///
     /// # Arguments
@@ -710,11 +1053,37 @@ mod tests {
         assert_eq!(args.uri, Some("mongodb://localhost:27017".to_string()));
     }
 
+    #[test]
+    fn test_cli_args_report() {
+        let args = CliArgs::try_parse_from(vec!["mongosh", "--report"]).unwrap();
+        assert_eq!(args.report.as_deref(), Some(""));
+
+        let args = CliArgs::try_parse_from(vec!["mongosh", "--report", "crash-1.txt"]).unwrap();
+        assert_eq!(args.report.as_deref(), Some("crash-1.txt"));
+    }
+
+    #[test]
+    fn test_cli_args_rpc() {
+        let args = CliArgs::try_parse_from(vec!["mongosh", "--rpc"]).unwrap();
+        assert!(args.rpc);
+        assert!(CliArgs::try_parse_from(vec!["mongosh", "--rpc", "--mcp"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_args_lsp() {
+        let args = CliArgs::try_parse_from(vec!["mongosh", "--no-connect", "lsp"]).unwrap();
+        assert!(matches!(args.command, Some(Commands::Lsp)));
+        assert!(args.no_connect);
+    }
+
     #[test]
     fn test_cli_args_with_flags() {
         let args = CliArgs::try_parse_from(vec!["mongosh", "--no-color", "--quiet"]).unwrap();
         assert!(args.no_color);
         assert!(args.quiet);
+
+        let args = CliArgs::try_parse_from(vec!["mongosh", "--show-sensitive"]).unwrap();
+        assert!(args.show_sensitive);
     }
 
     #[test]
@@ -0,0 +1,557 @@
+//! Interactive terminal prompts
+//!
+//! This module provides the small set of line, password and checkbox prompts
+//! used outside the REPL editor, such as re-entering credentials after the
+//! server rejects an authentication attempt, picking the targets of a bulk
+//! drop or answering the `lookup wizard` questions.
+
+use std::io::{self, IsTerminal, Write};
+use std::str::FromStr;
+
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use crossterm::terminal::{self, ClearType};
+use crossterm::{cursor, queue};
+use mongodb::options::{AuthMechanism, Credential};
+
+use crate::error::{ExecutionError, MongoshError, Result};
+use crate::parser::PromptRequest;
+
+/// Check whether stdin is attached to a terminal a user can answer prompts on
+pub fn is_interactive() -> bool {
+    io::stdin().is_terminal()
+}
+
+/// Print a prompt and read one line from stdin, without the trailing newline
+///
+/// # Arguments
+/// * `label` - Prompt text shown before the cursor
+///
+/// # Returns
+/// * `Result<String>` - Trimmed input line
+pub fn read_line(label: &str) -> Result<String> {
+    print!("{}", label);
+    io::stdout()
+        .flush()
+        .map_err(|e| MongoshError::Generic(format!("Failed to flush stdout: {}", e)))?;
+
+    let mut input = String::new();
+    io::stdin()
+        .read_line(&mut input)
+        .map_err(|e| MongoshError::Generic(format!("Failed to read input: {}", e)))?;
+
+    Ok(input.trim().to_string())
+}
+
+/// Print a prompt and read a password without echoing it
+///
+/// Falls back to a plain line read when stdin is not a terminal so piped
+/// input keeps working. Ctrl+C or Esc cancels the prompt.
+///
+/// # Arguments
+/// * `label` - Prompt text shown before the cursor
+///
+/// # Returns
+/// * `Result<String>` - Password as typed
+pub fn read_password(label: &str) -> Result<String> {
+    if !is_interactive() {
+        return read_line(label);
+    }
+
+    print!("{}", label);
+    io::stdout().flush()?;
+
+    terminal::enable_raw_mode()?;
+    let result = read_password_raw();
+    terminal::disable_raw_mode()?;
+    println!();
+
+    result
+}
+
+/// Collect password characters while the terminal is in raw mode
+fn read_password_raw() -> Result<String> {
+    let mut password = String::new();
+
+    loop {
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind == KeyEventKind::Release {
+            continue;
+        }
+
+        match key.code {
+            KeyCode::Enter => return Ok(password),
+            KeyCode::Backspace => {
+                password.pop();
+            }
+            KeyCode::Esc => {
+                return Err(ExecutionError::Cancelled("Password entry cancelled".to_string()).into());
+            }
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                return Err(ExecutionError::Cancelled("Password entry cancelled".to_string()).into());
+            }
+            KeyCode::Char(c) => password.push(c),
+            _ => {}
+        }
+    }
+}
+
+/// Print a prompt and read one line, completing it from candidates on Tab
+///
+/// Tab extends the input to the longest prefix shared by the matching
+/// candidates and lists them when several match. Esc or Ctrl+C cancels. When
+/// stdin is not a terminal this is a plain `read_line`.
+///
+/// # Arguments
+/// * `label` - Prompt text shown before the cursor
+/// * `candidates` - Values offered for completion
+///
+/// # Returns
+/// * `Result<String>` - Trimmed input line
+pub fn read_completed(label: &str, candidates: &[String]) -> Result<String> {
+    if !is_interactive() || candidates.is_empty() {
+        return read_line(label);
+    }
+
+    print!("{}", label);
+    io::stdout().flush()?;
+
+    terminal::enable_raw_mode()?;
+    let result = read_completed_raw(label, candidates);
+    terminal::disable_raw_mode()?;
+    println!();
+
+    result.map(|input| input.trim().to_string())
+}
+
+/// Edit the input line while the terminal is in raw mode
+fn read_completed_raw(label: &str, candidates: &[String]) -> Result<String> {
+    let mut input = String::new();
+    let mut stdout = io::stdout();
+
+    loop {
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind == KeyEventKind::Release {
+            continue;
+        }
+
+        match key.code {
+            KeyCode::Enter => return Ok(input),
+            KeyCode::Esc => {
+                return Err(ExecutionError::Cancelled("Input cancelled".to_string()).into());
+            }
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                return Err(ExecutionError::Cancelled("Input cancelled".to_string()).into());
+            }
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Tab => {
+                let (completed, matches) = complete_input(&input, candidates);
+                input = completed;
+                if matches.len() > 1 {
+                    write!(stdout, "\r\n{}\r\n", matches.join("  "))?;
+                }
+            }
+            KeyCode::Char(c) => input.push(c),
+            _ => {}
+        }
+
+        queue!(
+            stdout,
+            cursor::MoveToColumn(0),
+            terminal::Clear(ClearType::CurrentLine)
+        )?;
+        write!(stdout, "{}{}", label, input)?;
+        stdout.flush()?;
+    }
+}
+
+/// Complete an input against candidates
+///
+/// # Arguments
+/// * `input` - Text typed so far
+/// * `candidates` - Values offered for completion
+///
+/// # Returns
+/// * `(String, Vec<String>)` - Input extended to the longest common prefix of
+///   the matches, and the matches themselves
+fn complete_input(input: &str, candidates: &[String]) -> (String, Vec<String>) {
+    let matches: Vec<String> = candidates
+        .iter()
+        .filter(|candidate| candidate.starts_with(input))
+        .cloned()
+        .collect();
+
+    let Some(first) = matches.first() else {
+        return (input.to_string(), matches);
+    };
+    let mut prefix = first.as_str();
+    for candidate in &matches[1..] {
+        let common = prefix
+            .char_indices()
+            .zip(candidate.chars())
+            .find(|((_, a), b)| a != b)
+            .map_or(prefix.len().min(candidate.len()), |((i, _), _)| i);
+        prefix = &prefix[..common];
+    }
+
+    (prefix.to_string(), matches)
+}
+
+/// Let the user tick any subset of items from a checkbox list
+///
+/// Arrow keys (or j/k) move, Space toggles, `a` toggles all, Enter confirms
+/// and Esc or Ctrl+C cancels. When stdin is not a terminal the items are
+/// printed as a numbered list and a line such as `1,3-4` or `all` is read.
+///
+/// # Arguments
+/// * `label` - Prompt text shown above the list
+/// * `items` - Items to choose from
+///
+/// # Returns
+/// * `Result<Option<Vec<usize>>>` - Indices of the ticked items, `None` if cancelled
+pub fn select_many(label: &str, items: &[String]) -> Result<Option<Vec<usize>>> {
+    if !is_interactive() {
+        println!("{}", label);
+        for (i, item) in items.iter().enumerate() {
+            println!("  {}) {}", i + 1, item);
+        }
+        let input = read_line("Select (e.g. 1,3-4 or all; blank cancels): ")?;
+        return parse_selection(&input, items.len());
+    }
+
+    println!("{}", label);
+    println!("  (↑/↓ move, space toggle, a toggle all, enter confirm, esc cancel)");
+
+    terminal::enable_raw_mode()?;
+    let result = select_many_raw(items);
+    terminal::disable_raw_mode()?;
+    println!();
+
+    result
+}
+
+/// Checkbox list state driven by key presses
+#[derive(Debug)]
+struct Checklist {
+    /// Index of the highlighted item
+    cursor: usize,
+    /// Whether each item is ticked
+    checked: Vec<bool>,
+}
+
+/// Outcome of a key press on a checklist
+#[derive(Debug, PartialEq)]
+enum ChecklistKey {
+    Continue,
+    Confirm,
+    Cancel,
+}
+
+impl Checklist {
+    fn new(len: usize) -> Self {
+        Self {
+            cursor: 0,
+            checked: vec![false; len],
+        }
+    }
+
+    /// Apply one key press
+    fn handle(&mut self, code: KeyCode, modifiers: KeyModifiers) -> ChecklistKey {
+        let len = self.checked.len();
+        match code {
+            KeyCode::Enter => return ChecklistKey::Confirm,
+            KeyCode::Esc => return ChecklistKey::Cancel,
+            KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => {
+                return ChecklistKey::Cancel;
+            }
+            KeyCode::Up | KeyCode::Char('k') if len > 0 => {
+                self.cursor = (self.cursor + len - 1) % len;
+            }
+            KeyCode::Down | KeyCode::Char('j') if len > 0 => {
+                self.cursor = (self.cursor + 1) % len;
+            }
+            KeyCode::Char(' ') if len > 0 => {
+                self.checked[self.cursor] = !self.checked[self.cursor];
+            }
+            KeyCode::Char('a') => {
+                let all = self.checked.iter().all(|c| *c);
+                self.checked.iter_mut().for_each(|c| *c = !all);
+            }
+            _ => {}
+        }
+        ChecklistKey::Continue
+    }
+
+    /// Indices of the ticked items
+    fn selected(&self) -> Vec<usize> {
+        (0..self.checked.len())
+            .filter(|i| self.checked[*i])
+            .collect()
+    }
+}
+
+/// Run the checkbox list while the terminal is in raw mode
+fn select_many_raw(items: &[String]) -> Result<Option<Vec<usize>>> {
+    let mut list = Checklist::new(items.len());
+    let mut stdout = io::stdout();
+
+    loop {
+        for (i, item) in items.iter().enumerate() {
+            let pointer = if i == list.cursor { '>' } else { ' ' };
+            let mark = if list.checked[i] { 'x' } else { ' ' };
+            queue!(stdout, terminal::Clear(ClearType::CurrentLine))?;
+            write!(stdout, "{} [{}] {}\r\n", pointer, mark, item)?;
+        }
+        stdout.flush()?;
+
+        let action = loop {
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Release {
+                break list.handle(key.code, key.modifiers);
+            }
+        };
+
+        match action {
+            ChecklistKey::Confirm => return Ok(Some(list.selected())),
+            ChecklistKey::Cancel => return Ok(None),
+            ChecklistKey::Continue => {
+                queue!(stdout, cursor::MoveUp(items.len() as u16))?;
+            }
+        }
+    }
+}
+
+/// Parse a typed selection such as `1,3-4`, `all` or blank (cancel)
+///
+/// # Arguments
+/// * `input` - Comma-separated 1-based numbers and ranges
+/// * `len` - Number of items offered
+///
+/// # Returns
+/// * `Result<Option<Vec<usize>>>` - Sorted 0-based indices, `None` if blank
+fn parse_selection(input: &str, len: usize) -> Result<Option<Vec<usize>>> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Ok(None);
+    }
+    if input.eq_ignore_ascii_case("all") {
+        return Ok(Some((0..len).collect()));
+    }
+
+    let invalid = || -> MongoshError {
+        ExecutionError::InvalidParameters(format!(
+            "Invalid selection '{}'. Expected numbers between 1 and {}, e.g. 1,3-4",
+            input, len
+        ))
+        .into()
+    };
+    let number = |s: &str| match s.trim().parse::<usize>() {
+        Ok(n) if (1..=len).contains(&n) => Ok(n - 1),
+        _ => Err(invalid()),
+    };
+
+    let mut selected = Vec::new();
+    for part in input.split(',') {
+        match part.split_once('-') {
+            Some((start, end)) => {
+                let (start, end) = (number(start)?, number(end)?);
+                if start > end {
+                    return Err(invalid());
+                }
+                selected.extend(start..=end);
+            }
+            None => selected.push(number(part)?),
+        }
+    }
+    selected.sort_unstable();
+    selected.dedup();
+
+    Ok(Some(selected))
+}
+
+/// Ask the `prompt()` and `passwordPrompt()` questions of a command
+///
+/// Called before the command is parsed, so parsing itself never reads the
+/// terminal. Fails when there are questions but stdin is not a terminal.
+///
+/// # Arguments
+/// * `requests` - Calls listed by `parser::prompt_requests`
+///
+/// # Returns
+/// * `Result<Vec<String>>` - One answer per request, in order
+pub fn answer_prompts(requests: &[PromptRequest]) -> Result<Vec<String>> {
+    if !requests.is_empty() && !is_interactive() {
+        return Err(MongoshError::Generic(
+            "prompt() and passwordPrompt() require an interactive terminal".to_string(),
+        ));
+    }
+
+    requests
+        .iter()
+        .map(|request| {
+            let label = format!("{}: ", request.label);
+            if request.password {
+                read_password(&label)
+            } else {
+                read_line(&label)
+            }
+        })
+        .collect()
+}
+
+/// Offer to re-enter credentials after an authentication failure
+///
+/// Asks for confirmation first, then for username, password and an optional
+/// authentication mechanism. The authentication database is left unset so the
+/// one from the connection URI keeps applying.
+///
+/// # Arguments
+/// * `default_username` - Username offered when the user presses Enter
+///
+/// # Returns
+/// * `Result<Option<Credential>>` - New credentials, or `None` if declined
+pub fn prompt_credentials(default_username: Option<&str>) -> Result<Option<Credential>> {
+    let answer = read_line("Authentication failed. Re-enter credentials? (yes/no): ")?;
+    if !matches!(answer.to_lowercase().as_str(), "yes" | "y") {
+        return Ok(None);
+    }
+
+    let username_label = match default_username {
+        Some(name) => format!("Username [{}]: ", name),
+        None => "Username: ".to_string(),
+    };
+    let mut username = read_line(&username_label)?;
+    if username.is_empty() {
+        username = default_username.unwrap_or_default().to_string();
+    }
+    if username.is_empty() {
+        return Err(ExecutionError::InvalidParameters("Username is required".to_string()).into());
+    }
+
+    let password = read_password("Password: ")?;
+    let mechanism = parse_mechanism(&read_line("Mechanism [default]: ")?)?;
+
+    let mut credential = Credential::default();
+    credential.username = Some(username);
+    credential.password = Some(password);
+    credential.mechanism = mechanism;
+
+    Ok(Some(credential))
+}
+
+/// Parse an authentication mechanism name; blank input selects the default
+///
+/// # Arguments
+/// * `input` - Mechanism name such as `SCRAM-SHA-256`
+///
+/// # Returns
+/// * `Result<Option<AuthMechanism>>` - Parsed mechanism, `None` for default
+fn parse_mechanism(input: &str) -> Result<Option<AuthMechanism>> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Ok(None);
+    }
+
+    AuthMechanism::from_str(&input.to_uppercase())
+        .map(Some)
+        .map_err(|_| {
+            ExecutionError::InvalidParameters(format!(
+                "Unknown authentication mechanism '{}'. Expected SCRAM-SHA-1, SCRAM-SHA-256, PLAIN or MONGODB-X509",
+                input
+            ))
+            .into()
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_mechanism_blank_is_default() {
+        assert!(parse_mechanism("").unwrap().is_none());
+        assert!(parse_mechanism("   ").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_parse_mechanism_known_names() {
+        assert_eq!(
+            parse_mechanism("SCRAM-SHA-256").unwrap(),
+            Some(AuthMechanism::ScramSha256)
+        );
+        assert_eq!(
+            parse_mechanism("scram-sha-1").unwrap(),
+            Some(AuthMechanism::ScramSha1)
+        );
+    }
+
+    #[test]
+    fn test_parse_mechanism_unknown_name() {
+        assert!(parse_mechanism("KERBEROS-ISH").is_err());
+    }
+
+    #[test]
+    fn test_parse_selection() {
+        assert_eq!(parse_selection("", 3).unwrap(), None);
+        assert_eq!(parse_selection("all", 3).unwrap(), Some(vec![0, 1, 2]));
+        assert_eq!(parse_selection("3, 1", 3).unwrap(), Some(vec![0, 2]));
+        assert_eq!(parse_selection("1-2,2", 3).unwrap(), Some(vec![0, 1]));
+        assert!(parse_selection("0", 3).is_err());
+        assert!(parse_selection("4", 3).is_err());
+        assert!(parse_selection("3-1", 3).is_err());
+        assert!(parse_selection("x", 3).is_err());
+    }
+
+    #[test]
+    fn test_complete_input() {
+        let candidates = vec![
+            "customerId".to_string(),
+            "customer.name".to_string(),
+            "createdAt".to_string(),
+        ];
+
+        let (input, matches) = complete_input("cu", &candidates);
+        assert_eq!(input, "customer");
+        assert_eq!(matches.len(), 2);
+
+        let (input, matches) = complete_input("cr", &candidates);
+        assert_eq!(input, "createdAt");
+        assert_eq!(matches, vec!["createdAt".to_string()]);
+
+        let (input, matches) = complete_input("x", &candidates);
+        assert_eq!(input, "x");
+        assert!(matches.is_empty());
+    }
+
+    #[test]
+    fn test_checklist_keys() {
+        let mut list = Checklist::new(3);
+        let none = KeyModifiers::NONE;
+
+        assert_eq!(
+            list.handle(KeyCode::Char(' '), none),
+            ChecklistKey::Continue
+        );
+        list.handle(KeyCode::Up, none);
+        list.handle(KeyCode::Char(' '), none);
+        assert_eq!(list.selected(), vec![0, 2]);
+
+        list.handle(KeyCode::Char('a'), none);
+        assert_eq!(list.selected(), vec![0, 1, 2]);
+        list.handle(KeyCode::Char('a'), none);
+        assert!(list.selected().is_empty());
+
+        assert_eq!(list.handle(KeyCode::Enter, none), ChecklistKey::Confirm);
+        assert_eq!(
+            list.handle(KeyCode::Char('c'), KeyModifiers::CONTROL),
+            ChecklistKey::Cancel
+        );
+    }
+}
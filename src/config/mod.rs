@@ -29,6 +29,18 @@ pub struct Config {
     #[serde(default)]
     pub display: DisplayConfig,
 
+    /// Query execution configuration
+    #[serde(default)]
+    pub query: QueryConfig,
+
+    /// Safety limits
+    #[serde(default)]
+    pub safety: SafetyConfig,
+
+    /// Masking of sensitive fields
+    #[serde(default)]
+    pub redaction: RedactionConfig,
+
     /// History configuration
     #[serde(default)]
     pub history: HistoryConfig,
@@ -48,6 +60,10 @@ pub struct Config {
     /// AI completion configuration
     #[serde(default)]
     pub ai: AiConfig,
+
+    /// Plugin configuration
+    #[serde(default)]
+    pub plugins: PluginConfig,
 }
 
 impl Default for Config {
@@ -55,11 +71,15 @@ impl Default for Config {
         Self {
             connection: ConnectionConfig::default(),
             display: DisplayConfig::default(),
+            query: QueryConfig::default(),
+            safety: SafetyConfig::default(),
+            redaction: RedactionConfig::default(),
             history: HistoryConfig::default(),
             logging: LoggingConfig::default(),
             mcp: None,
             named_query: HashMap::new(),
             ai: AiConfig::default(),
+            plugins: PluginConfig::default(),
         }
     }
 }
@@ -113,6 +133,178 @@ impl Config {
         Ok(config)
     }
 
+    /// Override a single setting from a `key=value` assignment
+    ///
+    /// The key is a dotted path into the configuration, e.g.
+    /// `display.page_size`. The value is read as a TOML literal and falls
+    /// back to a plain string, so `50`, `true` and `json` all work unquoted.
+    ///
+    /// # Arguments
+    /// * `assignment` - Override in `key=value` form
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or error for unknown keys and invalid values
+    pub fn apply_override(&mut self, assignment: &str) -> Result<()> {
+        let invalid = |msg: String| MongoshError::Config(ConfigError::Generic(msg));
+
+        let (key, raw) = assignment.split_once('=').ok_or_else(|| {
+            invalid(format!(
+                "Invalid override '{}', expected key=value",
+                assignment
+            ))
+        })?;
+        let key = key.trim();
+        let path: Vec<&str> = key.split('.').collect();
+        let unknown = || invalid(format!("Unknown configuration key: {}", key));
+        if path.len() < 2 || path.iter().any(|part| part.is_empty()) {
+            return Err(unknown());
+        }
+
+        let mut root = toml::Value::try_from(&*self)
+            .map_err(|e| invalid(format!("Failed to serialize configuration: {}", e)))?;
+        let (leaf, parents) = path.split_last().ok_or_else(unknown)?;
+        let mut table = root.as_table_mut().ok_or_else(unknown)?;
+        for part in parents {
+            table = table
+                .get_mut(*part)
+                .and_then(toml::Value::as_table_mut)
+                .ok_or_else(unknown)?;
+        }
+        let value = parse_override_value(raw.trim(), table.get(*leaf));
+        table.insert(leaf.to_string(), value);
+
+        let updated: Config = root
+            .try_into()
+            .map_err(|e| invalid(format!("Invalid value for '{}': {}", key, e)))?;
+
+        // Keys that no setting reads are silently dropped by deserialization
+        let check = toml::Value::try_from(&updated)
+            .map_err(|e| invalid(format!("Failed to serialize configuration: {}", e)))?;
+        if path
+            .iter()
+            .try_fold(&check, |value, part| value.get(*part))
+            .is_none()
+        {
+            return Err(unknown());
+        }
+
+        *self = updated;
+        Ok(())
+    }
+
+    /// Read a single setting by its dotted key
+    ///
+    /// Strings are returned without quotes; sections such as `display` are
+    /// rendered as TOML.
+    ///
+    /// # Arguments
+    /// * `key` - Dotted path into the configuration, e.g. `display.format`
+    ///
+    /// # Returns
+    /// * `Result<String>` - Value or error for unknown keys
+    pub fn get_value(&self, key: &str) -> Result<String> {
+        let invalid = |msg: String| MongoshError::Config(ConfigError::Generic(msg));
+
+        let root = toml::Value::try_from(self)
+            .map_err(|e| invalid(format!("Failed to serialize configuration: {}", e)))?;
+        let value = key
+            .trim()
+            .split('.')
+            .try_fold(&root, |value, part| value.get(part))
+            .ok_or_else(|| invalid(format!("Unknown configuration key: {}", key.trim())))?;
+
+        match value {
+            toml::Value::String(s) => Ok(s.clone()),
+            toml::Value::Table(table) => toml::to_string(table)
+                .map(|s| s.trim_end().to_string())
+                .map_err(|e| invalid(format!("Failed to serialize configuration: {}", e))),
+            other => Ok(other.to_string()),
+        }
+    }
+
+    /// Write a single setting to the configuration file
+    ///
+    /// The value is checked the same way as `apply_override`. Only that key
+    /// is rewritten, so comments and all other settings in the file stay as
+    /// they are.
+    ///
+    /// # Arguments
+    /// * `path` - Path to configuration file (None for default path)
+    /// * `key` - Dotted path into the configuration, e.g. `display.page_size`
+    /// * `raw` - Value as typed by the user
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or error
+    pub fn save_setting(path: Option<&Path>, key: &str, raw: &str) -> Result<()> {
+        let invalid = |msg: String| MongoshError::Config(ConfigError::Generic(msg));
+        let config_path = path
+            .map(|p| p.to_path_buf())
+            .unwrap_or_else(Self::default_config_path);
+
+        let (mut config, content) = if config_path.exists() {
+            let content = fs::read_to_string(&config_path).map_err(|e| {
+                invalid(format!(
+                    "Failed to read config file '{}': {}",
+                    config_path.display(),
+                    e
+                ))
+            })?;
+            (Self::load_from_file(Some(&config_path))?, content)
+        } else {
+            let config = Self::default();
+            let content = config.to_toml_with_comments()?;
+            (config, content)
+        };
+
+        let key = key.trim();
+        config.apply_override(&format!("{}={}", key, raw))?;
+        let value = toml::Value::try_from(&config)
+            .map_err(|e| invalid(format!("Failed to serialize configuration: {}", e)))?;
+        let value = key
+            .split('.')
+            .try_fold(&value, |value, part| value.get(part))
+            .ok_or_else(|| invalid(format!("Unknown configuration key: {}", key)))?;
+        let item = format!("value = {}", value)
+            .parse::<toml_edit::DocumentMut>()
+            .ok()
+            .and_then(|mut doc| doc.remove("value"))
+            .ok_or_else(|| invalid(format!("Failed to serialize value for '{}'", key)))?;
+
+        let mut doc = content.parse::<toml_edit::DocumentMut>().map_err(|e| {
+            invalid(format!(
+                "Failed to parse config file '{}': {}",
+                config_path.display(),
+                e
+            ))
+        })?;
+        let path: Vec<&str> = key.split('.').collect();
+        let (leaf, parents) = path
+            .split_last()
+            .ok_or_else(|| invalid(format!("Unknown configuration key: {}", key)))?;
+        let mut table = doc.as_table_mut();
+        for part in parents {
+            table = table
+                .entry(part)
+                .or_insert(toml_edit::table())
+                .as_table_mut()
+                .ok_or_else(|| {
+                    invalid(format!(
+                        "Cannot update '{}': [{}] is not a table in the config file",
+                        key, part
+                    ))
+                })?;
+        }
+        table[*leaf] = item;
+
+        fs::write(&config_path, doc.to_string()).map_err(|e| {
+            invalid(format!(
+                "Failed to write config file '{}': {}",
+                config_path.display(),
+                e
+            ))
+        })
+    }
+
     /// Save configuration to file
     ///
     /// # Arguments
@@ -187,6 +379,8 @@ impl Config {
             table["max_pool_size"] = toml_edit::value(config.connection.max_pool_size as i64);
             table["min_pool_size"] = toml_edit::value(config.connection.min_pool_size as i64);
             table["idle_timeout"] = toml_edit::value(config.connection.idle_timeout as i64);
+            table["auto_retry_reads"] = toml_edit::value(config.connection.auto_retry_reads);
+            table["auto_retry_writes"] = toml_edit::value(config.connection.auto_retry_writes);
         });
 
         Self::update_section(doc, "display", |table| {
@@ -196,6 +390,27 @@ impl Config {
             table["syntax_highlighting"] = toml_edit::value(config.display.syntax_highlighting);
             table["show_timing"] = toml_edit::value(config.display.show_timing);
             table["json_indent"] = toml_edit::value(config.display.json_indent as i64);
+            table["accessible"] = toml_edit::value(config.display.accessible);
+            table["collapse_depth"] = toml_edit::value(config.display.collapse_depth as i64);
+            table["collapse_width"] = toml_edit::value(config.display.collapse_width as i64);
+            table["max_string_length"] = toml_edit::value(config.display.max_string_length as i64);
+            table["max_binary_length"] = toml_edit::value(config.display.max_binary_length as i64);
+            table["timezone"] = toml_edit::value(config.display.timezone.as_str());
+            table["date_format"] = toml_edit::value(config.display.date_format.as_str());
+            table["table_summary"] = toml_edit::value(config.display.table_summary);
+        });
+
+        Self::update_section(doc, "redaction", |table| {
+            table["enabled"] = toml_edit::value(config.redaction.enabled);
+            table["fields"] = toml_edit::value(
+                config
+                    .redaction
+                    .fields
+                    .iter()
+                    .map(String::as_str)
+                    .collect::<toml_edit::Array>(),
+            );
+            table["mask"] = toml_edit::value(config.redaction.mask.as_str());
         });
 
         Self::update_section(doc, "history", |table| {
@@ -218,6 +433,25 @@ impl Config {
             }
         });
 
+        let plugin_settings = toml::to_string(&config.plugins.config)
+            .ok()
+            .and_then(|content| content.parse::<toml_edit::DocumentMut>().ok());
+        Self::update_section(doc, "plugins", |table| {
+            table["disabled"] = toml_edit::value(
+                config
+                    .plugins
+                    .disabled
+                    .iter()
+                    .map(String::as_str)
+                    .collect::<toml_edit::Array>(),
+            );
+            if let Some(settings) = plugin_settings {
+                let mut settings = settings.as_table().clone();
+                settings.set_implicit(true);
+                table["config"] = toml_edit::Item::Table(settings);
+            }
+        });
+
         Ok(())
     }
 
@@ -246,6 +480,10 @@ impl Config {
 
         Self::validate_range(self.display.page_size, 1, 1000, "Page size")?;
         Self::validate_range(self.display.json_indent, 0, 8, "JSON indent")?;
+        crate::formatter::bson_utils::DateDisplay::new(
+            &self.display.timezone,
+            &self.display.date_format,
+        )?;
         Self::validate_range(self.history.max_size, 0, 10000, "Max history size")?;
 
         // Validate AI config
@@ -326,6 +564,15 @@ pub struct ConnectionConfig {
     /// Connection idle timeout in seconds
     #[serde(default = "default_idle_timeout")]
     pub idle_timeout: u64,
+
+    /// Run a read once more when it fails with a network or primary change error
+    #[serde(default = "default_auto_retry_reads")]
+    pub auto_retry_reads: bool,
+
+    /// Run an idempotent write once more when it fails with a network or
+    /// primary change error
+    #[serde(default)]
+    pub auto_retry_writes: bool,
 }
 
 impl ConnectionConfig {
@@ -376,7 +623,8 @@ impl ConnectionConfig {
 /// Display and output configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DisplayConfig {
-    /// Output format (json, json-pretty, table, compact)
+    /// Output format (json, json-pretty, table, compact, extjson, extjson-canonical,
+    /// chart, map)
     #[serde(default = "default_format")]
     pub format: OutputFormat,
 
@@ -399,6 +647,50 @@ pub struct DisplayConfig {
     /// JSON indentation (number of spaces)
     #[serde(default = "default_json_indent")]
     pub json_indent: usize,
+
+    /// Show server startup warnings and deprecation notices after connecting
+    #[serde(default = "default_show_startup_warnings")]
+    pub show_startup_warnings: bool,
+
+    /// Screen reader friendly output: linear, label-prefixed text instead of
+    /// tables, spinners and colors
+    #[serde(default)]
+    pub accessible: bool,
+
+    /// Nesting depth below which shell output shows documents and arrays as
+    /// `{…} (N keys)` / `[… N items]` (0 shows every level)
+    #[serde(default)]
+    pub collapse_depth: usize,
+
+    /// Documents and arrays with more entries than this are collapsed in shell
+    /// output (0 never collapses)
+    #[serde(default = "default_collapse_width")]
+    pub collapse_width: usize,
+
+    /// Characters of a string shown in shell output before it is cut off
+    /// with `…` (0 shows whole strings)
+    #[serde(default = "default_max_string_length")]
+    pub max_string_length: usize,
+
+    /// BinData larger than this many bytes is shown by its size, e.g.
+    /// `BinData(0, 2.40 MiB)`, in shell output (0 always shows the content)
+    #[serde(default = "default_max_binary_length")]
+    pub max_binary_length: usize,
+
+    /// Time zone dates are shown in: `utc`, `local` or an IANA name such as
+    /// `Europe/Berlin`
+    #[serde(default = "default_timezone")]
+    pub timezone: String,
+
+    /// strftime pattern for dates, e.g. `%Y-%m-%d %H:%M:%S %Z` (empty for
+    /// ISO 8601)
+    #[serde(default)]
+    pub date_format: String,
+
+    /// Append a footer to table output with the count, distinct count and,
+    /// for numeric columns, sum and average of each column
+    #[serde(default)]
+    pub table_summary: bool,
 }
 
 /// Output format options
@@ -444,9 +736,78 @@ pub enum OutputFormat {
     /// Suitable for: quick checks, counting results
     /// Example: "5 document(s) returned"
     Compact,
+
+    /// MongoDB Extended JSON v2, relaxed mode
+    ///
+    /// Keeps BSON types that plain JSON can't express, using native JSON
+    /// numbers where no precision is lost.
+    /// Suitable for: round-tripping data through drivers and mongoimport
+    /// Example: `{"_id": {"$oid": "..."}, "at": {"$date": "2024-01-01T00:00:00Z"}}`
+    ExtJsonRelaxed,
+
+    /// MongoDB Extended JSON v2, canonical mode
+    ///
+    /// Wraps every non-string value with its type, preserving it exactly.
+    /// Suitable for: lossless exchange, e.g. Int32 vs Int64 vs Double
+    /// Example: `{"count": {"$numberInt": "5"}}`
+    ExtJsonCanonical,
+
+    /// ASCII bar chart of `{_id, count}` style documents
+    ///
+    /// Draws one bar per document, scaled to the largest value, with the
+    /// value and its share of the total. Other results use the shell format.
+    /// Suitable for: eyeballing the output of `$group` stages
+    Chart,
+
+    /// ASCII map of the GeoJSON positions in the results
+    ///
+    /// Plots every position on a small grid and prints the bounding box as
+    /// an OpenStreetMap link. Results without GeoJSON use the shell format.
+    /// Suitable for: checking `$near` and `$geoWithin` queries
+    Map,
+
+    /// Format registered by a plugin, by name
+    ///
+    /// See [`crate::formatter::register_format`].
+    Custom(&'static str),
 }
 
 impl OutputFormat {
+    /// Parse a format name (`shell`, `json`, `json-pretty`, `table`, `compact`,
+    /// `extjson`, `extjson-canonical`, `chart`, `map` or a format registered
+    /// by a plugin)
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "shell" => Some(OutputFormat::Shell),
+            "json" => Some(OutputFormat::Json),
+            "json-pretty" | "jsonpretty" | "json_pretty" => Some(OutputFormat::JsonPretty),
+            "table" => Some(OutputFormat::Table),
+            "compact" => Some(OutputFormat::Compact),
+            "extjson" | "extjson-relaxed" | "ejson" => Some(OutputFormat::ExtJsonRelaxed),
+            "extjson-canonical" => Some(OutputFormat::ExtJsonCanonical),
+            "chart" => Some(OutputFormat::Chart),
+            "map" => Some(OutputFormat::Map),
+            _ => crate::formatter::find_format(name).map(|(name, _)| OutputFormat::Custom(name)),
+        }
+    }
+
+    /// Names of every available format, built-in ones first
+    pub fn names() -> Vec<&'static str> {
+        let mut names = vec![
+            "shell",
+            "json",
+            "json-pretty",
+            "table",
+            "compact",
+            "extjson",
+            "extjson-canonical",
+            "chart",
+            "map",
+        ];
+        names.extend(crate::formatter::custom_format_names());
+        names
+    }
+
     /// Convert to string representation
     pub fn as_str(&self) -> &'static str {
         match self {
@@ -455,6 +816,11 @@ impl OutputFormat {
             OutputFormat::JsonPretty => "json-pretty",
             OutputFormat::Table => "table",
             OutputFormat::Compact => "compact",
+            OutputFormat::ExtJsonRelaxed => "extjson",
+            OutputFormat::ExtJsonCanonical => "extjson-canonical",
+            OutputFormat::Chart => "chart",
+            OutputFormat::Map => "map",
+            OutputFormat::Custom(name) => name,
         }
     }
 }
@@ -474,20 +840,97 @@ impl<'de> serde::Deserialize<'de> for OutputFormat {
         D: serde::Deserializer<'de>,
     {
         let s = String::deserialize(deserializer)?;
-        match s.to_lowercase().as_str() {
-            "shell" => Ok(OutputFormat::Shell),
-            "json" => Ok(OutputFormat::Json),
-            "json-pretty" | "jsonpretty" | "json_pretty" => Ok(OutputFormat::JsonPretty),
-            "table" => Ok(OutputFormat::Table),
-            "compact" => Ok(OutputFormat::Compact),
-            _ => Err(serde::de::Error::unknown_variant(
+        // Plugin formats must be registered before the configuration is loaded
+        OutputFormat::from_name(&s).ok_or_else(|| {
+            serde::de::Error::unknown_variant(
                 &s,
-                &["shell", "json", "json-pretty", "table", "compact"],
-            )),
-        }
+                &[
+                    "shell",
+                    "json",
+                    "json-pretty",
+                    "table",
+                    "compact",
+                    "extjson",
+                    "extjson-canonical",
+                    "chart",
+                    "map",
+                ],
+            )
+        })
     }
 }
 
+/// Query execution configuration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QueryConfig {
+    /// Continue skip/OFFSET pages from where the previous page ended with a range filter
+    #[serde(default)]
+    pub keyset_pagination: bool,
+
+    /// Warn when a query skips at least this many documents (0 disables)
+    #[serde(default = "default_offset_warning_threshold")]
+    pub offset_warning_threshold: u64,
+
+    /// Keep read results in memory and answer repeated queries from them
+    #[serde(default)]
+    pub result_cache: bool,
+
+    /// Seconds a cached result is reused
+    #[serde(default = "default_result_cache_ttl")]
+    pub result_cache_ttl: u64,
+
+    /// Most results kept; the least recently used is dropped first
+    #[serde(default = "default_result_cache_size")]
+    pub result_cache_size: usize,
+}
+
+/// Safety limits protecting the server and terminal
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SafetyConfig {
+    /// Limit applied to find queries without their own limit (0 disables)
+    #[serde(default)]
+    pub max_results: u64,
+
+    /// Count the documents matched by updateMany/deleteMany before asking
+    /// for confirmation
+    #[serde(default)]
+    pub preview_counts: bool,
+
+    /// Copy documents removed by deleteOne/deleteMany to `__mongosh_trash`
+    /// so `undo last-delete` can restore them
+    #[serde(default)]
+    pub trash: bool,
+
+    /// Copy a collection to `<name>.__backup_YYYYMMDD` before drop() and
+    /// renameCollection() unless they pass `{ backup: false }`
+    #[serde(default)]
+    pub backup_before_drop: bool,
+
+    /// Age in days after which `backups cleanup` drops a backup
+    #[serde(default = "default_backup_retention_days")]
+    pub backup_retention_days: u64,
+
+    /// Refuse commands that modify data
+    #[serde(default)]
+    pub read_only: bool,
+}
+
+/// Masking of sensitive fields in displayed and exported documents
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RedactionConfig {
+    /// Mask matching fields (turned off by --show-sensitive)
+    #[serde(default = "default_redaction_enabled")]
+    pub enabled: bool,
+
+    /// Field patterns such as `*.password`, `ssn` or `profile.email`
+    #[serde(default)]
+    pub fields: Vec<String>,
+
+    /// Replacement shown for masked values
+    #[serde(default = "default_redaction_mask")]
+    pub mask: String,
+}
+
 /// Command history configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HistoryConfig {
@@ -544,6 +987,23 @@ impl LogLevel {
     }
 }
 
+/// Read the value of a `-o key=value` override
+///
+/// Settings that hold strings always take the raw text, so `-o
+/// display.format=json` and `-o connection.default_datasource=123` need no
+/// quoting.
+fn parse_override_value(raw: &str, current: Option<&toml::Value>) -> toml::Value {
+    let parsed = toml::from_str::<toml::Table>(&format!("value = {}", raw))
+        .ok()
+        .and_then(|mut table| table.remove("value"));
+    match (parsed, current) {
+        (Some(toml::Value::String(s)), _) => toml::Value::String(s),
+        (_, Some(toml::Value::String(_))) => toml::Value::String(raw.to_string()),
+        (Some(value), _) => value,
+        (None, _) => toml::Value::String(raw.to_string()),
+    }
+}
+
 // Default value functions for serde
 #[inline]
 fn default_datasources() -> HashMap<String, String> {
@@ -582,6 +1042,11 @@ fn default_idle_timeout() -> u64 {
     300
 }
 
+#[inline]
+fn default_auto_retry_reads() -> bool {
+    true
+}
+
 #[inline]
 fn default_format() -> OutputFormat {
     OutputFormat::Shell
@@ -612,6 +1077,31 @@ fn default_json_indent() -> usize {
     2
 }
 
+#[inline]
+fn default_show_startup_warnings() -> bool {
+    true
+}
+
+#[inline]
+fn default_collapse_width() -> usize {
+    100
+}
+
+#[inline]
+fn default_max_string_length() -> usize {
+    200
+}
+
+#[inline]
+fn default_max_binary_length() -> usize {
+    64
+}
+
+#[inline]
+fn default_timezone() -> String {
+    "utc".to_string()
+}
+
 #[inline]
 fn default_max_history_size() -> usize {
     1000
@@ -629,6 +1119,36 @@ fn default_persist_history() -> bool {
     true
 }
 
+#[inline]
+fn default_offset_warning_threshold() -> u64 {
+    10000
+}
+
+#[inline]
+fn default_result_cache_ttl() -> u64 {
+    60
+}
+
+#[inline]
+fn default_result_cache_size() -> usize {
+    100
+}
+
+#[inline]
+fn default_backup_retention_days() -> u64 {
+    7
+}
+
+#[inline]
+fn default_redaction_enabled() -> bool {
+    true
+}
+
+#[inline]
+fn default_redaction_mask() -> String {
+    "***".to_string()
+}
+
 #[inline]
 fn default_log_level() -> LogLevel {
     LogLevel::Warn
@@ -650,6 +1170,8 @@ impl Default for ConnectionConfig {
             max_pool_size: default_max_pool_size(),
             min_pool_size: default_min_pool_size(),
             idle_timeout: default_idle_timeout(),
+            auto_retry_reads: default_auto_retry_reads(),
+            auto_retry_writes: false,
         }
     }
 }
@@ -663,6 +1185,71 @@ impl Default for DisplayConfig {
             syntax_highlighting: default_syntax_highlighting(),
             show_timing: default_show_timing(),
             json_indent: default_json_indent(),
+            show_startup_warnings: default_show_startup_warnings(),
+            accessible: false,
+            collapse_depth: 0,
+            collapse_width: default_collapse_width(),
+            max_string_length: default_max_string_length(),
+            max_binary_length: default_max_binary_length(),
+            timezone: default_timezone(),
+            date_format: String::new(),
+            table_summary: false,
+        }
+    }
+}
+
+impl Default for QueryConfig {
+    fn default() -> Self {
+        Self {
+            keyset_pagination: false,
+            offset_warning_threshold: default_offset_warning_threshold(),
+            result_cache: false,
+            result_cache_ttl: default_result_cache_ttl(),
+            result_cache_size: default_result_cache_size(),
+        }
+    }
+}
+
+impl Default for SafetyConfig {
+    fn default() -> Self {
+        Self {
+            max_results: 0,
+            preview_counts: false,
+            trash: false,
+            backup_before_drop: false,
+            backup_retention_days: default_backup_retention_days(),
+            read_only: false,
+        }
+    }
+}
+
+impl SafetyConfig {
+    /// Check whether these settings may be replaced
+    ///
+    /// While `read_only` is on the safety settings are locked, so the
+    /// session it guards cannot switch it off.
+    ///
+    /// # Arguments
+    /// * `new` - Settings replacing these
+    ///
+    /// # Returns
+    /// * `Result<()>` - Error when `read_only` is on and `new` differs
+    pub fn check_change(&self, new: &SafetyConfig) -> Result<()> {
+        if self.read_only && self != new {
+            return Err(MongoshError::Config(ConfigError::Generic(
+                "Safety settings cannot be changed while safety.read_only is on".to_string(),
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl Default for RedactionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_redaction_enabled(),
+            fields: Vec::new(),
+            mask: default_redaction_mask(),
         }
     }
 }
@@ -700,6 +1287,25 @@ impl LogLevel {
     }
 }
 
+/// Plugin configuration
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PluginConfig {
+    /// Plugins loaded disabled; `plugin enable <name>` turns one on
+    #[serde(default)]
+    pub disabled: Vec<String>,
+
+    /// Settings passed to each plugin's `configure` hook, by plugin name
+    #[serde(default)]
+    pub config: HashMap<String, toml::Table>,
+}
+
+impl PluginConfig {
+    /// Whether a plugin starts enabled
+    pub fn is_enabled(&self, name: &str) -> bool {
+        !self.disabled.iter().any(|disabled| disabled == name)
+    }
+}
+
 /// AI completion configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AiConfig {
@@ -849,6 +1455,8 @@ mod tests {
         );
         assert_eq!(config.display.format, OutputFormat::Shell);
         assert!(config.display.color_output);
+        assert!(config.connection.auto_retry_reads);
+        assert!(!config.connection.auto_retry_writes);
     }
 
     #[test]
@@ -865,6 +1473,13 @@ mod tests {
         config.connection.min_pool_size = 20;
         config.connection.max_pool_size = 10;
         assert!(config.validate().is_err());
+
+        // Test unknown timezone
+        config = Config::default();
+        config.display.timezone = "Europe/Atlantis".to_string();
+        assert!(config.validate().is_err());
+        config.display.timezone = "Europe/Berlin".to_string();
+        assert!(config.validate().is_ok());
     }
 
     #[test]
@@ -876,4 +1491,129 @@ mod tests {
         assert!(toml_str.contains("[history]"));
         assert!(toml_str.contains("[logging]"));
     }
+
+    #[test]
+    fn test_redaction_config() {
+        let mut config = Config::default();
+        assert!(config.redaction.enabled);
+        assert!(config.redaction.fields.is_empty());
+        assert_eq!(config.redaction.mask, "***");
+
+        config.redaction.fields = vec!["*.password".to_string(), "ssn".to_string()];
+        let saved: Config = toml::from_str(&config.to_toml_with_comments().unwrap()).unwrap();
+        assert_eq!(saved.redaction, config.redaction);
+    }
+
+    #[test]
+    fn test_plugin_config() {
+        let config: Config = toml::from_str(
+            r#"
+            [plugins]
+            disabled = ["audit"]
+
+            [plugins.config.graph]
+            max_depth = 3
+            "#,
+        )
+        .unwrap();
+        assert!(!config.plugins.is_enabled("audit"));
+        assert!(config.plugins.is_enabled("graph"));
+        assert_eq!(
+            config.plugins.config["graph"].get("max_depth"),
+            Some(&toml::Value::Integer(3))
+        );
+
+        let saved: Config = toml::from_str(&config.to_toml_with_comments().unwrap()).unwrap();
+        assert_eq!(saved.plugins, config.plugins);
+    }
+
+    #[test]
+    fn test_apply_override() {
+        let mut config = Config::default();
+        config.apply_override("display.page_size=50").unwrap();
+        config.apply_override("display.color_output=false").unwrap();
+        config.apply_override("display.format=json").unwrap();
+        config
+            .apply_override("query.keyset_pagination = true")
+            .unwrap();
+        config
+            .apply_override("connection.default_datasource=123")
+            .unwrap();
+        config.apply_override("safety.read_only=true").unwrap();
+        assert_eq!(config.display.page_size, 50);
+        assert!(!config.display.color_output);
+        assert_eq!(config.display.format, OutputFormat::Json);
+        assert!(config.query.keyset_pagination);
+        assert!(config.safety.read_only);
+        assert_eq!(
+            config.connection.default_datasource,
+            Some("123".to_string())
+        );
+
+        assert!(config.apply_override("display.page_size").is_err());
+        assert!(config.apply_override("display.page_size=many").is_err());
+        assert!(config.apply_override("display.no_such_key=1").is_err());
+        assert!(config.apply_override("nosuch.key=1").is_err());
+        assert!(config.apply_override("display=1").is_err());
+        assert_eq!(config.display.page_size, 50);
+    }
+
+    #[test]
+    fn test_safety_check_change() {
+        let mut config = Config::default();
+        let relaxed = config.safety.clone();
+        config.apply_override("safety.read_only=true").unwrap();
+        assert!(relaxed.check_change(&config.safety).is_ok());
+        assert!(config.safety.check_change(&relaxed).is_err());
+        assert!(config.safety.check_change(&config.safety).is_ok());
+
+        let mut changed = config.safety.clone();
+        changed.max_results = 10;
+        assert!(config.safety.check_change(&changed).is_err());
+    }
+
+    #[test]
+    fn test_get_value() {
+        let mut config = Config::default();
+        config.apply_override("display.page_size=50").unwrap();
+
+        assert_eq!(config.get_value("display.page_size").unwrap(), "50");
+        assert_eq!(config.get_value("display.format").unwrap(), "shell");
+        assert_eq!(config.get_value("display.color_output").unwrap(), "true");
+        assert!(
+            config
+                .get_value("display")
+                .unwrap()
+                .contains("page_size = 50")
+        );
+        assert!(config.get_value("display.no_such_key").is_err());
+        assert!(config.get_value("").is_err());
+    }
+
+    #[test]
+    fn test_save_setting_keeps_rest_of_file() {
+        let path =
+            std::env::temp_dir().join(format!("mongosh-save-setting-{}.toml", std::process::id()));
+        fs::write(
+            &path,
+            "# my settings\n[display]\n# rows per page\npage_size = 20\nformat = \"table\"\n",
+        )
+        .unwrap();
+
+        Config::save_setting(Some(&path), "display.page_size", "50").unwrap();
+        Config::save_setting(Some(&path), "query.keyset_pagination", "true").unwrap();
+        assert!(Config::save_setting(Some(&path), "display.page_size", "many").is_err());
+
+        let content = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert!(content.contains("# my settings"));
+        assert!(content.contains("# rows per page"));
+        assert!(content.contains("page_size = 50"));
+        assert!(content.contains("format = \"table\""));
+
+        let config: Config = toml::from_str(&content).unwrap();
+        assert_eq!(config.display.page_size, 50);
+        assert_eq!(config.display.format, OutputFormat::Table);
+        assert!(config.query.keyset_pagination);
+    }
 }
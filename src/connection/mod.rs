@@ -7,7 +7,11 @@
 //! - Automatic reconnection with exponential backoff
 //! - Session management for transactions
 
-use mongodb::{Client, ClientSession, Database, options::ClientOptions};
+use mongodb::{
+    Client, ClientSession, Database,
+    event::EventHandler,
+    options::{ClientOptions, Credential},
+};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
@@ -35,6 +39,9 @@ pub struct ConnectionManager {
 
     /// Last activity timestamp for connection health tracking
     last_activity: Arc<RwLock<Option<Instant>>>,
+
+    /// Credentials entered in-session, overriding those from the URI
+    credential: Option<Credential>,
 }
 
 /// Connection state information
@@ -107,7 +114,33 @@ impl ConnectionManager {
             state: Arc::new(RwLock::new(ConnectionState::Disconnected)),
             uri,
             last_activity: Arc::new(RwLock::new(None)),
+            credential: None,
+        }
+    }
+
+    /// Override the credentials used by the next `connect()`
+    ///
+    /// An unset authentication source falls back to the one in the URI.
+    ///
+    /// # Arguments
+    /// * `credential` - Username, password and optional mechanism
+    pub fn set_credential(&mut self, credential: Credential) {
+        self.credential = Some(credential);
+    }
+
+    /// Get the username the connection authenticates as, if any
+    ///
+    /// # Returns
+    /// * `Option<String>` - Username from in-session credentials or the URI
+    pub async fn username(&self) -> Option<String> {
+        if let Some(credential) = &self.credential {
+            return credential.username.clone();
         }
+        Self::parse_uri(&self.uri)
+            .await
+            .ok()
+            .and_then(|options| options.credential)
+            .and_then(|credential| credential.username)
     }
 
     /// Establish connection to MongoDB
@@ -120,7 +153,8 @@ impl ConnectionManager {
 
         // Parse URI and create client options
         let options = Self::parse_uri(&self.uri).await?;
-        let configured_options = self.configure_pool(options);
+        let mut configured_options = self.configure_pool(options);
+        self.apply_credential(&mut configured_options);
 
         // Attempt connection with retry logic
         match self.connect_with_retry(configured_options).await {
@@ -279,6 +313,12 @@ impl ConnectionManager {
         options.max_pool_size = Some(self.config.max_pool_size);
         options.min_pool_size = Some(self.config.min_pool_size);
 
+        // Feed pool gauges of the metrics endpoint
+        crate::metrics::global().set_pool_max_size(self.config.max_pool_size);
+        options.cmap_event_handler = Some(EventHandler::callback(|event| {
+            crate::metrics::global().observe_pool_event(&event);
+        }));
+
         // Set timeouts from configuration
         options.connect_timeout = Some(Duration::from_secs(self.config.timeout));
         // Use a reasonable minimum for server selection timeout to handle secondary-only scenarios
@@ -322,6 +362,22 @@ impl ConnectionManager {
         options
     }
 
+    /// Apply in-session credentials on top of the options parsed from the URI
+    ///
+    /// # Arguments
+    /// * `options` - Client options to update
+    fn apply_credential(&self, options: &mut ClientOptions) {
+        let Some(credential) = &self.credential else {
+            return;
+        };
+
+        let mut credential = credential.clone();
+        if credential.source.is_none() {
+            credential.source = options.credential.as_ref().and_then(|c| c.source.clone());
+        }
+        options.credential = Some(credential);
+    }
+
     /// Update connection state
     ///
     /// # Arguments
@@ -476,6 +532,26 @@ impl ConnectionManager {
         Ok(())
     }
 
+    /// Verify the server accepts the configured credentials
+    ///
+    /// Client creation is lazy, so authentication only happens once a command
+    /// is sent. This sends a ping and keeps the driver error intact so callers
+    /// can tell authentication failures apart from other connection problems.
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or the driver error
+    pub async fn verify_authentication(&self) -> Result<()> {
+        use mongodb::bson::doc;
+
+        let client = self.get_client()?;
+        let db = client
+            .default_database()
+            .unwrap_or_else(|| client.database("admin"));
+
+        db.run_command(doc! { "ping": 1 }).await?;
+        Ok(())
+    }
+
     /// Get MongoDB server version
     ///
     /// # Arguments
@@ -692,6 +768,39 @@ mod tests {
         assert!(!sanitized.contains("pass"));
     }
 
+    #[tokio::test]
+    async fn test_username_prefers_in_session_credential() {
+        let config = ConnectionConfig::default();
+        let mut manager =
+            ConnectionManager::new("mongodb://alice:pw@localhost:27017".to_string(), config);
+        assert_eq!(manager.username().await.as_deref(), Some("alice"));
+
+        let mut credential = Credential::default();
+        credential.username = Some("bob".to_string());
+        manager.set_credential(credential);
+        assert_eq!(manager.username().await.as_deref(), Some("bob"));
+    }
+
+    #[tokio::test]
+    async fn test_apply_credential_keeps_uri_auth_source() {
+        let config = ConnectionConfig::default();
+        let mut manager = ConnectionManager::new(
+            "mongodb://alice:pw@localhost:27017/?authSource=reporting".to_string(),
+            config,
+        );
+
+        let mut credential = Credential::default();
+        credential.username = Some("bob".to_string());
+        credential.password = Some("secret".to_string());
+        manager.set_credential(credential);
+
+        let mut options = ConnectionManager::parse_uri(&manager.uri).await.unwrap();
+        manager.apply_credential(&mut options);
+        let applied = options.credential.unwrap();
+        assert_eq!(applied.username.as_deref(), Some("bob"));
+        assert_eq!(applied.source.as_deref(), Some("reporting"));
+    }
+
     #[test]
     fn test_sanitize_uri_no_credentials() {
         let config = ConnectionConfig::default();
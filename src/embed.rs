@@ -0,0 +1,286 @@
+//! High-level API for embedding the shell in other Rust tools
+//!
+//! [`MongoshSession`] wraps a connection, an execution context and the output
+//! formatter, so evaluating shell or SQL commands takes a few lines:
+//!
+//! ```no_run
+//! use mongosh::MongoshSession;
+//!
+//! # async fn example() -> mongosh::Result<()> {
+//! let session = MongoshSession::connect("mongodb://localhost:27017/shop").await?;
+//! let result = session.eval("db.users.find({ age: { $gt: 30 } }).limit(5)").await?;
+//! println!("{}", result);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`MongoshSession::stream`] returns the documents of a query as a
+//! `futures::Stream` instead, reading the cursor in batches as it is polled.
+//!
+//! A session behaves like one REPL session: `use` switches its database,
+//! `format table` changes how later results are rendered and `_` refers to
+//! the previous result. Commands that need the interactive shell, such as
+//! `exit`, `load()`, `record start` or monitors that run until Ctrl+C, are
+//! refused.
+//!
+//! A session never reads stdin. Operations the shell would confirm first,
+//! like `deleteMany` or `dropDatabase`, fail with
+//! [`ExecutionError::ConfirmationRequired`] from [`MongoshSession::eval`];
+//! run them with [`MongoshSession::eval_confirmed`] once the caller has
+//! decided they are intended.
+
+use std::fmt;
+
+use futures::Stream;
+use mongodb::bson::Document;
+use serde_json::Value as JsonValue;
+
+use crate::cli::extract_database_from_uri;
+use crate::config::{Config, OutputFormat};
+use crate::connection::ConnectionManager;
+use crate::error::{ExecutionError, Result};
+use crate::executor::{ConfirmationMode, ExecutionContext, ExecutionResult, QueryExecutor};
+use crate::formatter::Formatter;
+use crate::parser::{Command, Parser};
+use crate::repl::SharedState;
+
+/// Database used when the URI names none
+const DEFAULT_DATABASE: &str = "test";
+
+/// A connected shell session for library use
+pub struct MongoshSession {
+    /// Connection, session state and command routing
+    context: ExecutionContext,
+
+    /// Configuration the session was created with
+    config: Config,
+}
+
+/// Result of [`MongoshSession::eval`]
+#[derive(Debug, Clone)]
+pub struct FormattedResult {
+    /// Result as returned by the executor
+    pub result: ExecutionResult,
+
+    /// Result rendered as the shell would print it, without colors
+    pub text: String,
+}
+
+impl FormattedResult {
+    /// Documents of the result; counts, ids and listings are not documents
+    pub fn documents(&self) -> Option<Vec<Document>> {
+        self.result.data.to_documents()
+    }
+
+    /// Result as relaxed Extended JSON, `None` for messages and empty results
+    pub fn to_json(&self) -> Option<JsonValue> {
+        self.result
+            .data
+            .to_bson()
+            .map(|value| value.into_relaxed_extjson())
+    }
+}
+
+impl fmt::Display for FormattedResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+impl MongoshSession {
+    /// Connect with the default configuration
+    ///
+    /// # Arguments
+    /// * `uri` - MongoDB connection URI; its path selects the database
+    ///
+    /// # Returns
+    /// * `Result<Self>` - Connected session or connection error
+    pub async fn connect(uri: &str) -> Result<Self> {
+        Self::connect_with_config(uri, Config::default()).await
+    }
+
+    /// Connect with a configuration, e.g. one loaded with `Config::load_from_file`
+    ///
+    /// # Arguments
+    /// * `uri` - MongoDB connection URI; its path selects the database
+    /// * `config` - Connection, query, display and safety settings
+    ///
+    /// # Returns
+    /// * `Result<Self>` - Connected session or connection error
+    pub async fn connect_with_config(uri: &str, config: Config) -> Result<Self> {
+        let mut connection = ConnectionManager::new(uri.to_string(), config.connection.clone());
+        connection.connect().await?;
+        let server_version = match connection.get_client() {
+            Ok(client) => connection.get_server_version(client).await.ok(),
+            Err(_) => None,
+        };
+
+        let database =
+            extract_database_from_uri(uri).unwrap_or_else(|| DEFAULT_DATABASE.to_string());
+        let mut shared_state = SharedState::with_config(database, &config.display);
+        shared_state.set_connected(server_version);
+        shared_state.set_settings(config.clone());
+        shared_state.set_color_enabled(false);
+
+        Ok(Self {
+            context: ExecutionContext::new(connection, shared_state)
+                .with_confirmation(ConfirmationMode::Refuse),
+            config,
+        })
+    }
+
+    /// Parse, execute and format one shell or SQL command
+    ///
+    /// # Arguments
+    /// * `input` - Command as typed in the shell, e.g. `db.users.find()`
+    ///
+    /// # Returns
+    /// * `Result<FormattedResult>` - Result and its text, or the parse,
+    ///   execution or server error; `ConfirmationRequired` for operations
+    ///   the shell would confirm first
+    pub async fn eval(&self, input: &str) -> Result<FormattedResult> {
+        self.eval_in(&self.context, input).await
+    }
+
+    /// Like [`eval`](Self::eval), but run operations that need confirmation
+    ///
+    /// # Arguments
+    /// * `input` - Command as typed in the shell, e.g. `db.logs.deleteMany({})`
+    ///
+    /// # Returns
+    /// * `Result<FormattedResult>` - Result and its text, or the parse,
+    ///   execution or server error
+    pub async fn eval_confirmed(&self, input: &str) -> Result<FormattedResult> {
+        let context = self.context.with_confirmation(ConfirmationMode::Confirmed);
+        self.eval_in(&context, input).await
+    }
+
+    /// Parse, execute and format one command in the given context
+    async fn eval_in(&self, context: &ExecutionContext, input: &str) -> Result<FormattedResult> {
+        let mut parser = Parser::new();
+        parser.set_last_result(context.shared_state.get_last_result_value());
+        let command = parser.parse(input)?;
+        if matches!(
+            command,
+            Command::Exit
+                | Command::Retry
+                | Command::AiQuery(_)
+                | Command::Load(_)
+                | Command::Record(_)
+                | Command::Report(_)
+        ) || command.runs_until_interrupted()
+        {
+            return Err(ExecutionError::InvalidOperation(
+                "This command is only available in the interactive shell".to_string(),
+            )
+            .into());
+        }
+
+        let format = command.format_override();
+        let fields = command.pluck_fields().map(<[String]>::to_vec);
+        let full = command.full_values() || context.shared_state.get_full_values();
+        let result = context.execute(command).await?;
+        if !result.success {
+            return Err(ExecutionError::QueryFailed(
+                result
+                    .error
+                    .clone()
+                    .unwrap_or_else(|| "Command failed".to_string()),
+            )
+            .into());
+        }
+
+        let shared_state = &context.shared_state;
+        let mut display = self.config.display.clone();
+        display.format = format.unwrap_or_else(|| shared_state.get_format());
+        display.color_output = shared_state.get_color_enabled();
+        let text = Formatter::from_config(&display)
+            .with_full_values(full)
+            .with_projection(fields.as_deref().unwrap_or_default())
+            .with_redaction(&shared_state.get_redaction_config())
+            .format(&result)?;
+
+        Ok(FormattedResult { result, text })
+    }
+
+    /// Parse and execute a query, streaming its documents
+    ///
+    /// Unlike [`eval`](Self::eval), `find` and `aggregate` results are not
+    /// collected: the cursor is read one batch at a time as the stream is
+    /// polled.
+    ///
+    /// # Arguments
+    /// * `input` - Query as typed in the shell, e.g. `db.events.find()`
+    ///
+    /// # Returns
+    /// * `Result<impl Stream<Item = Result<Document>>>` - Document stream, or
+    ///   the parse or execution error
+    pub async fn stream(
+        &self,
+        input: &str,
+    ) -> Result<impl Stream<Item = Result<Document>> + Send + 'static> {
+        let mut parser = Parser::new();
+        parser.set_last_result(self.context.shared_state.get_last_result_value());
+        let Command::Query(query) = parser.parse(input)? else {
+            return Err(ExecutionError::InvalidOperation(
+                "Only queries can be streamed".to_string(),
+            )
+            .into());
+        };
+        QueryExecutor::new(self.context.clone())
+            .await?
+            .execute_stream(query)
+            .await
+    }
+
+    /// Current database
+    pub fn database(&self) -> String {
+        self.context.shared_state.get_database()
+    }
+
+    /// Switch the database used by later commands, like `use <name>`
+    pub async fn use_database(&self, name: &str) {
+        self.context.set_current_database(name.to_string()).await;
+    }
+
+    /// Set the format results are rendered in
+    pub fn set_format(&self, format: OutputFormat) {
+        self.context.shared_state.set_format(format);
+    }
+
+    /// Render results with ANSI colors (off by default)
+    pub fn set_color(&self, enabled: bool) {
+        self.context.shared_state.set_color_enabled(enabled);
+    }
+
+    /// Execution context, for running already parsed commands
+    pub fn context(&self) -> &ExecutionContext {
+        &self.context
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::executor::{ExecutionStats, ResultData};
+    use mongodb::bson::doc;
+
+    #[test]
+    fn test_formatted_result() {
+        let result = FormattedResult {
+            result: ExecutionResult {
+                success: true,
+                data: ResultData::Documents(vec![doc! { "name": "Ada", "age": 36 }]),
+                stats: ExecutionStats::default(),
+                error: None,
+            },
+            text: "[ { name: 'Ada', age: 36 } ]".to_string(),
+        };
+        assert_eq!(result.to_string(), "[ { name: 'Ada', age: 36 } ]");
+        assert_eq!(result.documents().unwrap().len(), 1);
+        assert_eq!(
+            result.to_json().unwrap(),
+            serde_json::json!([{ "name": "Ada", "age": 36 }])
+        );
+    }
+}
@@ -0,0 +1,95 @@
+//! Source snippets for parse errors.
+//!
+//! Renders the input line an error points at with a caret underline, in the
+//! style of compiler diagnostics:
+//!
+//! ```text
+//! Expected ':' after property key
+//!   |
+//! 1 | db.users.find({ name 'x' })
+//!   |                      ^^^
+//!   = hint: write properties as `key: value`
+//! ```
+
+use std::ops::Range;
+
+/// Render the line containing `span` with carets under the spanned text and
+/// an optional hint.
+///
+/// Spans are character offsets into `source`. An empty span, such as the end
+/// of input, gets a single caret; a span running past the end of its line is
+/// underlined up to the line end.
+///
+/// # Arguments
+/// * `source` - Input that was parsed
+/// * `span` - Character range of the offending text
+/// * `hint` - How to fix the error, shown under the carets
+///
+/// # Returns
+/// * `String` - Gutter, source line, caret line and hint, without a trailing
+///   newline
+pub fn render_snippet(source: &str, span: &Range<usize>, hint: Option<&str>) -> String {
+    let chars: Vec<char> = source.chars().collect();
+    let start = span.start.min(chars.len());
+
+    // Locate the line holding the start of the span
+    let line_start = chars[..start]
+        .iter()
+        .rposition(|&c| c == '\n')
+        .map_or(0, |i| i + 1);
+    let line_end = chars[start..]
+        .iter()
+        .position(|&c| c == '\n')
+        .map_or(chars.len(), |i| start + i);
+    let line_number = chars[..line_start].iter().filter(|&&c| c == '\n').count() + 1;
+    let line: String = chars[line_start..line_end].iter().collect();
+
+    // Keep tabs in the padding so the carets line up with the source
+    let padding: String = chars[line_start..start]
+        .iter()
+        .map(|&c| if c == '\t' { '\t' } else { ' ' })
+        .collect();
+    let width = span.end.min(line_end).saturating_sub(start).max(1);
+
+    let number = line_number.to_string();
+    let gutter = " ".repeat(number.len());
+    let mut snippet = format!(
+        "{gutter} |\n{number} | {line}\n{gutter} | {padding}{carets}",
+        line = line.trim_end(),
+        carets = "^".repeat(width)
+    );
+    if let Some(hint) = hint {
+        snippet.push_str(&format!("\n{gutter} = hint: {hint}"));
+    }
+    snippet
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_snippet() {
+        let snippet = render_snippet(
+            "db.users.find({ name 'x' })",
+            &(21..24),
+            Some("write properties as `key: value`"),
+        );
+        assert_eq!(
+            snippet,
+            "  |\n1 | db.users.find({ name 'x' })\n  |                      ^^^\n  = hint: write properties as `key: value`"
+        );
+    }
+
+    #[test]
+    fn test_render_snippet_end_of_input_and_later_lines() {
+        let snippet = render_snippet("db.users.find({", &(15..15), None);
+        assert!(snippet.ends_with(&format!("| {}^", " ".repeat(15))));
+
+        let source = "db.users.aggregate([\n  { $match: { a: 1 } \n])";
+        let snippet = render_snippet(source, &(43..44), None);
+        let lines: Vec<&str> = snippet.lines().collect();
+        assert_eq!(lines[1], "3 | ])");
+        assert_eq!(lines[2], "  | ^");
+    }
+}
@@ -3,9 +3,13 @@
 //! This module defines a streamlined error hierarchy that wraps MongoDB driver
 //! errors and provides additional application-specific error types.
 
+use std::ops::Range;
 use std::{fmt, io};
 
-use crate::error::mongo::format_mongodb_error;
+use crate::error::diagnostic::render_snippet;
+use crate::error::mongo::ErrorExplanation;
+#[cfg(feature = "native")]
+use crate::error::mongo::{ErrorResponse, format_mongodb_error};
 
 /// Crate-wide `Result` type using [`MongoshError`] as the error.
 pub type Result<T> = std::result::Result<T, MongoshError>;
@@ -17,6 +21,7 @@ pub type Result<T> = std::result::Result<T, MongoshError>;
 #[derive(Debug)]
 pub enum MongoshError {
     /// MongoDB driver errors (automatically formatted as structured JSON).
+    #[cfg(feature = "native")]
     MongoDb(mongodb::error::Error),
 
     /// Connection-related errors.
@@ -84,6 +89,43 @@ pub enum ParseError {
     /// Invalid aggregation pipeline.
     #[allow(dead_code)]
     InvalidPipeline(String),
+
+    /// Syntax error at a known position, rendered with the offending line.
+    Located {
+        /// What went wrong
+        message: String,
+        /// Input that was parsed
+        source: String,
+        /// Character range of the offending text in `source`
+        span: Range<usize>,
+        /// How to fix it
+        hint: Option<String>,
+    },
+}
+
+impl ParseError {
+    /// Create a syntax error pointing at a span of the parsed input.
+    ///
+    /// # Arguments
+    /// * `message` - What went wrong
+    /// * `source` - Input that was parsed
+    /// * `span` - Character range of the offending text
+    pub fn at(message: impl Into<String>, source: impl Into<String>, span: Range<usize>) -> Self {
+        ParseError::Located {
+            message: message.into(),
+            source: source.into(),
+            span,
+            hint: None,
+        }
+    }
+
+    /// Attach a hint to a located error; other errors are returned unchanged.
+    pub fn with_hint(mut self, text: impl Into<String>) -> Self {
+        if let ParseError::Located { hint, .. } = &mut self {
+            *hint = Some(text.into());
+        }
+        self
+    }
 }
 
 /// Execution-specific errors.
@@ -103,6 +145,10 @@ pub enum ExecutionError {
 
     /// Operation cancelled by user (e.g., Ctrl+C).
     Cancelled(String),
+
+    /// Dangerous operation that needs confirmation, but nobody can be asked
+    /// (JSON-RPC mode, embedded sessions).
+    ConfirmationRequired(String),
 }
 
 /// Configuration-specific errors.
@@ -124,6 +170,149 @@ pub enum ConfigError {
     Generic(String),
 }
 
+// ============================================================================
+// Error classification
+// ============================================================================
+
+/// Server error code for `AuthenticationFailed`.
+#[cfg(feature = "native")]
+const AUTHENTICATION_FAILED_CODE: i32 = 18;
+
+/// Message fragments that identify authentication failures once a driver
+/// error has been flattened into a string by the executor.
+const AUTHENTICATION_ERROR_MARKERS: &[&str] = &[
+    "Authentication failed",
+    "AuthenticationFailed",
+    "requires authentication",
+];
+
+/// Server error codes for failures that usually clear up on their own, such
+/// as an election in progress or a dropped connection.
+#[cfg(feature = "native")]
+const TRANSIENT_ERROR_CODES: &[i32] = &[
+    6,     // HostUnreachable
+    7,     // HostNotFound
+    89,    // NetworkTimeout
+    91,    // ShutdownInProgress
+    189,   // PrimarySteppedDown
+    9001,  // SocketException
+    10107, // NotWritablePrimary
+    11600, // InterruptedAtShutdown
+    11602, // InterruptedDueToReplStateChange
+    13435, // NotPrimaryNoSecondaryOk
+    13436, // NotPrimaryOrSecondary
+];
+
+/// Error labels the server and driver attach to retryable failures.
+#[cfg(feature = "native")]
+const TRANSIENT_ERROR_LABELS: &[&str] = &[
+    "RetryableWriteError",
+    "TransientTransactionError",
+    "SystemOverloadedError",
+];
+
+/// Message fragments that identify network and primary-election failures
+/// once a driver error has been flattened into a string by the executor.
+const TRANSIENT_ERROR_MARKERS: &[&str] = &[
+    "NotWritablePrimary",
+    "NotPrimaryNoSecondaryOk",
+    "NotPrimaryOrSecondary",
+    "PrimarySteppedDown",
+    "InterruptedDueToReplStateChange",
+    "not primary",
+    "HostUnreachable",
+    "NetworkTimeout",
+    "SocketException",
+    "Server selection timeout",
+    "connection pool cleared",
+    "Connection reset",
+    "Connection refused",
+    "Broken pipe",
+];
+
+impl MongoshError {
+    /// Check whether this error was caused by rejected or missing credentials.
+    ///
+    /// Driver errors are classified by kind and server code; errors that the
+    /// executor has already flattened into messages are matched textually.
+    pub fn is_authentication_error(&self) -> bool {
+        #[cfg(feature = "native")]
+        use mongodb::error::ErrorKind;
+
+        match self {
+            #[cfg(feature = "native")]
+            MongoshError::MongoDb(e) => match e.kind.as_ref() {
+                ErrorKind::Authentication { .. } => true,
+                ErrorKind::Command(c) => c.code == AUTHENTICATION_FAILED_CODE,
+                _ => is_authentication_message(&e.to_string()),
+            },
+            other => is_authentication_message(&other.to_string()),
+        }
+    }
+
+    /// Check whether this error is a network failure or a primary change
+    /// that is likely to succeed when the command is run again.
+    ///
+    /// Driver errors are classified by kind, label and server code; errors
+    /// that the executor has already flattened into messages are matched
+    /// textually.
+    pub fn is_transient_error(&self) -> bool {
+        #[cfg(feature = "native")]
+        use mongodb::error::ErrorKind;
+
+        match self {
+            #[cfg(feature = "native")]
+            MongoshError::MongoDb(e) => {
+                TRANSIENT_ERROR_LABELS
+                    .iter()
+                    .any(|label| e.contains_label(label))
+                    || match e.kind.as_ref() {
+                        ErrorKind::Io(_)
+                        | ErrorKind::ServerSelection { .. }
+                        | ErrorKind::ConnectionPoolCleared { .. } => true,
+                        ErrorKind::Command(c) => TRANSIENT_ERROR_CODES.contains(&c.code),
+                        _ => is_transient_message(&e.to_string()),
+                    }
+            }
+            MongoshError::Connection(ConnectionError::Timeout) => true,
+            MongoshError::Parse(_) | MongoshError::Config(_) => false,
+            MongoshError::Execution(
+                ExecutionError::Cancelled(_) | ExecutionError::ConfirmationRequired(_),
+            ) => false,
+            other => is_transient_message(&other.to_string()),
+        }
+    }
+
+    /// Explain a well-known server error and suggest what to run next.
+    ///
+    /// Parse and configuration errors never carry a server code, so only
+    /// driver errors and the messages the executor flattened them into are
+    /// considered.
+    pub fn explanation(&self) -> Option<ErrorExplanation> {
+        match self {
+            #[cfg(feature = "native")]
+            MongoshError::MongoDb(e) => ErrorResponse::from_mongodb_error(e).explanation,
+            MongoshError::Parse(_) | MongoshError::Config(_) => None,
+            other => ErrorExplanation::from_message(&other.to_string()),
+        }
+    }
+}
+
+/// Check whether an error message describes a network or election failure.
+fn is_transient_message(message: &str) -> bool {
+    let lower = message.to_lowercase();
+    TRANSIENT_ERROR_MARKERS
+        .iter()
+        .any(|marker| lower.contains(&marker.to_lowercase()))
+}
+
+/// Check whether an error message describes an authentication failure.
+fn is_authentication_message(message: &str) -> bool {
+    AUTHENTICATION_ERROR_MARKERS
+        .iter()
+        .any(|marker| message.contains(marker))
+}
+
 // ============================================================================
 // Display implementations
 // ============================================================================
@@ -131,6 +320,7 @@ pub enum ConfigError {
 impl fmt::Display for MongoshError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
+            #[cfg(feature = "native")]
             MongoshError::MongoDb(e) => format_mongodb_error(f, e),
             MongoshError::Connection(e) => write!(f, "ConnectionError: {}", e),
             MongoshError::Parse(e) => write!(f, "ParseError: {}", e),
@@ -164,6 +354,15 @@ impl fmt::Display for ParseError {
             ParseError::InvalidCommand(msg) => write!(f, "{}", msg),
             ParseError::InvalidQuery(msg) => write!(f, "{}", msg),
             ParseError::InvalidPipeline(msg) => write!(f, "{}", msg),
+            ParseError::Located {
+                message,
+                source,
+                span,
+                hint,
+            } => {
+                let snippet = render_snippet(source, span, hint.as_deref());
+                write!(f, "{}\n{}", message, snippet)
+            }
         }
     }
 }
@@ -176,6 +375,7 @@ impl fmt::Display for ExecutionError {
             ExecutionError::CursorError(msg) => write!(f, "{}", msg),
             ExecutionError::InvalidOperation(msg) => write!(f, "{}", msg),
             ExecutionError::Cancelled(msg) => write!(f, "{}", msg),
+            ExecutionError::ConfirmationRequired(msg) => write!(f, "{}", msg),
         }
     }
 }
@@ -205,6 +405,7 @@ impl std::error::Error for ConfigError {}
 // Conversions to MongoshError
 // ============================================================================
 
+#[cfg(feature = "native")]
 impl From<mongodb::error::Error> for MongoshError {
     fn from(err: mongodb::error::Error) -> Self {
         MongoshError::MongoDb(err)
@@ -265,8 +466,89 @@ impl From<bson::de::Error> for MongoshError {
     }
 }
 
+#[cfg(feature = "repl")]
 impl From<reedline::ReedlineError> for MongoshError {
     fn from(err: reedline::ReedlineError) -> Self {
         MongoshError::Generic(format!("Reedline error: {}", err))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_authentication_error_from_flattened_message() {
+        let err: MongoshError = ExecutionError::QueryFailed(
+            "Kind: Authentication failed. SCRAM failure: bad auth".to_string(),
+        )
+        .into();
+        assert!(err.is_authentication_error());
+
+        let err: MongoshError =
+            ExecutionError::QueryFailed("command find requires authentication".to_string()).into();
+        assert!(err.is_authentication_error());
+    }
+
+    #[test]
+    fn test_is_authentication_error_ignores_other_errors() {
+        let err: MongoshError = ConnectionError::NotConnected.into();
+        assert!(!err.is_authentication_error());
+
+        let err: MongoshError = ParseError::SyntaxError("unexpected token".to_string()).into();
+        assert!(!err.is_authentication_error());
+    }
+
+    #[test]
+    fn test_is_transient_error() {
+        let err: MongoshError = ExecutionError::QueryFailed(
+            "Command failed (NotWritablePrimary): not primary".to_string(),
+        )
+        .into();
+        assert!(err.is_transient_error());
+
+        let err: MongoshError = ExecutionError::QueryFailed(
+            "Server selection timeout: No available servers".to_string(),
+        )
+        .into();
+        assert!(err.is_transient_error());
+
+        let err: MongoshError = ExecutionError::QueryFailed(
+            "E11000 duplicate key error collection: shop.users".to_string(),
+        )
+        .into();
+        assert!(!err.is_transient_error());
+
+        let err: MongoshError =
+            ExecutionError::Cancelled("connection reset by user".to_string()).into();
+        assert!(!err.is_transient_error());
+    }
+
+    #[test]
+    fn test_explanation() {
+        let err: MongoshError = ExecutionError::QueryFailed(
+            "Kind: Command failed: Error code 13 (Unauthorized): not authorized on shop"
+                .to_string(),
+        )
+        .into();
+        let explanation = err.explanation().unwrap();
+        assert!(explanation.summary.contains("not allowed"));
+
+        let err: MongoshError =
+            ParseError::InvalidQuery("Error code 13 in a string literal".to_string()).into();
+        assert!(err.explanation().is_none());
+    }
+
+    #[test]
+    fn test_located_parse_error_display() {
+        let err = ParseError::at("Expected ')' after arguments", "db.users.find({}", 16..16)
+            .with_hint("close the call with ')'");
+        assert_eq!(
+            err.to_string(),
+            "Expected ')' after arguments\n  |\n1 | db.users.find({}\n  |                 ^\n  = hint: close the call with ')'"
+        );
+
+        let err = ParseError::InvalidCommand("unknown".to_string()).with_hint("ignored");
+        assert_eq!(err.to_string(), "unknown");
+    }
+}
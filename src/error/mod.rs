@@ -4,10 +4,13 @@
 //! - Structured error information extraction from MongoDB driver errors
 //! - Consistent JSON error formatting for APIs and logging
 //! - Application-specific error types
+//! - Source snippets with a caret under the error position for parse errors
 //!
 //! # Example
 //!
 //! ```rust,no_run
+//! # #[cfg(feature = "native")]
+//! # mod example {
 //! use mongosh::error::{Result, MongoshError};
 //! use mongosh::error::mongo::ErrorResponse;
 //!
@@ -21,8 +24,10 @@
 //!     let response = ErrorResponse::from_mongodb_error(err);
 //!     println!("{}", response.to_json_pretty().unwrap());
 //! }
+//! # }
 //! ```
 
+pub mod diagnostic;
 pub mod kinds;
 pub mod mongo;
 
@@ -2,6 +2,14 @@
 //!
 //! This module provides utilities to extract structured error information from
 //! MongoDB driver errors and format them as JSON for consistent error reporting.
+//! Common server error codes also get a plain-language explanation with
+//! suggested next commands, shown under the raw error in the shell.
+//!
+//! Without the `native` feature there are no driver errors to convert; only
+//! the explanations of server error codes and messages remain.
+
+// Most helpers only serve the driver error conversions
+#![cfg_attr(not(feature = "native"), allow(dead_code, unused_imports))]
 
 use serde::{Deserialize, Serialize};
 use std::fmt;
@@ -14,14 +22,21 @@ use std::fmt;
 #[derive(Debug, Clone)]
 pub struct ErrorResponse {
     pub error: ErrorInfo,
+
+    /// Explanation and remediation hints for well-known error codes; not
+    /// part of the JSON output
+    pub explanation: Option<ErrorExplanation>,
 }
 
 impl ErrorResponse {
     /// Create a new error response from a MongoDB error.
+    #[cfg(feature = "native")]
     pub fn from_mongodb_error(error: &mongodb::error::Error) -> Self {
-        Self {
-            error: ErrorInfo::from_mongodb_error(error),
-        }
+        let error = ErrorInfo::from_mongodb_error(error);
+        let explanation = error
+            .code
+            .and_then(|code| ErrorExplanation::for_code(code, error.details.as_ref()));
+        Self { error, explanation }
     }
 
     /// Convert to pretty-printed JSON string.
@@ -65,6 +80,7 @@ pub struct ErrorInfo {
 
 impl ErrorInfo {
     /// Extract structured error information from a MongoDB driver error.
+    #[cfg(feature = "native")]
     pub fn from_mongodb_error(error: &mongodb::error::Error) -> Self {
         use mongodb::error::ErrorKind;
 
@@ -220,6 +236,7 @@ impl ErrorInfo {
         }
     }
 
+    #[cfg(feature = "native")]
     fn from_write_failure(write_failure: &mongodb::error::WriteFailure) -> Self {
         use mongodb::error::WriteFailure;
 
@@ -268,6 +285,7 @@ impl ErrorInfo {
         }
     }
 
+    #[cfg(feature = "native")]
     fn from_command_error(command_error: &mongodb::error::CommandError) -> Self {
         Self {
             error_type: "mongo.command_error".to_string(),
@@ -283,6 +301,7 @@ impl ErrorInfo {
         }
     }
 
+    #[cfg(feature = "native")]
     fn from_insert_many_error(insert_error: &mongodb::error::InsertManyError) -> Self {
         // Prioritize write errors over write concern errors
         if let Some(write_errors) = &insert_error.write_errors {
@@ -335,6 +354,7 @@ impl ErrorInfo {
         }
     }
 
+    #[cfg(feature = "native")]
     fn from_bulk_write_error(bulk_error: &mongodb::error::BulkWriteError) -> Self {
         Self {
             error_type: "mongo.bulk_write_error".to_string(),
@@ -375,7 +395,173 @@ pub struct ErrorDetails {
     pub raw: Option<bson::Document>,
 }
 
+/// Plain-language explanation of a server error with suggested next steps.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ErrorExplanation {
+    /// What the error means
+    pub summary: String,
+
+    /// Commands to run or settings to change next
+    pub hints: Vec<String>,
+}
+
+impl ErrorExplanation {
+    /// Explain a server error code.
+    ///
+    /// # Arguments
+    /// * `code` - Server error code
+    /// * `details` - Collection, index and key involved, when known
+    ///
+    /// # Returns
+    /// * `Option<Self>` - Explanation for well-known codes, None otherwise
+    pub fn for_code(code: i32, details: Option<&ErrorDetails>) -> Option<Self> {
+        let collection = details
+            .and_then(|d| d.collection.as_deref())
+            .unwrap_or("<collection>");
+
+        let (summary, hints) = match code {
+            11000 | 11001 => {
+                let index = details
+                    .and_then(|d| d.index.as_deref())
+                    .map(|index| format!(" '{}'", index))
+                    .unwrap_or_default();
+                let filter = details
+                    .and_then(|d| d.key.clone())
+                    .map(|key| bson::Bson::Document(key).into_relaxed_extjson().to_string())
+                    .unwrap_or_else(|| "{ <field>: <value> }".to_string());
+                (
+                    format!(
+                        "Another document already has this value in the unique index{}.",
+                        index
+                    ),
+                    vec![
+                        format!(
+                            "db.{}.find({}) to see the existing document",
+                            collection, filter
+                        ),
+                        format!("db.{}.getIndexes() to see which fields are unique", collection),
+                        "updateOne(filter, update, { upsert: true }) to update the existing document instead".to_string(),
+                    ],
+                )
+            }
+            13 => (
+                "The current user is not allowed to run this operation.".to_string(),
+                vec![
+                    "db.runCommand({ connectionStatus: 1, showPrivileges: true }) to list your roles and privileges".to_string(),
+                    "ask an administrator for a role that grants the action, or reconnect as another user".to_string(),
+                ],
+            ),
+            18 => (
+                "The server rejected the username or password.".to_string(),
+                vec![
+                    "check the credentials and the authSource in the connection string (usually admin)".to_string(),
+                ],
+            ),
+            26 => (
+                "The database or collection does not exist.".to_string(),
+                vec![
+                    "show collections to list the collections of the current database".to_string(),
+                    "show dbs to list databases".to_string(),
+                ],
+            ),
+            48 => (
+                "A collection with this name already exists.".to_string(),
+                vec![
+                    "show collections to list existing collections".to_string(),
+                    format!("db.{}.drop() first if it should be replaced", collection),
+                ],
+            ),
+            50 => (
+                "The operation ran longer than its maxTimeMS limit and was stopped.".to_string(),
+                vec![
+                    format!(
+                        "db.{}.find(<filter>).explain(\"executionStats\") to check for a COLLSCAN",
+                        collection
+                    ),
+                    "add an index on the filtered and sorted fields, or raise maxTimeMS".to_string(),
+                    "currentOp to see what else is running on the server".to_string(),
+                ],
+            ),
+            85 | 86 => (
+                "An index with the same name or keys but different options already exists.".to_string(),
+                vec![
+                    format!("db.{}.getIndexes() to compare the existing index", collection),
+                    format!(
+                        "db.{}.dropIndex(<name>) before creating it with the new options",
+                        collection
+                    ),
+                ],
+            ),
+            121 => (
+                "The document does not satisfy the collection's validation rules.".to_string(),
+                vec![format!("db.{}.getValidator() to see the rules", collection)],
+            ),
+            211 => (
+                "The server has no cluster time signing key in its keys cache yet, which happens shortly after a replica set is initiated or while admin.system.keys cannot be read.".to_string(),
+                vec![
+                    "wait a few seconds and run retry".to_string(),
+                    "show log global --grep keys to look for key refresh errors".to_string(),
+                ],
+            ),
+            292 => (
+                "A sort or group stage needed more memory than allowed, and spilling to disk was not allowed.".to_string(),
+                vec![
+                    "pass { allowDiskUse: true } as an option to aggregate()".to_string(),
+                    "add an index that matches the sort so no in-memory sort is needed".to_string(),
+                ],
+            ),
+            10107 | 13435 | 13436 => (
+                "The server is not the primary, usually because an election is in progress.".to_string(),
+                vec![
+                    "run retry once a new primary has been elected".to_string(),
+                    "db.adminCommand({ replSetGetStatus: 1 }) to see the member states".to_string(),
+                ],
+            ),
+            _ => return None,
+        };
+
+        Some(Self { summary, hints })
+    }
+
+    /// Explain an error whose driver error was flattened into a message.
+    ///
+    /// The code is read from `Error code N` in command errors, `code: N` in
+    /// write errors or an `E11000` prefix; details such as the collection and
+    /// index are parsed from the message as well.
+    ///
+    /// # Arguments
+    /// * `message` - Error message
+    ///
+    /// # Returns
+    /// * `Option<Self>` - Explanation for well-known codes, None otherwise
+    pub fn from_message(message: &str) -> Option<Self> {
+        let code = ["Error code ", "code: "]
+            .iter()
+            .find_map(|prefix| {
+                let start = message.find(prefix)? + prefix.len();
+                let digits: String = message[start..]
+                    .chars()
+                    .take_while(|c| c.is_ascii_digit())
+                    .collect();
+                digits.parse::<i32>().ok()
+            })
+            .or_else(|| message.contains("E11000").then_some(11000))?;
+
+        let mut details = ErrorDetails {
+            namespace: None,
+            collection: None,
+            database: None,
+            index: None,
+            key: None,
+            raw: None,
+        };
+        enhance_details_from_message(&mut details, message);
+        Self::for_code(code, Some(&details))
+    }
+}
+
 /// Format a MongoDB error as a JSON error response.
+#[cfg(feature = "native")]
 pub fn format_mongodb_error_json(error: &mongodb::error::Error) -> String {
     let response = ErrorResponse::from_mongodb_error(error);
     response
@@ -384,6 +570,7 @@ pub fn format_mongodb_error_json(error: &mongodb::error::Error) -> String {
 }
 
 /// Format MongoDB error for Display trait implementation.
+#[cfg(feature = "native")]
 pub fn format_mongodb_error(
     f: &mut fmt::Formatter<'_>,
     error: &mongodb::error::Error,
@@ -396,6 +583,7 @@ pub fn format_mongodb_error(
 // ============================================================================
 
 /// Extract error details from a WriteError.
+#[cfg(feature = "native")]
 fn extract_write_error_details(write_error: &mongodb::error::WriteError) -> ErrorDetails {
     let mut details = ErrorDetails {
         namespace: None,
@@ -423,6 +611,7 @@ fn extract_write_error_details(write_error: &mongodb::error::WriteError) -> Erro
 }
 
 /// Extract error details from an IndexedWriteError.
+#[cfg(feature = "native")]
 fn extract_indexed_write_error_details(
     write_error: &mongodb::error::IndexedWriteError,
 ) -> ErrorDetails {
@@ -562,6 +751,7 @@ fn extract_field_from_message(message: &str, field_prefix: &str) -> Option<Strin
 }
 
 /// Get error labels from a MongoDB error.
+#[cfg(feature = "native")]
 fn get_error_labels(error: &mongodb::error::Error) -> Option<Vec<String>> {
     if error.labels().is_empty() {
         None
@@ -674,6 +864,43 @@ mod tests {
             "Operation failed"
         );
     }
+
+    #[test]
+    fn test_explanation_for_duplicate_key() {
+        let details = ErrorDetails {
+            namespace: Some("shop.users".to_string()),
+            collection: Some("users".to_string()),
+            database: Some("shop".to_string()),
+            index: Some("email_1".to_string()),
+            key: Some(bson::doc! { "email": "a@example.com" }),
+            raw: None,
+        };
+        let explanation = ErrorExplanation::for_code(11000, Some(&details)).unwrap();
+        assert!(explanation.summary.contains("'email_1'"));
+        assert_eq!(
+            explanation.hints[0],
+            r#"db.users.find({"email":"a@example.com"}) to see the existing document"#
+        );
+        assert!(ErrorExplanation::for_code(96, None).is_none());
+    }
+
+    #[test]
+    fn test_explanation_from_message() {
+        let explanation = ErrorExplanation::from_message(
+            "Kind: Command failed: Error code 50 (MaxTimeMSExpired): operation exceeded time limit",
+        )
+        .unwrap();
+        assert!(explanation.summary.contains("maxTimeMS"));
+        assert!(explanation.hints[0].starts_with("db.<collection>.find("));
+
+        let explanation = ErrorExplanation::from_message(
+            "E11000 duplicate key error collection: shop.users index: email_1 dup key: { email: 1 }",
+        )
+        .unwrap();
+        assert!(explanation.hints[1].starts_with("db.users.getIndexes()"));
+
+        assert!(ErrorExplanation::from_message("connection refused").is_none());
+    }
 }
 
 #[cfg(test)]
@@ -692,7 +919,10 @@ mod json_format_tests {
             labels: None,
         };
 
-        let response = ErrorResponse { error: error_info };
+        let response = ErrorResponse {
+            error: error_info,
+            explanation: None,
+        };
         let json_str = response.to_json_pretty().unwrap();
 
         println!("JSON Output:\n{}", json_str);
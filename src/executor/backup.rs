@@ -0,0 +1,633 @@
+//! mongodump-style dump and restore
+//!
+//! A dump directory holds one folder per database. Each collection is stored
+//! as `<name>.bson`, the documents as concatenated BSON, and
+//! `<name>.metadata.json`, its options and indexes as canonical extended JSON.
+//! This is the layout of `mongodump --out`, so dumps written here can be
+//! loaded with `mongorestore`, and uncompressed directory dumps from
+//! `mongodump` can be restored here. Archives (`--archive`) and gzipped dumps
+//! are not supported.
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+use futures::stream::TryStreamExt;
+use mongodb::Database;
+use mongodb::bson::{Bson, Document, RawDocumentBuf, doc};
+use mongodb::error::ErrorKind;
+use tokio::fs::{self, File};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWriteExt, BufReader, BufWriter};
+use tracing::info;
+
+use super::progress::{ProgressTracker, ProgressUnit};
+use crate::error::{MongoshError, Result};
+
+/// Documents sent per `insertMany` while restoring
+const RESTORE_BATCH_SIZE: usize = 1000;
+
+/// Largest document accepted from a `.bson` file (16 MiB plus server headroom)
+const MAX_DOCUMENT_SIZE: usize = 16 * 1024 * 1024 + 16 * 1024;
+
+/// Server error code for duplicate keys
+const DUPLICATE_KEY: i32 = 11000;
+
+/// Server error code when `create` targets an existing collection
+const NAMESPACE_EXISTS: i32 = 48;
+
+/// Outcome of dumping or restoring one collection
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct BackupEntry {
+    /// Collection name
+    pub collection: String,
+    /// Documents written or inserted
+    pub documents: u64,
+    /// Indexes written or created, without `_id_`
+    pub indexes: usize,
+    /// Documents skipped because their `_id` already existed
+    pub skipped: u64,
+}
+
+/// Dump collections of a database to `<dir>/<db>/`
+///
+/// # Arguments
+/// * `db` - Database to dump
+/// * `dir` - Dump directory
+/// * `collections` - Collections to dump; all when empty
+/// * `accessible` - Whether accessible mode is on, which hides progress bars
+///
+/// # Returns
+/// * `Result<Vec<BackupEntry>>` - One entry per dumped collection
+pub(super) async fn dump_database(
+    db: &Database,
+    dir: &Path,
+    collections: &[String],
+    accessible: bool,
+) -> Result<Vec<BackupEntry>> {
+    let target = dir.join(db.name());
+    fs::create_dir_all(&target)
+        .await
+        .map_err(|e| io_error("create", &target, e))?;
+
+    let filter = if collections.is_empty() {
+        doc! {}
+    } else {
+        doc! { "name": { "$in": collections } }
+    };
+    let mut specs: Vec<Document> = db
+        .run_cursor_command(doc! { "listCollections": 1, "filter": filter })
+        .await?
+        .try_collect()
+        .await?;
+    specs.retain(|spec| spec.get_str("name").is_ok_and(|name| !is_system(name)));
+    specs.sort_by(|a, b| a.get_str("name").ok().cmp(&b.get_str("name").ok()));
+
+    let mut entries = Vec::new();
+    for spec in specs {
+        let name = spec.get_str("name").unwrap_or_default().to_string();
+        let kind = spec.get_str("type").unwrap_or("collection").to_string();
+        info!("Dumping {}.{}", db.name(), name);
+
+        let indexes: Vec<Document> = if kind == "view" {
+            Vec::new()
+        } else {
+            db.run_cursor_command(doc! { "listIndexes": &name })
+                .await?
+                .try_collect()
+                .await?
+        };
+        let options = spec.get_document("options").cloned().unwrap_or_default();
+        let metadata = build_metadata(&name, &kind, options, &indexes);
+        let metadata_path = target.join(format!("{}.metadata.json", name));
+        fs::write(&metadata_path, metadata_json(metadata)?)
+            .await
+            .map_err(|e| io_error("write", &metadata_path, e))?;
+
+        let mut documents = 0;
+        if kind != "view" {
+            let bson_path = target.join(format!("{}.bson", name));
+            let file = File::create(&bson_path)
+                .await
+                .map_err(|e| io_error("create", &bson_path, e))?;
+            let mut writer = BufWriter::new(file);
+            let collection = db.collection::<RawDocumentBuf>(&name);
+            // The estimate comes from metadata, so it is cheap even when huge
+            let total = collection.estimated_document_count().await.ok();
+            let tracker =
+                ProgressTracker::for_terminal(&name, total, ProgressUnit::Documents, accessible);
+            let mut cursor = collection.find(doc! {}).await?;
+            while let Some(raw) = cursor.try_next().await? {
+                writer
+                    .write_all(raw.as_bytes())
+                    .await
+                    .map_err(|e| io_error("write", &bson_path, e))?;
+                documents += 1;
+                tracker.update(documents);
+            }
+            tracker.finish();
+            writer
+                .flush()
+                .await
+                .map_err(|e| io_error("write", &bson_path, e))?;
+        }
+
+        entries.push(BackupEntry {
+            collection: name,
+            documents,
+            indexes: count_secondary_indexes(&indexes),
+            skipped: 0,
+        });
+    }
+
+    Ok(entries)
+}
+
+/// Restore a dump directory into a database
+///
+/// Collections are created with their dumped options before their documents
+/// are inserted and their indexes rebuilt; views are created last. Documents
+/// whose `_id` already exists are skipped, as `mongorestore` does.
+///
+/// # Arguments
+/// * `db` - Database to restore into
+/// * `dir` - Directory holding `.bson`/`.metadata.json` files
+/// * `collections` - Collections to restore; all when empty
+/// * `drop` - Drop each collection before restoring it
+/// * `accessible` - Whether accessible mode is on, which hides progress bars
+///
+/// # Returns
+/// * `Result<Vec<BackupEntry>>` - One entry per restored collection
+pub(super) async fn restore_database(
+    db: &Database,
+    dir: &Path,
+    collections: &[String],
+    drop: bool,
+    accessible: bool,
+) -> Result<Vec<BackupEntry>> {
+    let mut names = dump_collections(dir).await?;
+    if !collections.is_empty() {
+        names.retain(|name| collections.contains(name));
+    }
+    if names.is_empty() {
+        return Err(MongoshError::Generic(format!(
+            "No collections to restore in '{}'",
+            dir.display()
+        )));
+    }
+
+    let mut planned = Vec::new();
+    for name in names {
+        let metadata = read_metadata(&dir.join(format!("{}.metadata.json", name))).await?;
+        planned.push((name, metadata));
+    }
+    // Views may be defined on collections restored in the same run
+    planned.sort_by_key(|(_, metadata)| is_view(metadata.as_ref()));
+
+    let mut entries = Vec::new();
+    for (name, metadata) in planned {
+        info!("Restoring {}.{}", db.name(), name);
+        if drop {
+            db.collection::<Document>(&name).drop().await?;
+        }
+
+        if let Some(metadata) = &metadata {
+            create_collection(db, &name, metadata).await?;
+        }
+
+        let mut entry = BackupEntry {
+            collection: name.clone(),
+            documents: 0,
+            indexes: 0,
+            skipped: 0,
+        };
+
+        let bson_path = dir.join(format!("{}.bson", name));
+        if is_file(&bson_path).await {
+            let file = File::open(&bson_path)
+                .await
+                .map_err(|e| io_error("open", &bson_path, e))?;
+            // Progress is measured in bytes read, since the file has no count
+            let size = file.metadata().await.map(|m| m.len()).ok();
+            let tracker =
+                ProgressTracker::for_terminal(&name, size, ProgressUnit::Bytes, accessible);
+            let mut reader = BufReader::new(file);
+            let collection = db.collection::<Document>(&name);
+            let mut batch = Vec::with_capacity(RESTORE_BATCH_SIZE);
+            let mut position = 0;
+            loop {
+                let document = read_document(&mut reader)
+                    .await
+                    .map_err(|e| io_error("read", &bson_path, e))?;
+                let done = document.is_none();
+                if let Some((document, size)) = document {
+                    position += size as u64;
+                    batch.push(document);
+                }
+                if batch.len() == RESTORE_BATCH_SIZE || (done && !batch.is_empty()) {
+                    let (inserted, skipped) =
+                        insert_batch(&collection, std::mem::take(&mut batch)).await?;
+                    entry.documents += inserted;
+                    entry.skipped += skipped;
+                    tracker.update(position);
+                }
+                if done {
+                    break;
+                }
+            }
+            tracker.finish();
+        }
+
+        if let Some(metadata) = &metadata {
+            entry.indexes = create_indexes(db, &name, metadata).await?;
+        }
+        entries.push(entry);
+    }
+
+    Ok(entries)
+}
+
+/// Pick the folder to restore from
+///
+/// A dump root written by `dump` holds one folder per database; when it has
+/// one for `db_name` that folder is used, otherwise `dir` itself.
+///
+/// # Arguments
+/// * `dir` - Directory given by the user
+/// * `db_name` - Database being restored into
+///
+/// # Returns
+/// * `PathBuf` - Directory holding the collection files
+pub(super) async fn restore_source(dir: &Path, db_name: &str) -> PathBuf {
+    let nested = dir.join(db_name);
+    if fs::metadata(&nested).await.is_ok_and(|m| m.is_dir()) {
+        nested
+    } else {
+        dir.to_path_buf()
+    }
+}
+
+/// Render dump or restore results, one line per collection
+///
+/// # Arguments
+/// * `action` - "Dumped" or "Restored"
+/// * `entries` - Per-collection outcomes
+/// * `location` - Directory written to or read from
+///
+/// # Returns
+/// * `String` - Summary text
+pub(super) fn summarize(action: &str, entries: &[BackupEntry], location: &Path) -> String {
+    let width = entries
+        .iter()
+        .map(|entry| entry.collection.len())
+        .max()
+        .unwrap_or(0);
+    let mut lines: Vec<String> = entries
+        .iter()
+        .map(|entry| {
+            let mut line = format!(
+                "{:<width$}  {} document(s), {} index(es)",
+                entry.collection, entry.documents, entry.indexes
+            );
+            if entry.skipped > 0 {
+                line.push_str(&format!(", {} duplicate(s) skipped", entry.skipped));
+            }
+            line
+        })
+        .collect();
+    let total: u64 = entries.iter().map(|entry| entry.documents).sum();
+    lines.push(format!(
+        "{} {} collection(s), {} document(s) {} {}",
+        action,
+        entries.len(),
+        total,
+        if action == "Dumped" { "to" } else { "from" },
+        location.display()
+    ));
+    lines.join("\n")
+}
+
+/// Build the `.metadata.json` content of a collection
+fn build_metadata(name: &str, kind: &str, options: Document, indexes: &[Document]) -> Document {
+    doc! {
+        "indexes": indexes.iter().cloned().map(Bson::Document).collect::<Vec<_>>(),
+        "collectionName": name,
+        "type": kind,
+        "options": options,
+    }
+}
+
+/// Serialize metadata as canonical extended JSON, like mongodump
+fn metadata_json(metadata: Document) -> Result<String> {
+    serde_json::to_string(&Bson::Document(metadata).into_canonical_extjson())
+        .map_err(|e| MongoshError::Generic(format!("Failed to serialize metadata: {}", e)))
+}
+
+/// Read a `.metadata.json` file, if present
+async fn read_metadata(path: &Path) -> Result<Option<Document>> {
+    if !is_file(path).await {
+        return Ok(None);
+    }
+    let content = fs::read_to_string(path)
+        .await
+        .map_err(|e| io_error("read", path, e))?;
+    parse_metadata(&content).map(Some).map_err(|e| {
+        MongoshError::Generic(format!("Invalid metadata in '{}': {}", path.display(), e))
+    })
+}
+
+/// Parse extended JSON metadata into a document
+fn parse_metadata(content: &str) -> std::result::Result<Document, String> {
+    let value: serde_json::Value = serde_json::from_str(content).map_err(|e| e.to_string())?;
+    match Bson::try_from(value).map_err(|e| e.to_string())? {
+        Bson::Document(document) => Ok(document),
+        _ => Err("expected a JSON object".to_string()),
+    }
+}
+
+/// Check whether metadata describes a view
+fn is_view(metadata: Option<&Document>) -> bool {
+    metadata.is_some_and(|metadata| {
+        metadata.get_str("type").is_ok_and(|kind| kind == "view")
+            || metadata
+                .get_document("options")
+                .is_ok_and(|options| options.contains_key("viewOn"))
+    })
+}
+
+/// Collection names found in a dump folder, sorted
+async fn dump_collections(dir: &Path) -> Result<Vec<String>> {
+    let mut entries = fs::read_dir(dir)
+        .await
+        .map_err(|e| io_error("read", dir, e))?;
+    let mut names = Vec::new();
+    while let Some(entry) = entries
+        .next_entry()
+        .await
+        .map_err(|e| io_error("read", dir, e))?
+    {
+        let file_name = entry.file_name().to_string_lossy().into_owned();
+        if let Some(name) = collection_from_file_name(&file_name).filter(|name| !is_system(name)) {
+            names.push(name.to_string());
+        }
+    }
+    names.sort();
+    names.dedup();
+    Ok(names)
+}
+
+/// Collection name of a dump file (`orders.bson`, `orders.metadata.json`)
+fn collection_from_file_name(file_name: &str) -> Option<&str> {
+    file_name
+        .strip_suffix(".metadata.json")
+        .or_else(|| file_name.strip_suffix(".bson"))
+        .filter(|name| !name.is_empty())
+}
+
+/// Internal collections (`system.*`) are neither dumped nor restored
+fn is_system(name: &str) -> bool {
+    name.starts_with("system.")
+}
+
+/// Number of indexes besides the default `_id_` index
+fn count_secondary_indexes(indexes: &[Document]) -> usize {
+    indexes
+        .iter()
+        .filter(|index| index.get_str("name").is_ok_and(|name| name != "_id_"))
+        .count()
+}
+
+/// Check whether a path is a regular file
+async fn is_file(path: &Path) -> bool {
+    fs::metadata(path).await.is_ok_and(|m| m.is_file())
+}
+
+/// Read the next document of a `.bson` file
+///
+/// # Returns
+/// * `io::Result<Option<(Document, usize)>>` - The document and its size in
+///   bytes, or `None` at end of file
+async fn read_document(
+    reader: &mut (impl AsyncRead + Unpin),
+) -> io::Result<Option<(Document, usize)>> {
+    let mut length = [0u8; 4];
+    match reader.read_exact(&mut length).await {
+        Ok(_) => {}
+        Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(e),
+    }
+
+    let size = i32::from_le_bytes(length);
+    if size < 5 || size as usize > MAX_DOCUMENT_SIZE {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("invalid BSON document length {}", size),
+        ));
+    }
+
+    let mut bytes = vec![0u8; size as usize];
+    bytes[..4].copy_from_slice(&length);
+    reader.read_exact(&mut bytes[4..]).await?;
+    Document::from_reader(bytes.as_slice())
+        .map(|document| Some((document, bytes.len())))
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// Create a collection or view with its dumped options
+async fn create_collection(db: &Database, name: &str, metadata: &Document) -> Result<()> {
+    let options = metadata
+        .get_document("options")
+        .cloned()
+        .unwrap_or_default();
+    if options.is_empty() {
+        return Ok(());
+    }
+
+    let mut command = doc! { "create": name };
+    command.extend(options);
+    match db.run_command(command).await {
+        Ok(_) => Ok(()),
+        Err(e) if matches!(e.kind.as_ref(), ErrorKind::Command(c) if c.code == NAMESPACE_EXISTS) => {
+            Ok(())
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Rebuild the dumped indexes of a collection
+///
+/// # Returns
+/// * `Result<usize>` - Number of indexes sent, without `_id_`
+async fn create_indexes(db: &Database, name: &str, metadata: &Document) -> Result<usize> {
+    let indexes: Vec<Bson> = metadata
+        .get_array("indexes")
+        .map(|indexes| {
+            indexes
+                .iter()
+                .filter_map(Bson::as_document)
+                .filter(|index| index.get_str("name").is_ok_and(|name| name != "_id_"))
+                .map(|index| {
+                    let mut index = index.clone();
+                    index.remove("ns");
+                    Bson::Document(index)
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    if indexes.is_empty() {
+        return Ok(0);
+    }
+
+    let count = indexes.len();
+    db.run_command(doc! { "createIndexes": name, "indexes": indexes })
+        .await?;
+    Ok(count)
+}
+
+/// Insert a batch, skipping documents whose `_id` already exists
+///
+/// # Returns
+/// * `Result<(u64, u64)>` - Documents inserted and skipped
+pub(super) async fn insert_batch(
+    collection: &mongodb::Collection<Document>,
+    batch: Vec<Document>,
+) -> Result<(u64, u64)> {
+    let total = batch.len() as u64;
+    match collection.insert_many(batch).ordered(false).await {
+        Ok(_) => Ok((total, 0)),
+        Err(e) => match e.kind.as_ref() {
+            ErrorKind::InsertMany(insert_error)
+                if insert_error.write_concern_error.is_none()
+                    && insert_error.write_errors.as_ref().is_some_and(|errors| {
+                        errors.iter().all(|error| error.code == DUPLICATE_KEY)
+                    }) =>
+            {
+                let skipped = insert_error
+                    .write_errors
+                    .as_ref()
+                    .map_or(0, |errors| errors.len() as u64);
+                Ok((total - skipped, skipped))
+            }
+            _ => Err(e.into()),
+        },
+    }
+}
+
+/// Error for a failed file operation
+fn io_error(action: &str, path: &Path, error: io::Error) -> MongoshError {
+    MongoshError::Generic(format!(
+        "Failed to {} '{}': {}",
+        action,
+        path.display(),
+        error
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_read_document() {
+        let mut bytes = Vec::new();
+        for i in 0..3 {
+            doc! { "_id": i, "name": "x" }
+                .to_writer(&mut bytes)
+                .unwrap();
+        }
+
+        let mut reader = bytes.as_slice();
+        for i in 0..3 {
+            let (document, size) = read_document(&mut reader).await.unwrap().unwrap();
+            assert_eq!(document, doc! { "_id": i, "name": "x" });
+            assert_eq!(size, bytes.len() / 3);
+        }
+        assert!(read_document(&mut reader).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_read_document_rejects_bad_length() {
+        let bytes = [1u8, 0, 0, 0, 0];
+        assert!(read_document(&mut bytes.as_slice()).await.is_err());
+    }
+
+    #[test]
+    fn test_metadata_round_trip() {
+        let indexes = vec![
+            doc! { "v": 2, "key": { "_id": 1 }, "name": "_id_" },
+            doc! { "v": 2, "key": { "email": 1 }, "name": "email_1", "unique": true },
+        ];
+        let metadata = build_metadata("users", "collection", doc! { "capped": false }, &indexes);
+        let json = metadata_json(metadata.clone()).unwrap();
+        assert!(json.contains(r#""v":{"$numberInt":"2"}"#));
+        assert_eq!(parse_metadata(&json).unwrap(), metadata);
+        assert_eq!(count_secondary_indexes(&indexes), 1);
+    }
+
+    #[test]
+    fn test_is_view() {
+        let view = doc! { "type": "view", "options": { "viewOn": "users", "pipeline": [] } };
+        assert!(is_view(Some(&view)));
+        assert!(!is_view(Some(
+            &doc! { "type": "collection", "options": {} }
+        )));
+        assert!(!is_view(None));
+    }
+
+    #[test]
+    fn test_collection_from_file_name() {
+        assert_eq!(collection_from_file_name("orders.bson"), Some("orders"));
+        assert_eq!(
+            collection_from_file_name("orders.items.metadata.json"),
+            Some("orders.items")
+        );
+        assert_eq!(collection_from_file_name("notes.txt"), None);
+        assert_eq!(collection_from_file_name(".bson"), None);
+    }
+
+    #[tokio::test]
+    async fn test_dump_collections_and_restore_source() {
+        let dir = std::env::temp_dir().join(format!("mongosh-dump-{}", std::process::id()));
+        let db_dir = dir.join("shop");
+        std::fs::create_dir_all(&db_dir).unwrap();
+        for file in [
+            "orders.bson",
+            "orders.metadata.json",
+            "active.metadata.json",
+            "system.views.bson",
+        ] {
+            std::fs::write(db_dir.join(file), b"").unwrap();
+        }
+
+        assert_eq!(restore_source(&dir, "shop").await, db_dir);
+        assert_eq!(restore_source(&db_dir, "shop").await, db_dir);
+        assert_eq!(
+            dump_collections(&db_dir).await.unwrap(),
+            vec!["active".to_string(), "orders".to_string()]
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_summarize() {
+        let entries = vec![
+            BackupEntry {
+                collection: "orders".to_string(),
+                documents: 120,
+                indexes: 2,
+                skipped: 0,
+            },
+            BackupEntry {
+                collection: "users".to_string(),
+                documents: 8,
+                indexes: 1,
+                skipped: 3,
+            },
+        ];
+        assert_eq!(
+            summarize("Restored", &entries, Path::new("dump/shop")),
+            "orders  120 document(s), 2 index(es)\n\
+             users   8 document(s), 1 index(es), 3 duplicate(s) skipped\n\
+             Restored 2 collection(s), 128 document(s) from dump/shop"
+        );
+    }
+}
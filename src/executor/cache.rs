@@ -0,0 +1,258 @@
+//! Session cache of read results
+//!
+//! With `query.result_cache` on (or after `cache on`), the results of reads
+//! such as `find`, `countDocuments`, `distinct` and `aggregate` are kept in
+//! memory, keyed by the database and the parsed command, so re-running the
+//! same query returns the kept result without a round trip. Entries expire
+//! after `query.result_cache_ttl` seconds, the least recently used entry is
+//! dropped when `query.result_cache_size` is reached, and any write run in the
+//! session clears the cache. Changes made by other clients show up once the
+//! entry expires.
+
+use std::time::{Duration, Instant};
+
+use indexmap::IndexMap;
+
+use super::result::{ExecutionResult, ExecutionStats, ResultData};
+use crate::parser::{Command, QueryCommand};
+
+/// A kept result
+struct CacheEntry {
+    data: ResultData,
+    stats: ExecutionStats,
+    stored: Instant,
+}
+
+/// Hit and size counters shown by `cache stats`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    /// Entries currently kept
+    pub entries: usize,
+    /// Lookups answered from the cache
+    pub hits: u64,
+    /// Lookups that ran the command
+    pub misses: u64,
+    /// Entries dropped to stay within the size limit
+    pub evictions: u64,
+}
+
+/// LRU cache of read results with a time to live
+#[derive(Default)]
+pub struct ResultCache {
+    /// Entries from least to most recently used
+    entries: IndexMap<String, CacheEntry>,
+    stats: CacheStats,
+}
+
+impl std::fmt::Debug for ResultCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ResultCache")
+            .field("entries", &self.entries.len())
+            .field("stats", &self.stats)
+            .finish()
+    }
+}
+
+impl ResultCache {
+    /// Look up a kept result
+    ///
+    /// # Arguments
+    /// * `key` - Key from [`cache_key`]
+    /// * `ttl` - Age after which an entry is stale
+    ///
+    /// # Returns
+    /// * `Option<ExecutionResult>` - The kept result, if fresh
+    pub fn get(&mut self, key: &str, ttl: Duration) -> Option<ExecutionResult> {
+        let fresh = self
+            .entries
+            .get(key)
+            .is_some_and(|entry| entry.stored.elapsed() < ttl);
+        if !fresh {
+            self.entries.shift_remove(key);
+            self.stats.misses += 1;
+            return None;
+        }
+
+        // Re-insert the entry at the most recently used end
+        let entry = self.entries.shift_remove(key)?;
+        self.stats.hits += 1;
+        let result = ExecutionResult::success(entry.data.clone(), entry.stats.clone());
+        self.entries.insert(key.to_string(), entry);
+        Some(result)
+    }
+
+    /// Keep a result, dropping the least recently used entries over `capacity`
+    ///
+    /// # Arguments
+    /// * `key` - Key from [`cache_key`]
+    /// * `result` - Successful result of the command
+    /// * `capacity` - Most entries to keep; 0 keeps nothing
+    pub fn insert(&mut self, key: String, result: &ExecutionResult, capacity: usize) {
+        if !is_cacheable(result) || capacity == 0 {
+            return;
+        }
+
+        self.entries.shift_remove(&key);
+        while self.entries.len() >= capacity {
+            self.entries.shift_remove_index(0);
+            self.stats.evictions += 1;
+        }
+        self.entries.insert(
+            key,
+            CacheEntry {
+                data: result.data.clone(),
+                stats: result.stats.clone(),
+                stored: Instant::now(),
+            },
+        );
+    }
+
+    /// Drop every entry, keeping the counters
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Current counters
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            entries: self.entries.len(),
+            ..self.stats
+        }
+    }
+}
+
+/// Key for a command whose result may be cached
+///
+/// Datasources often share database names, so the key names both.
+///
+/// # Arguments
+/// * `datasource` - Current datasource
+/// * `database` - Current database
+/// * `command` - Parsed command
+///
+/// # Returns
+/// * `Option<String>` - Key for reads, None for anything else
+pub fn cache_key(datasource: &str, database: &str, command: &Command) -> Option<String> {
+    let Command::Query(query) = command else {
+        return None;
+    };
+    let cacheable = match query {
+        QueryCommand::Find { .. }
+        | QueryCommand::FindOne { .. }
+        | QueryCommand::CountDocuments { .. }
+        | QueryCommand::EstimatedDocumentCount { .. }
+        | QueryCommand::Distinct { .. } => true,
+        QueryCommand::Aggregate { pipeline, .. } => !pipeline
+            .iter()
+            .any(|stage| stage.contains_key("$out") || stage.contains_key("$merge")),
+        _ => false,
+    };
+    // The parsed command is the same however the query was spaced or quoted
+    cacheable.then(|| format!("{}\n{}\n{:?}", datasource, database, query))
+}
+
+/// Whether a result can be replayed later
+///
+/// A find with more results than the first batch is not kept, since `it`
+/// pages through the server cursor of the run that produced it.
+fn is_cacheable(result: &ExecutionResult) -> bool {
+    result.success
+        && match &result.data {
+            ResultData::DocumentsWithPagination { has_more, .. } => !has_more,
+            ResultData::Stream(_) | ResultData::Message(_) => false,
+            _ => true,
+        }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::FindOptions;
+    use mongodb::bson::doc;
+
+    fn count(n: u64) -> ExecutionResult {
+        ExecutionResult::success(ResultData::Count(n), ExecutionStats::default())
+    }
+
+    fn cached_count(cache: &mut ResultCache, key: &str) -> Option<u64> {
+        match cache.get(key, Duration::from_secs(60))?.data {
+            ResultData::Count(n) => Some(n),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_result_cache_lru() {
+        let mut cache = ResultCache::default();
+        cache.insert("a".to_string(), &count(1), 2);
+        cache.insert("b".to_string(), &count(2), 2);
+        assert_eq!(cached_count(&mut cache, "a"), Some(1));
+
+        // "b" is now the least recently used
+        cache.insert("c".to_string(), &count(3), 2);
+        assert_eq!(cached_count(&mut cache, "b"), None);
+        assert_eq!(cached_count(&mut cache, "c"), Some(3));
+        assert_eq!(
+            cache.stats(),
+            CacheStats {
+                entries: 2,
+                hits: 2,
+                misses: 1,
+                evictions: 1,
+            }
+        );
+
+        cache.clear();
+        assert_eq!(cache.stats().entries, 0);
+    }
+
+    #[test]
+    fn test_result_cache_ttl_and_cacheable() {
+        let mut cache = ResultCache::default();
+        cache.insert("a".to_string(), &count(1), 10);
+        assert!(cache.get("a", Duration::ZERO).is_none());
+        assert_eq!(cache.stats().entries, 0);
+
+        let paged = ExecutionResult::success(
+            ResultData::DocumentsWithPagination {
+                documents: vec![doc! { "a": 1 }],
+                has_more: true,
+                displayed: 1,
+            },
+            ExecutionStats::default(),
+        );
+        cache.insert("b".to_string(), &paged, 10);
+        cache.insert(
+            "c".to_string(),
+            &ExecutionResult::error("x".to_string()),
+            10,
+        );
+        assert_eq!(cache.stats().entries, 0);
+    }
+
+    #[test]
+    fn test_cache_key() {
+        let find = Command::Query(QueryCommand::Find {
+            collection: "users".to_string(),
+            filter: doc! { "age": { "$gt": 30 } },
+            options: FindOptions::default(),
+        });
+        let key = cache_key("prod", "shop", &find).unwrap();
+        assert!(key.starts_with("prod\nshop\n"));
+        assert_ne!(cache_key("prod", "test", &find), Some(key.clone()));
+        assert_ne!(cache_key("staging", "shop", &find), Some(key));
+
+        let merge = Command::Query(QueryCommand::Aggregate {
+            collection: "users".to_string(),
+            pipeline: vec![doc! { "$merge": "copy" }],
+            options: Default::default(),
+        });
+        assert!(cache_key("prod", "shop", &merge).is_none());
+
+        let delete = Command::Query(QueryCommand::DeleteMany {
+            collection: "users".to_string(),
+            filter: doc! {},
+        });
+        assert!(cache_key("prod", "shop", &delete).is_none());
+    }
+}
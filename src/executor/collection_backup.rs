@@ -0,0 +1,243 @@
+//! Backup copies taken before drop() and renameCollection()
+//!
+//! With `safety.backup_before_drop` (or `{ backup: true }` on the call) a
+//! collection is copied to `<name>.__backup_YYYYMMDD` with an `$out`
+//! aggregation before it is dropped or renamed over. Only documents are
+//! copied, not indexes or collection options. A second backup of the same
+//! collection on the same day gets a `_HHMMSS` suffix instead of replacing
+//! the first one.
+//!
+//! `backups` lists the copies of the current database and `backups cleanup`
+//! drops the ones older than `safety.backup_retention_days`.
+
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use mongodb::Database;
+use mongodb::bson::{Document, doc};
+use tracing::info;
+
+use crate::error::Result;
+
+/// Separator between the collection name and the date of a backup
+const BACKUP_MARKER: &str = ".__backup_";
+
+/// A backup collection and when it was taken
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct Backup {
+    /// Name of the backup collection
+    pub name: String,
+    /// Collection the backup was taken of
+    pub source: String,
+    /// Time the backup was taken, to the day without a time suffix
+    pub taken_at: NaiveDateTime,
+}
+
+/// Copy a collection to a dated backup collection
+///
+/// # Arguments
+/// * `db` - Database of the collection
+/// * `collection` - Collection to copy
+///
+/// # Returns
+/// * `Result<Option<String>>` - Name of the backup, or None when the
+///   collection does not exist or is itself a backup
+pub(super) async fn create_backup(db: &Database, collection: &str) -> Result<Option<String>> {
+    if parse_backup_name(collection).is_some() {
+        return Ok(None);
+    }
+    let existing = db
+        .list_collection_names()
+        .filter(doc! { "name": { "$regex": format!("^{}", regex_escape(collection)) } })
+        .await?;
+    if !existing.iter().any(|name| name == collection) {
+        return Ok(None);
+    }
+
+    let now = Utc::now();
+    let mut name = backup_name(collection, now, false);
+    if existing.contains(&name) {
+        name = backup_name(collection, now, true);
+    }
+
+    db.collection::<Document>(collection)
+        .aggregate(vec![doc! { "$out": &name }])
+        .await?;
+    info!("Backed up collection '{}' to '{}'", collection, name);
+    Ok(Some(name))
+}
+
+/// List the backup collections of a database, oldest first
+///
+/// # Arguments
+/// * `db` - Database to look in
+///
+/// # Returns
+/// * `Result<Vec<Backup>>` - Backups found
+pub(super) async fn list_backups(db: &Database) -> Result<Vec<Backup>> {
+    let names = db
+        .list_collection_names()
+        .filter(doc! { "name": { "$regex": regex_escape(BACKUP_MARKER) } })
+        .await?;
+    let mut backups: Vec<Backup> = names
+        .iter()
+        .filter_map(|name| parse_backup_name(name))
+        .collect();
+    backups.sort_by(|a, b| a.taken_at.cmp(&b.taken_at).then(a.name.cmp(&b.name)));
+    Ok(backups)
+}
+
+/// Name of the backup of `collection` taken at `now`
+///
+/// # Arguments
+/// * `collection` - Collection to back up
+/// * `now` - Time of the backup
+/// * `with_time` - Add `_HHMMSS` to tell apart backups of the same day
+///
+/// # Returns
+/// * `String` - e.g. `orders.__backup_20260211`
+pub(super) fn backup_name(collection: &str, now: DateTime<Utc>, with_time: bool) -> String {
+    let pattern = if with_time { "%Y%m%d_%H%M%S" } else { "%Y%m%d" };
+    format!("{}{}{}", collection, BACKUP_MARKER, now.format(pattern))
+}
+
+/// Recognize a backup collection name
+///
+/// # Arguments
+/// * `name` - Collection name
+///
+/// # Returns
+/// * `Option<Backup>` - Source collection and time, if the name is a backup
+pub(super) fn parse_backup_name(name: &str) -> Option<Backup> {
+    let (source, stamp) = name.rsplit_once(BACKUP_MARKER)?;
+    let (date, time) = match stamp.split_once('_') {
+        Some((date, time)) => (date, NaiveTime::parse_from_str(time, "%H%M%S").ok()?),
+        None => (stamp, NaiveTime::MIN),
+    };
+    if source.is_empty() || date.len() != 8 {
+        return None;
+    }
+    let date = NaiveDate::parse_from_str(date, "%Y%m%d").ok()?;
+    Some(Backup {
+        name: name.to_string(),
+        source: source.to_string(),
+        taken_at: date.and_time(time),
+    })
+}
+
+/// Backups taken more than `older_than` seconds before `now`
+///
+/// # Arguments
+/// * `backups` - Backups to check
+/// * `now` - Current time
+/// * `older_than` - Age in seconds
+///
+/// # Returns
+/// * `Vec<String>` - Names of the expired backups
+pub(super) fn expired(backups: &[Backup], now: DateTime<Utc>, older_than: u64) -> Vec<String> {
+    let Some(cutoff) = i64::try_from(older_than)
+        .ok()
+        .and_then(Duration::try_seconds)
+        .and_then(|age| now.naive_utc().checked_sub_signed(age))
+    else {
+        return Vec::new();
+    };
+    backups
+        .iter()
+        .filter(|backup| backup.taken_at < cutoff)
+        .map(|backup| backup.name.clone())
+        .collect()
+}
+
+/// Describe the backups of a database with their age
+///
+/// # Arguments
+/// * `backups` - Backups, oldest first
+/// * `now` - Current time
+/// * `retention_days` - `safety.backup_retention_days`, to mark expired ones
+///
+/// # Returns
+/// * `String` - One line per backup
+pub(super) fn describe(backups: &[Backup], now: DateTime<Utc>, retention_days: u64) -> String {
+    if backups.is_empty() {
+        return "No backups in this database".to_string();
+    }
+    let expired = expired(backups, now, retention_days.saturating_mul(86_400));
+    let width = backups.iter().map(|b| b.name.len()).max().unwrap_or(0);
+    backups
+        .iter()
+        .map(|backup| {
+            let age = (now.naive_utc() - backup.taken_at).num_days().max(0);
+            let mut line = format!(
+                "{:<width$}  of {}, {} day(s) old",
+                backup.name,
+                backup.source,
+                age,
+                width = width
+            );
+            if expired.contains(&backup.name) {
+                line.push_str(" (expired)");
+            }
+            line
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Escape a collection name for use in a `$regex`
+fn regex_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if "\\^$.|?*+()[]{}".contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_backup_name_round_trip() {
+        let now = Utc.with_ymd_and_hms(2026, 2, 11, 9, 30, 5).unwrap();
+        assert_eq!(
+            backup_name("orders", now, false),
+            "orders.__backup_20260211"
+        );
+
+        let backup = parse_backup_name(&backup_name("app.orders", now, true)).unwrap();
+        assert_eq!(backup.name, "app.orders.__backup_20260211_093005");
+        assert_eq!(backup.source, "app.orders");
+        assert_eq!(backup.taken_at, now.naive_utc());
+
+        assert!(parse_backup_name("orders").is_none());
+        assert!(parse_backup_name("orders.__backup_2026").is_none());
+        assert!(parse_backup_name(".__backup_20260211").is_none());
+    }
+
+    #[test]
+    fn test_expired_backups() {
+        let backups: Vec<Backup> = ["a.__backup_20260201", "a.__backup_20260210_120000"]
+            .iter()
+            .filter_map(|name| parse_backup_name(name))
+            .collect();
+        let now = Utc.with_ymd_and_hms(2026, 2, 11, 0, 0, 0).unwrap();
+
+        assert_eq!(
+            expired(&backups, now, 7 * 86_400),
+            vec!["a.__backup_20260201"]
+        );
+        assert_eq!(expired(&backups, now, 3600).len(), 2);
+
+        let listing = describe(&backups, now, 7);
+        assert!(listing.starts_with("a.__backup_20260201         of a, 10 day(s) old (expired)"));
+        assert!(listing.ends_with("of a, 0 day(s) old"));
+    }
+
+    #[test]
+    fn test_regex_escape() {
+        assert_eq!(regex_escape("a.b$c"), "a\\.b\\$c");
+    }
+}
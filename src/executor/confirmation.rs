@@ -2,11 +2,18 @@
 //!
 //! This module provides functionality to prompt users for confirmation
 //! before executing potentially dangerous operations like delete, update, or drop.
+//! Bulk drops resolve their targets first and let the user pick a subset.
+//! Contexts without a terminal refuse instead of reading stdin, see
+//! [`ConfirmationMode`].
 
 use std::io::{self, Write};
 
-use crate::error::{MongoshError, Result};
-use crate::parser::{AdminCommand, QueryCommand};
+use mongodb::bson::Document;
+
+use super::context::ConfirmationMode;
+use crate::cli::prompt;
+use crate::error::{ExecutionError, MongoshError, Result};
+use crate::parser::{AdminCommand, Command, QueryCommand, UtilityCommand};
 
 /// Check if a query command is dangerous and requires confirmation
 pub fn is_dangerous_query(cmd: &QueryCommand) -> bool {
@@ -16,6 +23,7 @@ pub fn is_dangerous_query(cmd: &QueryCommand) -> bool {
             | QueryCommand::DeleteMany { .. }
             | QueryCommand::UpdateOne { .. }
             | QueryCommand::UpdateMany { .. }
+            | QueryCommand::BulkUpdateWithRateLimit { .. }
             | QueryCommand::ReplaceOne { .. }
             | QueryCommand::FindOneAndDelete { .. }
             | QueryCommand::FindOneAndUpdate { .. }
@@ -24,16 +32,124 @@ pub fn is_dangerous_query(cmd: &QueryCommand) -> bool {
     )
 }
 
+/// Collection and filter of a write whose matches are counted up front
+///
+/// Only updateMany and deleteMany with a non-empty filter are previewed;
+/// an empty filter matches the whole collection anyway.
+///
+/// # Arguments
+/// * `cmd` - Query command about to run
+///
+/// # Returns
+/// * `Option<(&str, &Document)>` - Collection and filter to count, if any
+pub fn preview_target(cmd: &QueryCommand) -> Option<(&str, &Document)> {
+    match cmd {
+        QueryCommand::UpdateMany {
+            collection, filter, ..
+        }
+        | QueryCommand::DeleteMany { collection, filter } => {
+            (!filter.is_empty()).then_some((collection.as_str(), filter))
+        }
+        _ => None,
+    }
+}
+
+/// Server commands that only read state and can run without confirmation
+/// when passed through `db.runCommand()` or `db.adminCommand()`
+const READ_ONLY_COMMANDS: &[&str] = &[
+    "buildInfo",
+    "collStats",
+    "connectionStatus",
+    "count",
+    "currentOp",
+    "dataSize",
+    "dbStats",
+    "distinct",
+    "explain",
+    "find",
+    "getCmdLineOpts",
+    "getLog",
+    "getParameter",
+    "hello",
+    "hostInfo",
+    "isMaster",
+    "listCollections",
+    "listCommands",
+    "listDatabases",
+    "listIndexes",
+    "ping",
+    "replSetGetConfig",
+    "replSetGetStatus",
+    "serverStatus",
+    "top",
+    "validate",
+    "whatsmyuri",
+];
+
+/// Check if a server command name only reads state
+///
+/// # Arguments
+/// * `name` - Command name, e.g. `serverStatus`
+///
+/// # Returns
+/// * `bool` - True if the command is known not to modify anything
+pub fn is_read_only_command(name: &str) -> bool {
+    READ_ONLY_COMMANDS.contains(&name)
+}
+
 /// Check if an admin command is dangerous and requires confirmation
 pub fn is_dangerous_admin(cmd: &AdminCommand) -> bool {
+    if let AdminCommand::RunCommand { command, .. } = cmd {
+        return command
+            .keys()
+            .next()
+            .is_none_or(|name| !is_read_only_command(name));
+    }
+
     matches!(
         cmd,
         AdminCommand::CreateIndex { .. }
             | AdminCommand::CreateIndexes { .. }
             | AdminCommand::DropIndex { .. }
             | AdminCommand::DropIndexes { .. }
-            | AdminCommand::DropCollection(..)
+            | AdminCommand::DropCollection { .. }
+            | AdminCommand::DropCollections { .. }
+            | AdminCommand::CleanupBackups { .. }
             | AdminCommand::RenameCollection { .. }
+            | AdminCommand::SetValidator { .. }
+            | AdminCommand::CollMod { .. }
+            | AdminCommand::ConvertToCapped { .. }
+            | AdminCommand::Restore { drop: true, .. }
+    )
+}
+
+/// Check if running a command asks for confirmation somewhere
+///
+/// Looks through pipes, `getSiblingDB`, `watch` and nested `parallel()`
+/// tasks, so a batch can be confirmed once before its tasks start.
+pub fn needs_confirmation(cmd: &Command) -> bool {
+    match cmd {
+        Command::Query(query) => is_dangerous_query(query),
+        Command::Admin(admin) => is_dangerous_admin(admin),
+        Command::Pipe(base, _) => needs_confirmation(base),
+        Command::WithDatabase { command, .. }
+        | Command::Watch { command, .. }
+        | Command::Utility(UtilityCommand::Inspect { command, .. }) => needs_confirmation(command),
+        Command::Parallel { commands, .. } => commands.iter().any(needs_confirmation),
+        _ => false,
+    }
+}
+
+/// Check if an admin command acts on several targets the user picks from
+///
+/// These commands are confirmed by `select_targets` once the executor has
+/// resolved what they match, instead of by a yes/no prompt up front.
+pub fn selects_targets(cmd: &AdminCommand) -> bool {
+    matches!(
+        cmd,
+        AdminCommand::DropCollections { .. }
+            | AdminCommand::DropIndexes { .. }
+            | AdminCommand::CleanupBackups { .. }
     )
 }
 
@@ -45,7 +161,18 @@ pub fn is_dangerous_admin(cmd: &AdminCommand) -> bool {
 /// # Returns
 /// * `Result<bool>` - True if user confirmed, false if cancelled, error on I/O failure
 pub fn prompt_confirmation() -> Result<bool> {
-    println!("⚠️ Dangerous operation! Continue? (yes/no): ");
+    prompt_confirmation_for(None)
+}
+
+/// Prompt user for confirmation, naming how many documents are affected
+///
+/// # Arguments
+/// * `affected` - Documents matched by the operation, if they were counted
+///
+/// # Returns
+/// * `Result<bool>` - True if user confirmed, false if cancelled, error on I/O failure
+pub fn prompt_confirmation_for(affected: Option<u64>) -> Result<bool> {
+    println!("{}", confirmation_message(affected));
     io::stdout()
         .flush()
         .map_err(|e| MongoshError::Generic(format!("Failed to flush stdout: {}", e)))?;
@@ -59,32 +186,117 @@ pub fn prompt_confirmation() -> Result<bool> {
     Ok(matches!(input.as_str(), "yes" | "y"))
 }
 
+/// Text of the confirmation prompt
+fn confirmation_message(affected: Option<u64>) -> String {
+    match affected {
+        Some(count) => format!(
+            "This will affect ~{} document(s)\n⚠️ Dangerous operation on ~{} document(s)! Continue? (yes/no): ",
+            count, count
+        ),
+        None => "⚠️ Dangerous operation! Continue? (yes/no): ".to_string(),
+    }
+}
+
+/// Confirm a dangerous operation the way the context asks for
+///
+/// # Arguments
+/// * `mode` - Whether to ask, assume consent or refuse
+/// * `affected` - Documents matched by the operation, if they were counted
+///
+/// # Returns
+/// * `Result<bool>` - True if confirmed, false if cancelled,
+///   `ConfirmationRequired` when nobody can be asked
+pub fn confirm(mode: ConfirmationMode, affected: Option<u64>) -> Result<bool> {
+    match mode {
+        ConfirmationMode::Ask => prompt_confirmation_for(affected),
+        ConfirmationMode::Confirmed => Ok(true),
+        ConfirmationMode::Refuse => Err(ExecutionError::ConfirmationRequired(
+            "This operation needs confirmation, but there is no terminal to ask".to_string(),
+        )
+        .into()),
+    }
+}
+
 /// Confirm a dangerous query operation
 ///
 /// # Arguments
 /// * `cmd` - Query command to check and confirm
+/// * `affected` - Documents the operation matches, from `safety.preview_counts`
+/// * `mode` - Whether to ask, assume consent or refuse
 ///
 /// # Returns
 /// * `Result<bool>` - True if confirmed or not dangerous, false if cancelled
-pub fn confirm_query_operation(cmd: &QueryCommand) -> Result<bool> {
+pub fn confirm_query_operation(
+    cmd: &QueryCommand,
+    affected: Option<u64>,
+    mode: ConfirmationMode,
+) -> Result<bool> {
     if !is_dangerous_query(cmd) {
         return Ok(true);
     }
-    prompt_confirmation()
+    confirm(mode, affected)
 }
 
 /// Confirm a dangerous admin operation
 ///
 /// # Arguments
 /// * `cmd` - Admin command to check and confirm
+/// * `mode` - Whether to ask, assume consent or refuse
 ///
 /// # Returns
 /// * `Result<bool>` - True if confirmed or not dangerous, false if cancelled
-pub fn confirm_admin_operation(cmd: &AdminCommand) -> Result<bool> {
-    if !is_dangerous_admin(cmd) {
+pub fn confirm_admin_operation(cmd: &AdminCommand, mode: ConfirmationMode) -> Result<bool> {
+    if !is_dangerous_admin(cmd) || selects_targets(cmd) {
         return Ok(true);
     }
-    prompt_confirmation()
+    confirm(mode, None)
+}
+
+/// Let the user choose which targets of a bulk operation to act on
+///
+/// A single target gets the usual yes/no prompt; several targets are shown
+/// as a checkbox list so any subset can be picked. A confirmed context acts
+/// on all targets.
+///
+/// # Arguments
+/// * `action` - Description of the operation, e.g. "Drop collections"
+/// * `targets` - Names matched by the operation
+/// * `mode` - Whether to ask, assume consent or refuse
+///
+/// # Returns
+/// * `Result<Vec<String>>` - Targets to act on, empty if cancelled
+pub fn select_targets(
+    action: &str,
+    targets: Vec<String>,
+    mode: ConfirmationMode,
+) -> Result<Vec<String>> {
+    if targets.is_empty() {
+        return Ok(targets);
+    }
+    if mode != ConfirmationMode::Ask {
+        return Ok(if confirm(mode, None)? { targets } else { Vec::new() });
+    }
+
+    match targets.len() {
+        1 => {
+            println!("{}: {}", action, targets[0]);
+            Ok(if prompt_confirmation()? {
+                targets
+            } else {
+                Vec::new()
+            })
+        }
+        _ => {
+            let label = format!("⚠️ {} - select the targets:", action);
+            let selected = prompt::select_many(&label, &targets)?.unwrap_or_default();
+            Ok(targets
+                .into_iter()
+                .enumerate()
+                .filter(|(i, _)| selected.contains(i))
+                .map(|(_, target)| target)
+                .collect())
+        }
+    }
 }
 
 #[cfg(test)]
@@ -108,6 +320,22 @@ mod tests {
         assert!(!is_dangerous_query(&find));
     }
 
+    #[test]
+    fn test_preview_target() {
+        let delete_many = |filter| QueryCommand::DeleteMany {
+            collection: "orders".to_string(),
+            filter,
+        };
+        let cmd = delete_many(doc! { "status": "stale" });
+        let (collection, filter) = preview_target(&cmd).unwrap();
+        assert_eq!(collection, "orders");
+        assert_eq!(filter, &doc! { "status": "stale" });
+        assert!(preview_target(&delete_many(doc! {})).is_none());
+
+        assert!(confirmation_message(Some(42)).starts_with("This will affect ~42 document(s)\n"));
+        assert!(!confirmation_message(None).contains('~'));
+    }
+
     #[test]
     fn test_is_dangerous_admin() {
         let create_index = AdminCommand::CreateIndex {
@@ -119,5 +347,103 @@ mod tests {
 
         let show_dbs = AdminCommand::ShowDatabases;
         assert!(!is_dangerous_admin(&show_dbs));
+
+        let restore = |drop| AdminCommand::Restore {
+            dir: "dump".to_string(),
+            collections: vec![],
+            drop,
+        };
+        assert!(is_dangerous_admin(&restore(true)));
+        assert!(!is_dangerous_admin(&restore(false)));
+    }
+
+    #[test]
+    fn test_bulk_drops_select_targets() {
+        let drop_many = AdminCommand::DropCollections {
+            filter: doc! { "name": { "$in": ["a", "b"] } },
+        };
+        assert!(is_dangerous_admin(&drop_many));
+        assert!(selects_targets(&drop_many));
+        assert!(confirm_admin_operation(&drop_many, ConfirmationMode::Refuse).unwrap());
+
+        let drop_one = AdminCommand::DropCollection {
+            collection: "a".to_string(),
+            backup: None,
+        };
+        assert!(!selects_targets(&drop_one));
+    }
+
+    #[test]
+    fn test_needs_confirmation() {
+        let delete = Command::Query(QueryCommand::DeleteMany {
+            collection: "orders".to_string(),
+            filter: doc! {},
+        });
+        let find = Command::Query(QueryCommand::Find {
+            collection: "orders".to_string(),
+            filter: doc! {},
+            options: Default::default(),
+        });
+        assert!(!needs_confirmation(&find));
+        assert!(needs_confirmation(&Command::WithDatabase {
+            database: "shop".to_string(),
+            command: Box::new(delete.clone()),
+        }));
+        assert!(needs_confirmation(&Command::Parallel {
+            commands: vec![find, delete],
+            concurrency: 2,
+        }));
+    }
+
+    #[test]
+    fn test_select_targets_empty() {
+        assert!(
+            select_targets("Drop collections", Vec::new(), ConfirmationMode::Refuse)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_confirmation_without_terminal() {
+        let delete = QueryCommand::DeleteMany {
+            collection: "orders".to_string(),
+            filter: doc! {},
+        };
+        let refused = confirm_query_operation(&delete, None, ConfirmationMode::Refuse);
+        assert!(matches!(
+            refused,
+            Err(MongoshError::Execution(
+                ExecutionError::ConfirmationRequired(_)
+            ))
+        ));
+        assert!(confirm_query_operation(&delete, None, ConfirmationMode::Confirmed).unwrap());
+
+        let targets = vec!["a".to_string(), "b".to_string()];
+        assert_eq!(
+            select_targets(
+                "Drop collections",
+                targets.clone(),
+                ConfirmationMode::Confirmed
+            )
+            .unwrap(),
+            targets
+        );
+        assert!(select_targets("Drop collections", targets, ConfirmationMode::Refuse).is_err());
+    }
+
+    #[test]
+    fn test_is_dangerous_run_command() {
+        let ping = AdminCommand::RunCommand {
+            command: doc! { "ping": 1 },
+            admin: true,
+        };
+        assert!(!is_dangerous_admin(&ping));
+
+        let drop = AdminCommand::RunCommand {
+            command: doc! { "dropDatabase": 1 },
+            admin: false,
+        };
+        assert!(is_dangerous_admin(&drop));
     }
 }
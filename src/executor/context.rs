@@ -7,14 +7,31 @@ use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
-use mongodb::{Client, Database};
+use mongodb::{Client, Database, options::Credential};
 use tokio_util::sync::CancellationToken;
 
+use crate::cli::prompt;
 use crate::config::ConnectionConfig;
 use crate::connection::ConnectionManager;
 use crate::error::{MongoshError, Result};
 use crate::repl::SharedState;
 
+/// How dangerous operations are confirmed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ConfirmationMode {
+    /// Ask on the terminal
+    #[default]
+    Ask,
+
+    /// Run without asking; the caller already confirmed, e.g. a whole
+    /// `parallel()` batch or an explicit `confirm` from an embedding
+    Confirmed,
+
+    /// Fail with [`ExecutionError::ConfirmationRequired`](crate::error::ExecutionError::ConfirmationRequired)
+    /// instead of reading stdin, for callers without a terminal
+    Refuse,
+}
+
 /// Execution context that maintains state across commands
 #[derive(Clone)]
 pub struct ExecutionContext {
@@ -38,6 +55,12 @@ pub struct ExecutionContext {
 
     /// Cancellation token for Ctrl+C handling
     cancel_token: CancellationToken,
+
+    /// Database used instead of the current one (set by `getSiblingDB`)
+    database_override: Option<String>,
+
+    /// How dangerous operations are confirmed
+    confirmation: ConfirmationMode,
 }
 
 impl ExecutionContext {
@@ -105,6 +128,8 @@ impl ExecutionContext {
             current_datasource: Arc::new(RwLock::new(initial_datasource)),
             client_id: Arc::new(client_id),
             cancel_token: CancellationToken::new(),
+            database_override: None,
+            confirmation: ConfirmationMode::default(),
         }
     }
 
@@ -133,7 +158,52 @@ impl ExecutionContext {
     /// # Returns
     /// * `String` - Current database name
     pub async fn get_current_database(&self) -> String {
-        self.shared_state.get_database()
+        self.database_override
+            .clone()
+            .unwrap_or_else(|| self.shared_state.get_database())
+    }
+
+    /// Create a context that runs commands against another database
+    ///
+    /// The session's current database is left unchanged.
+    ///
+    /// # Arguments
+    /// * `database` - Database name to use
+    ///
+    /// # Returns
+    /// * `Self` - Context sharing this connection but targeting `database`
+    pub fn with_database(&self, database: String) -> Self {
+        Self {
+            database_override: Some(database),
+            ..self.clone()
+        }
+    }
+
+    /// Create a context that confirms dangerous operations differently
+    ///
+    /// # Arguments
+    /// * `confirmation` - Whether to ask, assume consent or refuse
+    ///
+    /// # Returns
+    /// * `Self` - Context sharing this connection and session state
+    pub fn with_confirmation(&self, confirmation: ConfirmationMode) -> Self {
+        Self {
+            confirmation,
+            ..self.clone()
+        }
+    }
+
+    /// How dangerous operations are confirmed in this context
+    pub fn confirmation(&self) -> ConfirmationMode {
+        self.confirmation
+    }
+
+    /// Whether commands may ask questions on the terminal
+    ///
+    /// False for contexts that refuse or pre-confirm, so helpers such as
+    /// the lookup wizard or bookmark placeholders never block on stdin.
+    pub fn is_interactive(&self) -> bool {
+        self.confirmation == ConfirmationMode::Ask && prompt::is_interactive()
     }
 
     /// Set current database name
@@ -162,6 +232,43 @@ impl ExecutionContext {
     /// * `Ok(String)` - The database name that was activated
     /// * `Err(...)` - Datasource not found, or connection failed
     pub async fn switch_datasource(&self, datasource_name: &str) -> Result<String> {
+        let (new_conn, db_name) = self.open_datasource(datasource_name).await?;
+
+        // Swap in the new connection atomically.
+        {
+            let mut conn = self.connection.write().await;
+            *conn = new_conn;
+        }
+
+        // Update session state.
+        self.shared_state.set_database(db_name.clone());
+        *self.current_datasource.write().await = datasource_name.to_string();
+
+        Ok(db_name)
+    }
+
+    /// Connect to a named datasource without switching the session to it.
+    ///
+    /// Used by commands that read from a second cluster, such as
+    /// `db.collection.diff(other, { datasource })`.
+    ///
+    /// # Arguments
+    /// * `datasource_name` - Name of the datasource as defined in the config file
+    ///
+    /// # Returns
+    /// * `Ok((Client, String))` - Client and the database the datasource URI embeds
+    /// * `Err(...)` - Datasource not found, or connection failed
+    pub async fn connect_datasource(&self, datasource_name: &str) -> Result<(Client, String)> {
+        let (conn, db_name) = self.open_datasource(datasource_name).await?;
+        Ok((conn.get_client()?.clone(), db_name))
+    }
+
+    /// Build and connect a `ConnectionManager` for a named datasource.
+    ///
+    /// # Returns
+    /// * `Ok((ConnectionManager, String))` - Connected manager and the database
+    ///   embedded in its URI, `"test"` if none
+    async fn open_datasource(&self, datasource_name: &str) -> Result<(ConnectionManager, String)> {
         // Look up the URI for this datasource name.
         let uri = self
             .connection_config
@@ -178,20 +285,32 @@ impl ExecutionContext {
         let db_name = extract_db_from_uri(&uri).unwrap_or_else(|| "test".to_string());
 
         // Build and connect a fresh ConnectionManager.
-        let mut new_conn = ConnectionManager::new(uri, (*self.connection_config).clone());
-        new_conn.connect().await?;
-
-        // Swap in the new connection atomically.
-        {
-            let mut conn = self.connection.write().await;
-            *conn = new_conn;
-        }
+        let mut conn = ConnectionManager::new(uri, (*self.connection_config).clone());
+        conn.connect().await?;
+        Ok((conn, db_name))
+    }
 
-        // Update session state.
-        self.shared_state.set_database(db_name.clone());
-        *self.current_datasource.write().await = datasource_name.to_string();
+    /// Reconnect the current connection with new credentials.
+    ///
+    /// Used after an authentication failure so the user can fix their
+    /// username, password or mechanism without restarting the shell. The
+    /// credentials are verified with a ping before returning.
+    ///
+    /// # Arguments
+    /// * `credential` - Credentials entered by the user
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success, or the authentication / connection error
+    pub async fn reauthenticate(&self, credential: Credential) -> Result<()> {
+        let mut conn = self.connection.write().await;
+        conn.set_credential(credential);
+        conn.reconnect().await?;
+        conn.verify_authentication().await
+    }
 
-        Ok(db_name)
+    /// Return the username the current connection authenticates as, if any.
+    pub async fn get_current_username(&self) -> Option<String> {
+        self.connection.read().await.username().await
     }
 
     /// Return the name of the currently active datasource.
@@ -215,8 +334,8 @@ impl ExecutionContext {
         // Ensure connection is alive before getting database
         self.ensure_connected().await?;
 
+        let db_name = self.get_current_database().await;
         let conn = self.connection.read().await;
-        let db_name = self.shared_state.get_database();
         conn.get_database(&db_name)
     }
 
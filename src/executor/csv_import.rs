@@ -0,0 +1,398 @@
+//! CSV import for the `import` command
+//!
+//! A CSV file is read as one document per row, with field names taken from
+//! the header line. Quoted fields may contain delimiters, doubled quotes and
+//! line breaks. Values are typed the way a spreadsheet shows them:
+//! `true`/`false` become booleans, numbers written in the chosen locale
+//! (`1.234,5` for `--locale de`) become integers or doubles, and everything
+//! else stays a string. Empty fields are left out of the document, and
+//! integers with leading zeros (postal codes, ids) stay strings.
+//!
+//! The file is read one record at a time and inserted in batches, so large
+//! exports can be imported without loading them into memory.
+
+use std::path::Path;
+
+use mongodb::Collection;
+use mongodb::bson::{Bson, Document};
+use tokio::fs::File;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, BufReader};
+
+use super::progress::{ProgressTracker, ProgressUnit};
+use crate::error::{MongoshError, Result};
+use crate::parser::CsvImportOptions;
+
+/// Documents sent per `insertMany`
+const IMPORT_BATCH_SIZE: usize = 1000;
+
+/// Separators that stand for a space inside grouped digits (`1 234,5`)
+const SPACE_SEPARATORS: &[char] = &[' ', '\u{a0}', '\u{202f}'];
+
+/// Insert the rows of a CSV file into a collection
+///
+/// Rows are read and inserted one batch at a time, so the file never has to
+/// fit in memory.
+///
+/// # Arguments
+/// * `collection` - Target collection
+/// * `file` - CSV file with a header line
+/// * `options` - Delimiter and number format
+/// * `accessible` - Whether accessible mode is on, which hides progress bars
+///
+/// # Returns
+/// * `Result<u64>` - Number of inserted documents
+pub(super) async fn import_csv(
+    collection: &Collection<Document>,
+    file: &Path,
+    options: &CsvImportOptions,
+    accessible: bool,
+) -> Result<u64> {
+    let handle = File::open(file)
+        .await
+        .map_err(|e| MongoshError::Generic(format!("Failed to read {}: {}", file.display(), e)))?;
+    // Progress is measured in bytes read, since the row count is unknown
+    let size = handle.metadata().await.map(|m| m.len()).ok();
+    let mut reader = CsvReader::new(BufReader::new(handle), *options).await?;
+
+    let progress =
+        ProgressTracker::for_terminal(collection.name(), size, ProgressUnit::Bytes, accessible);
+    let mut batch = Vec::with_capacity(IMPORT_BATCH_SIZE);
+    let mut inserted = 0;
+    loop {
+        let document = reader.next_document().await?;
+        let done = document.is_none();
+        batch.extend(document);
+        if batch.len() == IMPORT_BATCH_SIZE || (done && !batch.is_empty()) {
+            let result = collection.insert_many(std::mem::take(&mut batch)).await?;
+            inserted += result.inserted_ids.len() as u64;
+            progress.update(reader.bytes_read());
+        }
+        if done {
+            break;
+        }
+    }
+    progress.finish();
+
+    Ok(inserted)
+}
+
+/// Reads the data rows of CSV text as documents, one row at a time
+pub(super) struct CsvReader<R> {
+    /// Source of the CSV text
+    reader: R,
+
+    /// Delimiter and number format
+    options: CsvImportOptions,
+
+    /// Field names from the header line
+    header: Vec<String>,
+
+    /// Records read so far, including the header
+    records: usize,
+
+    /// Bytes read so far
+    bytes_read: u64,
+
+    /// Buffer for the current line
+    line: String,
+}
+
+impl<R: AsyncBufRead + Unpin> CsvReader<R> {
+    /// Read the header line of CSV text
+    ///
+    /// # Arguments
+    /// * `reader` - CSV text, optionally starting with a byte order mark
+    /// * `options` - Delimiter and number format
+    ///
+    /// # Returns
+    /// * `Result<Self>` - Reader positioned at the first data row
+    pub(super) async fn new(reader: R, options: CsvImportOptions) -> Result<Self> {
+        let mut csv = Self {
+            reader,
+            options,
+            header: Vec::new(),
+            records: 0,
+            bytes_read: 0,
+            line: String::new(),
+        };
+
+        let Some(header) = csv.next_record().await? else {
+            return Err(MongoshError::Generic(
+                "The CSV file is empty; expected a header line".to_string(),
+            ));
+        };
+        if let Some(column) = header.iter().position(String::is_empty) {
+            return Err(MongoshError::Generic(format!(
+                "Column {} of the CSV header has no name",
+                column + 1
+            )));
+        }
+        csv.header = header;
+
+        Ok(csv)
+    }
+
+    /// Read the next data row, skipping blank lines
+    ///
+    /// # Returns
+    /// * `Result<Option<Document>>` - Document of the row, or None at the end
+    pub(super) async fn next_document(&mut self) -> Result<Option<Document>> {
+        loop {
+            let Some(record) = self.next_record().await? else {
+                return Ok(None);
+            };
+            if record.iter().all(String::is_empty) {
+                continue;
+            }
+            if record.len() > self.header.len() {
+                return Err(MongoshError::Generic(format!(
+                    "Row {} has {} fields, but the header names {}",
+                    self.records,
+                    record.len(),
+                    self.header.len()
+                )));
+            }
+
+            let mut document = Document::new();
+            for (name, value) in self.header.iter().zip(record) {
+                if let Some(value) = typed_value(&value, &self.options) {
+                    document.insert(name.clone(), value);
+                }
+            }
+            return Ok(Some(document));
+        }
+    }
+
+    /// Bytes of CSV text read so far
+    pub(super) fn bytes_read(&self) -> u64 {
+        self.bytes_read
+    }
+
+    /// Read one record of fields (RFC 4180), which may span several lines
+    ///
+    /// # Returns
+    /// * `Result<Option<Vec<String>>>` - Fields, or None at the end of the text
+    async fn next_record(&mut self) -> Result<Option<Vec<String>>> {
+        let mut record = Vec::new();
+        let mut field = String::new();
+        let mut in_quotes = false;
+
+        loop {
+            self.line.clear();
+            let read = self.reader.read_line(&mut self.line).await.map_err(|e| {
+                MongoshError::Generic(format!("Failed to read the CSV file: {}", e))
+            })?;
+            if read == 0 {
+                break;
+            }
+            self.bytes_read += read as u64;
+            let line = if self.bytes_read == read as u64 {
+                self.line.strip_prefix('\u{feff}').unwrap_or(&self.line)
+            } else {
+                &self.line
+            };
+
+            let mut chars = line.chars().peekable();
+            while let Some(ch) = chars.next() {
+                if in_quotes {
+                    match ch {
+                        '"' if chars.peek() == Some(&'"') => {
+                            field.push('"');
+                            chars.next();
+                        }
+                        '"' => in_quotes = false,
+                        _ => field.push(ch),
+                    }
+                    continue;
+                }
+
+                match ch {
+                    '"' if field.is_empty() => in_quotes = true,
+                    c if c == self.options.delimiter => record.push(std::mem::take(&mut field)),
+                    '\r' if chars.peek() == Some(&'\n') => {}
+                    '\n' => {
+                        record.push(field);
+                        self.records += 1;
+                        return Ok(Some(record));
+                    }
+                    _ => field.push(ch),
+                }
+            }
+        }
+
+        if in_quotes {
+            return Err(MongoshError::Generic(
+                "The CSV file ends inside a quoted field".to_string(),
+            ));
+        }
+        if field.is_empty() && record.is_empty() {
+            return Ok(None);
+        }
+        record.push(field);
+        self.records += 1;
+        Ok(Some(record))
+    }
+}
+
+/// Type a field: booleans, locale-formatted numbers, or the text itself
+///
+/// # Returns
+/// * `Option<Bson>` - Value, or `None` for an empty field
+fn typed_value(text: &str, options: &CsvImportOptions) -> Option<Bson> {
+    if text.is_empty() {
+        return None;
+    }
+    Some(match text {
+        "true" => Bson::Boolean(true),
+        "false" => Bson::Boolean(false),
+        _ => parse_number(text, options).unwrap_or_else(|| Bson::String(text.to_string())),
+    })
+}
+
+/// Read a number written with the decimal and thousands separators of a locale
+///
+/// Grouping separators are only accepted between groups of three digits,
+/// so `1.5` is not read as `15` under a decimal-comma locale.
+///
+/// # Returns
+/// * `Option<Bson>` - `Int32`, `Int64` or `Double`, or `None` if the text is not a number
+pub(super) fn parse_number(text: &str, options: &CsvImportOptions) -> Option<Bson> {
+    let (sign, unsigned) = match text.strip_prefix('-') {
+        Some(rest) => ("-", rest),
+        None => ("", text.strip_prefix('+').unwrap_or(text)),
+    };
+    let (mantissa, exponent) = match unsigned.find(['e', 'E']) {
+        Some(at) => (&unsigned[..at], Some(&unsigned[at + 1..])),
+        None => (unsigned, None),
+    };
+    let (integer, fraction) = match mantissa.split_once(options.decimal_separator) {
+        Some((integer, fraction)) => (integer, Some(fraction)),
+        None => (mantissa, None),
+    };
+
+    let integer = ungroup(integer, options.thousands_separator)?;
+    let all_digits = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_digit());
+    if !all_digits(&integer) || fraction.is_some_and(|f| !all_digits(f)) {
+        return None;
+    }
+    if let Some(exponent) = exponent {
+        let digits = exponent.strip_prefix(['-', '+']).unwrap_or(exponent);
+        if !all_digits(digits) {
+            return None;
+        }
+    }
+
+    if fraction.is_none() && exponent.is_none() {
+        // Leading zeros mark codes such as postal codes, not quantities
+        if integer.len() > 1 && integer.starts_with('0') {
+            return None;
+        }
+        if let Ok(n) = format!("{}{}", sign, integer).parse::<i64>() {
+            return Some(match i32::try_from(n) {
+                Ok(n) => Bson::Int32(n),
+                Err(_) => Bson::Int64(n),
+            });
+        }
+    }
+
+    let mut normalized = format!("{}{}", sign, integer);
+    if let Some(fraction) = fraction {
+        normalized.push('.');
+        normalized.push_str(fraction);
+    }
+    if let Some(exponent) = exponent {
+        normalized.push('e');
+        normalized.push_str(exponent);
+    }
+    normalized.parse::<f64>().ok().map(Bson::Double)
+}
+
+/// Remove grouping separators from the integer part of a number
+///
+/// # Returns
+/// * `Option<String>` - Digits, or `None` if the groups are malformed
+fn ungroup(integer: &str, separator: Option<char>) -> Option<String> {
+    let Some(separator) = separator else {
+        return Some(integer.to_string());
+    };
+    let is_separator = |c: char| {
+        c == separator || (SPACE_SEPARATORS.contains(&separator) && SPACE_SEPARATORS.contains(&c))
+    };
+    if !integer.contains(is_separator) {
+        return Some(integer.to_string());
+    }
+
+    let groups: Vec<&str> = integer.split(is_separator).collect();
+    let first_ok = (1..=3).contains(&groups[0].len());
+    if first_ok && groups[1..].iter().all(|group| group.len() == 3) {
+        Some(groups.concat())
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mongodb::bson::doc;
+
+    fn german() -> CsvImportOptions {
+        CsvImportOptions::for_locale("de-DE").unwrap()
+    }
+
+    async fn parse_csv(text: &str, options: CsvImportOptions) -> Result<Vec<Document>> {
+        let mut reader = CsvReader::new(text.as_bytes(), options).await?;
+        let mut documents = Vec::new();
+        while let Some(document) = reader.next_document().await? {
+            documents.push(document);
+        }
+        Ok(documents)
+    }
+
+    #[test]
+    fn test_parse_localized_numbers() {
+        let de = german();
+        assert_eq!(parse_number("1.234,5", &de), Some(Bson::Double(1234.5)));
+        assert_eq!(parse_number("-0,25", &de), Some(Bson::Double(-0.25)));
+        assert_eq!(parse_number("1.234.567", &de), Some(Bson::Int32(1_234_567)));
+        assert_eq!(parse_number("3,2e3", &de), Some(Bson::Double(3200.0)));
+        assert_eq!(parse_number("1.5", &de), None);
+        assert_eq!(parse_number("12.34.5", &de), None);
+
+        let fr = CsvImportOptions::for_locale("fr").unwrap();
+        assert_eq!(
+            parse_number("1\u{202f}234,5", &fr),
+            Some(Bson::Double(1234.5))
+        );
+
+        let en = CsvImportOptions::default();
+        assert_eq!(parse_number("1234.5", &en), Some(Bson::Double(1234.5)));
+        assert_eq!(parse_number("1,234", &en), None);
+        assert_eq!(
+            parse_number("9007199254740993", &en),
+            Some(Bson::Int64(9_007_199_254_740_993))
+        );
+        assert_eq!(parse_number("0042", &en), None);
+        assert_eq!(parse_number("12abc", &en), None);
+    }
+
+    #[tokio::test]
+    async fn test_parse_csv() {
+        let text = "\u{feff}name;price;zip;active;note\r\n\
+                    Kaffee;1.299,90;01067;true;\"Bohnen; 1kg\"\r\n\
+                    Tee;4,5;;false;\"sagt \"\"gut\"\"\nzweite Zeile\"\r\n\
+                    \r\n";
+        let documents = parse_csv(text, german()).await.unwrap();
+        assert_eq!(
+            documents,
+            vec![
+                doc! { "name": "Kaffee", "price": 1299.9, "zip": "01067", "active": true, "note": "Bohnen; 1kg" },
+                doc! { "name": "Tee", "price": 4.5, "active": false, "note": "sagt \"gut\"\nzweite Zeile" },
+            ]
+        );
+
+        assert!(parse_csv("", german()).await.is_err());
+        assert!(parse_csv("a;b\n1;2;3\n", german()).await.is_err());
+        assert!(parse_csv("a;b\n\"open\n", german()).await.is_err());
+    }
+}
@@ -0,0 +1,404 @@
+//! Interactive operations monitor for `currentOp --watch`
+//!
+//! The monitor takes over the terminal in raw mode and redraws the table of
+//! in-progress operations on every refresh. Arrow keys (or j/k) select an
+//! operation, `x` asks to kill it with `killOp`, `r` refreshes immediately and
+//! `q`, Esc or Ctrl+C leave the monitor. Killing is disabled while
+//! `safety.read_only` is on.
+
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::terminal::{self, ClearType};
+use crossterm::{cursor, execute};
+use futures::stream::TryStreamExt;
+use mongodb::Database;
+use mongodb::bson::{Bson, doc};
+
+use crate::error::{ExecutionError, MongoshError, Result};
+use crate::formatter::{CurrentOpFormatter, OperationInfo, format_opid};
+
+/// Comment attached to the monitor's own `$currentOp` query so it can be
+/// left out of the list
+const MONITOR_COMMENT: &str = "mongosh currentOp monitor";
+
+/// Fetch in-progress operations, longest running first
+///
+/// # Arguments
+/// * `admin` - The `admin` database
+/// * `all` - Include idle connections and sessions
+///
+/// # Returns
+/// * `Result<Vec<OperationInfo>>` - Operations reported by `$currentOp`
+pub(super) async fn fetch_operations(admin: &Database, all: bool) -> Result<Vec<OperationInfo>> {
+    let mut filter = doc! { "command.comment": { "$ne": MONITOR_COMMENT } };
+    if !all {
+        filter.insert("active", true);
+    }
+    let pipeline = vec![
+        doc! {
+            "$currentOp": {
+                "allUsers": true,
+                "idleConnections": all,
+                "idleSessions": all,
+            }
+        },
+        doc! { "$match": filter },
+    ];
+
+    let docs: Vec<_> = admin
+        .aggregate(pipeline)
+        .comment(Bson::String(MONITOR_COMMENT.to_string()))
+        .await
+        .map_err(|e| ExecutionError::QueryFailed(e.to_string()))?
+        .try_collect()
+        .await
+        .map_err(|e| ExecutionError::QueryFailed(e.to_string()))?;
+
+    Ok(OperationInfo::from_docs(&docs))
+}
+
+/// Kill an operation by opid
+async fn kill_operation(admin: &Database, opid: &Bson) -> Result<()> {
+    admin
+        .run_command(doc! { "killOp": 1, "op": opid.clone() })
+        .await
+        .map_err(|e| ExecutionError::QueryFailed(e.to_string()))?;
+    Ok(())
+}
+
+/// Run the monitor until the user quits
+///
+/// # Arguments
+/// * `admin` - The `admin` database
+/// * `interval` - Seconds between refreshes
+/// * `all` - Include idle connections and sessions
+/// * `read_only` - Refuse to kill operations (`safety.read_only`)
+///
+/// # Returns
+/// * `Result<usize>` - Number of operations killed
+pub(super) async fn run_monitor(
+    admin: &Database,
+    interval: u64,
+    all: bool,
+    read_only: bool,
+) -> Result<usize> {
+    let mut stdout = io::stdout();
+    terminal::enable_raw_mode()?;
+    execute!(stdout, terminal::EnterAlternateScreen, cursor::Hide)?;
+
+    let result = monitor_loop(admin, interval, all, read_only).await;
+
+    let _ = execute!(stdout, cursor::Show, terminal::LeaveAlternateScreen);
+    terminal::disable_raw_mode()?;
+    result
+}
+
+/// Refresh, draw and react to keys while the terminal is in raw mode
+async fn monitor_loop(
+    admin: &Database,
+    interval: u64,
+    all: bool,
+    read_only: bool,
+) -> Result<usize> {
+    let mut monitor = Monitor {
+        read_only,
+        ..Monitor::default()
+    };
+    let mut killed = 0;
+
+    'refresh: loop {
+        match fetch_operations(admin, all).await {
+            Ok(ops) => monitor.update(ops),
+            Err(e) => monitor.status = Some(e.to_string()),
+        }
+        let deadline = Instant::now() + Duration::from_secs(interval);
+
+        loop {
+            draw(&monitor.render(interval, all))?;
+            let timeout = deadline.saturating_duration_since(Instant::now());
+            let Some(key) = next_key(timeout).await? else {
+                continue 'refresh;
+            };
+
+            match monitor.handle(key.code, key.modifiers) {
+                MonitorAction::Continue => {}
+                MonitorAction::Refresh => continue 'refresh,
+                MonitorAction::Quit => return Ok(killed),
+                MonitorAction::Kill(opid) => {
+                    monitor.status = Some(match kill_operation(admin, &opid).await {
+                        Ok(()) => {
+                            killed += 1;
+                            format!("Killed operation {}", format_opid(&opid))
+                        }
+                        Err(e) => format!("Failed to kill operation {}: {}", format_opid(&opid), e),
+                    });
+                    continue 'refresh;
+                }
+            }
+        }
+    }
+}
+
+/// Wait up to `timeout` for a key press without blocking the runtime
+///
+/// Returns `None` on timeout or when another event, such as a resize,
+/// arrives, so the caller redraws.
+async fn next_key(timeout: Duration) -> Result<Option<KeyEvent>> {
+    tokio::task::spawn_blocking(move || -> io::Result<Option<KeyEvent>> {
+        if !event::poll(timeout)? {
+            return Ok(None);
+        }
+        match event::read()? {
+            Event::Key(key) if key.kind != KeyEventKind::Release => Ok(Some(key)),
+            _ => Ok(None),
+        }
+    })
+    .await
+    .map_err(|e| MongoshError::Generic(format!("Failed to read key: {}", e)))?
+    .map_err(Into::into)
+}
+
+/// Clear the screen and print a frame, translating newlines for raw mode
+fn draw(frame: &str) -> Result<()> {
+    let mut stdout = io::stdout();
+    execute!(
+        stdout,
+        terminal::Clear(ClearType::All),
+        cursor::MoveTo(0, 0)
+    )?;
+    write!(stdout, "{}", frame.replace('\n', "\r\n"))?;
+    stdout.flush()?;
+    Ok(())
+}
+
+/// Outcome of a key press in the monitor
+#[derive(Debug, PartialEq)]
+enum MonitorAction {
+    Continue,
+    Refresh,
+    Kill(Bson),
+    Quit,
+}
+
+/// Operations on screen, the selected row and the message under the table
+#[derive(Debug, Default)]
+struct Monitor {
+    /// Operations of the last refresh
+    ops: Vec<OperationInfo>,
+    /// Index of the selected operation
+    cursor: usize,
+    /// Waiting for y/n after `x`
+    confirming: bool,
+    /// Result of the last action or refresh error
+    status: Option<String>,
+    /// Whether killing is disabled by `safety.read_only`
+    read_only: bool,
+}
+
+impl Monitor {
+    /// Replace the operations, keeping the same operation selected if it is
+    /// still running
+    fn update(&mut self, ops: Vec<OperationInfo>) {
+        let position = self
+            .selected()
+            .and_then(|selected| ops.iter().position(|op| op.opid == selected.opid));
+        if self.confirming && position.is_none() {
+            self.confirming = false;
+            self.status = Some("Operation finished before it was killed".to_string());
+        }
+        self.cursor = position
+            .unwrap_or(self.cursor)
+            .min(ops.len().saturating_sub(1));
+        self.ops = ops;
+    }
+
+    /// Currently selected operation
+    fn selected(&self) -> Option<&OperationInfo> {
+        self.ops.get(self.cursor)
+    }
+
+    /// Apply one key press
+    fn handle(&mut self, code: KeyCode, modifiers: KeyModifiers) -> MonitorAction {
+        if self.confirming {
+            self.confirming = false;
+            let opid = self.selected().map(|op| op.opid.clone());
+            return match (code, opid) {
+                (KeyCode::Char('y') | KeyCode::Char('Y'), Some(opid)) => MonitorAction::Kill(opid),
+                _ => {
+                    self.status = Some("Kill cancelled".to_string());
+                    MonitorAction::Continue
+                }
+            };
+        }
+
+        let len = self.ops.len();
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc => return MonitorAction::Quit,
+            KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => {
+                return MonitorAction::Quit;
+            }
+            KeyCode::Char('r') => return MonitorAction::Refresh,
+            KeyCode::Up | KeyCode::Char('k') if len > 0 => {
+                self.cursor = (self.cursor + len - 1) % len;
+            }
+            KeyCode::Down | KeyCode::Char('j') if len > 0 => {
+                self.cursor = (self.cursor + 1) % len;
+            }
+            KeyCode::Char('x') if self.read_only => {
+                self.status =
+                    Some("Killing operations is disabled by safety.read_only".to_string());
+            }
+            KeyCode::Char('x') if len > 0 => {
+                self.confirming = true;
+                self.status = None;
+            }
+            _ => {}
+        }
+        MonitorAction::Continue
+    }
+
+    /// Render the header, table, status and key help
+    fn render(&self, interval: u64, all: bool) -> String {
+        let time = chrono::Local::now().format("%H:%M:%S");
+        let scope = if all { " including idle" } else { "" };
+        let mut frame = format!(
+            "{} operation(s){} at {}, refreshing every {}s\n{}\n",
+            self.ops.len(),
+            scope,
+            time,
+            interval,
+            CurrentOpFormatter::format(&self.ops, self.selected().map(|_| self.cursor))
+        );
+
+        match (self.confirming, self.selected()) {
+            (true, Some(op)) => frame.push_str(&format!(
+                "Kill operation {} on {}? (y/n)\n",
+                format_opid(&op.opid),
+                if op.ns.is_empty() { "-" } else { &op.ns }
+            )),
+            _ => {
+                if let Some(status) = &self.status {
+                    frame.push_str(status);
+                    frame.push('\n');
+                }
+            }
+        }
+        frame.push_str(if self.read_only {
+            "↑/↓ select  r refresh  q quit"
+        } else {
+            "↑/↓ select  x kill  r refresh  q quit"
+        });
+        frame
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn op(opid: i64, micros: i64) -> OperationInfo {
+        OperationInfo {
+            opid: Bson::Int64(opid),
+            op: "query".to_string(),
+            ns: "shop.orders".to_string(),
+            micros,
+            plan: "COLLSCAN".to_string(),
+            command: "find: orders".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_monitor_navigation_and_kill() {
+        let none = KeyModifiers::NONE;
+        let mut monitor = Monitor::default();
+        monitor.update(vec![op(1, 300), op(2, 200), op(3, 100)]);
+
+        monitor.handle(KeyCode::Up, none);
+        assert_eq!(monitor.cursor, 2);
+        monitor.handle(KeyCode::Char('j'), none);
+        assert_eq!(monitor.cursor, 0);
+        monitor.handle(KeyCode::Down, none);
+
+        assert_eq!(
+            monitor.handle(KeyCode::Char('x'), none),
+            MonitorAction::Continue
+        );
+        assert!(
+            monitor
+                .render(1, false)
+                .contains("Kill operation 2 on shop.orders? (y/n)")
+        );
+        assert_eq!(
+            monitor.handle(KeyCode::Char('y'), none),
+            MonitorAction::Kill(Bson::Int64(2))
+        );
+
+        monitor.handle(KeyCode::Char('x'), none);
+        assert_eq!(
+            monitor.handle(KeyCode::Char('n'), none),
+            MonitorAction::Continue
+        );
+        assert_eq!(monitor.status.as_deref(), Some("Kill cancelled"));
+
+        assert_eq!(
+            monitor.handle(KeyCode::Char('r'), none),
+            MonitorAction::Refresh
+        );
+        assert_eq!(monitor.handle(KeyCode::Esc, none), MonitorAction::Quit);
+        assert_eq!(
+            monitor.handle(KeyCode::Char('c'), KeyModifiers::CONTROL),
+            MonitorAction::Quit
+        );
+    }
+
+    #[test]
+    fn test_monitor_update_keeps_selection() {
+        let mut monitor = Monitor::default();
+        monitor.update(vec![op(1, 300), op(2, 200)]);
+        monitor.cursor = 1;
+
+        // Operation 2 now runs longest and moves to the top
+        monitor.update(vec![op(2, 900), op(1, 800), op(4, 10)]);
+        assert_eq!(monitor.cursor, 0);
+
+        // The selected operation finished while a kill was being confirmed
+        monitor.handle(KeyCode::Char('x'), KeyModifiers::NONE);
+        monitor.update(vec![op(1, 1000)]);
+        assert_eq!(monitor.cursor, 0);
+        assert!(!monitor.confirming);
+        assert_eq!(
+            monitor.status.as_deref(),
+            Some("Operation finished before it was killed")
+        );
+
+        monitor.update(Vec::new());
+        assert_eq!(monitor.cursor, 0);
+        assert_eq!(
+            monitor.handle(KeyCode::Char('x'), KeyModifiers::NONE),
+            MonitorAction::Continue
+        );
+        assert!(!monitor.confirming);
+    }
+
+    #[test]
+    fn test_monitor_read_only_refuses_kill() {
+        let mut monitor = Monitor {
+            read_only: true,
+            ..Monitor::default()
+        };
+        monitor.update(vec![op(1, 300)]);
+
+        assert_eq!(
+            monitor.handle(KeyCode::Char('x'), KeyModifiers::NONE),
+            MonitorAction::Continue
+        );
+        assert!(!monitor.confirming);
+        assert_eq!(
+            monitor.handle(KeyCode::Char('y'), KeyModifiers::NONE),
+            MonitorAction::Continue
+        );
+        assert!(!monitor.render(1, false).contains("x kill"));
+    }
+}
@@ -0,0 +1,429 @@
+//! Plain-language summaries of commands for `describe`
+//!
+//! A summary says what a command would do without running it, e.g.
+//! `Delete ALL documents in orders matching {"status":"x"}`. Commands that
+//! select documents with a filter also name the count to run, so the caller
+//! can append how many documents would be affected.
+
+use mongodb::bson::{Bson, Document};
+
+use crate::error::{ParseError, Result};
+use crate::parser::{AdminCommand, Command, QueryCommand};
+
+/// What a command would do
+#[derive(Debug, Clone, PartialEq)]
+pub struct Description {
+    /// Database the command runs against, when not the current one
+    pub database: Option<String>,
+
+    /// One-sentence summary
+    pub summary: String,
+
+    /// Count of the documents the command would read or change
+    pub estimate: Option<Estimate>,
+}
+
+/// A `countDocuments` estimating how many documents a command touches
+#[derive(Debug, Clone, PartialEq)]
+pub struct Estimate {
+    pub collection: String,
+    pub filter: Document,
+    pub skip: Option<u64>,
+    pub limit: Option<u64>,
+}
+
+impl Estimate {
+    fn new(collection: &str, filter: &Document) -> Self {
+        Self {
+            collection: collection.to_string(),
+            filter: filter.clone(),
+            skip: None,
+            limit: None,
+        }
+    }
+
+    /// Estimate for commands that act on the first match only
+    fn first(collection: &str, filter: &Document) -> Self {
+        Self {
+            limit: Some(1),
+            ..Self::new(collection, filter)
+        }
+    }
+}
+
+/// Summarize what a command would do
+///
+/// # Arguments
+/// * `command` - Parsed command
+///
+/// # Returns
+/// * `Result<Description>` - Summary, or an error for commands without one
+pub fn describe(command: &Command) -> Result<Description> {
+    match command {
+        Command::Query(query) => describe_query(query),
+        Command::Admin(admin) => describe_admin(admin),
+        Command::WithDatabase { database, command } => {
+            let description = describe(command)?;
+            Ok(Description {
+                database: Some(database.clone()),
+                summary: format!("{} (database {})", description.summary, database),
+                ..description
+            })
+        }
+        _ => Err(unsupported()),
+    }
+}
+
+/// Format a document count with thousands separators, e.g. `12,403 documents`
+pub fn count_phrase(count: u64) -> String {
+    let digits = count.to_string();
+    let mut grouped = String::new();
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(c);
+    }
+    let noun = if count == 1 { "document" } else { "documents" };
+    format!("{} {}", grouped, noun)
+}
+
+fn describe_query(query: &QueryCommand) -> Result<Description> {
+    let (summary, estimate) = match query {
+        QueryCommand::Find {
+            collection,
+            filter,
+            options,
+        } => {
+            let mut summary = format!("Find {}", selection("documents", collection, filter));
+            if let Some(projection) = &options.projection {
+                summary.push_str(&format!(", returning the fields {}", json(projection)));
+            }
+            if let Some(sort) = &options.sort {
+                summary.push_str(&format!(", sorted by {}", json(sort)));
+            }
+            if let Some(skip) = options.skip {
+                summary.push_str(&format!(", skipping the first {}", skip));
+            }
+            let limit = options.limit.map(i64::unsigned_abs).filter(|&n| n > 0);
+            if let Some(limit) = limit {
+                summary.push_str(&format!(", at most {}", limit));
+            }
+            let estimate = Estimate {
+                skip: options.skip,
+                limit,
+                ..Estimate::new(collection, filter)
+            };
+            (summary, Some(estimate))
+        }
+        QueryCommand::FindOne {
+            collection, filter, ..
+        } => (
+            format!(
+                "Find {}",
+                selection("the first document", collection, filter)
+            ),
+            Some(Estimate::first(collection, filter)),
+        ),
+        QueryCommand::CountDocuments { collection, filter } => (
+            format!("Count {}", selection("the documents", collection, filter)),
+            Some(Estimate::new(collection, filter)),
+        ),
+        QueryCommand::EstimatedDocumentCount { collection } => (
+            format!(
+                "Estimate the number of documents in {} from collection metadata",
+                collection
+            ),
+            None,
+        ),
+        QueryCommand::Distinct {
+            collection,
+            field,
+            filter,
+            ..
+        } => {
+            let filter = filter.clone().unwrap_or_default();
+            (
+                format!(
+                    "List the distinct values of '{}' across {}",
+                    field,
+                    selection("the documents", collection, &filter)
+                ),
+                Some(Estimate::new(collection, &filter)),
+            )
+        }
+        QueryCommand::Aggregate {
+            collection,
+            pipeline,
+            ..
+        } => {
+            let stages: Vec<&str> = pipeline
+                .iter()
+                .filter_map(|stage| stage.keys().next().map(String::as_str))
+                .collect();
+            let summary = format!(
+                "Run a {}-stage aggregation pipeline on {}: {}",
+                pipeline.len(),
+                collection,
+                stages.join(" → ")
+            );
+            // Documents entering the pipeline, when it starts with a filter
+            let estimate = pipeline
+                .first()
+                .and_then(|stage| stage.get_document("$match").ok())
+                .map(|filter| Estimate::new(collection, filter));
+            (summary, estimate)
+        }
+        QueryCommand::InsertOne { collection, .. } => {
+            (format!("Insert 1 document into {}", collection), None)
+        }
+        QueryCommand::InsertMany {
+            collection,
+            documents,
+        } => (
+            format!(
+                "Insert {} into {}",
+                count_phrase(documents.len() as u64),
+                collection
+            ),
+            None,
+        ),
+        QueryCommand::UpdateOne {
+            collection,
+            filter,
+            update,
+            options,
+        } => (
+            format!(
+                "Update {} with {}{}",
+                selection("the first document", collection, filter),
+                json(update),
+                upsert_note(options.upsert)
+            ),
+            Some(Estimate::first(collection, filter)),
+        ),
+        QueryCommand::UpdateMany {
+            collection,
+            filter,
+            update,
+            options,
+        } => (
+            format!(
+                "Update {} with {}{}",
+                selection("ALL documents", collection, filter),
+                json(update),
+                upsert_note(options.upsert)
+            ),
+            Some(Estimate::new(collection, filter)),
+        ),
+        QueryCommand::BulkUpdateWithRateLimit {
+            collection,
+            filter,
+            update,
+            batch_size,
+            ops_per_second,
+        } => (
+            format!(
+                "Update {} with {}, {} at a time at up to {} writes per second",
+                selection("ALL documents", collection, filter),
+                json(update),
+                batch_size,
+                ops_per_second
+            ),
+            Some(Estimate::new(collection, filter)),
+        ),
+        QueryCommand::ReplaceOne {
+            collection,
+            filter,
+            options,
+            ..
+        } => (
+            format!(
+                "Replace {}{}",
+                selection("the first document", collection, filter),
+                upsert_note(options.upsert)
+            ),
+            Some(Estimate::first(collection, filter)),
+        ),
+        QueryCommand::DeleteOne { collection, filter } => (
+            format!(
+                "Delete {}",
+                selection("the first document", collection, filter)
+            ),
+            Some(Estimate::first(collection, filter)),
+        ),
+        QueryCommand::DeleteMany { collection, filter } => (
+            format!("Delete {}", selection("ALL documents", collection, filter)),
+            Some(Estimate::new(collection, filter)),
+        ),
+        QueryCommand::FindOneAndDelete {
+            collection, filter, ..
+        } => (
+            format!(
+                "Delete {} and return it",
+                selection("the first document", collection, filter)
+            ),
+            Some(Estimate::first(collection, filter)),
+        ),
+        QueryCommand::FindOneAndUpdate {
+            collection,
+            filter,
+            update,
+            ..
+        } => (
+            format!(
+                "Update {} with {} and return it",
+                selection("the first document", collection, filter),
+                json(update)
+            ),
+            Some(Estimate::first(collection, filter)),
+        ),
+        QueryCommand::FindOneAndReplace {
+            collection, filter, ..
+        } => (
+            format!(
+                "Replace {} and return it",
+                selection("the first document", collection, filter)
+            ),
+            Some(Estimate::first(collection, filter)),
+        ),
+        QueryCommand::Explain { query, .. } => {
+            let description = describe_query(query)?;
+            (
+                format!(
+                    "Show the query plan for: {}",
+                    lowercase_first(&description.summary)
+                ),
+                None,
+            )
+        }
+        _ => return Err(unsupported()),
+    };
+
+    Ok(Description {
+        database: None,
+        summary,
+        estimate,
+    })
+}
+
+fn describe_admin(admin: &AdminCommand) -> Result<Description> {
+    let (summary, estimate) = match admin {
+        AdminCommand::DropCollection { collection, .. } => (
+            format!(
+                "Drop the collection {} with ALL its documents and indexes",
+                collection
+            ),
+            Some(Estimate::new(collection, &Document::new())),
+        ),
+        AdminCommand::DropIndex { collection, index } => (
+            format!("Drop the index '{}' of {}", index, collection),
+            None,
+        ),
+        _ => return Err(unsupported()),
+    };
+
+    Ok(Description {
+        database: None,
+        summary,
+        estimate,
+    })
+}
+
+/// `<what> in <collection> [matching <filter>]`
+fn selection(what: &str, collection: &str, filter: &Document) -> String {
+    if filter.is_empty() {
+        format!("{} in {}", what, collection)
+    } else {
+        format!("{} in {} matching {}", what, collection, json(filter))
+    }
+}
+
+fn upsert_note(upsert: bool) -> &'static str {
+    if upsert {
+        ", inserting one if none match"
+    } else {
+        ""
+    }
+}
+
+fn lowercase_first(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_lowercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Compact relaxed Extended JSON
+fn json(doc: &Document) -> String {
+    Bson::Document(doc.clone())
+        .into_relaxed_extjson()
+        .to_string()
+}
+
+fn unsupported() -> crate::error::MongoshError {
+    ParseError::InvalidCommand(
+        "describe supports collection queries (find, aggregate, insert, update, replace, delete, \
+         count, distinct) and drops"
+            .to_string(),
+    )
+    .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+
+    fn summary(input: &str) -> Description {
+        describe(&Parser::new().parse(input).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_describe_delete_many() {
+        let description = summary("db.orders.deleteMany({ status: 'x' })");
+        assert_eq!(
+            description.summary,
+            "Delete ALL documents in orders matching {\"status\":\"x\"}"
+        );
+        assert_eq!(description.estimate.unwrap().limit, None);
+    }
+
+    #[test]
+    fn test_describe_single_document_operations() {
+        let description =
+            summary("db.users.updateOne({ _id: 1 }, { $set: { a: 1 } }, { upsert: true })");
+        assert!(
+            description
+                .summary
+                .starts_with("Update the first document in users")
+        );
+        assert!(description.summary.ends_with("inserting one if none match"));
+        assert_eq!(description.estimate.unwrap().limit, Some(1));
+    }
+
+    #[test]
+    fn test_describe_find_with_options() {
+        let description = summary("db.users.find({}).sort({ age: -1 }).skip(10).limit(5)");
+        assert_eq!(
+            description.summary,
+            "Find documents in users, sorted by {\"age\":-1}, skipping the first 10, at most 5"
+        );
+        let estimate = description.estimate.unwrap();
+        assert_eq!((estimate.skip, estimate.limit), (Some(10), Some(5)));
+    }
+
+    #[test]
+    fn test_describe_unsupported() {
+        let command = Parser::new().parse("show dbs").unwrap();
+        assert!(describe(&command).is_err());
+    }
+
+    #[test]
+    fn test_count_phrase() {
+        assert_eq!(count_phrase(1), "1 document");
+        assert_eq!(count_phrase(12403), "12,403 documents");
+        assert_eq!(count_phrase(1_000_000), "1,000,000 documents");
+    }
+}
@@ -9,8 +9,9 @@ use tokio_util::sync::CancellationToken;
 use tracing::{debug, info};
 
 use crate::error::Result;
+use crate::formatter::Redactor;
 
-use super::progress::ProgressTracker;
+use super::super::progress::ProgressTracker;
 use super::streaming::StreamingQuery;
 use super::writers::FormatWriter;
 
@@ -40,6 +41,8 @@ pub struct ExportCoordinator {
     writer: Box<dyn FormatWriter>,
     /// Cancellation token for aborting export
     cancel_token: Option<CancellationToken>,
+    /// Masks sensitive fields before they are written
+    redactor: Option<Redactor>,
 }
 
 impl ExportCoordinator {
@@ -54,6 +57,7 @@ impl ExportCoordinator {
             tracker,
             writer,
             cancel_token: None,
+            redactor: None,
         }
     }
 
@@ -63,6 +67,12 @@ impl ExportCoordinator {
         self
     }
 
+    /// Mask sensitive fields of exported documents
+    pub fn with_redaction(mut self, redactor: Option<Redactor>) -> Self {
+        self.redactor = redactor;
+        self
+    }
+
     /// Execute the export operation
     ///
     /// This is the main entry point that orchestrates the entire export process:
@@ -109,7 +119,12 @@ impl ExportCoordinator {
             debug!("Fetching batch #{}", batch_count + 1);
 
             match self.query.next_batch().await? {
-                Some(docs) => {
+                Some(mut docs) => {
+                    if let Some(ref redactor) = self.redactor {
+                        for doc in docs.iter_mut() {
+                            redactor.redact_document(doc);
+                        }
+                    }
                     let count = docs.len();
                     debug!("Received batch of {} documents", count);
 
@@ -4,14 +4,15 @@
 //! - Streaming exports to avoid memory issues with large datasets
 //! - Multiple query types (Find, Aggregate, etc.)
 //! - Progress tracking with real-time feedback
-//! - Multiple output formats (JSON Lines, CSV)
+//! - Multiple output formats (JSON Lines, CSV, Excel)
 //!
 //! # Architecture
 //!
 //! The export system is built on three main components:
 //!
 //! 1. **StreamingQuery**: Abstracts different query types into a unified streaming interface
-//! 2. **ProgressTracker**: Provides real-time progress feedback to users
+//! 2. **ProgressTracker**: Provides real-time progress feedback to users, shared
+//!    with other long-running operations
 //! 3. **FormatWriter**: Handles writing documents to different file formats
 //!
 //! These components are orchestrated by the **ExportCoordinator**, which manages
@@ -40,15 +41,19 @@
 //! // let result = coordinator.execute().await?;
 //! ```
 
+#[cfg(feature = "export")]
 pub mod coordinator;
-pub mod progress;
 pub mod streaming;
+#[cfg(feature = "export")]
 pub mod writers;
 
+#[cfg(feature = "export")]
 pub use coordinator::ExportCoordinator;
-pub use progress::ProgressTracker;
+#[cfg(feature = "export")]
+pub use super::progress::{ProgressTracker, ProgressUnit};
 pub use streaming::StreamingQuery;
-pub use writers::{CsvWriter, FormatWriter, JsonLWriter};
+#[cfg(feature = "export")]
+pub use writers::{CsvWriter, FormatWriter, HtmlWriter, JsonLWriter, XlsxWriter};
 
 
 
@@ -3,8 +3,11 @@
 //! This module provides a unified interface for streaming documents from different
 //! query types (Find, Aggregate, etc.) without loading all results into memory.
 
+use std::collections::VecDeque;
+
 use async_trait::async_trait;
-use futures::TryStreamExt;
+use futures::stream::{BoxStream, SelectAll};
+use futures::{stream, Stream, TryStreamExt};
 use mongodb::bson::Document;
 use mongodb::Cursor;
 use tracing::{debug, info};
@@ -27,6 +30,46 @@ pub trait StreamingQuery: Send {
     async fn close(&mut self) -> Result<()>;
 }
 
+/// Turn a streaming query into a stream of documents
+///
+/// The next batch is only fetched once the documents of the previous one have
+/// been consumed, so a slow consumer holds at most one batch in memory. The
+/// query is closed when it is exhausted; an error is yielded once and ends the
+/// stream.
+///
+/// # Arguments
+/// * `query` - Streaming query, e.g. from `QueryMode::Streaming`
+///
+/// # Returns
+/// * `impl Stream<Item = Result<Document>>` - Documents in cursor order
+pub fn document_stream(
+    query: Box<dyn StreamingQuery>,
+) -> impl Stream<Item = Result<Document>> + Send {
+    stream::unfold(Some((query, VecDeque::new())), |state| async move {
+        let (mut query, mut buffer) = state?;
+        loop {
+            if let Some(doc) = buffer.pop_front() {
+                return Some((Ok(doc), Some((query, buffer))));
+            }
+            match query.next_batch().await {
+                Ok(Some(batch)) => buffer.extend(batch),
+                Ok(None) => {
+                    if let Err(e) = query.close().await {
+                        debug!("Failed to close streaming query: {}", e);
+                    }
+                    return None;
+                }
+                Err(e) => {
+                    if let Err(close_error) = query.close().await {
+                        debug!("Failed to close streaming query: {}", close_error);
+                    }
+                    return Some((Err(e), None));
+                }
+            }
+        }
+    })
+}
+
 /// Generic cursor-based streaming query implementation
 ///
 /// This implementation works for both Find and Aggregate operations,
@@ -148,6 +191,90 @@ impl AggregateStreamingQuery {
     }
 }
 
+/// Streaming query reading several cursors at once
+///
+/// Used by `.parallel(n)`, which opens one cursor per `_id` range. Documents
+/// are yielded in the order they arrive from any cursor, so the merged order
+/// is not the order of the collection.
+pub struct MergedStreamingQuery {
+    streams: Option<SelectAll<BoxStream<'static, Result<Document>>>>,
+    batch_size: u32,
+    /// Documents still to yield when the read has a limit
+    remaining: Option<u64>,
+    total_fetched: u64,
+}
+
+impl MergedStreamingQuery {
+    /// Create a query merging several document streams
+    ///
+    /// # Arguments
+    /// * `streams` - One stream per range, e.g. mapped cursors
+    /// * `batch_size` - Number of documents to fetch per batch
+    /// * `limit` - Most documents to yield across all streams
+    pub fn new(
+        streams: Vec<BoxStream<'static, Result<Document>>>,
+        batch_size: u32,
+        limit: Option<u64>,
+    ) -> Self {
+        Self {
+            streams: Some(stream::select_all(streams)),
+            batch_size,
+            remaining: limit,
+            total_fetched: 0,
+        }
+    }
+}
+
+#[async_trait]
+impl StreamingQuery for MergedStreamingQuery {
+    async fn next_batch(&mut self) -> Result<Option<Vec<Document>>> {
+        let Some(streams) = self.streams.as_mut() else {
+            return Ok(None);
+        };
+
+        let wanted = match self.remaining {
+            Some(remaining) => remaining.min(self.batch_size as u64),
+            None => self.batch_size as u64,
+        };
+        let mut batch = Vec::with_capacity(wanted as usize);
+        while (batch.len() as u64) < wanted {
+            match streams.try_next().await {
+                Ok(Some(doc)) => batch.push(doc),
+                Ok(None) => break,
+                Err(e) => {
+                    // Dropping the streams closes the other cursors
+                    self.streams = None;
+                    return Err(e);
+                }
+            }
+        }
+
+        if let Some(remaining) = self.remaining.as_mut() {
+            *remaining -= batch.len() as u64;
+        }
+        if batch.is_empty() {
+            debug!(
+                "Merged streaming query exhausted after {} documents",
+                self.total_fetched
+            );
+            self.streams = None;
+            return Ok(None);
+        }
+        self.total_fetched += batch.len() as u64;
+        Ok(Some(batch))
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        if self.streams.take().is_some() {
+            info!(
+                "Closed merged streaming query after fetching {} documents",
+                self.total_fetched
+            );
+        }
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -160,4 +287,94 @@ mod tests {
         // Verify we can use StreamingQuery as a trait object
         fn _accepts_streaming_query(_query: Box<dyn StreamingQuery>) {}
     }
+
+    /// Streaming query over fixed batches, failing after them if asked to
+    struct BatchQuery {
+        batches: VecDeque<Vec<Document>>,
+        fail: bool,
+        closed: bool,
+    }
+
+    #[async_trait]
+    impl StreamingQuery for BatchQuery {
+        async fn next_batch(&mut self) -> Result<Option<Vec<Document>>> {
+            match self.batches.pop_front() {
+                Some(batch) => Ok(Some(batch)),
+                None if self.fail => Err(crate::error::MongoshError::Generic(
+                    "cursor killed".to_string(),
+                )),
+                None => Ok(None),
+            }
+        }
+
+        async fn close(&mut self) -> Result<()> {
+            assert!(!self.closed, "closed twice");
+            self.closed = true;
+            Ok(())
+        }
+    }
+
+    fn batch_query(fail: bool) -> Box<dyn StreamingQuery> {
+        use mongodb::bson::doc;
+        Box::new(BatchQuery {
+            batches: VecDeque::from(vec![
+                vec![doc! { "n": 1 }, doc! { "n": 2 }],
+                vec![],
+                vec![doc! { "n": 3 }],
+            ]),
+            fail,
+            closed: false,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_document_stream() {
+        use futures::StreamExt;
+
+        let docs: Vec<Document> = document_stream(batch_query(false))
+            .map(|doc| doc.unwrap())
+            .collect()
+            .await;
+        let numbers: Vec<i32> = docs.iter().map(|d| d.get_i32("n").unwrap()).collect();
+        assert_eq!(numbers, vec![1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn test_document_stream_error_ends_stream() {
+        use futures::StreamExt;
+
+        let items: Vec<Result<Document>> = document_stream(batch_query(true)).collect().await;
+        assert_eq!(items.len(), 4);
+        assert!(items[..3].iter().all(|item| item.is_ok()));
+        assert!(items[3].is_err());
+    }
+
+    #[tokio::test]
+    async fn test_merged_streaming_query_limit() {
+        use futures::StreamExt;
+        use mongodb::bson::doc;
+
+        let ranges = (0..3)
+            .map(|range| {
+                let docs = (0..4).map(move |n| Ok(doc! { "range": range, "n": n }));
+                stream::iter(docs).boxed()
+            })
+            .collect();
+        let mut query = MergedStreamingQuery::new(ranges, 5, Some(7));
+
+        let mut total = 0;
+        while let Some(batch) = query.next_batch().await.unwrap() {
+            assert!(batch.len() <= 5);
+            total += batch.len();
+        }
+        assert_eq!(total, 7);
+
+        let ranges = (0..2)
+            .map(|_| stream::iter(vec![Ok(doc! { "n": 1 })]).boxed())
+            .collect();
+        let mut query = MergedStreamingQuery::new(ranges, 10, None);
+        assert_eq!(query.next_batch().await.unwrap().unwrap().len(), 2);
+        assert!(query.next_batch().await.unwrap().is_none());
+        query.close().await.unwrap();
+    }
 }
@@ -7,24 +7,26 @@ use std::collections::BTreeSet;
 
 use async_trait::async_trait;
 use mongodb::bson::Document;
-use tokio::fs::File;
-use tokio::io::{AsyncWriteExt, BufWriter};
+use tokio::io::AsyncWriteExt;
 use tracing::debug;
 
 use crate::error::{ExecutionError, Result};
 use crate::formatter::bson_utils::{BsonConverter, PlainTextConverter};
+use crate::parser::ExportCompression;
 
-use super::{create_writer, validate_path, FormatWriter};
+use super::{create_writer, validate_path, FormatWriter, OutputStream};
 
 /// Writer for CSV format
 ///
 /// CSV format exports documents as comma-separated values with a header row.
 /// Fields are automatically detected from the documents.
 pub struct CsvWriter {
-    /// Buffered file writer
-    writer: BufWriter<File>,
-    /// Path to the output file
+    /// Buffered, optionally compressing file writer
+    writer: OutputStream,
+    /// Path to the output file, or a description of the stream
     path: String,
+    /// Whether the output is a file whose size can be read back
+    to_file: bool,
     /// Column headers (field names)
     headers: Vec<String>,
     /// Whether headers have been written
@@ -38,20 +40,38 @@ pub struct CsvWriter {
 impl CsvWriter {
     /// Create a new CSV writer
     ///
+    /// Files ending in `.gz` or `.zst` are compressed accordingly.
+    ///
     /// # Arguments
     /// * `path` - Output file path
     ///
     /// # Returns
     /// * `Result<Self>` - New writer instance or error
     pub async fn new(path: &str) -> Result<Self> {
+        Self::with_compression(path, ExportCompression::from_path(path)).await
+    }
+
+    /// Create a new CSV writer with explicit compression
+    ///
+    /// # Arguments
+    /// * `path` - Output file path
+    /// * `compression` - Compression to apply, or `None` for plain text
+    ///
+    /// # Returns
+    /// * `Result<Self>` - New writer instance or error
+    pub async fn with_compression(
+        path: &str,
+        compression: Option<ExportCompression>,
+    ) -> Result<Self> {
         validate_path(path)?;
-        let writer = create_writer(path).await?;
+        let writer = create_writer(path, compression).await?;
 
         debug!("Created CSV writer for: {}", path);
 
         Ok(Self {
             writer,
             path: path.to_string(),
+            to_file: true,
             headers: Vec::new(),
             headers_written: false,
             written: 0,
@@ -59,6 +79,28 @@ impl CsvWriter {
         })
     }
 
+    /// Create a new CSV writer on an already open stream (stdout, a pipe)
+    ///
+    /// # Arguments
+    /// * `writer` - Output stream
+    /// * `target` - Description of the stream for messages
+    ///
+    /// # Returns
+    /// * `Self` - New writer instance
+    pub fn from_stream(writer: OutputStream, target: &str) -> Self {
+        debug!("Created CSV writer for: {}", target);
+
+        Self {
+            writer,
+            path: target.to_string(),
+            to_file: false,
+            headers: Vec::new(),
+            headers_written: false,
+            written: 0,
+            converter: PlainTextConverter::new(),
+        }
+    }
+
     /// Collect headers from a batch of documents
     ///
     /// # Arguments
@@ -188,7 +230,8 @@ impl FormatWriter for CsvWriter {
     }
 
     async fn finalize(&mut self) -> Result<()> {
-        self.writer.flush().await.map_err(|e| {
+        // shutdown() also writes the trailer of compressed streams
+        self.writer.shutdown().await.map_err(|e| {
             ExecutionError::InvalidOperation(format!("Failed to flush file: {}", e))
         })?;
 
@@ -197,6 +240,9 @@ impl FormatWriter for CsvWriter {
     }
 
     async fn file_size(&self) -> Result<u64> {
+        if !self.to_file {
+            return Ok(0);
+        }
         let metadata = tokio::fs::metadata(&self.path).await.map_err(|e| {
             ExecutionError::InvalidOperation(format!("Failed to get file metadata: {}", e))
         })?;
@@ -234,6 +280,34 @@ mod tests {
         fs::remove_file(path).await.ok();
     }
 
+    #[tokio::test]
+    async fn test_csv_writer_zstd() {
+        use async_compression::tokio::bufread::ZstdDecoder;
+        use tokio::io::{AsyncReadExt, BufReader};
+
+        // Explicit compression applies whatever the extension
+        let path = "test_output_zstd.csv";
+        let mut writer = CsvWriter::with_compression(path, Some(ExportCompression::Zstd))
+            .await
+            .unwrap();
+        writer
+            .write_batch(&[doc! { "name": "Alice" }, doc! { "name": "Bob" }])
+            .await
+            .unwrap();
+        writer.finalize().await.unwrap();
+
+        let compressed = fs::read(path).await.unwrap();
+        let mut content = String::new();
+        ZstdDecoder::new(BufReader::new(&compressed[..]))
+            .read_to_string(&mut content)
+            .await
+            .unwrap();
+        assert_eq!(content, "name\nAlice\nBob\n");
+
+        // Cleanup
+        fs::remove_file(path).await.ok();
+    }
+
     #[tokio::test]
     async fn test_csv_writer_with_special_characters() {
         let path = "test_special.csv";
@@ -0,0 +1,354 @@
+//! HTML writer for export operations
+//!
+//! This module exports MongoDB documents as a standalone HTML page holding a
+//! styled table, so results can be shared with people who don't use the
+//! shell. Clicking a column header sorts the table; the script and styles are
+//! embedded, so the file opens in any browser without network access.
+
+use std::collections::BTreeSet;
+
+use async_trait::async_trait;
+use mongodb::bson::{Bson, Document};
+use tokio::io::AsyncWriteExt;
+use tracing::debug;
+
+use crate::error::{ExecutionError, Result};
+use crate::formatter::bson_utils::{BsonConverter, PlainTextConverter};
+use crate::parser::ExportCompression;
+
+use super::{FormatWriter, OutputStream, create_writer, validate_path};
+
+/// Styles of the exported page
+const STYLE: &str = r#"body { font-family: -apple-system, "Segoe UI", Helvetica, Arial, sans-serif; margin: 2em; color: #1f2328; }
+h1 { font-size: 1.3em; margin-bottom: 0.2em; }
+p.meta { color: #656d76; margin-top: 0; }
+table { border-collapse: collapse; font-size: 0.9em; }
+th, td { border: 1px solid #d0d7de; padding: 6px 10px; text-align: left; vertical-align: top; }
+th { background: #f6f8fa; cursor: pointer; user-select: none; white-space: nowrap; position: sticky; top: 0; }
+th[aria-sort="ascending"]::after { content: " \25B2"; }
+th[aria-sort="descending"]::after { content: " \25BC"; }
+tr:nth-child(even) td { background: #f9fafb; }
+td.num { text-align: right; font-variant-numeric: tabular-nums; }
+td pre { margin: 0; font-family: ui-monospace, Menlo, Consolas, monospace; white-space: pre-wrap; }"#;
+
+/// Click-to-sort script; numeric columns sort by value, others as text
+const SCRIPT: &str = r#"document.querySelectorAll("th").forEach(function (th, col) {
+  th.addEventListener("click", function () {
+    var body = th.closest("table").tBodies[0];
+    var asc = th.getAttribute("aria-sort") !== "ascending";
+    th.parentNode.querySelectorAll("th").forEach(function (h) { h.removeAttribute("aria-sort"); });
+    th.setAttribute("aria-sort", asc ? "ascending" : "descending");
+    var key = function (row) { var cell = row.cells[col]; return cell ? cell.textContent : ""; };
+    Array.from(body.rows).sort(function (a, b) {
+      var x = key(a), y = key(b), nx = parseFloat(x), ny = parseFloat(y);
+      var cmp = !isNaN(nx) && !isNaN(ny) && isFinite(x) && isFinite(y)
+        ? nx - ny
+        : x.localeCompare(y, undefined, { numeric: true });
+      return asc ? cmp : -cmp;
+    }).forEach(function (row) { body.appendChild(row); });
+  });
+});"#;
+
+/// A table cell ready to be written
+struct Cell {
+    /// Escaped cell content
+    html: String,
+    /// Whether the value is a number, aligned to the right
+    numeric: bool,
+}
+
+/// Writer for HTML format
+///
+/// Rows are kept in memory and the page is written on `finalize()`, so
+/// fields first seen in later batches still get a column of their own.
+pub struct HtmlWriter {
+    /// Buffered, optionally compressing file writer
+    writer: OutputStream,
+    /// Path to the output file, or a description of the stream
+    path: String,
+    /// Whether the output is a file whose size can be read back
+    to_file: bool,
+    /// Column headers (field names)
+    headers: Vec<String>,
+    /// Rows of cells, one per header known when the row was added
+    rows: Vec<Vec<Option<Cell>>>,
+    /// Whether the page has been written
+    finished: bool,
+    /// Converter for BSON to plain text
+    converter: PlainTextConverter,
+}
+
+impl HtmlWriter {
+    /// Create a new HTML writer
+    ///
+    /// Files ending in `.gz` or `.zst` are compressed accordingly.
+    ///
+    /// # Arguments
+    /// * `path` - Output file path
+    ///
+    /// # Returns
+    /// * `Result<Self>` - New writer instance or error
+    pub async fn new(path: &str) -> Result<Self> {
+        Self::with_compression(path, ExportCompression::from_path(path)).await
+    }
+
+    /// Create a new HTML writer with explicit compression
+    ///
+    /// # Arguments
+    /// * `path` - Output file path
+    /// * `compression` - Compression to apply, or `None` for plain text
+    ///
+    /// # Returns
+    /// * `Result<Self>` - New writer instance or error
+    pub async fn with_compression(
+        path: &str,
+        compression: Option<ExportCompression>,
+    ) -> Result<Self> {
+        validate_path(path)?;
+        let writer = create_writer(path, compression).await?;
+
+        debug!("Created HTML writer for: {}", path);
+
+        Ok(Self::build(writer, path, true))
+    }
+
+    /// Create a new HTML writer on an already open stream (stdout, a pipe)
+    ///
+    /// # Arguments
+    /// * `writer` - Output stream
+    /// * `target` - Description of the stream for messages
+    ///
+    /// # Returns
+    /// * `Self` - New writer instance
+    pub fn from_stream(writer: OutputStream, target: &str) -> Self {
+        debug!("Created HTML writer for: {}", target);
+
+        Self::build(writer, target, false)
+    }
+
+    fn build(writer: OutputStream, path: &str, to_file: bool) -> Self {
+        Self {
+            writer,
+            path: path.to_string(),
+            to_file,
+            headers: Vec::new(),
+            rows: Vec::new(),
+            finished: false,
+            converter: PlainTextConverter::new(),
+        }
+    }
+
+    /// Add fields not seen before as new columns, in sorted order
+    ///
+    /// # Arguments
+    /// * `docs` - Documents to scan for field names
+    fn collect_headers(&mut self, docs: &[Document]) {
+        let mut new_fields = BTreeSet::new();
+        for doc in docs {
+            for key in doc.keys() {
+                if !self.headers.contains(key) {
+                    new_fields.insert(key.clone());
+                }
+            }
+        }
+        self.headers.extend(new_fields);
+    }
+
+    /// Convert a value into a table cell
+    ///
+    /// # Arguments
+    /// * `value` - Field value
+    ///
+    /// # Returns
+    /// * `Cell` - Escaped content; documents and arrays keep their layout
+    fn cell(&self, value: &Bson) -> Cell {
+        let numeric = matches!(
+            value,
+            Bson::Int32(_) | Bson::Int64(_) | Bson::Double(_) | Bson::Decimal128(_)
+        );
+        let text = escape_html(&self.converter.convert(value));
+        let html = match value {
+            Bson::Document(_) | Bson::Array(_) => format!("<pre>{}</pre>", text),
+            _ => text,
+        };
+        Cell { html, numeric }
+    }
+
+    /// Render the complete page
+    ///
+    /// # Returns
+    /// * `String` - Standalone HTML document
+    fn render(&self) -> String {
+        let title = escape_html(
+            std::path::Path::new(&self.path)
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .filter(|_| self.to_file)
+                .as_deref()
+                .unwrap_or("Export"),
+        );
+
+        let mut page = String::new();
+        page.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+        page.push_str(&format!("<title>{}</title>\n", title));
+        page.push_str(&format!("<style>\n{}\n</style>\n</head>\n<body>\n", STYLE));
+        page.push_str(&format!("<h1>{}</h1>\n", title));
+        page.push_str(&format!(
+            "<p class=\"meta\">{} document(s), exported {}</p>\n",
+            self.rows.len(),
+            chrono::Local::now().format("%Y-%m-%d %H:%M:%S %Z")
+        ));
+
+        page.push_str("<table>\n<thead>\n<tr>");
+        for header in &self.headers {
+            page.push_str(&format!("<th>{}</th>", escape_html(header)));
+        }
+        page.push_str("</tr>\n</thead>\n<tbody>\n");
+
+        for row in &self.rows {
+            page.push_str("<tr>");
+            for col in 0..self.headers.len() {
+                match row.get(col).and_then(Option::as_ref) {
+                    Some(cell) if cell.numeric => {
+                        page.push_str(&format!("<td class=\"num\">{}</td>", cell.html))
+                    }
+                    Some(cell) => page.push_str(&format!("<td>{}</td>", cell.html)),
+                    None => page.push_str("<td></td>"),
+                }
+            }
+            page.push_str("</tr>\n");
+        }
+
+        page.push_str("</tbody>\n</table>\n");
+        page.push_str(&format!("<script>\n{}\n</script>\n", SCRIPT));
+        page.push_str("</body>\n</html>\n");
+        page
+    }
+}
+
+/// Escape text for use in HTML content and attribute values
+///
+/// # Arguments
+/// * `text` - Raw text
+///
+/// # Returns
+/// * `String` - Text with `&`, `<`, `>`, `"` and `'` replaced by entities
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[async_trait]
+impl FormatWriter for HtmlWriter {
+    async fn write_batch(&mut self, docs: &[Document]) -> Result<usize> {
+        if docs.is_empty() {
+            return Ok(0);
+        }
+
+        self.collect_headers(docs);
+        for doc in docs {
+            let row = self
+                .headers
+                .iter()
+                .map(|field| doc.get(field).map(|value| self.cell(value)))
+                .collect();
+            self.rows.push(row);
+        }
+
+        debug!(
+            "Wrote {} documents to HTML (total: {})",
+            docs.len(),
+            self.rows.len()
+        );
+
+        Ok(docs.len())
+    }
+
+    async fn finalize(&mut self) -> Result<()> {
+        if self.finished {
+            return Ok(());
+        }
+
+        let page = self.render();
+        self.writer.write_all(page.as_bytes()).await.map_err(|e| {
+            ExecutionError::InvalidOperation(format!("Failed to write HTML: {}", e))
+        })?;
+        // shutdown() also writes the trailer of compressed streams
+        self.writer.shutdown().await.map_err(|e| {
+            ExecutionError::InvalidOperation(format!("Failed to flush file: {}", e))
+        })?;
+        self.finished = true;
+
+        debug!(
+            "Finalized HTML file: {} ({} documents)",
+            self.path,
+            self.rows.len()
+        );
+        Ok(())
+    }
+
+    async fn file_size(&self) -> Result<u64> {
+        if !self.to_file || !self.finished {
+            return Ok(0);
+        }
+        let metadata = tokio::fs::metadata(&self.path).await.map_err(|e| {
+            ExecutionError::InvalidOperation(format!("Failed to get file metadata: {}", e))
+        })?;
+        Ok(metadata.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mongodb::bson::doc;
+    use tokio::fs;
+
+    #[tokio::test]
+    async fn test_html_writer_basic() {
+        let path = "test_output.html";
+        let mut writer = HtmlWriter::new(path).await.unwrap();
+
+        writer
+            .write_batch(&[doc! { "name": "<Alice>", "age": 30 }])
+            .await
+            .unwrap();
+        writer
+            .write_batch(&[doc! { "name": "Bob", "tags": ["a", "b"] }])
+            .await
+            .unwrap();
+        assert_eq!(writer.headers, vec!["age", "name", "tags"]);
+
+        writer.finalize().await.unwrap();
+        assert!(writer.file_size().await.unwrap() > 0);
+
+        let page = fs::read_to_string(path).await.unwrap();
+        assert!(page.starts_with("<!DOCTYPE html>"));
+        assert!(page.contains("<th>age</th><th>name</th><th>tags</th>"));
+        assert!(page.contains("<td class=\"num\">30</td><td>&lt;Alice&gt;</td><td></td>"));
+        assert!(page.contains("<td></td><td>Bob</td><td><pre>"));
+        assert!(page.contains("2 document(s)"));
+        assert!(page.contains("<script>"));
+
+        // Cleanup
+        fs::remove_file(path).await.ok();
+    }
+
+    #[test]
+    fn test_escape_html() {
+        assert_eq!(
+            escape_html(r#"<a href="x">Tom & 'Jerry'</a>"#),
+            "&lt;a href=&quot;x&quot;&gt;Tom &amp; &#39;Jerry&#39;&lt;/a&gt;"
+        );
+    }
+}
@@ -5,24 +5,26 @@
 
 use async_trait::async_trait;
 use mongodb::bson::Document;
-use tokio::fs::File;
-use tokio::io::{AsyncWriteExt, BufWriter};
+use tokio::io::AsyncWriteExt;
 use tracing::debug;
 
 use crate::error::{ExecutionError, Result};
 use crate::formatter::JsonFormatter;
+use crate::parser::ExportCompression;
 
-use super::{create_writer, validate_path, FormatWriter};
+use super::{create_writer, validate_path, FormatWriter, OutputStream};
 
 /// Writer for JSON Lines format
 ///
 /// JSON Lines format writes one JSON document per line, making it easy to
 /// stream and process large datasets.
 pub struct JsonLWriter {
-    /// Buffered file writer
-    writer: BufWriter<File>,
-    /// Path to the output file
+    /// Buffered, optionally compressing file writer
+    writer: OutputStream,
+    /// Path to the output file, or a description of the stream
     path: String,
+    /// Whether the output is a file whose size can be read back
+    to_file: bool,
     /// Number of documents written
     written: usize,
     /// JSON formatter for converting BSON to JSON
@@ -32,25 +34,64 @@ pub struct JsonLWriter {
 impl JsonLWriter {
     /// Create a new JSON Lines writer
     ///
+    /// Files ending in `.gz` or `.zst` are compressed accordingly.
+    ///
     /// # Arguments
     /// * `path` - Output file path
     ///
     /// # Returns
     /// * `Result<Self>` - New writer instance or error
     pub async fn new(path: &str) -> Result<Self> {
+        Self::with_compression(path, ExportCompression::from_path(path)).await
+    }
+
+    /// Create a new JSON Lines writer with explicit compression
+    ///
+    /// # Arguments
+    /// * `path` - Output file path
+    /// * `compression` - Compression to apply, or `None` for plain text
+    ///
+    /// # Returns
+    /// * `Result<Self>` - New writer instance or error
+    pub async fn with_compression(
+        path: &str,
+        compression: Option<ExportCompression>,
+    ) -> Result<Self> {
         validate_path(path)?;
-        let writer = create_writer(path).await?;
+        let writer = create_writer(path, compression).await?;
 
         debug!("Created JSON Lines writer for: {}", path);
 
         Ok(Self {
             writer,
             path: path.to_string(),
+            to_file: true,
             written: 0,
             // Use compact JSON format without extended JSON notation
             formatter: JsonFormatter::new(false, false, 0),
         })
     }
+
+    /// Create a new JSON Lines writer on an already open stream (stdout, a pipe)
+    ///
+    /// # Arguments
+    /// * `writer` - Output stream
+    /// * `target` - Description of the stream for messages
+    ///
+    /// # Returns
+    /// * `Self` - New writer instance
+    pub fn from_stream(writer: OutputStream, target: &str) -> Self {
+        debug!("Created JSON Lines writer for: {}", target);
+
+        Self {
+            writer,
+            path: target.to_string(),
+            to_file: false,
+            written: 0,
+            // Use compact JSON format without extended JSON notation
+            formatter: JsonFormatter::new(false, false, 0),
+        }
+    }
 }
 
 #[async_trait]
@@ -76,7 +117,8 @@ impl FormatWriter for JsonLWriter {
     }
 
     async fn finalize(&mut self) -> Result<()> {
-        self.writer.flush().await.map_err(|e| {
+        // shutdown() also writes the trailer of compressed streams
+        self.writer.shutdown().await.map_err(|e| {
             ExecutionError::InvalidOperation(format!("Failed to flush file: {}", e))
         })?;
 
@@ -85,6 +127,9 @@ impl FormatWriter for JsonLWriter {
     }
 
     async fn file_size(&self) -> Result<u64> {
+        if !self.to_file {
+            return Ok(0);
+        }
         let metadata = tokio::fs::metadata(&self.path).await.map_err(|e| {
             ExecutionError::InvalidOperation(format!("Failed to get file metadata: {}", e))
         })?;
@@ -161,6 +206,50 @@ mod tests {
         fs::remove_file(path).await.ok();
     }
 
+    #[tokio::test]
+    async fn test_jsonl_writer_gzip() {
+        use async_compression::tokio::bufread::GzipDecoder;
+        use tokio::io::{AsyncReadExt, BufReader};
+
+        let path = "test_output.jsonl.gz";
+        let mut writer = JsonLWriter::new(path).await.unwrap();
+        writer
+            .write_batch(&[doc! { "id": 1 }, doc! { "id": 2 }])
+            .await
+            .unwrap();
+        writer.finalize().await.unwrap();
+
+        let compressed = fs::read(path).await.unwrap();
+        assert_eq!(&compressed[..2], &[0x1f, 0x8b]);
+
+        let mut content = String::new();
+        GzipDecoder::new(BufReader::new(&compressed[..]))
+            .read_to_string(&mut content)
+            .await
+            .unwrap();
+        assert_eq!(content.lines().count(), 2);
+
+        // Cleanup
+        fs::remove_file(path).await.ok();
+    }
+
+    #[tokio::test]
+    async fn test_jsonl_writer_stream() {
+        use tokio::io::AsyncReadExt;
+
+        let (sink, mut source) = tokio::io::duplex(4096);
+        let mut writer = JsonLWriter::from_stream(Box::new(sink), "stdout");
+        writer.write_batch(&[doc! { "id": 1 }]).await.unwrap();
+        writer.finalize().await.unwrap();
+        assert_eq!(writer.file_size().await.unwrap(), 0);
+        drop(writer);
+
+        let mut content = String::new();
+        source.read_to_string(&mut content).await.unwrap();
+        assert_eq!(content.lines().count(), 1);
+        assert!(content.contains("\"id\""));
+    }
+
     #[tokio::test]
     async fn test_jsonl_writer_invalid_directory() {
         let result = JsonLWriter::new("/nonexistent/directory/file.jsonl").await;
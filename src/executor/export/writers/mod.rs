@@ -1,21 +1,27 @@
 //! Format writers for export operations
 //!
 //! This module provides a unified interface for writing documents to different
-//! file formats (JSON Lines, CSV, Excel, etc.).
+//! file formats (JSON Lines, CSV, Excel, HTML, etc.).
 
+use async_compression::tokio::write::{GzipEncoder, ZstdEncoder};
 use async_trait::async_trait;
 use mongodb::bson::Document;
 use std::path::Path;
 use tokio::fs::File;
-use tokio::io::BufWriter;
+use tokio::io::{AsyncWrite, BufWriter};
 
 use crate::error::Result;
+use crate::parser::ExportCompression;
 
 pub mod jsonl;
 pub mod csv;
+pub mod xlsx;
+pub mod html;
 
 pub use jsonl::JsonLWriter;
 pub use csv::CsvWriter;
+pub use xlsx::XlsxWriter;
+pub use html::HtmlWriter;
 
 /// Trait for writing documents to different file formats
 #[async_trait]
@@ -42,18 +48,48 @@ pub trait FormatWriter: Send {
     async fn file_size(&self) -> Result<u64>;
 }
 
+/// Output stream of a text format writer, compressed or not
+///
+/// Writers must call `shutdown()` when done so compressed streams write
+/// their trailer.
+pub type OutputStream = Box<dyn AsyncWrite + Send + Unpin>;
+
 /// Helper function to create a buffered file writer
 ///
 /// # Arguments
 /// * `path` - File path to create
+/// * `compression` - Compression to apply, if any
 ///
 /// # Returns
-/// * `Result<BufWriter<File>>` - Buffered writer or error
-pub(crate) async fn create_writer(path: &str) -> Result<BufWriter<File>> {
+/// * `Result<OutputStream>` - Buffered, optionally compressing writer or error
+pub(crate) async fn create_writer(
+    path: &str,
+    compression: Option<ExportCompression>,
+) -> Result<OutputStream> {
     let file = File::create(path).await.map_err(|e| {
         crate::error::ExecutionError::InvalidOperation(format!("Failed to create file: {}", e))
     })?;
-    Ok(BufWriter::with_capacity(8 * 1024 * 1024, file)) // 8MB buffer
+    let writer = BufWriter::with_capacity(8 * 1024 * 1024, file); // 8MB buffer
+    Ok(wrap_stream(writer, compression))
+}
+
+/// Wrap any byte sink (stdout, a child process) as an output stream
+///
+/// # Arguments
+/// * `writer` - Destination of the exported bytes
+/// * `compression` - Compression to apply, if any
+///
+/// # Returns
+/// * `OutputStream` - Optionally compressing writer
+pub(crate) fn wrap_stream<W>(writer: W, compression: Option<ExportCompression>) -> OutputStream
+where
+    W: AsyncWrite + Send + Unpin + 'static,
+{
+    match compression {
+        None => Box::new(writer),
+        Some(ExportCompression::Gzip) => Box::new(GzipEncoder::new(writer)),
+        Some(ExportCompression::Zstd) => Box::new(ZstdEncoder::new(writer)),
+    }
 }
 
 /// Helper function to validate file path and directory
@@ -0,0 +1,416 @@
+//! Excel (xlsx) writer for export operations
+//!
+//! This module exports MongoDB documents to an Excel workbook with a header
+//! row, native cell types for numbers, booleans and dates, and columns sized
+//! to their contents. Exports larger than one worksheet continue on a new
+//! sheet.
+//!
+//! Worksheets use rust_xlsxwriter's constant memory mode: each row is flushed
+//! to a temporary file once the next one starts, so memory use does not grow
+//! with the export. Rows can only be appended, so a field first seen after a
+//! worksheet's header row was written starts a new worksheet.
+
+use std::collections::BTreeSet;
+
+use async_trait::async_trait;
+use chrono::{Datelike, Timelike};
+use mongodb::bson::{Bson, DateTime, Document};
+use rust_xlsxwriter::{ExcelDateTime, Format, Workbook, Worksheet, XlsxError};
+use tracing::debug;
+
+use crate::error::{ExecutionError, Result};
+use crate::formatter::bson_utils::{BsonConverter, PlainTextConverter};
+
+use super::{FormatWriter, validate_path};
+
+/// Rows per worksheet, including the header row (Excel's limit)
+const MAX_ROWS_PER_SHEET: u32 = 1_048_576;
+
+/// Longest text Excel accepts in a cell
+const MAX_CELL_CHARS: usize = 32_767;
+
+/// Widest column Excel accepts, in characters
+const MAX_COLUMN_WIDTH: usize = 255;
+
+/// Largest integer an Excel number holds exactly (2^53)
+const MAX_EXACT_NUMBER: u64 = 9_007_199_254_740_992;
+
+/// Number format for date cells
+const DATE_FORMAT: &str = "yyyy-mm-dd hh:mm:ss.000";
+
+/// Writer for Excel format
+///
+/// Rows are streamed to temporary files and the workbook is assembled on
+/// `finalize()`. Columns are the fields of the first batch in sorted order;
+/// fields first seen later are appended as new columns.
+pub struct XlsxWriter {
+    /// Workbook holding the worksheets; the last one is being filled
+    workbook: Workbook,
+    /// Number of worksheets started so far
+    sheet_count: usize,
+    /// Next row to write on the current worksheet
+    row: u32,
+    /// Rows per worksheet, including the header row
+    max_rows: u32,
+    /// Path to the output file
+    path: String,
+    /// Column headers (field names)
+    headers: Vec<String>,
+    /// Columns in the header row of the current worksheet
+    sheet_columns: usize,
+    /// Widest value of each column of the current worksheet, in characters
+    widths: Vec<usize>,
+    /// Number of documents written
+    written: usize,
+    /// Whether the workbook has been saved
+    saved: bool,
+    /// Format for header cells
+    header_format: Format,
+    /// Format for date cells
+    date_format: Format,
+    /// Converter for values without a native cell type
+    converter: PlainTextConverter,
+}
+
+impl XlsxWriter {
+    /// Create a new Excel writer
+    ///
+    /// # Arguments
+    /// * `path` - Output file path
+    ///
+    /// # Returns
+    /// * `Result<Self>` - New writer instance or error
+    pub async fn new(path: &str) -> Result<Self> {
+        validate_path(path)?;
+
+        let mut workbook = Workbook::new();
+        workbook.add_worksheet_with_constant_memory();
+        debug!("Created xlsx writer for: {}", path);
+
+        Ok(Self {
+            workbook,
+            sheet_count: 1,
+            row: 0,
+            max_rows: MAX_ROWS_PER_SHEET,
+            path: path.to_string(),
+            headers: Vec::new(),
+            sheet_columns: 0,
+            widths: Vec::new(),
+            written: 0,
+            saved: false,
+            header_format: Format::new().set_bold(),
+            date_format: Format::new().set_num_format(DATE_FORMAT),
+            converter: PlainTextConverter::new(),
+        })
+    }
+
+    /// Add fields not seen before as new columns
+    ///
+    /// # Arguments
+    /// * `docs` - Documents to scan for field names
+    fn collect_headers(&mut self, docs: &[Document]) {
+        let mut new_fields = BTreeSet::new();
+        for doc in docs {
+            for key in doc.keys() {
+                if !self.headers.contains(key) {
+                    new_fields.insert(key.clone());
+                }
+            }
+        }
+        self.headers.extend(new_fields);
+    }
+
+    /// Worksheet currently being filled
+    fn current_sheet(workbook: &mut Workbook, sheet_count: usize) -> Result<&mut Worksheet> {
+        workbook
+            .worksheet_from_index(sheet_count - 1)
+            .map_err(xlsx_error)
+    }
+
+    /// Write the header row of the current worksheet
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or error
+    fn write_header(&mut self) -> Result<()> {
+        let sheet = Self::current_sheet(&mut self.workbook, self.sheet_count)?;
+        for (col, field) in self.headers.iter().enumerate() {
+            sheet
+                .write_string_with_format(0, col as u16, field, &self.header_format)
+                .map_err(xlsx_error)?;
+        }
+        self.widths = self
+            .headers
+            .iter()
+            .map(|field| field.chars().count())
+            .collect();
+        self.sheet_columns = self.headers.len();
+        self.row = 1;
+        Ok(())
+    }
+
+    /// Size the columns of the current worksheet to their widest value
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or error
+    fn finish_sheet(&mut self) -> Result<()> {
+        let sheet = Self::current_sheet(&mut self.workbook, self.sheet_count)?;
+        for (col, width) in self.widths.iter().enumerate() {
+            sheet
+                .set_column_width(col as u16, (width + 1).min(MAX_COLUMN_WIDTH) as f64)
+                .map_err(xlsx_error)?;
+        }
+        Ok(())
+    }
+
+    /// Finish the current worksheet and start the next one
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or error
+    fn next_sheet(&mut self) -> Result<()> {
+        self.finish_sheet()?;
+        self.workbook.add_worksheet_with_constant_memory();
+        self.sheet_count += 1;
+        self.row = 0;
+        debug!("Started worksheet {} of {}", self.sheet_count, self.path);
+        Ok(())
+    }
+
+    /// Write a single document as a row
+    ///
+    /// # Arguments
+    /// * `doc` - Document to write
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or error
+    fn write_row(&mut self, doc: &Document) -> Result<()> {
+        // The header row is already flushed, so new columns need a new sheet
+        if self.row > 0 && (self.row >= self.max_rows || self.sheet_columns < self.headers.len()) {
+            self.next_sheet()?;
+        }
+        if self.row == 0 {
+            self.write_header()?;
+        }
+
+        let sheet = Self::current_sheet(&mut self.workbook, self.sheet_count)?;
+        for (col, field) in self.headers.iter().enumerate() {
+            if let Some(value) = doc.get(field) {
+                let width = Self::write_cell(
+                    sheet,
+                    self.row,
+                    col as u16,
+                    value,
+                    &self.date_format,
+                    &self.converter,
+                )?;
+                self.widths[col] = self.widths[col].max(width);
+            }
+        }
+        self.row += 1;
+        Ok(())
+    }
+
+    /// Write a value using the closest native cell type
+    ///
+    /// # Returns
+    /// * `Result<usize>` - Displayed width of the value, in characters
+    fn write_cell(
+        sheet: &mut Worksheet,
+        row: u32,
+        col: u16,
+        value: &Bson,
+        date_format: &Format,
+        converter: &PlainTextConverter,
+    ) -> Result<usize> {
+        let (result, width) = match value {
+            Bson::Null | Bson::Undefined => return Ok(0),
+            Bson::Int32(n) => (sheet.write_number(row, col, *n as f64), n.to_string().len()),
+            Bson::Int64(n) if n.unsigned_abs() <= MAX_EXACT_NUMBER => {
+                (sheet.write_number(row, col, *n as f64), n.to_string().len())
+            }
+            Bson::Double(f) if f.is_finite() => {
+                (sheet.write_number(row, col, *f), f.to_string().len())
+            }
+            Bson::Boolean(b) => (sheet.write_boolean(row, col, *b), if *b { 4 } else { 5 }),
+            Bson::DateTime(dt) => match excel_datetime(dt) {
+                Some(date) => (
+                    sheet.write_datetime_with_format(row, col, &date, date_format),
+                    DATE_FORMAT.len(),
+                ),
+                // Dates Excel cannot represent (before 1900) are kept as text
+                None => {
+                    let text = converter.convert(value);
+                    let width = text.chars().count();
+                    (sheet.write_string(row, col, text), width)
+                }
+            },
+            other => {
+                let text: String = converter
+                    .convert(other)
+                    .chars()
+                    .take(MAX_CELL_CHARS)
+                    .collect();
+                let width = text.chars().count();
+                (sheet.write_string(row, col, text), width)
+            }
+        };
+        result.map(|_| width).map_err(xlsx_error)
+    }
+}
+
+/// Convert a BSON date to an Excel date, keeping the milliseconds
+///
+/// # Arguments
+/// * `dt` - Date to convert
+///
+/// # Returns
+/// * `Option<ExcelDateTime>` - The date, or `None` outside Excel's range
+///   (years 1900 to 9999)
+fn excel_datetime(dt: &DateTime) -> Option<ExcelDateTime> {
+    let utc = chrono::DateTime::from_timestamp_millis(dt.timestamp_millis())?.naive_utc();
+    ExcelDateTime::from_ymd(
+        u16::try_from(utc.year()).ok()?,
+        utc.month() as u8,
+        utc.day() as u8,
+    )
+    .and_then(|date| {
+        date.and_hms_milli(
+            utc.hour() as u16,
+            utc.minute() as u8,
+            utc.second() as u8,
+            (utc.nanosecond() / 1_000_000) as u16,
+        )
+    })
+    .ok()
+}
+
+/// Convert an xlsx library error into an execution error
+fn xlsx_error(e: XlsxError) -> crate::error::MongoshError {
+    ExecutionError::InvalidOperation(format!("Failed to write xlsx: {}", e)).into()
+}
+
+#[async_trait]
+impl FormatWriter for XlsxWriter {
+    async fn write_batch(&mut self, docs: &[Document]) -> Result<usize> {
+        if docs.is_empty() {
+            return Ok(0);
+        }
+
+        self.collect_headers(docs);
+        for doc in docs {
+            self.write_row(doc)?;
+        }
+
+        self.written += docs.len();
+        debug!(
+            "Wrote {} documents to xlsx (total: {})",
+            docs.len(),
+            self.written
+        );
+
+        Ok(docs.len())
+    }
+
+    async fn finalize(&mut self) -> Result<()> {
+        if self.saved {
+            return Ok(());
+        }
+
+        self.finish_sheet()?;
+        self.workbook.save(&self.path).map_err(xlsx_error)?;
+        self.saved = true;
+
+        debug!(
+            "Finalized xlsx file: {} ({} documents, {} sheets)",
+            self.path, self.written, self.sheet_count
+        );
+        Ok(())
+    }
+
+    async fn file_size(&self) -> Result<u64> {
+        if !self.saved {
+            return Ok(0);
+        }
+        let metadata = tokio::fs::metadata(&self.path).await.map_err(|e| {
+            ExecutionError::InvalidOperation(format!("Failed to get file metadata: {}", e))
+        })?;
+        Ok(metadata.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mongodb::bson::doc;
+    use tokio::fs;
+
+    #[tokio::test]
+    async fn test_xlsx_writer_basic() {
+        let path = "test_output.xlsx";
+        let mut writer = XlsxWriter::new(path).await.unwrap();
+
+        let docs = vec![
+            doc! { "name": "Alice", "age": 30, "joined": DateTime::from_millis(1_700_000_000_000) },
+            doc! { "name": "Bob", "age": 25.5, "active": true, "tags": ["a", "b"] },
+        ];
+
+        let written = writer.write_batch(&docs).await.unwrap();
+        assert_eq!(written, 2);
+        assert_eq!(
+            writer.headers,
+            vec!["active", "age", "joined", "name", "tags"]
+        );
+
+        writer.finalize().await.unwrap();
+        assert!(writer.file_size().await.unwrap() > 0);
+
+        // Cleanup
+        fs::remove_file(path).await.ok();
+    }
+
+    #[tokio::test]
+    async fn test_xlsx_writer_splits_sheets() {
+        let path = "test_split.xlsx";
+        let mut writer = XlsxWriter::new(path).await.unwrap();
+        writer.max_rows = 3;
+
+        let docs: Vec<Document> = (0..5).map(|i| doc! { "n": i }).collect();
+        writer.write_batch(&docs).await.unwrap();
+
+        // Header plus two rows per sheet
+        assert_eq!(writer.sheet_count, 3);
+        assert_eq!(writer.row, 2);
+
+        writer.finalize().await.unwrap();
+        fs::remove_file(path).await.ok();
+    }
+
+    #[tokio::test]
+    async fn test_xlsx_writer_new_fields_start_sheet() {
+        let path = "test_new_fields.xlsx";
+        let mut writer = XlsxWriter::new(path).await.unwrap();
+
+        writer.write_batch(&[doc! { "a": 1 }]).await.unwrap();
+        writer.write_batch(&[doc! { "a": 2 }]).await.unwrap();
+        assert_eq!(writer.sheet_count, 1);
+
+        writer
+            .write_batch(&[doc! { "a": 3, "b": "wide value" }])
+            .await
+            .unwrap();
+        assert_eq!(writer.sheet_count, 2);
+        assert_eq!(writer.headers, vec!["a", "b"]);
+        assert_eq!(writer.widths, vec![1, 10]);
+
+        writer.finalize().await.unwrap();
+        fs::remove_file(path).await.ok();
+    }
+
+    #[test]
+    fn test_excel_datetime_keeps_milliseconds() {
+        let date = excel_datetime(&DateTime::from_millis(1_700_000_000_123)).unwrap();
+        let expected = 25_569.0 + 1_700_000_000_123.0 / 86_400_000.0;
+        assert!((date.to_excel() - expected).abs() < 1e-9);
+
+        assert!(excel_datetime(&DateTime::from_millis(-2_300_000_000_000)).is_none());
+    }
+}
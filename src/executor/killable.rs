@@ -66,11 +66,11 @@ impl MongoOpKiller {
         Self { client }
     }
 
-    /// Attempt to kill an operation identified by its comment.
+    /// Attempt to kill the operations identified by a comment.
     ///
     /// This method:
     /// 1. Queries `$currentOp` to find operations matching the comment
-    /// 2. Extracts the `opid` from the first matching operation
+    /// 2. Extracts the `opid` from each matching operation
     /// 3. Calls `killOp` with that `opid`
     ///
     /// # Arguments
@@ -112,9 +112,9 @@ impl MongoOpKiller {
             }
         };
 
-        // Try to get the first matching operation
+        // Kill every matching operation; parallel reads share one comment
         use futures::stream::StreamExt;
-        if let Some(result) = cursor.next().await {
+        while let Some(result) = cursor.next().await {
             let op_doc = match result {
                 Ok(doc) => doc,
                 Err(_e) => return Ok(()),
@@ -123,16 +123,13 @@ impl MongoOpKiller {
             // Extract opid (can be i32 or i64)
             let opid = match extract_opid(&op_doc) {
                 Ok(id) => id,
-                Err(_e) => return Ok(()),
+                Err(_e) => continue,
             };
 
-            // Call killOp
-            let kill_result = admin_db
+            // Call killOp; ignore result - we tried our best
+            let _ = admin_db
                 .run_command(doc! { "killOp": 1, "op": opid })
                 .await;
-
-            // Ignore result - we tried our best
-            let _ = kill_result;
         }
 
         Ok(())
@@ -0,0 +1,314 @@
+//! Interactive `$lookup` wizard
+//!
+//! `lookup wizard` asks for the local and foreign collections, the join keys
+//! and the output field, then prints the generated aggregation and can run
+//! it. Collection and field names complete on Tab; field names come from a
+//! `$sample` of each collection, so fields missing from the sample can still
+//! be typed by hand.
+
+use std::collections::BTreeSet;
+
+use futures::stream::TryStreamExt;
+use mongodb::bson::{Bson, Document, doc};
+
+use crate::cli::prompt;
+use crate::error::{ExecutionError, Result};
+use crate::parser::{AggregateOptions, QueryCommand, QueryMode};
+
+use super::context::ExecutionContext;
+use super::query::QueryExecutor;
+use super::result::{ExecutionResult, ExecutionStats, ResultData};
+
+/// Documents sampled per collection to offer field names
+const SAMPLE_SIZE: i64 = 100;
+
+/// Guided construction of a `$lookup` stage
+pub(super) struct LookupWizard {
+    /// Execution context
+    context: ExecutionContext,
+}
+
+impl LookupWizard {
+    /// Create a new wizard
+    ///
+    /// # Arguments
+    /// * `context` - Execution context
+    ///
+    /// # Returns
+    /// * `Self` - New wizard
+    pub fn new(context: ExecutionContext) -> Self {
+        Self { context }
+    }
+
+    /// Ask for the join, print the generated aggregation and optionally run it
+    ///
+    /// # Returns
+    /// * `Result<ExecutionResult>` - Aggregation results if run, otherwise the
+    ///   generated command
+    pub async fn run(&self) -> Result<ExecutionResult> {
+        if !self.context.is_interactive() {
+            return Err(ExecutionError::InvalidOperation(
+                "The lookup wizard can only run interactively".to_string(),
+            )
+            .into());
+        }
+        let db = self.context.get_database().await?;
+        let mut collections: Vec<String> = db
+            .list_collection_names()
+            .await?
+            .into_iter()
+            .filter(|name| !name.starts_with("system."))
+            .collect();
+        collections.sort();
+
+        println!("$lookup wizard (tab completes, esc cancels)");
+        let local = Self::ask_collection("Local collection: ", &collections)?;
+        let local_fields = self.sample_fields(&local).await?;
+        let foreign = Self::ask_collection("Foreign collection: ", &collections)?;
+        let foreign_fields = self.sample_fields(&foreign).await?;
+
+        let local_field =
+            prompt::read_completed(&format!("Local field ({}): ", local), &local_fields)?;
+        if local_field.is_empty() {
+            return Err(
+                ExecutionError::InvalidParameters("A local field is required".to_string()).into(),
+            );
+        }
+        let foreign_field = or_default(
+            prompt::read_completed(
+                &format!("Foreign field ({}) [_id]: ", foreign),
+                &foreign_fields,
+            )?,
+            "_id",
+        );
+        let as_field = or_default(
+            prompt::read_line(&format!("Output field [{}]: ", foreign))?,
+            &foreign,
+        );
+
+        let pipeline = vec![lookup_stage(
+            &foreign,
+            &local_field,
+            &foreign_field,
+            &as_field,
+        )];
+        let command = render_aggregate(&local, &pipeline);
+        println!("\n{}\n", command);
+
+        let answer = prompt::read_line("Run it now? (yes/no): ")?;
+        if !matches!(answer.to_lowercase().as_str(), "yes" | "y") {
+            return Ok(ExecutionResult {
+                success: true,
+                data: ResultData::Message(String::new()),
+                stats: ExecutionStats::default(),
+                error: None,
+            });
+        }
+
+        let executor = QueryExecutor::new(self.context.clone()).await?;
+        executor
+            .execute(
+                QueryCommand::Aggregate {
+                    collection: local,
+                    pipeline,
+                    options: AggregateOptions::default(),
+                },
+                QueryMode::default(),
+            )
+            .await
+    }
+
+    /// Ask for a collection of the current database
+    fn ask_collection(label: &str, collections: &[String]) -> Result<String> {
+        let name = prompt::read_completed(label, collections)?;
+        if !collections.contains(&name) {
+            return Err(ExecutionError::InvalidParameters(format!(
+                "Collection '{}' not found in the current database",
+                name
+            ))
+            .into());
+        }
+        Ok(name)
+    }
+
+    /// Field paths seen in a sample of a collection
+    async fn sample_fields(&self, collection: &str) -> Result<Vec<String>> {
+        let db = self.context.get_database().await?;
+        let documents: Vec<Document> = db
+            .collection::<Document>(collection)
+            .aggregate(vec![doc! { "$sample": { "size": SAMPLE_SIZE } }])
+            .await?
+            .try_collect()
+            .await?;
+        Ok(field_paths(&documents))
+    }
+}
+
+/// Use a default for a blank answer
+fn or_default(answer: String, default: &str) -> String {
+    if answer.is_empty() {
+        default.to_string()
+    } else {
+        answer
+    }
+}
+
+/// Dotted paths of all fields in some documents, `_id` first
+///
+/// Embedded documents and arrays of documents are descended into, so
+/// `{ items: [{ sku: 1 }] }` yields `items` and `items.sku`.
+///
+/// # Arguments
+/// * `documents` - Sampled documents
+///
+/// # Returns
+/// * `Vec<String>` - Sorted, unique field paths
+fn field_paths(documents: &[Document]) -> Vec<String> {
+    fn collect(prefix: &str, document: &Document, paths: &mut BTreeSet<String>) {
+        for (key, value) in document {
+            let path = if prefix.is_empty() {
+                key.clone()
+            } else {
+                format!("{}.{}", prefix, key)
+            };
+            match value {
+                Bson::Document(nested) => collect(&path, nested, paths),
+                Bson::Array(items) => {
+                    for item in items {
+                        if let Bson::Document(nested) = item {
+                            collect(&path, nested, paths);
+                        }
+                    }
+                }
+                _ => {}
+            }
+            paths.insert(path);
+        }
+    }
+
+    let mut paths = BTreeSet::new();
+    for document in documents {
+        collect("", document, &mut paths);
+    }
+
+    let mut paths: Vec<String> = paths.into_iter().collect();
+    if let Some(i) = paths.iter().position(|path| path == "_id") {
+        let id = paths.remove(i);
+        paths.insert(0, id);
+    }
+    paths
+}
+
+/// Build a `$lookup` stage
+///
+/// # Arguments
+/// * `from` - Foreign collection
+/// * `local_field` - Field of the local documents
+/// * `foreign_field` - Field of the foreign documents
+/// * `as_field` - Array field receiving the matches
+///
+/// # Returns
+/// * `Document` - The stage
+fn lookup_stage(
+    from: &str,
+    local_field: &str,
+    foreign_field: &str,
+    as_field: &str,
+) -> Document {
+    doc! {
+        "$lookup": {
+            "from": from,
+            "localField": local_field,
+            "foreignField": foreign_field,
+            "as": as_field,
+        }
+    }
+}
+
+/// Render an aggregation as a shell command that can be pasted back
+///
+/// # Arguments
+/// * `collection` - Collection to aggregate
+/// * `pipeline` - Pipeline stages
+///
+/// # Returns
+/// * `String` - e.g. `db.orders.aggregate([{"$lookup": {...}}])`
+fn render_aggregate(collection: &str, pipeline: &[Document]) -> String {
+    let stages: Vec<String> = pipeline
+        .iter()
+        .map(|stage| {
+            Bson::Document(stage.clone())
+                .into_relaxed_extjson()
+                .to_string()
+        })
+        .collect();
+
+    let is_identifier = collection
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && collection
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+    let target = if is_identifier {
+        format!("db.{}", collection)
+    } else {
+        format!("db.getCollection({:?})", collection)
+    };
+
+    format!("{}.aggregate([{}])", target, stages.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_field_paths() {
+        let documents = vec![
+            doc! { "_id": 1, "customerId": 7, "items": [{ "sku": "a" }, 3] },
+            doc! { "_id": 2, "shipping": { "city": "Oslo" } },
+        ];
+        assert_eq!(
+            field_paths(&documents),
+            vec![
+                "_id",
+                "customerId",
+                "items",
+                "items.sku",
+                "shipping",
+                "shipping.city",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lookup_stage_and_render() {
+        let stage = lookup_stage("customers", "customerId", "_id", "customer");
+        assert_eq!(
+            stage,
+            doc! {
+                "$lookup": {
+                    "from": "customers",
+                    "localField": "customerId",
+                    "foreignField": "_id",
+                    "as": "customer",
+                }
+            }
+        );
+        assert_eq!(
+            render_aggregate("orders", &[stage.clone()]),
+            r#"db.orders.aggregate([{"$lookup":{"from":"customers","localField":"customerId","foreignField":"_id","as":"customer"}}])"#
+        );
+        assert!(
+            render_aggregate("order-items", &[stage])
+                .starts_with(r#"db.getCollection("order-items")"#)
+        );
+    }
+
+    #[test]
+    fn test_or_default() {
+        assert_eq!(or_default(String::new(), "_id"), "_id");
+        assert_eq!(or_default("sku".to_string(), "_id"), "sku");
+    }
+}
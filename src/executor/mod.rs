@@ -4,15 +4,28 @@
 //! and performs the corresponding MongoDB operations. It has been refactored
 //! into separate sub-modules for better organization:
 //!
+//! Only the result types are available without the `native` feature, so the
+//! formatter can render results in `wasm` builds.
+//!
 //! ## Module Structure
 //!
 //! - `context`: ExecutionContext for managing state and connections
-//! - `result`: Result types (ExecutionResult, ResultData, ExecutionStats)
+//! - `result`: Result types (ExecutionResult, ResultData, ExecutionStats and
+//!   the typed admin listings)
 //! - `router`: CommandRouter for dispatching commands to executors
 //! - `confirmation`: User confirmation for dangerous operations
 //! - `query`: QueryExecutor for CRUD operations
 //! - `admin`: AdminExecutor for administrative commands
+//! - `backup`: mongodump-style dump and restore used by AdminExecutor
+//! - `cache`: session cache of read results (`query.result_cache`)
+//! - `collection_backup`: copies taken before drop and renameCollection
+//! - `retry`: which failed commands are safe to run again
+//! - `trash`: `safety.trash` copies of deleted documents and `undo last-delete`
+//! - `current_op`: interactive `currentOp --watch` monitor used by AdminExecutor
+//! - `progress`: progress bars for exports, dumps, index builds and aggregations
 //! - `utility`: UtilityExecutor for utility commands
+//! - `describe`: plain-language summaries of commands, used by UtilityExecutor
+//! - `lookup`: interactive `$lookup` wizard run by UtilityExecutor
 //!
 //! ## Architecture
 //!
@@ -44,6 +57,8 @@
 //! ## Usage Example
 //!
 //! ```rust,no_run
+//! # #[cfg(feature = "native")]
+//! # mod example {
 //! use mongosh::executor::ExecutionContext;
 //! use mongosh::connection::ConnectionManager;
 //! use mongosh::repl::SharedState;
@@ -55,24 +70,64 @@
 //!     let result = context.execute(command).await.unwrap();
 //!     println!("{:?}", result);
 //! }
+//! # }
 //! ```
 
 // Module declarations
+#[cfg(feature = "native")]
 mod admin;
+#[cfg(feature = "native")]
+mod backup;
+#[cfg(feature = "native")]
+mod cache;
+#[cfg(feature = "native")]
+mod collection_backup;
+#[cfg(feature = "native")]
 mod confirmation;
+#[cfg(feature = "native")]
 mod context;
+#[cfg(feature = "native")]
+mod csv_import;
+#[cfg(feature = "native")]
+mod current_op;
+#[cfg(feature = "native")]
+mod describe;
+#[cfg(feature = "native")]
 mod export;
+#[cfg(feature = "native")]
 mod killable;
+#[cfg(feature = "native")]
+mod lookup;
+#[cfg(feature = "native")]
+mod progress;
+#[cfg(feature = "native")]
 mod query;
 mod result;
+#[cfg(feature = "native")]
+mod retry;
+#[cfg(feature = "native")]
 mod router;
+#[cfg(feature = "native")]
+mod trash;
+#[cfg(feature = "native")]
 mod utility;
 
 // Re-export public types
-pub use context::ExecutionContext;
+#[cfg(feature = "native")]
+pub use cache::ResultCache;
+#[cfg(feature = "native")]
+pub use context::{ConfirmationMode, ExecutionContext};
+#[cfg(feature = "native")]
 #[allow(unused_imports)]
 pub use killable::run_killable_command;
+#[cfg(feature = "native")]
+#[allow(unused_imports)]
+pub use query::{PageBookmark, QueryExecutor};
 pub use result::{ExecutionResult, ResultData};
+#[cfg(feature = "native")]
+#[allow(unused_imports)]
+pub use retry::{is_read_only, retry_hint, should_auto_retry};
+#[cfg(feature = "native")]
 pub use router::CommandRouter;
 
 // ExecutionStats is part of the public API (used in lib and tests)
@@ -80,14 +135,24 @@ pub use router::CommandRouter;
 #[cfg_attr(not(test), allow(unused_imports))]
 pub use result::ExecutionStats;
 
+// Typed admin listings carried by ResultData
+#[allow(unused_imports)]
+pub use result::{CollectionInfo, DatabaseInfo, IndexInfo, UserInfo};
+
 // Re-export for convenience
+#[cfg(feature = "native")]
 use crate::error::Result;
+#[cfg(feature = "native")]
 use crate::parser::Command;
 
+#[cfg(feature = "native")]
 impl ExecutionContext {
     /// Execute a command using the command router
     ///
-    /// This is the main entry point for command execution.
+    /// This is the main entry point for command execution. Successful results
+    /// carrying data are kept for `output last` and `_`. With
+    /// `query.result_cache` on, repeated reads are answered from the result
+    /// cache, and writes clear it.
     ///
     /// # Arguments
     /// * `command` - Parsed command to execute
@@ -95,8 +160,62 @@ impl ExecutionContext {
     /// # Returns
     /// * `Result<ExecutionResult>` - Execution result or error
     pub async fn execute(&self, command: Command) -> Result<ExecutionResult> {
-        let router = CommandRouter::new(self.clone()).await?;
-        router.route(command).await
+        let start = std::time::Instant::now();
+        let kind = crate::metrics::command_kind(&command);
+
+        let query_config = self.shared_state.get_query_config();
+        let cache_key = if query_config.result_cache {
+            cache::cache_key(
+                &self.get_current_datasource().await,
+                &self.get_current_database().await,
+                &command,
+            )
+        } else {
+            None
+        };
+        let ttl = std::time::Duration::from_secs(query_config.result_cache_ttl);
+        if let Some(key) = &cache_key {
+            let cached = self.shared_state.result_cache().get(key, ttl);
+            if let Some(result) = cached {
+                // `it` must not page the cursor of an older query
+                self.shared_state.clear_cursor().await;
+                self.shared_state.set_last_result(result.data.clone());
+                return Ok(result);
+            }
+        }
+        let writes = !is_read_only(&command);
+
+        let outcome = match CommandRouter::new(self.clone()).await {
+            Ok(router) => router.route(command).await,
+            Err(e) => Err(e),
+        };
+        crate::metrics::global().observe_command(
+            kind,
+            outcome.as_ref().is_ok_and(|result| result.success),
+            start.elapsed(),
+        );
+        // Even a failed write may have changed some documents
+        if writes {
+            self.shared_state.result_cache().clear();
+        }
+        let result = outcome?;
+
+        if let Some(key) = cache_key {
+            self.shared_state
+                .result_cache()
+                .insert(key, &result, query_config.result_cache_size);
+        }
+
+        // Messages such as "Output format set to: json" would replace the data
+        if result.success
+            && !matches!(
+                result.data,
+                ResultData::Message(_) | ResultData::None | ResultData::Stream(_)
+            )
+        {
+            self.shared_state.set_last_result(result.data.clone());
+        }
+        Ok(result)
     }
 }
 
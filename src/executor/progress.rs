@@ -0,0 +1,229 @@
+//! Progress bars for long-running operations
+//!
+//! Exports, dumps and restores, index builds and interactive aggregations
+//! report their progress through a [`ProgressTracker`]. With a known total it
+//! draws a bar with the rate and ETA, otherwise a spinner with the count and
+//! rate. Trackers made with [`ProgressTracker::for_terminal`] stay hidden when
+//! stderr is not a terminal or accessible mode is on, and only appear once the
+//! operation has run for [`SHOW_AFTER`], so quick commands don't flash a bar.
+
+use std::io::{self, IsTerminal};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use indicatif::{ProgressBar, ProgressDrawTarget, ProgressStyle};
+
+/// How long an operation runs before its progress is drawn
+pub const SHOW_AFTER: Duration = Duration::from_secs(1);
+
+/// What a tracker counts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProgressUnit {
+    /// Documents, keys or other items
+    Documents,
+    /// Bytes, e.g. of a file being read
+    Bytes,
+}
+
+/// Progress tracker for long-running operations
+///
+/// Tracks how much work is done and displays a progress bar with statistics
+/// like speed and ETA.
+pub struct ProgressTracker {
+    /// Amount processed so far
+    processed: AtomicU64,
+    /// Start time of the operation
+    start_time: Instant,
+    /// Progress bar (optional, can be disabled)
+    bar: Option<ProgressBar>,
+    /// What is counted
+    unit: ProgressUnit,
+    /// Whether the bar is drawn yet; delayed bars start hidden
+    shown: AtomicBool,
+}
+
+impl ProgressTracker {
+    /// Create a new progress tracker counting documents
+    ///
+    /// # Arguments
+    /// * `total` - Total number of documents if known (None for unknown)
+    /// * `enable_bar` - Whether to display a progress bar
+    ///
+    /// # Returns
+    /// * `Self` - New progress tracker instance, drawn immediately
+    pub fn new(total: Option<u64>, enable_bar: bool) -> Self {
+        let bar = enable_bar.then(|| {
+            let bar = ProgressBar::new_spinner();
+            bar.set_style(style(total.is_some(), ProgressUnit::Documents));
+            if let Some(total) = total {
+                bar.set_length(total);
+            }
+            bar
+        });
+        Self::with_bar(bar, ProgressUnit::Documents, true)
+    }
+
+    /// Create a tracker for an operation run from the terminal
+    ///
+    /// The tracker is disabled when stderr is not a terminal or in accessible
+    /// mode, and is drawn once the operation has run for [`SHOW_AFTER`].
+    ///
+    /// # Arguments
+    /// * `label` - Operation shown before the bar, e.g. a collection name
+    /// * `total` - Total amount if known; enables the bar and ETA
+    /// * `unit` - What is counted
+    /// * `accessible` - Whether accessible mode is on
+    ///
+    /// # Returns
+    /// * `Self` - New progress tracker instance
+    pub fn for_terminal(
+        label: &str,
+        total: Option<u64>,
+        unit: ProgressUnit,
+        accessible: bool,
+    ) -> Self {
+        if accessible || !io::stderr().is_terminal() {
+            return Self::with_bar(None, unit, false);
+        }
+
+        let bar = ProgressBar::with_draw_target(total, ProgressDrawTarget::hidden());
+        bar.set_style(style(total.is_some(), unit));
+        if !label.is_empty() {
+            bar.set_prefix(format!("{} ", label));
+        }
+        Self::with_bar(Some(bar), unit, false)
+    }
+
+    fn with_bar(bar: Option<ProgressBar>, unit: ProgressUnit, shown: bool) -> Self {
+        Self {
+            processed: AtomicU64::new(0),
+            start_time: Instant::now(),
+            bar,
+            unit,
+            shown: AtomicBool::new(shown),
+        }
+    }
+
+    /// Update progress with new count
+    ///
+    /// # Arguments
+    /// * `count` - Total amount processed so far
+    pub fn update(&self, count: u64) {
+        self.processed.store(count, Ordering::Relaxed);
+
+        if let Some(ref bar) = self.bar {
+            self.reveal(bar);
+            bar.set_position(count);
+        }
+    }
+
+    /// Set or change the total, e.g. when an index build moves to a new phase
+    ///
+    /// # Arguments
+    /// * `total` - Total amount; switches a spinner to a bar with ETA
+    pub fn set_total(&self, total: u64) {
+        if let Some(ref bar) = self.bar {
+            if bar.length().is_none() {
+                bar.set_style(style(true, self.unit));
+            }
+            bar.set_length(total);
+        }
+    }
+
+    /// Show a short status after the counts, e.g. the current phase
+    ///
+    /// # Arguments
+    /// * `message` - Status text
+    pub fn set_message(&self, message: impl Into<String>) {
+        if let Some(ref bar) = self.bar {
+            bar.set_message(message.into());
+        }
+    }
+
+    /// Whether progress is drawn, e.g. to skip polling the server for it
+    pub fn is_enabled(&self) -> bool {
+        self.bar.is_some()
+    }
+
+    /// Finish and clear the progress bar
+    pub fn finish(&self) {
+        if let Some(ref bar) = self.bar {
+            bar.finish_and_clear();
+        }
+    }
+
+    /// Start drawing a delayed bar once the operation has run long enough
+    fn reveal(&self, bar: &ProgressBar) {
+        if self.shown.load(Ordering::Relaxed) || self.start_time.elapsed() < SHOW_AFTER {
+            return;
+        }
+        self.shown.store(true, Ordering::Relaxed);
+        bar.set_draw_target(ProgressDrawTarget::stderr());
+        bar.enable_steady_tick(Duration::from_millis(120));
+    }
+}
+
+impl Drop for ProgressTracker {
+    fn drop(&mut self) {
+        // An operation that fails part way must not leave its bar behind
+        self.finish();
+    }
+}
+
+/// Bar style for a known total, spinner style otherwise
+fn style(has_total: bool, unit: ProgressUnit) -> ProgressStyle {
+    let template = match (has_total, unit) {
+        (true, ProgressUnit::Documents) => {
+            "{spinner:.green} {prefix}[{bar:40.cyan/blue}] {pos}/{len} ({per_sec}, ETA {eta}) {msg}"
+        }
+        (true, ProgressUnit::Bytes) => {
+            "{spinner:.green} {prefix}[{bar:40.cyan/blue}] {bytes}/{total_bytes} ({bytes_per_sec}, ETA {eta}) {msg}"
+        }
+        (false, ProgressUnit::Documents) => {
+            "{spinner:.green} {prefix}{pos} documents ({per_sec}) {msg}"
+        }
+        (false, ProgressUnit::Bytes) => "{spinner:.green} {prefix}{bytes} ({bytes_per_sec}) {msg}",
+    };
+    ProgressStyle::default_bar()
+        .template(template)
+        .unwrap()
+        .progress_chars("#>-")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_progress_tracker_with_total() {
+        let tracker = ProgressTracker::new(Some(1000), false);
+        tracker.update(500);
+        assert_eq!(tracker.processed.load(Ordering::Relaxed), 500);
+    }
+
+    #[test]
+    fn test_progress_tracker_without_total() {
+        let tracker = ProgressTracker::new(None, false);
+        tracker.update(500);
+        assert_eq!(tracker.processed.load(Ordering::Relaxed), 500);
+    }
+
+    #[test]
+    fn test_progress_tracker_accessible_is_disabled() {
+        let tracker = ProgressTracker::for_terminal("users", Some(10), ProgressUnit::Bytes, true);
+        assert!(!tracker.is_enabled());
+        tracker.set_total(20);
+        tracker.set_message("scanning");
+        tracker.update(5);
+        assert_eq!(tracker.processed.load(Ordering::Relaxed), 5);
+    }
+
+    #[test]
+    fn test_progress_styles() {
+        for has_total in [true, false] {
+            for unit in [ProgressUnit::Documents, ProgressUnit::Bytes] {
+                style(has_total, unit);
+            }
+        }
+    }
+}
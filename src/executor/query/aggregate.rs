@@ -13,6 +13,7 @@ use crate::error::{ExecutionError, Result};
 use crate::parser::AggregateOptions;
 use super::super::export::streaming::AggregateStreamingQuery;
 use super::super::killable::run_killable_command;
+use super::super::progress::{ProgressTracker, ProgressUnit};
 use super::super::result::{ExecutionResult, ExecutionStats, ResultData};
 
 /// Aggregate operations implementation
@@ -25,6 +26,11 @@ impl super::QueryExecutor {
         options: AggregateOptions,
         mode: crate::parser::QueryMode,
     ) -> Result<ExecutionResult> {
+        if options.parallel.is_some_and(|ranges| ranges > 1) {
+            return self
+                .execute_aggregate_parallel(collection, pipeline, options, mode)
+                .await;
+        }
         match mode {
             crate::parser::QueryMode::Interactive { .. } => {
                 self.execute_aggregate_interactive(collection, pipeline, options).await
@@ -52,6 +58,7 @@ impl super::QueryExecutor {
         let client_id = self.context.get_client_id();
         let cancel_token = self.context.get_cancel_token();
         let db_name = self.context.get_current_database().await;
+        let accessible = self.context.shared_state.get_accessible();
 
         // Execute aggregate with killOp support
         let documents = run_killable_command(
@@ -139,8 +146,14 @@ impl super::QueryExecutor {
                         .await
                         .map_err(|e| ExecutionError::QueryFailed(e.to_string()))?;
 
-                    // Collect results
+                    // Collect results; large pipelines show how many have arrived
                     let mut documents = Vec::new();
+                    let tracker = ProgressTracker::for_terminal(
+                        "aggregate",
+                        None,
+                        ProgressUnit::Documents,
+                        accessible,
+                    );
 
                     while let Some(doc) = cursor
                         .try_next()
@@ -148,7 +161,9 @@ impl super::QueryExecutor {
                         .map_err(|e| ExecutionError::CursorError(e.to_string()))?
                     {
                         documents.push(doc);
+                        tracker.update(documents.len() as u64);
                     }
+                    tracker.finish();
 
                     let count = documents.len();
                     info!("Aggregation returned {} documents", count);
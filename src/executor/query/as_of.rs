@@ -0,0 +1,384 @@
+//! Point-in-time reads for query executor
+//!
+//! This module runs `db.collection.asOf(timestamp, filter)` and
+//! `find(...).asOf(timestamp)`. The find is sent with a snapshot read concern
+//! at the requested cluster time, which returns the exact state of the
+//! collection as long as the server still holds that history (see
+//! `minSnapshotHistoryWindowInSeconds`, or a point-in-time secondary).
+//!
+//! When the snapshot is no longer available the current documents are
+//! returned instead, reconciled with the oplog: documents inserted after the
+//! timestamp are dropped, documents changed since are marked, and documents
+//! deleted since are listed by `_id`. Changes made inside multi-document
+//! transactions are not visible to this fallback.
+
+use std::collections::HashMap;
+
+use futures::stream::TryStreamExt;
+use mongodb::bson::{Bson, Document, Timestamp, doc};
+use mongodb::error::ErrorKind;
+use tracing::{debug, warn};
+
+use crate::error::{ExecutionError, MongoshError, Result};
+use crate::parser::FindOptions;
+
+use super::super::result::{ExecutionResult, ExecutionStats, ResultData};
+
+/// Server error codes meaning the snapshot at the requested time is gone
+const SNAPSHOT_ERROR_CODES: &[i32] = &[
+    239, // SnapshotTooOld
+    246, // SnapshotUnavailable
+];
+
+/// Field added to documents reconstructed from the oplog
+const AS_OF_FIELD: &str = "_asOf";
+
+/// Build a `find` command reading the collection at a cluster time
+///
+/// # Arguments
+/// * `collection` - Collection name
+/// * `filter` - Query filter
+/// * `options` - Find options
+/// * `at` - Cluster time to read at
+///
+/// # Returns
+/// * `Document` - Command for `runCursorCommand`
+pub(super) fn build_snapshot_find(
+    collection: &str,
+    filter: Document,
+    options: &FindOptions,
+    at: Timestamp,
+) -> Document {
+    let mut command = doc! { "find": collection, "filter": filter };
+    if let Some(sort) = &options.sort {
+        command.insert("sort", sort.clone());
+    }
+    if let Some(projection) = &options.projection {
+        command.insert("projection", projection.clone());
+    }
+    if let Some(skip) = options.skip {
+        command.insert("skip", skip as i64);
+    }
+    if let Some(limit) = options.limit.filter(|limit| *limit > 0) {
+        command.insert("limit", limit);
+    }
+    if let Some(batch_size) = options.batch_size {
+        command.insert("batchSize", batch_size as i64);
+    }
+    if let Some(collation) = &options.collation {
+        command.insert("collation", collation.clone());
+    }
+    if let Some(hint) = &options.hint {
+        command.insert("hint", hint.clone());
+    }
+    if let Some(max_time_ms) = options.max_time_ms {
+        command.insert("maxTimeMS", max_time_ms as i64);
+    }
+    command.insert(
+        "readConcern",
+        doc! { "level": "snapshot", "atClusterTime": Bson::Timestamp(at) },
+    );
+    command
+}
+
+/// Changes recorded in the oplog for one document after the requested time
+#[derive(Debug)]
+struct OplogChanges {
+    /// Operation type of the first change (`i`, `u` or `d`)
+    first_op: String,
+    /// Operation type of the latest change
+    last_op: String,
+    /// Number of changes
+    count: i64,
+    /// Time of the latest change
+    last_ts: Bson,
+}
+
+/// Key identifying a document `_id` in a hash map
+fn id_key(id: &Bson) -> String {
+    id.clone().into_relaxed_extjson().to_string()
+}
+
+/// Reconcile current documents with oplog entries written after a timestamp
+///
+/// Documents first inserted after the timestamp are removed, documents
+/// changed since are marked with `_asOf: { state: "modified", ... }` and, when
+/// `list_deleted` is set, documents deleted since are appended as
+/// `{ _id, _asOf: { state: "deleted", ... } }` since their content is unknown.
+///
+/// # Arguments
+/// * `current` - Documents matching the filter now
+/// * `entries` - Oplog entries for the namespace, oldest first
+/// * `list_deleted` - Whether to append placeholders for deleted documents
+///
+/// # Returns
+/// * `Vec<Document>` - Approximate documents at the timestamp
+pub(super) fn apply_oplog(
+    current: Vec<Document>,
+    entries: &[Document],
+    list_deleted: bool,
+) -> Vec<Document> {
+    let mut order = Vec::new();
+    let mut changes: HashMap<String, (Bson, OplogChanges)> = HashMap::new();
+
+    for entry in entries {
+        let op = entry.get_str("op").unwrap_or_default();
+        let id = match op {
+            "i" | "d" => entry.get_document("o").ok().and_then(|o| o.get("_id")),
+            "u" => entry.get_document("o2").ok().and_then(|o2| o2.get("_id")),
+            _ => None,
+        };
+        let Some(id) = id else {
+            continue;
+        };
+        let ts = entry.get("ts").cloned().unwrap_or(Bson::Null);
+
+        let key = id_key(id);
+        match changes.get_mut(&key) {
+            Some((_, change)) => {
+                change.last_op = op.to_string();
+                change.count += 1;
+                change.last_ts = ts;
+            }
+            None => {
+                order.push(key.clone());
+                let change = OplogChanges {
+                    first_op: op.to_string(),
+                    last_op: op.to_string(),
+                    count: 1,
+                    last_ts: ts,
+                };
+                changes.insert(key, (id.clone(), change));
+            }
+        }
+    }
+
+    let marker = |state: &str, change: &OplogChanges| {
+        doc! {
+            "state": state,
+            "changesSince": change.count,
+            "lastChange": change.last_ts.clone(),
+        }
+    };
+
+    let mut documents: Vec<Document> = current
+        .into_iter()
+        .filter_map(|mut document| {
+            let Some((_, change)) = document.get("_id").and_then(|id| changes.get(&id_key(id)))
+            else {
+                return Some(document);
+            };
+            if change.first_op == "i" {
+                return None;
+            }
+            document.insert(AS_OF_FIELD, marker("modified", change));
+            Some(document)
+        })
+        .collect();
+
+    if list_deleted {
+        for key in &order {
+            let (id, change) = &changes[key];
+            if change.last_op == "d" && change.first_op != "i" {
+                documents.push(doc! { "_id": id.clone(), AS_OF_FIELD: marker("deleted", change) });
+            }
+        }
+    }
+
+    documents
+}
+
+/// Check whether an error means the requested snapshot is no longer held
+fn is_snapshot_unavailable(error: &mongodb::error::Error) -> bool {
+    matches!(error.kind.as_ref(), ErrorKind::Command(c) if SNAPSHOT_ERROR_CODES.contains(&c.code))
+}
+
+/// Point-in-time read operations implementation
+impl super::QueryExecutor {
+    /// Execute a find at a past cluster time
+    ///
+    /// # Arguments
+    /// * `collection` - Collection name
+    /// * `filter` - Query filter
+    /// * `options` - Find options
+    /// * `at` - Cluster time to read at
+    ///
+    /// # Returns
+    /// * `Result<ExecutionResult>` - Documents as they were at the timestamp
+    pub(super) async fn execute_find_as_of(
+        &self,
+        collection: String,
+        filter: Document,
+        options: FindOptions,
+        at: Timestamp,
+    ) -> Result<ExecutionResult> {
+        let db = self.context.get_database().await?;
+        let command = build_snapshot_find(&collection, filter.clone(), &options, at);
+        debug!("Executing point-in-time find: {:?}", command);
+
+        let snapshot = match db.run_cursor_command(command).await {
+            Ok(cursor) => cursor.try_collect::<Vec<Document>>().await,
+            Err(e) => Err(e),
+        };
+        let documents = match snapshot {
+            Ok(documents) => documents,
+            Err(e) if is_snapshot_unavailable(&e) => {
+                self.reconstruct_from_oplog(&collection, filter, options, at)
+                    .await?
+            }
+            Err(e) => return Err(MongoshError::MongoDb(e)),
+        };
+
+        let count = documents.len();
+        Ok(ExecutionResult {
+            success: true,
+            data: ResultData::Documents(documents),
+            stats: ExecutionStats {
+                execution_time_ms: 0,
+                documents_returned: count,
+                documents_affected: None,
+            },
+            error: None,
+        })
+    }
+
+    /// Approximate the documents at a timestamp from current data and the oplog
+    async fn reconstruct_from_oplog(
+        &self,
+        collection: &str,
+        filter: Document,
+        options: FindOptions,
+        at: Timestamp,
+    ) -> Result<Vec<Document>> {
+        let client = self.context.get_client().await?;
+        let db_name = self.context.get_current_database().await;
+        let oplog = client.database("local").collection::<Document>("oplog.rs");
+
+        // The oplog has to reach back to the requested time
+        let oldest = oplog
+            .find_one(doc! {})
+            .sort(doc! { "$natural": 1 })
+            .await?
+            .and_then(|entry| entry.get_timestamp("ts").ok());
+        match oldest {
+            Some(ts) if (ts.time, ts.increment) <= (at.time, at.increment) => {}
+            _ => {
+                return Err(ExecutionError::InvalidOperation(format!(
+                    "No snapshot or oplog history reaches back to Timestamp({}, {})",
+                    at.time, at.increment
+                ))
+                .into());
+            }
+        }
+
+        let ns = format!("{}.{}", db_name, collection);
+        let entries: Vec<Document> = oplog
+            .find(doc! { "ns": &ns, "ts": { "$gt": Bson::Timestamp(at) } })
+            .sort(doc! { "$natural": 1 })
+            .projection(doc! { "op": 1, "ts": 1, "o._id": 1, "o2._id": 1 })
+            .await?
+            .try_collect()
+            .await?;
+
+        warn!(
+            "Snapshot history no longer covers Timestamp({}, {}); \
+             showing current documents reconciled with {} oplog entries",
+            at.time,
+            at.increment,
+            entries.len()
+        );
+
+        let mut find_options = mongodb::options::FindOptions::default();
+        find_options.sort = options.sort;
+        find_options.projection = options.projection;
+        find_options.skip = options.skip;
+        find_options.limit = options.limit;
+        let current: Vec<Document> = client
+            .database(&db_name)
+            .collection::<Document>(collection)
+            .find(filter.clone())
+            .with_options(find_options)
+            .await?
+            .try_collect()
+            .await?;
+
+        // Deleted documents can only be listed when the filter cannot exclude them
+        let list_deleted = filter.keys().all(|key| key == "_id");
+        Ok(apply_oplog(current, &entries, list_deleted))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ts(time: u32) -> Bson {
+        Bson::Timestamp(Timestamp { time, increment: 1 })
+    }
+
+    #[test]
+    fn test_build_snapshot_find() {
+        let options = FindOptions {
+            sort: Some(doc! { "createdAt": -1 }),
+            limit: Some(10),
+            ..Default::default()
+        };
+        let at = Timestamp {
+            time: 1700000000,
+            increment: 2,
+        };
+        let command = build_snapshot_find("orders", doc! { "status": "paid" }, &options, at);
+        assert_eq!(
+            command,
+            doc! {
+                "find": "orders",
+                "filter": { "status": "paid" },
+                "sort": { "createdAt": -1 },
+                "limit": 10_i64,
+                "readConcern": { "level": "snapshot", "atClusterTime": Bson::Timestamp(at) },
+            }
+        );
+    }
+
+    #[test]
+    fn test_apply_oplog() {
+        let current = vec![
+            doc! { "_id": 1, "status": "paid" },
+            doc! { "_id": 2, "status": "new" },
+            doc! { "_id": 3, "status": "new" },
+        ];
+        let entries = vec![
+            doc! { "op": "u", "ts": ts(10), "o2": { "_id": 1 } },
+            doc! { "op": "i", "ts": ts(11), "o": { "_id": 3 } },
+            doc! { "op": "d", "ts": ts(12), "o": { "_id": 4 } },
+            doc! { "op": "i", "ts": ts(13), "o": { "_id": 5 } },
+            doc! { "op": "d", "ts": ts(14), "o": { "_id": 5 } },
+            doc! { "op": "u", "ts": ts(15), "o2": { "_id": 1 } },
+            doc! { "op": "n", "ts": ts(16), "o": { "msg": "periodic noop" } },
+        ];
+
+        let documents = apply_oplog(current, &entries, true);
+        assert_eq!(
+            documents,
+            vec![
+                doc! {
+                    "_id": 1,
+                    "status": "paid",
+                    "_asOf": { "state": "modified", "changesSince": 2_i64, "lastChange": ts(15) },
+                },
+                doc! { "_id": 2, "status": "new" },
+                doc! {
+                    "_id": 4,
+                    "_asOf": { "state": "deleted", "changesSince": 1_i64, "lastChange": ts(12) },
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_apply_oplog_without_deleted() {
+        let entries = vec![doc! { "op": "d", "ts": ts(12), "o": { "_id": 4 } }];
+        let documents = apply_oplog(vec![doc! { "_id": 1 }], &entries, false);
+        assert_eq!(documents, vec![doc! { "_id": 1 }]);
+    }
+}
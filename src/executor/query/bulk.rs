@@ -0,0 +1,150 @@
+//! Rate-limited bulk updates
+//!
+//! `bulkUpdateWithRateLimit()` walks the documents matching a filter in `_id`
+//! order, updating one batch at a time and sleeping between batches so that
+//! no more than `opsPerSecond` documents are written per second. Each batch
+//! re-applies the filter, so documents changed by someone else in the
+//! meantime are skipped rather than overwritten.
+
+use std::time::{Duration, Instant};
+
+use futures::stream::TryStreamExt;
+use mongodb::Collection;
+use mongodb::bson::{Bson, Document, doc};
+use tracing::info;
+
+use crate::error::{MongoshError, Result};
+use super::super::result::{ExecutionResult, ExecutionStats, ResultData};
+
+/// Time to wait before the next batch to stay within the rate limit
+///
+/// # Arguments
+/// * `processed` - Documents written so far
+/// * `ops_per_second` - Maximum documents written per second
+/// * `elapsed` - Time since the first batch started
+///
+/// # Returns
+/// * `Duration` - Delay before the next batch, zero if behind schedule
+fn throttle_delay(processed: u64, ops_per_second: u32, elapsed: Duration) -> Duration {
+    let target = Duration::from_secs_f64(processed as f64 / ops_per_second.max(1) as f64);
+    target.saturating_sub(elapsed)
+}
+
+impl super::QueryExecutor {
+    /// Execute bulkUpdateWithRateLimit command
+    ///
+    /// # Arguments
+    /// * `collection` - Collection name
+    /// * `filter` - Query filter
+    /// * `update` - Update document or pipeline stage document
+    /// * `batch_size` - Documents updated per batch
+    /// * `ops_per_second` - Maximum documents updated per second
+    ///
+    /// # Returns
+    /// * `Result<ExecutionResult>` - Total matched and modified counts
+    pub(super) async fn execute_bulk_update_with_rate_limit(
+        &self,
+        collection: String,
+        filter: Document,
+        update: Document,
+        batch_size: u32,
+        ops_per_second: u32,
+    ) -> Result<ExecutionResult> {
+        let client = self.context.get_client().await?;
+        let cancel_token = self.context.get_cancel_token();
+        let db_name = self.context.get_current_database().await;
+        let coll: Collection<Document> = client.database(&db_name).collection(&collection);
+
+        let start = Instant::now();
+        let mut last_id: Option<Bson> = None;
+        let mut matched = 0u64;
+        let mut modified = 0u64;
+        let mut processed = 0u64;
+        let mut batches = 0u64;
+
+        loop {
+            let batch_filter = match &last_id {
+                Some(id) => doc! { "$and": [filter.clone(), { "_id": { "$gt": id.clone() } }] },
+                None => filter.clone(),
+            };
+            let ids: Vec<Bson> = coll
+                .find(batch_filter)
+                .projection(doc! { "_id": 1 })
+                .sort(doc! { "_id": 1 })
+                .limit(batch_size as i64)
+                .await?
+                .try_collect::<Vec<Document>>()
+                .await?
+                .into_iter()
+                .filter_map(|d| d.get("_id").cloned())
+                .collect();
+
+            let Some(next_last) = ids.last().cloned() else {
+                break;
+            };
+            let count = ids.len() as u64;
+
+            let result = coll
+                .update_many(
+                    doc! { "$and": [filter.clone(), { "_id": { "$in": ids } }] },
+                    update.clone(),
+                )
+                .await?;
+            processed += count;
+            matched += result.matched_count;
+            modified += result.modified_count;
+            batches += 1;
+            last_id = Some(next_last);
+
+            info!(
+                "bulkUpdateWithRateLimit: batch {} on '{}': {} matched, {} modified so far",
+                batches, collection, matched, modified
+            );
+
+            if count < batch_size as u64 {
+                break;
+            }
+
+            let delay = throttle_delay(processed, ops_per_second, start.elapsed());
+            tokio::select! {
+                _ = tokio::time::sleep(delay) => {}
+                _ = cancel_token.cancelled() => {
+                    return Err(MongoshError::Generic(format!(
+                        "bulkUpdateWithRateLimit() interrupted after {} batches: {} matched, {} modified",
+                        batches, matched, modified
+                    )));
+                }
+            }
+        }
+
+        Ok(ExecutionResult {
+            success: true,
+            data: ResultData::Update { matched, modified },
+            stats: ExecutionStats {
+                execution_time_ms: 0,
+                documents_returned: 0,
+                documents_affected: Some(modified),
+            },
+            error: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_throttle_delay() {
+        // 500 documents at 100/s should take 5s
+        assert_eq!(
+            throttle_delay(500, 100, Duration::from_secs(2)),
+            Duration::from_secs(3)
+        );
+        // Already behind schedule
+        assert_eq!(
+            throttle_delay(500, 100, Duration::from_secs(6)),
+            Duration::ZERO
+        );
+    }
+}
@@ -0,0 +1,636 @@
+//! Collection diff for query executor
+//!
+//! This module runs `db.collection.diff(other, options)`. Both collections are
+//! read sorted by `_id` and merged in a single pass, so neither has to fit in
+//! memory. Documents present on one side only and documents whose fields
+//! differ are counted; the first few are shown in the summary and all of them
+//! can be written to a JSON Lines file with `{ out: "diff.jsonl" }`.
+//!
+//! The other collection may live in another database (`{ db }`) or behind a
+//! configured datasource (`{ datasource }`), e.g. to verify a migration.
+
+use std::cmp::Ordering;
+
+use futures::stream::{self, Stream, TryStreamExt};
+use mongodb::bson::{Bson, Document, doc};
+use tokio::fs::File;
+use tokio::io::{AsyncWriteExt, BufWriter};
+use tracing::info;
+
+use crate::error::{ExecutionError, MongoshError, Result};
+use crate::parser::DiffOptions;
+
+use super::super::result::{ExecutionResult, ExecutionStats, ResultData};
+
+/// Differences shown in the summary; the output file receives all of them
+const MAX_SHOWN_DIFFERENCES: usize = 20;
+
+/// One difference between the two collections
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum Difference {
+    /// Document only in the first collection
+    OnlyInA(Document),
+    /// Document only in the second collection
+    OnlyInB(Document),
+    /// Same `_id` on both sides with differing fields
+    Changed {
+        a: Document,
+        b: Document,
+        fields: Vec<String>,
+    },
+}
+
+impl Difference {
+    /// Short form for the summary: `{ _id, status, fields? }`
+    fn summary(&self) -> Document {
+        let mut summary = doc! { "_id": self.id(), "status": self.status() };
+        if let Difference::Changed { fields, .. } = self {
+            summary.insert("fields", fields.clone());
+        }
+        summary
+    }
+
+    /// Full form for the output file, including the documents involved
+    fn to_document(&self) -> Document {
+        let mut document = self.summary();
+        match self {
+            Difference::OnlyInA(a) => {
+                document.insert("a", a.clone());
+            }
+            Difference::OnlyInB(b) => {
+                document.insert("b", b.clone());
+            }
+            Difference::Changed { a, b, .. } => {
+                document.insert("a", a.clone());
+                document.insert("b", b.clone());
+            }
+        }
+        document
+    }
+
+    fn id(&self) -> Bson {
+        let document = match self {
+            Difference::OnlyInA(document) | Difference::OnlyInB(document) => document,
+            Difference::Changed { a, .. } => a,
+        };
+        document.get("_id").cloned().unwrap_or(Bson::Null)
+    }
+
+    fn status(&self) -> &'static str {
+        match self {
+            Difference::OnlyInA(_) => "onlyInA",
+            Difference::OnlyInB(_) => "onlyInB",
+            Difference::Changed { .. } => "changed",
+        }
+    }
+}
+
+/// Which cursor(s) to advance after comparing the current documents
+#[derive(Debug, PartialEq)]
+pub(super) enum Step {
+    /// Only the first side has this `_id`
+    TakeA,
+    /// Only the second side has this `_id`
+    TakeB,
+    /// Both sides have this `_id`
+    TakeBoth,
+}
+
+/// Decide how to advance a merge of two `_id`-sorted streams
+///
+/// # Arguments
+/// * `a` - Current document of the first collection
+/// * `b` - Current document of the second collection
+///
+/// # Returns
+/// * `Option<Step>` - Next step, `None` once both sides are exhausted
+pub(super) fn next_step(a: Option<&Document>, b: Option<&Document>) -> Option<Step> {
+    match (a, b) {
+        (None, None) => None,
+        (Some(_), None) => Some(Step::TakeA),
+        (None, Some(_)) => Some(Step::TakeB),
+        (Some(a), Some(b)) => {
+            let null = Bson::Null;
+            let a_id = a.get("_id").unwrap_or(&null);
+            let b_id = b.get("_id").unwrap_or(&null);
+            Some(match compare_bson(a_id, b_id) {
+                Ordering::Less => Step::TakeA,
+                Ordering::Greater => Step::TakeB,
+                Ordering::Equal => Step::TakeBoth,
+            })
+        }
+    }
+}
+
+/// Cursors and current documents of a merge, and whether it has started
+type MergeState<A, B> = (A, B, Option<Document>, Option<Document>, bool);
+
+/// Merge two `_id`-sorted document streams into their differences
+///
+/// # Arguments
+/// * `left` - Documents of the first collection, sorted by `_id`
+/// * `right` - Documents of the second collection, sorted by `_id`
+///
+/// # Returns
+/// * `impl Stream` - One item per `_id`: the difference, or `None` when both
+///   sides hold identical documents
+pub(super) fn merge_differences<A, B>(
+    left: A,
+    right: B,
+) -> impl Stream<Item = Result<Option<Difference>>>
+where
+    A: Stream<Item = Result<Document>> + Unpin,
+    B: Stream<Item = Result<Document>> + Unpin,
+{
+    stream::try_unfold((left, right, None, None, false), merge_step)
+}
+
+/// Compare the current documents of a merge and advance past them
+async fn merge_step<A, B>(
+    (mut left, mut right, mut a, mut b, started): MergeState<A, B>,
+) -> Result<Option<(Option<Difference>, MergeState<A, B>)>>
+where
+    A: Stream<Item = Result<Document>> + Unpin,
+    B: Stream<Item = Result<Document>> + Unpin,
+{
+    if !started {
+        a = left.try_next().await?;
+        b = right.try_next().await?;
+    }
+
+    let Some(step) = next_step(a.as_ref(), b.as_ref()) else {
+        return Ok(None);
+    };
+    let difference = match step {
+        Step::TakeA => {
+            let document = a.take().unwrap_or_default();
+            a = left.try_next().await?;
+            Some(Difference::OnlyInA(document))
+        }
+        Step::TakeB => {
+            let document = b.take().unwrap_or_default();
+            b = right.try_next().await?;
+            Some(Difference::OnlyInB(document))
+        }
+        Step::TakeBoth => {
+            let (x, y) = (a.take().unwrap_or_default(), b.take().unwrap_or_default());
+            a = left.try_next().await?;
+            b = right.try_next().await?;
+            let fields = changed_fields(&x, &y);
+            (!fields.is_empty()).then_some(Difference::Changed { a: x, b: y, fields })
+        }
+    };
+    Ok(Some((difference, (left, right, a, b, true))))
+}
+
+/// Rank of a value's type in the server's sort order
+fn type_rank(value: &Bson) -> u8 {
+    match value {
+        Bson::MinKey => 0,
+        Bson::Null | Bson::Undefined => 1,
+        Bson::Int32(_) | Bson::Int64(_) | Bson::Double(_) | Bson::Decimal128(_) => 2,
+        Bson::String(_) | Bson::Symbol(_) => 3,
+        Bson::Document(_) => 4,
+        Bson::Array(_) => 5,
+        Bson::Binary(_) => 6,
+        Bson::ObjectId(_) => 7,
+        Bson::Boolean(_) => 8,
+        Bson::DateTime(_) => 9,
+        Bson::Timestamp(_) => 10,
+        Bson::RegularExpression(_) => 11,
+        Bson::MaxKey => 13,
+        _ => 12,
+    }
+}
+
+/// Value of an integer, `None` for other numbers
+fn as_i64(value: &Bson) -> Option<i64> {
+    match value {
+        Bson::Int32(n) => Some(*n as i64),
+        Bson::Int64(n) => Some(*n),
+        _ => None,
+    }
+}
+
+/// Numeric value of a number, for comparing across numeric types
+fn as_f64(value: &Bson) -> f64 {
+    match value {
+        Bson::Int32(n) => *n as f64,
+        Bson::Int64(n) => *n as f64,
+        Bson::Double(n) => *n,
+        Bson::Decimal128(d) => d.to_string().parse().unwrap_or(f64::NAN),
+        _ => f64::NAN,
+    }
+}
+
+/// Compare two numbers, integers exactly even beyond 2^53
+fn compare_numbers(a: &Bson, b: &Bson) -> Ordering {
+    match (as_i64(a), as_i64(b)) {
+        (Some(x), Some(y)) => x.cmp(&y),
+        (Some(x), None) => compare_integer_float(x, as_f64(b)),
+        (None, Some(y)) => compare_integer_float(y, as_f64(a)).reverse(),
+        (None, None) => as_f64(a).total_cmp(&as_f64(b)),
+    }
+}
+
+/// Compare an integer with a floating point number without rounding either
+fn compare_integer_float(integer: i64, float: f64) -> Ordering {
+    // 2^63, the first float above every i64
+    const LIMIT: f64 = 9_223_372_036_854_775_808.0;
+
+    if float.is_nan() {
+        // NaN sorts before every other number
+        return Ordering::Greater;
+    }
+    if float >= LIMIT {
+        return Ordering::Less;
+    }
+    if float < -LIMIT {
+        return Ordering::Greater;
+    }
+
+    let whole = float.trunc();
+    integer
+        .cmp(&(whole as i64))
+        .then_with(|| 0.0_f64.total_cmp(&(float - whole)))
+}
+
+/// Compare two values in the order the server sorts them
+///
+/// Covers the types used as `_id` in practice: numbers compare across
+/// numeric types, strings compare bytewise (no collation), documents and
+/// arrays compare element by element.
+///
+/// # Arguments
+/// * `a` - First value
+/// * `b` - Second value
+///
+/// # Returns
+/// * `Ordering` - Sort order of `a` relative to `b`
+pub(super) fn compare_bson(a: &Bson, b: &Bson) -> Ordering {
+    let rank = type_rank(a).cmp(&type_rank(b));
+    if rank != Ordering::Equal {
+        return rank;
+    }
+
+    match (a, b) {
+        (Bson::String(x) | Bson::Symbol(x), Bson::String(y) | Bson::Symbol(y)) => x.cmp(y),
+        (Bson::ObjectId(x), Bson::ObjectId(y)) => x.bytes().cmp(&y.bytes()),
+        (Bson::Boolean(x), Bson::Boolean(y)) => x.cmp(y),
+        (Bson::DateTime(x), Bson::DateTime(y)) => x.cmp(y),
+        (Bson::Timestamp(x), Bson::Timestamp(y)) => {
+            (x.time, x.increment).cmp(&(y.time, y.increment))
+        }
+        (Bson::Binary(x), Bson::Binary(y)) => (x.bytes.len(), u8::from(x.subtype), &x.bytes)
+            .cmp(&(y.bytes.len(), u8::from(y.subtype), &y.bytes)),
+        (Bson::Document(x), Bson::Document(y)) => {
+            for ((xk, xv), (yk, yv)) in x.iter().zip(y.iter()) {
+                let order = type_rank(xv)
+                    .cmp(&type_rank(yv))
+                    .then_with(|| xk.cmp(yk))
+                    .then_with(|| compare_bson(xv, yv));
+                if order != Ordering::Equal {
+                    return order;
+                }
+            }
+            x.len().cmp(&y.len())
+        }
+        (Bson::Array(x), Bson::Array(y)) => {
+            for (xv, yv) in x.iter().zip(y.iter()) {
+                let order = compare_bson(xv, yv);
+                if order != Ordering::Equal {
+                    return order;
+                }
+            }
+            x.len().cmp(&y.len())
+        }
+        _ if type_rank(a) == 2 => compare_numbers(a, b),
+        _ => a
+            .clone()
+            .into_relaxed_extjson()
+            .to_string()
+            .cmp(&b.clone().into_relaxed_extjson().to_string()),
+    }
+}
+
+/// Dotted paths of the fields that differ between two documents
+///
+/// Embedded documents are compared field by field; any other value,
+/// including arrays, is compared as a whole.
+///
+/// # Arguments
+/// * `a` - Document from the first collection
+/// * `b` - Document from the second collection
+///
+/// # Returns
+/// * `Vec<String>` - Differing paths in document order, empty if equal
+pub(super) fn changed_fields(a: &Document, b: &Document) -> Vec<String> {
+    fn collect(prefix: &str, a: &Document, b: &Document, fields: &mut Vec<String>) {
+        let path = |key: &str| {
+            if prefix.is_empty() {
+                key.to_string()
+            } else {
+                format!("{}.{}", prefix, key)
+            }
+        };
+
+        for (key, a_value) in a {
+            match (a_value, b.get(key)) {
+                (Bson::Document(x), Some(Bson::Document(y))) => collect(&path(key), x, y, fields),
+                (x, Some(y)) if x == y => {}
+                _ => fields.push(path(key)),
+            }
+        }
+        for key in b.keys().filter(|key| !a.contains_key(key.as_str())) {
+            fields.push(path(key));
+        }
+    }
+
+    let mut fields = Vec::new();
+    collect("", a, b, &mut fields);
+    fields
+}
+
+/// Running totals of a diff
+#[derive(Debug, Default)]
+struct DiffReport {
+    only_in_a: u64,
+    only_in_b: u64,
+    changed: u64,
+    identical: u64,
+    shown: Vec<Document>,
+}
+
+impl DiffReport {
+    fn record(&mut self, difference: &Difference) {
+        match difference {
+            Difference::OnlyInA(_) => self.only_in_a += 1,
+            Difference::OnlyInB(_) => self.only_in_b += 1,
+            Difference::Changed { .. } => self.changed += 1,
+        }
+        if self.shown.len() < MAX_SHOWN_DIFFERENCES {
+            self.shown.push(difference.summary());
+        }
+    }
+}
+
+/// Collection diff implementation
+impl super::QueryExecutor {
+    /// Compare two collections matched on `_id`
+    ///
+    /// # Arguments
+    /// * `collection` - First collection, in the current database
+    /// * `other` - Second collection
+    /// * `options` - Filter, location of `other` and output file
+    ///
+    /// # Returns
+    /// * `Result<ExecutionResult>` - Summary with counts and the first differences
+    pub(super) async fn execute_diff(
+        &self,
+        collection: String,
+        other: String,
+        options: DiffOptions,
+    ) -> Result<ExecutionResult> {
+        let db = self.context.get_database().await?;
+        let (other_db, other_label) = match (&options.datasource, &options.database) {
+            (Some(datasource), database) => {
+                let (client, default_db) = self.context.connect_datasource(datasource).await?;
+                let name = database.clone().unwrap_or(default_db);
+                let label = format!("{}:{}.{}", datasource, name, other);
+                (client.database(&name), label)
+            }
+            (None, Some(database)) => (
+                self.context.get_client().await?.database(database),
+                format!("{}.{}", database, other),
+            ),
+            (None, None) => (db.clone(), format!("{}.{}", db.name(), other)),
+        };
+        info!(
+            "Comparing {}.{} with {}",
+            db.name(),
+            collection,
+            other_label
+        );
+
+        let mut writer = match &options.output {
+            Some(path) => Some(BufWriter::new(File::create(path).await.map_err(|e| {
+                MongoshError::Generic(format!("Failed to create '{}': {}", path, e))
+            })?)),
+            None => None,
+        };
+
+        let left = db
+            .collection::<Document>(&collection)
+            .find(options.filter.clone())
+            .sort(doc! { "_id": 1 })
+            .await?
+            .map_err(MongoshError::from);
+        let right = other_db
+            .collection::<Document>(&other)
+            .find(options.filter.clone())
+            .sort(doc! { "_id": 1 })
+            .await?
+            .map_err(MongoshError::from);
+
+        let cancel_token = self.context.get_cancel_token();
+        let mut report = DiffReport::default();
+        let mut differences = std::pin::pin!(merge_differences(left, right));
+
+        while let Some(difference) = differences.try_next().await? {
+            if cancel_token.is_cancelled() {
+                return Err(ExecutionError::Cancelled("diff() cancelled".to_string()).into());
+            }
+
+            let Some(difference) = difference else {
+                report.identical += 1;
+                continue;
+            };
+            report.record(&difference);
+            if let (Some(writer), Some(path)) = (writer.as_mut(), &options.output) {
+                let line = Bson::Document(difference.to_document()).into_relaxed_extjson();
+                writer
+                    .write_all(format!("{}\n", line).as_bytes())
+                    .await
+                    .map_err(|e| {
+                        MongoshError::Generic(format!("Failed to write '{}': {}", path, e))
+                    })?;
+            }
+        }
+
+        if let (Some(mut writer), Some(path)) = (writer, &options.output) {
+            writer
+                .flush()
+                .await
+                .map_err(|e| MongoshError::Generic(format!("Failed to write '{}': {}", path, e)))?;
+        }
+
+        let differences = report.only_in_a + report.only_in_b + report.changed;
+        let mut summary = doc! {
+            "a": format!("{}.{}", db.name(), collection),
+            "b": other_label,
+            "onlyInA": report.only_in_a as i64,
+            "onlyInB": report.only_in_b as i64,
+            "changed": report.changed as i64,
+            "identical": report.identical as i64,
+            "differences": report.shown,
+        };
+        if let Some(path) = &options.output {
+            summary.insert("out", path.as_str());
+        }
+
+        Ok(ExecutionResult {
+            success: true,
+            data: ResultData::Document(summary),
+            stats: ExecutionStats {
+                execution_time_ms: 0, // Will be set by caller
+                documents_returned: differences as usize,
+                documents_affected: None,
+            },
+            error: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mongodb::bson::oid::ObjectId;
+
+    /// Run the merge over in-memory `_id`-sorted documents
+    async fn diff(a: Vec<Document>, b: Vec<Document>) -> Vec<Option<Difference>> {
+        merge_differences(
+            stream::iter(a.into_iter().map(Ok)),
+            stream::iter(b.into_iter().map(Ok)),
+        )
+        .try_collect()
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_merge_by_id() {
+        let a = vec![
+            doc! { "_id": 1, "v": 1 },
+            doc! { "_id": 2, "v": 2 },
+            doc! { "_id": 4, "v": 4 },
+        ];
+        let b = vec![
+            doc! { "_id": 2, "v": 20 },
+            doc! { "_id": 3, "v": 3 },
+            doc! { "_id": 4, "v": 4 },
+        ];
+        let statuses: Vec<Option<(Bson, &str)>> = diff(a, b)
+            .await
+            .iter()
+            .map(|difference| {
+                difference
+                    .as_ref()
+                    .map(|difference| (difference.id(), difference.status()))
+            })
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                Some((Bson::Int32(1), "onlyInA")),
+                Some((Bson::Int32(2), "changed")),
+                Some((Bson::Int32(3), "onlyInB")),
+                None,
+            ]
+        );
+
+        assert!(diff(Vec::new(), Vec::new()).await.is_empty());
+    }
+
+    #[test]
+    fn test_compare_bson() {
+        assert_eq!(
+            compare_bson(&Bson::Int32(2), &Bson::Double(2.5)),
+            Ordering::Less
+        );
+        assert_eq!(
+            compare_bson(&Bson::Int64(3), &Bson::Int32(3)),
+            Ordering::Equal
+        );
+        assert_eq!(
+            compare_bson(&Bson::Int32(100), &Bson::String("1".to_string())),
+            Ordering::Less
+        );
+        // 2^53 + 1 and 2^53 are the same f64
+        let big = 9_007_199_254_740_993_i64;
+        assert_eq!(
+            compare_bson(&Bson::Int64(big), &Bson::Int64(big - 1)),
+            Ordering::Greater
+        );
+        assert_eq!(
+            compare_bson(&Bson::Int64(big), &Bson::Double((big - 1) as f64)),
+            Ordering::Greater
+        );
+        assert_eq!(
+            compare_bson(&Bson::Double(-0.5), &Bson::Int32(0)),
+            Ordering::Less
+        );
+        assert_eq!(
+            compare_bson(&Bson::Double(f64::NAN), &Bson::Int64(i64::MIN)),
+            Ordering::Less
+        );
+        assert_eq!(
+            compare_bson(&Bson::Int64(i64::MAX), &Bson::Double(1e19)),
+            Ordering::Less
+        );
+        assert_eq!(
+            compare_bson(
+                &Bson::String("b".to_string()),
+                &Bson::String("a".to_string())
+            ),
+            Ordering::Greater
+        );
+        let (x, y) = (ObjectId::new(), ObjectId::new());
+        assert_eq!(
+            compare_bson(&Bson::ObjectId(x), &Bson::ObjectId(y)),
+            x.bytes().cmp(&y.bytes())
+        );
+        assert_eq!(
+            compare_bson(&Bson::Null, &Bson::ObjectId(x)),
+            Ordering::Less
+        );
+    }
+
+    #[test]
+    fn test_changed_fields() {
+        let a = doc! { "_id": 1, "name": "Ada", "address": { "city": "Oslo", "zip": "0150" }, "tags": [1] };
+        let b = doc! { "_id": 1, "name": "Ada", "address": { "city": "Bergen", "zip": "0150" }, "tags": [1, 2], "email": "a@x" };
+        assert_eq!(
+            changed_fields(&a, &b),
+            vec!["address.city", "tags", "email"]
+        );
+        assert!(changed_fields(&a, &a).is_empty());
+    }
+
+    #[test]
+    fn test_difference_documents() {
+        let difference = Difference::Changed {
+            a: doc! { "_id": 7, "v": 1 },
+            b: doc! { "_id": 7, "v": 2 },
+            fields: vec!["v".to_string()],
+        };
+        assert_eq!(
+            difference.summary(),
+            doc! { "_id": 7, "status": "changed", "fields": ["v"] }
+        );
+        assert_eq!(
+            difference.to_document(),
+            doc! {
+                "_id": 7,
+                "status": "changed",
+                "fields": ["v"],
+                "a": { "_id": 7, "v": 1 },
+                "b": { "_id": 7, "v": 2 },
+            }
+        );
+
+        let mut report = DiffReport::default();
+        report.record(&Difference::OnlyInB(doc! { "_id": 8 }));
+        assert_eq!(report.only_in_b, 1);
+        assert_eq!(report.shown, vec![doc! { "_id": 8, "status": "onlyInB" }]);
+    }
+}
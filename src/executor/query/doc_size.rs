@@ -0,0 +1,83 @@
+//! Document size operations for query executor
+//!
+//! This module runs `db.collection.docSize(filter)` and
+//! `db.collection.largestDocuments(n)`. Sizes are computed on the server with
+//! `$bsonSize` so documents never have to be transferred to be measured.
+
+use mongodb::bson::{Document, doc};
+
+use crate::error::Result;
+use crate::parser::{AggregateOptions, QueryMode};
+use super::super::result::ExecutionResult;
+
+/// Field holding the encoded BSON size of each document, in bytes
+const SIZE_FIELD: &str = "bsonSize";
+
+/// Field holding the size as a percentage of the maximum document size
+const PERCENT_FIELD: &str = "percentOfMax";
+
+/// Maximum BSON document size accepted by the server (16 MiB)
+const MAX_BSON_SIZE: i64 = 16 * 1024 * 1024;
+
+/// Build the aggregation pipeline reporting the largest matching documents
+pub(super) fn build_doc_size_pipeline(filter: &Document, limit: i64) -> Vec<Document> {
+    let mut pipeline = Vec::new();
+    if !filter.is_empty() {
+        pipeline.push(doc! { "$match": filter.clone() });
+    }
+
+    pipeline.push(doc! { "$project": { "_id": 1, SIZE_FIELD: { "$bsonSize": "$$ROOT" } } });
+    pipeline.push(doc! { "$sort": { SIZE_FIELD: -1 } });
+    pipeline.push(doc! { "$limit": limit });
+    pipeline.push(doc! {
+        "$addFields": {
+            PERCENT_FIELD: {
+                "$round": [
+                    { "$multiply": [{ "$divide": [format!("${}", SIZE_FIELD), MAX_BSON_SIZE] }, 100] },
+                    2,
+                ],
+            },
+        },
+    });
+    pipeline
+}
+
+/// Document size operations implementation
+impl super::QueryExecutor {
+    /// Execute a document size report built by `docSize()` or `largestDocuments()`
+    pub(super) async fn execute_doc_size(
+        &self,
+        collection: String,
+        filter: Document,
+        limit: i64,
+        mode: QueryMode,
+    ) -> Result<ExecutionResult> {
+        let pipeline = build_doc_size_pipeline(&filter, limit);
+        self.execute_aggregate(collection, pipeline, AggregateOptions::default(), mode)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_doc_size_pipeline() {
+        let pipeline = build_doc_size_pipeline(&doc! { "level": "error" }, 5);
+        assert_eq!(pipeline.len(), 5);
+        assert!(pipeline[0].contains_key("$match"));
+        let project = pipeline[1].get_document("$project").unwrap();
+        assert_eq!(
+            project.get_document(SIZE_FIELD).unwrap().get_str("$bsonSize").unwrap(),
+            "$$ROOT"
+        );
+        assert_eq!(pipeline[3].get_i64("$limit").unwrap(), 5);
+    }
+
+    #[test]
+    fn test_build_doc_size_pipeline_without_filter() {
+        let pipeline = build_doc_size_pipeline(&Document::new(), 10);
+        assert!(pipeline[0].contains_key("$project"));
+    }
+}
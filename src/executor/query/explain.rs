@@ -66,8 +66,23 @@ impl super::QueryExecutor {
                 self.build_count_explain(&collection, filter, verbosity_str, &db).await?
             }
 
-            QueryCommand::Distinct { field, filter, .. } => {
-                self.build_distinct_explain(&collection, field, filter, verbosity_str, &db).await?
+            QueryCommand::Distinct { field, filter, collation, .. } => {
+                self.build_distinct_explain(&collection, field, filter, collation, verbosity_str, &db).await?
+            }
+
+            QueryCommand::DocSize { filter, limit, .. } => {
+                let pipeline = super::doc_size::build_doc_size_pipeline(&filter, limit);
+                self.build_aggregate_explain(&collection, pipeline, AggregateOptions::default(), verbosity_str, &db).await?
+            }
+
+            QueryCommand::Search { search, limit, .. } => {
+                let pipeline = super::search::build_search_pipeline(&search, limit);
+                self.build_aggregate_explain(&collection, pipeline, AggregateOptions::default(), verbosity_str, &db).await?
+            }
+
+            QueryCommand::VectorSearch { vector_search, .. } => {
+                let pipeline = super::search::build_vector_search_pipeline(&vector_search);
+                self.build_aggregate_explain(&collection, pipeline, AggregateOptions::default(), verbosity_str, &db).await?
             }
 
             _ => {
@@ -262,6 +277,7 @@ impl super::QueryExecutor {
         collection: &str,
         field: String,
         filter: Option<Document>,
+        collation: Option<Document>,
         verbosity: &str,
         db: &mongodb::Database,
     ) -> Result<Document> {
@@ -271,6 +287,9 @@ impl super::QueryExecutor {
         if let Some(filter_doc) = filter {
             distinct_cmd.insert("query", filter_doc);
         }
+        if let Some(collation) = collation {
+            distinct_cmd.insert("collation", collation);
+        }
 
         let mut explain_cmd = Document::new();
         explain_cmd.insert("explain", distinct_cmd);
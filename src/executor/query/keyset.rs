@@ -0,0 +1,334 @@
+//! Keyset pagination for deep skips
+//!
+//! Skipping documents is O(n) on the server: every skipped document is read
+//! and discarded. When keyset pagination is enabled, finds are sorted on a
+//! total order (the sort key plus an `_id` tiebreaker) and the sort key of
+//! the last document of each complete page is kept as a bookmark. A find
+//! asking for the page that starts right after it, i.e. the same query with
+//! `skip` equal to the documents seen so far, is rewritten into
+//! `{ key: { $gt: boundary } }` and reads no skipped documents at all.
+//!
+//! A skip that does not continue the bookmarked page runs as a plain skip:
+//! finding its boundary would take the same scan the rewrite avoids.
+//!
+//! Null and missing sort keys sort first ascending and last descending, and
+//! the range filter includes them accordingly. The range operators compare
+//! within one BSON type, so a sort key holding values of several types
+//! (apart from null) can still skip documents of the other types.
+
+use mongodb::bson::{Bson, Document, doc};
+use tracing::{debug, warn};
+
+use crate::error::Result;
+use crate::parser::FindOptions;
+
+use super::super::result::{ExecutionResult, ResultData};
+
+/// Where the last complete keyset page ended
+#[derive(Debug, Clone, PartialEq)]
+pub struct PageBookmark {
+    /// Namespace, filter and keyset sort of the paged find
+    query: Document,
+
+    /// Documents before the next page, the skip that continues it
+    position: u64,
+
+    /// Sort key values and `_id` of the page's last document, by path
+    boundary: Document,
+}
+
+/// A find paged with keyset pagination, to bookmark once it returns
+#[derive(Debug, Clone)]
+pub(super) struct KeysetPage {
+    /// Namespace, filter and keyset sort of the find
+    query: Document,
+
+    /// Skip requested by the find
+    start: u64,
+}
+
+/// Apply the configured pagination policy to a find
+impl super::QueryExecutor {
+    /// Warn about deep skips, or continue a bookmarked page with a range filter
+    ///
+    /// # Arguments
+    /// * `collection` - Collection name
+    /// * `filter` - Query filter
+    /// * `options` - Find options
+    ///
+    /// # Returns
+    /// * `Result<(Document, FindOptions, Option<KeysetPage>)>` - Filter and
+    ///   options to execute, and the page to bookmark when keyset pagination
+    ///   applies
+    pub(super) async fn apply_pagination_policy(
+        &self,
+        collection: &str,
+        filter: Document,
+        options: FindOptions,
+    ) -> Result<(Document, FindOptions, Option<KeysetPage>)> {
+        let skip = options.skip.filter(|skip| *skip > 0).unwrap_or(0);
+        let config = self.context.shared_state.get_query_config();
+        let deep = config.offset_warning_threshold > 0 && skip >= config.offset_warning_threshold;
+
+        if !config.keyset_pagination {
+            if deep {
+                warn!(
+                    "Skipping {} documents scans and discards each one on the server; \
+                     use a range filter on the sort key or enable keyset pagination (--keyset)",
+                    skip
+                );
+            }
+            return Ok((filter, options, None));
+        }
+
+        let Some(sort) = keyset_sort(options.sort.as_ref()) else {
+            debug!("Keyset pagination needs a single-field sort, using skip");
+            return Ok((filter, options, None));
+        };
+
+        let database = self.context.get_current_database().await;
+        let page = KeysetPage {
+            query: doc! {
+                "ns": format!("{}.{}", database, collection),
+                "filter": filter.clone(),
+                "sort": sort.clone(),
+            },
+            start: skip,
+        };
+        let options = FindOptions {
+            sort: Some(sort.clone()),
+            ..options
+        };
+        if skip == 0 {
+            return Ok((filter, options, Some(page)));
+        }
+
+        let bookmark = self
+            .context
+            .shared_state
+            .get_page_bookmark()
+            .filter(|bookmark| bookmark.query == page.query && bookmark.position == skip);
+        let Some(range) = bookmark.and_then(|bookmark| range_filter(&sort, &bookmark.boundary))
+        else {
+            if deep {
+                warn!(
+                    "Skipping {} documents scans and discards each one on the server; \
+                     keyset pagination only avoids it for the page after the previous one",
+                    skip
+                );
+            }
+            return Ok((filter, options, Some(page)));
+        };
+
+        debug!("Continued skip {} with keyset filter {:?}", skip, range);
+        let filter = if filter.is_empty() {
+            range
+        } else {
+            doc! { "$and": [filter, range] }
+        };
+        let options = FindOptions {
+            skip: None,
+            ..options
+        };
+        Ok((filter, options, Some(page)))
+    }
+
+    /// Bookmark the end of a keyset page so the next page can continue it
+    ///
+    /// Only complete results are bookmarked; a page still open for `it`,
+    /// an empty page or one whose projection dropped the sort key clears
+    /// the bookmark.
+    ///
+    /// # Arguments
+    /// * `page` - Page from [`apply_pagination_policy`](Self::apply_pagination_policy)
+    /// * `result` - Result of the find
+    pub(super) fn remember_page(&self, page: KeysetPage, result: &ExecutionResult) {
+        let ResultData::Documents(documents) = &result.data else {
+            self.context.shared_state.set_page_bookmark(None);
+            return;
+        };
+        let sort = page.query.get_document("sort").ok();
+        let bookmark = documents
+            .last()
+            .zip(sort)
+            .and_then(|(last, sort)| page_boundary(sort, last))
+            .map(|boundary| PageBookmark {
+                query: page.query.clone(),
+                position: page.start + documents.len() as u64,
+                boundary,
+            });
+        self.context.shared_state.set_page_bookmark(bookmark);
+    }
+}
+
+/// Build the sort used for keyset pagination
+///
+/// Only an unsorted query (which is paged by `_id`) or a single ascending or
+/// descending key is supported. `_id` is appended as a tiebreaker so that the
+/// order is total.
+fn keyset_sort(sort: Option<&Document>) -> Option<Document> {
+    let Some(sort) = sort.filter(|s| !s.is_empty()) else {
+        return Some(doc! { "_id": 1 });
+    };
+    if sort.len() > 1 {
+        return None;
+    }
+
+    let (key, direction) = sort.iter().next()?;
+    let direction = sort_direction(direction)?;
+    let mut keyset = doc! { key.clone(): direction };
+    if key != "_id" {
+        keyset.insert("_id", direction);
+    }
+    Some(keyset)
+}
+
+/// Read a numeric sort direction as 1 or -1
+fn sort_direction(value: &Bson) -> Option<i32> {
+    let direction = match value {
+        Bson::Int32(n) => *n as i64,
+        Bson::Int64(n) => *n,
+        Bson::Double(n) => *n as i64,
+        _ => return None,
+    };
+    match direction {
+        1 => Some(1),
+        -1 => Some(-1),
+        _ => None,
+    }
+}
+
+/// Sort key values of a document, by path; missing keys are null
+fn page_boundary(sort: &Document, document: &Document) -> Option<Document> {
+    sort.keys()
+        .map(|path| Some((path.clone(), value_at(document, path)?)))
+        .collect()
+}
+
+/// Build the filter matching documents after the boundary in sort order
+fn range_filter(sort: &Document, boundary: &Document) -> Option<Document> {
+    let mut keys = sort.iter();
+    let (key, direction) = keys.next()?;
+    let ascending = sort_direction(direction)? == 1;
+    let operator = if ascending { "$gt" } else { "$lt" };
+    let value = boundary.get(key)?.clone();
+
+    if keys.next().is_none() {
+        // Sorting on _id alone, which is unique and never null
+        if value == Bson::Null {
+            return None;
+        }
+        return Some(doc! { key.clone(): { operator: value } });
+    }
+
+    // Ties on the sort key are broken by _id
+    let id = boundary.get("_id").filter(|id| **id != Bson::Null)?.clone();
+    let tie = doc! { key.clone(): value.clone(), "_id": { operator: id } };
+    let filter = match (value, ascending) {
+        // Null and missing sort first: everything non-null comes after
+        (Bson::Null, true) => doc! { "$or": [{ key.clone(): { "$ne": null } }, tie] },
+        (Bson::Null, false) => tie,
+        // ... and last when descending
+        (value, true) => doc! { "$or": [{ key.clone(): { operator: value } }, tie] },
+        (value, false) => doc! {
+            "$or": [{ key.clone(): { operator: value } }, tie, { key.clone(): null }]
+        },
+    };
+    Some(filter)
+}
+
+/// Look up a dotted path, rejecting values that cannot bound a range
+///
+/// Missing values and `undefined` read as null, which is how they sort.
+/// Arrays sort by their smallest or largest element, so they are rejected.
+fn value_at(document: &Document, path: &str) -> Option<Bson> {
+    let mut current = document;
+    let mut parts = path.split('.').peekable();
+    while let Some(part) = parts.next() {
+        let value = match current.get(part) {
+            None | Some(Bson::Null | Bson::Undefined) => return Some(Bson::Null),
+            Some(value) => value,
+        };
+        if parts.peek().is_none() {
+            return match value {
+                Bson::Array(_) => None,
+                value => Some(value.clone()),
+            };
+        }
+        current = match value {
+            Bson::Document(document) => document,
+            Bson::Array(_) => return None,
+            _ => return Some(Bson::Null),
+        };
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keyset_sort() {
+        assert_eq!(keyset_sort(None), Some(doc! { "_id": 1 }));
+        assert_eq!(
+            keyset_sort(Some(&doc! { "createdAt": -1 })),
+            Some(doc! { "createdAt": -1, "_id": -1 })
+        );
+        assert_eq!(keyset_sort(Some(&doc! { "_id": -1 })), Some(doc! { "_id": -1 }));
+        assert_eq!(keyset_sort(Some(&doc! { "a": 1, "b": 1 })), None);
+        assert_eq!(keyset_sort(Some(&doc! { "score": { "$meta": "textScore" } })), None);
+    }
+
+    #[test]
+    fn test_page_boundary() {
+        let sort = doc! { "meta.price": -1, "_id": -1 };
+        assert_eq!(
+            page_boundary(&sort, &doc! { "_id": 7, "meta": { "price": 9.5 } }),
+            Some(doc! { "meta.price": 9.5, "_id": 7 })
+        );
+        assert_eq!(
+            page_boundary(&sort, &doc! { "_id": 8 }),
+            Some(doc! { "meta.price": null, "_id": 8 })
+        );
+        assert_eq!(
+            page_boundary(&sort, &doc! { "_id": 9, "meta": { "price": [1, 2] } }),
+            None
+        );
+    }
+
+    #[test]
+    fn test_range_filter() {
+        let filter = range_filter(&doc! { "_id": 1 }, &doc! { "_id": 42 }).unwrap();
+        assert_eq!(filter, doc! { "_id": { "$gt": 42 } });
+
+        let filter = range_filter(
+            &doc! { "meta.price": -1, "_id": -1 },
+            &doc! { "meta.price": 9.5, "_id": 7 },
+        )
+        .unwrap();
+        assert_eq!(
+            filter,
+            doc! { "$or": [
+                { "meta.price": { "$lt": 9.5 } },
+                { "meta.price": 9.5, "_id": { "$lt": 7 } },
+                { "meta.price": null },
+            ] }
+        );
+
+        let sort = doc! { "price": 1, "_id": 1 };
+        let boundary = doc! { "price": null, "_id": 3 };
+        assert_eq!(
+            range_filter(&sort, &boundary).unwrap(),
+            doc! { "$or": [
+                { "price": { "$ne": null } },
+                { "price": null, "_id": { "$gt": 3 } },
+            ] }
+        );
+        assert_eq!(
+            range_filter(&doc! { "price": -1, "_id": -1 }, &boundary).unwrap(),
+            doc! { "price": null, "_id": { "$lt": 3 } }
+        );
+        assert!(range_filter(&sort, &doc! { "_id": 1 }).is_none());
+    }
+}
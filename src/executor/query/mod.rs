@@ -6,6 +6,10 @@
 //! - Aggregate: aggregate
 //! - FindAndModify: findOneAndDelete, findOneAndUpdate, findOneAndReplace
 //! - Explain: explain command support
+//! - Search: Atlas Search and Vector Search helpers
+//! - Size: docSize, largestDocuments
+//! - Point in time: asOf
+//! - Comparison: diff
 //!
 //! The module is organized into sub-modules by operation type:
 //! - `read`: Read operations
@@ -13,13 +17,25 @@
 //! - `aggregate`: Aggregation operations
 //! - `find_and_modify`: FindAndModify operations
 //! - `explain`: Explain operations
+//! - `search`: Atlas Search and Vector Search operations
+//! - `doc_size`: Document size reports
+//! - `keyset`: Keyset pagination for deep skips
+//! - `bulk`: Rate-limited bulk updates
+//! - `as_of`: Snapshot reads at a past cluster time with an oplog fallback
+//! - `diff`: `_id`-ordered comparison of two collections
+//! - `parallel`: `.parallel(n)` reads over concurrent `_id` ranges
 
 use std::time::Instant;
 
-use crate::error::{MongoshError, Result};
+use futures::future::Either;
+use futures::{stream, Stream};
+use mongodb::bson::Document;
+
+use crate::error::{ExecutionError, MongoshError, Result};
 use crate::parser::{QueryCommand, QueryMode};
-use super::confirmation::confirm_query_operation;
+use super::confirmation::{confirm_query_operation, preview_target};
 use super::context::ExecutionContext;
+use super::export::streaming::document_stream;
 use super::result::{ExecutionResult, ExecutionStats, ResultData};
 
 // Sub-modules
@@ -28,6 +44,18 @@ mod write;
 mod aggregate;
 mod find_and_modify;
 mod explain;
+mod search;
+mod doc_size;
+mod keyset;
+mod bulk;
+mod as_of;
+mod diff;
+mod parallel;
+
+pub use keyset::PageBookmark;
+
+/// Documents fetched per cursor batch by `execute_stream`
+const STREAM_BATCH_SIZE: u32 = 1000;
 
 /// Query executor for CRUD operations
 pub struct QueryExecutor {
@@ -50,7 +78,8 @@ impl QueryExecutor {
     /// Execute a query command
     pub async fn execute(&self, cmd: QueryCommand, mode: QueryMode) -> Result<ExecutionResult> {
         // Check if operation requires confirmation
-        if !confirm_query_operation(&cmd)? {
+        let affected = self.preview_count(&cmd).await;
+        if !confirm_query_operation(&cmd, affected, self.context.confirmation())? {
             return Ok(ExecutionResult {
                 success: true,
                 data: ResultData::Message("Operation cancelled by user".to_string()),
@@ -103,6 +132,23 @@ impl QueryExecutor {
                 options: _,
             } => self.execute_update_many(collection, filter, update).await,
 
+            QueryCommand::BulkUpdateWithRateLimit {
+                collection,
+                filter,
+                update,
+                batch_size,
+                ops_per_second,
+            } => {
+                self.execute_bulk_update_with_rate_limit(
+                    collection,
+                    filter,
+                    update,
+                    batch_size,
+                    ops_per_second,
+                )
+                .await
+            }
+
             QueryCommand::DeleteOne { collection, filter } => {
                 self.execute_delete_one(collection, filter).await
             }
@@ -125,7 +171,11 @@ impl QueryExecutor {
                 collection,
                 field,
                 filter,
-            } => self.execute_distinct(collection, field, filter).await,
+                collation,
+            } => {
+                self.execute_distinct(collection, field, filter, collation)
+                    .await
+            }
 
             QueryCommand::ReplaceOne {
                 collection,
@@ -195,6 +245,32 @@ impl QueryExecutor {
                 .await
             }
 
+            QueryCommand::DocSize {
+                collection,
+                filter,
+                limit,
+            } => self.execute_doc_size(collection, filter, limit, mode).await,
+
+            QueryCommand::Diff {
+                collection,
+                other,
+                options,
+            } => self.execute_diff(collection, other, options).await,
+
+            QueryCommand::Search {
+                collection,
+                search,
+                limit,
+            } => self.execute_search(collection, search, limit, mode).await,
+
+            QueryCommand::VectorSearch {
+                collection,
+                vector_search,
+            } => {
+                self.execute_vector_search(collection, vector_search, mode)
+                    .await
+            }
+
             QueryCommand::Explain {
                 collection,
                 verbosity,
@@ -215,4 +291,80 @@ impl QueryExecutor {
             result
         }
     }
+
+    /// Count the documents a write will affect, for the confirmation prompt
+    ///
+    /// Runs only with `safety.preview_counts` and for the writes picked by
+    /// `preview_target`. A failed count is reported and the prompt is shown
+    /// without it, so it never blocks the write.
+    ///
+    /// # Arguments
+    /// * `cmd` - Query command about to run
+    ///
+    /// # Returns
+    /// * `Option<u64>` - Matching documents, if counted
+    async fn preview_count(&self, cmd: &QueryCommand) -> Option<u64> {
+        if !self.context.shared_state.get_safety_config().preview_counts {
+            return None;
+        }
+        let (collection, filter) = preview_target(cmd)?;
+
+        match self
+            .execute_count(collection.to_string(), Some(filter.clone()))
+            .await
+        {
+            Ok(ExecutionResult {
+                data: ResultData::Count(count),
+                ..
+            }) => Some(count),
+            Ok(_) => None,
+            Err(e) => {
+                eprintln!("Warning: could not count affected documents: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Execute a query command as a stream of documents
+    ///
+    /// `find` and `aggregate` read their cursor one batch at a time as the
+    /// stream is polled, so large result sets are processed with backpressure
+    /// instead of being collected first. Other commands that return documents,
+    /// such as `findOne`, run to completion before their documents are streamed.
+    ///
+    /// # Arguments
+    /// * `cmd` - Query command to execute
+    ///
+    /// # Returns
+    /// * `Result<impl Stream<Item = Result<Document>>>` - Document stream, or an
+    ///   error if the command fails or does not return documents
+    #[allow(dead_code)] // library API, not used by the binary
+    pub async fn execute_stream(
+        &self,
+        cmd: QueryCommand,
+    ) -> Result<impl Stream<Item = Result<Document>> + Send + 'static> {
+        let mode = QueryMode::Streaming {
+            batch_size: STREAM_BATCH_SIZE,
+        };
+        let result = self.execute(cmd, mode).await?;
+        if !result.success {
+            return Err(ExecutionError::QueryFailed(
+                result.error.unwrap_or_else(|| "Command failed".to_string()),
+            )
+            .into());
+        }
+
+        match result.data {
+            ResultData::Stream(query) => Ok(Either::Left(document_stream(query))),
+            data => match data.to_documents() {
+                Some(documents) => Ok(Either::Right(stream::iter(
+                    documents.into_iter().map(Ok),
+                ))),
+                None => Err(ExecutionError::InvalidOperation(
+                    "Command does not return documents".to_string(),
+                )
+                .into()),
+            },
+        }
+    }
 }
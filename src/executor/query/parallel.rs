@@ -0,0 +1,465 @@
+//! Parallel reads over `_id` ranges
+//!
+//! `.parallel(n)` on a find or an aggregation splits the collection into `n`
+//! `_id` ranges and reads them concurrently, one cursor per range, merging
+//! the results client-side. Split points come from a `$sample` of `_id`s, so
+//! the ranges hold roughly as many documents each without a scan. The merged
+//! documents come in the order the ranges return them.
+//!
+//! Only reads whose results can simply be concatenated are split: finds
+//! without `sort` or `skip`, and pipelines of per-document stages, optionally
+//! ending in `$count`, whose counts are summed. Collations are refused, since
+//! the split points are ordered by simple binary comparison.
+
+use futures::StreamExt;
+use futures::stream::{BoxStream, TryStreamExt};
+use mongodb::Collection;
+use mongodb::bson::{Bson, Document, doc};
+use mongodb::options::AggregateOptions as MongoAggregateOptions;
+use tracing::info;
+
+use crate::error::{ExecutionError, Result};
+use crate::parser::{AggregateOptions, FindOptions, QueryMode};
+
+use super::super::export::streaming::{MergedStreamingQuery, StreamingQuery};
+use super::super::killable::run_killable_command;
+use super::super::progress::{ProgressTracker, ProgressUnit};
+use super::super::result::{ExecutionResult, ExecutionStats, ResultData};
+
+/// `_id`s sampled per range to pick the split points
+const SAMPLES_PER_RANGE: u32 = 16;
+
+/// Stages that transform each document on its own, so a pipeline made of
+/// them gives the same documents when run per range
+const PER_DOCUMENT_STAGES: &[&str] = &[
+    "$match",
+    "$project",
+    "$addFields",
+    "$set",
+    "$unset",
+    "$replaceRoot",
+    "$replaceWith",
+    "$unwind",
+    "$lookup",
+    "$graphLookup",
+    "$redact",
+];
+
+/// Parallel read implementation
+impl super::QueryExecutor {
+    /// Execute a find over `_id` ranges concurrently
+    ///
+    /// # Arguments
+    /// * `collection` - Collection name
+    /// * `filter` - Query filter
+    /// * `options` - Find options; `parallel` is the number of ranges
+    /// * `mode` - Interactive reads collect every match, streaming reads
+    ///   return a merged stream for export
+    ///
+    /// # Returns
+    /// * `Result<ExecutionResult>` - Documents or a stream
+    pub(super) async fn execute_find_parallel(
+        &self,
+        collection: String,
+        filter: Document,
+        options: FindOptions,
+        mode: QueryMode,
+    ) -> Result<ExecutionResult> {
+        let unsupported = [
+            (options.sort.is_some(), "sort()"),
+            (options.skip.is_some_and(|skip| skip > 0), "skip()"),
+            (options.hint.is_some(), "hint()"),
+            (options.as_of.is_some(), "asOf()"),
+            (options.collation.is_some(), "collation()"),
+        ];
+        if let Some((_, method)) = unsupported.iter().find(|(used, _)| *used) {
+            return Err(ExecutionError::InvalidOperation(format!(
+                "parallel() cannot be combined with {}",
+                method
+            ))
+            .into());
+        }
+
+        let ranges = options.parallel.unwrap_or(1);
+        let limit = options
+            .limit
+            .filter(|&limit| limit > 0)
+            .map(|limit| limit as u64);
+        info!(
+            "Executing find on collection '{}' over {} _id ranges",
+            collection, ranges
+        );
+
+        let accessible = self.context.shared_state.get_accessible();
+        let coll = self.collection(&collection).await?;
+        let open = move |comment: String| async move {
+            let mut streams = Vec::new();
+            for range in id_ranges(&coll, ranges).await? {
+                let mut find_opts = mongodb::options::FindOptions::default();
+                find_opts.comment = Some(Bson::String(comment.clone()));
+                find_opts.limit = limit.map(|limit| limit as i64);
+                find_opts.projection = options.projection.clone();
+                find_opts.batch_size = options.batch_size;
+                let cursor = coll
+                    .find(restrict(&filter, range))
+                    .with_options(find_opts)
+                    .await
+                    .map_err(|e| ExecutionError::QueryFailed(e.to_string()))?;
+                streams.push(cursor.map_err(Into::into).boxed());
+            }
+            Ok(streams)
+        };
+
+        match mode {
+            QueryMode::Interactive { batch_size } => {
+                // The results are complete, so there is nothing for `it` to page
+                self.context.shared_state.clear_cursor().await;
+                let documents = self
+                    .run_ranges(move |comment| async move {
+                        let query =
+                            MergedStreamingQuery::new(open(comment).await?, batch_size, limit);
+                        collect(query, accessible).await
+                    })
+                    .await?;
+                Ok(documents_result(documents))
+            }
+            QueryMode::Streaming { batch_size } => {
+                let streams = self.run_ranges(open).await?;
+                Ok(stream_result(MergedStreamingQuery::new(
+                    streams, batch_size, limit,
+                )))
+            }
+        }
+    }
+
+    /// Execute an aggregation over `_id` ranges concurrently
+    ///
+    /// # Arguments
+    /// * `collection` - Collection name
+    /// * `pipeline` - Per-document stages, optionally ending in `$count`
+    /// * `options` - Aggregate options; `parallel` is the number of ranges
+    /// * `mode` - Interactive reads collect every result, streaming reads
+    ///   return a merged stream for export
+    ///
+    /// # Returns
+    /// * `Result<ExecutionResult>` - Documents or a stream
+    pub(super) async fn execute_aggregate_parallel(
+        &self,
+        collection: String,
+        pipeline: Vec<Document>,
+        options: AggregateOptions,
+        mode: QueryMode,
+    ) -> Result<ExecutionResult> {
+        let unsupported = [
+            (options.hint.is_some(), "a hint"),
+            (options.collation.is_some(), "a collation"),
+        ];
+        if let Some((_, option)) = unsupported.iter().find(|(used, _)| *used) {
+            return Err(ExecutionError::InvalidOperation(format!(
+                "parallel() cannot be combined with {}",
+                option
+            ))
+            .into());
+        }
+        let count_field = count_field(&pipeline)?;
+        if count_field.is_some() && matches!(mode, QueryMode::Streaming { .. }) {
+            return Err(ExecutionError::InvalidOperation(
+                "A parallel $count cannot be exported".to_string(),
+            )
+            .into());
+        }
+
+        let ranges = options.parallel.unwrap_or(1);
+        info!(
+            "Executing aggregate on collection '{}' over {} _id ranges",
+            collection, ranges
+        );
+
+        let mut agg_opts = MongoAggregateOptions::default();
+        agg_opts.allow_disk_use = options.allow_disk_use.then_some(true);
+        agg_opts.batch_size = options.batch_size;
+        agg_opts.max_time = options.max_time_ms.map(std::time::Duration::from_millis);
+        agg_opts.let_vars = options.let_vars;
+
+        let accessible = self.context.shared_state.get_accessible();
+        let coll = self.collection(&collection).await?;
+        let open = move |comment: String| async move {
+            let mut streams = Vec::new();
+            for range in id_ranges(&coll, ranges).await? {
+                let mut ranged = vec![doc! { "$match": range }];
+                ranged.extend(pipeline.iter().cloned());
+                let mut range_opts = agg_opts.clone();
+                range_opts.comment = Some(Bson::String(comment.clone()));
+                let cursor = coll
+                    .aggregate(ranged)
+                    .with_options(range_opts)
+                    .await
+                    .map_err(|e| ExecutionError::QueryFailed(e.to_string()))?;
+                streams.push(cursor.map_err(Into::into).boxed());
+            }
+            Ok(streams)
+        };
+
+        match mode {
+            QueryMode::Interactive { batch_size } => {
+                let documents = self
+                    .run_ranges(move |comment| async move {
+                        let query =
+                            MergedStreamingQuery::new(open(comment).await?, batch_size, None);
+                        collect(query, accessible).await
+                    })
+                    .await?;
+                let documents = match count_field {
+                    Some(field) => sum_counts(&documents, &field),
+                    None => documents,
+                };
+                Ok(documents_result(documents))
+            }
+            QueryMode::Streaming { batch_size } => {
+                let streams = self.run_ranges(open).await?;
+                Ok(stream_result(MergedStreamingQuery::new(
+                    streams, batch_size, None,
+                )))
+            }
+        }
+    }
+
+    /// Handle for a collection of the current database
+    async fn collection(&self, name: &str) -> Result<Collection<Document>> {
+        Ok(self.context.get_database().await?.collection(name))
+    }
+
+    /// Run range reads with killOp support; every range shares one comment,
+    /// so Ctrl+C kills them all
+    async fn run_ranges<T, F, Fut>(&self, run: F) -> Result<T>
+    where
+        F: FnOnce(String) -> Fut,
+        Fut: Future<Output = Result<T>> + Send + 'static,
+        T: Send + 'static,
+    {
+        let client = self.context.get_client().await?;
+        let client_id = self.context.get_client_id();
+        let cancel_token = self.context.get_cancel_token();
+        run_killable_command(client, client_id, cancel_token, |_, handle| {
+            Box::pin(run(handle.comment().to_string()))
+        })
+        .await
+    }
+}
+
+/// Split a collection into `_id` ranges
+///
+/// # Arguments
+/// * `coll` - Collection to split
+/// * `ranges` - Number of ranges wanted
+///
+/// # Returns
+/// * `Result<Vec<Document>>` - One `_id` filter per range; fewer than
+///   `ranges` for small collections
+async fn id_ranges(coll: &Collection<Document>, ranges: u32) -> Result<Vec<Document>> {
+    if ranges <= 1 {
+        return Ok(vec![Document::new()]);
+    }
+
+    let pipeline = vec![
+        doc! { "$sample": { "size": i64::from(ranges * SAMPLES_PER_RANGE) } },
+        doc! { "$project": { "_id": 1 } },
+        doc! { "$sort": { "_id": 1 } },
+    ];
+    let ids: Vec<Bson> = coll
+        .aggregate(pipeline)
+        .await?
+        .try_filter_map(|mut sampled| async move { Ok(sampled.remove("_id")) })
+        .try_collect()
+        .await?;
+    Ok(range_filters(&split_points(&ids, ranges as usize)))
+}
+
+/// Pick evenly spaced split points from sorted `_id`s
+///
+/// Range queries only match values of the bound's type, so `_id`s of mixed
+/// types are not split.
+fn split_points(ids: &[Bson], ranges: usize) -> Vec<Bson> {
+    let Some(first) = ids.first() else {
+        return Vec::new();
+    };
+    if ids
+        .iter()
+        .any(|id| id.element_type() != first.element_type())
+    {
+        return Vec::new();
+    }
+
+    let mut points: Vec<Bson> = (1..ranges)
+        .map(|i| ids[i * ids.len() / ranges].clone())
+        .collect();
+    points.dedup();
+    points.retain(|point| point != first);
+    points
+}
+
+/// `_id` filters between consecutive split points
+///
+/// The first range takes everything below the first point with `$not`, so
+/// `_id`s of other types, which no range bound matches, land there.
+fn range_filters(points: &[Bson]) -> Vec<Document> {
+    let Some(first) = points.first() else {
+        return vec![Document::new()];
+    };
+
+    let mut filters = vec![doc! { "_id": { "$not": { "$gte": first.clone() } } }];
+    for pair in points.windows(2) {
+        filters.push(doc! { "_id": { "$gte": pair[0].clone(), "$lt": pair[1].clone() } });
+    }
+    filters.push(doc! { "_id": { "$gte": points[points.len() - 1].clone() } });
+    filters
+}
+
+/// Combine a query filter with a range filter
+fn restrict(filter: &Document, range: Document) -> Document {
+    match (filter.is_empty(), range.is_empty()) {
+        (_, true) => filter.clone(),
+        (true, false) => range,
+        (false, false) => doc! { "$and": [filter.clone(), range] },
+    }
+}
+
+/// Check a pipeline can be run per range
+///
+/// # Returns
+/// * `Result<Option<String>>` - Field of a final `$count`, whose per-range
+///   counts must be summed
+fn count_field(pipeline: &[Document]) -> Result<Option<String>> {
+    let (count_field, stages) = match pipeline.split_last() {
+        Some((last, stages)) if last.get_str("$count").is_ok() => {
+            (last.get_str("$count").ok().map(String::from), stages)
+        }
+        _ => (None, pipeline),
+    };
+
+    let stage_name = |stage: &Document| stage.keys().next().cloned().unwrap_or_default();
+    if let Some(stage) = stages
+        .iter()
+        .map(stage_name)
+        .find(|name| !PER_DOCUMENT_STAGES.contains(&name.as_str()))
+    {
+        return Err(ExecutionError::InvalidOperation(format!(
+            "parallel() cannot run {}; it supports per-document stages ({}) and a final $count",
+            stage,
+            PER_DOCUMENT_STAGES.join(", ")
+        ))
+        .into());
+    }
+    Ok(count_field)
+}
+
+/// Sum the `$count` results of the ranges; like `$count`, no document when
+/// nothing matched
+fn sum_counts(documents: &[Document], field: &str) -> Vec<Document> {
+    let total: i64 = documents
+        .iter()
+        .filter_map(|document| match document.get(field) {
+            Some(Bson::Int32(n)) => Some(i64::from(*n)),
+            Some(Bson::Int64(n)) => Some(*n),
+            _ => None,
+        })
+        .sum();
+    if total == 0 {
+        return Vec::new();
+    }
+    vec![doc! { field: total }]
+}
+
+/// Read every document of a merged query, showing how many have arrived
+async fn collect(mut query: MergedStreamingQuery, accessible: bool) -> Result<Vec<Document>> {
+    let tracker =
+        ProgressTracker::for_terminal("parallel", None, ProgressUnit::Documents, accessible);
+    let mut documents = Vec::new();
+    while let Some(batch) = query.next_batch().await? {
+        documents.extend(batch);
+        tracker.update(documents.len() as u64);
+    }
+    tracker.finish();
+    query.close().await?;
+    Ok(documents)
+}
+
+fn documents_result(documents: Vec<Document>) -> ExecutionResult {
+    let count = documents.len();
+    ExecutionResult {
+        success: true,
+        data: ResultData::Documents(documents),
+        stats: ExecutionStats {
+            execution_time_ms: 0,
+            documents_returned: count,
+            documents_affected: None,
+        },
+        error: None,
+    }
+}
+
+fn stream_result(query: MergedStreamingQuery) -> ExecutionResult {
+    ExecutionResult {
+        success: true,
+        data: ResultData::Stream(Box::new(query)),
+        stats: ExecutionStats::default(),
+        error: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_points_and_ranges() {
+        let ids: Vec<Bson> = (0..16).map(Bson::Int32).collect();
+        let points = split_points(&ids, 4);
+        assert_eq!(
+            points,
+            vec![Bson::Int32(4), Bson::Int32(8), Bson::Int32(12)]
+        );
+
+        let filters = range_filters(&points);
+        assert_eq!(filters.len(), 4);
+        assert_eq!(filters[0], doc! { "_id": { "$not": { "$gte": 4 } } });
+        assert_eq!(filters[1], doc! { "_id": { "$gte": 4, "$lt": 8 } });
+        assert_eq!(filters[3], doc! { "_id": { "$gte": 12 } });
+    }
+
+    #[test]
+    fn test_split_points_small_or_mixed() {
+        assert!(split_points(&[], 4).is_empty());
+        assert!(split_points(&[Bson::Int32(1)], 4).is_empty());
+        let mixed = vec![Bson::Int32(1), Bson::String("a".to_string())];
+        assert!(split_points(&mixed, 2).is_empty());
+        assert_eq!(range_filters(&[]), vec![Document::new()]);
+    }
+
+    #[test]
+    fn test_restrict() {
+        let range = doc! { "_id": { "$gte": 4 } };
+        assert_eq!(restrict(&doc! {}, range.clone()), range);
+        assert_eq!(restrict(&doc! { "a": 1 }, doc! {}), doc! { "a": 1 });
+        assert_eq!(
+            restrict(&doc! { "a": 1 }, range.clone()),
+            doc! { "$and": [{ "a": 1 }, range] }
+        );
+    }
+
+    #[test]
+    fn test_count_field() {
+        let pipeline = vec![doc! { "$match": { "a": 1 } }, doc! { "$count": "n" }];
+        assert_eq!(count_field(&pipeline).unwrap(), Some("n".to_string()));
+        assert_eq!(count_field(&pipeline[..1]).unwrap(), None);
+
+        let pipeline = vec![doc! { "$group": { "_id": "$a" } }];
+        assert!(count_field(&pipeline).is_err());
+        let pipeline = vec![doc! { "$count": "n" }, doc! { "$match": { "n": 1 } }];
+        assert!(count_field(&pipeline).is_err());
+
+        let documents = vec![doc! { "n": 3 }, doc! { "n": 4_i64 }];
+        assert_eq!(sum_counts(&documents, "n"), vec![doc! { "n": 7_i64 }]);
+        assert!(sum_counts(&[], "n").is_empty());
+    }
+}
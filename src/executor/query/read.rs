@@ -7,8 +7,9 @@
 
 use futures::stream::TryStreamExt;
 use mongodb::Collection;
-use mongodb::bson::{Bson, Document};
-use tracing::{debug, info};
+use mongodb::bson::{self, Bson, Document};
+use mongodb::options::Collation;
+use tracing::{debug, info, warn};
 
 use crate::error::{ExecutionError, Result};
 use crate::parser::{FindOptions, QueryMode};
@@ -17,6 +18,13 @@ use super::super::export::streaming::FindStreamingQuery;
 use super::super::killable::run_killable_command;
 use super::super::result::{ExecutionResult, ExecutionStats, ResultData};
 
+/// Convert a collation document from the shell into driver options
+pub(super) fn to_collation(collation: Document) -> Result<Collation> {
+    bson::from_document(collation).map_err(|e| {
+        ExecutionError::InvalidParameters(format!("Invalid collation: {}", e)).into()
+    })
+}
+
 /// Read operations implementation
 impl super::QueryExecutor {
     /// Execute findOne command
@@ -47,6 +55,9 @@ impl super::QueryExecutor {
         find_options.projection = options.projection;
         find_options.sort = options.sort;
         find_options.limit = Some(1); // FindOne always limits to 1
+        if let Some(collation) = options.collation {
+            find_options.collation = Some(to_collation(collation)?);
+        }
 
         // Execute find query
         let mut cursor = coll.find(filter).with_options(find_options).await?;
@@ -86,9 +97,37 @@ impl super::QueryExecutor {
         options: FindOptions,
         mode: QueryMode,
     ) -> Result<ExecutionResult> {
+        let options = self.apply_max_results(options);
+        if options.parallel.is_some_and(|ranges| ranges > 1) {
+            return self
+                .execute_find_parallel(collection, filter, options, mode)
+                .await;
+        }
+        if let Some(at) = options.as_of {
+            if let QueryMode::Streaming { .. } = mode {
+                return Err(ExecutionError::InvalidOperation(
+                    "asOf() results cannot be exported".to_string(),
+                )
+                .into());
+            }
+            return self
+                .execute_find_as_of(collection, filter, options, at)
+                .await;
+        }
+
+        let (filter, options, page) = self
+            .apply_pagination_policy(&collection, filter, options)
+            .await?;
+
         match mode {
             QueryMode::Interactive { batch_size } => {
-                self.execute_find_interactive(collection, filter, options, batch_size).await
+                let result = self
+                    .execute_find_interactive(collection, filter, options, batch_size)
+                    .await?;
+                if let Some(page) = page {
+                    self.remember_page(page, &result);
+                }
+                Ok(result)
             }
             QueryMode::Streaming { batch_size } => {
                 self.execute_find_streaming(collection, filter, options, batch_size).await
@@ -96,6 +135,27 @@ impl super::QueryExecutor {
         }
     }
 
+    /// Limit an unbounded find to `safety.max_results`
+    ///
+    /// A find without a limit (or with `limit(0)`, which the server treats as
+    /// no limit) gets the configured limit and a notice, unless `.noLimit()`
+    /// was chained.
+    fn apply_max_results(&self, mut options: FindOptions) -> FindOptions {
+        let max_results = self.context.shared_state.get_safety_config().max_results;
+        let unbounded = matches!(options.limit, None | Some(0));
+        if max_results == 0 || options.no_limit || !unbounded {
+            return options;
+        }
+
+        warn!(
+            "Results limited to {} documents (safety.max_results); \
+             append .noLimit() to return all",
+            max_results
+        );
+        options.limit = Some(max_results as i64);
+        options
+    }
+
     /// Execute find in streaming mode for export
     pub(super) async fn execute_find_streaming(
         &self,
@@ -158,6 +218,11 @@ impl super::QueryExecutor {
                         debug!("Applied projection");
                     }
 
+                    if let Some(ref collation) = options.collation {
+                        find_opts.collation = Some(to_collation(collation.clone())?);
+                        debug!("Applied collation");
+                    }
+
                     // Execute query and create cursor
                     let cursor = coll
                         .find(filter)
@@ -255,6 +320,11 @@ impl super::QueryExecutor {
                         debug!("Applied projection");
                     }
 
+                    if let Some(ref collation) = options.collation {
+                        find_opts.collation = Some(to_collation(collation.clone())?);
+                        debug!("Applied collation");
+                    }
+
                     // Execute query and create cursor
                     let cursor = coll
                         .find(filter)
@@ -451,6 +521,7 @@ impl super::QueryExecutor {
     /// * `collection` - Collection name
     /// * `field` - Field to get distinct values for
     /// * `filter` - Optional query filter
+    /// * `collation` - Optional collation for string comparison
     ///
     /// # Returns
     /// * `Result<ExecutionResult>` - Distinct values result or error
@@ -459,6 +530,7 @@ impl super::QueryExecutor {
         collection: String,
         field: String,
         filter: Option<Document>,
+        collation: Option<Document>,
     ) -> Result<ExecutionResult> {
         debug!(
             "Executing distinct on collection '{}' for field '{}'",
@@ -479,6 +551,7 @@ impl super::QueryExecutor {
                 let collection = collection.clone();
                 let field = field.clone();
                 let filter = filter.clone();
+                let collation = collation.clone();
 
                 Box::pin(async move {
                     let coll: Collection<Document> = client
@@ -489,6 +562,9 @@ impl super::QueryExecutor {
                     let mut options = DistinctOptions::default();
                     // CRITICAL: Set comment for killOp support
                     options.comment = Some(Bson::String(handle.comment().to_string()));
+                    if let Some(collation) = collation {
+                        options.collation = Some(to_collation(collation)?);
+                    }
 
                     let filter_doc = filter.unwrap_or_else(|| Document::new());
                     let values = coll
@@ -0,0 +1,368 @@
+//! Atlas Search operations for query executor
+//!
+//! This module runs `db.collection.search(...)` helpers. The `$search` stage is
+//! followed by a score projection and, when requested, search highlights that
+//! are flattened into readable strings with hits marked as `**hit**`.
+//!
+//! `db.collection.vectorSearch(...)` runs a `$vectorSearch` stage the same way,
+//! resolving the index and checking the query vector length against the
+//! search index definition when Atlas exposes it.
+
+use futures::stream::TryStreamExt;
+use mongodb::Collection;
+use mongodb::bson::{Bson, Document, doc};
+use tracing::debug;
+
+use crate::error::{ExecutionError, Result};
+use crate::parser::{AggregateOptions, MAX_NUM_CANDIDATES, QueryMode};
+use super::super::result::{ExecutionResult, ResultData};
+
+/// Field holding the relevance score of each search result
+pub(super) const SCORE_FIELD: &str = "_score";
+
+/// Field holding rendered search highlights
+pub(super) const HIGHLIGHTS_FIELD: &str = "_highlights";
+
+/// Build the aggregation pipeline for a `$search` query
+pub(super) fn build_search_pipeline(search: &Document, limit: i64) -> Vec<Document> {
+    let mut add_fields = doc! { SCORE_FIELD: { "$meta": "searchScore" } };
+    if search.contains_key("highlight") {
+        add_fields.insert(HIGHLIGHTS_FIELD, doc! { "$meta": "searchHighlights" });
+    }
+
+    vec![
+        doc! { "$search": search.clone() },
+        doc! { "$limit": limit },
+        doc! { "$addFields": add_fields },
+    ]
+}
+
+/// Candidates considered per requested result when `numCandidates` is omitted
+const CANDIDATES_PER_RESULT: i64 = 10;
+
+/// Build the aggregation pipeline for a `$vectorSearch` query
+pub(super) fn build_vector_search_pipeline(vector_search: &Document) -> Vec<Document> {
+    let mut stage = vector_search.clone();
+    let exact = matches!(stage.get("exact"), Some(Bson::Boolean(true)));
+    if !exact && !stage.contains_key("numCandidates") {
+        let limit = stage.get_i64("limit").unwrap_or(1);
+        stage.insert(
+            "numCandidates",
+            (limit * CANDIDATES_PER_RESULT).min(MAX_NUM_CANDIDATES),
+        );
+    }
+
+    vec![
+        doc! { "$vectorSearch": stage },
+        doc! { "$addFields": { SCORE_FIELD: { "$meta": "vectorSearchScore" } } },
+    ]
+}
+
+/// Resolve the vector index for a query and check its dimensions
+///
+/// `indexes` are the collection's search index definitions as returned by
+/// `$listSearchIndexes`. When no index name is given, the single
+/// `vectorSearch` index covering the query path is used. The query vector
+/// length is checked against the field's `numDimensions` when known.
+fn resolve_vector_index(vector_search: &mut Document, indexes: &[Document]) -> Result<()> {
+    let path = vector_search.get_str("path").unwrap_or_default().to_string();
+    let requested = vector_search.get_str("index").ok().map(str::to_string);
+
+    let candidates: Vec<(&str, Option<i64>)> = indexes
+        .iter()
+        .filter(|index| matches!(index.get_str("type"), Ok("vectorSearch")))
+        .filter_map(|index| {
+            let name = index.get_str("name").ok()?;
+            if requested.as_deref().is_some_and(|r| r != name) {
+                return None;
+            }
+            let field = vector_field(index, &path)?;
+            Some((name, field.get("numDimensions").and_then(bson_to_i64)))
+        })
+        .collect();
+
+    let (name, dimensions) = match (requested.as_deref(), candidates.as_slice()) {
+        (_, [single]) => *single,
+        (Some(_), []) => return Ok(()),
+        (None, []) => {
+            return Err(ExecutionError::InvalidParameters(format!(
+                "vectorSearch() requires 'index': no vector search index covers path '{}'",
+                path
+            ))
+            .into());
+        }
+        (_, many) => {
+            let names: Vec<&str> = many.iter().map(|(name, _)| *name).collect();
+            return Err(ExecutionError::InvalidParameters(format!(
+                "Several vector search indexes cover path '{}' ({}). Pass 'index' to pick one",
+                path,
+                names.join(", ")
+            ))
+            .into());
+        }
+    };
+
+    let vector_len = vector_search
+        .get_array("queryVector")
+        .map(|v| v.len())
+        .unwrap_or_default() as i64;
+    if let Some(dimensions) = dimensions
+        && dimensions != vector_len
+    {
+        return Err(ExecutionError::InvalidParameters(format!(
+            "queryVector has {} dimensions but index '{}' expects {} for path '{}'",
+            vector_len, name, dimensions, path
+        ))
+        .into());
+    }
+
+    let name = name.to_string();
+    vector_search.insert("index", name);
+    Ok(())
+}
+
+/// Find the `vector` field definition for `path` in a search index
+fn vector_field<'a>(index: &'a Document, path: &str) -> Option<&'a Document> {
+    let definition = index
+        .get_document("latestDefinition")
+        .or_else(|_| index.get_document("definition"))
+        .ok()?;
+
+    definition
+        .get_array("fields")
+        .ok()?
+        .iter()
+        .filter_map(|field| field.as_document())
+        .find(|field| {
+            matches!(field.get_str("type"), Ok("vector"))
+                && field.get_str("path").is_ok_and(|p| p == path)
+        })
+}
+
+/// Read an integer from any BSON numeric type
+fn bson_to_i64(value: &Bson) -> Option<i64> {
+    match value {
+        Bson::Int32(n) => Some(*n as i64),
+        Bson::Int64(n) => Some(*n),
+        Bson::Double(n) => Some(*n as i64),
+        _ => None,
+    }
+}
+
+/// Reorder a search result so the score comes first and flatten highlights
+fn present_search_result(mut doc: Document) -> Document {
+    let mut result = Document::new();
+
+    if let Some(score) = doc.remove(SCORE_FIELD) {
+        result.insert(SCORE_FIELD, score);
+    }
+
+    let highlights = doc.remove(HIGHLIGHTS_FIELD);
+    for (key, value) in doc {
+        result.insert(key, value);
+    }
+
+    if let Some(Bson::Array(highlights)) = highlights {
+        let rendered: Vec<Bson> = highlights
+            .iter()
+            .filter_map(|h| h.as_document())
+            .map(|h| Bson::String(render_highlight(h)))
+            .collect();
+        result.insert(HIGHLIGHTS_FIELD, rendered);
+    }
+
+    result
+}
+
+/// Render one `searchHighlights` entry as `path: text with **hits**`
+fn render_highlight(highlight: &Document) -> String {
+    let path = highlight.get_str("path").unwrap_or("?");
+    let mut text = String::new();
+
+    if let Ok(texts) = highlight.get_array("texts") {
+        for part in texts.iter().filter_map(|t| t.as_document()) {
+            let value = part.get_str("value").unwrap_or_default();
+            if matches!(part.get_str("type"), Ok("hit")) {
+                text.push_str("**");
+                text.push_str(value);
+                text.push_str("**");
+            } else {
+                text.push_str(value);
+            }
+        }
+    }
+
+    format!("{}: {}", path, text)
+}
+
+/// Search operations implementation
+impl super::QueryExecutor {
+    /// Execute an Atlas Search query built by `db.collection.search()`
+    pub(super) async fn execute_search(
+        &self,
+        collection: String,
+        search: Document,
+        limit: i64,
+        mode: QueryMode,
+    ) -> Result<ExecutionResult> {
+        let pipeline = build_search_pipeline(&search, limit);
+
+        let mut result = self
+            .execute_aggregate(collection, pipeline, AggregateOptions::default(), mode)
+            .await?;
+
+        if let ResultData::Documents(docs) = result.data {
+            result.data =
+                ResultData::Documents(docs.into_iter().map(present_search_result).collect());
+        }
+
+        Ok(result)
+    }
+
+    /// Execute a vector search query built by `db.collection.vectorSearch()`
+    pub(super) async fn execute_vector_search(
+        &self,
+        collection: String,
+        mut vector_search: Document,
+        mode: QueryMode,
+    ) -> Result<ExecutionResult> {
+        match self.list_search_indexes(&collection).await {
+            Ok(indexes) => resolve_vector_index(&mut vector_search, &indexes)?,
+            Err(e) => debug!("Search index metadata unavailable: {}", e),
+        }
+
+        let pipeline = build_vector_search_pipeline(&vector_search);
+
+        let mut result = self
+            .execute_aggregate(collection, pipeline, AggregateOptions::default(), mode)
+            .await?;
+
+        if let ResultData::Documents(docs) = result.data {
+            result.data =
+                ResultData::Documents(docs.into_iter().map(present_search_result).collect());
+        }
+
+        Ok(result)
+    }
+
+    /// Fetch the search index definitions of a collection
+    async fn list_search_indexes(&self, collection: &str) -> Result<Vec<Document>> {
+        let db = self.context.get_database().await?;
+        let coll: Collection<Document> = db.collection(collection);
+        let indexes = coll.list_search_indexes().await?.try_collect().await?;
+        Ok(indexes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_search_pipeline() {
+        let search = doc! { "text": { "query": "coffee", "path": "title" } };
+        let pipeline = build_search_pipeline(&search, 5);
+        assert_eq!(pipeline.len(), 3);
+        assert!(pipeline[0].contains_key("$search"));
+        assert_eq!(pipeline[1].get_i64("$limit").unwrap(), 5);
+        let add_fields = pipeline[2].get_document("$addFields").unwrap();
+        assert!(add_fields.contains_key(SCORE_FIELD));
+        assert!(!add_fields.contains_key(HIGHLIGHTS_FIELD));
+    }
+
+    #[test]
+    fn test_build_search_pipeline_with_highlight() {
+        let search = doc! {
+            "text": { "query": "coffee", "path": "title" },
+            "highlight": { "path": "title" },
+        };
+        let pipeline = build_search_pipeline(&search, 5);
+        let add_fields = pipeline[2].get_document("$addFields").unwrap();
+        assert!(add_fields.contains_key(HIGHLIGHTS_FIELD));
+    }
+
+    #[test]
+    fn test_present_search_result() {
+        let raw = doc! {
+            "_id": 1,
+            "title": "Best coffee in town",
+            "_score": 2.5,
+            "_highlights": [{
+                "path": "title",
+                "score": 1.2,
+                "texts": [
+                    { "value": "Best ", "type": "text" },
+                    { "value": "coffee", "type": "hit" },
+                    { "value": " in town", "type": "text" },
+                ],
+            }],
+        };
+
+        let presented = present_search_result(raw);
+        let keys: Vec<&String> = presented.keys().collect();
+        assert_eq!(keys.first().unwrap().as_str(), SCORE_FIELD);
+        assert_eq!(
+            presented.get_array(HIGHLIGHTS_FIELD).unwrap()[0].as_str().unwrap(),
+            "title: Best **coffee** in town"
+        );
+    }
+
+    fn vector_index(name: &str, path: &str, dimensions: i32) -> Document {
+        doc! {
+            "name": name,
+            "type": "vectorSearch",
+            "latestDefinition": {
+                "fields": [
+                    { "type": "vector", "path": path, "numDimensions": dimensions, "similarity": "cosine" },
+                    { "type": "filter", "path": "year" },
+                ],
+            },
+        }
+    }
+
+    #[test]
+    fn test_build_vector_search_pipeline_defaults_candidates() {
+        let spec = doc! { "queryVector": [0.1, 0.2], "path": "embedding", "limit": 5_i64 };
+        let pipeline = build_vector_search_pipeline(&spec);
+        let stage = pipeline[0].get_document("$vectorSearch").unwrap();
+        assert_eq!(stage.get_i64("numCandidates").unwrap(), 50);
+        let add_fields = pipeline[1].get_document("$addFields").unwrap();
+        assert_eq!(
+            add_fields.get_document(SCORE_FIELD).unwrap().get_str("$meta").unwrap(),
+            "vectorSearchScore"
+        );
+
+        let exact = doc! { "queryVector": [0.1], "path": "embedding", "limit": 5_i64, "exact": true };
+        let pipeline = build_vector_search_pipeline(&exact);
+        let stage = pipeline[0].get_document("$vectorSearch").unwrap();
+        assert!(!stage.contains_key("numCandidates"));
+    }
+
+    #[test]
+    fn test_resolve_vector_index_picks_single_match() {
+        let mut spec = doc! { "queryVector": [0.1, 0.2, 0.3], "path": "embedding" };
+        let indexes = vec![
+            vector_index("plot_index", "plot_embedding", 1536),
+            vector_index("embedding_index", "embedding", 3),
+        ];
+        resolve_vector_index(&mut spec, &indexes).unwrap();
+        assert_eq!(spec.get_str("index").unwrap(), "embedding_index");
+    }
+
+    #[test]
+    fn test_resolve_vector_index_checks_dimensions() {
+        let mut spec = doc! { "queryVector": [0.1, 0.2], "path": "embedding", "index": "idx" };
+        let indexes = vec![vector_index("idx", "embedding", 1536)];
+        let err = resolve_vector_index(&mut spec, &indexes).unwrap_err();
+        assert!(err.to_string().contains("expects 1536"));
+    }
+
+    #[test]
+    fn test_resolve_vector_index_without_metadata() {
+        let mut named = doc! { "queryVector": [0.1], "path": "embedding", "index": "idx" };
+        assert!(resolve_vector_index(&mut named, &[]).is_ok());
+
+        let mut unnamed = doc! { "queryVector": [0.1], "path": "embedding" };
+        assert!(resolve_vector_index(&mut unnamed, &[]).is_err());
+    }
+}
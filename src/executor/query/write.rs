@@ -13,6 +13,7 @@ use tracing::{debug, info};
 use crate::error::{ExecutionError, Result};
 use super::super::killable::run_killable_command;
 use super::super::result::{ExecutionResult, ExecutionStats, ResultData};
+use super::super::trash::delete_to_trash;
 
 /// Check if MongoDB server version supports comment field on write operations
 ///
@@ -250,22 +251,16 @@ impl super::QueryExecutor {
         );
 
         let db = self.context.get_database().await?;
+        if self.context.shared_state.get_safety_config().trash {
+            let deleted = delete_to_trash(&db, &collection, filter, false).await?;
+            return Ok(Self::delete_result(deleted));
+        }
+
         let coll: Collection<Document> = db.collection(&collection);
 
         let result = coll.delete_one(filter).await?;
 
-        Ok(ExecutionResult {
-            success: true,
-            data: ResultData::Delete {
-                deleted: result.deleted_count,
-            },
-            stats: ExecutionStats {
-                execution_time_ms: 0,
-                documents_returned: 0,
-                documents_affected: Some(result.deleted_count),
-            },
-            error: None,
-        })
+        Ok(Self::delete_result(result.deleted_count))
     }
 
     /// Execute deleteMany command
@@ -286,6 +281,12 @@ impl super::QueryExecutor {
             collection, filter
         );
 
+        if self.context.shared_state.get_safety_config().trash {
+            let db = self.context.get_database().await?;
+            let deleted = delete_to_trash(&db, &collection, filter, true).await?;
+            return Ok(Self::delete_result(deleted));
+        }
+
         let client = self.context.get_client().await?;
         let client_id = self.context.get_client_id();
         let cancel_token = self.context.get_cancel_token();
@@ -325,18 +326,21 @@ impl super::QueryExecutor {
         )
         .await?;
 
-        Ok(ExecutionResult {
+        Ok(Self::delete_result(result.deleted_count))
+    }
+
+    /// Result of a delete that removed `deleted` documents
+    fn delete_result(deleted: u64) -> ExecutionResult {
+        ExecutionResult {
             success: true,
-            data: ResultData::Delete {
-                deleted: result.deleted_count,
-            },
+            data: ResultData::Delete { deleted },
             stats: ExecutionStats {
                 execution_time_ms: 0,
                 documents_returned: 0,
-                documents_affected: Some(result.deleted_count),
+                documents_affected: Some(deleted),
             },
             error: None,
-        })
+        }
     }
 
     /// Execute replaceOne command
@@ -3,10 +3,12 @@
 //! This module defines the data structures for representing command execution results:
 //! - ExecutionResult: Overall result of a command execution
 //! - ResultData: Various types of data that can be returned
+//! - IndexInfo, DatabaseInfo, CollectionInfo, UserInfo: Typed admin listings
 //! - ExecutionStats: Statistics about the execution
 
-use mongodb::bson::Document;
+use bson::{Bson, Document, doc};
 
+#[cfg(feature = "native")]
 use super::export::StreamingQuery;
 
 /// Result of command execution
@@ -64,6 +66,18 @@ pub enum ResultData {
     /// List of strings
     List(Vec<String>),
 
+    /// Index definitions of a collection
+    Indexes(Vec<IndexInfo>),
+
+    /// Databases on the server
+    Databases(Vec<DatabaseInfo>),
+
+    /// Collections and views in a database
+    Collections(Vec<CollectionInfo>),
+
+    /// Users defined in a database
+    Users(Vec<UserInfo>),
+
     /// No data
     None,
 
@@ -72,6 +86,7 @@ pub enum ResultData {
     /// This variant holds a streaming query that can be used to
     /// fetch documents in batches without loading everything into memory.
     /// Cannot be cloned - used for export operations only.
+    #[cfg(feature = "native")]
     Stream(Box<dyn StreamingQuery>),
 }
 
@@ -106,7 +121,16 @@ impl std::fmt::Debug for ResultData {
             ResultData::Count(count) => f.debug_tuple("Count").field(count).finish(),
             ResultData::Message(msg) => f.debug_tuple("Message").field(msg).finish(),
             ResultData::List(list) => f.debug_tuple("List").field(list).finish(),
+            ResultData::Indexes(indexes) => f.debug_tuple("Indexes").field(indexes).finish(),
+            ResultData::Databases(databases) => {
+                f.debug_tuple("Databases").field(databases).finish()
+            }
+            ResultData::Collections(collections) => {
+                f.debug_tuple("Collections").field(collections).finish()
+            }
+            ResultData::Users(users) => f.debug_tuple("Users").field(users).finish(),
             ResultData::None => f.write_str("None"),
+            #[cfg(feature = "native")]
             ResultData::Stream(_) => f.write_str("Stream(<streaming query>)"),
         }
     }
@@ -141,7 +165,12 @@ impl Clone for ResultData {
             ResultData::Count(count) => ResultData::Count(*count),
             ResultData::Message(msg) => ResultData::Message(msg.clone()),
             ResultData::List(list) => ResultData::List(list.clone()),
+            ResultData::Indexes(indexes) => ResultData::Indexes(indexes.clone()),
+            ResultData::Databases(databases) => ResultData::Databases(databases.clone()),
+            ResultData::Collections(collections) => ResultData::Collections(collections.clone()),
+            ResultData::Users(users) => ResultData::Users(users.clone()),
             ResultData::None => ResultData::None,
+            #[cfg(feature = "native")]
             ResultData::Stream(_) => {
                 panic!("Cannot clone ResultData::Stream - streaming queries are not cloneable")
             }
@@ -149,6 +178,212 @@ impl Clone for ResultData {
     }
 }
 
+impl ResultData {
+    /// Structured form of a typed admin listing, one document per entry
+    ///
+    /// # Returns
+    /// * `Option<Vec<Document>>` - Documents for `Indexes`, `Databases`,
+    ///   `Collections` and `Users`; `None` for every other variant
+    pub fn to_documents(&self) -> Option<Vec<Document>> {
+        match self {
+            ResultData::Indexes(indexes) => Some(indexes.iter().map(IndexInfo::to_document).collect()),
+            ResultData::Databases(databases) => {
+                Some(databases.iter().map(DatabaseInfo::to_document).collect())
+            }
+            ResultData::Collections(collections) => {
+                Some(collections.iter().map(CollectionInfo::to_document).collect())
+            }
+            ResultData::Users(users) => Some(users.iter().map(UserInfo::to_document).collect()),
+            _ => None,
+        }
+    }
+
+    /// Value of the result as referenced by `_` in a later command
+    ///
+    /// # Returns
+    /// * `Option<Bson>` - Documents as an array, a single document, ids,
+    ///   counts and listings; `None` for messages and empty results
+    pub fn to_bson(&self) -> Option<Bson> {
+        let strings =
+            |items: &[String]| Bson::Array(items.iter().cloned().map(Bson::String).collect());
+
+        match self {
+            ResultData::Documents(documents)
+            | ResultData::DocumentsWithPagination { documents, .. } => Some(Bson::Array(
+                documents.iter().cloned().map(Bson::Document).collect(),
+            )),
+            ResultData::Document(document) => Some(Bson::Document(document.clone())),
+            ResultData::InsertOne { inserted_id } => Some(Bson::String(inserted_id.clone())),
+            ResultData::InsertMany { inserted_ids } => Some(strings(inserted_ids)),
+            ResultData::Update { matched, modified } => Some(Bson::Document(doc! {
+                "matchedCount": *matched as i64,
+                "modifiedCount": *modified as i64,
+            })),
+            ResultData::Delete { deleted } => {
+                Some(Bson::Document(doc! { "deletedCount": *deleted as i64 }))
+            }
+            ResultData::Count(count) => Some(Bson::Int64(*count as i64)),
+            ResultData::List(items) => Some(strings(items)),
+            ResultData::Message(_) | ResultData::None => None,
+            #[cfg(feature = "native")]
+            ResultData::Stream(_) => None,
+            _ => self
+                .to_documents()
+                .map(|documents| Bson::Array(documents.into_iter().map(Bson::Document).collect())),
+        }
+    }
+}
+
+impl From<Vec<Document>> for ResultData {
+    fn from(documents: Vec<Document>) -> Self {
+        ResultData::Documents(documents)
+    }
+}
+
+impl From<Document> for ResultData {
+    fn from(document: Document) -> Self {
+        ResultData::Document(document)
+    }
+}
+
+/// Index definition as returned by `listIndexes`
+#[derive(Debug, Clone, PartialEq)]
+pub struct IndexInfo {
+    /// Index name
+    pub name: String,
+
+    /// Key pattern, e.g. `{ email: 1 }`
+    pub key: Document,
+
+    /// Remaining index options (`v`, `unique`, `expireAfterSeconds`, ...)
+    pub options: Document,
+}
+
+impl IndexInfo {
+    /// Split a raw `listIndexes` entry into name, key pattern and options
+    ///
+    /// # Arguments
+    /// * `spec` - Index specification document
+    ///
+    /// # Returns
+    /// * `Self` - Parsed index; missing fields are left empty
+    pub fn from_document(mut spec: Document) -> Self {
+        let name = match spec.remove("name") {
+            Some(Bson::String(name)) => name,
+            _ => String::new(),
+        };
+        let key = match spec.remove("key") {
+            Some(Bson::Document(key)) => key,
+            _ => Document::new(),
+        };
+        Self {
+            name,
+            key,
+            options: spec,
+        }
+    }
+
+    /// Render as a document with the key pattern and name first
+    pub fn to_document(&self) -> Document {
+        let mut document = doc! { "key": self.key.clone(), "name": &self.name };
+        document.extend(self.options.clone());
+        document
+    }
+}
+
+/// Database entry as returned by `listDatabases`
+#[derive(Debug, Clone, PartialEq)]
+pub struct DatabaseInfo {
+    /// Database name
+    pub name: String,
+
+    /// Size on disk in bytes
+    pub size_on_disk: u64,
+
+    /// Whether the database holds no data
+    pub empty: bool,
+}
+
+impl DatabaseInfo {
+    /// Render as a document using the server's field names
+    pub fn to_document(&self) -> Document {
+        doc! {
+            "name": &self.name,
+            "sizeOnDisk": self.size_on_disk as i64,
+            "empty": self.empty,
+        }
+    }
+}
+
+/// Collection entry as returned by `listCollections`
+#[derive(Debug, Clone, PartialEq)]
+pub struct CollectionInfo {
+    /// Collection name
+    pub name: String,
+
+    /// Collection type: `collection`, `view` or `timeseries`
+    pub kind: String,
+}
+
+impl CollectionInfo {
+    /// Render as a document using the server's field names
+    pub fn to_document(&self) -> Document {
+        doc! { "name": &self.name, "type": &self.kind }
+    }
+}
+
+/// User entry as returned by `usersInfo`
+#[derive(Debug, Clone, PartialEq)]
+pub struct UserInfo {
+    /// User name
+    pub user: String,
+
+    /// Database the user is defined in
+    pub db: String,
+
+    /// Granted roles as `role@db`
+    pub roles: Vec<String>,
+}
+
+impl UserInfo {
+    /// Parse a `usersInfo` entry
+    ///
+    /// # Arguments
+    /// * `entry` - User document with `user`, `db` and `roles` fields
+    ///
+    /// # Returns
+    /// * `Self` - Parsed user; missing fields are left empty
+    pub fn from_document(entry: &Document) -> Self {
+        let roles = entry
+            .get_array("roles")
+            .map(|roles| {
+                roles
+                    .iter()
+                    .filter_map(Bson::as_document)
+                    .map(|role| {
+                        format!(
+                            "{}@{}",
+                            role.get_str("role").unwrap_or_default(),
+                            role.get_str("db").unwrap_or_default()
+                        )
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Self {
+            user: entry.get_str("user").unwrap_or_default().to_string(),
+            db: entry.get_str("db").unwrap_or_default().to_string(),
+            roles,
+        }
+    }
+
+    /// Render as a document with roles as `role@db` strings
+    pub fn to_document(&self) -> Document {
+        doc! { "user": &self.user, "db": &self.db, "roles": &self.roles }
+    }
+}
+
 /// Execution statistics
 #[derive(Debug, Clone, Default)]
 pub struct ExecutionStats {
@@ -186,3 +421,66 @@ impl ExecutionResult {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_result_data_to_bson() {
+        let data = ResultData::DocumentsWithPagination {
+            documents: vec![doc! { "_id": 1 }, doc! { "_id": 2 }],
+            has_more: true,
+            displayed: 2,
+        };
+        assert_eq!(
+            data.to_bson(),
+            Some(Bson::Array(vec![
+                Bson::Document(doc! { "_id": 1 }),
+                Bson::Document(doc! { "_id": 2 }),
+            ]))
+        );
+        assert_eq!(ResultData::Count(3).to_bson(), Some(Bson::Int64(3)));
+        assert!(ResultData::Message("ok".to_string()).to_bson().is_none());
+        assert!(ResultData::None.to_bson().is_none());
+    }
+
+    #[test]
+    fn test_index_info_round_trip() {
+        let spec = doc! { "v": 2, "key": { "email": 1 }, "name": "email_1", "unique": true };
+        let index = IndexInfo::from_document(spec);
+        assert_eq!(index.name, "email_1");
+        assert_eq!(index.key, doc! { "email": 1 });
+        assert_eq!(index.options, doc! { "v": 2, "unique": true });
+        assert_eq!(
+            index.to_document(),
+            doc! { "key": { "email": 1 }, "name": "email_1", "v": 2, "unique": true }
+        );
+    }
+
+    #[test]
+    fn test_user_info_roles() {
+        let entry = doc! {
+            "user": "app",
+            "db": "shop",
+            "roles": [{ "role": "readWrite", "db": "shop" }, { "role": "read", "db": "logs" }],
+        };
+        let user = UserInfo::from_document(&entry);
+        assert_eq!(user.user, "app");
+        assert_eq!(user.roles, vec!["readWrite@shop", "read@logs"]);
+    }
+
+    #[test]
+    fn test_to_documents_only_for_typed_listings() {
+        let data = ResultData::Databases(vec![DatabaseInfo {
+            name: "admin".to_string(),
+            size_on_disk: 40960,
+            empty: false,
+        }]);
+        assert_eq!(
+            data.to_documents(),
+            Some(vec![doc! { "name": "admin", "sizeOnDisk": 40960_i64, "empty": false }])
+        );
+        assert_eq!(ResultData::List(vec!["a".to_string()]).to_documents(), None);
+    }
+}
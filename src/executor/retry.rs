@@ -0,0 +1,380 @@
+//! Retry policy for commands that failed with a transient error
+//!
+//! Reads can always be run again. A write may already have been applied when
+//! the connection dropped or the primary stepped down, so it is only safe to
+//! repeat when applying it twice has the same effect as applying it once.
+//! This module tells the two apart and explains what repeating an unsafe
+//! write can do, for the automatic retry and the `retry` command.
+
+use mongodb::bson::{Bson, Document};
+
+use crate::config::ConnectionConfig;
+use crate::parser::{AdminCommand, Command, ConfigCommand, QueryCommand, UtilityCommand};
+
+use super::confirmation::is_read_only_command;
+
+/// Update operators whose effect accumulates when applied again
+const NON_IDEMPOTENT_OPERATORS: &[&str] = &["$inc", "$mul", "$push", "$pop", "$bit"];
+
+/// Check whether a command leaves the data unchanged
+///
+/// # Arguments
+/// * `cmd` - Command to classify
+///
+/// # Returns
+/// * `bool` - True for reads and local commands
+pub fn is_read_only(cmd: &Command) -> bool {
+    match cmd {
+        Command::Query(query) => is_read_only_query(query),
+        Command::Admin(admin) => is_read_only_admin(admin),
+        Command::Config(config) => !matches!(config, ConfigCommand::ExecuteNamedQuery { .. }),
+        Command::Pipe(base, _) => is_read_only(base),
+        Command::WithDatabase { command, .. } | Command::Watch { command, .. } => {
+            is_read_only(command)
+        }
+        Command::Parallel { commands, .. } => commands.iter().all(is_read_only),
+        Command::Utility(UtilityCommand::Inspect { command, .. }) => is_read_only(command),
+        // Plugins can run any command against the server
+        Command::Load(_) | Command::Utility(UtilityCommand::Plugin { .. }) => false,
+        Command::Utility(_)
+        | Command::Help(_)
+        | Command::AiQuery(_)
+        | Command::Retry
+        | Command::Record(_)
+        | Command::Report(_)
+        | Command::Exit => true,
+    }
+}
+
+/// Explain what running a write again can do, if it is not safe to repeat
+///
+/// # Arguments
+/// * `cmd` - Command that failed
+///
+/// # Returns
+/// * `Option<String>` - Hint for writes that are not idempotent, None for
+///   reads and writes that can be repeated safely
+pub fn retry_hint(cmd: &Command) -> Option<String> {
+    match cmd {
+        Command::Query(query) => query_hint(query),
+        Command::Admin(admin) => admin_hint(admin),
+        Command::Config(ConfigCommand::ExecuteNamedQuery { name, .. }) => Some(format!(
+            "the named query '{}' may modify data that was already changed",
+            name
+        )),
+        Command::Pipe(base, _) => retry_hint(base),
+        Command::WithDatabase { command, .. } => retry_hint(command),
+        Command::Parallel { commands, .. } => commands
+            .iter()
+            .find_map(retry_hint)
+            .map(|hint| format!("tasks that completed run again; {}", hint)),
+        Command::Load(_) => Some("statements that ran before the error run again".to_string()),
+        Command::Utility(UtilityCommand::Plugin { prefix, .. }) => Some(format!(
+            "the plugin command '{}' may modify data that was already changed",
+            prefix
+        )),
+        _ => None,
+    }
+}
+
+/// Decide whether a failed command is run once more automatically
+///
+/// # Arguments
+/// * `cmd` - Command that failed with a transient error
+/// * `config` - Connection settings with the retry switches
+///
+/// # Returns
+/// * `bool` - True if the command may be retried without asking
+pub fn should_auto_retry(cmd: &Command, config: &ConnectionConfig) -> bool {
+    if is_read_only(cmd) {
+        config.auto_retry_reads
+    } else {
+        config.auto_retry_writes && retry_hint(cmd).is_none()
+    }
+}
+
+/// Check whether a query command only reads
+fn is_read_only_query(query: &QueryCommand) -> bool {
+    match query {
+        QueryCommand::Aggregate { pipeline, .. } => !writes_output(pipeline),
+        QueryCommand::Find { .. }
+        | QueryCommand::FindOne { .. }
+        | QueryCommand::CountDocuments { .. }
+        | QueryCommand::EstimatedDocumentCount { .. }
+        | QueryCommand::Distinct { .. }
+        | QueryCommand::DocSize { .. }
+        | QueryCommand::Diff { .. }
+        | QueryCommand::Search { .. }
+        | QueryCommand::VectorSearch { .. }
+        | QueryCommand::Explain { .. } => true,
+        _ => false,
+    }
+}
+
+/// Check whether an admin command only reads
+fn is_read_only_admin(admin: &AdminCommand) -> bool {
+    match admin {
+        AdminCommand::RunCommand { command, .. } => command
+            .keys()
+            .next()
+            .is_some_and(|name| is_read_only_command(name)),
+        AdminCommand::ShowDatabases
+        | AdminCommand::ShowCollections
+        | AdminCommand::ShowUsers
+        | AdminCommand::ShowRoles
+        | AdminCommand::ShowProfile
+        | AdminCommand::ShowLogs { .. }
+        | AdminCommand::Top { .. }
+        | AdminCommand::CurrentOp { .. }
+        | AdminCommand::ReplicationLag { .. }
+        | AdminCommand::OplogInfo
+        | AdminCommand::OplogTail { .. }
+        | AdminCommand::UseDatabase(_)
+        | AdminCommand::ListIndexes(_)
+        | AdminCommand::StorageReport
+        | AdminCommand::ClusterReport
+        | AdminCommand::IndexStats(_)
+        | AdminCommand::IndexReport { .. }
+        | AdminCommand::CollectionStats { .. }
+        | AdminCommand::GetValidator(_)
+        | AdminCommand::ValidateCollection { .. }
+        | AdminCommand::ListBackups
+        | AdminCommand::Dump { .. } => true,
+        _ => false,
+    }
+}
+
+/// Hint for query writes that are not safe to repeat
+fn query_hint(query: &QueryCommand) -> Option<String> {
+    match query {
+        QueryCommand::InsertOne { document, .. } => {
+            Some(insert_hint(std::slice::from_ref(document)))
+        }
+        QueryCommand::InsertMany { documents, .. } => Some(insert_hint(documents)),
+        QueryCommand::UpdateOne { update, .. }
+        | QueryCommand::UpdateMany { update, .. }
+        | QueryCommand::FindOneAndUpdate { update, .. }
+        | QueryCommand::BulkUpdateWithRateLimit { update, .. } => update_hint(update),
+        QueryCommand::DeleteOne { filter, .. } | QueryCommand::FindOneAndDelete { filter, .. } => {
+            delete_one_hint(filter)
+        }
+        QueryCommand::FindAndModify {
+            query,
+            remove,
+            update,
+            ..
+        } => {
+            if *remove {
+                delete_one_hint(query)
+            } else {
+                update.as_ref().and_then(update_hint)
+            }
+        }
+        QueryCommand::BulkWrite { operations, .. } => operations
+            .iter()
+            .find_map(bulk_operation_hint)
+            .map(|hint| format!("operations that succeeded are applied again; {}", hint)),
+        _ => None,
+    }
+}
+
+/// Hint for admin writes that are not safe to repeat
+fn admin_hint(admin: &AdminCommand) -> Option<String> {
+    match admin {
+        AdminCommand::DropIndex { .. }
+        | AdminCommand::DropIndexes { .. }
+        | AdminCommand::DropCollection { .. }
+        | AdminCommand::DropCollections { .. }
+        | AdminCommand::CleanupBackups { .. } => {
+            Some("it fails with a not-found error if the drop already happened".to_string())
+        }
+        AdminCommand::RenameCollection { .. } => {
+            Some("it fails if the collection was already renamed".to_string())
+        }
+        AdminCommand::CreateCollection { .. } => {
+            Some("it fails if the collection was already created".to_string())
+        }
+        AdminCommand::Restore { drop: false, .. } => {
+            Some("documents that were restored are inserted again".to_string())
+        }
+        AdminCommand::ImportCsv { .. } => {
+            Some("rows that were imported are inserted again".to_string())
+        }
+        AdminCommand::UndoLastDelete => {
+            Some("it restores the delete before the last one if the first run finished".to_string())
+        }
+        AdminCommand::RunCommand { command, .. } => command
+            .keys()
+            .next()
+            .filter(|name| !is_read_only_command(name))
+            .map(|name| {
+                format!(
+                    "the server command '{}' may already have been applied",
+                    name
+                )
+            }),
+        _ => None,
+    }
+}
+
+/// Hint for inserts, which either duplicate documents or hit duplicate keys
+fn insert_hint(documents: &[Document]) -> String {
+    if documents.iter().all(|doc| doc.contains_key("_id")) {
+        "documents inserted by the first attempt make it fail with duplicate key errors".to_string()
+    } else {
+        "documents inserted by the first attempt are inserted again under new _ids".to_string()
+    }
+}
+
+/// Hint for updates using operators that accumulate
+fn update_hint(update: &Document) -> Option<String> {
+    update
+        .keys()
+        .find(|key| NON_IDEMPOTENT_OPERATORS.contains(&key.as_str()))
+        .map(|operator| {
+            format!(
+                "{} is applied again if the first attempt succeeded",
+                operator
+            )
+        })
+}
+
+/// Hint for deleting a single document chosen by a non-unique filter
+fn delete_one_hint(filter: &Document) -> Option<String> {
+    if filter.contains_key("_id") {
+        None
+    } else {
+        Some("another document matching the filter may be deleted".to_string())
+    }
+}
+
+/// Hint for one `bulkWrite` operation, e.g. `{ insertOne: { document } }`
+fn bulk_operation_hint(operation: &Document) -> Option<String> {
+    let (name, body) = operation.iter().next()?;
+    let body = match body {
+        Bson::Document(body) => body,
+        _ => return None,
+    };
+    match name.as_str() {
+        "insertOne" => body
+            .get_document("document")
+            .ok()
+            .map(|doc| insert_hint(std::slice::from_ref(doc))),
+        "updateOne" | "updateMany" => body.get_document("update").ok().and_then(update_hint),
+        "deleteOne" => body.get_document("filter").ok().and_then(delete_one_hint),
+        _ => None,
+    }
+}
+
+/// Check whether a pipeline writes its output with `$out` or `$merge`
+fn writes_output(pipeline: &[Document]) -> bool {
+    pipeline
+        .last()
+        .is_some_and(|stage| stage.contains_key("$out") || stage.contains_key("$merge"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::{AggregateOptions, UpdateOptions};
+    use mongodb::bson::doc;
+
+    fn update_many(update: Document) -> Command {
+        Command::Query(QueryCommand::UpdateMany {
+            collection: "orders".to_string(),
+            filter: doc! { "status": "open" },
+            update,
+            options: UpdateOptions::default(),
+        })
+    }
+
+    #[test]
+    fn test_is_read_only() {
+        let find = Command::Query(QueryCommand::CountDocuments {
+            collection: "orders".to_string(),
+            filter: doc! {},
+        });
+        assert!(is_read_only(&find));
+
+        let aggregate = |pipeline: Vec<Document>| {
+            Command::Query(QueryCommand::Aggregate {
+                collection: "orders".to_string(),
+                pipeline,
+                options: AggregateOptions::default(),
+            })
+        };
+        assert!(is_read_only(&aggregate(vec![doc! { "$match": {} }])));
+        assert!(!is_read_only(&aggregate(vec![doc! { "$out": "copy" }])));
+
+        let server_status = Command::Admin(AdminCommand::RunCommand {
+            command: doc! { "serverStatus": 1 },
+            admin: true,
+        });
+        assert!(is_read_only(&server_status));
+        assert!(!is_read_only(&update_many(doc! { "$set": { "a": 1 } })));
+
+        let plugin = Command::Utility(UtilityCommand::Plugin {
+            prefix: "graph".to_string(),
+            input: "graph rebuild".to_string(),
+        });
+        assert!(!is_read_only(&plugin));
+        assert!(retry_hint(&plugin).is_some());
+    }
+
+    #[test]
+    fn test_retry_hint() {
+        assert!(retry_hint(&update_many(doc! { "$set": { "a": 1 } })).is_none());
+        assert_eq!(
+            retry_hint(&update_many(
+                doc! { "$set": { "a": 1 }, "$inc": { "n": 1 } }
+            ))
+            .as_deref(),
+            Some("$inc is applied again if the first attempt succeeded")
+        );
+
+        let insert = Command::Query(QueryCommand::InsertOne {
+            collection: "orders".to_string(),
+            document: doc! { "sku": "a" },
+        });
+        assert!(retry_hint(&insert).unwrap().contains("new _ids"));
+
+        let delete = |filter: Document| {
+            Command::Query(QueryCommand::DeleteOne {
+                collection: "orders".to_string(),
+                filter,
+            })
+        };
+        assert!(retry_hint(&delete(doc! { "_id": 1 })).is_none());
+        assert!(retry_hint(&delete(doc! { "status": "old" })).is_some());
+
+        let bulk = Command::Query(QueryCommand::BulkWrite {
+            collection: "orders".to_string(),
+            operations: vec![
+                doc! { "deleteMany": { "filter": {} } },
+                doc! { "updateOne": { "filter": {}, "update": { "$push": { "tags": "x" } } } },
+            ],
+            ordered: true,
+        });
+        assert!(retry_hint(&bulk).unwrap().contains("$push"));
+    }
+
+    #[test]
+    fn test_should_auto_retry() {
+        let mut config = ConnectionConfig::default();
+        let read = Command::Query(QueryCommand::EstimatedDocumentCount {
+            collection: "orders".to_string(),
+        });
+        let safe_write = update_many(doc! { "$set": { "a": 1 } });
+        let unsafe_write = update_many(doc! { "$inc": { "n": 1 } });
+
+        assert!(should_auto_retry(&read, &config));
+        assert!(!should_auto_retry(&safe_write, &config));
+
+        config.auto_retry_writes = true;
+        assert!(should_auto_retry(&safe_write, &config));
+        assert!(!should_auto_retry(&unsafe_write, &config));
+
+        config.auto_retry_reads = false;
+        assert!(!should_auto_retry(&read, &config));
+    }
+}
@@ -6,25 +6,51 @@
 //! - Admin commands → AdminExecutor
 //! - Utility commands → UtilityExecutor
 
+use crossterm::cursor::MoveTo;
+use crossterm::execute;
+use crossterm::terminal::{Clear, ClearType};
+use futures::stream::{self, StreamExt};
+use mongodb::bson::{Document, doc};
 use std::collections::HashMap;
 use std::fs;
-use std::time::Instant;
+use std::io::{self, IsTerminal};
+use std::path::Path;
+use std::time::{Duration, Instant};
 use tabled::{builder::Builder, settings::Style};
 use tracing::debug;
 
-use crate::config::{Config, OutputFormat};
+use crate::cli::prompt;
+use crate::config::{Config, DisplayConfig, OutputFormat};
 use crate::error::{ExecutionError, Result};
-use crate::parser::{Command, ConfigCommand, ExportFormat, PipeCommand, QueryMode};
-
+#[cfg(feature = "export")]
+use crate::formatter::Redactor;
+use crate::formatter::{Formatter, WatchFormatter};
+use crate::parser::{
+    Command, ConfigCommand, ExportCompression, ExportFormat, PipeCommand, QueryMode,
+};
+#[cfg(feature = "plugins")]
+use crate::plugin;
+
+use crate::repl::SharedState;
 use crate::repl::ai_context::ContextReader;
+use crate::repl::bookmarks::{self, BookmarkStore};
 #[cfg(feature = "ai-completion")]
 use crate::repl::ai_context::{ContextGenerator, Sampler};
+use crate::repl::session::SessionState;
 
 use super::admin::AdminExecutor;
-use super::context::ExecutionContext;
-use super::export::{CsvWriter, ExportCoordinator, FormatWriter, JsonLWriter, ProgressTracker};
+use super::confirmation::{confirm, needs_confirmation};
+use super::context::{ConfirmationMode, ExecutionContext};
+#[cfg(feature = "export")]
+use super::export::writers::{OutputStream, wrap_stream};
+#[cfg(feature = "export")]
+use super::export::{
+    CsvWriter, ExportCoordinator, FormatWriter, HtmlWriter, JsonLWriter, ProgressTracker,
+    ProgressUnit, XlsxWriter,
+};
 use super::query::QueryExecutor;
 use super::result::{ExecutionResult, ExecutionStats, ResultData};
+use super::retry::is_read_only;
 use super::utility::UtilityExecutor;
 
 /// Command router that dispatches commands to appropriate executors
@@ -55,6 +81,21 @@ impl CommandRouter {
     pub async fn route(&self, command: Command) -> Result<ExecutionResult> {
         debug!("Routing command: {:?}", command);
 
+        // Named queries and scripts are checked as their commands run
+        let runs_commands = matches!(
+            command,
+            Command::Load(_) | Command::Config(ConfigCommand::ExecuteNamedQuery { .. })
+        );
+        if self.context.shared_state.get_safety_config().read_only
+            && !runs_commands
+            && !is_read_only(&command)
+        {
+            return Err(ExecutionError::InvalidOperation(
+                "Writes are disabled by safety.read_only".to_string(),
+            )
+            .into());
+        }
+
         let start = Instant::now();
 
         let result = match command {
@@ -72,6 +113,10 @@ impl CommandRouter {
             }
             Command::Config(config_cmd) => self.execute_config(config_cmd).await,
             Command::Pipe(base_cmd, pipe_cmd) => self.execute_pipe(*base_cmd, pipe_cmd).await,
+            Command::WithDatabase { database, command } => {
+                let router = CommandRouter::new(self.context.with_database(database)).await?;
+                Box::pin(router.route(*command)).await
+            }
             Command::Help(topic) => self.execute_help(topic).await,
             Command::AiQuery(_) => Ok(ExecutionResult {
                 success: true,
@@ -81,6 +126,35 @@ impl CommandRouter {
                 stats: ExecutionStats::default(),
                 error: None,
             }),
+            Command::Parallel {
+                commands,
+                concurrency,
+            } => self.execute_parallel(commands, concurrency).await,
+            Command::Watch {
+                interval,
+                source,
+                command,
+            } => self.execute_watch(interval, &source, *command).await,
+            // Only the REPL and script mode can run the statements of a file
+            Command::Load(path) => Err(ExecutionError::InvalidOperation(format!(
+                "load('{}') is only available in the interactive shell and scripts",
+                path
+            ))
+            .into()),
+            Command::Retry => Ok(ExecutionResult {
+                success: true,
+                data: ResultData::Message(
+                    "Failed commands are retried by the REPL loop.".to_string(),
+                ),
+                stats: ExecutionStats::default(),
+                error: None,
+            }),
+            Command::Record(_) | Command::Report(_) => Ok(ExecutionResult {
+                success: true,
+                data: ResultData::Message("Sessions are recorded by the REPL loop.".to_string()),
+                stats: ExecutionStats::default(),
+                error: None,
+            }),
             Command::Exit => Ok(ExecutionResult {
                 success: true,
                 data: ResultData::Message("Exiting...".to_string()),
@@ -95,6 +169,177 @@ impl CommandRouter {
         result
     }
 
+    /// Execute commands concurrently over the shared connection pool
+    ///
+    /// At most `concurrency` commands run at once. Every command runs even if
+    /// another fails; the result has one summary document per command, in
+    /// the order given. Tasks that need confirmation are confirmed once for
+    /// the whole batch before any task starts, so concurrent tasks never
+    /// compete for stdin.
+    ///
+    /// # Arguments
+    /// * `commands` - Commands to execute
+    /// * `concurrency` - Maximum number of commands in flight
+    ///
+    /// # Returns
+    /// * `Result<ExecutionResult>` - Per-command summaries
+    async fn execute_parallel(
+        &self,
+        commands: Vec<Command>,
+        concurrency: usize,
+    ) -> Result<ExecutionResult> {
+        let start = Instant::now();
+        let total = commands.len();
+
+        let dangerous: Vec<String> = commands
+            .iter()
+            .enumerate()
+            .filter(|(_, command)| needs_confirmation(command))
+            .map(|(i, _)| (i + 1).to_string())
+            .collect();
+        let context = if dangerous.is_empty() {
+            self.context.clone()
+        } else {
+            if self.context.confirmation() == ConfirmationMode::Ask {
+                println!("parallel: task(s) {} modify data", dangerous.join(", "));
+            }
+            if !confirm(self.context.confirmation(), None)? {
+                return Ok(ExecutionResult {
+                    success: true,
+                    data: ResultData::Message("Operation cancelled by user".to_string()),
+                    stats: ExecutionStats::default(),
+                    error: None,
+                });
+            }
+            self.context.with_confirmation(ConfirmationMode::Confirmed)
+        };
+        let router = CommandRouter::new(context).await?;
+
+        let results: Vec<Result<ExecutionResult>> = stream::iter(
+            commands
+                .into_iter()
+                .map(|command| Box::pin(router.route(command))),
+        )
+        .buffered(concurrency.max(1))
+        .collect()
+        .await;
+
+        let mut failed = 0;
+        let documents: Vec<Document> = results
+            .iter()
+            .enumerate()
+            .map(|(i, result)| {
+                let mut summary = doc! { "task": (i + 1) as i64 };
+                match result {
+                    Ok(result) if result.success => {
+                        summary.insert("ok", true);
+                        for (key, value) in result_summary(&result.data) {
+                            summary.insert(key, value);
+                        }
+                    }
+                    Ok(result) => {
+                        failed += 1;
+                        summary.insert("ok", false);
+                        summary.insert("error", result.error.clone().unwrap_or_default());
+                    }
+                    Err(e) => {
+                        failed += 1;
+                        summary.insert("ok", false);
+                        summary.insert("error", e.to_string());
+                    }
+                }
+                summary
+            })
+            .collect();
+
+        debug!("parallel: {} of {} tasks failed", failed, total);
+
+        Ok(ExecutionResult {
+            success: failed == 0,
+            data: ResultData::Documents(documents),
+            stats: ExecutionStats {
+                execution_time_ms: start.elapsed().as_millis() as u64,
+                documents_returned: total,
+                ..Default::default()
+            },
+            error: (failed > 0).then(|| format!("{} of {} parallel tasks failed", failed, total)),
+        })
+    }
+
+    /// Re-run a command every `interval` seconds until Ctrl+C
+    ///
+    /// Each run replaces the previous one on screen, with output that changed
+    /// since the previous run highlighted. A failing run shows its error and
+    /// the watch goes on.
+    ///
+    /// # Arguments
+    /// * `interval` - Seconds between runs
+    /// * `source` - Watched command as typed, for the header
+    /// * `command` - Command to run
+    ///
+    /// # Returns
+    /// * `Result<ExecutionResult>` - Number of runs once interrupted
+    async fn execute_watch(
+        &self,
+        interval: u64,
+        source: &str,
+        command: Command,
+    ) -> Result<ExecutionResult> {
+        let state = &self.context.shared_state;
+        let accessible = state.get_accessible();
+        // Output is compared as plain text; changes get the only highlighting
+        let display = DisplayConfig {
+            format: command
+                .format_override()
+                .unwrap_or_else(|| state.get_format()),
+            color_output: false,
+            show_timing: false,
+            accessible,
+            ..DisplayConfig::default()
+        };
+        let formatter = Formatter::from_config(&display)
+            .with_projection(command.pluck_fields().unwrap_or_default())
+            .with_redaction(&state.get_redaction_config());
+        let watch = WatchFormatter::new(state.get_color_enabled() && !accessible);
+        let clear_screen = !accessible && io::stdout().is_terminal();
+        let cancel_token = self.context.get_cancel_token();
+
+        let mut previous: Option<String> = None;
+        let mut runs = 0u64;
+        loop {
+            let output = match Box::pin(self.route(command.clone())).await {
+                Ok(result) => formatter.format(&result).unwrap_or_else(|e| e.to_string()),
+                Err(e) => e.to_string(),
+            };
+            if cancel_token.is_cancelled() {
+                break;
+            }
+            runs += 1;
+
+            if clear_screen {
+                let _ = execute!(io::stdout(), Clear(ClearType::All), MoveTo(0, 0));
+            } else if runs > 1 {
+                println!();
+            }
+            let time = chrono::Local::now().format("%H:%M:%S").to_string();
+            println!("{}\n", watch.header(interval, source, &time));
+            println!("{}", watch.highlight_changes(previous.as_deref(), &output));
+            previous = Some(output);
+
+            tokio::select! {
+                _ = cancel_token.cancelled() => break,
+                _ = tokio::time::sleep(Duration::from_secs(interval)) => {}
+            }
+        }
+
+        Ok(ExecutionResult {
+            success: true,
+            data: ResultData::Message(format!("Stopped watching after {} run(s)", runs)),
+            stats: ExecutionStats::default(),
+            error: None,
+        })
+    }
+
     /// Execute piped command (query |> export/explain)
     ///
     /// # Arguments
@@ -110,100 +355,20 @@ impl CommandRouter {
     ) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<ExecutionResult>> + Send + '_>>
     {
         Box::pin(async move {
-            match pipe_cmd {
-                PipeCommand::Export { format, file } => {
-                    // Execute query in streaming mode for export
-                    let result = if let Command::Query(query_cmd) = base_cmd {
-                        let executor = QueryExecutor::new(self.context.clone()).await?;
-                        executor
-                            .execute(query_cmd, QueryMode::Streaming { batch_size: 1000 })
-                            .await?
-                    } else {
-                        return Err(ExecutionError::InvalidOperation(
-                            "Export can only be used with query commands".to_string(),
-                        )
-                        .into());
-                    };
-
-                    // Extract streaming query from result
-                    let query = match result.data {
-                        ResultData::Stream(stream) => stream,
-                        _ => {
-                            return Err(ExecutionError::InvalidOperation(
-                                "Query did not return streaming data for export".to_string(),
-                            )
-                            .into());
-                        }
-                    };
-
-                    // Generate filename if not provided
-                    let filename = file.unwrap_or_else(|| {
-                        use chrono::Local;
-                        let timestamp = Local::now().format("%Y-%m-%d_%H-%M-%S");
-                        match format {
-                            ExportFormat::JsonL => format!("export-{}.jsonl", timestamp),
-                            ExportFormat::Csv => format!("export-{}.csv", timestamp),
-                        }
-                    });
-
-                    // Create format writer
-                    let writer: Box<dyn FormatWriter> = match format {
-                        ExportFormat::JsonL => Box::new(JsonLWriter::new(&filename).await?),
-                        ExportFormat::Csv => Box::new(CsvWriter::new(&filename).await?),
-                    };
-
-                    // Create progress tracker
-                    let tracker = ProgressTracker::new(None, true);
-
-                    // Create cancellation token and setup Ctrl+C handler
-                    let cancel_token = tokio_util::sync::CancellationToken::new();
-                    let cancel_token_clone = cancel_token.clone();
-
-                    // Setup Ctrl+C handler for cancellation
-                    tokio::spawn(async move {
-                        match tokio::signal::ctrl_c().await {
-                            Ok(()) => {
-                                cancel_token_clone.cancel();
-                            }
-                            Err(err) => {
-                                eprintln!("Failed to listen for Ctrl+C: {}", err);
-                            }
-                        }
-                    });
-
-                    // Create coordinator and execute export with cancellation support
-                    let mut coordinator = ExportCoordinator::new(query, tracker, writer)
-                        .with_cancellation(cancel_token);
-                    let export_result = coordinator.execute().await?;
-
-                    // Format result message based on cancellation status
-                    let message = if export_result.cancelled {
-                        format!(
-                            "Export cancelled. Exported {} documents to {} ({:.2} MB) before cancellation",
-                            export_result.documents_exported,
-                            filename,
-                            export_result.file_size_bytes as f64 / 1024.0 / 1024.0
-                        )
-                    } else {
-                        format!(
-                            "Exported {} documents to {} ({:.2} MB) in {:.2}s",
-                            export_result.documents_exported,
-                            filename,
-                            export_result.file_size_bytes as f64 / 1024.0 / 1024.0,
-                            export_result.elapsed_ms as f64 / 1000.0
-                        )
-                    };
+            // Pipe on a sibling database: run the whole pipe in that database
+            if let Command::WithDatabase { database, command } = base_cmd {
+                let router = CommandRouter::new(self.context.with_database(database)).await?;
+                return router.execute_pipe(*command, pipe_cmd).await;
+            }
 
-                    Ok(ExecutionResult {
-                        success: true,
-                        data: ResultData::Message(message),
-                        stats: ExecutionStats {
-                            execution_time_ms: export_result.elapsed_ms,
-                            documents_returned: 0,
-                            documents_affected: Some(export_result.documents_exported),
-                        },
-                        error: None,
-                    })
+            match pipe_cmd {
+                PipeCommand::Export {
+                    format,
+                    file,
+                    compression,
+                } => {
+                    self.execute_export(base_cmd, format, file, compression)
+                        .await
                 }
                 PipeCommand::Explain => {
                     // Execute base command normally for explain
@@ -220,10 +385,288 @@ impl CommandRouter {
                         error: None,
                     })
                 }
+                // Only change how the result is displayed
+                PipeCommand::Format(_) | PipeCommand::Pluck(_) | PipeCommand::Full => {
+                    self.route(base_cmd).await
+                }
+                PipeCommand::Save(path) => {
+                    let format = base_cmd.format_override();
+                    let fields = base_cmd.pluck_fields().unwrap_or_default().to_vec();
+                    let result = self.route(base_cmd).await?;
+                    if !result.success {
+                        return Ok(result);
+                    }
+
+                    self.context.shared_state.set_last_result(result.data.clone());
+                    UtilityExecutor::new(self.context.clone())
+                        .save_result(&result, &path, format, &fields)
+                }
+                PipeCommand::Copy => {
+                    let fields = base_cmd.pluck_fields().unwrap_or_default().to_vec();
+                    let result = self.route(base_cmd).await?;
+                    if !result.success {
+                        return Ok(result);
+                    }
+
+                    self.context.shared_state.set_last_result(result.data.clone());
+                    UtilityExecutor::new(self.context.clone()).copy_result(&result, &fields)
+                }
             }
         })
     }
 
+    /// Execute a query and stream its results to a file, stdout or a command
+    ///
+    /// # Arguments
+    /// * `base_cmd` - Query command to export
+    /// * `format` - Output file format
+    /// * `file` - Output file, `-` for stdout, `!command` to pipe into a shell
+    ///   command, or a timestamped file name when not given
+    /// * `compression` - Explicit compression; otherwise taken from the file extension
+    ///
+    /// # Returns
+    /// * `Result<ExecutionResult>` - Export summary or error
+    #[cfg(feature = "export")]
+    async fn execute_export(
+        &self,
+        base_cmd: Command,
+        format: ExportFormat,
+        file: Option<String>,
+        compression: Option<ExportCompression>,
+    ) -> Result<ExecutionResult> {
+        let to_stdout = file.as_deref() == Some("-");
+        let shell_command = file
+            .as_deref()
+            .and_then(|f| f.strip_prefix('!'))
+            .map(|c| c.trim().to_string());
+        let to_file = !to_stdout && shell_command.is_none();
+
+        let compression = compression.or_else(|| {
+            file.as_deref()
+                .filter(|_| to_file)
+                .and_then(ExportCompression::from_path)
+        });
+        if format == ExportFormat::Xlsx && compression.is_some() {
+            return Err(ExecutionError::InvalidOperation(
+                "xlsx exports cannot be compressed: the format is already zip-compressed"
+                    .to_string(),
+            )
+            .into());
+        }
+        if format == ExportFormat::Xlsx && !to_file {
+            return Err(ExecutionError::InvalidOperation(
+                "xlsx exports need a file; use jsonl or csv for stdout and commands".to_string(),
+            )
+            .into());
+        }
+        if shell_command.as_deref() == Some("") {
+            return Err(
+                ExecutionError::InvalidOperation("Missing command after '!'".to_string()).into(),
+            );
+        }
+
+        // Execute query in streaming mode for export
+        let result = if let Command::Query(query_cmd) = base_cmd {
+            let executor = QueryExecutor::new(self.context.clone()).await?;
+            executor
+                .execute(query_cmd, QueryMode::Streaming { batch_size: 1000 })
+                .await?
+        } else {
+            return Err(ExecutionError::InvalidOperation(
+                "Export can only be used with query commands".to_string(),
+            )
+            .into());
+        };
+
+        // Extract streaming query from result
+        let query = match result.data {
+            ResultData::Stream(stream) => stream,
+            _ => {
+                return Err(ExecutionError::InvalidOperation(
+                    "Query did not return streaming data for export".to_string(),
+                )
+                .into());
+            }
+        };
+
+        // Streams: stdout, or the stdin of a spawned command
+        let mut child = None;
+        let (writer, filename) = if let Some(command) = &shell_command {
+            let mut process = spawn_export_command(command)?;
+            let stdin = process.stdin.take().ok_or_else(|| {
+                ExecutionError::InvalidOperation(format!(
+                    "Failed to open the input of '{}'",
+                    command
+                ))
+            })?;
+            child = Some(process);
+            let stream = wrap_stream(tokio::io::BufWriter::new(stdin), compression);
+            let target = format!("'{}'", command);
+            (stream_writer(format, stream, &target), target)
+        } else if to_stdout {
+            let stream = wrap_stream(tokio::io::BufWriter::new(tokio::io::stdout()), compression);
+            (
+                stream_writer(format, stream, "stdout"),
+                "stdout".to_string(),
+            )
+        } else {
+            self.file_writer(format, file, compression).await?
+        };
+
+        // Progress output would interleave with the exported data
+        let accessible = self.context.shared_state.get_accessible();
+        let tracker = if to_file {
+            ProgressTracker::for_terminal("Exporting", None, ProgressUnit::Documents, accessible)
+        } else {
+            ProgressTracker::new(None, false)
+        };
+
+        // Create cancellation token and setup Ctrl+C handler
+        let cancel_token = tokio_util::sync::CancellationToken::new();
+        let cancel_token_clone = cancel_token.clone();
+
+        // Setup Ctrl+C handler for cancellation
+        tokio::spawn(async move {
+            match tokio::signal::ctrl_c().await {
+                Ok(()) => {
+                    cancel_token_clone.cancel();
+                }
+                Err(err) => {
+                    eprintln!("Failed to listen for Ctrl+C: {}", err);
+                }
+            }
+        });
+
+        // Create coordinator and execute export with cancellation support
+        let redactor = Redactor::from_config(&self.context.shared_state.get_redaction_config());
+        let mut coordinator = ExportCoordinator::new(query, tracker, writer)
+            .with_cancellation(cancel_token)
+            .with_redaction(redactor);
+        let export_result = coordinator.execute().await?;
+
+        // Dropping the writer closes the command's input so it can finish
+        drop(coordinator);
+        let exit_note = match child {
+            Some(mut child) => {
+                let status = child.wait().await.map_err(|e| {
+                    ExecutionError::InvalidOperation(format!("Failed to wait for command: {}", e))
+                })?;
+                if status.success() {
+                    String::new()
+                } else {
+                    format!(" (command exited with {})", status)
+                }
+            }
+            None => String::new(),
+        };
+
+        // Format result message based on cancellation status
+        let message = if export_result.cancelled {
+            format!(
+                "Export cancelled. Exported {} documents to {} before cancellation",
+                export_result.documents_exported, filename
+            )
+        } else if !to_file {
+            format!(
+                "Exported {} documents to {} in {:.2}s{}",
+                export_result.documents_exported,
+                filename,
+                export_result.elapsed_ms as f64 / 1000.0,
+                exit_note
+            )
+        } else {
+            format!(
+                "Exported {} documents to {} ({:.2} MB) in {:.2}s",
+                export_result.documents_exported,
+                filename,
+                export_result.file_size_bytes as f64 / 1024.0 / 1024.0,
+                export_result.elapsed_ms as f64 / 1000.0
+            )
+        };
+
+        // stdout carries the data, so the summary goes to stderr
+        let message = if to_stdout {
+            eprintln!("{}", message);
+            String::new()
+        } else {
+            message
+        };
+
+        Ok(ExecutionResult {
+            success: true,
+            data: ResultData::Message(message),
+            stats: ExecutionStats {
+                execution_time_ms: export_result.elapsed_ms,
+                documents_returned: 0,
+                documents_affected: Some(export_result.documents_exported),
+            },
+            error: None,
+        })
+    }
+
+    /// Create the writer for a file export
+    ///
+    /// # Arguments
+    /// * `format` - Output file format
+    /// * `file` - Output file, or a timestamped name when not given
+    /// * `compression` - Compression to apply, if any
+    ///
+    /// # Returns
+    /// * `Result<(Box<dyn FormatWriter>, String)>` - Writer and the file name
+    #[cfg(feature = "export")]
+    async fn file_writer(
+        &self,
+        format: ExportFormat,
+        file: Option<String>,
+        compression: Option<ExportCompression>,
+    ) -> Result<(Box<dyn FormatWriter>, String)> {
+        // Generate filename if not provided
+        let filename = file.unwrap_or_else(|| {
+            use chrono::Local;
+            let timestamp = Local::now().format("%Y-%m-%d_%H-%M-%S");
+            let name = match format {
+                ExportFormat::JsonL => format!("export-{}.jsonl", timestamp),
+                ExportFormat::Csv => format!("export-{}.csv", timestamp),
+                ExportFormat::Xlsx => format!("export-{}.xlsx", timestamp),
+                ExportFormat::Html => format!("export-{}.html", timestamp),
+            };
+            match compression {
+                Some(compression) => format!("{}.{}", name, compression.extension()),
+                None => name,
+            }
+        });
+
+        // Create format writer
+        let writer: Box<dyn FormatWriter> = match format {
+            ExportFormat::JsonL => {
+                Box::new(JsonLWriter::with_compression(&filename, compression).await?)
+            }
+            ExportFormat::Csv => {
+                Box::new(CsvWriter::with_compression(&filename, compression).await?)
+            }
+            ExportFormat::Xlsx => Box::new(XlsxWriter::new(&filename).await?),
+            ExportFormat::Html => {
+                Box::new(HtmlWriter::with_compression(&filename, compression).await?)
+            }
+        };
+        Ok((writer, filename))
+    }
+
+    /// Report that export support was left out of this build
+    #[cfg(not(feature = "export"))]
+    async fn execute_export(
+        &self,
+        _base_cmd: Command,
+        _format: ExportFormat,
+        _file: Option<String>,
+        _compression: Option<ExportCompression>,
+    ) -> Result<ExecutionResult> {
+        Err(ExecutionError::InvalidOperation(
+            "Export is not available: mongosh was built without the 'export' feature".to_string(),
+        )
+        .into())
+    }
+
     /// Execute help command
     ///
     /// # Arguments
@@ -232,38 +675,13 @@ impl CommandRouter {
     /// # Returns
     /// * `Result<ExecutionResult>` - Help text
     async fn execute_help(&self, topic: Option<String>) -> Result<ExecutionResult> {
-        let help_text = if let Some(t) = topic {
-            format!("Help for: {}\n(Not yet implemented)", t)
-        } else {
-            r#"MongoDB Shell Commands:
-
-Configuration:
-  format [shell|json|json-pretty|table|compact] - Set/get output format
-  color [on|off]                                - Enable/disable color output
-  config                                        - Show current configuration
-
-Named Queries:
-  query                                       - List all named queries
-  query <name> [args...]                      - Execute a named query with arguments
-  query save <name> <query>                   - Save a new named query
-  query delete <name>                         - Delete a named query
-
-  Parameter substitution:
-    '$1', '$2'...                             - String parameters (with quotes in template)
-    $1, $2...                                 - Numeric/raw parameters (no quotes in template)
-    $*                                        - Raw aggregation: 18, 25, 30
-    $@                                        - String aggregation: 'admin', 'user'
-
-  Examples:
-    query save user "db.users.find({name: '\$1', age: \$2})"
-    query user John 25                        -> {name: 'John', age: 25}
-
-Utility:
-  help                                        - Show this help
-  help <command>                              - Show help for specific command
-  exit / quit                                 - Exit shell
-"#
-            .to_string()
+        let use_colors = self.context.shared_state.get_color_enabled();
+        let help_text = match topic {
+            Some(t) => match crate::cli::help::lookup(&t) {
+                Ok(cmd) => crate::cli::help::render_styled(cmd, use_colors),
+                Err(suggestions) => crate::cli::help::unknown_topic_message(&t, &suggestions),
+            },
+            None => crate::cli::help::render_overview(use_colors),
         };
 
         Ok(ExecutionResult {
@@ -286,18 +704,15 @@ Utility:
 
         let message = match cmd {
             ConfigCommand::SetFormat(format_str) => {
-                let format = match format_str.to_lowercase().as_str() {
-                    "shell" => OutputFormat::Shell,
-                    "json" => OutputFormat::Json,
-                    "json-pretty" | "jsonpretty" => OutputFormat::JsonPretty,
-                    "table" => OutputFormat::Table,
-                    "compact" => OutputFormat::Compact,
-                    _ => {
+                let format = match OutputFormat::from_name(&format_str) {
+                    Some(format) => format,
+                    None => {
                         return Ok(ExecutionResult {
                             success: false,
                             data: ResultData::Message(format!(
-                                "Invalid format: '{}'\n\nSupported formats: shell, json, json-pretty, table, compact",
-                                format_str
+                                "Invalid format: '{}'\n\nSupported formats: {}",
+                                format_str,
+                                OutputFormat::names().join(", ")
                             )),
                             stats: ExecutionStats::default(),
                             error: Some("Invalid format".to_string()),
@@ -310,16 +725,11 @@ Utility:
             }
             ConfigCommand::GetFormat => {
                 let format = shared_state.get_format();
-                let format_str = match format {
-                    OutputFormat::Shell => "shell",
-                    OutputFormat::Json => "json",
-                    OutputFormat::JsonPretty => "json-pretty",
-                    OutputFormat::Table => "table",
-                    OutputFormat::Compact => "compact",
-                };
+                let format_str = format.as_str();
                 format!(
-                    "Current format: {}\n\nSupported formats: shell, json, json-pretty, table, compact",
-                    format_str
+                    "Current format: {}\n\nSupported formats: {}",
+                    format_str,
+                    OutputFormat::names().join(", ")
                 )
             }
             ConfigCommand::SetColor(enabled) => {
@@ -338,13 +748,7 @@ Utility:
             }
             ConfigCommand::ShowConfig => {
                 let format = shared_state.get_format();
-                let format_str = match format {
-                    OutputFormat::Shell => "shell",
-                    OutputFormat::Json => "json",
-                    OutputFormat::JsonPretty => "json-pretty",
-                    OutputFormat::Table => "table",
-                    OutputFormat::Compact => "compact",
-                };
+                let format_str = format.as_str();
                 let color = if shared_state.get_color_enabled() {
                     "enabled"
                 } else {
@@ -357,12 +761,41 @@ Utility:
   color: {}
 
 Available Commands:
-  format [shell|json|json-pretty|table|compact]   - Set/get output format
+  format [shell|json|json-pretty|table|compact|extjson|extjson-canonical|chart|map]
+                                                  - Set/get output format
   color [on|off]                                  - Set/get color output
-  config                                          - Show this configuration"#,
+  config                                          - Show this configuration
+  config get <key>                                - Show a setting, e.g. display.page_size
+  config set <key> <value> [--save]               - Change a setting, --save writes it to the config file"#,
                     format_str, color
                 )
             }
+            ConfigCommand::GetSetting(key) => shared_state.get_settings().get_value(&key)?,
+            ConfigCommand::SetSetting { key, value, save } => {
+                let mut settings = shared_state.get_settings();
+                settings.apply_override(&format!("{}={}", key, value))?;
+                settings.validate()?;
+                shared_state
+                    .get_safety_config()
+                    .check_change(&settings.safety)?;
+                shared_state.set_settings(settings);
+
+                let mut message =
+                    format!("{} = {}", key, shared_state.get_settings().get_value(&key)?);
+                if save {
+                    let config_path = self
+                        .context
+                        .config_path
+                        .clone()
+                        .unwrap_or_else(Config::default_config_path);
+                    Config::save_setting(Some(&config_path), &key, &value)?;
+                    message.push_str(&format!(" (saved to {})", config_path.display()));
+                }
+                if !SharedState::is_live_setting(&key) {
+                    message.push_str("\nThis setting takes effect after a restart");
+                }
+                message
+            }
             ConfigCommand::ListNamedQueries => {
                 return self.list_named_query().await;
             }
@@ -375,10 +808,118 @@ Available Commands:
             ConfigCommand::DeleteNamedQuery(name) => {
                 return self.delete_named_query(&name).await;
             }
+            ConfigCommand::ListBookmarks => return self.list_bookmarks(),
+            ConfigCommand::SaveBookmark { name, query } => {
+                return self.save_bookmark(&name, &query);
+            }
+            ConfigCommand::RunBookmark(name) => return self.run_bookmark(&name).await,
+            ConfigCommand::DeleteBookmark(name) => {
+                let path = BookmarkStore::default_path();
+                let mut store = BookmarkStore::load(&path)?;
+                if store.bookmarks.remove(&name).is_none() {
+                    return Err(ExecutionError::InvalidOperation(format!(
+                        "Bookmark '{}' not found",
+                        name
+                    ))
+                    .into());
+                }
+                store.save(&path)?;
+                format!("Bookmark '{}' deleted", name)
+            }
+            command @ (ConfigCommand::ListPlugins
+            | ConfigCommand::PluginInfo(_)
+            | ConfigCommand::SetPluginEnabled { .. }) => {
+                return Self::execute_plugin_command(command);
+            }
+            ConfigCommand::SetResultCache(enabled) => {
+                let mut query_config = shared_state.get_query_config();
+                query_config.result_cache = enabled;
+                shared_state.set_query_config(query_config);
+                if !enabled {
+                    shared_state.result_cache().clear();
+                }
+                format!(
+                    "Result cache {} for this session",
+                    if enabled { "enabled" } else { "disabled" }
+                )
+            }
+            ConfigCommand::ClearResultCache => {
+                let mut cache = shared_state.result_cache();
+                let entries = cache.stats().entries;
+                cache.clear();
+                format!("Cleared {} cached result(s)", entries)
+            }
+            ConfigCommand::ResultCacheStats => {
+                let query_config = shared_state.get_query_config();
+                let stats = shared_state.result_cache().stats();
+                let lookups = stats.hits + stats.misses;
+                let hit_rate = if lookups == 0 {
+                    0.0
+                } else {
+                    stats.hits as f64 * 100.0 / lookups as f64
+                };
+                format!(
+                    "Result cache: {}\nEntries:      {}/{} (TTL {}s)\nHits:         {} ({:.1}%)\nMisses:       {}\nEvictions:    {}",
+                    if query_config.result_cache { "on" } else { "off" },
+                    stats.entries,
+                    query_config.result_cache_size,
+                    query_config.result_cache_ttl,
+                    stats.hits,
+                    hit_rate,
+                    stats.misses,
+                    stats.evictions
+                )
+            }
+            ConfigCommand::ToggleFullValues => {
+                let full = !shared_state.get_full_values();
+                shared_state.set_full_values(full);
+                if full {
+                    "Showing whole values: strings, BinData and nested values are no longer shortened"
+                        .to_string()
+                } else {
+                    "Showing previews of large values".to_string()
+                }
+            }
             ConfigCommand::AiGenerate { collection, force } => {
                 return self.execute_ai_generate(collection, force).await;
             }
             ConfigCommand::AiStatus => return self.execute_ai_status().await,
+            ConfigCommand::SaveSession(path) => {
+                let datasource = self.context.get_current_datasource().await;
+                let mut session = SessionState::capture(shared_state, datasource);
+                session.named_queries = self.load_named_query().await?.into_iter().collect();
+                session.bookmarks = BookmarkStore::load(&BookmarkStore::default_path())?.bookmarks;
+                session.save(Path::new(&path))?;
+                format!("Session saved to {}", path)
+            }
+            ConfigCommand::LoadSession(path) => {
+                let session = SessionState::load(Path::new(&path))?;
+                shared_state
+                    .get_safety_config()
+                    .check_change(&session.safety)?;
+                let current = self.context.get_current_datasource().await;
+                if !session.datasource.is_empty() && session.datasource != current {
+                    self.context.switch_datasource(&session.datasource).await?;
+                }
+                session.apply(shared_state);
+
+                // Saved entries win over ones with the same name
+                if !session.named_queries.is_empty() {
+                    let mut queries = self.load_named_query().await?;
+                    queries.extend(session.named_queries.clone());
+                    self.save_config_with_query(queries).await?;
+                }
+                if !session.bookmarks.is_empty() {
+                    let bookmark_path = BookmarkStore::default_path();
+                    let mut store = BookmarkStore::load(&bookmark_path)?;
+                    store.bookmarks.extend(session.bookmarks.clone());
+                    store.save(&bookmark_path)?;
+                }
+                format!(
+                    "Session restored from {} (database: {})",
+                    path, session.database
+                )
+            }
         };
 
         Ok(ExecutionResult {
@@ -497,6 +1038,7 @@ Available Commands:
 
         // Parse and execute the query
         let mut parser = crate::parser::Parser::new();
+        parser.set_last_result(self.context.shared_state.get_last_result_value());
         let command = parser.parse(&substituted_query)?;
         Box::pin(self.route(command)).await
     }
@@ -767,12 +1309,292 @@ Available Commands:
             error: None,
         })
     }
+
+    /// List query bookmarks
+    fn list_bookmarks(&self) -> Result<ExecutionResult> {
+        let store = BookmarkStore::load(&BookmarkStore::default_path())?;
+
+        let message = if store.bookmarks.is_empty() {
+            "No bookmarks saved.".to_string()
+        } else {
+            let mut builder = Builder::default();
+            builder.push_record(vec!["Name", "Query"]);
+            for (name, query) in &store.bookmarks {
+                builder.push_record(vec![name.as_str(), query.as_str()]);
+            }
+            let mut table = builder.build();
+            table.with(Style::ascii());
+            table.to_string()
+        };
+
+        Ok(ExecutionResult::success(
+            ResultData::Message(message),
+            ExecutionStats::default(),
+        ))
+    }
+
+    /// Execute `plugin list`, `plugin info`, `plugin enable` and `plugin disable`
+    #[cfg(feature = "plugins")]
+    fn execute_plugin_command(cmd: ConfigCommand) -> Result<ExecutionResult> {
+        let message = match cmd {
+            ConfigCommand::ListPlugins => return Self::list_plugins(),
+            ConfigCommand::PluginInfo(name) => Self::plugin_info(&name)?,
+            ConfigCommand::SetPluginEnabled { name, enabled } => {
+                plugin::set_enabled(&name, enabled)?;
+                let state = if enabled { "enabled" } else { "disabled" };
+                format!("Plugin '{}' {} for this session", name, state)
+            }
+            _ => unreachable!("not a plugin command"),
+        };
+
+        Ok(ExecutionResult::success(
+            ResultData::Message(message),
+            ExecutionStats::default(),
+        ))
+    }
+
+    /// Report that plugin support was left out of this build
+    #[cfg(not(feature = "plugins"))]
+    fn execute_plugin_command(_cmd: ConfigCommand) -> Result<ExecutionResult> {
+        Err(ExecutionError::InvalidOperation(
+            "Plugins are not available: mongosh was built without the 'plugins' feature"
+                .to_string(),
+        )
+        .into())
+    }
+
+    /// List loaded plugins
+    #[cfg(feature = "plugins")]
+    fn list_plugins() -> Result<ExecutionResult> {
+        let plugins = plugin::plugins();
+
+        let message = if plugins.is_empty() {
+            "No plugins loaded.".to_string()
+        } else {
+            let mut builder = Builder::default();
+            builder.push_record(vec!["Name", "Status", "Formats", "Commands"]);
+            for info in &plugins {
+                let status = if info.enabled { "enabled" } else { "disabled" };
+                builder.push_record(vec![
+                    info.name.clone(),
+                    status.to_string(),
+                    info.formats.join(", "),
+                    info.commands.join(", "),
+                ]);
+            }
+            let mut table = builder.build();
+            table.with(Style::ascii());
+            table.to_string()
+        };
+
+        Ok(ExecutionResult::success(
+            ResultData::Message(message),
+            ExecutionStats::default(),
+        ))
+    }
+
+    /// Describe a loaded plugin: status, extensions and settings
+    #[cfg(feature = "plugins")]
+    fn plugin_info(name: &str) -> Result<String> {
+        let info = plugin::plugins()
+            .into_iter()
+            .find(|info| info.name == name)
+            .ok_or_else(|| {
+                ExecutionError::InvalidOperation(format!("No plugin named '{}' is loaded", name))
+            })?;
+
+        let none = |items: &[String]| {
+            if items.is_empty() {
+                "(none)".to_string()
+            } else {
+                items.join(", ")
+            }
+        };
+        let mut lines = vec![
+            format!("Plugin:   {}", info.name),
+            format!(
+                "Status:   {}",
+                if info.enabled { "enabled" } else { "disabled" }
+            ),
+            format!("Formats:  {}", none(&info.formats)),
+            format!("Commands: {}", none(&info.commands)),
+        ];
+        if info.settings.is_empty() {
+            lines.push("Settings: (none)".to_string());
+        } else {
+            lines.push(format!("Settings: [plugins.config.{}]", info.name));
+            let settings = toml::to_string(&info.settings).unwrap_or_default();
+            lines.extend(settings.lines().map(|line| format!("  {}", line)));
+        }
+        Ok(lines.join("\n"))
+    }
+
+    /// Save a query bookmark
+    ///
+    /// Queries without placeholders are parsed first so typos are caught when
+    /// saving rather than at the first run.
+    fn save_bookmark(&self, name: &str, query: &str) -> Result<ExecutionResult> {
+        if bookmarks::placeholders(query).is_empty() {
+            crate::parser::Parser::new().parse(query)?;
+        }
+
+        let path = BookmarkStore::default_path();
+        let mut store = BookmarkStore::load(&path)?;
+        let replaced = store
+            .bookmarks
+            .insert(name.to_string(), query.to_string())
+            .is_some();
+        store.save(&path)?;
+
+        let message = if replaced {
+            format!("Bookmark '{}' updated", name)
+        } else {
+            format!("Bookmark '{}' saved", name)
+        };
+        Ok(ExecutionResult::success(
+            ResultData::Message(message),
+            ExecutionStats::default(),
+        ))
+    }
+
+    /// Run a query bookmark, prompting for each `{{name}}` placeholder
+    async fn run_bookmark(&self, name: &str) -> Result<ExecutionResult> {
+        let store = BookmarkStore::load(&BookmarkStore::default_path())?;
+        let query = store.bookmarks.get(name).ok_or_else(|| {
+            ExecutionError::InvalidOperation(format!("Bookmark '{}' not found", name))
+        })?;
+
+        let names = bookmarks::placeholders(query);
+        if !names.is_empty() && !self.context.is_interactive() {
+            return Err(ExecutionError::InvalidOperation(format!(
+                "Bookmark '{}' needs values for {} and can only run interactively",
+                name,
+                names.join(", ")
+            ))
+            .into());
+        }
+        let mut values = HashMap::new();
+        for placeholder in names {
+            let value = prompt::read_line(&format!("{}: ", placeholder))?;
+            values.insert(placeholder, value);
+        }
+
+        let mut parser = crate::parser::Parser::new();
+        parser.set_last_result(self.context.shared_state.get_last_result_value());
+        let command = parser.parse(&bookmarks::fill(query, &values))?;
+        Box::pin(self.route(command)).await
+    }
+}
+
+/// Create a text format writer on a stream (stdout or a command's input)
+///
+/// # Arguments
+/// * `format` - Output format; xlsx is rejected before streams are opened
+/// * `stream` - Output stream
+/// * `target` - Description of the stream for messages
+///
+/// # Returns
+/// * `Box<dyn FormatWriter>` - Writer for the stream
+#[cfg(feature = "export")]
+fn stream_writer(
+    format: ExportFormat,
+    stream: OutputStream,
+    target: &str,
+) -> Box<dyn FormatWriter> {
+    match format {
+        ExportFormat::Csv => Box::new(CsvWriter::from_stream(stream, target)),
+        ExportFormat::Html => Box::new(HtmlWriter::from_stream(stream, target)),
+        ExportFormat::JsonL | ExportFormat::Xlsx => {
+            Box::new(JsonLWriter::from_stream(stream, target))
+        }
+    }
+}
+
+/// Start a shell command that reads the export from its standard input
+///
+/// # Arguments
+/// * `command` - Command line, run by `sh -c` (`cmd /C` on Windows)
+///
+/// # Returns
+/// * `Result<tokio::process::Child>` - Running command or error
+#[cfg(feature = "export")]
+fn spawn_export_command(command: &str) -> Result<tokio::process::Child> {
+    #[cfg(windows)]
+    let mut process = {
+        let mut process = tokio::process::Command::new("cmd");
+        process.args(["/C", command]);
+        process
+    };
+    #[cfg(not(windows))]
+    let mut process = {
+        let mut process = tokio::process::Command::new("sh");
+        process.args(["-c", command]);
+        process
+    };
+
+    process
+        .stdin(std::process::Stdio::piped())
+        .spawn()
+        .map_err(|e| {
+            ExecutionError::InvalidOperation(format!("Failed to start '{}': {}", command, e)).into()
+        })
+}
+
+/// Summarize a result as document fields for parallel() output
+///
+/// # Arguments
+/// * `data` - Result data of one command
+///
+/// # Returns
+/// * `Document` - Counts and ids describing the result
+fn result_summary(data: &ResultData) -> Document {
+    match data {
+        ResultData::Documents(documents) => doc! { "documents": documents.len() as i64 },
+        ResultData::DocumentsWithPagination {
+            displayed,
+            has_more,
+            ..
+        } => doc! { "documents": *displayed as i64, "hasMore": *has_more },
+        ResultData::Document(_) => doc! { "documents": 1_i64 },
+        ResultData::InsertOne { inserted_id } => doc! { "insertedId": inserted_id },
+        ResultData::InsertMany { inserted_ids } => doc! { "inserted": inserted_ids.len() as i64 },
+        ResultData::Update { matched, modified } => {
+            doc! { "matched": *matched as i64, "modified": *modified as i64 }
+        }
+        ResultData::Delete { deleted } => doc! { "deleted": *deleted as i64 },
+        ResultData::Count(count) => doc! { "count": *count as i64 },
+        ResultData::Message(message) => doc! { "message": message },
+        ResultData::List(items) => doc! { "items": items.len() as i64 },
+        ResultData::Indexes(_)
+        | ResultData::Databases(_)
+        | ResultData::Collections(_)
+        | ResultData::Users(_) => {
+            doc! { "items": data.to_documents().map_or(0, |docs| docs.len()) as i64 }
+        }
+        ResultData::None | ResultData::Stream(_) => Document::new(),
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_result_summary() {
+        assert_eq!(
+            result_summary(&ResultData::Update {
+                matched: 3,
+                modified: 2
+            }),
+            doc! { "matched": 3_i64, "modified": 2_i64 }
+        );
+        assert_eq!(
+            result_summary(&ResultData::Documents(vec![doc! {}, doc! {}])),
+            doc! { "documents": 2_i64 }
+        );
+        assert_eq!(result_summary(&ResultData::None), Document::new());
+    }
+
     #[test]
     fn test_substitute_parameters_with_numbers() {
         let router = CommandRouter {
@@ -853,6 +1675,62 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_config_set_updates_shared_state() {
+        let state = crate::repl::SharedState::new("test".to_string());
+        let router = CommandRouter {
+            context: ExecutionContext::new(
+                crate::connection::ConnectionManager::new(
+                    "mongodb://localhost:27017".to_string(),
+                    crate::config::ConnectionConfig::default(),
+                ),
+                state.clone(),
+            ),
+        };
+        let message = |result: ExecutionResult| match result.data {
+            ResultData::Message(text) => text,
+            other => panic!("unexpected result: {:?}", other),
+        };
+
+        let result = router
+            .execute_config(ConfigCommand::SetSetting {
+                key: "display.format".to_string(),
+                value: "json".to_string(),
+                save: false,
+            })
+            .await
+            .unwrap();
+        assert_eq!(message(result), "display.format = json");
+        assert_eq!(state.get_format(), OutputFormat::Json);
+
+        let result = router
+            .execute_config(ConfigCommand::SetSetting {
+                key: "display.page_size".to_string(),
+                value: "50".to_string(),
+                save: false,
+            })
+            .await
+            .unwrap();
+        assert!(message(result).contains("takes effect after a restart"));
+
+        let result = router
+            .execute_config(ConfigCommand::GetSetting("display.page_size".to_string()))
+            .await
+            .unwrap();
+        assert_eq!(message(result), "50");
+
+        assert!(
+            router
+                .execute_config(ConfigCommand::SetSetting {
+                    key: "display.nope".to_string(),
+                    value: "1".to_string(),
+                    save: false,
+                })
+                .await
+                .is_err()
+        );
+    }
+
     #[tokio::test]
     async fn test_command_router_help() {
         // This is a placeholder test - would need proper setup with ConnectionManager
@@ -0,0 +1,243 @@
+//! Trash for deleted documents and `undo last-delete`
+//!
+//! With `safety.trash` turned on, `deleteOne` and `deleteMany` first copy
+//! the documents they match to the `__mongosh_trash` collection of the same
+//! database, then delete exactly those documents by `_id`. Every delete gets
+//! its own batch id, so `undo last-delete` can put the documents of the most
+//! recent batch back where they came from.
+//!
+//! A trash entry looks like:
+//!
+//! ```text
+//! { _id: ObjectId, batch: ObjectId, collection: "orders",
+//!   deletedAt: ISODate, doc: { ...the deleted document... } }
+//! ```
+//!
+//! The trash is never emptied automatically; drop `__mongosh_trash` to
+//! reclaim the space.
+
+use futures::stream::TryStreamExt;
+use mongodb::bson::oid::ObjectId;
+use mongodb::bson::{Bson, DateTime, Document, doc};
+use mongodb::{Collection, Database};
+use tracing::info;
+
+use super::backup::insert_batch;
+use crate::error::{ExecutionError, Result};
+
+/// Collection holding deleted documents, in the database they came from
+const TRASH_COLLECTION: &str = "__mongosh_trash";
+
+/// Documents copied and deleted per round trip
+const TRASH_BATCH_SIZE: usize = 1000;
+
+/// Copy the documents matched by a delete to the trash, then delete them
+///
+/// Only documents that were copied are deleted, by `_id`, so documents
+/// inserted while the delete runs are left alone.
+///
+/// # Arguments
+/// * `db` - Database of the collection
+/// * `collection` - Collection to delete from
+/// * `filter` - Delete filter
+/// * `many` - Delete every match (`deleteMany`) instead of the first one
+///
+/// # Returns
+/// * `Result<u64>` - Number of documents deleted
+pub(super) async fn delete_to_trash(
+    db: &Database,
+    collection: &str,
+    filter: Document,
+    many: bool,
+) -> Result<u64> {
+    if collection == TRASH_COLLECTION {
+        return Err(ExecutionError::InvalidOperation(format!(
+            "Documents deleted from {} are not moved to the trash again; turn off safety.trash to delete them",
+            TRASH_COLLECTION
+        ))
+        .into());
+    }
+
+    let source: Collection<Document> = db.collection(collection);
+    let trash: Collection<Document> = db.collection(TRASH_COLLECTION);
+    let batch = ObjectId::new();
+    let deleted_at = DateTime::now();
+
+    let mut cursor = source.find(filter).limit(if many { 0 } else { 1 }).await?;
+    let mut pending = Vec::with_capacity(TRASH_BATCH_SIZE);
+    let mut deleted = 0;
+    while let Some(document) = cursor.try_next().await? {
+        pending.push(document);
+        if pending.len() == TRASH_BATCH_SIZE {
+            deleted += move_batch(&source, &trash, batch, deleted_at, &mut pending).await?;
+        }
+    }
+    deleted += move_batch(&source, &trash, batch, deleted_at, &mut pending).await?;
+
+    info!(
+        "Moved {} document(s) from '{}' to the trash (batch {})",
+        deleted, collection, batch
+    );
+    Ok(deleted)
+}
+
+/// Copy documents to the trash and delete them from their collection
+async fn move_batch(
+    source: &Collection<Document>,
+    trash: &Collection<Document>,
+    batch: ObjectId,
+    deleted_at: DateTime,
+    pending: &mut Vec<Document>,
+) -> Result<u64> {
+    if pending.is_empty() {
+        return Ok(0);
+    }
+
+    let documents = std::mem::take(pending);
+    let ids: Vec<Bson> = documents
+        .iter()
+        .filter_map(|document| document.get("_id").cloned())
+        .collect();
+    let entries: Vec<Document> = documents
+        .into_iter()
+        .map(|document| trash_entry(batch, source.name(), deleted_at, document))
+        .collect();
+
+    trash.insert_many(entries).await?;
+    let result = source.delete_many(doc! { "_id": { "$in": ids } }).await?;
+    Ok(result.deleted_count)
+}
+
+/// Build the trash entry of a deleted document
+fn trash_entry(
+    batch: ObjectId,
+    collection: &str,
+    deleted_at: DateTime,
+    document: Document,
+) -> Document {
+    doc! {
+        "batch": batch,
+        "collection": collection,
+        "deletedAt": deleted_at,
+        "doc": document,
+    }
+}
+
+/// Put the documents of the most recent delete back into their collections
+///
+/// Documents whose `_id` exists again are skipped. The batch is removed
+/// from the trash afterwards, so running the command again restores the
+/// delete before it.
+///
+/// # Arguments
+/// * `db` - Database whose trash is used
+///
+/// # Returns
+/// * `Result<String>` - Summary of the restored documents
+pub(super) async fn undo_last_delete(db: &Database) -> Result<String> {
+    let trash: Collection<Document> = db.collection(TRASH_COLLECTION);
+    let Some(last) = trash
+        .find_one(doc! {})
+        .sort(doc! { "deletedAt": -1, "_id": -1 })
+        .await?
+    else {
+        return Ok(format!("Nothing to undo: {} is empty", TRASH_COLLECTION));
+    };
+    let batch = last.get_object_id("batch").map_err(|_| {
+        ExecutionError::InvalidOperation(format!(
+            "The latest entry of {} has no batch id",
+            TRASH_COLLECTION
+        ))
+    })?;
+
+    let entries: Vec<Document> = trash
+        .find(doc! { "batch": batch })
+        .sort(doc! { "_id": 1 })
+        .await?
+        .try_collect()
+        .await?;
+
+    let mut restored: Vec<(String, u64, u64)> = Vec::new();
+    for (collection, documents) in group_by_collection(entries) {
+        let (inserted, skipped) = insert_batch(&db.collection(&collection), documents).await?;
+        restored.push((collection, inserted, skipped));
+    }
+    trash.delete_many(doc! { "batch": batch }).await?;
+
+    Ok(summarize_undo(batch, &restored))
+}
+
+/// Group trash entries by their collection, keeping the order of deletion
+fn group_by_collection(entries: Vec<Document>) -> Vec<(String, Vec<Document>)> {
+    let mut groups: Vec<(String, Vec<Document>)> = Vec::new();
+    for mut entry in entries {
+        let (Ok(collection), Ok(document)) = (
+            entry.get_str("collection").map(str::to_string),
+            entry.get_document_mut("doc").map(std::mem::take),
+        ) else {
+            continue;
+        };
+        match groups.iter_mut().find(|(name, _)| *name == collection) {
+            Some((_, documents)) => documents.push(document),
+            None => groups.push((collection, vec![document])),
+        }
+    }
+    groups
+}
+
+/// Describe what `undo last-delete` restored
+fn summarize_undo(batch: ObjectId, restored: &[(String, u64, u64)]) -> String {
+    let mut lines: Vec<String> = restored
+        .iter()
+        .map(|(collection, inserted, skipped)| {
+            let mut line = format!("Restored {} document(s) to {}", inserted, collection);
+            if *skipped > 0 {
+                line.push_str(&format!(
+                    ", {} skipped because their _id exists again",
+                    skipped
+                ));
+            }
+            line
+        })
+        .collect();
+    lines.push(format!("Removed batch {} from {}", batch, TRASH_COLLECTION));
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_group_by_collection() {
+        let batch = ObjectId::new();
+        let now = DateTime::now();
+        let entries = vec![
+            trash_entry(batch, "orders", now, doc! { "_id": 1 }),
+            trash_entry(batch, "users", now, doc! { "_id": 2 }),
+            trash_entry(batch, "orders", now, doc! { "_id": 3 }),
+            doc! { "batch": batch, "collection": "broken" },
+        ];
+        let groups = group_by_collection(entries);
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].0, "orders");
+        assert_eq!(groups[0].1, vec![doc! { "_id": 1 }, doc! { "_id": 3 }]);
+        assert_eq!(groups[1].1, vec![doc! { "_id": 2 }]);
+    }
+
+    #[test]
+    fn test_summarize_undo() {
+        let batch = ObjectId::new();
+        let summary = summarize_undo(
+            batch,
+            &[("orders".to_string(), 2, 0), ("users".to_string(), 1, 1)],
+        );
+        let lines: Vec<&str> = summary.lines().collect();
+        assert_eq!(lines[0], "Restored 2 document(s) to orders");
+        assert_eq!(
+            lines[1],
+            "Restored 1 document(s) to users, 1 skipped because their _id exists again"
+        );
+        assert!(lines[2].contains(&batch.to_hex()));
+    }
+}
@@ -5,13 +5,37 @@
 //! - Helper functions
 //! - Miscellaneous non-database commands
 //! - Cursor iteration (it command)
+//! - BSON size of literal documents (Object.bsonsize)
+//! - Shell helpers (printjson, sleep), also applied to query results
+//! - Interactive `$lookup` builder (lookup wizard)
+//! - Saving results to a file (output last, `.save()`)
+//! - Copying results to the clipboard (copy, `.copy()`)
+//! - Showing collapsed fields of the previous result (expand)
+//! - Driver code for a command (translate)
+//! - Shell query of a SQL statement (sql explain)
+//! - Summary of what a command would do, with a count of the documents it touches (describe)
+//! - Commands run by plugins
 
+use std::fs;
+use std::time::Duration;
+
+use mongodb::bson::{Bson, Document};
+use mongodb::options::CountOptions;
+
+use crate::config::{DisplayConfig, OutputFormat};
 use crate::error::{MongoshError, Result};
-use crate::parser::UtilityCommand;
+use crate::formatter::{Formatter, ShellFormatter};
+use crate::parser::{Command, UtilityCommand, ValueHelper};
 use tracing::info;
 
 use super::context::ExecutionContext;
+use super::describe::{count_phrase, describe};
+use super::lookup::LookupWizard;
 use super::result::{ExecutionResult, ExecutionStats, ResultData};
+use super::router::CommandRouter;
+
+/// Largest text `copy` puts on the clipboard
+const MAX_CLIPBOARD_BYTES: usize = 1024 * 1024;
 
 /// Executor for utility commands
 pub struct UtilityExecutor {
@@ -46,8 +70,289 @@ impl UtilityExecutor {
                 stats: ExecutionStats::default(),
                 error: None,
             }),
+            UtilityCommand::PrintJson(value) => Ok(ExecutionResult {
+                success: true,
+                data: print_json(value)?,
+                stats: ExecutionStats::default(),
+                error: None,
+            }),
+            UtilityCommand::Inspect {
+                helper,
+                command,
+                single,
+            } => self.execute_inspect(helper, *command, single).await,
+            UtilityCommand::Sleep(millis) => self.execute_sleep(millis).await,
             UtilityCommand::Iterate => self.execute_iterate().await,
+            UtilityCommand::LookupWizard => LookupWizard::new(self.context.clone()).run().await,
+            UtilityCommand::BsonSize(document) => Ok(ExecutionResult {
+                success: true,
+                data: ResultData::Message(bson_size(&document)?.to_string()),
+                stats: ExecutionStats::default(),
+                error: None,
+            }),
+            UtilityCommand::SaveLastResult(path) => {
+                let result = self.last_result("save")?;
+                self.save_result(&result, &path, None, &[])
+            }
+            UtilityCommand::CopyLastResult => {
+                let result = self.last_result("copy")?;
+                self.copy_result(&result, &[])
+            }
+            UtilityCommand::ExpandLastResult(path) => {
+                let result = self.last_result("expand")?;
+                self.expand(&result.data, &path)
+            }
+            UtilityCommand::Translate { language, command } => {
+                let database = self.context.get_current_database().await;
+                let code = crate::parser::translate(language, &database, &command)?;
+                Ok(ExecutionResult {
+                    success: true,
+                    data: ResultData::Message(code),
+                    stats: ExecutionStats::default(),
+                    error: None,
+                })
+            }
+            UtilityCommand::SqlExplain(command) => Ok(ExecutionResult {
+                success: true,
+                data: ResultData::Message(crate::parser::shell_query(&command)?),
+                stats: ExecutionStats::default(),
+                error: None,
+            }),
+            UtilityCommand::Describe(command) => self.execute_describe(&command).await,
+            UtilityCommand::Plugin { prefix, input } => {
+                let extension = crate::parser::find_extension(&prefix).ok_or_else(|| {
+                    MongoshError::Generic(format!("No plugin handles '{}' commands", prefix))
+                })?;
+                let data = extension.execute(&input, &self.context).await?;
+                Ok(ExecutionResult::success(data, ExecutionStats::default()))
+            }
+        }
+    }
+
+    /// Run a command and print its result through a shell helper
+    ///
+    /// # Arguments
+    /// * `helper` - Helper applied to the result, e.g. printjson
+    /// * `command` - Command whose result is printed
+    /// * `single` - Print the first document, or null, instead of all results
+    ///
+    /// # Returns
+    /// * `Result<ExecutionResult>` - The helper's output, or the command's
+    ///   result if it failed
+    async fn execute_inspect(
+        &self,
+        helper: ValueHelper,
+        command: Command,
+        single: bool,
+    ) -> Result<ExecutionResult> {
+        let router = CommandRouter::new(self.context.clone()).await?;
+        let result = Box::pin(router.route(command)).await?;
+        if !result.success {
+            return Ok(result);
+        }
+
+        let value = match result.data.to_bson() {
+            Some(Bson::Array(items)) if single => items.into_iter().next().unwrap_or(Bson::Null),
+            Some(value) => value,
+            None => Bson::Null,
+        };
+        Box::pin(self.execute(helper.apply(value)?)).await
+    }
+
+    /// Summarize a command and count the documents it would read or change
+    ///
+    /// # Arguments
+    /// * `command` - Command to describe; it is not run
+    ///
+    /// # Returns
+    /// * `Result<ExecutionResult>` - Summary, e.g. `Delete ALL documents in
+    ///   orders matching {...} — estimated 12,403 documents`
+    async fn execute_describe(&self, command: &Command) -> Result<ExecutionResult> {
+        let description = describe(command)?;
+        let mut text = description.summary;
+
+        if let Some(estimate) = description.estimate {
+            let context = match description.database {
+                Some(database) => self.context.with_database(database),
+                None => self.context.clone(),
+            };
+            let mut options = CountOptions::default();
+            options.skip = estimate.skip;
+            options.limit = estimate.limit;
+            let count = context
+                .get_database()
+                .await?
+                .collection::<Document>(&estimate.collection)
+                .count_documents(estimate.filter)
+                .with_options(options)
+                .await?;
+            text.push_str(&format!(" — estimated {}", count_phrase(count)));
+        }
+
+        Ok(ExecutionResult {
+            success: true,
+            data: ResultData::Message(text),
+            stats: ExecutionStats::default(),
+            error: None,
+        })
+    }
+
+    /// The previous result, for `output last` and `copy`
+    fn last_result(&self, action: &str) -> Result<ExecutionResult> {
+        let data = self.context.shared_state.get_last_result().ok_or_else(|| {
+            MongoshError::Generic(format!(
+                "No result to {}. Please run a query first.",
+                action
+            ))
+        })?;
+        Ok(ExecutionResult::success(data, ExecutionStats::default()))
+    }
+
+    /// Formatter for output leaving the terminal: no colors, timing or previews
+    fn plain_formatter(&self, format: OutputFormat, fields: &[String]) -> Formatter {
+        let display = DisplayConfig {
+            format,
+            color_output: false,
+            show_timing: false,
+            ..DisplayConfig::default()
+        };
+        Formatter::from_config(&display)
+            .with_full_values(true)
+            .with_projection(fields)
+            .with_redaction(&self.context.shared_state.get_redaction_config())
+    }
+
+    /// Write a result to a file as it would be displayed, without colors
+    ///
+    /// # Arguments
+    /// * `result` - Result to write
+    /// * `path` - Destination file, overwritten if it exists
+    /// * `format` - Format to use instead of the current one
+    /// * `fields` - Fields to keep of each document; empty for whole documents
+    ///
+    /// # Returns
+    /// * `Result<ExecutionResult>` - Message naming the file written
+    pub fn save_result(
+        &self,
+        result: &ExecutionResult,
+        path: &str,
+        format: Option<OutputFormat>,
+        fields: &[String],
+    ) -> Result<ExecutionResult> {
+        let format = format.unwrap_or_else(|| self.context.shared_state.get_format());
+        let output = self.plain_formatter(format, fields).format(result)?;
+
+        fs::write(path, format!("{}\n", output))
+            .map_err(|e| MongoshError::Generic(format!("Failed to write '{}': {}", path, e)))?;
+
+        Ok(ExecutionResult {
+            success: true,
+            data: ResultData::Message(format!(
+                "Saved {} to {} ({})",
+                saved_summary(&result.data),
+                path,
+                format.as_str()
+            )),
+            stats: ExecutionStats::default(),
+            error: None,
+        })
+    }
+
+    /// Put a result on the system clipboard as pretty-printed JSON
+    ///
+    /// # Arguments
+    /// * `result` - Result to copy
+    /// * `fields` - Fields to keep of each document; empty for whole documents
+    ///
+    /// # Returns
+    /// * `Result<ExecutionResult>` - Message with the amount copied
+    pub fn copy_result(
+        &self,
+        result: &ExecutionResult,
+        fields: &[String],
+    ) -> Result<ExecutionResult> {
+        let output = self
+            .plain_formatter(OutputFormat::JsonPretty, fields)
+            .format(result)?;
+        if output.len() > MAX_CLIPBOARD_BYTES {
+            return Err(MongoshError::Generic(format!(
+                "Result is too large to copy ({} KiB, limit {} KiB). Use `output last > <file>` instead.",
+                output.len() / 1024,
+                MAX_CLIPBOARD_BYTES / 1024
+            )));
         }
+
+        set_clipboard(output)?;
+        Ok(ExecutionResult {
+            success: true,
+            data: ResultData::Message(format!(
+                "Copied {} to the clipboard",
+                saved_summary(&result.data)
+            )),
+            stats: ExecutionStats::default(),
+            error: None,
+        })
+    }
+
+    /// Show one value of a result, e.g. a field collapsed in shell output
+    ///
+    /// Documents are returned as results, so they become `_` and a following
+    /// `expand` drills further into them. Other values are shown in shell
+    /// syntax.
+    ///
+    /// # Arguments
+    /// * `data` - Result holding the value
+    /// * `path` - Dotted path; see [`resolve_path`]
+    ///
+    /// # Returns
+    /// * `Result<ExecutionResult>` - The value, or error if the path is missing
+    fn expand(&self, data: &ResultData, path: &str) -> Result<ExecutionResult> {
+        let data = match resolve_path(data, path)? {
+            Bson::Document(document) => ResultData::Document(document),
+            Bson::Array(values)
+                if !values.is_empty() && values.iter().all(|v| v.as_document().is_some()) =>
+            {
+                ResultData::Documents(
+                    values
+                        .into_iter()
+                        .filter_map(|value| match value {
+                            Bson::Document(document) => Some(document),
+                            _ => None,
+                        })
+                        .collect(),
+                )
+            }
+            value => {
+                let state = &self.context.shared_state;
+                let mut formatter = ShellFormatter::new(state.get_color_enabled());
+                if !state.get_full_values() {
+                    let display = state.get_settings().display;
+                    formatter = formatter
+                        .with_collapse(display.collapse_depth, display.collapse_width)
+                        .with_previews(display.max_string_length, display.max_binary_length);
+                }
+                ResultData::Message(formatter.format_value(&value))
+            }
+        };
+        Ok(ExecutionResult::success(data, ExecutionStats::default()))
+    }
+
+    /// Pause for the given number of milliseconds, stopping early on Ctrl+C
+    async fn execute_sleep(&self, millis: u64) -> Result<ExecutionResult> {
+        let cancel_token = self.context.get_cancel_token();
+        tokio::select! {
+            _ = tokio::time::sleep(Duration::from_millis(millis)) => {}
+            _ = cancel_token.cancelled() => {
+                return Err(MongoshError::Generic("sleep() interrupted".to_string()));
+            }
+        }
+
+        Ok(ExecutionResult {
+            success: true,
+            data: ResultData::None,
+            stats: ExecutionStats::default(),
+            error: None,
+        })
     }
 
     /// Execute iteration command (get next batch from cursor)
@@ -149,6 +454,101 @@ impl UtilityExecutor {
     }
 }
 
+/// Compute the encoded BSON size of a document, in bytes
+/// Replace the clipboard contents with text
+#[cfg(feature = "clipboard")]
+fn set_clipboard(text: String) -> Result<()> {
+    arboard::Clipboard::new()
+        .and_then(|mut clipboard| clipboard.set_text(text))
+        .map_err(|e| MongoshError::Generic(format!("Failed to access the clipboard: {}", e)))
+}
+
+/// Report that clipboard support was left out of this build
+#[cfg(not(feature = "clipboard"))]
+fn set_clipboard(_text: String) -> Result<()> {
+    Err(MongoshError::Generic(
+        "Copy is not available: mongosh was built without the 'clipboard' feature".to_string(),
+    ))
+}
+
+/// Describe what a saved or copied result holds, e.g. `20 document(s)`
+fn saved_summary(data: &ResultData) -> String {
+    match data {
+        ResultData::Documents(documents)
+        | ResultData::DocumentsWithPagination { documents, .. } => {
+            format!("{} document(s)", documents.len())
+        }
+        ResultData::Document(_) => "1 document".to_string(),
+        _ => "result".to_string(),
+    }
+}
+
+fn bson_size(document: &Document) -> Result<usize> {
+    let mut bytes = Vec::new();
+    document.to_writer(&mut bytes)?;
+    Ok(bytes.len())
+}
+
+/// Render a value for printjson: documents use the configured formatter,
+/// anything else is shown as relaxed Extended JSON
+fn print_json(value: Bson) -> Result<ResultData> {
+    match value {
+        Bson::Document(document) => Ok(ResultData::Document(document)),
+        value => serde_json::to_string_pretty(&value.into_relaxed_extjson())
+            .map(ResultData::Message)
+            .map_err(|e| MongoshError::Generic(format!("Cannot serialize value: {}", e))),
+    }
+}
+
+/// Find the value at a dotted path in a result
+///
+/// When the result holds several documents, the first segment is the
+/// position of the document (`expand 2.items.0`); otherwise the path starts
+/// at the fields of the single document. Array elements are selected by
+/// position.
+///
+/// # Arguments
+/// * `data` - Result to look in
+/// * `path` - Dotted path, e.g. `address.geo` or `0.items.3`
+///
+/// # Returns
+/// * `Result<Bson>` - The value, or error naming the part that is missing
+fn resolve_path(data: &ResultData, path: &str) -> Result<Bson> {
+    let mut value = match data {
+        ResultData::Document(document) => Bson::Document(document.clone()),
+        ResultData::Documents(documents)
+        | ResultData::DocumentsWithPagination { documents, .. } => match documents.as_slice() {
+            [document] => Bson::Document(document.clone()),
+            documents => Bson::Array(documents.iter().cloned().map(Bson::Document).collect()),
+        },
+        _ => {
+            return Err(MongoshError::Generic(
+                "The previous result has no documents to expand".to_string(),
+            ));
+        }
+    };
+
+    let segments: Vec<&str> = path.split('.').collect();
+    for (i, segment) in segments.iter().enumerate() {
+        let next = match &mut value {
+            Bson::Document(document) => document.remove(segment),
+            Bson::Array(values) => segment
+                .parse::<usize>()
+                .ok()
+                .filter(|index| *index < values.len())
+                .map(|index| values.swap_remove(index)),
+            _ => None,
+        };
+        value = next.ok_or_else(|| {
+            MongoshError::Generic(format!(
+                "No value at '{}' in the previous result",
+                segments[..=i].join(".")
+            ))
+        })?;
+    }
+    Ok(value)
+}
+
 impl Default for UtilityExecutor {
     fn default() -> Self {
         // Create a minimal context for testing
@@ -181,6 +581,60 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_bson_size() {
+        use mongodb::bson::doc;
+
+        // int32 length + terminator
+        assert_eq!(bson_size(&Document::new()).unwrap(), 5);
+        // 5 + type byte + "a\0" + int32 value
+        assert_eq!(bson_size(&doc! { "a": 1_i32 }).unwrap(), 12);
+    }
+
+    #[test]
+    fn test_print_json() {
+        use mongodb::bson::doc;
+
+        assert!(matches!(
+            print_json(Bson::Document(doc! { "a": 1 })).unwrap(),
+            ResultData::Document(_)
+        ));
+        match print_json(Bson::Array(vec![Bson::Int32(1)])).unwrap() {
+            ResultData::Message(text) => assert_eq!(text, "[\n  1\n]"),
+            _ => panic!("Expected Message result"),
+        }
+    }
+
+    #[test]
+    fn test_resolve_path() {
+        use mongodb::bson::doc;
+
+        let single = ResultData::Document(doc! {
+            "name": "Ada",
+            "orders": [{ "total": 10 }, { "total": 25 }],
+        });
+        assert_eq!(
+            resolve_path(&single, "orders.1.total").unwrap(),
+            Bson::Int32(25)
+        );
+        assert!(resolve_path(&single, "orders.2").is_err());
+
+        let many = ResultData::Documents(vec![doc! { "a": 1 }, doc! { "a": { "b": 2 } }]);
+        assert_eq!(resolve_path(&many, "1.a.b").unwrap(), Bson::Int32(2));
+        let err = resolve_path(&many, "1.a.c").unwrap_err().to_string();
+        assert!(err.contains("'1.a.c'"));
+
+        assert!(resolve_path(&ResultData::Count(3), "a").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_utility_executor_sleep() {
+        let executor = UtilityExecutor::default();
+        let result = executor.execute(UtilityCommand::Sleep(1)).await.unwrap();
+        assert!(result.success);
+        assert!(matches!(result.data, ResultData::None));
+    }
+
     #[test]
     fn test_utility_executor_default() {
         let _executor = UtilityExecutor::default();
@@ -0,0 +1,193 @@
+//! Screen reader friendly formatting
+//!
+//! Output is linear text with a label in front of every value so it reads
+//! well when spoken: no box drawing, no indentation-only structure and no
+//! meaning carried by color alone.
+//!
+//! ```text
+//! row 1 of 2: name=Alice age=30
+//! row 2 of 2: name=Bob age=25
+//! ```
+
+use bson::{Bson, Document};
+
+use super::bson_utils::{BsonConverter, PlainTextConverter};
+use crate::error::Result;
+use crate::executor::ResultData;
+
+/// Formatter for `display.accessible` output
+pub struct AccessibleFormatter {
+    /// Converter for scalar values
+    converter: PlainTextConverter,
+}
+
+impl AccessibleFormatter {
+    /// Create a new accessible formatter
+    pub fn new() -> Self {
+        Self {
+            converter: PlainTextConverter::new(),
+        }
+    }
+
+    /// Format result data as linear, label-prefixed text
+    ///
+    /// # Arguments
+    /// * `data` - Result data to format
+    ///
+    /// # Returns
+    /// * `Result<String>` - Formatted text or error
+    pub fn format(&self, data: &ResultData) -> Result<String> {
+        match data {
+            ResultData::Documents(docs) => Ok(self.format_rows(docs)),
+            ResultData::DocumentsWithPagination {
+                documents,
+                has_more,
+                displayed: _,
+            } => {
+                let mut result = self.format_rows(documents);
+                if *has_more {
+                    result.push_str("\nmore results available, type it to continue");
+                }
+                Ok(result)
+            }
+            ResultData::Document(doc) => Ok(format!("document: {}", self.format_fields(doc))),
+            ResultData::InsertOne { inserted_id } => Ok(format!("inserted id: {}", inserted_id)),
+            ResultData::InsertMany { inserted_ids } => Ok(format!(
+                "inserted {} documents, ids: {}",
+                inserted_ids.len(),
+                inserted_ids.join(", ")
+            )),
+            ResultData::Update { matched, modified } => {
+                Ok(format!("matched: {}, modified: {}", matched, modified))
+            }
+            ResultData::Delete { deleted } => Ok(format!("deleted: {}", deleted)),
+            ResultData::Message(msg) => Ok(msg.clone()),
+            ResultData::List(items) => Ok(items
+                .iter()
+                .enumerate()
+                .map(|(i, item)| format!("item {} of {}: {}", i + 1, items.len(), item))
+                .collect::<Vec<_>>()
+                .join("\n")),
+            ResultData::Indexes(_)
+            | ResultData::Databases(_)
+            | ResultData::Collections(_)
+            | ResultData::Users(_) => {
+                Ok(self.format_rows(&data.to_documents().unwrap_or_default()))
+            }
+            ResultData::Count(count) => Ok(format!("count: {}", count)),
+            ResultData::None => Ok("no result".to_string()),
+            #[cfg(feature = "native")]
+            ResultData::Stream(_) => {
+                // Streaming queries should not reach formatter - they're consumed by export
+                Err(crate::error::ExecutionError::InvalidOperation(
+                    "Cannot format streaming query - use export instead".to_string(),
+                )
+                .into())
+            }
+        }
+    }
+
+    /// Format documents as one numbered line each
+    fn format_rows(&self, docs: &[Document]) -> String {
+        if docs.is_empty() {
+            return "no documents".to_string();
+        }
+
+        docs.iter()
+            .enumerate()
+            .map(|(i, doc)| {
+                format!(
+                    "row {} of {}: {}",
+                    i + 1,
+                    docs.len(),
+                    self.format_fields(doc)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Format document fields as space separated `key=value` pairs
+    fn format_fields(&self, doc: &Document) -> String {
+        if doc.is_empty() {
+            return "empty".to_string();
+        }
+
+        doc.iter()
+            .map(|(key, value)| format!("{}={}", key, self.format_value(value)))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Format a value, spelling out nested documents and arrays
+    fn format_value(&self, value: &Bson) -> String {
+        match value {
+            Bson::Document(doc) => format!("{{{}}}", self.format_fields(doc)),
+            Bson::Array(items) => format!(
+                "[{}]",
+                items
+                    .iter()
+                    .map(|item| self.format_value(item))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            Bson::Null => "null".to_string(),
+            other => self.converter.convert(other),
+        }
+    }
+}
+
+impl Default for AccessibleFormatter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bson::doc;
+
+    #[test]
+    fn test_format_rows() {
+        let formatter = AccessibleFormatter::new();
+        let docs = vec![
+            doc! { "name": "Alice", "age": 30 },
+            doc! { "name": "Bob", "tags": ["a", "b"], "address": { "city": "Oslo" } },
+        ];
+        let result = formatter.format(&ResultData::Documents(docs)).unwrap();
+        assert_eq!(
+            result,
+            "row 1 of 2: name=Alice age=30\nrow 2 of 2: name=Bob tags=[a, b] address={city=Oslo}"
+        );
+    }
+
+    #[test]
+    fn test_format_summaries() {
+        let formatter = AccessibleFormatter::new();
+        assert_eq!(
+            formatter.format(&ResultData::Documents(vec![])).unwrap(),
+            "no documents"
+        );
+        assert_eq!(
+            formatter
+                .format(&ResultData::Update {
+                    matched: 3,
+                    modified: 2
+                })
+                .unwrap(),
+            "matched: 3, modified: 2"
+        );
+        let result = formatter
+            .format(&ResultData::DocumentsWithPagination {
+                documents: vec![doc! { "x": Bson::Null }],
+                has_more: true,
+                displayed: 1,
+            })
+            .unwrap();
+        assert_eq!(
+            result,
+            "row 1 of 1: x=null\nmore results available, type it to continue"
+        );
+    }
+}
@@ -2,7 +2,7 @@
 //!
 //! This module defines the traits that all BSON converters must implement.
 
-use mongodb::bson::{Bson, Document};
+use bson::{Bson, Document};
 use serde_json::Value as JsonValue;
 
 /// Core trait for BSON value conversion
@@ -61,14 +61,14 @@ pub trait BsonStringConverter {
     fn format_double(&self, f: f64) -> String;
     fn format_boolean(&self, b: bool) -> String;
     fn format_null(&self) -> String;
-    fn format_object_id(&self, oid: &mongodb::bson::oid::ObjectId) -> String;
-    fn format_datetime(&self, dt: &mongodb::bson::DateTime) -> String;
-    fn format_decimal128(&self, d: &mongodb::bson::Decimal128) -> String;
+    fn format_object_id(&self, oid: &bson::oid::ObjectId) -> String;
+    fn format_datetime(&self, dt: &bson::DateTime) -> String;
+    fn format_decimal128(&self, d: &bson::Decimal128) -> String;
     fn format_array(&self, arr: &[Bson]) -> String;
     fn format_document(&self, doc: &Document) -> String;
-    fn format_binary(&self, bin: &mongodb::bson::Binary) -> String;
-    fn format_regex(&self, regex: &mongodb::bson::Regex) -> String;
-    fn format_timestamp(&self, ts: &mongodb::bson::Timestamp) -> String;
+    fn format_binary(&self, bin: &bson::Binary) -> String;
+    fn format_regex(&self, regex: &bson::Regex) -> String;
+    fn format_timestamp(&self, ts: &bson::Timestamp) -> String;
     fn format_undefined(&self) -> String;
     fn format_min_key(&self) -> String;
     fn format_max_key(&self) -> String;
@@ -102,14 +102,14 @@ pub trait BsonStringConverter {
 /// Trait for JSON conversion
 pub trait BsonJsonConverter {
     // Methods for JSON-specific conversions
-    fn convert_object_id(&self, oid: &mongodb::bson::oid::ObjectId) -> JsonValue;
-    fn convert_datetime(&self, dt: &mongodb::bson::DateTime) -> JsonValue;
-    fn convert_decimal128(&self, d: &mongodb::bson::Decimal128) -> JsonValue;
+    fn convert_object_id(&self, oid: &bson::oid::ObjectId) -> JsonValue;
+    fn convert_datetime(&self, dt: &bson::DateTime) -> JsonValue;
+    fn convert_decimal128(&self, d: &bson::Decimal128) -> JsonValue;
     fn convert_array(&self, arr: &[Bson]) -> JsonValue;
     fn convert_document_to_json(&self, doc: &Document) -> JsonValue;
-    fn convert_binary(&self, bin: &mongodb::bson::Binary) -> JsonValue;
-    fn convert_regex(&self, regex: &mongodb::bson::Regex) -> JsonValue;
-    fn convert_timestamp(&self, ts: &mongodb::bson::Timestamp) -> JsonValue;
+    fn convert_binary(&self, bin: &bson::Binary) -> JsonValue;
+    fn convert_regex(&self, regex: &bson::Regex) -> JsonValue;
+    fn convert_timestamp(&self, ts: &bson::Timestamp) -> JsonValue;
 
     /// Convert BSON value to JSON (provided implementation)
     fn convert_to_json(&self, value: &Bson) -> JsonValue {
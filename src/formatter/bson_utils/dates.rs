@@ -0,0 +1,145 @@
+//! Time zone and format of displayed dates
+//!
+//! `display.timezone` picks the zone ISODate values are shown in (`utc`,
+//! `local` or an IANA name such as `Europe/Berlin`) and `display.date_format`
+//! an optional strftime pattern. By default dates are shown as ISO 8601 in
+//! UTC, as stored.
+
+use std::fmt::{Display, Write};
+
+use bson::DateTime;
+use chrono::format::{Item, StrftimeItems};
+use chrono::{Local, SecondsFormat, TimeZone, Utc};
+use chrono_tz::Tz;
+
+use super::helpers::datetime_to_iso_string;
+use crate::error::{ConfigError, MongoshError, Result};
+
+/// Zone dates are converted to before display
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+enum DisplayZone {
+    /// Coordinated Universal Time, as stored
+    #[default]
+    Utc,
+    /// Time zone of the machine running the shell
+    Local,
+    /// IANA time zone, e.g. `America/New_York`
+    Named(Tz),
+}
+
+/// How ISODate values are rendered
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DateDisplay {
+    zone: DisplayZone,
+    /// strftime pattern; ISO 8601 when `None`
+    format: Option<String>,
+}
+
+impl DateDisplay {
+    /// Create a date display from the `display` settings
+    ///
+    /// # Arguments
+    /// * `timezone` - `utc`, `local` or an IANA zone name
+    /// * `date_format` - strftime pattern, e.g. `%Y-%m-%d %H:%M:%S %Z`; empty
+    ///   for ISO 8601
+    ///
+    /// # Returns
+    /// * `Result<Self>` - Date display, or error for an unknown zone or an
+    ///   invalid pattern
+    pub fn new(timezone: &str, date_format: &str) -> Result<Self> {
+        let zone = match timezone.trim() {
+            "" => DisplayZone::Utc,
+            name if name.eq_ignore_ascii_case("utc") => DisplayZone::Utc,
+            name if name.eq_ignore_ascii_case("local") => DisplayZone::Local,
+            name => DisplayZone::Named(name.parse::<Tz>().map_err(|_| {
+                config_error(format!(
+                    "Unknown timezone '{}'. Use utc, local or an IANA name such as Europe/Berlin",
+                    name
+                ))
+            })?),
+        };
+
+        let format = (!date_format.is_empty()).then(|| date_format.to_string());
+        if let Some(pattern) = &format
+            && StrftimeItems::new(pattern).any(|item| matches!(item, Item::Error))
+        {
+            return Err(config_error(format!("Invalid date format '{}'", pattern)));
+        }
+
+        Ok(Self { zone, format })
+    }
+
+    /// Render a date in the configured zone and format
+    ///
+    /// # Arguments
+    /// * `dt` - BSON date
+    ///
+    /// # Returns
+    /// * `String` - Rendered date; dates out of range fall back to ISO 8601
+    ///   or milliseconds
+    pub fn format(&self, dt: &DateTime) -> String {
+        if *self == Self::default() {
+            return datetime_to_iso_string(dt);
+        }
+        let Some(utc) = Utc.timestamp_millis_opt(dt.timestamp_millis()).single() else {
+            return datetime_to_iso_string(dt);
+        };
+
+        let rendered = match self.zone {
+            DisplayZone::Utc => self.render(utc),
+            DisplayZone::Local => self.render(utc.with_timezone(&Local)),
+            DisplayZone::Named(tz) => self.render(utc.with_timezone(&tz)),
+        };
+        rendered.unwrap_or_else(|| datetime_to_iso_string(dt))
+    }
+
+    fn render<Z: TimeZone>(&self, dt: chrono::DateTime<Z>) -> Option<String>
+    where
+        Z::Offset: Display,
+    {
+        let Some(pattern) = &self.format else {
+            return Some(dt.to_rfc3339_opts(SecondsFormat::Millis, true));
+        };
+        // Writing instead of to_string(): a specifier the zone can't fill fails
+        // here rather than panicking
+        let mut rendered = String::new();
+        write!(rendered, "{}", dt.format(pattern)).ok()?;
+        Some(rendered)
+    }
+}
+
+fn config_error(message: String) -> MongoshError {
+    MongoshError::Config(ConfigError::Generic(message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 2023-12-06T11:39:48.373Z
+    const MILLIS: i64 = 1701862788373;
+
+    #[test]
+    fn test_default_is_utc_iso() {
+        let dt = DateTime::from_millis(MILLIS);
+        let display = DateDisplay::new("utc", "").unwrap();
+        assert_eq!(display.format(&dt), datetime_to_iso_string(&dt));
+    }
+
+    #[test]
+    fn test_named_zone_and_format() {
+        let dt = DateTime::from_millis(MILLIS);
+        let berlin = DateDisplay::new("Europe/Berlin", "").unwrap();
+        assert_eq!(berlin.format(&dt), "2023-12-06T12:39:48.373+01:00");
+
+        let tokyo = DateDisplay::new("Asia/Tokyo", "%Y-%m-%d %H:%M %Z").unwrap();
+        assert_eq!(tokyo.format(&dt), "2023-12-06 20:39 JST");
+    }
+
+    #[test]
+    fn test_invalid_settings() {
+        assert!(DateDisplay::new("Mars/Olympus", "").is_err());
+        assert!(DateDisplay::new("utc", "%Y-%").is_err());
+        assert!(DateDisplay::new("LOCAL", "").is_ok());
+    }
+}
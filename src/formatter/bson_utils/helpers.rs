@@ -2,7 +2,7 @@
 //!
 //! This module provides common utility functions used by various BSON converters.
 
-use mongodb::bson::{Binary, Bson, DateTime, Document, spec::BinarySubtype};
+use bson::{Binary, Bson, DateTime, Document, spec::BinarySubtype};
 
 /// Convert DateTime to ISO 8601 string
 ///
@@ -118,10 +118,29 @@ pub fn truncate_string(s: &str, max_len: usize) -> String {
     }
 }
 
+/// Read an integer that may be stored as any numeric type
+///
+/// Server statistics store counters and sizes as int32, int64 or double
+/// depending on their magnitude and the server version.
+///
+/// # Arguments
+/// * `value` - Field value, if present
+///
+/// # Returns
+/// The value truncated to an integer, or 0 when missing or not a number
+pub fn number_of(value: Option<&Bson>) -> i64 {
+    match value {
+        Some(Bson::Int32(n)) => *n as i64,
+        Some(Bson::Int64(n)) => *n,
+        Some(Bson::Double(n)) => *n as i64,
+        _ => 0,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use mongodb::bson::{DateTime as BsonDateTime, doc};
+    use bson::{DateTime as BsonDateTime, doc};
 
     #[test]
     fn test_datetime_to_iso_string() {
@@ -178,6 +197,15 @@ mod tests {
         assert!(format_double_smart(3.14159).starts_with("3.14"));
     }
 
+    #[test]
+    fn test_number_of() {
+        assert_eq!(number_of(Some(&Bson::Int32(7))), 7);
+        assert_eq!(number_of(Some(&Bson::Int64(1 << 40))), 1 << 40);
+        assert_eq!(number_of(Some(&Bson::Double(2.9))), 2);
+        assert_eq!(number_of(Some(&Bson::String("7".to_string()))), 0);
+        assert_eq!(number_of(None), 0);
+    }
+
     #[test]
     fn test_truncate_string() {
         assert_eq!(truncate_string("hello", 10), "hello");
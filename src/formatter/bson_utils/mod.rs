@@ -5,6 +5,7 @@
 //! - Shell-style formatting (MongoDB shell compatible)
 //! - Compact display for table cells
 //! - JSON value conversion
+//! - Dates in the configured time zone and format
 //!
 //! # Design
 //!
@@ -13,10 +14,13 @@
 //! interchangeably.
 
 mod converter;
+mod dates;
 mod helpers;
 mod strategies;
 
 pub use converter::BsonConverter;
+pub use dates::DateDisplay;
+pub(crate) use helpers::number_of;
 pub use strategies::{CompactConverter, JsonConverter, PlainTextConverter, ShellStyleConverter};
 
 #[cfg(test)]
@@ -6,14 +6,16 @@
 //! - CompactConverter: Compact display for table cells
 //! - JsonConverter: JSON value conversion
 
-use mongodb::bson::{
+use bson::{
     Binary, Bson, DateTime, Decimal128, Document, Regex, Timestamp, oid::ObjectId,
 };
 use serde_json::Value as JsonValue;
 
 use super::converter::{BsonConverter, BsonJsonConverter, BsonStringConverter};
+use super::dates::DateDisplay;
 use super::helpers::*;
 use crate::formatter::colorizer::Colorizer;
+use crate::formatter::listing::format_size;
 
 /// Plain text converter for simple string conversion
 ///
@@ -119,10 +121,22 @@ impl BsonStringConverter for PlainTextConverter {
 /// Shell-style converter for MongoDB shell-compatible output
 ///
 /// Formats BSON values with type wrappers (ObjectId(), ISODate(), etc.)
-/// and supports color highlighting
+/// and supports color highlighting. Nested documents and arrays that are too
+/// deep or too wide can be collapsed to a one-line summary, and long strings
+/// and binary data shortened to a preview.
 pub struct ShellStyleConverter {
     colorizer: Colorizer,
     indent: usize,
+    /// Nesting level from which documents and arrays are collapsed (0 = never)
+    collapse_depth: usize,
+    /// Entries above which documents and arrays are collapsed (0 = never)
+    collapse_width: usize,
+    /// Characters of a string shown before it is cut off (0 = all)
+    max_string_length: usize,
+    /// Bytes above which BinData shows its size instead of its content (0 = never)
+    max_binary_length: usize,
+    /// Time zone and format of ISODate values
+    dates: DateDisplay,
 }
 
 impl ShellStyleConverter {
@@ -134,6 +148,11 @@ impl ShellStyleConverter {
         Self {
             colorizer: Colorizer::new(use_colors),
             indent: 2,
+            collapse_depth: 0,
+            collapse_width: 0,
+            max_string_length: 0,
+            max_binary_length: 0,
+            dates: DateDisplay::default(),
         }
     }
 
@@ -147,9 +166,80 @@ impl ShellStyleConverter {
         Self {
             colorizer: Colorizer::new(use_colors),
             indent,
+            collapse_depth: 0,
+            collapse_width: 0,
+            max_string_length: 0,
+            max_binary_length: 0,
+            dates: DateDisplay::default(),
         }
     }
 
+    /// Collapse nested documents and arrays that are too deep or too wide
+    ///
+    /// The value being converted is never collapsed itself, only what it
+    /// contains.
+    ///
+    /// # Arguments
+    /// * `depth` - Nesting level from which values are collapsed (0 = never)
+    /// * `width` - Entries above which values are collapsed (0 = never)
+    ///
+    /// # Returns
+    /// * `Self` - Converter showing collapsed values as `{…} (N keys)` or
+    ///   `[… N items]`
+    pub fn with_collapse(mut self, depth: usize, width: usize) -> Self {
+        self.collapse_depth = depth;
+        self.collapse_width = width;
+        self
+    }
+
+    /// Shorten long strings and binary data to a preview
+    ///
+    /// # Arguments
+    /// * `max_string_length` - Characters shown of a string before `…` (0 = all)
+    /// * `max_binary_length` - Bytes above which BinData shows only its size,
+    ///   e.g. `BinData(0, 2.40 MiB)` (0 = never)
+    ///
+    /// # Returns
+    /// * `Self` - Converter showing previews of large values
+    pub fn with_previews(mut self, max_string_length: usize, max_binary_length: usize) -> Self {
+        self.max_string_length = max_string_length;
+        self.max_binary_length = max_binary_length;
+        self
+    }
+
+    /// Show ISODate values in another time zone or format
+    ///
+    /// # Arguments
+    /// * `dates` - Time zone and format from the display settings
+    ///
+    /// # Returns
+    /// * `Self` - Converter rendering dates with `dates`
+    pub fn with_dates(mut self, dates: DateDisplay) -> Self {
+        self.dates = dates;
+        self
+    }
+
+    /// Summary shown in place of a document or array that is collapsed
+    fn collapsed(&self, value: &Bson, indent_level: usize) -> Option<String> {
+        let (len, document) = match value {
+            Bson::Document(doc) => (doc.len(), true),
+            Bson::Array(arr) => (arr.len(), false),
+            _ => return None,
+        };
+        let too_deep = self.collapse_depth > 0 && indent_level >= self.collapse_depth;
+        let too_wide = self.collapse_width > 0 && len > self.collapse_width;
+        if indent_level == 0 || len == 0 || !(too_deep || too_wide) {
+            return None;
+        }
+
+        let plural = if len == 1 { "" } else { "s" };
+        Some(if document {
+            format!("{{…}} ({} key{})", len, plural)
+        } else {
+            format!("[… {} item{}]", len, plural)
+        })
+    }
+
     /// Format array with indentation
     fn format_array_with_indent(&self, arr: &[Bson], indent_level: usize) -> String {
         if arr.is_empty() {
@@ -203,6 +293,9 @@ impl ShellStyleConverter {
 
     /// Convert with specific indent level
     pub fn convert_with_indent(&self, value: &Bson, indent_level: usize) -> String {
+        if let Some(summary) = self.collapsed(value, indent_level) {
+            return summary;
+        }
         match value {
             Bson::Array(arr) => self.format_array_with_indent(arr, indent_level),
             Bson::Document(doc) => self.format_document_with_indent(doc, indent_level),
@@ -221,7 +314,12 @@ impl BsonConverter for ShellStyleConverter {
 
 impl BsonStringConverter for ShellStyleConverter {
     fn format_string(&self, s: &str) -> String {
-        self.colorizer.string(s)
+        match s.char_indices().nth(self.max_string_length) {
+            Some((end, _)) if self.max_string_length > 0 => {
+                self.colorizer.string(&format!("{}…", &s[..end]))
+            }
+            _ => self.colorizer.string(s),
+        }
     }
 
     fn format_int32(&self, n: i32) -> String {
@@ -249,8 +347,7 @@ impl BsonStringConverter for ShellStyleConverter {
     }
 
     fn format_datetime(&self, dt: &DateTime) -> String {
-        let iso = datetime_to_iso_string(dt);
-        self.colorizer.iso_date(&iso)
+        self.colorizer.iso_date(&self.dates.format(dt))
     }
 
     fn format_decimal128(&self, d: &Decimal128) -> String {
@@ -267,6 +364,11 @@ impl BsonStringConverter for ShellStyleConverter {
 
     fn format_binary(&self, bin: &Binary) -> String {
         let subtype_num = binary_subtype_to_u8(bin.subtype);
+        if self.max_binary_length > 0 && bin.bytes.len() > self.max_binary_length {
+            return self
+                .colorizer
+                .bin_data_size(subtype_num, &format_size(bin.bytes.len() as u64));
+        }
         self.colorizer.bin_data(subtype_num, &binary_to_hex(bin))
     }
 
@@ -447,6 +549,8 @@ impl BsonStringConverter for CompactConverter {
 pub struct JsonConverter {
     /// Whether to simplify BSON types (true) or preserve extended JSON (false)
     simplify: bool,
+    /// Time zone and format of dates
+    dates: DateDisplay,
 }
 
 impl JsonConverter {
@@ -455,7 +559,22 @@ impl JsonConverter {
     /// # Arguments
     /// * `simplify` - If true, convert BSON types to simple JSON types
     pub fn new(simplify: bool) -> Self {
-        Self { simplify }
+        Self {
+            simplify,
+            dates: DateDisplay::default(),
+        }
+    }
+
+    /// Render dates in another time zone or format
+    ///
+    /// # Arguments
+    /// * `dates` - Time zone and format from the display settings
+    ///
+    /// # Returns
+    /// * `Self` - Converter rendering dates with `dates`
+    pub fn with_dates(mut self, dates: DateDisplay) -> Self {
+        self.dates = dates;
+        self
     }
 
     /// Create a simplified JSON converter (default)
@@ -490,8 +609,7 @@ impl BsonJsonConverter for JsonConverter {
     }
 
     fn convert_datetime(&self, dt: &DateTime) -> JsonValue {
-        let iso = datetime_to_iso_string(dt);
-        JsonValue::String(iso)
+        JsonValue::String(self.dates.format(dt))
     }
 
     fn convert_decimal128(&self, d: &Decimal128) -> JsonValue {
@@ -538,7 +656,7 @@ impl BsonJsonConverter for JsonConverter {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use mongodb::bson::{Bson, doc, oid::ObjectId};
+    use bson::{Bson, doc, oid::ObjectId};
 
     #[test]
     fn test_plain_text_converter() {
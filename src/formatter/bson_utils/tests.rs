@@ -2,7 +2,7 @@
 
 use super::helpers::*;
 use super::*;
-use mongodb::bson::{
+use bson::{
     Binary, Bson, DateTime, Decimal128, Regex, Timestamp, doc, oid::ObjectId, spec::BinarySubtype,
 };
 use serde_json::Value as JsonValue;
@@ -277,6 +277,31 @@ fn test_shell_style_with_indent() {
     assert!(result.contains("{"));
 }
 
+#[test]
+fn test_shell_style_collapse() {
+    let items: Vec<Bson> = (0..500).map(Bson::Int32).collect();
+    let doc = doc! {
+        "items": items,
+        "tags": ["a", "b"],
+        "meta": { "owner": { "name": "Ada" } },
+    };
+
+    let wide = ShellStyleConverter::new(false).with_collapse(0, 100);
+    let result = wide.convert(&Bson::Document(doc.clone()));
+    assert!(result.contains("items: [… 500 items]"));
+    assert!(result.contains("'b'"));
+    assert!(result.contains("'Ada'"));
+
+    let shallow = ShellStyleConverter::new(false).with_collapse(2, 0);
+    let result = shallow.convert(&Bson::Document(doc.clone()));
+    assert!(result.contains("owner: {…} (1 key)"));
+    assert!(!result.contains("'Ada'"));
+
+    // The converted value itself is never collapsed
+    let result = wide.convert(&doc.get("items").unwrap().clone());
+    assert!(result.contains("499"));
+}
+
 // ===== CompactConverter Tests =====
 
 #[test]
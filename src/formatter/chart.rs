@@ -0,0 +1,245 @@
+//! ASCII bar charts of grouped counts
+//!
+//! The `chart` output format draws one bar per document for results shaped
+//! like the output of a `$group` stage, e.g. `{ _id: "shipped", count: 42 }`.
+//! The label is the `_id` (or the first non-numeric field) and the value is
+//! the `count` field (or the first numeric field). Bars are scaled to the
+//! largest value and followed by the value and its share of the total.
+//!
+//! ```text
+//! shipped    ████████████████████████████████████████  120  60.0%
+//! pending    ████████████████▋                          50  25.0%
+//! cancelled  ██████████                                 30  15.0%
+//! ```
+
+use bson::{Bson, Document};
+
+use super::bson_utils::{BsonConverter, CompactConverter};
+use crate::executor::ResultData;
+
+/// Width of the longest bar (characters)
+const BAR_WIDTH: usize = 40;
+
+/// Longest label shown before it is cut off
+const MAX_LABEL_WIDTH: usize = 30;
+
+/// Partial blocks for eighths of a character
+const PARTIAL_BLOCKS: [char; 8] = [' ', '▏', '▎', '▍', '▌', '▋', '▊', '▉'];
+
+/// One bar of the chart
+struct Bar {
+    label: String,
+    value: f64,
+}
+
+/// Formatter drawing bar charts of `{_id, count}` style documents
+pub struct ChartFormatter {
+    /// Enable colored bars
+    use_colors: bool,
+
+    /// Converter for labels
+    converter: CompactConverter,
+}
+
+impl ChartFormatter {
+    /// Create a new chart formatter
+    ///
+    /// # Arguments
+    /// * `use_colors` - Enable colored output
+    ///
+    /// # Returns
+    /// * `Self` - New formatter
+    pub fn new(use_colors: bool) -> Self {
+        Self {
+            use_colors,
+            converter: CompactConverter::new(),
+        }
+    }
+
+    /// Draw a bar chart of the documents of a result
+    ///
+    /// # Arguments
+    /// * `data` - Result data to chart
+    ///
+    /// # Returns
+    /// * `Option<String>` - Chart, or None when the result holds no documents
+    ///   with a numeric value
+    pub fn format(&self, data: &ResultData) -> Option<String> {
+        let documents = match data {
+            ResultData::Documents(documents)
+            | ResultData::DocumentsWithPagination { documents, .. } => documents,
+            _ => return None,
+        };
+
+        let bars: Vec<Bar> = documents
+            .iter()
+            .enumerate()
+            .filter_map(|(index, doc)| self.bar(index, doc))
+            .collect();
+        if bars.is_empty() {
+            return None;
+        }
+        Some(self.render(&bars))
+    }
+
+    /// Label and value of one document
+    ///
+    /// # Arguments
+    /// * `index` - Position of the document, used as label without a better one
+    /// * `doc` - Document to chart
+    ///
+    /// # Returns
+    /// * `Option<Bar>` - Bar, or None when the document has no numeric value
+    fn bar(&self, index: usize, doc: &Document) -> Option<Bar> {
+        let value = doc.get("count").and_then(as_number).or_else(|| {
+            doc.iter()
+                .filter(|(key, _)| key.as_str() != "_id")
+                .find_map(|(_, value)| as_number(value))
+        })?;
+
+        let label = match doc.get("_id") {
+            Some(Bson::Null) => "null".to_string(),
+            Some(id) => self.converter.convert(id),
+            None => doc
+                .values()
+                .find(|value| as_number(value).is_none())
+                .map(|value| self.converter.convert(value))
+                .unwrap_or_else(|| format!("#{}", index + 1)),
+        };
+
+        Some(Bar { label, value })
+    }
+
+    /// Render the bars with scaled lengths, values and percentages
+    fn render(&self, bars: &[Bar]) -> String {
+        let labels: Vec<String> = bars.iter().map(|bar| truncate(&bar.label)).collect();
+        let values: Vec<String> = bars.iter().map(|bar| format_value(bar.value)).collect();
+        let label_width = labels.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+        let value_width = values.iter().map(String::len).max().unwrap_or(0);
+
+        let max = bars.iter().map(|bar| bar.value).fold(0.0, f64::max);
+        let total: f64 = bars.iter().map(|bar| bar.value.max(0.0)).sum();
+
+        bars.iter()
+            .zip(labels.iter().zip(&values))
+            .map(|(bar, (label, value))| {
+                let length = if max > 0.0 {
+                    bar.value.max(0.0) / max * BAR_WIDTH as f64
+                } else {
+                    0.0
+                };
+                let drawn = draw_bar(length);
+                let padding = " ".repeat(BAR_WIDTH - drawn.chars().count());
+                let drawn = if self.use_colors {
+                    format!("\x1b[36m{}\x1b[0m", drawn)
+                } else {
+                    drawn
+                };
+                let share = if total > 0.0 {
+                    bar.value.max(0.0) / total * 100.0
+                } else {
+                    0.0
+                };
+                format!(
+                    "{:<label_width$}  {}{}  {:>value_width$}  {:>5.1}%",
+                    label,
+                    drawn,
+                    padding,
+                    value,
+                    share,
+                    label_width = label_width,
+                    value_width = value_width,
+                )
+                .trim_end()
+                .to_string()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Numeric value of a field, if it is a number
+fn as_number(value: &Bson) -> Option<f64> {
+    match value {
+        Bson::Int32(n) => Some(*n as f64),
+        Bson::Int64(n) => Some(*n as f64),
+        Bson::Double(f) if f.is_finite() => Some(*f),
+        Bson::Decimal128(d) => d.to_string().parse().ok(),
+        _ => None,
+    }
+}
+
+/// Draw a bar of `length` characters using eighth blocks for the remainder
+fn draw_bar(length: f64) -> String {
+    let eighths = (length * 8.0).round() as usize;
+    let mut bar = "█".repeat(eighths / 8);
+    if !eighths.is_multiple_of(8) {
+        bar.push(PARTIAL_BLOCKS[eighths % 8]);
+    }
+    bar
+}
+
+/// Format a value without a fraction when it is whole
+fn format_value(value: f64) -> String {
+    if value.fract() == 0.0 && value.abs() < 1e15 {
+        format!("{:.0}", value)
+    } else {
+        format!("{:.2}", value)
+    }
+}
+
+/// Cut a label to [`MAX_LABEL_WIDTH`] characters
+fn truncate(label: &str) -> String {
+    if label.chars().count() <= MAX_LABEL_WIDTH {
+        return label.to_string();
+    }
+    let mut cut: String = label.chars().take(MAX_LABEL_WIDTH - 1).collect();
+    cut.push('…');
+    cut
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bson::doc;
+
+    #[test]
+    fn test_chart_group_counts() {
+        let formatter = ChartFormatter::new(false);
+        let data = ResultData::Documents(vec![
+            doc! { "_id": "shipped", "count": 120 },
+            doc! { "_id": "pending", "count": 50i64 },
+            doc! { "_id": null, "count": 30.0 },
+        ]);
+        let chart = formatter.format(&data).unwrap();
+        let lines: Vec<&str> = chart.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("shipped  ████████████████████████████████████████  120"));
+        assert!(lines[0].ends_with("60.0%"));
+        assert!(lines[1].contains("▋"));
+        assert!(lines[1].ends_with("25.0%"));
+        assert!(lines[2].starts_with("null"));
+    }
+
+    #[test]
+    fn test_chart_value_and_label_fallbacks() {
+        let formatter = ChartFormatter::new(false);
+        let data =
+            ResultData::Documents(vec![doc! { "status": "a", "total": 1.5 }, doc! { "n": 3 }]);
+        let chart = formatter.format(&data).unwrap();
+        assert!(chart.starts_with("a "));
+        assert!(chart.contains("1.50"));
+        assert!(chart.lines().nth(1).unwrap().starts_with("#2"));
+
+        let text = ResultData::Documents(vec![doc! { "_id": "a", "name": "x" }]);
+        assert!(formatter.format(&text).is_none());
+        assert!(formatter.format(&ResultData::Count(3)).is_none());
+    }
+
+    #[test]
+    fn test_draw_bar() {
+        assert_eq!(draw_bar(0.0), "");
+        assert_eq!(draw_bar(2.5), "██▌");
+        assert_eq!(truncate(&"x".repeat(40)).chars().count(), MAX_LABEL_WIDTH);
+    }
+}
@@ -0,0 +1,635 @@
+//! Health report formatting for `clusterReport()`
+//!
+//! This module turns the results of `serverStatus`, `replSetGetStatus`,
+//! `dbStats` and `connPoolStats` into one page of checks for on-call use:
+//! cache pressure, replication lag, connection saturation, collection scans
+//! and disk usage. Each check is rated OK, WARN or CRIT against fixed
+//! thresholds and the worst rating is repeated in the heading.
+
+use bson::{Bson, Document};
+
+use super::colorizer::AnsiColors;
+use super::listing::format_size;
+
+/// Cache fill at which WiredTiger starts background eviction
+const CACHE_FILL_WARN: f64 = 0.80;
+
+/// Cache fill at which application threads are made to evict
+const CACHE_FILL_CRIT: f64 = 0.95;
+
+/// Dirty share of the cache at which background eviction starts
+const CACHE_DIRTY_WARN: f64 = 0.05;
+
+/// Dirty share of the cache at which application threads are made to evict
+const CACHE_DIRTY_CRIT: f64 = 0.20;
+
+/// Seconds a secondary may trail the primary before it is flagged
+const LAG_WARN_SECS: i64 = 10;
+
+/// Seconds behind the primary that put reads and failover at risk
+const LAG_CRIT_SECS: i64 = 60;
+
+/// Share of the connection limit in use before it is flagged
+const CONNECTIONS_WARN: f64 = 0.80;
+
+/// Share of the connection limit in use at which new clients may be refused
+const CONNECTIONS_CRIT: f64 = 0.95;
+
+/// Share of queries answered by a collection scan before it is flagged
+const COLLSCAN_WARN: f64 = 0.05;
+
+/// Share of queries answered by a collection scan that needs attention now
+const COLLSCAN_CRIT: f64 = 0.25;
+
+/// Filesystem usage of the data directory before it is flagged
+const DISK_WARN: f64 = 0.80;
+
+/// Filesystem usage of the data directory that needs attention now
+const DISK_CRIT: f64 = 0.90;
+
+/// Rating of a single check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Health {
+    /// The numbers needed for the check were not available
+    Unknown,
+    Ok,
+    Warn,
+    Critical,
+}
+
+impl Health {
+    /// Rate a value against a warning and a critical threshold
+    fn rate(value: f64, warn: f64, critical: f64) -> Self {
+        if value >= critical {
+            Health::Critical
+        } else if value >= warn {
+            Health::Warn
+        } else {
+            Health::Ok
+        }
+    }
+
+    /// Short label shown in front of a check
+    pub fn label(&self) -> &'static str {
+        match self {
+            Health::Unknown => "N/A",
+            Health::Ok => "OK",
+            Health::Warn => "WARN",
+            Health::Critical => "CRIT",
+        }
+    }
+
+    /// Color of the label
+    pub fn color(&self) -> &'static str {
+        match self {
+            Health::Unknown => AnsiColors::DIM,
+            Health::Ok => AnsiColors::GREEN,
+            Health::Warn => AnsiColors::YELLOW,
+            Health::Critical => AnsiColors::RED,
+        }
+    }
+}
+
+/// Replication state of one replica set member
+#[derive(Debug, Clone, PartialEq)]
+pub struct MemberLag {
+    /// Member host and port
+    pub name: String,
+
+    /// Member state, e.g. PRIMARY or SECONDARY
+    pub state: String,
+
+    /// Whether the member is reachable from the node that answered
+    pub healthy: bool,
+
+    /// Seconds behind the primary; `None` for the primary, arbiters and
+    /// members without an optime, or when there is no primary
+    pub lag_secs: Option<i64>,
+}
+
+impl MemberLag {
+    /// Read every member of a `replSetGetStatus` result
+    ///
+    /// Lag is the difference between the `optimeDate` of the primary and
+    /// that of the member.
+    ///
+    /// # Arguments
+    /// * `status` - `replSetGetStatus` result
+    ///
+    /// # Returns
+    /// * `Vec<MemberLag>` - Members in the order the server lists them
+    pub fn from_status(status: &Document) -> Vec<MemberLag> {
+        let members: Vec<&Document> = status
+            .get_array("members")
+            .map(|members| members.iter().filter_map(Bson::as_document).collect())
+            .unwrap_or_default();
+        let primary_optime = members
+            .iter()
+            .find(|member| member.get_str("stateStr").is_ok_and(|s| s == "PRIMARY"))
+            .and_then(|primary| optime_millis(primary));
+
+        members
+            .iter()
+            .map(|member| {
+                let state = member.get_str("stateStr").unwrap_or("UNKNOWN").to_string();
+                let lag_secs = match (state.as_str(), primary_optime, optime_millis(member)) {
+                    ("PRIMARY" | "ARBITER", _, _) => None,
+                    (_, Some(primary), Some(optime)) => Some((primary - optime).max(0) / 1000),
+                    _ => None,
+                };
+                MemberLag {
+                    name: member.get_str("name").unwrap_or("?").to_string(),
+                    healthy: number_of(member.get("health")).is_none_or(|h| h > 0.0),
+                    state,
+                    lag_secs,
+                }
+            })
+            .collect()
+    }
+
+    /// The member furthest behind the primary
+    ///
+    /// # Arguments
+    /// * `members` - Members from `from_status`
+    ///
+    /// # Returns
+    /// * `Option<&MemberLag>` - Member with the largest lag, if any has one
+    pub fn worst(members: &[MemberLag]) -> Option<&MemberLag> {
+        members
+            .iter()
+            .filter(|m| m.lag_secs.is_some())
+            .max_by_key(|m| m.lag_secs)
+    }
+
+    /// Rate the member: unhealthy or lagging members are flagged
+    pub fn health(&self) -> Health {
+        if !self.healthy || !matches!(self.state.as_str(), "PRIMARY" | "SECONDARY" | "ARBITER") {
+            return Health::Critical;
+        }
+        match self.lag_secs {
+            Some(lag) => Health::rate(lag as f64, LAG_WARN_SECS as f64, LAG_CRIT_SECS as f64),
+            None => Health::Ok,
+        }
+    }
+}
+
+/// Server command results a cluster report is built from
+///
+/// Only `serverStatus` is required; the other commands may fail on a
+/// standalone server or for lack of privileges, and their error is shown
+/// in place of the check.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClusterStats {
+    /// `serverStatus` result
+    pub server_status: Document,
+
+    /// `replSetGetStatus` result, or why it failed
+    pub repl_status: Result<Document, String>,
+
+    /// `dbStats` result of the current database, or why it failed
+    pub db_stats: Result<Document, String>,
+
+    /// `connPoolStats` result, or why it failed
+    pub conn_pool: Result<Document, String>,
+}
+
+/// One line of the report, with optional indented detail lines
+struct Check {
+    name: String,
+    health: Health,
+    summary: String,
+    details: Vec<String>,
+}
+
+/// Formatter for cluster health reports
+pub struct ClusterReportFormatter {
+    /// Color the ratings
+    use_colors: bool,
+}
+
+impl ClusterReportFormatter {
+    /// Create a new cluster report formatter
+    ///
+    /// # Arguments
+    /// * `use_colors` - Color the OK/WARN/CRIT labels
+    pub fn new(use_colors: bool) -> Self {
+        Self { use_colors }
+    }
+
+    /// Render the report
+    ///
+    /// # Arguments
+    /// * `stats` - Results of the server commands
+    ///
+    /// # Returns
+    /// * `String` - Heading with the overall rating, then one line per check
+    pub fn format(&self, stats: &ClusterStats) -> String {
+        let checks = [
+            cache_check(&stats.server_status),
+            replication_check(&stats.repl_status),
+            connection_check(&stats.server_status, stats.conn_pool.as_ref().ok()),
+            collscan_check(&stats.server_status),
+            disk_check(&stats.db_stats),
+        ];
+        let overall = checks
+            .iter()
+            .map(|check| check.health)
+            .max()
+            .unwrap_or(Health::Unknown);
+
+        let status = &stats.server_status;
+        let mut lines = vec![format!(
+            "Cluster report for {} (MongoDB {}, up {}) - {}",
+            status.get_str("host").unwrap_or("unknown host"),
+            status.get_str("version").unwrap_or("?"),
+            format_uptime(number_of(status.get("uptime")).unwrap_or(0.0) as u64),
+            self.label(overall)
+        )];
+        lines.push(String::new());
+
+        let width = checks.iter().map(|c| c.name.len()).max().unwrap_or(0);
+        for check in &checks {
+            let label = format!("[{}]", check.health.label());
+            lines.push(format!(
+                "{}{}  {:<width$}  {}",
+                self.paint(check.health, &label),
+                " ".repeat(6 - label.len()),
+                check.name,
+                check.summary,
+                width = width
+            ));
+            for detail in &check.details {
+                lines.push(format!("{}  {}", " ".repeat(8 + width), detail));
+            }
+        }
+        lines.join("\n")
+    }
+
+    /// Rating label, colored when enabled
+    fn label(&self, health: Health) -> String {
+        self.paint(health, health.label())
+    }
+
+    /// Wrap text in the color of a rating
+    fn paint(&self, health: Health, text: &str) -> String {
+        if self.use_colors {
+            format!("{}{}{}", health.color(), text, AnsiColors::RESET)
+        } else {
+            text.to_string()
+        }
+    }
+}
+
+/// WiredTiger cache fill and dirty share
+fn cache_check(server_status: &Document) -> Check {
+    let cache = server_status
+        .get_document("wiredTiger")
+        .and_then(|wt| wt.get_document("cache"))
+        .ok();
+    let used = cache.and_then(|c| number_of(c.get("bytes currently in the cache")));
+    let max = cache.and_then(|c| number_of(c.get("maximum bytes configured")));
+    let dirty = cache.and_then(|c| number_of(c.get("tracked dirty bytes in the cache")));
+
+    let (Some(used), Some(max)) = (used, max.filter(|max| *max > 0.0)) else {
+        return unknown("Cache", "no WiredTiger cache statistics");
+    };
+    let fill = used / max;
+    let dirty = dirty.unwrap_or(0.0) / max;
+    Check {
+        name: "Cache".to_string(),
+        health: Health::rate(fill, CACHE_FILL_WARN, CACHE_FILL_CRIT).max(Health::rate(
+            dirty,
+            CACHE_DIRTY_WARN,
+            CACHE_DIRTY_CRIT,
+        )),
+        summary: format!(
+            "{:.0}% full ({} of {}), {:.1}% dirty",
+            fill * 100.0,
+            format_size(used as u64),
+            format_size(max as u64),
+            dirty * 100.0
+        ),
+        details: Vec::new(),
+    }
+}
+
+/// Member states and lag behind the primary
+fn replication_check(repl_status: &Result<Document, String>) -> Check {
+    let status = match repl_status {
+        Ok(status) => status,
+        Err(e) if e.contains("replSet") || e.contains("NoReplicationEnabled") => {
+            return unknown("Replication", "not a replica set");
+        }
+        Err(e) => return unknown("Replication", e),
+    };
+
+    let members = MemberLag::from_status(status);
+    let set = status.get_str("set").unwrap_or("replica set");
+    let health = members
+        .iter()
+        .map(MemberLag::health)
+        .max()
+        .unwrap_or(Health::Unknown);
+    let (health, summary) = match MemberLag::worst(&members) {
+        _ if !members.iter().any(|m| m.state == "PRIMARY") => {
+            (Health::Critical, format!("{}: no primary", set))
+        }
+        Some(worst) => (
+            health,
+            format!(
+                "{}: worst lag {}s ({})",
+                set,
+                worst.lag_secs.unwrap_or(0),
+                worst.name
+            ),
+        ),
+        None => (health, format!("{}: no secondaries", set)),
+    };
+
+    let width = members.iter().map(|m| m.name.len()).max().unwrap_or(0);
+    let details = members
+        .iter()
+        .map(|m| {
+            let mut line = format!("{:<width$}  {}", m.name, m.state, width = width);
+            if let Some(lag) = m.lag_secs {
+                line.push_str(&format!(", {}s behind", lag));
+            }
+            if !m.healthy {
+                line.push_str(", unreachable");
+            }
+            line
+        })
+        .collect();
+
+    Check {
+        name: "Replication".to_string(),
+        health,
+        summary,
+        details,
+    }
+}
+
+/// Incoming connections against the limit, plus the outgoing pool
+fn connection_check(server_status: &Document, conn_pool: Option<&Document>) -> Check {
+    let connections = server_status.get_document("connections").ok();
+    let current = connections.and_then(|c| number_of(c.get("current")));
+    let available = connections.and_then(|c| number_of(c.get("available")));
+    let (Some(current), Some(available)) = (current, available) else {
+        return unknown("Connections", "no connection statistics");
+    };
+
+    let limit = current + available;
+    let used = if limit > 0.0 { current / limit } else { 0.0 };
+    let mut summary = format!(
+        "{} of {} in use ({:.1}%)",
+        current as u64,
+        limit as u64,
+        used * 100.0
+    );
+    if let Some(pool) = conn_pool {
+        let in_use = number_of(pool.get("totalInUse")).unwrap_or(0.0) as u64;
+        let idle = number_of(pool.get("totalAvailable")).unwrap_or(0.0) as u64;
+        summary.push_str(&format!("; pool: {} in use, {} idle", in_use, idle));
+    }
+
+    Check {
+        name: "Connections".to_string(),
+        health: Health::rate(used, CONNECTIONS_WARN, CONNECTIONS_CRIT),
+        summary,
+        details: Vec::new(),
+    }
+}
+
+/// Collection scans since startup, against the number of queries
+fn collscan_check(server_status: &Document) -> Check {
+    let metrics = server_status.get_document("metrics").ok();
+    let scans = metrics
+        .and_then(|m| m.get_document("queryExecutor").ok())
+        .and_then(|qe| qe.get_document("collectionScans").ok())
+        .and_then(|cs| number_of(cs.get("total")));
+    let Some(scans) = scans else {
+        return unknown("Collscans", "no collection scan metrics");
+    };
+    let queries = server_status
+        .get_document("opcounters")
+        .ok()
+        .and_then(|op| number_of(op.get("query")))
+        .unwrap_or(0.0);
+    let share = if queries > 0.0 { scans / queries } else { 0.0 };
+
+    let mut summary = format!(
+        "{} collection scans since startup ({:.1}% of {} queries)",
+        scans as u64,
+        share * 100.0,
+        queries as u64
+    );
+    let examined = metrics
+        .and_then(|m| m.get_document("queryExecutor").ok())
+        .and_then(|qe| number_of(qe.get("scannedObjects")));
+    let returned = metrics
+        .and_then(|m| m.get_document("document").ok())
+        .and_then(|d| number_of(d.get("returned")));
+    if let (Some(examined), Some(returned)) = (examined, returned.filter(|r| *r > 0.0)) {
+        summary.push_str(&format!(
+            ", {:.0} documents examined per document returned",
+            examined / returned
+        ));
+    }
+
+    Check {
+        name: "Collscans".to_string(),
+        health: Health::rate(share, COLLSCAN_WARN, COLLSCAN_CRIT),
+        summary,
+        details: Vec::new(),
+    }
+}
+
+/// Filesystem usage of the data directory, from `dbStats`
+fn disk_check(db_stats: &Result<Document, String>) -> Check {
+    let stats = match db_stats {
+        Ok(stats) => stats,
+        Err(e) => return unknown("Disk", e),
+    };
+    let name = match stats.get_str("db") {
+        Ok(db) => format!("Disk ({})", db),
+        Err(_) => "Disk".to_string(),
+    };
+    let sizes = format!(
+        "data {}, indexes {}",
+        format_size(number_of(stats.get("dataSize")).unwrap_or(0.0) as u64),
+        format_size(number_of(stats.get("indexSize")).unwrap_or(0.0) as u64)
+    );
+
+    let used = number_of(stats.get("fsUsedSize"));
+    let total = number_of(stats.get("fsTotalSize")).filter(|total| *total > 0.0);
+    let (health, summary) = match (used, total) {
+        (Some(used), Some(total)) => (
+            Health::rate(used / total, DISK_WARN, DISK_CRIT),
+            format!(
+                "{:.0}% of {} used; {}",
+                used / total * 100.0,
+                format_size(total as u64),
+                sizes
+            ),
+        ),
+        _ => (
+            Health::Unknown,
+            format!("filesystem size unknown; {}", sizes),
+        ),
+    };
+
+    Check {
+        name,
+        health,
+        summary,
+        details: Vec::new(),
+    }
+}
+
+/// A check whose numbers were not available
+fn unknown(name: &str, reason: &str) -> Check {
+    Check {
+        name: name.to_string(),
+        health: Health::Unknown,
+        summary: reason.to_string(),
+        details: Vec::new(),
+    }
+}
+
+/// `optimeDate` of a member in milliseconds since the epoch
+fn optime_millis(member: &Document) -> Option<i64> {
+    member
+        .get_datetime("optimeDate")
+        .ok()
+        .map(|date| date.timestamp_millis())
+}
+
+/// Format seconds as `3d 4h`, `5h 12m` or `42m`
+fn format_uptime(secs: u64) -> String {
+    let (days, hours, minutes) = (secs / 86_400, secs % 86_400 / 3600, secs % 3600 / 60);
+    if days > 0 {
+        format!("{}d {}h", days, hours)
+    } else if hours > 0 {
+        format!("{}h {}m", hours, minutes)
+    } else {
+        format!("{}m", minutes)
+    }
+}
+
+/// Read a number that may be stored as any numeric type
+fn number_of(value: Option<&Bson>) -> Option<f64> {
+    match value {
+        Some(Bson::Int32(n)) => Some(*n as f64),
+        Some(Bson::Int64(n)) => Some(*n as f64),
+        Some(Bson::Double(n)) => Some(*n),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bson::{DateTime, doc};
+
+    fn repl_status() -> Document {
+        let now = DateTime::from_millis(1_700_000_000_000);
+        let behind = |secs: i64| DateTime::from_millis(now.timestamp_millis() - secs * 1000);
+        doc! {
+            "set": "rs0",
+            "members": [
+                { "name": "db1:27017", "stateStr": "PRIMARY", "health": 1.0, "optimeDate": now },
+                { "name": "db2:27017", "stateStr": "SECONDARY", "health": 1.0, "optimeDate": behind(2) },
+                { "name": "db3:27017", "stateStr": "SECONDARY", "health": 1.0, "optimeDate": behind(75) },
+                { "name": "db4:27017", "stateStr": "ARBITER", "health": 1 },
+            ]
+        }
+    }
+
+    #[test]
+    fn test_member_lag_from_status() {
+        let members = MemberLag::from_status(&repl_status());
+        assert_eq!(members.len(), 4);
+        assert_eq!(members[0].lag_secs, None);
+        assert_eq!(members[1].lag_secs, Some(2));
+        assert_eq!(members[1].health(), Health::Ok);
+        assert_eq!(members[2].lag_secs, Some(75));
+        assert_eq!(members[2].health(), Health::Critical);
+        assert_eq!(members[3].lag_secs, None);
+
+        let down = MemberLag {
+            name: "db5:27017".to_string(),
+            state: "(not reachable/healthy)".to_string(),
+            healthy: false,
+            lag_secs: None,
+        };
+        assert_eq!(down.health(), Health::Critical);
+    }
+
+    #[test]
+    fn test_format_report() {
+        let stats = ClusterStats {
+            server_status: doc! {
+                "host": "db1:27017",
+                "version": "7.0.5",
+                "uptime": 273_600.0,
+                "wiredTiger": { "cache": {
+                    "bytes currently in the cache": 850_i64,
+                    "maximum bytes configured": 1000_i64,
+                    "tracked dirty bytes in the cache": 10_i64,
+                } },
+                "connections": { "current": 100, "available": 900 },
+                "opcounters": { "query": 1000_i64 },
+                "metrics": {
+                    "queryExecutor": { "scannedObjects": 5000_i64, "collectionScans": { "total": 10_i64 } },
+                    "document": { "returned": 100_i64 },
+                },
+            },
+            repl_status: Ok(repl_status()),
+            db_stats: Ok(doc! {
+                "db": "shop",
+                "dataSize": 2048,
+                "indexSize": 1024,
+                "fsUsedSize": 40.0,
+                "fsTotalSize": 100.0,
+            }),
+            conn_pool: Err("not authorized".to_string()),
+        };
+
+        let output = ClusterReportFormatter::new(false).format(&stats);
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(
+            lines[0],
+            "Cluster report for db1:27017 (MongoDB 7.0.5, up 3d 4h) - CRIT"
+        );
+        assert!(lines[2].starts_with("[WARN]  Cache  "));
+        assert!(lines[2].ends_with("85% full (850.00 B of 1000.00 B), 1.0% dirty"));
+        assert!(lines[3].starts_with("[CRIT]  Replication"));
+        assert!(lines[3].ends_with("rs0: worst lag 75s (db3:27017)"));
+        assert!(
+            lines[6]
+                .trim_start()
+                .starts_with("db3:27017  SECONDARY, 75s behind")
+        );
+        assert!(lines[8].ends_with("100 of 1000 in use (10.0%)"));
+        assert!(lines[9].starts_with("[OK]    Collscans"));
+        assert!(
+            lines[9]
+                .ends_with("(1.0% of 1000 queries), 50 documents examined per document returned")
+        );
+        assert!(lines[10].starts_with("[OK]    Disk (shop)"));
+        assert!(lines[10].ends_with("40% of 100.00 B used; data 2.00 KiB, indexes 1.00 KiB"));
+    }
+
+    #[test]
+    fn test_standalone_and_missing_metrics() {
+        let replication = replication_check(&Err(
+            "not running with --replSet (NoReplicationEnabled)".to_string(),
+        ));
+        assert_eq!(replication.health, Health::Unknown);
+        assert_eq!(replication.summary, "not a replica set");
+
+        let cache = cache_check(&doc! {});
+        assert_eq!(cache.health, Health::Unknown);
+        assert_eq!(format_uptime(2 * 3600 + 5 * 60), "2h 5m");
+    }
+}
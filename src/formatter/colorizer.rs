@@ -13,6 +13,7 @@ impl AnsiColors {
     pub const RESET: &'static str = "\x1b[0m";
     pub const BOLD: &'static str = "\x1b[1m";
     pub const DIM: &'static str = "\x1b[2m";
+    pub const REVERSE: &'static str = "\x1b[7m";
 
     // Foreground colors
     pub const BLACK: &'static str = "\x1b[30m";
@@ -186,6 +187,32 @@ impl Colorizer {
         }
     }
 
+    /// Format the size of BinData shown in place of its content
+    ///
+    /// # Arguments
+    /// * `subtype` - Binary subtype number
+    /// * `size` - Readable size, e.g. `2.40 MiB`
+    ///
+    /// # Returns
+    /// * `String` - Formatted and colorized BinData preview
+    pub fn bin_data_size(&self, subtype: u8, size: &str) -> String {
+        if self.enabled {
+            format!(
+                "{}BinData{}({}{}{}, {}{}{})",
+                AnsiColors::MAGENTA,
+                AnsiColors::RESET,
+                AnsiColors::YELLOW,
+                subtype,
+                AnsiColors::RESET,
+                AnsiColors::CYAN,
+                size,
+                AnsiColors::RESET
+            )
+        } else {
+            format!("BinData({}, {})", subtype, size)
+        }
+    }
+
     /// Format RegularExpression with colors
     ///
     /// # Arguments
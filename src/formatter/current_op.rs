@@ -0,0 +1,229 @@
+//! Active operation formatting for the `currentOp` monitor
+//!
+//! This module reads the documents returned by the `$currentOp` aggregation
+//! stage into a compact row per operation and renders them as a table,
+//! longest running first. The monitor marks the selected row so it can be
+//! killed with a key press.
+
+use bson::{Bson, Document};
+use tabled::{
+    builder::Builder,
+    settings::{Alignment, Modify, Style, object::Columns},
+};
+
+use super::bson_utils::number_of;
+
+/// Longest command summary shown in the table, in characters
+const MAX_COMMAND_WIDTH: usize = 40;
+
+/// One in-progress operation
+#[derive(Debug, Clone, PartialEq)]
+pub struct OperationInfo {
+    /// Operation id accepted by `killOp`; a string such as `shard:123` on mongos
+    pub opid: Bson,
+
+    /// Operation type (query, insert, command, getmore, ...)
+    pub op: String,
+
+    /// Namespace the operation runs against
+    pub ns: String,
+
+    /// Time running in microseconds
+    pub micros: i64,
+
+    /// Plan summary, e.g. `IXSCAN { status: 1 }` or `COLLSCAN`
+    pub plan: String,
+
+    /// Command name and first argument, e.g. `find: orders`
+    pub command: String,
+}
+
+impl OperationInfo {
+    /// Read an operation from a `$currentOp` document
+    ///
+    /// # Arguments
+    /// * `doc` - Document produced by `$currentOp`
+    ///
+    /// # Returns
+    /// * `Option<Self>` - None when the document has no opid
+    pub fn from_doc(doc: &Document) -> Option<Self> {
+        let opid = doc.get("opid")?.clone();
+        let micros = match doc.get("microsecs_running") {
+            Some(value) => number_of(Some(value)),
+            None => number_of(doc.get("secs_running")) * 1_000_000,
+        };
+
+        Some(Self {
+            opid,
+            op: doc.get_str("op").unwrap_or_default().to_string(),
+            ns: doc.get_str("ns").unwrap_or_default().to_string(),
+            micros,
+            plan: doc.get_str("planSummary").unwrap_or_default().to_string(),
+            command: doc
+                .get_document("command")
+                .map(summarize_command)
+                .unwrap_or_default(),
+        })
+    }
+
+    /// Read and sort all operations of a `$currentOp` result
+    ///
+    /// # Arguments
+    /// * `docs` - Documents produced by `$currentOp`
+    ///
+    /// # Returns
+    /// * `Vec<Self>` - Operations, longest running first
+    pub fn from_docs(docs: &[Document]) -> Vec<Self> {
+        let mut ops: Vec<Self> = docs.iter().filter_map(Self::from_doc).collect();
+        ops.sort_by(|a, b| {
+            b.micros
+                .cmp(&a.micros)
+                .then_with(|| a.opid.to_string().cmp(&b.opid.to_string()))
+        });
+        ops
+    }
+}
+
+/// Formatter for `currentOp` tables
+pub struct CurrentOpFormatter;
+
+impl CurrentOpFormatter {
+    /// Render operations as a table
+    ///
+    /// # Arguments
+    /// * `ops` - Operations in display order
+    /// * `selected` - Row marked with `>`, if any
+    ///
+    /// # Returns
+    /// * `String` - Table, or a note when nothing is running
+    pub fn format(ops: &[OperationInfo], selected: Option<usize>) -> String {
+        if ops.is_empty() {
+            return "No active operations".to_string();
+        }
+
+        let mut builder = Builder::default();
+        builder.push_record([
+            "",
+            "Opid",
+            "Type",
+            "Namespace",
+            "Duration",
+            "Plan",
+            "Command",
+        ]);
+        for (i, op) in ops.iter().enumerate() {
+            builder.push_record([
+                if selected == Some(i) { ">" } else { "" }.to_string(),
+                format_opid(&op.opid),
+                op.op.clone(),
+                op.ns.clone(),
+                format_duration(op.micros),
+                op.plan.clone(),
+                op.command.clone(),
+            ]);
+        }
+
+        let mut table = builder.build();
+        table.with(Style::modern());
+        table.with(Modify::new(Columns::new(4..5)).with(Alignment::right()));
+        table.to_string()
+    }
+}
+
+/// Render an opid without the quotes BSON adds to strings
+pub fn format_opid(opid: &Bson) -> String {
+    match opid {
+        Bson::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Summarize a command document as its name and first value
+fn summarize_command(command: &Document) -> String {
+    let Some((name, value)) = command.iter().next() else {
+        return String::new();
+    };
+    let value = match value {
+        Bson::String(s) => s.clone(),
+        Bson::Document(_) | Bson::Array(_) => "…".to_string(),
+        other => other.to_string(),
+    };
+    let summary = format!("{}: {}", name, value);
+    if summary.chars().count() > MAX_COMMAND_WIDTH {
+        let truncated: String = summary.chars().take(MAX_COMMAND_WIDTH - 1).collect();
+        format!("{}…", truncated)
+    } else {
+        summary
+    }
+}
+
+/// Format microseconds as `850ms`, `12.4s` or `3m05s`
+fn format_duration(micros: i64) -> String {
+    let millis = micros / 1000;
+    if millis < 1000 {
+        format!("{}ms", millis)
+    } else if millis < 60_000 {
+        format!("{:.1}s", millis as f64 / 1000.0)
+    } else {
+        let secs = millis / 1000;
+        format!("{}m{:02}s", secs / 60, secs % 60)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bson::doc;
+
+    #[test]
+    fn test_from_docs_sorted_by_duration() {
+        let ops = OperationInfo::from_docs(&[
+            doc! {
+                "opid": 7,
+                "op": "query",
+                "ns": "shop.orders",
+                "microsecs_running": 2_500_000_i64,
+                "planSummary": "COLLSCAN",
+                "command": { "find": "orders", "filter": { "status": "open" } },
+            },
+            doc! { "opid": "shard1:42", "op": "update", "secs_running": 90 },
+            doc! { "op": "none" },
+        ]);
+
+        assert_eq!(ops.len(), 2);
+        assert_eq!(ops[0].opid, Bson::String("shard1:42".to_string()));
+        assert_eq!(ops[0].micros, 90_000_000);
+        assert_eq!(ops[1].plan, "COLLSCAN");
+        assert_eq!(ops[1].command, "find: orders");
+    }
+
+    #[test]
+    fn test_format_marks_selected_row() {
+        let ops = OperationInfo::from_docs(&[doc! {
+            "opid": 7,
+            "op": "query",
+            "ns": "shop.orders",
+            "microsecs_running": 2_500_000_i64,
+        }]);
+        let output = CurrentOpFormatter::format(&ops, Some(0));
+        let row = output
+            .lines()
+            .find(|line| line.contains("shop.orders"))
+            .unwrap();
+        assert!(row.contains(">"));
+        assert!(row.contains("2.5s"));
+        assert_eq!(
+            CurrentOpFormatter::format(&[], None),
+            "No active operations"
+        );
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(850_000), "850ms");
+        assert_eq!(format_duration(12_400_000), "12.4s");
+        assert_eq!(format_duration(185_000_000), "3m05s");
+        assert_eq!(format_opid(&Bson::String("s:1".to_string())), "s:1");
+        assert_eq!(format_opid(&Bson::Int64(9)), "9");
+    }
+}
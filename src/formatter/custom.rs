@@ -0,0 +1,151 @@
+//! Output formats added by plugins
+//!
+//! A plugin implements [`DocumentFormatter`] and registers it under a name,
+//! which then works everywhere a built-in format name does: `format yaml`,
+//! `--format=yaml` and `|> format yaml`. Registered formats are process-wide
+//! and cannot replace the built-in ones.
+//!
+//! Custom formatters only render documents; messages, write acknowledgements
+//! and listings keep the shell format.
+
+use std::sync::{Arc, RwLock};
+
+use bson::Document;
+
+use crate::config::OutputFormat;
+use crate::error::{MongoshError, Result};
+
+/// Renders query results in a custom output format
+pub trait DocumentFormatter: Send + Sync {
+    /// Format the documents of a result
+    ///
+    /// # Arguments
+    /// * `documents` - Documents to render, possibly empty
+    ///
+    /// # Returns
+    /// * `Result<String>` - Formatted text
+    fn format_documents(&self, documents: &[Document]) -> Result<String>;
+}
+
+/// A registered format; the name is leaked once so `OutputFormat` stays `Copy`
+type Entry = (&'static str, Arc<dyn DocumentFormatter>);
+
+/// Formats registered by plugins, in registration order
+static FORMATTERS: RwLock<Vec<Entry>> = RwLock::new(Vec::new());
+
+/// Register a custom output format
+///
+/// # Arguments
+/// * `name` - Format name as typed after `format`, matched case-insensitively
+/// * `formatter` - Formatter rendering the documents
+///
+/// # Returns
+/// * `Result<OutputFormat>` - The new format, or an error if the name is taken
+#[allow(dead_code)] // library API, not used by the binary
+pub fn register_format(name: &str, formatter: Arc<dyn DocumentFormatter>) -> Result<OutputFormat> {
+    let name = name.trim().to_lowercase();
+    if name.is_empty() || name.contains(char::is_whitespace) {
+        return Err(MongoshError::Generic(format!(
+            "Invalid format name '{}'",
+            name
+        )));
+    }
+    if OutputFormat::from_name(&name).is_some() {
+        return Err(MongoshError::Generic(format!(
+            "Output format '{}' is already defined",
+            name
+        )));
+    }
+
+    let name: &'static str = Box::leak(name.into_boxed_str());
+    FORMATTERS
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .push((name, formatter));
+    Ok(OutputFormat::Custom(name))
+}
+
+/// Remove a registered format, e.g. when its plugin is disabled
+///
+/// # Arguments
+/// * `name` - Format name
+/// * `formatter` - Formatter it was registered with; a format of the same
+///   name registered by someone else stays
+///
+/// # Returns
+/// * `bool` - Whether the format was removed
+#[cfg(feature = "plugins")]
+pub(crate) fn unregister_format(name: &str, formatter: &Arc<dyn DocumentFormatter>) -> bool {
+    let mut formatters = FORMATTERS.write().unwrap_or_else(|e| e.into_inner());
+    let before = formatters.len();
+    formatters.retain(|(registered, registered_formatter)| {
+        !(registered.eq_ignore_ascii_case(name) && Arc::ptr_eq(registered_formatter, formatter))
+    });
+    formatters.len() < before
+}
+
+/// Look up a registered format by name
+///
+/// # Returns
+/// * `Option<Entry>` - Canonical name and formatter, if registered
+pub(crate) fn find_format(name: &str) -> Option<Entry> {
+    FORMATTERS
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .iter()
+        .find(|(registered, _)| registered.eq_ignore_ascii_case(name))
+        .cloned()
+}
+
+/// Names of the registered formats, in registration order
+pub fn custom_format_names() -> Vec<&'static str> {
+    FORMATTERS
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .iter()
+        .map(|(name, _)| *name)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bson::doc;
+
+    struct KeysFormatter;
+
+    impl DocumentFormatter for KeysFormatter {
+        fn format_documents(&self, documents: &[Document]) -> Result<String> {
+            Ok(documents
+                .iter()
+                .map(|doc| doc.keys().cloned().collect::<Vec<_>>().join(","))
+                .collect::<Vec<_>>()
+                .join("\n"))
+        }
+    }
+
+    #[test]
+    fn test_register_format() {
+        let format = register_format("Keys-Test", Arc::new(KeysFormatter)).unwrap();
+        assert_eq!(format, OutputFormat::Custom("keys-test"));
+        assert_eq!(OutputFormat::from_name("KEYS-TEST"), Some(format));
+        assert!(custom_format_names().contains(&"keys-test"));
+
+        let (_, formatter) = find_format("keys-test").unwrap();
+        let output = formatter
+            .format_documents(&[doc! { "a": 1, "b": 2 }])
+            .unwrap();
+        assert_eq!(output, "a,b");
+
+        assert!(register_format("keys-test", Arc::new(KeysFormatter)).is_err());
+        assert!(register_format("json", Arc::new(KeysFormatter)).is_err());
+
+        #[cfg(feature = "plugins")]
+        {
+            let other: Arc<dyn DocumentFormatter> = Arc::new(KeysFormatter);
+            assert!(!unregister_format("keys-test", &other));
+            assert!(unregister_format("keys-test", &formatter));
+            assert_eq!(OutputFormat::from_name("keys-test"), None);
+        }
+    }
+}
@@ -0,0 +1,99 @@
+//! Error formatting with explanations for common server errors
+//!
+//! The raw error is printed unchanged, followed by a short explanation and
+//! suggested next commands when the server error code is a well-known one
+//! such as a duplicate key or an exceeded time limit.
+
+use super::colorizer::AnsiColors;
+use crate::error::MongoshError;
+use crate::error::mongo::ErrorExplanation;
+
+/// Formatter for errors shown in the shell
+pub struct ErrorFormatter {
+    /// Color the explanation and hints
+    use_colors: bool,
+}
+
+impl ErrorFormatter {
+    /// Create a new error formatter
+    ///
+    /// # Arguments
+    /// * `use_colors` - Color the explanation and hints
+    pub fn new(use_colors: bool) -> Self {
+        Self { use_colors }
+    }
+
+    /// Format an error with its explanation, if any
+    ///
+    /// # Arguments
+    /// * `error` - Error to display
+    ///
+    /// # Returns
+    /// * `String` - Raw error, followed by the explanation and hints
+    pub fn format(&self, error: &MongoshError) -> String {
+        let raw = error.to_string();
+        match error.explanation() {
+            Some(explanation) => format!("{}\n{}", raw, self.format_explanation(&explanation)),
+            None => raw,
+        }
+    }
+
+    /// Format an explanation as a summary line and a `Try:` list
+    ///
+    /// # Arguments
+    /// * `explanation` - Explanation of a server error
+    ///
+    /// # Returns
+    /// * `String` - Summary and one line per hint
+    pub fn format_explanation(&self, explanation: &ErrorExplanation) -> String {
+        let (summary_color, hint_color, reset) = if self.use_colors {
+            (AnsiColors::YELLOW, AnsiColors::DIM, AnsiColors::RESET)
+        } else {
+            ("", "", "")
+        };
+
+        let mut lines = vec![format!("{}{}{}", summary_color, explanation.summary, reset)];
+        if !explanation.hints.is_empty() {
+            lines.push(format!("{}Try:{}", hint_color, reset));
+            lines.extend(
+                explanation
+                    .hints
+                    .iter()
+                    .map(|hint| format!("{}  - {}{}", hint_color, hint, reset)),
+            );
+        }
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ExecutionError;
+
+    #[test]
+    fn test_format_with_explanation() {
+        let err: MongoshError = ExecutionError::QueryFailed(
+            "E11000 duplicate key error collection: shop.users index: email_1 dup key: { email: \"a\" }"
+                .to_string(),
+        )
+        .into();
+        let output = ErrorFormatter::new(false).format(&err);
+        let lines: Vec<&str> = output.lines().collect();
+
+        assert!(lines[0].starts_with("ExecutionError: E11000 duplicate key error"));
+        assert!(lines[1].contains("unique index 'email_1'"));
+        assert_eq!(lines[2], "Try:");
+        assert!(lines[4].starts_with("  - db.users.getIndexes()"));
+        assert!(!output.contains('\x1b'));
+    }
+
+    #[test]
+    fn test_format_without_explanation() {
+        let err = MongoshError::Generic("No failed command to retry".to_string());
+        assert_eq!(
+            ErrorFormatter::new(true).format(&err),
+            "No failed command to retry"
+        );
+    }
+}
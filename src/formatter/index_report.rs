@@ -0,0 +1,252 @@
+//! Index usage formatting for `db.indexReport()`
+//!
+//! This module merges the `$indexStats` output of every collection into one
+//! table of operation counts per index. Indexes that served no operations
+//! since a cutoff date are flagged as unused so they can be reviewed for
+//! removal. An index is only flagged when its counter started before the
+//! cutoff, since `$indexStats` counters reset when the server restarts or the
+//! index is rebuilt.
+
+use bson::{Bson, DateTime, Document};
+use chrono::{TimeZone, Utc};
+use tabled::{
+    builder::Builder,
+    settings::{Alignment, Modify, Style, object::Columns},
+};
+
+use super::bson_utils::number_of;
+
+/// Operation count of one index, summed over all hosts that reported it
+#[derive(Debug, Clone, PartialEq)]
+pub struct IndexUsage {
+    /// Collection the index belongs to
+    pub collection: String,
+
+    /// Index name
+    pub name: String,
+
+    /// Index key pattern
+    pub key: Document,
+
+    /// Operations that used the index
+    pub ops: i64,
+
+    /// Earliest time the counters were started on any host
+    pub since: Option<DateTime>,
+}
+
+impl IndexUsage {
+    /// Merge the `$indexStats` documents of one collection by index name
+    ///
+    /// Sharded and replicated deployments report an entry per host; their
+    /// counts are added up and the earliest start time is kept.
+    ///
+    /// # Arguments
+    /// * `collection` - Collection name
+    /// * `stats` - Documents returned by `$indexStats`
+    ///
+    /// # Returns
+    /// * `Vec<Self>` - One entry per index
+    pub fn from_stats(collection: &str, stats: &[Document]) -> Vec<Self> {
+        let mut usages: Vec<Self> = Vec::new();
+        for entry in stats {
+            let Ok(name) = entry.get_str("name") else {
+                continue;
+            };
+            let accesses = entry.get_document("accesses").ok();
+            let ops = accesses.map_or(0, |a| number_of(a.get("ops")));
+            let since = accesses.and_then(|a| a.get_datetime("since").ok().copied());
+
+            match usages.iter_mut().find(|usage| usage.name == name) {
+                Some(usage) => {
+                    usage.ops += ops;
+                    usage.since = match (usage.since, since) {
+                        (Some(a), Some(b)) => Some(a.min(b)),
+                        (a, b) => a.or(b),
+                    };
+                }
+                None => usages.push(Self {
+                    collection: collection.to_string(),
+                    name: name.to_string(),
+                    key: entry.get_document("key").cloned().unwrap_or_default(),
+                    ops,
+                    since,
+                }),
+            }
+        }
+        usages
+    }
+}
+
+/// Formatter for index usage reports
+pub struct IndexReportFormatter {
+    /// Indexes unused since this date are flagged; None flags every index
+    /// with no operations
+    cutoff: Option<DateTime>,
+}
+
+impl IndexReportFormatter {
+    /// Create a new index report formatter
+    ///
+    /// # Arguments
+    /// * `cutoff` - Flag indexes with no operations since this date
+    pub fn new(cutoff: Option<DateTime>) -> Self {
+        Self { cutoff }
+    }
+
+    /// Whether an index served no operations since the cutoff
+    ///
+    /// The `_id` index is never flagged since it cannot be dropped.
+    pub fn is_unused(&self, usage: &IndexUsage) -> bool {
+        usage.name != "_id_"
+            && usage.ops == 0
+            && usage
+                .since
+                .is_some_and(|since| self.cutoff.is_none_or(|cutoff| since <= cutoff))
+    }
+
+    /// Render usage rows as a table, unused indexes first
+    ///
+    /// # Arguments
+    /// * `usages` - Usage of every index
+    ///
+    /// # Returns
+    /// * `String` - Table followed by a summary line
+    pub fn format(&self, mut usages: Vec<IndexUsage>) -> String {
+        if usages.is_empty() {
+            return "No indexes found".to_string();
+        }
+
+        usages.sort_by(|a, b| {
+            self.is_unused(b)
+                .cmp(&self.is_unused(a))
+                .then(a.ops.cmp(&b.ops))
+                .then(a.collection.cmp(&b.collection))
+                .then(a.name.cmp(&b.name))
+        });
+        let unused = usages.iter().filter(|u| self.is_unused(u)).count();
+
+        let mut builder = Builder::default();
+        builder.push_record(["Collection", "Index", "Key", "Ops", "Since", "Status"]);
+        for usage in &usages {
+            builder.push_record([
+                usage.collection.clone(),
+                usage.name.clone(),
+                format_key(&usage.key),
+                usage.ops.to_string(),
+                usage.since.map(format_date).unwrap_or_default(),
+                if self.is_unused(usage) {
+                    "unused".to_string()
+                } else {
+                    String::new()
+                },
+            ]);
+        }
+
+        let mut table = builder.build();
+        table.with(Style::modern());
+        table.with(Modify::new(Columns::new(3..4)).with(Alignment::right()));
+
+        let period = match self.cutoff {
+            Some(cutoff) => format!("since {}", format_date(cutoff)),
+            None => "since their counters started".to_string(),
+        };
+        format!(
+            "{}\n{} of {} indexes had no operations {}",
+            table,
+            unused,
+            usages.len(),
+            period
+        )
+    }
+}
+
+/// Render a key pattern compactly, e.g. `{ status: 1, createdAt: -1 }`
+fn format_key(key: &Document) -> String {
+    let fields: Vec<String> = key
+        .iter()
+        .map(|(field, value)| match value {
+            Bson::String(s) => format!("{}: '{}'", field, s),
+            other => format!("{}: {}", field, number_of(Some(other))),
+        })
+        .collect();
+    format!("{{ {} }}", fields.join(", "))
+}
+
+/// Format a date as `YYYY-MM-DD HH:MM`
+fn format_date(date: DateTime) -> String {
+    match Utc.timestamp_millis_opt(date.timestamp_millis()).single() {
+        Some(time) => time.format("%Y-%m-%d %H:%M").to_string(),
+        None => "-".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bson::doc;
+
+    fn stats(name: &str, ops: i64, since_ms: i64) -> Document {
+        doc! {
+            "name": name,
+            "key": { name.trim_end_matches("_1"): 1 },
+            "host": "db1:27017",
+            "accesses": { "ops": ops, "since": DateTime::from_millis(since_ms) },
+        }
+    }
+
+    #[test]
+    fn test_from_stats_merges_hosts() {
+        let usages = IndexUsage::from_stats(
+            "orders",
+            &[
+                stats("status_1", 3, 2_000),
+                stats("status_1", 4, 1_000),
+                stats("_id_", 0, 1_000),
+            ],
+        );
+        assert_eq!(usages.len(), 2);
+        assert_eq!(usages[0].ops, 7);
+        assert_eq!(usages[0].since, Some(DateTime::from_millis(1_000)));
+        assert_eq!(usages[0].key, doc! { "status": 1 });
+    }
+
+    #[test]
+    fn test_is_unused() {
+        let usage = |name: &str, ops: i64, since_ms: i64| IndexUsage {
+            collection: "orders".to_string(),
+            name: name.to_string(),
+            key: doc! {},
+            ops,
+            since: Some(DateTime::from_millis(since_ms)),
+        };
+
+        let formatter = IndexReportFormatter::new(None);
+        assert!(formatter.is_unused(&usage("sku_1", 0, 5_000)));
+        assert!(!formatter.is_unused(&usage("sku_1", 1, 5_000)));
+        assert!(!formatter.is_unused(&usage("_id_", 0, 5_000)));
+
+        let formatter = IndexReportFormatter::new(Some(DateTime::from_millis(3_000)));
+        assert!(formatter.is_unused(&usage("sku_1", 0, 1_000)));
+        // Counting started after the cutoff, so usage before it is unknown
+        assert!(!formatter.is_unused(&usage("sku_1", 0, 5_000)));
+    }
+
+    #[test]
+    fn test_format_sorts_unused_first() {
+        let mut usages = IndexUsage::from_stats("orders", &[stats("status_1", 9, 0)]);
+        usages.extend(IndexUsage::from_stats("users", &[stats("email_1", 0, 0)]));
+
+        let output = IndexReportFormatter::new(None).format(usages);
+        let email = output.find("email_1").unwrap();
+        let status = output.find("status_1").unwrap();
+        assert!(email < status);
+        assert!(output.contains("unused"));
+        assert!(output.contains("{ email: 1 }"));
+        assert!(output.ends_with("1 of 2 indexes had no operations since their counters started"));
+        assert_eq!(
+            IndexReportFormatter::new(None).format(Vec::new()),
+            "No indexes found"
+        );
+    }
+}
@@ -4,15 +4,26 @@
 //! - Pretty-printed and compact JSON output
 //! - BSON type conversion to standard JSON types
 //! - Optional color highlighting for JSON output
+//! - MongoDB Extended JSON v2 (relaxed or canonical) for lossless output
 //! - Support for ObjectId, DateTime, Int64, Decimal128, Binary, etc.
 
 use colored_json::prelude::*;
-use mongodb::bson::{Bson, Document};
+use bson::{Bson, Document};
 
-use super::bson_utils::{BsonConverter, JsonConverter};
+use super::bson_utils::{BsonConverter, DateDisplay, JsonConverter};
 use crate::error::Result;
 use crate::executor::ResultData;
 
+/// Flavour of MongoDB Extended JSON v2
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExtJsonMode {
+    /// Plain JSON numbers and ISO dates where no precision is lost,
+    /// e.g. `{"$date": "2024-01-01T00:00:00Z"}`
+    Relaxed,
+    /// Every non-string value wrapped with its type, e.g. `{"$numberInt": "1"}`
+    Canonical,
+}
+
 /// JSON formatter with pretty printing support
 pub struct JsonFormatter {
     /// Enable pretty printing
@@ -26,6 +37,9 @@ pub struct JsonFormatter {
 
     /// Converter for BSON to JSON
     converter: JsonConverter,
+
+    /// Emit Extended JSON instead of simplified JSON
+    extjson: Option<ExtJsonMode>,
 }
 
 impl JsonFormatter {
@@ -43,9 +57,37 @@ impl JsonFormatter {
             indent,
             use_colors,
             converter: JsonConverter::simplified(),
+            extjson: None,
         }
     }
 
+    /// Emit MongoDB Extended JSON v2, keeping every BSON type
+    ///
+    /// Dates are written as stored, ignoring the display time zone, so the
+    /// output can be read back by drivers and mongoimport.
+    ///
+    /// # Arguments
+    /// * `mode` - Relaxed or canonical Extended JSON
+    ///
+    /// # Returns
+    /// * `Self` - Formatter emitting Extended JSON
+    pub fn with_extjson(mut self, mode: ExtJsonMode) -> Self {
+        self.extjson = Some(mode);
+        self
+    }
+
+    /// Render dates in another time zone or format
+    ///
+    /// # Arguments
+    /// * `dates` - Time zone and format from the display settings
+    ///
+    /// # Returns
+    /// * `Self` - Formatter rendering dates with `dates`
+    pub fn with_dates(mut self, dates: DateDisplay) -> Self {
+        self.converter = self.converter.with_dates(dates);
+        self
+    }
+
     /// Format result data as JSON
     ///
     /// # Arguments
@@ -65,6 +107,12 @@ impl JsonFormatter {
                 let list_str = items.join("\n");
                 Ok(list_str)
             }
+            ResultData::Indexes(_)
+            | ResultData::Databases(_)
+            | ResultData::Collections(_)
+            | ResultData::Users(_) => {
+                self.format_documents(&data.to_documents().unwrap_or_default())
+            }
             ResultData::InsertOne { inserted_id } => {
                 Ok(format!("{{ \"insertedId\": \"{}\" }}", inserted_id))
             }
@@ -83,6 +131,7 @@ impl JsonFormatter {
             ResultData::Delete { deleted } => Ok(format!("{{ \"deletedCount\": {} }}", deleted)),
             ResultData::Count(count) => Ok(format!("{}", count)),
             ResultData::None => Ok("null".to_string()),
+            #[cfg(feature = "native")]
             ResultData::Stream(_) => {
                 // Streaming queries should not reach formatter - they're consumed by export
                 Err(crate::error::ExecutionError::InvalidOperation(
@@ -170,9 +219,15 @@ impl JsonFormatter {
 
     /// Convert BSON document to simplified JSON
     ///
-    /// Converts BSON types to human-readable JSON using the JsonConverter
+    /// Converts BSON types to human-readable JSON using the JsonConverter,
+    /// or to Extended JSON when enabled
     fn bson_to_simplified_json(&self, doc: &Document) -> serde_json::Value {
-        self.converter.convert(&Bson::Document(doc.clone()))
+        let value = Bson::Document(doc.clone());
+        match self.extjson {
+            None => self.converter.convert(&value),
+            Some(ExtJsonMode::Relaxed) => value.into_relaxed_extjson(),
+            Some(ExtJsonMode::Canonical) => value.into_canonical_extjson(),
+        }
     }
 }
 
@@ -185,7 +240,7 @@ impl Default for JsonFormatter {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use mongodb::bson::doc;
+    use bson::doc;
 
     #[test]
     fn test_json_formatter() {
@@ -198,7 +253,7 @@ mod tests {
 
     #[test]
     fn test_json_formatter_simplified_objectid() {
-        use mongodb::bson::oid::ObjectId;
+        use bson::oid::ObjectId;
         let formatter = JsonFormatter::new(true, false, 2);
         let oid = ObjectId::parse_str("65705d84dfc3f3b5094e1f72").unwrap();
         let doc = doc! { "_id": oid };
@@ -211,7 +266,7 @@ mod tests {
 
     #[test]
     fn test_json_formatter_simplified_datetime() {
-        use mongodb::bson::DateTime;
+        use bson::DateTime;
         let formatter = JsonFormatter::new(true, false, 2);
         let dt = DateTime::from_millis(1701862788373);
         let doc = doc! { "created_time": dt };
@@ -236,7 +291,7 @@ mod tests {
 
     #[test]
     fn test_json_formatter_complete_document() {
-        use mongodb::bson::{DateTime, oid::ObjectId};
+        use bson::{DateTime, oid::ObjectId};
         let formatter = JsonFormatter::new(true, false, 2);
         let oid = ObjectId::parse_str("65705d84dfc3f3b5094e1f72").unwrap();
         let dt = DateTime::from_millis(1701862788373);
@@ -299,4 +354,55 @@ mod tests {
         assert!(pretty_result.contains('\n'));
         assert!(!compact_result.contains('\n'));
     }
+
+    #[test]
+    fn test_json_formatter_typed_listing() {
+        use crate::executor::CollectionInfo;
+
+        let formatter = JsonFormatter::new(false, false, 2);
+        let data = ResultData::Collections(vec![CollectionInfo {
+            name: "users".to_string(),
+            kind: "collection".to_string(),
+        }]);
+        let result = formatter.format(&data).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
+        assert_eq!(
+            parsed,
+            serde_json::json!([{ "name": "users", "type": "collection" }])
+        );
+    }
+
+    #[test]
+    fn test_json_formatter_extjson() {
+        let doc = doc! {
+            "n": 1,
+            "big": 5_000_000_000i64,
+            "at": bson::DateTime::from_millis(1701862788373),
+        };
+
+        let relaxed = JsonFormatter::new(false, false, 2).with_extjson(ExtJsonMode::Relaxed);
+        let parsed: serde_json::Value =
+            serde_json::from_str(&relaxed.format_document(&doc).unwrap()).unwrap();
+        assert_eq!(
+            parsed,
+            serde_json::json!({
+                "n": 1,
+                "big": 5_000_000_000i64,
+                "at": { "$date": "2023-12-06T11:39:48.373Z" },
+            })
+        );
+
+        let canonical = JsonFormatter::new(false, false, 2).with_extjson(ExtJsonMode::Canonical);
+        let output = canonical.format_document(&doc).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(parsed["n"], serde_json::json!({ "$numberInt": "1" }));
+        assert_eq!(
+            parsed["at"],
+            serde_json::json!({ "$date": { "$numberLong": "1701862788373" } })
+        );
+
+        // Canonical output reads back to the same types
+        let value: Bson = parsed.try_into().unwrap();
+        assert_eq!(value, Bson::Document(doc));
+    }
 }
@@ -0,0 +1,148 @@
+//! Shell views for typed admin listings
+//!
+//! This module renders `show dbs`, `show collections` and `show users` the way
+//! the MongoDB shell does: one aligned line per entry instead of raw
+//! documents. Index listings keep the document view and are not handled here.
+
+use crate::executor::{CollectionInfo, DatabaseInfo, UserInfo};
+
+/// Formatter for database, collection and user listings
+pub struct ListingFormatter;
+
+impl ListingFormatter {
+    /// One line per database with its size on disk, e.g. `admin  40.00 KiB`
+    ///
+    /// # Arguments
+    /// * `databases` - Databases to list
+    ///
+    /// # Returns
+    /// * `String` - Aligned listing
+    pub fn databases(databases: &[DatabaseInfo]) -> String {
+        let width = databases.iter().map(|db| db.name.len()).max().unwrap_or(0);
+        databases
+            .iter()
+            .map(|db| format!("{:<width$}  {}", db.name, format_size(db.size_on_disk)))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// One line per collection; views and time series are tagged
+    ///
+    /// # Arguments
+    /// * `collections` - Collections to list
+    ///
+    /// # Returns
+    /// * `String` - Collection names
+    pub fn collections(collections: &[CollectionInfo]) -> String {
+        collections
+            .iter()
+            .map(|coll| match coll.kind.as_str() {
+                "collection" => coll.name.clone(),
+                kind => format!("{} [{}]", coll.name, kind),
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// One line per user with the granted roles
+    ///
+    /// # Arguments
+    /// * `users` - Users to list
+    ///
+    /// # Returns
+    /// * `String` - Users as `user@db  roles: role@db, ...`
+    pub fn users(users: &[UserInfo]) -> String {
+        if users.is_empty() {
+            return "(no users)".to_string();
+        }
+
+        let names: Vec<String> = users
+            .iter()
+            .map(|user| format!("{}@{}", user.user, user.db))
+            .collect();
+        let width = names.iter().map(String::len).max().unwrap_or(0);
+        names
+            .iter()
+            .zip(users)
+            .map(|(name, user)| format!("{:<width$}  roles: {}", name, user.roles.join(", ")))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Format a byte count with a binary unit, e.g. `8.00 KiB`
+pub(super) fn format_size(bytes: u64) -> String {
+    const UNITS: &[&str] = &["B", "KiB", "MiB", "GiB", "TiB"];
+
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.2} {}", size, UNITS[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(0), "0.00 B");
+        assert_eq!(format_size(8192), "8.00 KiB");
+        assert_eq!(format_size(3 * 1024 * 1024 * 1024), "3.00 GiB");
+    }
+
+    #[test]
+    fn test_databases_are_aligned() {
+        let databases = vec![
+            DatabaseInfo {
+                name: "admin".to_string(),
+                size_on_disk: 40960,
+                empty: false,
+            },
+            DatabaseInfo {
+                name: "reporting".to_string(),
+                size_on_disk: 1024 * 1024,
+                empty: false,
+            },
+        ];
+        assert_eq!(
+            ListingFormatter::databases(&databases),
+            "admin      40.00 KiB\nreporting  1.00 MiB"
+        );
+    }
+
+    #[test]
+    fn test_collections_tag_views() {
+        let collections = vec![
+            CollectionInfo {
+                name: "users".to_string(),
+                kind: "collection".to_string(),
+            },
+            CollectionInfo {
+                name: "active_users".to_string(),
+                kind: "view".to_string(),
+            },
+        ];
+        assert_eq!(
+            ListingFormatter::collections(&collections),
+            "users\nactive_users [view]"
+        );
+    }
+
+    #[test]
+    fn test_users_list_roles() {
+        let users = vec![UserInfo {
+            user: "app".to_string(),
+            db: "shop".to_string(),
+            roles: vec!["readWrite@shop".to_string()],
+        }];
+        assert_eq!(
+            ListingFormatter::users(&users),
+            "app@shop  roles: readWrite@shop"
+        );
+        assert_eq!(ListingFormatter::users(&[]), "(no users)");
+    }
+}
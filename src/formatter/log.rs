@@ -0,0 +1,348 @@
+//! Server log formatting for `show log`
+//!
+//! This module parses lines returned by the `getLog` command into columns
+//! (time, severity, component, context, message), filters them and renders
+//! them with severity coloring. Both the structured JSON format used since
+//! MongoDB 4.4 and the older plain-text format are understood.
+
+use chrono::{DateTime, FixedOffset};
+use serde_json::Value;
+
+use super::colorizer::AnsiColors;
+
+/// Format of pre-4.4 plain-text log timestamps
+const LEGACY_TIME_FORMAT: &str = "%Y-%m-%dT%H:%M:%S%.3f%z";
+
+/// Width of the component column
+const COMPONENT_WIDTH: usize = 10;
+
+/// One parsed server log line
+#[derive(Debug, Clone, PartialEq)]
+struct LogEntry {
+    /// Timestamp as written by the server
+    time: String,
+
+    /// Severity: F, E, W, I or D1-D5
+    severity: String,
+
+    /// Log component such as NETWORK or COMMAND
+    component: String,
+
+    /// Thread or connection context
+    context: String,
+
+    /// Message text
+    message: String,
+
+    /// Attributes of JSON lines as compact JSON, empty if none
+    attributes: String,
+}
+
+impl LogEntry {
+    /// Parse a log line in either the JSON or the legacy text format
+    fn parse(line: &str) -> Self {
+        Self::parse_json(line).unwrap_or_else(|| Self::parse_legacy(line))
+    }
+
+    /// Parse a structured (4.4+) JSON log line
+    fn parse_json(line: &str) -> Option<Self> {
+        let value: Value = serde_json::from_str(line).ok()?;
+        let object = value.as_object()?;
+
+        let text = |key: &str| {
+            object
+                .get(key)
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string()
+        };
+        let time = object
+            .get("t")
+            .and_then(|t| t.get("$date"))
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string();
+
+        let attributes = object
+            .get("attr")
+            .filter(|a| !a.is_null())
+            .map(Value::to_string)
+            .unwrap_or_default();
+
+        Some(Self {
+            time,
+            severity: text("s"),
+            component: text("c"),
+            context: text("ctx"),
+            message: text("msg"),
+            attributes,
+        })
+    }
+
+    /// Parse a legacy line: `<time> <severity> <component> [<context>] <message>`
+    fn parse_legacy(line: &str) -> Self {
+        let mut parts = line.splitn(4, ' ');
+        let time = parts.next().unwrap_or_default();
+        let severity = parts.next().unwrap_or_default();
+        let component = parts.next().unwrap_or_default();
+        let rest = parts.next().unwrap_or_default().trim_start();
+
+        if DateTime::parse_from_str(time, LEGACY_TIME_FORMAT).is_err() {
+            return Self {
+                time: String::new(),
+                severity: String::new(),
+                component: String::new(),
+                context: String::new(),
+                message: line.to_string(),
+                attributes: String::new(),
+            };
+        }
+
+        let (context, message) = match rest.strip_prefix('[').and_then(|r| r.split_once("] ")) {
+            Some((context, message)) => (context, message),
+            None => ("", rest),
+        };
+
+        Self {
+            time: time.to_string(),
+            severity: severity.to_string(),
+            component: component.trim().to_string(),
+            context: context.to_string(),
+            message: message.to_string(),
+            attributes: String::new(),
+        }
+    }
+
+    /// Parse the entry timestamp
+    fn timestamp(&self) -> Option<DateTime<FixedOffset>> {
+        DateTime::parse_from_rfc3339(&self.time)
+            .or_else(|_| DateTime::parse_from_str(&self.time, LEGACY_TIME_FORMAT))
+            .ok()
+    }
+}
+
+/// Filters applied to log lines before display
+#[derive(Debug, Clone, Default)]
+pub struct LogFilter {
+    /// Case-insensitive text that must appear in the line
+    pub grep: Option<String>,
+
+    /// Only keep entries at or after this time
+    pub since: Option<DateTime<FixedOffset>>,
+}
+
+/// Formatter for `getLog` results
+pub struct LogFormatter {
+    /// Enable severity coloring
+    use_colors: bool,
+}
+
+impl LogFormatter {
+    /// Create a new log formatter
+    ///
+    /// # Arguments
+    /// * `use_colors` - Color lines by severity
+    pub fn new(use_colors: bool) -> Self {
+        Self { use_colors }
+    }
+
+    /// Filter and format raw log lines
+    ///
+    /// # Arguments
+    /// * `lines` - Lines from the `log` array of a `getLog` result
+    /// * `filter` - Filters to apply
+    ///
+    /// # Returns
+    /// * `String` - One formatted line per matching entry
+    pub fn format(&self, lines: &[String], filter: &LogFilter) -> String {
+        let formatted = self.format_lines(lines, filter);
+        if formatted.is_empty() {
+            return "No matching log entries".to_string();
+        }
+        formatted.join("\n")
+    }
+
+    /// Filter and format raw log lines, one output line per matching entry
+    ///
+    /// # Arguments
+    /// * `lines` - Lines from the `log` array of a `getLog` result
+    /// * `filter` - Filters to apply
+    ///
+    /// # Returns
+    /// * `Vec<String>` - Formatted entries, empty if none match
+    pub fn format_lines(&self, lines: &[String], filter: &LogFilter) -> Vec<String> {
+        let needle = filter.grep.as_ref().map(|g| g.to_lowercase());
+
+        lines
+            .iter()
+            .filter(|line| {
+                needle
+                    .as_ref()
+                    .is_none_or(|needle| line.to_lowercase().contains(needle))
+            })
+            .map(|line| LogEntry::parse(line))
+            .filter(|entry| {
+                filter
+                    .since
+                    .is_none_or(|since| entry.timestamp().is_some_and(|t| t >= since))
+            })
+            .map(|entry| self.format_entry(&entry))
+            .collect()
+    }
+
+    /// Summarize log lines as a short list of distinct messages
+    ///
+    /// # Arguments
+    /// * `title` - Heading printed above the messages
+    /// * `lines` - Raw log lines
+    ///
+    /// # Returns
+    /// * `Option<String>` - Summary, or `None` if there is nothing to show
+    pub fn summarize(&self, title: &str, lines: &[String]) -> Option<String> {
+        let mut messages: Vec<String> = Vec::new();
+        for line in lines {
+            let entry = LogEntry::parse(line);
+            let message = entry.message.trim_start_matches('*').trim();
+            if !message.is_empty() && !messages.iter().any(|m| m == message) {
+                messages.push(message.to_string());
+            }
+        }
+
+        if messages.is_empty() {
+            return None;
+        }
+
+        let (color, reset) = if self.use_colors {
+            (AnsiColors::YELLOW, AnsiColors::RESET)
+        } else {
+            ("", "")
+        };
+        let mut output = format!("{}{}:{}", color, title, reset);
+        for message in messages {
+            output.push_str(&format!("\n{}  - {}{}", color, message, reset));
+        }
+        Some(output)
+    }
+
+    /// Render one entry as aligned columns
+    fn format_entry(&self, entry: &LogEntry) -> String {
+        let mut line = if entry.time.is_empty() {
+            entry.message.clone()
+        } else {
+            format!(
+                "{} {:<2} {:<width$} [{}] {}",
+                entry.time,
+                entry.severity,
+                entry.component,
+                entry.context,
+                entry.message,
+                width = COMPONENT_WIDTH
+            )
+        };
+        if !entry.attributes.is_empty() {
+            line.push(' ');
+            line.push_str(&entry.attributes);
+        }
+
+        match self.severity_color(&entry.severity) {
+            Some(color) => format!("{}{}{}", color, line, AnsiColors::RESET),
+            None => line,
+        }
+    }
+
+    /// Color for a severity, if coloring is enabled
+    fn severity_color(&self, severity: &str) -> Option<&'static str> {
+        if !self.use_colors {
+            return None;
+        }
+        match severity {
+            "F" | "E" => Some(AnsiColors::RED),
+            "W" => Some(AnsiColors::YELLOW),
+            s if s.starts_with('D') => Some(AnsiColors::DIM),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const JSON_LINE: &str = r#"{"t":{"$date":"2024-05-01T12:00:05.123+00:00"},"s":"I","c":"COMMAND","id":51803,"ctx":"conn12","msg":"Slow query","attr":{"ns":"shop.orders","durationMillis":250}}"#;
+    const WARNING_LINE: &str = r#"{"t":{"$date":"2024-05-01T11:00:00.000+00:00"},"s":"W","c":"CONTROL","id":22120,"ctx":"initandlisten","msg":"Access control is not enabled for the database"}"#;
+    const LEGACY_LINE: &str =
+        "2019-03-05T10:00:00.123+0000 I NETWORK  [conn1] end connection 127.0.0.1:50000";
+
+    #[test]
+    fn test_parse_json_line() {
+        let entry = LogEntry::parse(JSON_LINE);
+        assert_eq!(entry.severity, "I");
+        assert_eq!(entry.component, "COMMAND");
+        assert_eq!(entry.context, "conn12");
+        assert_eq!(entry.message, "Slow query");
+        assert!(entry.attributes.contains("durationMillis"));
+        assert!(entry.timestamp().is_some());
+    }
+
+    #[test]
+    fn test_parse_legacy_line() {
+        let entry = LogEntry::parse(LEGACY_LINE);
+        assert_eq!(entry.severity, "I");
+        assert_eq!(entry.component, "NETWORK");
+        assert_eq!(entry.context, "conn1");
+        assert_eq!(entry.message, "end connection 127.0.0.1:50000");
+        assert!(entry.timestamp().is_some());
+
+        let unknown = LogEntry::parse("not a log line");
+        assert_eq!(unknown.message, "not a log line");
+    }
+
+    #[test]
+    fn test_format_grep_and_since() {
+        let lines = vec![JSON_LINE.to_string(), WARNING_LINE.to_string()];
+        let formatter = LogFormatter::new(false);
+
+        let filter = LogFilter {
+            grep: Some("SLOW QUERY".to_string()),
+            since: None,
+        };
+        let output = formatter.format(&lines, &filter);
+        assert!(output.contains("Slow query"));
+        assert!(!output.contains("Access control"));
+
+        let filter = LogFilter {
+            grep: None,
+            since: DateTime::parse_from_rfc3339("2024-05-01T11:30:00+00:00").ok(),
+        };
+        let output = formatter.format(&lines, &filter);
+        assert_eq!(output.lines().count(), 1);
+        assert!(output.contains("conn12"));
+    }
+
+    #[test]
+    fn test_format_severity_colors() {
+        let lines = vec![WARNING_LINE.to_string()];
+        let output = LogFormatter::new(true).format(&lines, &LogFilter::default());
+        assert!(output.starts_with(AnsiColors::YELLOW));
+
+        let output = LogFormatter::new(false).format(&lines, &LogFilter::default());
+        assert!(!output.contains('\x1b'));
+    }
+
+    #[test]
+    fn test_summarize() {
+        let lines = vec![
+            WARNING_LINE.to_string(),
+            WARNING_LINE.to_string(),
+            "2019-03-05T10:00:00.123+0000 I CONTROL  [initandlisten] ** WARNING: Using the XFS filesystem is strongly recommended".to_string(),
+            "2019-03-05T10:00:00.123+0000 I CONTROL  [initandlisten] ".to_string(),
+        ];
+        let summary = LogFormatter::new(false)
+            .summarize("Server warnings", &lines)
+            .unwrap();
+        assert_eq!(summary.lines().count(), 3);
+        assert!(summary.contains("  - WARNING: Using the XFS filesystem"));
+
+        assert!(LogFormatter::new(false).summarize("Server warnings", &[]).is_none());
+    }
+}
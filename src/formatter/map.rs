@@ -0,0 +1,311 @@
+//! ASCII map preview of geospatial results
+//!
+//! The `map` output format (also chosen by `.geo()`) plots the GeoJSON
+//! positions found in each result document on a small character grid,
+//! followed by the bounding box and an OpenStreetMap link showing it, as a
+//! quick sanity check that a `$near` or `$geoWithin` query returned places
+//! where they were expected.
+//!
+//! ```text
+//! 52.5400 ┤      •
+//!         │  •         2
+//! 52.4900 ┤•
+//!         └──────────────
+//!          13.3500  13.4500
+//! 4 point(s) in 3 document(s)
+//! https://www.openstreetmap.org/?minlon=13.3500&minlat=52.4900&maxlon=13.4500&maxlat=52.5400
+//! ```
+
+use bson::{Bson, Document};
+
+use crate::executor::ResultData;
+
+/// Width of the plot area (characters)
+const PLOT_WIDTH: usize = 60;
+
+/// Height of the plot area (lines)
+const PLOT_HEIGHT: usize = 16;
+
+/// Formatter plotting the positions of GeoJSON values in result documents
+pub struct MapFormatter {
+    /// Enable colored points
+    use_colors: bool,
+}
+
+impl MapFormatter {
+    /// Create a new map formatter
+    ///
+    /// # Arguments
+    /// * `use_colors` - Enable colored output
+    ///
+    /// # Returns
+    /// * `Self` - New formatter
+    pub fn new(use_colors: bool) -> Self {
+        Self { use_colors }
+    }
+
+    /// Plot the positions found in the documents of a result
+    ///
+    /// # Arguments
+    /// * `data` - Result data to plot
+    ///
+    /// # Returns
+    /// * `Option<String>` - Plot with bounding box and link, or None when the
+    ///   result holds no GeoJSON positions
+    pub fn format(&self, data: &ResultData) -> Option<String> {
+        let documents: &[Document] = match data {
+            ResultData::Documents(documents)
+            | ResultData::DocumentsWithPagination { documents, .. } => documents,
+            ResultData::Document(document) => std::slice::from_ref(document),
+            _ => return None,
+        };
+
+        let mut points = Vec::new();
+        let mut with_points = 0;
+        for document in documents {
+            let before = points.len();
+            for value in document.values() {
+                collect_positions(value, &mut points);
+            }
+            if points.len() > before {
+                with_points += 1;
+            }
+        }
+        if points.is_empty() {
+            return None;
+        }
+
+        let bbox = BoundingBox::around(&points);
+        Some(format!(
+            "{}\n{} point(s) in {} document(s)\n{}",
+            self.plot(&points, &bbox),
+            points.len(),
+            with_points,
+            bbox.osm_link()
+        ))
+    }
+
+    /// Draw the points on a grid with latitude and longitude axes
+    fn plot(&self, points: &[(f64, f64)], bbox: &BoundingBox) -> String {
+        let mut counts = vec![vec![0usize; PLOT_WIDTH]; PLOT_HEIGHT];
+        for &(lng, lat) in points {
+            let x = scale(lng, bbox.min_lng, bbox.max_lng, PLOT_WIDTH);
+            let y = PLOT_HEIGHT - 1 - scale(lat, bbox.min_lat, bbox.max_lat, PLOT_HEIGHT);
+            counts[y][x] += 1;
+        }
+
+        let top = format!("{:.4}", bbox.max_lat);
+        let bottom = format!("{:.4}", bbox.min_lat);
+        let margin = top.len().max(bottom.len());
+
+        let mut lines = Vec::with_capacity(PLOT_HEIGHT + 2);
+        for (row, cells) in counts.iter().enumerate() {
+            let (label, axis) = match row {
+                0 => (top.as_str(), '┤'),
+                r if r == PLOT_HEIGHT - 1 => (bottom.as_str(), '┤'),
+                _ => ("", '│'),
+            };
+            let cells: String = cells.iter().map(|&count| self.marker(count)).collect();
+            lines.push(
+                format!("{:>margin$} {}{}", label, axis, cells, margin = margin)
+                    .trim_end()
+                    .to_string(),
+            );
+        }
+        lines.push(format!(
+            "{:>margin$} └{}",
+            "",
+            "─".repeat(PLOT_WIDTH),
+            margin = margin
+        ));
+
+        let left = format!("{:.4}", bbox.min_lng);
+        let right = format!("{:.4}", bbox.max_lng);
+        let gap = (PLOT_WIDTH + 1)
+            .saturating_sub(left.len() + right.len())
+            .max(1);
+        lines.push(format!(
+            "{:>margin$}  {}{}{}",
+            "",
+            left,
+            " ".repeat(gap),
+            right,
+            margin = margin
+        ));
+        lines.join("\n")
+    }
+
+    /// Marker for a cell holding `count` points
+    fn marker(&self, count: usize) -> String {
+        let marker = match count {
+            0 => return " ".to_string(),
+            1 => "•".to_string(),
+            2..=9 => count.to_string(),
+            _ => "*".to_string(),
+        };
+        if self.use_colors {
+            format!("\x1b[32m{}\x1b[0m", marker)
+        } else {
+            marker
+        }
+    }
+}
+
+/// Smallest box holding every point, widened when all points coincide
+struct BoundingBox {
+    min_lng: f64,
+    min_lat: f64,
+    max_lng: f64,
+    max_lat: f64,
+}
+
+impl BoundingBox {
+    fn around(points: &[(f64, f64)]) -> Self {
+        let mut bbox = Self {
+            min_lng: f64::MAX,
+            min_lat: f64::MAX,
+            max_lng: f64::MIN,
+            max_lat: f64::MIN,
+        };
+        for &(lng, lat) in points {
+            bbox.min_lng = bbox.min_lng.min(lng);
+            bbox.max_lng = bbox.max_lng.max(lng);
+            bbox.min_lat = bbox.min_lat.min(lat);
+            bbox.max_lat = bbox.max_lat.max(lat);
+        }
+        // About 1 km around a single place
+        const MIN_SPAN: f64 = 0.01;
+        if bbox.max_lng - bbox.min_lng < MIN_SPAN {
+            bbox.min_lng = (bbox.min_lng - MIN_SPAN / 2.0).max(-180.0);
+            bbox.max_lng = (bbox.max_lng + MIN_SPAN / 2.0).min(180.0);
+        }
+        if bbox.max_lat - bbox.min_lat < MIN_SPAN {
+            bbox.min_lat = (bbox.min_lat - MIN_SPAN / 2.0).max(-90.0);
+            bbox.max_lat = (bbox.max_lat + MIN_SPAN / 2.0).min(90.0);
+        }
+        bbox
+    }
+
+    /// OpenStreetMap page showing the box
+    fn osm_link(&self) -> String {
+        format!(
+            "https://www.openstreetmap.org/?minlon={:.4}&minlat={:.4}&maxlon={:.4}&maxlat={:.4}",
+            self.min_lng, self.min_lat, self.max_lng, self.max_lat
+        )
+    }
+}
+
+/// Cell index of `value` within `[min, max]` on an axis of `cells` cells
+fn scale(value: f64, min: f64, max: f64, cells: usize) -> usize {
+    let ratio = if max > min {
+        (value - min) / (max - min)
+    } else {
+        0.5
+    };
+    ((ratio * (cells - 1) as f64).round() as usize).min(cells - 1)
+}
+
+/// Collect `(longitude, latitude)` positions of GeoJSON values below `value`
+fn collect_positions(value: &Bson, points: &mut Vec<(f64, f64)>) {
+    match value {
+        Bson::Document(doc) => {
+            if let (Ok(_), Some(coordinates)) = (doc.get_str("type"), doc.get("coordinates")) {
+                collect_coordinates(coordinates, points);
+            } else if let Ok(geometries) = doc.get_array("geometries") {
+                geometries
+                    .iter()
+                    .for_each(|geometry| collect_positions(geometry, points));
+            } else {
+                doc.values()
+                    .for_each(|nested| collect_positions(nested, points));
+            }
+        }
+        Bson::Array(items) => items
+            .iter()
+            .for_each(|item| collect_positions(item, points)),
+        _ => {}
+    }
+}
+
+/// Collect the positions of a (possibly nested) GeoJSON coordinates array
+fn collect_coordinates(value: &Bson, points: &mut Vec<(f64, f64)>) {
+    let Bson::Array(items) = value else {
+        return;
+    };
+    match items.as_slice() {
+        [lng, lat, ..] if number(lng).is_some() && number(lat).is_some() => {
+            let (lng, lat) = (
+                number(lng).unwrap_or_default(),
+                number(lat).unwrap_or_default(),
+            );
+            if (-180.0..=180.0).contains(&lng) && (-90.0..=90.0).contains(&lat) {
+                points.push((lng, lat));
+            }
+        }
+        _ => items
+            .iter()
+            .for_each(|item| collect_coordinates(item, points)),
+    }
+}
+
+fn number(value: &Bson) -> Option<f64> {
+    match value {
+        Bson::Int32(n) => Some(*n as f64),
+        Bson::Int64(n) => Some(*n as f64),
+        Bson::Double(f) if f.is_finite() => Some(*f),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bson::doc;
+
+    fn place(lng: f64, lat: f64) -> Document {
+        doc! { "name": "x", "location": { "type": "Point", "coordinates": [lng, lat] } }
+    }
+
+    #[test]
+    fn test_map_plot_and_link() {
+        let formatter = MapFormatter::new(false);
+        let data = ResultData::Documents(vec![
+            place(13.35, 52.49),
+            place(13.45, 52.54),
+            place(13.45, 52.54),
+            doc! { "name": "no location" },
+        ]);
+        let map = formatter.format(&data).unwrap();
+        let lines: Vec<&str> = map.lines().collect();
+
+        assert!(lines[0].starts_with("52.5400 ┤"));
+        assert!(lines[0].ends_with('2'));
+        assert!(lines[PLOT_HEIGHT - 1].starts_with("52.4900 ┤•"));
+        assert!(map.contains("3 point(s) in 3 document(s)"));
+        assert!(map.ends_with(
+            "https://www.openstreetmap.org/?minlon=13.3500&minlat=52.4900&maxlon=13.4500&maxlat=52.5400"
+        ));
+    }
+
+    #[test]
+    fn test_collect_positions() {
+        let mut points = Vec::new();
+        let area = bson::bson!({
+            "type": "Polygon",
+            "coordinates": [[[0, 0], [3, 6], [6, 1], [0, 0]]],
+        });
+        collect_positions(&area, &mut points);
+        assert_eq!(points.len(), 4);
+
+        let formatter = MapFormatter::new(false);
+        assert!(
+            formatter
+                .format(&ResultData::Documents(vec![doc! { "a": [1, 2] }]))
+                .is_none()
+        );
+
+        // A single place gets a box around it
+        let bbox = BoundingBox::around(&[(2.0, 48.0)]);
+        assert!(bbox.max_lng > bbox.min_lng && bbox.max_lat > bbox.min_lat);
+    }
+}
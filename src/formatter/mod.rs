@@ -8,6 +8,8 @@
 //!   - ObjectId('...'), ISODate('...'), Long('...')
 //!   - Pretty-printed nested documents and arrays
 //!   - Optional color highlighting
+//!   - Deep or wide nested values collapsed to `{…} (N keys)` / `[… N items]`
+//!   - Long strings and large BinData shortened to a preview
 //!
 //! - **Json**: Compact single-line JSON
 //!   - Minified output without whitespace
@@ -25,30 +27,112 @@
 //!   - Shows only count/summary, not full content
 //!   - Example: "5 document(s) returned"
 //!
+//! - **ExtJsonRelaxed** / **ExtJsonCanonical**: MongoDB Extended JSON v2
+//!   - Keeps every BSON type, e.g. `{"$oid": "..."}`, `{"$numberLong": "5"}`
+//!   - Suitable for round-tripping through drivers and mongoimport
+//!
+//! - **Chart**: ASCII bar chart of `{_id, count}` style documents
+//!   - One scaled bar per document with its value and percentage
+//!   - Other results fall back to the shell format
+//!
+//! - **Map**: ASCII scatter of the GeoJSON positions in the results
+//!   - Followed by the bounding box as an OpenStreetMap link
+//!   - Other results fall back to the shell format
+//!
+//! When `display.accessible` is set, Shell and Table output is replaced by
+//! linear, label-prefixed lines (`row 1 of 5: name=Alice age=30`) and colors
+//! are turned off.
+//!
 //! # Module Structure
 //!
+//! - `accessible`: Screen reader friendly linear formatter
+//! - `chart`: Bar charts of grouped counts
+//! - `cluster_report`: Health checks for `clusterReport()`
 //! - `colorizer`: ANSI color support for terminal output
+//! - `custom`: Output formats registered by plugins
 //! - `shell`: Shell-style formatter (mongosh compatible)
 //! - `json`: JSON formatter with BSON type simplification
+//! - `map`: Map preview of geospatial results
 //! - `table`: Table formatter for document collections
 //! - `stats`: Statistics formatter for execution metrics
-
+//! - `validation`: Readable reports for `validate` command results
+//! - `top`: Per-namespace usage tables for the `top` command
+//! - `current_op`: Active operation tables for the `currentOp` monitor
+//! - `error`: Errors with explanations and hints for common server error codes
+//! - `index_report`: Index usage tables for `db.indexReport()`
+//! - `oplog`: Oplog window for `rs.printOplogInfo()` and entries for `oplog tail`
+//! - `log`: Parsed, filtered and colored server log lines for `show log`
+//! - `listing`: Aligned views for `show dbs`, `show collections` and `show users`
+//! - `storage`: Database and collection size tree for `storageReport()`
+//! - `projection`: Client-side field selection for `::` and `.pluck()`
+//! - `replication`: Per-member lag tables for `rs.lag`
+//! - `redaction`: Masking of sensitive fields configured under `[redaction]`
+//! - `sink`: Output destinations (terminal, file, buffer, WebSocket)
+//! - `watch`: Change highlighting for repeated runs of the `watch` command
+
+mod accessible;
 pub mod bson_utils;
+mod chart;
+mod cluster_report;
 mod colorizer;
+mod current_op;
+mod custom;
+mod error;
+mod index_report;
 mod json;
+mod listing;
+mod log;
+mod map;
+mod oplog;
+mod projection;
+mod redaction;
+mod replication;
 mod shell;
+mod sink;
 mod stats;
+mod storage;
 mod table;
-
-pub use colorizer::Colorizer;
-pub use json::JsonFormatter;
+mod top;
+mod validation;
+mod watch;
+
+pub use accessible::AccessibleFormatter;
+pub use cluster_report::{ClusterReportFormatter, ClusterStats, MemberLag};
+pub use colorizer::{AnsiColors, Colorizer};
+pub use current_op::{CurrentOpFormatter, OperationInfo, format_opid};
+pub(crate) use custom::find_format;
+#[cfg(feature = "plugins")]
+pub(crate) use custom::unregister_format;
+#[allow(unused_imports)]
+pub use custom::{DocumentFormatter, custom_format_names, register_format};
+pub use error::ErrorFormatter;
+pub use index_report::{IndexReportFormatter, IndexUsage};
+pub use json::{ExtJsonMode, JsonFormatter};
+pub use listing::ListingFormatter;
+pub use log::{LogFilter, LogFormatter};
+pub use oplog::{OplogFormatter, OplogInfo};
+pub use projection::FieldProjection;
+pub use redaction::Redactor;
+pub use replication::ReplicationLagFormatter;
 pub use shell::ShellFormatter;
+#[allow(unused_imports)]
+pub use sink::{BufferSink, FileSink, OutputSink, TerminalSink, strip_ansi};
+#[cfg(feature = "native")]
+#[allow(unused_imports)]
+pub use sink::WebSocketSink;
 pub use stats::StatsFormatter;
+pub use storage::{DatabaseStorage, StorageReportFormatter, StorageSizes};
 pub use table::TableFormatter;
+pub use top::TopFormatter;
+pub use validation::ValidationFormatter;
+pub use watch::WatchFormatter;
 
-use crate::config::OutputFormat;
-use crate::error::Result;
+use crate::config::{OutputFormat, RedactionConfig};
+use crate::error::{MongoshError, Result};
 use crate::executor::{ExecutionResult, ResultData};
+use bson_utils::DateDisplay;
+use chart::ChartFormatter;
+use map::MapFormatter;
 
 /// Main formatter for execution results
 pub struct Formatter {
@@ -66,6 +150,33 @@ pub struct Formatter {
 
     /// Show execution timing
     show_timing: bool,
+
+    /// Screen reader friendly output
+    accessible: bool,
+
+    /// Nesting level from which shell output collapses values (0 = never)
+    collapse_depth: usize,
+
+    /// Entries above which shell output collapses values (0 = never)
+    collapse_width: usize,
+
+    /// Characters of a string shell output shows before `…` (0 = all)
+    max_string_length: usize,
+
+    /// Bytes above which shell output shows only the size of BinData (0 = never)
+    max_binary_length: usize,
+
+    /// Time zone and format of dates in shell and JSON output
+    dates: DateDisplay,
+
+    /// Append a footer of column aggregates to table output
+    table_summary: bool,
+
+    /// Masks sensitive fields before formatting
+    redactor: Option<Redactor>,
+
+    /// Keeps only the requested fields before formatting
+    projection: Option<FieldProjection>,
 }
 
 impl Formatter {
@@ -80,13 +191,66 @@ impl Formatter {
     /// # Returns
     /// * `Self` - New formatter instance
     pub fn from_config(display_config: &crate::config::DisplayConfig) -> Self {
+        let use_colors = display_config.color_output && !display_config.accessible;
         Self {
             format_type: display_config.format,
-            colorizer: Colorizer::new(display_config.color_output),
-            use_colors: display_config.color_output,
+            colorizer: Colorizer::new(use_colors),
+            use_colors,
             json_indent: display_config.json_indent,
             show_timing: display_config.show_timing,
+            accessible: display_config.accessible,
+            collapse_depth: display_config.collapse_depth,
+            collapse_width: display_config.collapse_width,
+            max_string_length: display_config.max_string_length,
+            max_binary_length: display_config.max_binary_length,
+            // Settings are checked when the configuration is loaded or changed
+            dates: DateDisplay::new(&display_config.timezone, &display_config.date_format)
+                .unwrap_or_default(),
+            table_summary: display_config.table_summary,
+            redactor: None,
+            projection: None,
+        }
+    }
+
+    /// Mask sensitive fields of formatted documents
+    ///
+    /// # Arguments
+    /// * `config` - Redaction configuration
+    ///
+    /// # Returns
+    /// * `Self` - Formatter masking the configured fields
+    pub fn with_redaction(mut self, config: &RedactionConfig) -> Self {
+        self.redactor = Redactor::from_config(config);
+        self
+    }
+
+    /// Show only the given fields of formatted documents
+    ///
+    /// # Arguments
+    /// * `fields` - Dotted field paths; empty to show whole documents
+    ///
+    /// # Returns
+    /// * `Self` - Formatter projecting documents onto the fields
+    pub fn with_projection(mut self, fields: &[String]) -> Self {
+        self.projection = FieldProjection::new(fields);
+        self
+    }
+
+    /// Show values in full, e.g. for `.full()` or after `show full`
+    ///
+    /// # Arguments
+    /// * `full` - Turn off collapsing and previews of large values
+    ///
+    /// # Returns
+    /// * `Self` - Formatter showing whole strings, BinData and nested values
+    pub fn with_full_values(mut self, full: bool) -> Self {
+        if full {
+            self.collapse_depth = 0;
+            self.collapse_width = 0;
+            self.max_string_length = 0;
+            self.max_binary_length = 0;
         }
+        self
     }
 
     /// Format execution result according to configured format
@@ -101,12 +265,34 @@ impl Formatter {
             return self.format_error(result);
         }
 
+        let projected = self
+            .projection
+            .as_ref()
+            .and_then(|projection| projection.project_data(&result.data));
+        let data = projected.as_ref().unwrap_or(&result.data);
+
+        let redacted = self
+            .redactor
+            .as_ref()
+            .and_then(|redactor| redactor.redact_data(data));
+        let data = redacted.as_ref().unwrap_or(data);
+
         let output = match self.format_type {
-            OutputFormat::Shell => self.format_shell(&result.data)?,
-            OutputFormat::Json => self.format_json(&result.data, false)?,
-            OutputFormat::JsonPretty => self.format_json(&result.data, true)?,
-            OutputFormat::Table => self.format_table(&result.data)?,
-            OutputFormat::Compact => self.format_compact(&result.data)?,
+            OutputFormat::Shell | OutputFormat::Table | OutputFormat::Chart | OutputFormat::Map
+                if self.accessible =>
+            {
+                AccessibleFormatter::new().format(data)?
+            }
+            OutputFormat::Shell => self.format_shell(data)?,
+            OutputFormat::Json => self.format_json(data, false)?,
+            OutputFormat::JsonPretty => self.format_json(data, true)?,
+            OutputFormat::Table => self.format_table(data)?,
+            OutputFormat::Compact => self.format_compact(data)?,
+            OutputFormat::ExtJsonRelaxed => self.format_extjson(data, ExtJsonMode::Relaxed)?,
+            OutputFormat::ExtJsonCanonical => self.format_extjson(data, ExtJsonMode::Canonical)?,
+            OutputFormat::Chart => self.format_chart(data)?,
+            OutputFormat::Map => self.format_map(data)?,
+            OutputFormat::Custom(name) => self.format_custom(name, data)?,
         };
 
         // Append statistics if enabled
@@ -118,6 +304,20 @@ impl Formatter {
         }
     }
 
+    /// Format an execution result and write it to a sink
+    ///
+    /// # Arguments
+    /// * `result` - Execution result to format
+    /// * `sink` - Destination; colors are stripped unless it is a terminal
+    ///
+    /// # Returns
+    /// * `Result<String>` - Formatted output as written, or error
+    pub fn write_to(&self, result: &ExecutionResult, sink: &mut dyn OutputSink) -> Result<String> {
+        let output = self.format(result)?;
+        sink.write_output(&output)?;
+        Ok(output)
+    }
+
     /// Format result data as Shell format
     ///
     /// # Arguments
@@ -126,7 +326,10 @@ impl Formatter {
     /// # Returns
     /// * `Result<String>` - Shell formatted string or error
     pub fn format_shell(&self, data: &ResultData) -> Result<String> {
-        let shell_formatter = ShellFormatter::new(self.use_colors);
+        let shell_formatter = ShellFormatter::new(self.use_colors)
+            .with_collapse(self.collapse_depth, self.collapse_width)
+            .with_previews(self.max_string_length, self.max_binary_length)
+            .with_dates(self.dates.clone());
         match data {
             ResultData::Documents(docs) => {
                 if docs.is_empty() {
@@ -214,8 +417,15 @@ impl Formatter {
             )),
             ResultData::Message(msg) => Ok(msg.clone()),
             ResultData::List(items) => Ok(items.join("\n")),
+            ResultData::Indexes(_) => self.format_shell(&ResultData::Documents(
+                data.to_documents().unwrap_or_default(),
+            )),
+            ResultData::Databases(databases) => Ok(ListingFormatter::databases(databases)),
+            ResultData::Collections(collections) => Ok(ListingFormatter::collections(collections)),
+            ResultData::Users(users) => Ok(ListingFormatter::users(users)),
             ResultData::Count(count) => Ok(format!("{}", count)),
             ResultData::None => Ok("null".to_string()),
+            #[cfg(feature = "native")]
             ResultData::Stream(_) => {
                 // Streaming queries should not reach formatter - they're consumed by export
                 Err(crate::error::ExecutionError::InvalidOperation(
@@ -234,10 +444,60 @@ impl Formatter {
     /// # Returns
     /// * `Result<String>` - JSON string or error
     pub fn format_json(&self, data: &ResultData, pretty: bool) -> Result<String> {
-        let formatter = JsonFormatter::new(pretty, self.use_colors, self.json_indent);
+        let formatter = JsonFormatter::new(pretty, self.use_colors, self.json_indent)
+            .with_dates(self.dates.clone());
         formatter.format(data)
     }
 
+    /// Format result data as MongoDB Extended JSON v2
+    ///
+    /// Dates keep their stored value regardless of `display.timezone`, so the
+    /// output can be imported again without loss.
+    ///
+    /// # Arguments
+    /// * `data` - Result data to format
+    /// * `mode` - Relaxed or canonical Extended JSON
+    ///
+    /// # Returns
+    /// * `Result<String>` - Extended JSON string or error
+    pub fn format_extjson(&self, data: &ResultData, mode: ExtJsonMode) -> Result<String> {
+        let formatter =
+            JsonFormatter::new(true, self.use_colors, self.json_indent).with_extjson(mode);
+        formatter.format(data)
+    }
+
+    /// Format result data with a format registered by a plugin
+    ///
+    /// Only documents go through the plugin's formatter; other results use
+    /// the shell format.
+    ///
+    /// # Arguments
+    /// * `name` - Registered format name
+    /// * `data` - Result data to format
+    ///
+    /// # Returns
+    /// * `Result<String>` - Formatted string or error
+    pub fn format_custom(&self, name: &str, data: &ResultData) -> Result<String> {
+        let (_, formatter) = find_format(name)
+            .ok_or_else(|| MongoshError::Generic(format!("Unknown output format '{}'", name)))?;
+        match data {
+            ResultData::Documents(documents) => formatter.format_documents(documents),
+            ResultData::DocumentsWithPagination {
+                documents,
+                has_more,
+                ..
+            } => {
+                let mut output = formatter.format_documents(documents)?;
+                if *has_more {
+                    output.push_str("\nType \"it\" for more");
+                }
+                Ok(output)
+            }
+            ResultData::Document(doc) => formatter.format_documents(std::slice::from_ref(doc)),
+            other => self.format_shell(other),
+        }
+    }
+
     /// Format result data as table
     ///
     /// # Arguments
@@ -246,10 +506,47 @@ impl Formatter {
     /// # Returns
     /// * `Result<String>` - Table string or error
     pub fn format_table(&self, data: &ResultData) -> Result<String> {
-        let formatter = TableFormatter::new();
+        let mut formatter = TableFormatter::new().with_summary(self.table_summary);
+        // Plucked fields are the table's columns, in the order they were listed
+        if let Some(projection) = &self.projection {
+            formatter = formatter.with_columns(&projection.fields());
+        }
         formatter.format(data)
     }
 
+    /// Format result data as a bar chart
+    ///
+    /// Results without documents holding a numeric value use the shell
+    /// format.
+    ///
+    /// # Arguments
+    /// * `data` - Result data to format
+    ///
+    /// # Returns
+    /// * `Result<String>` - Chart string or error
+    pub fn format_chart(&self, data: &ResultData) -> Result<String> {
+        match ChartFormatter::new(self.use_colors).format(data) {
+            Some(chart) => Ok(chart),
+            None => self.format_shell(data),
+        }
+    }
+
+    /// Format result data as a map of its GeoJSON positions
+    ///
+    /// Results without GeoJSON positions use the shell format.
+    ///
+    /// # Arguments
+    /// * `data` - Result data to format
+    ///
+    /// # Returns
+    /// * `Result<String>` - Map string or error
+    pub fn format_map(&self, data: &ResultData) -> Result<String> {
+        match MapFormatter::new(self.use_colors).format(data) {
+            Some(map) => Ok(map),
+            None => self.format_shell(data),
+        }
+    }
+
     /// Format result data in compact form
     ///
     /// # Arguments
@@ -286,8 +583,15 @@ impl Formatter {
             ResultData::Delete { deleted } => Ok(format!("Deleted {} document(s)", deleted)),
             ResultData::Message(msg) => Ok(msg.clone()),
             ResultData::List(items) => Ok(format!("{} item(s)", items.len())),
+            ResultData::Indexes(indexes) => Ok(format!("{} index(es)", indexes.len())),
+            ResultData::Databases(databases) => Ok(format!("{} database(s)", databases.len())),
+            ResultData::Collections(collections) => {
+                Ok(format!("{} collection(s)", collections.len()))
+            }
+            ResultData::Users(users) => Ok(format!("{} user(s)", users.len())),
             ResultData::Count(count) => Ok(format!("Count: {}", count)),
             ResultData::None => Ok("null".to_string()),
+            #[cfg(feature = "native")]
             ResultData::Stream(_) => {
                 // Streaming queries should not reach formatter - they're consumed by export
                 Err(crate::error::ExecutionError::InvalidOperation(
@@ -337,7 +641,7 @@ impl Default for Formatter {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use mongodb::bson::doc;
+    use bson::doc;
 
     #[test]
     fn test_formatter_creation() {
@@ -361,6 +665,82 @@ mod tests {
         assert!(result.contains("1 document(s)"));
     }
 
+    #[test]
+    fn test_format_extjson() {
+        let mut config = crate::config::DisplayConfig::default();
+        config.format = OutputFormat::from_name("extjson-canonical").unwrap();
+        config.color_output = false;
+        config.show_timing = false;
+        config.timezone = "Asia/Tokyo".to_string();
+        let formatter = Formatter::from_config(&config);
+        let result = ExecutionResult::success(
+            ResultData::Documents(vec![doc! {
+                "n": 1i64,
+                "at": bson::DateTime::from_millis(0),
+            }]),
+            crate::executor::ExecutionStats::default(),
+        );
+        let output = formatter.format(&result).unwrap();
+        assert!(output.contains(r#""$numberLong": "1""#));
+        // Dates are not shifted to the display time zone
+        assert!(output.contains(r#""$numberLong": "0""#));
+    }
+
+    #[test]
+    fn test_write_to_plain_sink() {
+        let mut config = crate::config::DisplayConfig::default();
+        config.format = OutputFormat::Shell;
+        config.color_output = true;
+        config.show_timing = false;
+        let result = ExecutionResult {
+            success: true,
+            data: ResultData::Document(doc! { "name": "Ada" }),
+            stats: crate::executor::ExecutionStats::default(),
+            error: None,
+        };
+        let sink = BufferSink::new();
+        let output = Formatter::from_config(&config)
+            .write_to(&result, &mut sink.clone())
+            .unwrap();
+        assert!(output.contains('\u{1b}'));
+        assert_eq!(sink.contents(), format!("{}\n", strip_ansi(&output)));
+    }
+
+    #[test]
+    fn test_format_with_redaction() {
+        let mut config = crate::config::DisplayConfig::default();
+        config.format = OutputFormat::Json;
+        config.color_output = false;
+        config.show_timing = false;
+        let redaction = RedactionConfig {
+            fields: vec!["*.password".to_string()],
+            ..RedactionConfig::default()
+        };
+        let result = ExecutionResult {
+            success: true,
+            data: ResultData::Documents(vec![doc! { "user": "ann", "password": "hunter2" }]),
+            stats: crate::executor::ExecutionStats::default(),
+            error: None,
+        };
+
+        let output = Formatter::from_config(&config)
+            .with_redaction(&redaction)
+            .format(&result)
+            .unwrap();
+        assert!(output.contains("***"));
+        assert!(!output.contains("hunter2"));
+
+        let disabled = RedactionConfig {
+            enabled: false,
+            ..redaction
+        };
+        let output = Formatter::from_config(&config)
+            .with_redaction(&disabled)
+            .format(&result)
+            .unwrap();
+        assert!(output.contains("hunter2"));
+    }
+
     #[test]
     fn test_format_shell_documents_as_array() {
         let mut config = crate::config::DisplayConfig::default();
@@ -393,10 +773,28 @@ mod tests {
         config.format = OutputFormat::Shell;
         config.color_output = false;
         let formatter = Formatter::from_config(&config);
-        let docs: Vec<mongodb::bson::Document> = vec![];
+        let docs: Vec<bson::Document> = vec![];
         let result = formatter
             .format_shell(&ResultData::Documents(docs))
             .unwrap();
         assert_eq!(result, "[]");
     }
+
+    #[test]
+    fn test_accessible_mode_replaces_table_and_colors() {
+        let mut config = crate::config::DisplayConfig::default();
+        config.format = OutputFormat::Table;
+        config.color_output = true;
+        config.show_timing = false;
+        config.accessible = true;
+        let formatter = Formatter::from_config(&config);
+        assert!(!formatter.use_colors);
+
+        let result = ExecutionResult::success(
+            ResultData::Documents(vec![doc! { "name": "Alice", "age": 30 }]),
+            Default::default(),
+        );
+        let output = formatter.format(&result).unwrap();
+        assert!(output.starts_with("row 1 of 1: name=Alice age=30"));
+    }
 }
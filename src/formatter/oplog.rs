@@ -0,0 +1,271 @@
+//! Oplog formatting for `rs.printOplogInfo()` and `oplog tail`
+//!
+//! This module renders the size and time window of `local.oplog.rs`, and
+//! turns single oplog entries into one-line summaries with the operation,
+//! namespace and a compact JSON rendering of what changed.
+
+use bson::{Bson, Document, Timestamp};
+use chrono::{DateTime, TimeZone, Utc};
+
+use super::bson_utils::number_of;
+use super::colorizer::AnsiColors;
+use super::listing::format_size;
+
+/// Longest JSON rendering of an entry's document before it is truncated
+const MAX_DETAIL_WIDTH: usize = 200;
+
+/// Size and time range of the oplog
+#[derive(Debug, Clone, PartialEq)]
+pub struct OplogInfo {
+    /// Configured maximum size in bytes
+    pub max_size: u64,
+
+    /// Bytes currently used by entries
+    pub used_size: u64,
+
+    /// Timestamp of the oldest entry
+    pub first: Option<Timestamp>,
+
+    /// Timestamp of the newest entry
+    pub last: Option<Timestamp>,
+}
+
+impl OplogInfo {
+    /// Build from a `collStats` result of `local.oplog.rs`
+    ///
+    /// # Arguments
+    /// * `stats` - `collStats` result
+    /// * `first` - `ts` of the oldest entry
+    /// * `last` - `ts` of the newest entry
+    ///
+    /// # Returns
+    /// * `OplogInfo` - Sizes and window
+    pub fn from_stats(stats: &Document, first: Option<Timestamp>, last: Option<Timestamp>) -> Self {
+        Self {
+            max_size: number_of(stats.get("maxSize")).max(0) as u64,
+            used_size: number_of(stats.get("size")).max(0) as u64,
+            first,
+            last,
+        }
+    }
+
+    /// Seconds between the oldest and the newest entry
+    pub fn window_secs(&self) -> Option<u32> {
+        match (self.first, self.last) {
+            (Some(first), Some(last)) => Some(last.time.saturating_sub(first.time)),
+            _ => None,
+        }
+    }
+}
+
+/// Formatter for oplog information and entries
+pub struct OplogFormatter {
+    /// Color entries by operation
+    use_colors: bool,
+}
+
+impl OplogFormatter {
+    /// Create a new oplog formatter
+    ///
+    /// # Arguments
+    /// * `use_colors` - Color entries by operation
+    pub fn new(use_colors: bool) -> Self {
+        Self { use_colors }
+    }
+
+    /// Render oplog size and window, in the layout of `rs.printReplicationInfo()`
+    ///
+    /// # Arguments
+    /// * `info` - Oplog sizes and first/last timestamps
+    /// * `now` - Current time
+    ///
+    /// # Returns
+    /// * `String` - One labelled line per value
+    pub fn info(info: &OplogInfo, now: DateTime<Utc>) -> String {
+        let used = if info.max_size > 0 {
+            format!(
+                "{} ({:.1}%)",
+                format_size(info.used_size),
+                info.used_size as f64 / info.max_size as f64 * 100.0
+            )
+        } else {
+            format_size(info.used_size)
+        };
+        let window = match info.window_secs() {
+            Some(secs) => format!("{} secs ({:.2} hrs)", secs, secs as f64 / 3600.0),
+            None => "empty oplog".to_string(),
+        };
+        let time = |ts: Option<Timestamp>| match ts {
+            Some(ts) => format_time(i64::from(ts.time) * 1000),
+            None => "-".to_string(),
+        };
+
+        [
+            ("configured oplog size:", format_size(info.max_size)),
+            ("used:", used),
+            ("log length start to end:", window),
+            ("oplog first event time:", time(info.first)),
+            ("oplog last event time:", time(info.last)),
+            ("now:", format_time(now.timestamp_millis())),
+        ]
+        .iter()
+        .map(|(label, value)| format!("{:<25}{}", label, value))
+        .collect::<Vec<_>>()
+        .join("\n")
+    }
+
+    /// Render one oplog entry as a single line
+    ///
+    /// # Arguments
+    /// * `entry` - Document from `local.oplog.rs`
+    ///
+    /// # Returns
+    /// * `String` - Time, operation, namespace and changed document
+    pub fn entry(&self, entry: &Document) -> String {
+        let millis = match (entry.get_datetime("wall"), entry.get_timestamp("ts")) {
+            (Ok(wall), _) => wall.timestamp_millis(),
+            (_, Ok(ts)) => i64::from(ts.time) * 1000,
+            _ => 0,
+        };
+        let op = entry.get_str("op").unwrap_or("?");
+        let (name, color) = match op {
+            "i" => ("insert", AnsiColors::GREEN),
+            "u" => ("update", AnsiColors::YELLOW),
+            "d" => ("delete", AnsiColors::RED),
+            "c" => ("command", AnsiColors::CYAN),
+            "n" => ("noop", AnsiColors::DIM),
+            other => (other, ""),
+        };
+        let name = format!("{:<7}", name);
+        let name = if self.use_colors && !color.is_empty() {
+            format!("{}{}{}", color, name, AnsiColors::RESET)
+        } else {
+            name
+        };
+
+        let object = entry.get_document("o").ok();
+        let detail = match (
+            op,
+            entry.get_document("o2").ok().and_then(|o2| o2.get("_id")),
+        ) {
+            ("u", Some(id)) => format!(
+                "_id: {} {}",
+                compact_json(id.clone()),
+                object.map_or_else(String::new, |o| compact_json(Bson::Document(o.clone())))
+            ),
+            ("n", _) => object
+                .and_then(|o| o.get_str("msg").ok())
+                .map(str::to_string)
+                .unwrap_or_default(),
+            _ => object.map_or_else(String::new, |o| compact_json(Bson::Document(o.clone()))),
+        };
+
+        format!(
+            "{}  {}  {}  {}",
+            format_time(millis),
+            name,
+            entry.get_str("ns").unwrap_or(""),
+            truncate(&detail)
+        )
+        .trim_end()
+        .to_string()
+    }
+}
+
+/// Format milliseconds since the epoch as a UTC date and time
+fn format_time(millis: i64) -> String {
+    match Utc.timestamp_millis_opt(millis).single() {
+        Some(time) => time.format("%Y-%m-%d %H:%M:%S UTC").to_string(),
+        None => "-".to_string(),
+    }
+}
+
+/// Render a value as single-line relaxed Extended JSON
+fn compact_json(value: Bson) -> String {
+    value.into_relaxed_extjson().to_string()
+}
+
+/// Shorten text to `MAX_DETAIL_WIDTH` characters
+fn truncate(text: &str) -> String {
+    if text.chars().count() > MAX_DETAIL_WIDTH {
+        let truncated: String = text.chars().take(MAX_DETAIL_WIDTH - 1).collect();
+        format!("{}…", truncated)
+    } else {
+        text.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bson::doc;
+
+    #[test]
+    fn test_format_info() {
+        let first = Timestamp {
+            time: 1_760_600_000,
+            increment: 1,
+        };
+        let last = Timestamp {
+            time: first.time + 19_352,
+            increment: 4,
+        };
+        let info = OplogInfo::from_stats(
+            &doc! { "maxSize": 1_073_741_824_i64, "size": 268_435_456.0 },
+            Some(first),
+            Some(last),
+        );
+        assert_eq!(info.window_secs(), Some(19_352));
+
+        let now = Utc.timestamp_opt(i64::from(last.time) + 5, 0).unwrap();
+        let output = OplogFormatter::info(&info, now);
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines[0], "configured oplog size:   1.00 GiB");
+        assert_eq!(lines[1], "used:                    256.00 MiB (25.0%)");
+        assert_eq!(lines[2], "log length start to end: 19352 secs (5.38 hrs)");
+        assert_eq!(lines[3], "oplog first event time:  2025-10-16 07:33:20 UTC");
+        assert_eq!(lines[5], "now:                     2025-10-16 12:55:57 UTC");
+
+        let empty = OplogInfo::from_stats(&doc! {}, None, None);
+        assert!(OplogFormatter::info(&empty, now).contains("empty oplog"));
+    }
+
+    #[test]
+    fn test_format_entries() {
+        let formatter = OplogFormatter::new(false);
+        let ts = Timestamp {
+            time: 1_760_600_000,
+            increment: 1,
+        };
+
+        let insert =
+            doc! { "ts": ts, "op": "i", "ns": "shop.users", "o": { "_id": 1, "name": "Ada" } };
+        assert_eq!(
+            formatter.entry(&insert),
+            r#"2025-10-16 07:33:20 UTC  insert   shop.users  {"_id":1,"name":"Ada"}"#
+        );
+
+        let update = doc! {
+            "ts": ts, "op": "u", "ns": "shop.users",
+            "o": { "$v": 2, "diff": { "u": { "name": "Grace" } } },
+            "o2": { "_id": 1 },
+        };
+        assert!(
+            formatter
+                .entry(&update)
+                .ends_with(r#"update   shop.users  _id: 1 {"$v":2,"diff":{"u":{"name":"Grace"}}}"#)
+        );
+
+        let noop = doc! { "ts": ts, "op": "n", "ns": "", "o": { "msg": "periodic noop" } };
+        assert!(formatter.entry(&noop).ends_with("noop       periodic noop"));
+
+        let colored = OplogFormatter::new(true).entry(&insert);
+        assert!(colored.contains(&format!(
+            "{}insert {}",
+            AnsiColors::GREEN,
+            AnsiColors::RESET
+        )));
+
+        assert_eq!(truncate(&"x".repeat(300)).chars().count(), MAX_DETAIL_WIDTH);
+    }
+}
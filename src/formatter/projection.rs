@@ -0,0 +1,212 @@
+//! Client-side projection of displayed documents
+//!
+//! `db.users.find() :: .name, .age`, `|> pluck name, age` and the
+//! `.pluck("name", "age")` chain keep only the listed fields of each result
+//! document once it has been fetched; the query sent to the server is not
+//! changed. Fields are dotted paths, kept in the order they were listed, and a
+//! path through an array applies to every embedded document of the array.
+//! In table output the listed fields are the columns, in the same order.
+
+use bson::{Bson, Document};
+
+use crate::executor::ResultData;
+
+/// Keeps only the requested fields of result documents
+#[derive(Debug, Clone)]
+pub struct FieldProjection {
+    /// Requested fields split into path segments
+    paths: Vec<Vec<String>>,
+}
+
+impl FieldProjection {
+    /// Create a projection from field paths
+    ///
+    /// # Arguments
+    /// * `fields` - Dotted field paths, e.g. `name` or `profile.city`
+    ///
+    /// # Returns
+    /// * `Option<Self>` - None when no fields are given
+    pub fn new(fields: &[String]) -> Option<Self> {
+        let paths: Vec<Vec<String>> = fields
+            .iter()
+            .map(|field| field.trim().trim_start_matches('.'))
+            .filter(|field| !field.is_empty())
+            .map(|field| field.split('.').map(str::to_string).collect())
+            .collect();
+
+        if paths.is_empty() {
+            None
+        } else {
+            Some(Self { paths })
+        }
+    }
+
+    /// Requested fields as dotted paths, in the order they were listed
+    ///
+    /// # Returns
+    /// * `Vec<String>` - Field paths, used as the columns of table output
+    pub fn fields(&self) -> Vec<String> {
+        self.paths.iter().map(|path| path.join(".")).collect()
+    }
+
+    /// Project a single document
+    ///
+    /// # Arguments
+    /// * `document` - Document to project
+    ///
+    /// # Returns
+    /// * `Document` - Document holding only the requested fields
+    pub fn project_document(&self, document: &Document) -> Document {
+        let paths: Vec<&[String]> = self.paths.iter().map(Vec::as_slice).collect();
+        project(document, &paths)
+    }
+
+    /// Project the documents of a result
+    ///
+    /// # Arguments
+    /// * `data` - Result data to project
+    ///
+    /// # Returns
+    /// * `Option<ResultData>` - Projected copy, or None when the data holds no
+    ///   documents
+    pub fn project_data(&self, data: &ResultData) -> Option<ResultData> {
+        let project_all = |documents: &[Document]| -> Vec<Document> {
+            documents
+                .iter()
+                .map(|document| self.project_document(document))
+                .collect()
+        };
+
+        match data {
+            ResultData::Documents(documents) => Some(ResultData::Documents(project_all(documents))),
+            ResultData::DocumentsWithPagination {
+                documents,
+                has_more,
+                displayed,
+            } => Some(ResultData::DocumentsWithPagination {
+                documents: project_all(documents),
+                has_more: *has_more,
+                displayed: *displayed,
+            }),
+            ResultData::Document(document) => {
+                Some(ResultData::Document(self.project_document(document)))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Build a document from the fields at `paths`, grouped by first segment
+fn project(document: &Document, paths: &[&[String]]) -> Document {
+    let mut projected = Document::new();
+
+    for path in paths {
+        let Some((key, _)) = path.split_first() else {
+            continue;
+        };
+        if projected.contains_key(key) {
+            continue;
+        }
+        let Some(value) = document.get(key) else {
+            continue;
+        };
+
+        // All remaining paths below this field, or the whole field if one ends here
+        let tails: Vec<&[String]> = paths
+            .iter()
+            .filter(|other| other.first() == Some(key))
+            .map(|other| &other[1..])
+            .collect();
+
+        let value = if tails.iter().any(|tail| tail.is_empty()) {
+            Some(value.clone())
+        } else {
+            project_value(value, &tails)
+        };
+        if let Some(value) = value {
+            projected.insert(key.clone(), value);
+        }
+    }
+
+    projected
+}
+
+/// Descend into embedded documents and arrays of documents
+fn project_value(value: &Bson, paths: &[&[String]]) -> Option<Bson> {
+    match value {
+        Bson::Document(nested) => {
+            let nested = project(nested, paths);
+            (!nested.is_empty()).then_some(Bson::Document(nested))
+        }
+        Bson::Array(items) => Some(Bson::Array(
+            items
+                .iter()
+                .filter_map(|item| project_value(item, paths))
+                .collect(),
+        )),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bson::doc;
+
+    fn projection(fields: &[&str]) -> FieldProjection {
+        let fields: Vec<String> = fields.iter().map(|field| field.to_string()).collect();
+        FieldProjection::new(&fields).unwrap()
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(FieldProjection::new(&[]).is_none());
+        assert!(FieldProjection::new(&[" ".to_string(), ".".to_string()]).is_none());
+        assert_eq!(
+            projection(&[".age", "profile.city"]).fields(),
+            vec!["age", "profile.city"]
+        );
+    }
+
+    #[test]
+    fn test_project_document() {
+        let projection = projection(&[".age", "name", "profile.city", "orders.total"]);
+        let document = doc! {
+            "_id": 1,
+            "name": "Alice",
+            "age": 30,
+            "profile": { "city": "Oslo", "phone": "555" },
+            "orders": [{ "total": 5, "items": 2 }, { "items": 1 }, 3],
+        };
+        assert_eq!(
+            projection.project_document(&document),
+            doc! {
+                "age": 30,
+                "name": "Alice",
+                "profile": { "city": "Oslo" },
+                "orders": [{ "total": 5 }],
+            }
+        );
+        assert_eq!(projection.project_document(&doc! { "x": 1 }), doc! {});
+    }
+
+    #[test]
+    fn test_project_whole_and_nested_field() {
+        let projection = projection(&["profile.city", "profile"]);
+        let document = doc! { "profile": { "city": "Oslo", "phone": "555" } };
+        assert_eq!(projection.project_document(&document), document);
+    }
+
+    #[test]
+    fn test_project_data() {
+        let projection = projection(&["b"]);
+        let data = ResultData::Documents(vec![doc! { "a": 1, "b": 2 }]);
+        match projection.project_data(&data) {
+            Some(ResultData::Documents(documents)) => {
+                assert_eq!(documents, vec![doc! { "b": 2 }]);
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+        assert!(projection.project_data(&ResultData::Count(1)).is_none());
+    }
+}
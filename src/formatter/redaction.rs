@@ -0,0 +1,239 @@
+//! Masking of sensitive fields in displayed and exported documents
+//!
+//! Field patterns come from the `[redaction]` config section. A pattern is a
+//! dotted field path in which `*` stands for any number of path segments, so
+//! `*.password` masks `password` at any depth and `profile.*.phone` masks
+//! every `phone` below `profile`. A pattern without dots, such as `ssn`, is
+//! matched at any depth as well. Array elements share the path of their
+//! array, and names are compared case-insensitively.
+
+use bson::{Bson, Document};
+
+use crate::config::RedactionConfig;
+use crate::executor::ResultData;
+
+/// Masks the values of fields matching the configured patterns
+#[derive(Debug, Clone)]
+pub struct Redactor {
+    /// Patterns split into path segments
+    patterns: Vec<Vec<String>>,
+
+    /// Replacement for masked values
+    mask: String,
+}
+
+impl Redactor {
+    /// Create a redactor from configuration
+    ///
+    /// # Arguments
+    /// * `config` - Redaction configuration
+    ///
+    /// # Returns
+    /// * `Option<Self>` - None when redaction is disabled or has no patterns
+    pub fn from_config(config: &RedactionConfig) -> Option<Self> {
+        if !config.enabled {
+            return None;
+        }
+
+        let patterns: Vec<Vec<String>> = config
+            .fields
+            .iter()
+            .map(|pattern| pattern.trim())
+            .filter(|pattern| !pattern.is_empty())
+            .map(|pattern| {
+                let mut segments: Vec<String> = pattern
+                    .split('.')
+                    .map(|segment| segment.to_lowercase())
+                    .collect();
+                if segments.len() == 1 {
+                    segments.insert(0, "*".to_string());
+                }
+                segments
+            })
+            .collect();
+
+        if patterns.is_empty() {
+            None
+        } else {
+            Some(Self {
+                patterns,
+                mask: config.mask.clone(),
+            })
+        }
+    }
+
+    /// Mask matching fields of a document in place
+    ///
+    /// # Arguments
+    /// * `document` - Document to redact
+    pub fn redact_document(&self, document: &mut Document) {
+        self.redact_at(&mut Vec::new(), document);
+    }
+
+    /// Redact the documents of a result
+    ///
+    /// # Arguments
+    /// * `data` - Result data to redact
+    ///
+    /// # Returns
+    /// * `Option<ResultData>` - Redacted copy, or None when the data holds no
+    ///   documents
+    pub fn redact_data(&self, data: &ResultData) -> Option<ResultData> {
+        let redact_all = |documents: &[Document]| -> Vec<Document> {
+            documents
+                .iter()
+                .map(|document| {
+                    let mut document = document.clone();
+                    self.redact_document(&mut document);
+                    document
+                })
+                .collect()
+        };
+
+        match data {
+            ResultData::Documents(documents) => Some(ResultData::Documents(redact_all(documents))),
+            ResultData::DocumentsWithPagination {
+                documents,
+                has_more,
+                displayed,
+            } => Some(ResultData::DocumentsWithPagination {
+                documents: redact_all(documents),
+                has_more: *has_more,
+                displayed: *displayed,
+            }),
+            ResultData::Document(document) => {
+                let mut document = document.clone();
+                self.redact_document(&mut document);
+                Some(ResultData::Document(document))
+            }
+            _ => None,
+        }
+    }
+
+    /// Whether a field path matches any pattern
+    ///
+    /// # Arguments
+    /// * `path` - Lowercased path segments
+    ///
+    /// # Returns
+    /// * `bool` - True if the field must be masked
+    fn is_sensitive(&self, path: &[String]) -> bool {
+        self.patterns
+            .iter()
+            .any(|pattern| segments_match(pattern, path))
+    }
+
+    /// Walk a document below `path`, masking matching fields
+    fn redact_at(&self, path: &mut Vec<String>, document: &mut Document) {
+        for (key, value) in document.iter_mut() {
+            path.push(key.to_lowercase());
+            if self.is_sensitive(path) {
+                *value = Bson::String(self.mask.clone());
+            } else {
+                self.redact_value(path, value);
+            }
+            path.pop();
+        }
+    }
+
+    /// Descend into embedded documents and arrays
+    fn redact_value(&self, path: &mut Vec<String>, value: &mut Bson) {
+        match value {
+            Bson::Document(nested) => self.redact_at(path, nested),
+            Bson::Array(items) => {
+                for item in items {
+                    self.redact_value(path, item);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Match path segments against pattern segments, `*` spanning any number
+fn segments_match(pattern: &[String], path: &[String]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((first, rest)) if first == "*" => {
+            segments_match(rest, path) || (!path.is_empty() && segments_match(pattern, &path[1..]))
+        }
+        Some((first, rest)) => path
+            .split_first()
+            .is_some_and(|(segment, tail)| segment == first && segments_match(rest, tail)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bson::doc;
+
+    fn redactor(fields: &[&str]) -> Redactor {
+        Redactor::from_config(&RedactionConfig {
+            fields: fields.iter().map(|field| field.to_string()).collect(),
+            ..RedactionConfig::default()
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_from_config() {
+        assert!(Redactor::from_config(&RedactionConfig::default()).is_none());
+        assert!(
+            Redactor::from_config(&RedactionConfig {
+                fields: vec!["ssn".to_string()],
+                enabled: false,
+                ..RedactionConfig::default()
+            })
+            .is_none()
+        );
+    }
+
+    #[test]
+    fn test_redact_document() {
+        let redactor = redactor(&["*.password", "ssn", "profile.email"]);
+        let mut document = doc! {
+            "name": "Alice",
+            "password": "hunter2",
+            "SSN": "123-45-6789",
+            "profile": { "email": "a@example.com", "city": "Oslo" },
+            "accounts": [{ "login": "alice", "password": "x" }, 3],
+            "contact": { "email": "kept@example.com" },
+        };
+        redactor.redact_document(&mut document);
+        assert_eq!(
+            document,
+            doc! {
+                "name": "Alice",
+                "password": "***",
+                "SSN": "***",
+                "profile": { "email": "***", "city": "Oslo" },
+                "accounts": [{ "login": "alice", "password": "***" }, 3],
+                "contact": { "email": "kept@example.com" },
+            }
+        );
+    }
+
+    #[test]
+    fn test_redact_data() {
+        let redactor = redactor(&["secret"]);
+        let data = ResultData::Documents(vec![doc! { "secret": { "a": 1 }, "b": 2 }]);
+        match redactor.redact_data(&data) {
+            Some(ResultData::Documents(documents)) => {
+                assert_eq!(documents, vec![doc! { "secret": "***", "b": 2 }]);
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+        assert!(redactor.redact_data(&ResultData::Count(1)).is_none());
+    }
+
+    #[test]
+    fn test_segments_match() {
+        let segments = |s: &str| s.split('.').map(str::to_string).collect::<Vec<_>>();
+        assert!(segments_match(&segments("*.b"), &segments("b")));
+        assert!(segments_match(&segments("*.b"), &segments("a.x.b")));
+        assert!(segments_match(&segments("a.*.c"), &segments("a.b.c")));
+        assert!(!segments_match(&segments("a.b"), &segments("x.a.b")));
+        assert!(!segments_match(&segments("a.*"), &segments("b.c")));
+    }
+}
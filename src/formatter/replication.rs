@@ -0,0 +1,171 @@
+//! Replication lag tables for `rs.lag`
+//!
+//! This module renders the members of a `replSetGetStatus` result as an
+//! aligned table of state and lag behind the primary. Lag is colored with
+//! the same thresholds as the replication check of `clusterReport()`.
+
+use super::cluster_report::{Health, MemberLag};
+use super::colorizer::AnsiColors;
+
+/// Formatter for replication lag tables
+pub struct ReplicationLagFormatter {
+    /// Color lag and state by health
+    use_colors: bool,
+}
+
+impl ReplicationLagFormatter {
+    /// Create a new replication lag formatter
+    ///
+    /// # Arguments
+    /// * `use_colors` - Color lag and state by health
+    pub fn new(use_colors: bool) -> Self {
+        Self { use_colors }
+    }
+
+    /// Render members as a table
+    ///
+    /// # Arguments
+    /// * `set` - Replica set name
+    /// * `members` - Members from `MemberLag::from_status`
+    ///
+    /// # Returns
+    /// * `String` - Heading and one row per member
+    pub fn format(&self, set: &str, members: &[MemberLag]) -> String {
+        if members.is_empty() {
+            return format!("Replica set {} has no members", set);
+        }
+
+        let name_width = members
+            .iter()
+            .map(|m| m.name.len())
+            .max()
+            .unwrap_or(0)
+            .max("Member".len());
+        let state_width = members
+            .iter()
+            .map(|m| m.state.len())
+            .max()
+            .unwrap_or(0)
+            .max("State".len());
+
+        let mut lines = vec![
+            format!("Replica set {}", set),
+            format!(
+                "{:<name_width$}  {:<state_width$}  Lag",
+                "Member",
+                "State",
+                name_width = name_width,
+                state_width = state_width
+            ),
+        ];
+        for member in members {
+            let health = member.health();
+            let lag = match member.lag_secs {
+                Some(lag) => self.paint(health, &format!("{}s", lag)),
+                None => "-".to_string(),
+            };
+            // A critical member without lag is down or in a bad state
+            let state = format!("{:<width$}", member.state, width = state_width);
+            let state = if !member.healthy || (health == Health::Critical && lag == "-") {
+                self.paint(Health::Critical, &state)
+            } else {
+                state
+            };
+            lines.push(format!(
+                "{:<name_width$}  {}  {}",
+                member.name,
+                state,
+                lag,
+                name_width = name_width
+            ));
+        }
+        lines.join("\n")
+    }
+
+    /// Describe the member exceeding a lag limit, if any
+    ///
+    /// # Arguments
+    /// * `members` - Members from `MemberLag::from_status`
+    /// * `max_lag` - Largest acceptable lag in seconds
+    ///
+    /// # Returns
+    /// * `Option<String>` - Alert for the member furthest behind, if over the limit
+    pub fn alert(members: &[MemberLag], max_lag: u64) -> Option<String> {
+        let worst = MemberLag::worst(members)?;
+        let lag = worst.lag_secs?;
+        (lag > i64::try_from(max_lag).unwrap_or(i64::MAX)).then(|| {
+            format!(
+                "Replication lag of {}s on {} exceeds --max-lag {}s",
+                lag, worst.name, max_lag
+            )
+        })
+    }
+
+    /// Wrap text in the color of a rating, when enabled
+    fn paint(&self, health: Health, text: &str) -> String {
+        if self.use_colors {
+            format!("{}{}{}", health.color(), text, AnsiColors::RESET)
+        } else {
+            text.to_string()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn member(name: &str, state: &str, lag_secs: Option<i64>) -> MemberLag {
+        MemberLag {
+            name: name.to_string(),
+            state: state.to_string(),
+            healthy: true,
+            lag_secs,
+        }
+    }
+
+    #[test]
+    fn test_format_lag_table() {
+        let members = vec![
+            member("db1:27017", "PRIMARY", None),
+            member("db2:27017", "SECONDARY", Some(3)),
+            member("db3:27017", "SECONDARY", Some(42)),
+        ];
+
+        let plain = ReplicationLagFormatter::new(false).format("rs0", &members);
+        let lines: Vec<&str> = plain.lines().collect();
+        assert_eq!(lines[0], "Replica set rs0");
+        assert_eq!(lines[1], "Member     State      Lag");
+        assert_eq!(lines[2], "db1:27017  PRIMARY    -");
+        assert_eq!(lines[4], "db3:27017  SECONDARY  42s");
+
+        let colored = ReplicationLagFormatter::new(true).format("rs0", &members);
+        assert!(colored.contains(&format!("{}42s{}", AnsiColors::YELLOW, AnsiColors::RESET)));
+        assert!(colored.contains(&format!("{}3s{}", AnsiColors::GREEN, AnsiColors::RESET)));
+
+        let down = MemberLag {
+            healthy: false,
+            ..member("db4:27017", "(not reachable/healthy)", None)
+        };
+        let colored = ReplicationLagFormatter::new(true).format("rs0", &[down]);
+        assert!(colored.contains(&format!(
+            "{}(not reachable/healthy){}",
+            AnsiColors::RED,
+            AnsiColors::RESET
+        )));
+    }
+
+    #[test]
+    fn test_alert() {
+        let members = vec![
+            member("db1:27017", "PRIMARY", None),
+            member("db2:27017", "SECONDARY", Some(31)),
+        ];
+        assert_eq!(
+            ReplicationLagFormatter::alert(&members, 30).as_deref(),
+            Some("Replication lag of 31s on db2:27017 exceeds --max-lag 30s")
+        );
+        assert!(ReplicationLagFormatter::alert(&members, 31).is_none());
+        assert!(ReplicationLagFormatter::alert(&members[..1], 0).is_none());
+    }
+}
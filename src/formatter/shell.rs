@@ -5,10 +5,13 @@
 //! - Pretty-printed nested documents and arrays
 //! - Optional color highlighting for different value types
 //! - Indentation support for readable output
+//! - Collapsing of deep or wide nested values (`{…} (32 keys)`, `[… 500 items]`)
+//! - Previews of long strings and large BinData (`BinData(0, 2.40 MiB)`)
+//! - ISODate values in the configured time zone and format
 
-use mongodb::bson::{Bson, Document};
+use bson::{Bson, Document};
 
-use super::bson_utils::{BsonConverter, ShellStyleConverter};
+use super::bson_utils::{BsonConverter, DateDisplay, ShellStyleConverter};
 use super::colorizer::Colorizer;
 
 /// Shell-style formatter (mongosh compatible)
@@ -39,6 +42,46 @@ impl ShellFormatter {
         }
     }
 
+    /// Collapse nested documents and arrays that are too deep or too wide
+    ///
+    /// # Arguments
+    /// * `depth` - Nesting level from which values are collapsed (0 = never)
+    /// * `width` - Entries above which values are collapsed (0 = never)
+    ///
+    /// # Returns
+    /// * `Self` - Formatter showing collapsed values as a one-line summary
+    pub fn with_collapse(mut self, depth: usize, width: usize) -> Self {
+        self.converter = self.converter.with_collapse(depth, width);
+        self
+    }
+
+    /// Shorten long strings and binary data to a preview
+    ///
+    /// # Arguments
+    /// * `max_string_length` - Characters shown of a string before `…` (0 = all)
+    /// * `max_binary_length` - Bytes above which BinData shows only its size (0 = never)
+    ///
+    /// # Returns
+    /// * `Self` - Formatter showing previews of large values
+    pub fn with_previews(mut self, max_string_length: usize, max_binary_length: usize) -> Self {
+        self.converter = self
+            .converter
+            .with_previews(max_string_length, max_binary_length);
+        self
+    }
+
+    /// Show ISODate values in another time zone or format
+    ///
+    /// # Arguments
+    /// * `dates` - Time zone and format from the display settings
+    ///
+    /// # Returns
+    /// * `Self` - Formatter rendering dates with `dates`
+    pub fn with_dates(mut self, dates: DateDisplay) -> Self {
+        self.converter = self.converter.with_dates(dates);
+        self
+    }
+
     /// Format a BSON document in shell style
     ///
     /// # Arguments
@@ -89,7 +132,6 @@ impl ShellFormatter {
     ///
     /// # Returns
     /// * `String` - Formatted value
-    #[allow(dead_code)]
     pub fn format_value(&self, value: &Bson) -> String {
         self.converter.convert(value)
     }
@@ -98,11 +140,11 @@ impl ShellFormatter {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use mongodb::bson::doc;
+    use bson::doc;
 
     #[test]
     fn test_shell_formatter_objectid() {
-        use mongodb::bson::oid::ObjectId;
+        use bson::oid::ObjectId;
         let formatter = ShellFormatter::new(false);
         let oid = ObjectId::parse_str("65705d84dfc3f3b5094e1f72").unwrap();
         let doc = doc! { "_id": oid };
@@ -113,7 +155,7 @@ mod tests {
 
     #[test]
     fn test_shell_formatter_datetime() {
-        use mongodb::bson::DateTime;
+        use bson::DateTime;
         let formatter = ShellFormatter::new(false);
         let dt = DateTime::from_millis(1701862788373);
         let doc = doc! { "created_time": dt };
@@ -123,6 +165,16 @@ mod tests {
         assert!(result.contains("2023-12-06"));
     }
 
+    #[test]
+    fn test_shell_formatter_datetime_timezone() {
+        use bson::DateTime;
+        let dates = DateDisplay::new("America/New_York", "").unwrap();
+        let formatter = ShellFormatter::new(false).with_dates(dates);
+        let doc = doc! { "created_time": DateTime::from_millis(1701862788373) };
+        let result = formatter.format_document(&doc);
+        assert!(result.contains("ISODate('2023-12-06T06:39:48.373-05:00')"));
+    }
+
     #[test]
     fn test_shell_formatter_long() {
         let formatter = ShellFormatter::new(false);
@@ -176,4 +228,51 @@ mod tests {
         assert!(result.contains("'rust'"));
         assert!(result.contains("'mongodb'"));
     }
+
+    #[test]
+    fn test_shell_formatter_collapse() {
+        let formatter = ShellFormatter::new(false).with_collapse(1, 0);
+        let doc = doc! {
+            "name": "dalei",
+            "address": { "city": "Berlin", "zip": "10115" },
+            "tags": ["rust", "mongodb", "cli"],
+            "empty": [],
+        };
+        let result = formatter.format_document(&doc);
+        assert!(result.contains("name: 'dalei'"));
+        assert!(result.contains("address: {…} (2 keys)"));
+        assert!(result.contains("tags: [… 3 items]"));
+        assert!(result.contains("empty: []"));
+        assert!(!result.contains("Berlin"));
+    }
+
+    #[test]
+    fn test_shell_formatter_previews() {
+        use bson::Binary;
+        use bson::spec::BinarySubtype;
+
+        let formatter = ShellFormatter::new(false).with_previews(5, 16);
+        let small = Binary {
+            subtype: BinarySubtype::Generic,
+            bytes: vec![1; 16],
+        };
+        let photo = Binary {
+            subtype: BinarySubtype::Generic,
+            bytes: vec![0; 3 * 1024 * 1024],
+        };
+        let doc = doc! {
+            "bio": "abcdefghij",
+            "nick": "abcde",
+            "small": small,
+            "photo": photo,
+        };
+        let result = formatter.format_document(&doc);
+        assert!(result.contains("bio: 'abcde…'"));
+        assert!(result.contains("nick: 'abcde'"));
+        assert!(result.contains("small: BinData(0, '01010101"));
+        assert!(result.contains("photo: BinData(0, 3.00 MiB)"));
+
+        let full = ShellFormatter::new(false).format_document(&doc);
+        assert!(full.contains("'abcdefghij'"));
+    }
 }
@@ -0,0 +1,267 @@
+//! Output destinations for formatted results
+//!
+//! An [`OutputSink`] receives the text produced by the [`Formatter`] and
+//! decides where it goes. The shell prints to a [`TerminalSink`]; library
+//! users and server modes can collect output in a [`BufferSink`], append it to
+//! a [`FileSink`] or forward it to a WebSocket connection through a
+//! [`WebSocketSink`].
+//!
+//! Sinks that are not a terminal receive plain text: ANSI color sequences are
+//! stripped before writing, so colored output never ends up in files or
+//! client messages.
+//!
+//! [`Formatter`]: super::Formatter
+
+use std::fs::{File, OpenOptions};
+use std::io::{self, IsTerminal, Write};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+#[cfg(feature = "native")]
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::error::{MongoshError, Result};
+
+/// Destination for formatted output
+pub trait OutputSink: Send {
+    /// Write text exactly as given
+    ///
+    /// # Arguments
+    /// * `text` - Text to write
+    fn write_str(&mut self, text: &str) -> Result<()>;
+
+    /// Whether the sink renders ANSI colors
+    fn is_terminal(&self) -> bool {
+        false
+    }
+
+    /// Flush buffered output
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Write one block of output followed by a newline
+    ///
+    /// Colors are stripped unless the sink is a terminal.
+    ///
+    /// # Arguments
+    /// * `text` - Formatted output, possibly colored
+    fn write_output(&mut self, text: &str) -> Result<()> {
+        let mut line = if self.is_terminal() {
+            text.to_string()
+        } else {
+            strip_ansi(text)
+        };
+        line.push('\n');
+        self.write_str(&line)?;
+        self.flush()
+    }
+}
+
+/// Standard output or standard error
+#[derive(Debug)]
+pub struct TerminalSink {
+    /// Write to stderr instead of stdout
+    stderr: bool,
+}
+
+impl TerminalSink {
+    /// Sink writing to standard output
+    pub fn stdout() -> Self {
+        Self { stderr: false }
+    }
+
+    /// Sink writing to standard error
+    pub fn stderr() -> Self {
+        Self { stderr: true }
+    }
+}
+
+impl OutputSink for TerminalSink {
+    fn write_str(&mut self, text: &str) -> Result<()> {
+        if self.stderr {
+            io::stderr().lock().write_all(text.as_bytes())?;
+        } else {
+            io::stdout().lock().write_all(text.as_bytes())?;
+        }
+        Ok(())
+    }
+
+    fn is_terminal(&self) -> bool {
+        if self.stderr {
+            io::stderr().is_terminal()
+        } else {
+            io::stdout().is_terminal()
+        }
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        if self.stderr {
+            io::stderr().flush()?;
+        } else {
+            io::stdout().flush()?;
+        }
+        Ok(())
+    }
+}
+
+/// File output, appended to what the file already holds
+#[derive(Debug)]
+pub struct FileSink {
+    file: File,
+}
+
+impl FileSink {
+    /// Open a file for appending, creating it if needed
+    ///
+    /// # Arguments
+    /// * `path` - File to write to
+    ///
+    /// # Returns
+    /// * `Result<Self>` - Sink or the error opening the file
+    pub fn append(path: &Path) -> Result<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| {
+                MongoshError::Generic(format!(
+                    "Failed to open output file '{}': {}",
+                    path.display(),
+                    e
+                ))
+            })?;
+        Ok(Self { file })
+    }
+}
+
+impl OutputSink for FileSink {
+    fn write_str(&mut self, text: &str) -> Result<()> {
+        self.file.write_all(text.as_bytes())?;
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.file.flush()?;
+        Ok(())
+    }
+}
+
+/// In-memory output
+///
+/// Clones share the same buffer, so one clone can be handed to code that
+/// writes while another reads the result.
+#[derive(Debug, Clone, Default)]
+pub struct BufferSink {
+    buffer: Arc<Mutex<String>>,
+}
+
+impl BufferSink {
+    /// Create an empty buffer
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Everything written so far
+    pub fn contents(&self) -> String {
+        self.buffer.lock().unwrap().clone()
+    }
+
+    /// Take everything written so far, leaving the buffer empty
+    pub fn take(&self) -> String {
+        std::mem::take(&mut *self.buffer.lock().unwrap())
+    }
+}
+
+impl OutputSink for BufferSink {
+    fn write_str(&mut self, text: &str) -> Result<()> {
+        self.buffer.lock().unwrap().push_str(text);
+        Ok(())
+    }
+}
+
+/// Output for a WebSocket connection
+///
+/// Each write becomes one message on the channel; the task owning the
+/// connection sends them to the client as text frames.
+#[cfg(feature = "native")]
+#[derive(Debug, Clone)]
+pub struct WebSocketSink {
+    sender: UnboundedSender<String>,
+}
+
+#[cfg(feature = "native")]
+impl WebSocketSink {
+    /// Create a sink feeding a connection's outgoing message channel
+    ///
+    /// # Arguments
+    /// * `sender` - Channel read by the connection task
+    pub fn new(sender: UnboundedSender<String>) -> Self {
+        Self { sender }
+    }
+}
+
+#[cfg(feature = "native")]
+impl OutputSink for WebSocketSink {
+    fn write_str(&mut self, text: &str) -> Result<()> {
+        self.sender
+            .send(text.to_string())
+            .map_err(|_| MongoshError::Generic("WebSocket connection closed".to_string()))
+    }
+}
+
+/// Remove ANSI escape sequences such as colors
+pub fn strip_ansi(text: &str) -> String {
+    let mut plain = String::with_capacity(text.len());
+    let mut chars = text.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\u{1b}' {
+            plain.push(c);
+            continue;
+        }
+        // CSI sequences end with a letter, e.g. ESC [ 1 ; 3 2 m
+        if chars.next() == Some('[') {
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        }
+    }
+
+    plain
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strip_ansi() {
+        assert_eq!(strip_ansi("\u{1b}[1;32mok\u{1b}[0m done"), "ok done");
+        assert_eq!(strip_ansi("plain"), "plain");
+    }
+
+    #[test]
+    fn test_buffer_sink_strips_colors() {
+        let sink = BufferSink::new();
+        let mut writer = sink.clone();
+        writer.write_output("\u{1b}[32m'Ada'\u{1b}[0m").unwrap();
+        writer.write_output("36").unwrap();
+        assert_eq!(sink.take(), "'Ada'\n36\n");
+        assert!(sink.contents().is_empty());
+    }
+
+    #[cfg(feature = "native")]
+    #[test]
+    fn test_websocket_sink() {
+        let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+        let mut sink = WebSocketSink::new(sender);
+        sink.write_output("\u{1b}[1mdone\u{1b}[0m").unwrap();
+        assert_eq!(receiver.try_recv().unwrap(), "done\n");
+
+        drop(receiver);
+        assert!(sink.write_output("lost").is_err());
+    }
+}
@@ -0,0 +1,210 @@
+//! Storage size formatting for `storageReport()`
+//!
+//! This module renders the sizes gathered from `dbStats` and `collStats` as a
+//! tree of databases and their collections, largest first, with data, index
+//! and on-disk storage sizes in aligned columns.
+
+use bson::Document;
+
+use super::bson_utils::number_of;
+use super::listing::format_size;
+
+/// Data, index and storage sizes in bytes
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct StorageSizes {
+    /// Uncompressed size of the documents
+    pub data: u64,
+
+    /// Size of all indexes
+    pub index: u64,
+
+    /// Space allocated for the documents on disk
+    pub storage: u64,
+}
+
+impl StorageSizes {
+    /// Read sizes from a `dbStats` result
+    pub fn from_db_stats(stats: &Document) -> Self {
+        Self {
+            data: number_of(stats.get("dataSize")).max(0) as u64,
+            index: number_of(stats.get("indexSize")).max(0) as u64,
+            storage: number_of(stats.get("storageSize")).max(0) as u64,
+        }
+    }
+
+    /// Read sizes from a `collStats` result
+    pub fn from_coll_stats(stats: &Document) -> Self {
+        Self {
+            data: number_of(stats.get("size")).max(0) as u64,
+            index: number_of(stats.get("totalIndexSize")).max(0) as u64,
+            storage: number_of(stats.get("storageSize")).max(0) as u64,
+        }
+    }
+
+    /// Space used on disk by documents and indexes together
+    fn total(&self) -> u64 {
+        self.storage + self.index
+    }
+}
+
+/// Sizes of one database and its collections
+#[derive(Debug, Clone, PartialEq)]
+pub struct DatabaseStorage {
+    /// Database name
+    pub name: String,
+
+    /// Database totals
+    pub sizes: StorageSizes,
+
+    /// Sizes per collection
+    pub collections: Vec<(String, StorageSizes)>,
+
+    /// Why the database could not be measured, e.g. missing privileges
+    pub error: Option<String>,
+}
+
+/// Formatter for storage reports
+pub struct StorageReportFormatter;
+
+impl StorageReportFormatter {
+    /// Render databases and collections as a tree, largest first
+    ///
+    /// Entries are ordered by storage plus index size, the space they take
+    /// on disk.
+    ///
+    /// # Arguments
+    /// * `databases` - Sizes of every database
+    ///
+    /// # Returns
+    /// * `String` - Aligned tree followed by a total line
+    pub fn format(mut databases: Vec<DatabaseStorage>) -> String {
+        if databases.is_empty() {
+            return "No databases found".to_string();
+        }
+
+        databases.sort_by(|a, b| {
+            b.sizes
+                .total()
+                .cmp(&a.sizes.total())
+                .then(a.name.cmp(&b.name))
+        });
+
+        for db in &mut databases {
+            db.collections
+                .sort_by(|a, b| b.1.total().cmp(&a.1.total()).then(a.0.cmp(&b.0)));
+        }
+
+        let mut rows: Vec<(String, Option<StorageSizes>, Option<&str>)> = Vec::new();
+        let mut total = StorageSizes::default();
+        for db in &databases {
+            total.data += db.sizes.data;
+            total.index += db.sizes.index;
+            total.storage += db.sizes.storage;
+
+            match &db.error {
+                Some(error) => rows.push((db.name.clone(), None, Some(error.as_str()))),
+                None => rows.push((db.name.clone(), Some(db.sizes), None)),
+            }
+            let count = db.collections.len();
+            for (i, (name, sizes)) in db.collections.iter().enumerate() {
+                let branch = if i + 1 == count { "└─" } else { "├─" };
+                rows.push((format!("{} {}", branch, name), Some(*sizes), None));
+            }
+        }
+        rows.push(("Total".to_string(), Some(total), None));
+
+        let width = rows
+            .iter()
+            .map(|(name, _, _)| name.chars().count())
+            .max()
+            .unwrap_or(0)
+            .max("Database / collection".len());
+        let mut lines = vec![format!(
+            "{:<width$}  {:>12}  {:>12}  {:>12}",
+            "Database / collection", "Data", "Index", "Storage"
+        )];
+        for (name, sizes, error) in rows {
+            let padding = width - name.chars().count();
+            let line = match (sizes, error) {
+                (_, Some(error)) => format!("{}{}  ({})", name, " ".repeat(padding), error),
+                (Some(sizes), None) => format!(
+                    "{}{}  {:>12}  {:>12}  {:>12}",
+                    name,
+                    " ".repeat(padding),
+                    format_size(sizes.data),
+                    format_size(sizes.index),
+                    format_size(sizes.storage)
+                ),
+                (None, None) => name,
+            };
+            lines.push(line);
+        }
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bson::doc;
+
+    fn sizes(data: u64, index: u64, storage: u64) -> StorageSizes {
+        StorageSizes {
+            data,
+            index,
+            storage,
+        }
+    }
+
+    #[test]
+    fn test_sizes_from_stats() {
+        let db = StorageSizes::from_db_stats(
+            &doc! { "dataSize": 2048.0, "indexSize": 1024_i64, "storageSize": 4096 },
+        );
+        assert_eq!(db, sizes(2048, 1024, 4096));
+
+        let coll = StorageSizes::from_coll_stats(
+            &doc! { "size": 10, "totalIndexSize": 20, "storageSize": 30 },
+        );
+        assert_eq!(coll, sizes(10, 20, 30));
+    }
+
+    #[test]
+    fn test_format_tree_sorted_descending() {
+        let output = StorageReportFormatter::format(vec![
+            DatabaseStorage {
+                name: "small".to_string(),
+                sizes: sizes(1024, 0, 1024),
+                collections: vec![("logs".to_string(), sizes(1024, 0, 1024))],
+                error: None,
+            },
+            DatabaseStorage {
+                name: "shop".to_string(),
+                sizes: sizes(3072, 2048, 4096),
+                collections: vec![
+                    ("customers".to_string(), sizes(1024, 1024, 1024)),
+                    ("orders".to_string(), sizes(2048, 1024, 3072)),
+                ],
+                error: None,
+            },
+            DatabaseStorage {
+                name: "secret".to_string(),
+                sizes: StorageSizes::default(),
+                collections: Vec::new(),
+                error: Some("not authorized".to_string()),
+            },
+        ]);
+
+        let lines: Vec<&str> = output.lines().collect();
+        assert!(lines[0].starts_with("Database / collection"));
+        assert!(lines[1].starts_with("shop "));
+        assert!(lines[1].ends_with("4.00 KiB"));
+        assert!(lines[2].starts_with("├─ orders"));
+        assert!(lines[3].starts_with("└─ customers"));
+        assert!(lines[4].starts_with("small"));
+        assert!(lines[5].starts_with("└─ logs"));
+        assert!(lines[6].starts_with("secret") && lines[6].ends_with("(not authorized)"));
+        assert!(lines[7].starts_with("Total"));
+        assert!(lines[7].ends_with("5.00 KiB"));
+    }
+}
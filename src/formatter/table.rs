@@ -2,14 +2,17 @@
 //!
 //! This module provides table-style formatting for displaying multiple documents:
 //! - Builder pattern for dynamic schema support
-//! - Automatic column extraction from all documents
+//! - Automatic column extraction from all documents, or an explicit column
+//!   list such as `name, address.city` from `.table([...])` / `--columns`
 //! - BSON type handling with shell-style wrappers
 //! - Configurable styles and width limits
 //! - Nested document and array support
+//! - Optional summary footer (`display.table_summary`) with the count and
+//!   distinct count of each column, and sum/average of numeric columns
 
-#[cfg(test)]
-use mongodb::bson::Bson;
-use mongodb::bson::Document;
+use std::collections::HashSet;
+
+use bson::{Bson, Document};
 use tabled::{
     Table,
     builder::Builder,
@@ -40,6 +43,12 @@ pub struct TableFormatter {
 
     /// Converter for BSON values
     converter: CompactConverter,
+
+    /// Columns to show in this order; the union of all fields when `None`
+    columns: Option<Vec<String>>,
+
+    /// Append a footer row of column aggregates
+    summary: bool,
 }
 
 impl TableFormatter {
@@ -53,9 +62,36 @@ impl TableFormatter {
             max_table_width: DEFAULT_MAX_TABLE_WIDTH,
             use_colors: false,
             converter: CompactConverter::new(),
+            columns: None,
+            summary: false,
         }
     }
 
+    /// Append a footer row with the aggregates of each column
+    ///
+    /// # Arguments
+    /// * `summary` - Show count and distinct count of every column, and sum
+    ///   and average of numeric columns
+    ///
+    /// # Returns
+    /// * `Self` - Formatter with or without the summary footer
+    pub fn with_summary(mut self, summary: bool) -> Self {
+        self.summary = summary;
+        self
+    }
+
+    /// Show these columns in this order instead of every field
+    ///
+    /// # Arguments
+    /// * `columns` - Field names or dotted paths, e.g. `address.city`
+    ///
+    /// # Returns
+    /// * `Self` - Formatter with explicit columns; unchanged for an empty list
+    pub fn with_columns(mut self, columns: &[String]) -> Self {
+        self.columns = (!columns.is_empty()).then(|| columns.to_vec());
+        self
+    }
+
     /// Format result data as table
     ///
     /// # Arguments
@@ -79,6 +115,16 @@ impl TableFormatter {
             }
             ResultData::Document(doc) => self.format_documents(&[doc.clone()]),
             ResultData::Message(msg) => Ok(msg.clone()),
+            ResultData::Indexes(_)
+            | ResultData::Databases(_)
+            | ResultData::Collections(_)
+            | ResultData::Users(_) => {
+                let docs = data.to_documents().unwrap_or_default();
+                if docs.is_empty() {
+                    return Ok("(empty result set)".to_string());
+                }
+                self.format_documents(&docs)
+            }
             _ => Ok(format!("{:?}", data)),
         }
     }
@@ -91,8 +137,11 @@ impl TableFormatter {
     /// # Returns
     /// * `Result<String>` - Table string
     fn format_documents(&self, docs: &[Document]) -> Result<String> {
-        // Extract all unique field names
-        let fields = self.extract_field_names(docs);
+        // Explicit columns, or all unique field names
+        let fields = match &self.columns {
+            Some(columns) => columns.clone(),
+            None => self.extract_field_names(docs),
+        };
 
         if fields.is_empty() {
             return Ok("(no fields found)".to_string());
@@ -113,6 +162,15 @@ impl TableFormatter {
             builder.push_record(row);
         }
 
+        // Add the summary footer
+        if self.summary {
+            let footer: Vec<String> = fields
+                .iter()
+                .map(|field| self.summarize_column(docs, field))
+                .collect();
+            builder.push_record(footer);
+        }
+
         // Build and style the table
         let mut table = builder.build();
 
@@ -132,6 +190,9 @@ impl TableFormatter {
         // Apply colorization if enabled
         if self.use_colors {
             table.modify(Rows::first(), Color::FG_CYAN | Color::BOLD);
+            if self.summary {
+                table.modify(Rows::last(), Color::FG_YELLOW);
+            }
         }
 
         Ok(table.to_string())
@@ -168,17 +229,60 @@ impl TableFormatter {
     ///
     /// # Arguments
     /// * `doc` - Document containing the field
-    /// * `field` - Field name to extract
+    /// * `field` - Field name or dotted path to extract
     ///
     /// # Returns
     /// * `String` - Formatted field value
     fn format_field_value(&self, doc: &Document, field: &str) -> String {
-        match doc.get(field) {
-            Some(value) => self.converter.convert(value),
+        match field_value(doc, field) {
+            Some(value) => self.converter.convert(&value),
             None => String::from(""),
         }
     }
 
+    /// Summarize a column for the footer
+    ///
+    /// # Arguments
+    /// * `docs` - Documents shown in the table
+    /// * `field` - Field name or dotted path of the column
+    ///
+    /// # Returns
+    /// * `String` - Count and distinct count of the non-null values, plus sum
+    ///   and average when every value is a number
+    fn summarize_column(&self, docs: &[Document], field: &str) -> String {
+        let values: Vec<Bson> = docs
+            .iter()
+            .filter_map(|doc| field_value(doc, field))
+            .filter(|value| !matches!(value, Bson::Null | Bson::Undefined))
+            .collect();
+        let distinct: HashSet<String> = values.iter().map(Bson::to_string).collect();
+
+        let mut lines = vec![
+            format!("count: {}", values.len()),
+            format!("distinct: {}", distinct.len()),
+        ];
+
+        let numbers: Option<Vec<f64>> = values
+            .iter()
+            .map(|value| match value {
+                Bson::Int32(n) => Some(*n as f64),
+                Bson::Int64(n) => Some(*n as f64),
+                Bson::Double(f) => Some(*f),
+                _ => None,
+            })
+            .collect();
+        if let Some(numbers) = numbers.filter(|numbers| !numbers.is_empty()) {
+            let sum: f64 = numbers.iter().sum();
+            lines.push(format!("sum: {}", format_aggregate(sum)));
+            lines.push(format!(
+                "avg: {}",
+                format_aggregate(sum / numbers.len() as f64)
+            ));
+        }
+
+        lines.join("\n")
+    }
+
     /// Apply table style
     ///
     /// # Arguments
@@ -188,6 +292,56 @@ impl TableFormatter {
     }
 }
 
+/// Value of a field, looking up dotted paths in embedded documents
+fn field_value(doc: &Document, field: &str) -> Option<Bson> {
+    if let Some(value) = doc.get(field) {
+        return Some(value.clone());
+    }
+    let (key, rest) = field.split_once('.')?;
+    value_at_path(doc.get(key)?, rest)
+}
+
+/// Format a sum or average, with at most two decimals
+fn format_aggregate(value: f64) -> String {
+    if value.fract() == 0.0 && value.abs() < 1e15 {
+        format!("{:.0}", value)
+    } else {
+        let rounded = format!("{:.2}", value);
+        rounded
+            .trim_end_matches('0')
+            .trim_end_matches('.')
+            .to_string()
+    }
+}
+
+/// Value at a dotted path below `value`
+///
+/// A path through an array collects the value from each embedded document,
+/// e.g. `items.sku` gives the SKUs of all items.
+fn value_at_path(value: &Bson, path: &str) -> Option<Bson> {
+    let (key, rest) = match path.split_once('.') {
+        Some((key, rest)) => (key, Some(rest)),
+        None => (path, None),
+    };
+    match value {
+        Bson::Document(doc) => {
+            let value = doc.get(key)?;
+            match rest {
+                Some(rest) => value_at_path(value, rest),
+                None => Some(value.clone()),
+            }
+        }
+        Bson::Array(items) => {
+            let values: Vec<Bson> = items
+                .iter()
+                .filter_map(|item| value_at_path(item, path))
+                .collect();
+            (!values.is_empty()).then_some(Bson::Array(values))
+        }
+        _ => None,
+    }
+}
+
 impl Default for TableFormatter {
     fn default() -> Self {
         Self::new()
@@ -197,7 +351,7 @@ impl Default for TableFormatter {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use mongodb::bson::{doc, oid::ObjectId};
+    use bson::{doc, oid::ObjectId};
 
     #[test]
     fn test_table_formatter_creation() {
@@ -323,7 +477,7 @@ mod tests {
 
     #[test]
     fn test_actual_table_output() {
-        use mongodb::bson::DateTime;
+        use bson::DateTime;
 
         let formatter = TableFormatter::new();
         let docs = vec![
@@ -359,4 +513,64 @@ mod tests {
         assert!(result.contains("dalei"));
         assert!(result.contains("20"));
     }
+
+    #[test]
+    fn test_format_explicit_columns() {
+        let columns = vec![
+            "name".to_string(),
+            "address.city".to_string(),
+            "items.sku".to_string(),
+        ];
+        let formatter = TableFormatter::new().with_columns(&columns);
+        let docs = vec![
+            doc! {
+                "age": 30,
+                "name": "Alice",
+                "address": { "city": "Paris", "zip": "75001" },
+                "items": [{ "sku": "A1" }, { "sku": "B2" }],
+            },
+            doc! { "name": "Bob" },
+        ];
+        let result = formatter.format(&ResultData::Documents(docs)).unwrap();
+        let header = result.lines().nth(1).unwrap();
+        let name = header.find("name").unwrap();
+        let city = header.find("address.city").unwrap();
+        let sku = header.find("items.sku").unwrap();
+        assert!(name < city && city < sku);
+        assert!(!result.contains("age"));
+        assert!(!result.contains("75001"));
+        assert!(result.contains("Paris"));
+        assert!(result.contains("[A1, B2]"));
+    }
+
+    #[test]
+    fn test_format_summary_footer() {
+        let formatter = TableFormatter::new().with_summary(true);
+        let docs = vec![
+            doc! { "name": "Alice", "age": 30, "city": "Oslo" },
+            doc! { "name": "Bob", "age": 25.5, "city": "Oslo" },
+            doc! { "name": "Carol", "age": null },
+        ];
+        let result = formatter
+            .format(&ResultData::Documents(docs.clone()))
+            .unwrap();
+        assert!(result.contains("count: 3"));
+        assert!(result.contains("sum: 55.5"));
+        assert!(result.contains("avg: 27.75"));
+        assert!(result.contains("distinct: 1"));
+
+        assert_eq!(
+            formatter.summarize_column(&docs, "age"),
+            "count: 2\ndistinct: 2\nsum: 55.5\navg: 27.75"
+        );
+        assert_eq!(
+            formatter.summarize_column(&docs, "city"),
+            "count: 2\ndistinct: 1"
+        );
+        assert_eq!(format_aggregate(10.0 / 3.0), "3.33");
+
+        let plain = TableFormatter::new();
+        let result = plain.format(&ResultData::Documents(docs)).unwrap();
+        assert!(!result.contains("count:"));
+    }
 }
@@ -0,0 +1,250 @@
+//! Namespace usage formatting for the `top` command
+//!
+//! This module turns the raw output of the `top` admin command into a
+//! per-collection table of lock time and operation counts. Two samples can be
+//! diffed to show which namespaces were busy during an interval.
+
+use bson::{Bson, Document};
+use tabled::{
+    builder::Builder,
+    settings::{Alignment, Modify, Style, object::Columns},
+};
+
+use super::bson_utils::number_of;
+
+/// Lock time and operation counts for one namespace
+#[derive(Debug, Clone, Default, PartialEq)]
+struct NamespaceUsage {
+    /// Namespace in `db.collection` form
+    ns: String,
+
+    /// Total time in microseconds
+    total_time: i64,
+
+    /// Number of operations
+    total_count: i64,
+
+    /// Time holding read locks in microseconds
+    read_time: i64,
+
+    /// Number of read lock acquisitions
+    read_count: i64,
+
+    /// Time holding write locks in microseconds
+    write_time: i64,
+
+    /// Number of write lock acquisitions
+    write_count: i64,
+}
+
+impl NamespaceUsage {
+    /// Read usage counters for a namespace from its `top` entry
+    fn from_entry(ns: &str, entry: &Document) -> Self {
+        let (total_time, total_count) = time_and_count(entry, "total");
+        let (read_time, read_count) = time_and_count(entry, "readLock");
+        let (write_time, write_count) = time_and_count(entry, "writeLock");
+        Self {
+            ns: ns.to_string(),
+            total_time,
+            total_count,
+            read_time,
+            read_count,
+            write_time,
+            write_count,
+        }
+    }
+
+    /// Counters accumulated since an earlier sample
+    fn since(&self, earlier: &Self) -> Self {
+        Self {
+            ns: self.ns.clone(),
+            total_time: (self.total_time - earlier.total_time).max(0),
+            total_count: (self.total_count - earlier.total_count).max(0),
+            read_time: (self.read_time - earlier.read_time).max(0),
+            read_count: (self.read_count - earlier.read_count).max(0),
+            write_time: (self.write_time - earlier.write_time).max(0),
+            write_count: (self.write_count - earlier.write_count).max(0),
+        }
+    }
+}
+
+/// Formatter for `top` command results
+pub struct TopFormatter {
+    /// Maximum number of namespaces to show
+    limit: usize,
+}
+
+impl TopFormatter {
+    /// Create a new top formatter
+    ///
+    /// # Arguments
+    /// * `limit` - Maximum number of namespaces to show
+    pub fn new(limit: usize) -> Self {
+        Self { limit }
+    }
+
+    /// Format cumulative usage since server start
+    ///
+    /// # Arguments
+    /// * `sample` - Raw `top` command result
+    ///
+    /// # Returns
+    /// * `String` - Table of namespaces, busiest first
+    pub fn format(&self, sample: &Document) -> String {
+        let usage = parse_totals(sample);
+        self.render(usage, "Namespace usage since server start")
+    }
+
+    /// Format usage accumulated between two samples
+    ///
+    /// # Arguments
+    /// * `before` - First `top` result
+    /// * `after` - Second `top` result
+    /// * `seconds` - Interval between the samples
+    ///
+    /// # Returns
+    /// * `String` - Table of namespaces active during the interval
+    pub fn format_delta(&self, before: &Document, after: &Document, seconds: u64) -> String {
+        let earlier = parse_totals(before);
+        let usage = parse_totals(after)
+            .into_iter()
+            .map(|current| match earlier.iter().find(|e| e.ns == current.ns) {
+                Some(previous) => current.since(previous),
+                None => current,
+            })
+            .filter(|u| u.total_count > 0 || u.total_time > 0)
+            .collect();
+        self.render(usage, &format!("Namespace usage over the last {}s", seconds))
+    }
+
+    /// Sort, truncate and render usage rows as a table
+    fn render(&self, mut usage: Vec<NamespaceUsage>, title: &str) -> String {
+        if usage.is_empty() {
+            return format!("{}: no activity", title);
+        }
+
+        usage.sort_by(|a, b| b.total_time.cmp(&a.total_time).then(a.ns.cmp(&b.ns)));
+        let hidden = usage.len().saturating_sub(self.limit);
+        usage.truncate(self.limit);
+
+        let mut builder = Builder::default();
+        builder.push_record([
+            "Namespace",
+            "Total (ms)",
+            "Ops",
+            "Read (ms)",
+            "Reads",
+            "Write (ms)",
+            "Writes",
+        ]);
+        for u in &usage {
+            builder.push_record([
+                u.ns.clone(),
+                format_ms(u.total_time),
+                u.total_count.to_string(),
+                format_ms(u.read_time),
+                u.read_count.to_string(),
+                format_ms(u.write_time),
+                u.write_count.to_string(),
+            ]);
+        }
+
+        let mut table = builder.build();
+        table.with(Style::modern());
+        table.with(Modify::new(Columns::new(1..)).with(Alignment::right()));
+
+        let mut output = format!("{}\n{}", title, table);
+        if hidden > 0 {
+            output.push_str(&format!("\n({} more namespaces not shown)", hidden));
+        }
+        output
+    }
+}
+
+/// Extract per-namespace usage from a `top` result, skipping the note entry
+fn parse_totals(sample: &Document) -> Vec<NamespaceUsage> {
+    let Ok(totals) = sample.get_document("totals") else {
+        return Vec::new();
+    };
+
+    totals
+        .iter()
+        .filter_map(|(ns, entry)| match entry {
+            Bson::Document(entry) if !ns.is_empty() => Some(NamespaceUsage::from_entry(ns, entry)),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Read `{ time, count }` for one counter group
+fn time_and_count(entry: &Document, key: &str) -> (i64, i64) {
+    let Ok(group) = entry.get_document(key) else {
+        return (0, 0);
+    };
+    (number_of(group.get("time")), number_of(group.get("count")))
+}
+
+/// Format microseconds as milliseconds
+fn format_ms(micros: i64) -> String {
+    format!("{:.1}", micros as f64 / 1000.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bson::doc;
+
+    fn sample(orders_time: i64, orders_count: i64) -> Document {
+        doc! {
+            "totals": {
+                "note": "all times in microseconds",
+                "shop.orders": {
+                    "total": { "time": orders_time, "count": orders_count },
+                    "readLock": { "time": orders_time / 2, "count": orders_count / 2 },
+                    "writeLock": { "time": orders_time / 2, "count": orders_count / 2 },
+                },
+                "shop.users": {
+                    "total": { "time": 5_000_i64, "count": 10_i64 },
+                    "readLock": { "time": 5_000_i64, "count": 10_i64 },
+                    "writeLock": { "time": 0_i64, "count": 0_i64 },
+                },
+            },
+            "ok": 1.0,
+        }
+    }
+
+    #[test]
+    fn test_parse_totals_skips_note() {
+        let usage = parse_totals(&sample(20_000, 40));
+        assert_eq!(usage.len(), 2);
+        assert_eq!(usage[0].ns, "shop.orders");
+        assert_eq!(usage[0].write_count, 20);
+    }
+
+    #[test]
+    fn test_format_sorts_by_total_time() {
+        let report = TopFormatter::new(10).format(&sample(20_000, 40));
+        let orders = report.find("shop.orders").unwrap();
+        let users = report.find("shop.users").unwrap();
+        assert!(orders < users);
+        assert!(report.contains("20.0"));
+    }
+
+    #[test]
+    fn test_format_delta_shows_only_active_namespaces() {
+        let report = TopFormatter::new(10).format_delta(&sample(20_000, 40), &sample(32_000, 50), 5);
+        assert!(report.starts_with("Namespace usage over the last 5s"));
+        assert!(report.contains("shop.orders"));
+        assert!(report.contains("12.0"));
+        assert!(!report.contains("shop.users"));
+    }
+
+    #[test]
+    fn test_format_limit_and_empty() {
+        let report = TopFormatter::new(1).format(&sample(20_000, 40));
+        assert!(report.contains("(1 more namespaces not shown)"));
+
+        let idle = TopFormatter::new(10).format_delta(&sample(20_000, 40), &sample(20_000, 40), 1);
+        assert!(idle.ends_with("no activity"));
+    }
+}
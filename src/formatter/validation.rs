@@ -0,0 +1,153 @@
+//! Validation report formatting
+//!
+//! This module turns the raw output of the `validate` command into a short,
+//! readable report: overall verdict, scan counters, per-index problems and the
+//! server's error and warning messages.
+
+use bson::{Bson, Document};
+
+/// Formatter for `db.collection.validate()` results
+pub struct ValidationFormatter;
+
+impl ValidationFormatter {
+    /// Create a new validation formatter
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Format a `validate` command result as a readable report
+    ///
+    /// # Arguments
+    /// * `result` - Raw `validate` command result
+    ///
+    /// # Returns
+    /// * `String` - Multi-line report
+    pub fn format(&self, result: &Document) -> String {
+        let ns = result.get_str("ns").unwrap_or("collection");
+        let valid = result.get_bool("valid").unwrap_or(false);
+        let mut lines = vec![format!(
+            "Validation of {}: {}",
+            ns,
+            if valid { "VALID" } else { "INVALID" }
+        )];
+
+        let counters = [
+            ("nrecords", "Records scanned"),
+            ("nIndexes", "Indexes checked"),
+            ("nInvalidDocuments", "Invalid documents"),
+            ("nNonCompliantDocuments", "Documents violating the validator"),
+            ("corruptRecords", "Corrupt records"),
+            ("missingIndexEntries", "Missing index entries"),
+            ("extraIndexEntries", "Extra index entries"),
+        ];
+        for (key, label) in counters {
+            if let Some(count) = count_of(result.get(key)) {
+                lines.push(format!("  {:<34} {}", format!("{}:", label), count));
+            }
+        }
+
+        if let Ok(details) = result.get_document("indexDetails") {
+            let invalid: Vec<&str> = details
+                .iter()
+                .filter(|(_, detail)| {
+                    detail
+                        .as_document()
+                        .and_then(|d| d.get_bool("valid").ok())
+                        == Some(false)
+                })
+                .map(|(name, _)| name.as_str())
+                .collect();
+            if !invalid.is_empty() {
+                lines.push(format!("  Invalid indexes: {}", invalid.join(", ")));
+            }
+        }
+
+        if result.get_bool("repaired").unwrap_or(false) {
+            lines.push("  Collection was repaired".to_string());
+        }
+
+        push_messages(&mut lines, "Errors", result.get("errors"));
+        push_messages(&mut lines, "Warnings", result.get("warnings"));
+
+        lines.join("\n")
+    }
+}
+
+impl Default for ValidationFormatter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Read a counter that may be a number or an array of entries
+fn count_of(value: Option<&Bson>) -> Option<i64> {
+    match value? {
+        Bson::Int32(n) => Some(*n as i64),
+        Bson::Int64(n) => Some(*n),
+        Bson::Double(n) => Some(*n as i64),
+        Bson::Array(items) => Some(items.len() as i64),
+        _ => None,
+    }
+}
+
+/// Append a titled list of messages when there are any
+fn push_messages(lines: &mut Vec<String>, title: &str, messages: Option<&Bson>) {
+    let Some(Bson::Array(messages)) = messages else {
+        return;
+    };
+    if messages.is_empty() {
+        return;
+    }
+
+    lines.push(format!("{}:", title));
+    for message in messages {
+        match message {
+            Bson::String(text) => lines.push(format!("  - {}", text)),
+            other => lines.push(format!("  - {}", other)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bson::doc;
+
+    #[test]
+    fn test_format_valid_collection() {
+        let result = doc! {
+            "ns": "shop.orders",
+            "nrecords": 1200,
+            "nIndexes": 2,
+            "nInvalidDocuments": 0,
+            "valid": true,
+            "errors": [],
+            "warnings": [],
+            "ok": 1.0,
+        };
+        let report = ValidationFormatter::new().format(&result);
+        assert!(report.starts_with("Validation of shop.orders: VALID"));
+        assert!(report.contains("Records scanned:"));
+        assert!(!report.contains("Errors:"));
+    }
+
+    #[test]
+    fn test_format_invalid_collection() {
+        let result = doc! {
+            "ns": "shop.orders",
+            "valid": false,
+            "nNonCompliantDocuments": 4,
+            "indexDetails": {
+                "_id_": { "valid": true },
+                "email_1": { "valid": false },
+            },
+            "errors": ["Index email_1 has 3 missing entries"],
+            "warnings": ["Detected one or more documents not compliant with the collection's schema"],
+        };
+        let report = ValidationFormatter::new().format(&result);
+        assert!(report.contains("INVALID"));
+        assert!(report.contains("Invalid indexes: email_1"));
+        assert!(report.contains("Errors:\n  - Index email_1 has 3 missing entries"));
+        assert!(report.contains("Warnings:"));
+    }
+}
@@ -0,0 +1,151 @@
+//! Screen rendering for the `watch` command
+//!
+//! Each run of a watched command is printed below a header naming the command
+//! and the interval. Output that changed since the previous run is shown in
+//! reverse video, or marked with `*` at the start of the line when colors are
+//! off.
+
+use super::colorizer::AnsiColors;
+
+/// Formatter for repeated runs of a watched command
+pub struct WatchFormatter {
+    /// Highlight changes with ANSI codes instead of line markers
+    use_colors: bool,
+}
+
+impl WatchFormatter {
+    /// Create a new watch formatter
+    ///
+    /// # Arguments
+    /// * `use_colors` - Highlight changes with ANSI codes
+    ///
+    /// # Returns
+    /// * `Self` - New formatter
+    pub fn new(use_colors: bool) -> Self {
+        Self { use_colors }
+    }
+
+    /// Header line shown above each run
+    ///
+    /// # Arguments
+    /// * `interval` - Seconds between runs
+    /// * `source` - Watched command as typed
+    /// * `time` - Time of the run
+    ///
+    /// # Returns
+    /// * `String` - e.g. `Every 5s: db.orders.countDocuments()  12:00:05`
+    pub fn header(&self, interval: u64, source: &str, time: &str) -> String {
+        let header = format!("Every {}s: {}  {}", interval, source, time);
+        if self.use_colors {
+            format!("{}{}{}", AnsiColors::BOLD, header, AnsiColors::RESET)
+        } else {
+            header
+        }
+    }
+
+    /// Highlight the parts of an output that changed since the previous run
+    ///
+    /// Lines are compared by position; within a changed line only the span
+    /// between the common prefix and suffix is highlighted, so a changed
+    /// count stands out in an otherwise identical line.
+    ///
+    /// # Arguments
+    /// * `previous` - Output of the previous run, None for the first run
+    /// * `current` - Output of this run
+    ///
+    /// # Returns
+    /// * `String` - Output with changes highlighted
+    pub fn highlight_changes(&self, previous: Option<&str>, current: &str) -> String {
+        let Some(previous) = previous else {
+            return current.to_string();
+        };
+        let previous: Vec<&str> = previous.lines().collect();
+
+        current
+            .lines()
+            .enumerate()
+            .map(|(i, line)| {
+                let before = previous.get(i).copied();
+                if before == Some(line) {
+                    if self.use_colors {
+                        line.to_string()
+                    } else {
+                        format!("  {}", line)
+                    }
+                } else if self.use_colors {
+                    self.highlight_line(before.unwrap_or(""), line)
+                } else {
+                    format!("* {}", line)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Reverse the span of `line` that differs from `before`
+    fn highlight_line(&self, before: &str, line: &str) -> String {
+        let old: Vec<char> = before.chars().collect();
+        let new: Vec<char> = line.chars().collect();
+
+        let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+        let suffix = old[prefix..]
+            .iter()
+            .rev()
+            .zip(new[prefix..].iter().rev())
+            .take_while(|(a, b)| a == b)
+            .count();
+        let end = new.len() - suffix;
+        if prefix == end {
+            return line.to_string();
+        }
+
+        format!(
+            "{}{}{}{}{}",
+            new[..prefix].iter().collect::<String>(),
+            AnsiColors::REVERSE,
+            new[prefix..end].iter().collect::<String>(),
+            AnsiColors::RESET,
+            new[end..].iter().collect::<String>()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_first_run_unchanged() {
+        let formatter = WatchFormatter::new(true);
+        assert_eq!(formatter.highlight_changes(None, "42"), "42");
+    }
+
+    #[test]
+    fn test_highlight_changed_span() {
+        let formatter = WatchFormatter::new(true);
+        let output = formatter.highlight_changes(Some("{ n: 41 }\nsame"), "{ n: 42 }\nsame\nnew");
+        assert_eq!(
+            output,
+            format!(
+                "{{ n: 4{}2{} }}\nsame\n{}new{}",
+                AnsiColors::REVERSE,
+                AnsiColors::RESET,
+                AnsiColors::REVERSE,
+                AnsiColors::RESET
+            )
+        );
+    }
+
+    #[test]
+    fn test_markers_without_colors() {
+        let formatter = WatchFormatter::new(false);
+        assert_eq!(
+            formatter.highlight_changes(Some("a\nb"), "a\nc"),
+            "  a\n* c"
+        );
+        assert_eq!(
+            formatter.header(5, "db.orders.countDocuments()", "12:00:05"),
+            "Every 5s: db.orders.countDocuments()  12:00:05"
+        );
+    }
+}
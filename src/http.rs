@@ -0,0 +1,182 @@
+//! Minimal HTTP/1.1 handling for the built-in endpoints
+//!
+//! `--metrics-port` and `mongosh serve` answer one request per connection and
+//! close it, so a request head, a `Content-Length` body and a complete
+//! response are all they need; this keeps an HTTP framework out of the
+//! dependency tree.
+
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+/// Largest request head accepted
+const MAX_HEAD_BYTES: usize = 16 * 1024;
+
+/// Largest request body accepted
+pub const MAX_BODY_BYTES: usize = 1024 * 1024;
+
+/// A parsed request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Request {
+    /// Method, e.g. `GET`
+    pub method: String,
+
+    /// Path without the query string
+    pub path: String,
+
+    /// Header names, lowercased, and values in the order received
+    pub headers: Vec<(String, String)>,
+
+    /// Request body, empty without `Content-Length`
+    pub body: Vec<u8>,
+}
+
+/// Read one request
+///
+/// # Returns
+/// * `std::io::Result<Request>` - Request, or an `InvalidData` error for a
+///   malformed or oversized one
+pub async fn read_request<S: AsyncRead + Unpin>(stream: &mut S) -> std::io::Result<Request> {
+    let mut data = Vec::new();
+    let mut buf = [0u8; 4096];
+
+    let head_end = loop {
+        if let Some(pos) = data.windows(4).position(|w| w == b"\r\n\r\n") {
+            break pos;
+        }
+        if data.len() > MAX_HEAD_BYTES {
+            return Err(invalid("request head too large"));
+        }
+        let n = stream.read(&mut buf).await?;
+        if n == 0 {
+            return Err(invalid("connection closed before the request was complete"));
+        }
+        data.extend_from_slice(&buf[..n]);
+    };
+
+    let head = String::from_utf8_lossy(&data[..head_end]).to_string();
+    let mut lines = head.lines();
+    let mut request_line = lines.next().unwrap_or_default().split_whitespace();
+    let method = request_line.next().unwrap_or_default().to_string();
+    let target = request_line.next().unwrap_or_default();
+    let path = target.split('?').next().unwrap_or_default().to_string();
+    if method.is_empty() || path.is_empty() {
+        return Err(invalid("malformed request line"));
+    }
+
+    let headers: Vec<(String, String)> = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim().to_ascii_lowercase(), value.trim().to_string()))
+        .collect();
+
+    let content_length = headers
+        .iter()
+        .find(|(name, _)| name == "content-length")
+        .map(|(_, value)| value.parse::<usize>())
+        .transpose()
+        .map_err(|_| invalid("invalid Content-Length"))?
+        .unwrap_or(0);
+    if content_length > MAX_BODY_BYTES {
+        return Err(invalid("request body too large"));
+    }
+
+    let mut body = data.split_off(head_end + 4);
+    while body.len() < content_length {
+        let n = stream.read(&mut buf).await?;
+        if n == 0 {
+            return Err(invalid("connection closed before the body was complete"));
+        }
+        body.extend_from_slice(&buf[..n]);
+    }
+    body.truncate(content_length);
+
+    Ok(Request {
+        method,
+        path,
+        headers,
+        body,
+    })
+}
+
+impl Request {
+    /// Value of a header
+    ///
+    /// # Arguments
+    /// * `name` - Header name, matched case-insensitively
+    ///
+    /// # Returns
+    /// * `Option<&str>` - First value of the header, `None` if it is missing
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Write a complete response and close the connection
+///
+/// # Arguments
+/// * `status` - Status line text, e.g. `200 OK`
+/// * `content_type` - `Content-Type` header value
+/// * `body` - Response body
+pub async fn write_response<S: AsyncWrite + Unpin>(
+    stream: &mut S,
+    status: &str,
+    content_type: &str,
+    body: &str,
+) -> std::io::Result<()> {
+    stream
+        .write_all(response(status, content_type, body).as_bytes())
+        .await?;
+    stream.shutdown().await
+}
+
+/// Render a response
+fn response(status: &str, content_type: &str, body: &str) -> String {
+    format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    )
+}
+
+fn invalid(message: &str) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, message.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_read_request() {
+        let raw = b"POST /query?x=1 HTTP/1.1\r\nHost: localhost\r\ncontent-length: 15\r\n\r\ndb.users.find()";
+        let request = read_request(&mut &raw[..]).await.unwrap();
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/query");
+        assert_eq!(request.body, b"db.users.find()");
+        assert_eq!(request.header("Host"), Some("localhost"));
+        assert_eq!(request.header("origin"), None);
+
+        let raw = b"GET /metrics HTTP/1.1\r\n\r\n";
+        let request = read_request(&mut &raw[..]).await.unwrap();
+        assert_eq!(request.path, "/metrics");
+        assert!(request.body.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_read_request_invalid() {
+        assert!(read_request(&mut &b"GET / HTTP/1.1\r\n"[..]).await.is_err());
+        let raw = b"POST / HTTP/1.1\r\nContent-Length: 10\r\n\r\nshort";
+        assert!(read_request(&mut &raw[..]).await.is_err());
+    }
+
+    #[test]
+    fn test_response() {
+        assert_eq!(
+            response("200 OK", "text/plain", "ok\n"),
+            "HTTP/1.1 200 OK\r\nContent-Type: text/plain\r\nContent-Length: 3\r\nConnection: close\r\n\r\nok\n"
+        );
+    }
+}
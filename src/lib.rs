@@ -8,52 +8,106 @@
 //! - `cli`: Command-line interface and argument parsing
 //! - `config`: Configuration management
 //! - `connection`: MongoDB connection management
+//! - `crash`: Local crash reports
+//! - `embed`: `MongoshSession`, a high-level API for evaluating commands
 //! - `error`: Error types and handling
 //! - `executor`: Command execution engine
 //! - `formatter`: Output formatting and display
+//! - `mcp`: MCP server (`mcp` feature)
 //! - `parser`: Command and query parsing
-//! - `plugins`: Plugin system for extensibility
-//! - `repl`: Interactive REPL engine
+//! - `plugin`: Extension points for applications built on the library (`plugins` feature)
+//! - `playground`: Offline validation, SQL conversion and formatting (`wasm` feature)
+//! - `repl`: Interactive REPL engine (`repl` feature) and shared session state
+//!
+//! # Features
+//!
+//! All features are enabled by default and required by the `mongosh` binary.
+//! Embedders that only need `connection` and `executor` can turn the others
+//! off:
+//!
+//! ```toml
+//! mongosh = { version = "0.9", default-features = false, features = ["native"] }
+//! ```
+//!
+//! - `native`: MongoDB driver, tokio runtime and command execution; every
+//!   module that talks to a server needs it
+//! - `repl`: line editor, completion, highlighting and hints (implies `sql`)
+//! - `sql`: SQL query input
+//! - `script`: `load()` and `--file` script runtime
+//! - `export`: `|> export` writers for JSON Lines, CSV and Excel
+//! - `mcp`: MCP server
+//! - `plugins`: the `plugin` module and the `plugin` shell commands
+//! - `wasm-plugins`: plugins loaded from sandboxed `.wasm` modules (off by
+//!   default)
+//! - `lua`: Lua scripts run with `load()` and `--file` (off by default)
+//! - `ai-completion`: AI completion and query generation (off by default)
+//! - `wasm`: the `playground` module; build without default features to get a
+//!   parser and formatter that compile to WebAssembly (off by default)
 //!
 //! # Example
 //!
 //! ```no_run
-//! use mongosh::{config::Config, connection::ConnectionManager};
+//! # #[cfg(feature = "native")]
+//! # mod example {
+//! use mongosh::MongoshSession;
 //!
 //! #[tokio::main]
 //! async fn main() -> Result<(), Box<dyn std::error::Error>> {
-//!     let config = Config::default();
-//!     let mut manager = ConnectionManager::new(
-//!         "mongodb://localhost:27017".to_string(),
-//!         config.connection,
-//!     );
-//!
-//!     manager.connect().await?;
-//!     println!("Connected to MongoDB");
-//!
-//!     manager.disconnect().await?;
+//!     let session = MongoshSession::connect("mongodb://localhost:27017/shop").await?;
+//!     let result = session.eval("db.orders.find({ status: 'pending' }).limit(5)").await?;
+//!     println!("{}", result);
 //!     Ok(())
 //! }
+//! # }
+//! # fn main() {}
 //! ```
 
+#[cfg(feature = "native")]
 pub mod cli;
 pub mod config;
+#[cfg(feature = "native")]
 pub mod connection;
+#[cfg(feature = "native")]
+pub mod crash;
+#[cfg(feature = "native")]
+pub mod embed;
 pub mod error;
 pub mod executor;
 pub mod formatter;
+#[cfg(feature = "native")]
+pub mod http;
+#[cfg(all(feature = "repl", feature = "script"))]
+pub mod lsp;
+#[cfg(feature = "native")]
+pub mod metrics;
 pub mod parser;
+#[cfg(feature = "wasm")]
+pub mod playground;
+#[cfg(feature = "plugins")]
+pub mod plugin;
+#[cfg(feature = "native")]
 pub mod repl;
+#[cfg(feature = "repl")]
+pub mod rpc;
+#[cfg(feature = "native")]
+pub mod serve;
 pub mod mcp;
 
 // Re-export commonly used types
 pub use config::Config;
+#[cfg(feature = "native")]
 pub use connection::ConnectionManager;
+#[cfg(feature = "native")]
+pub use embed::{FormattedResult, MongoshSession};
 pub use error::{MongoshError, Result};
-pub use executor::{CommandRouter, ExecutionResult};
+#[cfg(feature = "native")]
+pub use executor::CommandRouter;
+pub use executor::ExecutionResult;
 pub use formatter::Formatter;
 pub use parser::{Command, Parser};
+#[cfg(feature = "repl")]
 pub use repl::ReplEngine;
+#[cfg(feature = "mcp")]
 pub use mcp::MongoShellServer;
 
 /// Library version
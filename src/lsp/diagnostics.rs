@@ -0,0 +1,123 @@
+//! Parser diagnostics for script documents
+//!
+//! A document is split into statements the way `load()` splits a script and
+//! each statement is parsed on its own, so an editor underlines exactly the
+//! statements a script run would stop at.
+
+use std::collections::HashMap;
+
+use crate::error::{MongoshError, ParseError};
+use crate::parser::{Parser, prompt_requests};
+use crate::repl::script::split_statements;
+
+/// A parse error located in a document
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// 0-based line and character (Unicode scalar) column of the start
+    pub start: (usize, usize),
+
+    /// 0-based line and character column of the end, exclusive
+    pub end: (usize, usize),
+
+    /// Error message
+    pub message: String,
+}
+
+/// Parse every statement of a document
+///
+/// # Arguments
+/// * `source` - Document text
+///
+/// # Returns
+/// * `Vec<Diagnostic>` - One diagnostic per statement that fails to parse
+pub fn diagnose(source: &str) -> Vec<Diagnostic> {
+    let lines: Vec<&str> = source.lines().collect();
+    let mut diagnostics = Vec::new();
+
+    for statement in split_statements(source) {
+        // prompt() and env() are resolved when the script runs; check with
+        // blank answers and an empty environment
+        let mut parser = Parser::new();
+        parser.set_prompt_answers(vec![String::new(); prompt_requests(&statement.text).len()]);
+        parser.set_environment(HashMap::new());
+        let error = match parser.parse(&statement.text) {
+            Ok(_) => continue,
+            Err(e) => e,
+        };
+
+        // Statements are trimmed, so the first line is found in the source line
+        let first_line = statement.line - 1;
+        let head = statement.text.lines().next().unwrap_or_default();
+        let column = lines
+            .get(first_line)
+            .and_then(|line| line.find(head))
+            .map_or(0, |byte| lines[first_line][..byte].chars().count());
+        let locate = |offset: usize| {
+            let before: String = statement.text.chars().take(offset).collect();
+            let line_offset = before.matches('\n').count();
+            let col = before
+                .rsplit('\n')
+                .next()
+                .unwrap_or_default()
+                .chars()
+                .count();
+            if line_offset == 0 {
+                (first_line, column + col)
+            } else {
+                (first_line + line_offset, col)
+            }
+        };
+
+        let length = statement.text.chars().count();
+        let (span, message) = match &error {
+            MongoshError::Parse(ParseError::Located {
+                message,
+                source: parsed,
+                span,
+                hint,
+            }) => {
+                // The span is relative to the parsed part, e.g. the command before a pipe
+                let base = statement
+                    .text
+                    .find(parsed.as_str())
+                    .map_or(0, |byte| statement.text[..byte].chars().count());
+                let message = match hint {
+                    Some(hint) => format!("{}\n{}", message, hint),
+                    None => message.clone(),
+                };
+                (base + span.start..base + span.end, message)
+            }
+            _ => (0..length, error.to_string()),
+        };
+        let start = span.start.min(length);
+        let end = span.end.max(start + 1).min(length.max(1));
+        diagnostics.push(Diagnostic {
+            start: locate(start),
+            end: locate(end),
+            message,
+        });
+    }
+
+    diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_script() {
+        let source = "use shop\ndb.orders.find({ status: 'A' })\n  .limit(5)\nshow collections\n";
+        assert!(diagnose(source).is_empty());
+    }
+
+    #[test]
+    fn test_invalid_statement() {
+        let source = "db.orders.find()\n  db.orders.find({ status: }\n";
+        let diagnostics = diagnose(source);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].start.0, 1);
+        assert!(diagnostics[0].start.1 >= 2);
+        assert!(diagnostics[0].end >= diagnostics[0].start);
+    }
+}
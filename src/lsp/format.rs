@@ -0,0 +1,150 @@
+//! Document formatting for script documents
+//!
+//! Formatting only changes whitespace outside strings and comments: lines are
+//! indented one level deeper than the line that opened their bracket, lines
+//! continuing a method chain (`.sort()`) get one extra level, trailing
+//! whitespace is removed, runs of blank lines are collapsed to one and the
+//! document ends with a single newline. Lines inside a multi-line template
+//! literal or block comment are kept as they are.
+
+/// Where the scanner is at the end of a line
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+struct ScanState {
+    /// Indentation level of the line each open bracket is on, innermost last
+    open: Vec<usize>,
+
+    /// Inside a `/* */` comment
+    in_comment: bool,
+
+    /// Inside a template literal
+    in_template: bool,
+}
+
+/// Format a document
+///
+/// # Arguments
+/// * `source` - Document text
+/// * `indent` - One indentation level, e.g. two spaces or a tab
+///
+/// # Returns
+/// * `String` - Formatted text
+pub fn format_document(source: &str, indent: &str) -> String {
+    let mut out = String::with_capacity(source.len());
+    let mut state = ScanState::default();
+    let mut blank_run = 0;
+
+    for line in source.lines() {
+        if state.in_comment || state.in_template {
+            out.push_str(line);
+            out.push('\n');
+            scan(line, 0, &mut state);
+            continue;
+        }
+
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            blank_run += 1;
+            continue;
+        }
+        if blank_run > 0 && !out.is_empty() {
+            out.push('\n');
+        }
+        blank_run = 0;
+
+        let closers = trimmed
+            .chars()
+            .take_while(|c| matches!(c, ')' | ']' | '}'))
+            .count();
+        // A closing line lines up with the line that opened its outermost bracket
+        let mut level = match closers {
+            0 => state.open.last().map_or(0, |level| level + 1),
+            n => state.open[state.open.len().saturating_sub(n)..]
+                .first()
+                .copied()
+                .unwrap_or(0),
+        };
+        if trimmed.starts_with('.') {
+            level += 1;
+        }
+        out.push_str(&indent.repeat(level));
+        out.push_str(trimmed);
+        out.push('\n');
+        scan(trimmed, level, &mut state);
+    }
+
+    out
+}
+
+/// Update the scan state with one line at an indentation level
+fn scan(line: &str, level: usize, state: &mut ScanState) {
+    let chars: Vec<char> = line.chars().collect();
+    let mut quote: Option<char> = state.in_template.then_some('`');
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if state.in_comment {
+            if c == '*' && chars.get(i + 1) == Some(&'/') {
+                state.in_comment = false;
+                i += 1;
+            }
+        } else if let Some(q) = quote {
+            if c == '\\' {
+                i += 1;
+            } else if c == q {
+                quote = None;
+            }
+        } else {
+            match c {
+                '/' if chars.get(i + 1) == Some(&'/') => break,
+                '/' if chars.get(i + 1) == Some(&'*') => {
+                    state.in_comment = true;
+                    i += 1;
+                }
+                '\'' | '"' | '`' => quote = Some(c),
+                '(' | '[' | '{' => state.open.push(level),
+                ')' | ']' | '}' => {
+                    state.open.pop();
+                }
+                _ => {}
+            }
+        }
+        i += 1;
+    }
+
+    // Only template literals span lines
+    state.in_template = quote == Some('`');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reindent() {
+        let source = "db.orders.aggregate([\n{ $match: { status: 'A' } },\n    {\n$group: {\n_id: '$cust',\n}\n}\n])   \n.toArray()\n";
+        assert_eq!(
+            format_document(source, "  "),
+            "db.orders.aggregate([\n  { $match: { status: 'A' } },\n  {\n    $group: {\n      _id: '$cust',\n    }\n  }\n])\n  .toArray()\n"
+        );
+    }
+
+    #[test]
+    fn test_blank_lines_and_strings() {
+        let source =
+            "\n\nuse shop\n\n\n\nprint('{ (')\n// }\ndb.users.find({ name: `a\n   b` })\n\n";
+        assert_eq!(
+            format_document(source, "\t"),
+            "use shop\n\nprint('{ (')\n// }\ndb.users.find({ name: `a\n   b` })\n"
+        );
+    }
+
+    #[test]
+    fn test_block_comment() {
+        let source = "/* {\n   keep\n*/\ndb.a.find({\nx: 1 })\n";
+        assert_eq!(
+            format_document(source, "  "),
+            "/* {\n   keep\n*/\ndb.a.find({\n  x: 1 })\n"
+        );
+    }
+}
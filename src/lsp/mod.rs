@@ -0,0 +1,509 @@
+//! Language server for shell scripts (`mongosh lsp`)
+//!
+//! Speaks the Language Server Protocol over stdio so editors get the shell's
+//! own intelligence in `.mongodb` and `.js` script files:
+//!
+//! - completion from the REPL's completion engine, including collection
+//!   names when the server is reachable
+//! - hover documentation for `$` operators and shell methods
+//! - diagnostics from the parser, one per statement that would fail in
+//!   `load()`, published whenever a document is opened or changed
+//! - whole-document formatting (re-indentation, see [`format_document`])
+//!
+//! Documents are synchronized in full on every change. Positions use UTF-16
+//! columns as the protocol requires. Without a connection, or with
+//! `--no-connect`, everything works except collection name completion.
+
+mod diagnostics;
+mod format;
+mod operators;
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use serde_json::{Value as JsonValue, json};
+use tokio::io::{
+    AsyncBufRead, AsyncBufReadExt, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader,
+};
+use tracing::debug;
+
+use crate::cli::help;
+use crate::error::{MongoshError, Result};
+use crate::executor::ExecutionContext;
+use crate::repl::SharedState;
+use crate::repl::completion::{CompletionEngine, MongoCandidateProvider};
+use crate::rpc::{self, COMMAND_FAILED, INVALID_PARAMS, METHOD_NOT_FOUND, Request, RpcError};
+use diagnostics::diagnose;
+use format::format_document;
+use operators::OperatorDoc;
+
+/// Open documents and the completion engine
+struct Server {
+    /// Text of each open document by URI
+    documents: HashMap<String, String>,
+
+    /// Completion over the session's database
+    completion: Arc<CompletionEngine>,
+}
+
+/// Answer LSP messages on stdin until `exit` or end of input
+///
+/// # Arguments
+/// * `shared_state` - Session state, for the current database
+/// * `context` - Execution context used to list collections, if connected
+///
+/// # Returns
+/// * `Result<()>` - Success or an I/O error on stdio
+pub async fn run(shared_state: SharedState, context: Option<ExecutionContext>) -> Result<()> {
+    let provider = MongoCandidateProvider::new(shared_state, context.map(Arc::new));
+    let mut server = Server {
+        documents: HashMap::new(),
+        completion: Arc::new(CompletionEngine::new(Arc::new(provider))),
+    };
+
+    let mut stdin = BufReader::new(tokio::io::stdin());
+    let mut stdout = tokio::io::stdout();
+    while let Some(message) = read_message(&mut stdin).await.map_err(io_error)? {
+        let outgoing = match rpc::parse_request(&message) {
+            Ok(request) if request.method == "exit" => break,
+            Ok(request) => server.handle(request).await,
+            Err(error) => vec![rpc::response_message(JsonValue::Null, Err(error))],
+        };
+        for message in outgoing {
+            write_message(&mut stdout, &message)
+                .await
+                .map_err(io_error)?;
+        }
+    }
+
+    Ok(())
+}
+
+impl Server {
+    /// Handle one request or notification
+    ///
+    /// # Returns
+    /// * `Vec<JsonValue>` - The response, if the message was a request, and
+    ///   any notifications to send
+    async fn handle(&mut self, request: Request) -> Vec<JsonValue> {
+        debug!("LSP message: {}", request.method);
+        let params = &request.params;
+        let mut notifications = Vec::new();
+
+        let response = match request.method.as_str() {
+            "initialize" => Ok(json!({
+                "capabilities": {
+                    "textDocumentSync": 1,
+                    "completionProvider": { "triggerCharacters": [".", "$", " "] },
+                    "hoverProvider": true,
+                    "documentFormattingProvider": true,
+                },
+                "serverInfo": { "name": "mongosh", "version": env!("CARGO_PKG_VERSION") },
+            })),
+            "shutdown" => Ok(JsonValue::Null),
+            "textDocument/didOpen" | "textDocument/didChange" => {
+                let text = match request.method.as_str() {
+                    "textDocument/didOpen" => params["textDocument"]["text"].as_str(),
+                    _ => params["contentChanges"]
+                        .as_array()
+                        .and_then(|changes| changes.last())
+                        .and_then(|change| change["text"].as_str()),
+                };
+                match (document_uri(params), text) {
+                    (Ok(uri), Some(text)) => {
+                        notifications.push(publish_diagnostics(&uri, text));
+                        self.documents.insert(uri, text.to_string());
+                    }
+                    _ => debug!("Ignoring malformed {}", request.method),
+                }
+                Ok(JsonValue::Null)
+            }
+            "textDocument/didClose" => {
+                if let Ok(uri) = document_uri(params) {
+                    self.documents.remove(&uri);
+                    notifications.push(publish_diagnostics(&uri, ""));
+                }
+                Ok(JsonValue::Null)
+            }
+            "textDocument/completion" => self.complete(params).await,
+            "textDocument/hover" => self.hover(params),
+            "textDocument/formatting" => self.format(params),
+            method => Err(RpcError::new(
+                METHOD_NOT_FOUND,
+                format!("Unknown method '{}'", method),
+            )),
+        };
+
+        // Notifications, including unknown `$/` ones, are never answered
+        let mut outgoing = Vec::new();
+        if let Some(id) = request.id {
+            outgoing.push(rpc::response_message(id, response));
+        }
+        outgoing.extend(notifications);
+        outgoing
+    }
+
+    /// Completion items at a position
+    async fn complete(&self, params: &JsonValue) -> std::result::Result<JsonValue, RpcError> {
+        let (line_number, character) = position(params)?;
+        let line = self.line(params, line_number)?;
+        let cursor = byte_offset(&line, character);
+
+        // Collection names may be fetched, which blocks
+        let engine = self.completion.clone();
+        let (start, pairs) = tokio::task::spawn_blocking(move || {
+            let (start, pairs) = engine.complete(&line[..cursor], cursor);
+            (utf16_column(&line[..start]), pairs)
+        })
+        .await
+        .map_err(|e| RpcError::new(COMMAND_FAILED, e.to_string()))?;
+
+        let range = json!({
+            "start": { "line": line_number, "character": start },
+            "end": { "line": line_number, "character": character },
+        });
+        let items: Vec<JsonValue> = pairs
+            .into_iter()
+            .map(|pair| {
+                json!({
+                    "label": pair.display,
+                    "detail": pair.description,
+                    "textEdit": { "range": range, "newText": pair.replacement },
+                })
+            })
+            .collect();
+        Ok(json!(items))
+    }
+
+    /// Documentation of the operator or method under the cursor
+    fn hover(&self, params: &JsonValue) -> std::result::Result<JsonValue, RpcError> {
+        let (line_number, character) = position(params)?;
+        let line = self.line(params, line_number)?;
+        let Some((start, end)) = word_at(&line, byte_offset(&line, character)) else {
+            return Ok(JsonValue::Null);
+        };
+        let word = &line[start..end];
+
+        let markdown = if word.starts_with('$') {
+            operators::find(word).map(OperatorDoc::to_markdown)
+        } else {
+            help::find(word)
+                .map(|cmd| format!("**{}**\n\n{}", cmd.name, help::render_markdown_body(cmd)))
+        };
+        Ok(match markdown {
+            Some(value) => json!({
+                "contents": { "kind": "markdown", "value": value },
+                "range": {
+                    "start": { "line": line_number, "character": utf16_column(&line[..start]) },
+                    "end": { "line": line_number, "character": utf16_column(&line[..end]) },
+                },
+            }),
+            None => JsonValue::Null,
+        })
+    }
+
+    /// Edits that format a whole document
+    fn format(&self, params: &JsonValue) -> std::result::Result<JsonValue, RpcError> {
+        let uri = document_uri(params)?;
+        let text = self.document(&uri)?;
+        let options = &params["options"];
+        let indent = if options["insertSpaces"].as_bool().unwrap_or(true) {
+            " ".repeat(options["tabSize"].as_u64().unwrap_or(2) as usize)
+        } else {
+            "\t".to_string()
+        };
+
+        let formatted = format_document(text, &indent);
+        if formatted == *text {
+            return Ok(json!([]));
+        }
+        Ok(json!([{
+            "range": { "start": { "line": 0, "character": 0 }, "end": end_position(text) },
+            "newText": formatted,
+        }]))
+    }
+
+    /// Text of an open document
+    fn document(&self, uri: &str) -> std::result::Result<&String, RpcError> {
+        self.documents
+            .get(uri)
+            .ok_or_else(|| RpcError::new(INVALID_PARAMS, format!("Document '{}' is not open", uri)))
+    }
+
+    /// One line of the document a request refers to, empty past the end
+    fn line(
+        &self,
+        params: &JsonValue,
+        line_number: usize,
+    ) -> std::result::Result<String, RpcError> {
+        let text = self.document(&document_uri(params)?)?;
+        Ok(text
+            .lines()
+            .nth(line_number)
+            .unwrap_or_default()
+            .to_string())
+    }
+}
+
+/// `textDocument/publishDiagnostics` notification for a document
+fn publish_diagnostics(uri: &str, text: &str) -> JsonValue {
+    let lines: Vec<&str> = text.lines().collect();
+    let position = |(line, column): (usize, usize)| {
+        let prefix: String = lines
+            .get(line)
+            .map(|text| text.chars().take(column).collect())
+            .unwrap_or_default();
+        json!({ "line": line, "character": utf16_column(&prefix) })
+    };
+    let diagnostics: Vec<JsonValue> = diagnose(text)
+        .into_iter()
+        .map(|diagnostic| {
+            json!({
+                "range": { "start": position(diagnostic.start), "end": position(diagnostic.end) },
+                "severity": 1,
+                "source": "mongosh",
+                "message": diagnostic.message,
+            })
+        })
+        .collect();
+
+    json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/publishDiagnostics",
+        "params": { "uri": uri, "diagnostics": diagnostics },
+    })
+}
+
+/// `textDocument.uri` of a request
+fn document_uri(params: &JsonValue) -> std::result::Result<String, RpcError> {
+    params["textDocument"]["uri"]
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| RpcError::new(INVALID_PARAMS, "\"textDocument.uri\" is required"))
+}
+
+/// `position` of a request as line and UTF-16 column
+fn position(params: &JsonValue) -> std::result::Result<(usize, usize), RpcError> {
+    let position = &params["position"];
+    match (position["line"].as_u64(), position["character"].as_u64()) {
+        (Some(line), Some(character)) => Ok((line as usize, character as usize)),
+        _ => Err(RpcError::new(INVALID_PARAMS, "\"position\" is required")),
+    }
+}
+
+/// Byte offset of a UTF-16 column, clamped to the end of the line
+fn byte_offset(line: &str, utf16: usize) -> usize {
+    let mut units = 0;
+    for (index, c) in line.char_indices() {
+        if units >= utf16 {
+            return index;
+        }
+        units += c.len_utf16();
+    }
+    line.len()
+}
+
+/// UTF-16 length of a string
+fn utf16_column(prefix: &str) -> usize {
+    prefix.chars().map(char::len_utf16).sum()
+}
+
+/// Position just past the last character of a text
+fn end_position(text: &str) -> JsonValue {
+    let line = text.matches('\n').count();
+    let last = text.rsplit('\n').next().unwrap_or_default();
+    json!({ "line": line, "character": utf16_column(last) })
+}
+
+/// Byte range of the identifier or `$` operator around a byte offset
+fn word_at(line: &str, offset: usize) -> Option<(usize, usize)> {
+    let is_word = |c: char| c.is_alphanumeric() || c == '_' || c == '$';
+    let start = line[..offset]
+        .char_indices()
+        .rev()
+        .take_while(|(_, c)| is_word(*c))
+        .last()
+        .map_or(offset, |(index, _)| index);
+    let end = line[offset..]
+        .char_indices()
+        .find(|(_, c)| !is_word(*c))
+        .map_or(line.len(), |(index, _)| offset + index);
+    (start < end).then_some((start, end))
+}
+
+/// Read one `Content-Length` framed message
+///
+/// # Returns
+/// * `std::io::Result<Option<String>>` - Message body, or `None` at end of input
+async fn read_message<R: AsyncBufRead + Unpin>(reader: &mut R) -> std::io::Result<Option<String>> {
+    let mut content_length = None;
+    let mut line = String::new();
+    loop {
+        line.clear();
+        if reader.read_line(&mut line).await? == 0 {
+            return Ok(None);
+        }
+        let header = line.trim_end();
+        if header.is_empty() {
+            if content_length.is_some() {
+                break;
+            }
+            continue;
+        }
+        if let Some((name, value)) = header.split_once(':')
+            && name.trim().eq_ignore_ascii_case("content-length")
+        {
+            content_length = Some(value.trim().parse::<usize>().map_err(|_| {
+                std::io::Error::new(std::io::ErrorKind::InvalidData, "invalid Content-Length")
+            })?);
+        }
+    }
+
+    let mut body = vec![0u8; content_length.unwrap_or_default()];
+    reader.read_exact(&mut body).await?;
+    String::from_utf8(body)
+        .map(Some)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+}
+
+/// Write one `Content-Length` framed message
+async fn write_message<W: AsyncWrite + Unpin>(
+    writer: &mut W,
+    message: &JsonValue,
+) -> std::io::Result<()> {
+    let body = message.to_string();
+    writer
+        .write_all(format!("Content-Length: {}\r\n\r\n{}", body.len(), body).as_bytes())
+        .await?;
+    writer.flush().await
+}
+
+fn io_error(e: std::io::Error) -> MongoshError {
+    MongoshError::Generic(format!("Language server I/O failed: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn server() -> Server {
+        let provider = MongoCandidateProvider::new(SharedState::new("test".to_string()), None);
+        Server {
+            documents: HashMap::new(),
+            completion: Arc::new(CompletionEngine::new(Arc::new(provider))),
+        }
+    }
+
+    fn request(id: Option<i64>, method: &str, params: JsonValue) -> Request {
+        Request {
+            id: id.map(|id| json!(id)),
+            method: method.to_string(),
+            params,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_message_framing() {
+        let mut written = Vec::new();
+        write_message(&mut written, &json!({ "id": 1, "text": "é" }))
+            .await
+            .unwrap();
+        assert!(written.starts_with(b"Content-Length: 20\r\n\r\n"));
+
+        let mut input = written.clone();
+        input.extend_from_slice(&written);
+        let mut reader = BufReader::new(&input[..]);
+        let first = read_message(&mut reader).await.unwrap().unwrap();
+        assert_eq!(
+            serde_json::from_str::<JsonValue>(&first).unwrap()["text"],
+            "é"
+        );
+        assert!(read_message(&mut reader).await.unwrap().is_some());
+        assert!(read_message(&mut reader).await.unwrap().is_none());
+    }
+
+    #[test]
+    fn test_positions() {
+        let line = "a😀b.find";
+        assert_eq!(byte_offset(line, 3), 5);
+        assert_eq!(byte_offset(line, 100), line.len());
+        assert_eq!(utf16_column("a😀"), 3);
+        assert_eq!(end_position("ab\ncd"), json!({ "line": 1, "character": 2 }));
+        assert_eq!(word_at("{ age: { $gte: 1 } }", 10), Some((9, 13)));
+        assert_eq!(word_at("db.users.find()", 10), Some((9, 13)));
+        assert_eq!(word_at("a  b", 2), None);
+    }
+
+    #[tokio::test]
+    async fn test_document_lifecycle() {
+        let mut server = server();
+        let uri = "file:///tmp/report.mongodb";
+
+        let outgoing = server
+            .handle(request(
+                None,
+                "textDocument/didOpen",
+                json!({ "textDocument": { "uri": uri, "text": "db.orders.find({ a: }\n" } }),
+            ))
+            .await;
+        assert_eq!(outgoing.len(), 1);
+        assert_eq!(outgoing[0]["method"], "textDocument/publishDiagnostics");
+        assert_eq!(
+            outgoing[0]["params"]["diagnostics"]
+                .as_array()
+                .unwrap()
+                .len(),
+            1
+        );
+
+        let outgoing = server
+            .handle(request(
+                None,
+                "textDocument/didChange",
+                json!({
+                    "textDocument": { "uri": uri },
+                    "contentChanges": [{ "text": "db.orders.find({\nqty: { $gte: 1 } })\n" }],
+                }),
+            ))
+            .await;
+        assert!(
+            outgoing[0]["params"]["diagnostics"]
+                .as_array()
+                .unwrap()
+                .is_empty()
+        );
+
+        let hover = server
+            .handle(request(
+                Some(1),
+                "textDocument/hover",
+                json!({
+                    "textDocument": { "uri": uri },
+                    "position": { "line": 1, "character": 9 },
+                }),
+            ))
+            .await;
+        let contents = hover[0]["result"]["contents"]["value"].as_str().unwrap();
+        assert!(contents.starts_with("**$gte**"));
+
+        let edits = server
+            .handle(request(
+                Some(2),
+                "textDocument/formatting",
+                json!({
+                    "textDocument": { "uri": uri },
+                    "options": { "tabSize": 4, "insertSpaces": true },
+                }),
+            ))
+            .await;
+        assert_eq!(
+            edits[0]["result"][0]["newText"],
+            "db.orders.find({\n    qty: { $gte: 1 } })\n"
+        );
+
+        let unknown = server
+            .handle(request(Some(3), "workspace/symbol", json!({})))
+            .await;
+        assert_eq!(unknown[0]["error"]["code"], METHOD_NOT_FOUND);
+    }
+}
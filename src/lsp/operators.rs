@@ -0,0 +1,513 @@
+//! Hover documentation for query, update and aggregation operators
+
+/// Documentation of one `$` operator
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OperatorDoc {
+    /// Operator name including the `$`
+    pub name: &'static str,
+
+    /// Where the operator is used: query, update, stage or expression
+    pub kind: &'static str,
+
+    /// Usage example
+    pub syntax: &'static str,
+
+    /// One-line description
+    pub description: &'static str,
+}
+
+impl OperatorDoc {
+    /// Render as Markdown for a hover
+    pub fn to_markdown(&self) -> String {
+        format!(
+            "**{}** ({} operator)\n\n{}\n\n```javascript\n{}\n```",
+            self.name, self.kind, self.description, self.syntax
+        )
+    }
+}
+
+/// Look up an operator by name, e.g. `$gte`
+pub fn find(name: &str) -> Option<&'static OperatorDoc> {
+    OPERATORS.iter().find(|op| op.name == name)
+}
+
+const fn op(
+    name: &'static str,
+    kind: &'static str,
+    syntax: &'static str,
+    description: &'static str,
+) -> OperatorDoc {
+    OperatorDoc {
+        name,
+        kind,
+        syntax,
+        description,
+    }
+}
+
+/// Documented operators
+pub const OPERATORS: &[OperatorDoc] = &[
+    // Query comparison
+    op(
+        "$eq",
+        "query",
+        "{ field: { $eq: value } }",
+        "Matches values equal to a value.",
+    ),
+    op(
+        "$ne",
+        "query",
+        "{ field: { $ne: value } }",
+        "Matches values not equal to a value, including missing fields.",
+    ),
+    op(
+        "$gt",
+        "query",
+        "{ field: { $gt: value } }",
+        "Matches values greater than a value.",
+    ),
+    op(
+        "$gte",
+        "query",
+        "{ field: { $gte: value } }",
+        "Matches values greater than or equal to a value.",
+    ),
+    op(
+        "$lt",
+        "query",
+        "{ field: { $lt: value } }",
+        "Matches values less than a value.",
+    ),
+    op(
+        "$lte",
+        "query",
+        "{ field: { $lte: value } }",
+        "Matches values less than or equal to a value.",
+    ),
+    op(
+        "$in",
+        "query",
+        "{ field: { $in: [v1, v2] } }",
+        "Matches any of the values in an array.",
+    ),
+    op(
+        "$nin",
+        "query",
+        "{ field: { $nin: [v1, v2] } }",
+        "Matches none of the values in an array, including missing fields.",
+    ),
+    // Query logical
+    op(
+        "$and",
+        "query",
+        "{ $and: [ { a: 1 }, { b: 2 } ] }",
+        "Matches documents that satisfy all clauses.",
+    ),
+    op(
+        "$or",
+        "query",
+        "{ $or: [ { a: 1 }, { b: 2 } ] }",
+        "Matches documents that satisfy at least one clause.",
+    ),
+    op(
+        "$nor",
+        "query",
+        "{ $nor: [ { a: 1 }, { b: 2 } ] }",
+        "Matches documents that fail all clauses.",
+    ),
+    op(
+        "$not",
+        "query",
+        "{ field: { $not: { $gt: 5 } } }",
+        "Inverts the effect of an operator expression.",
+    ),
+    // Query element and evaluation
+    op(
+        "$exists",
+        "query",
+        "{ field: { $exists: true } }",
+        "Matches documents that have (or lack) the field.",
+    ),
+    op(
+        "$type",
+        "query",
+        "{ field: { $type: \"string\" } }",
+        "Matches values of a BSON type.",
+    ),
+    op(
+        "$regex",
+        "query",
+        "{ field: { $regex: /^a/, $options: \"i\" } }",
+        "Matches strings against a regular expression.",
+    ),
+    op(
+        "$expr",
+        "query",
+        "{ $expr: { $gt: [\"$spent\", \"$budget\"] } }",
+        "Uses aggregation expressions in a query.",
+    ),
+    op(
+        "$jsonSchema",
+        "query",
+        "{ $jsonSchema: { required: [\"name\"] } }",
+        "Matches documents against a JSON Schema.",
+    ),
+    op(
+        "$mod",
+        "query",
+        "{ qty: { $mod: [4, 0] } }",
+        "Matches values whose remainder by a divisor equals a value.",
+    ),
+    op(
+        "$text",
+        "query",
+        "{ $text: { $search: \"coffee\" } }",
+        "Performs a text search on a text-indexed collection.",
+    ),
+    op(
+        "$where",
+        "query",
+        "{ $where: \"this.a > this.b\" }",
+        "Matches documents against a JavaScript expression.",
+    ),
+    // Query array
+    op(
+        "$all",
+        "query",
+        "{ tags: { $all: [\"a\", \"b\"] } }",
+        "Matches arrays that contain all the values.",
+    ),
+    op(
+        "$elemMatch",
+        "query",
+        "{ results: { $elemMatch: { $gte: 80, $lt: 85 } } }",
+        "Matches arrays with an element that satisfies all conditions.",
+    ),
+    op(
+        "$size",
+        "query",
+        "{ tags: { $size: 2 } }",
+        "Matches arrays with the given number of elements.",
+    ),
+    // Update
+    op(
+        "$set",
+        "update",
+        "{ $set: { status: \"done\" } }",
+        "Sets the value of fields; in a pipeline, adds fields.",
+    ),
+    op(
+        "$unset",
+        "update",
+        "{ $unset: { temp: \"\" } }",
+        "Removes fields.",
+    ),
+    op(
+        "$inc",
+        "update",
+        "{ $inc: { count: 1 } }",
+        "Increments a field by a number.",
+    ),
+    op(
+        "$mul",
+        "update",
+        "{ $mul: { price: 1.1 } }",
+        "Multiplies a field by a number.",
+    ),
+    op(
+        "$rename",
+        "update",
+        "{ $rename: { old: \"new\" } }",
+        "Renames a field.",
+    ),
+    op(
+        "$min",
+        "update",
+        "{ $min: { low: 10 } }",
+        "Updates a field only if the value is lower; in $group, the minimum.",
+    ),
+    op(
+        "$max",
+        "update",
+        "{ $max: { high: 90 } }",
+        "Updates a field only if the value is higher; in $group, the maximum.",
+    ),
+    op(
+        "$currentDate",
+        "update",
+        "{ $currentDate: { updatedAt: true } }",
+        "Sets a field to the current date.",
+    ),
+    op(
+        "$setOnInsert",
+        "update",
+        "{ $setOnInsert: { createdAt: new Date() } }",
+        "Sets fields only when an upsert inserts a document.",
+    ),
+    op(
+        "$push",
+        "update",
+        "{ $push: { tags: \"new\" } }",
+        "Appends a value to an array; in $group, collects values.",
+    ),
+    op(
+        "$addToSet",
+        "update",
+        "{ $addToSet: { tags: \"new\" } }",
+        "Adds a value to an array unless present; in $group, collects unique values.",
+    ),
+    op(
+        "$pull",
+        "update",
+        "{ $pull: { tags: \"old\" } }",
+        "Removes array elements matching a value or condition.",
+    ),
+    op(
+        "$pop",
+        "update",
+        "{ $pop: { queue: -1 } }",
+        "Removes the first (-1) or last (1) array element.",
+    ),
+    op(
+        "$each",
+        "update",
+        "{ $push: { tags: { $each: [\"a\", \"b\"] } } }",
+        "Adds several values with $push or $addToSet.",
+    ),
+    // Aggregation stages
+    op(
+        "$match",
+        "stage",
+        "{ $match: { status: \"A\" } }",
+        "Filters documents with a query.",
+    ),
+    op(
+        "$project",
+        "stage",
+        "{ $project: { name: 1, _id: 0 } }",
+        "Includes, excludes or computes fields.",
+    ),
+    op(
+        "$group",
+        "stage",
+        "{ $group: { _id: \"$status\", total: { $sum: \"$amount\" } } }",
+        "Groups documents by a key and computes accumulators.",
+    ),
+    op(
+        "$sort",
+        "stage",
+        "{ $sort: { createdAt: -1 } }",
+        "Orders documents by fields.",
+    ),
+    op(
+        "$limit",
+        "stage",
+        "{ $limit: 10 }",
+        "Passes the first n documents.",
+    ),
+    op(
+        "$skip",
+        "stage",
+        "{ $skip: 10 }",
+        "Skips the first n documents.",
+    ),
+    op(
+        "$unwind",
+        "stage",
+        "{ $unwind: \"$items\" }",
+        "Outputs one document per array element.",
+    ),
+    op(
+        "$lookup",
+        "stage",
+        "{ $lookup: { from: \"orders\", localField: \"_id\", foreignField: \"userId\", as: \"orders\" } }",
+        "Joins documents from another collection.",
+    ),
+    op(
+        "$addFields",
+        "stage",
+        "{ $addFields: { total: { $add: [\"$a\", \"$b\"] } } }",
+        "Adds computed fields.",
+    ),
+    op(
+        "$count",
+        "stage",
+        "{ $count: \"total\" }",
+        "Outputs the number of documents.",
+    ),
+    op(
+        "$facet",
+        "stage",
+        "{ $facet: { byStatus: [ { $sortByCount: \"$status\" } ] } }",
+        "Runs several pipelines on the same input.",
+    ),
+    op(
+        "$bucket",
+        "stage",
+        "{ $bucket: { groupBy: \"$price\", boundaries: [0, 100, 200] } }",
+        "Groups documents into ranges.",
+    ),
+    op(
+        "$sortByCount",
+        "stage",
+        "{ $sortByCount: \"$status\" }",
+        "Groups by a value and sorts by count.",
+    ),
+    op(
+        "$replaceRoot",
+        "stage",
+        "{ $replaceRoot: { newRoot: \"$doc\" } }",
+        "Replaces each document with an embedded one.",
+    ),
+    op(
+        "$out",
+        "stage",
+        "{ $out: \"results\" }",
+        "Writes the results to a collection; must be the last stage.",
+    ),
+    op(
+        "$merge",
+        "stage",
+        "{ $merge: { into: \"results\" } }",
+        "Merges the results into a collection; must be the last stage.",
+    ),
+    op(
+        "$sample",
+        "stage",
+        "{ $sample: { size: 5 } }",
+        "Picks random documents.",
+    ),
+    op(
+        "$search",
+        "stage",
+        "{ $search: { text: { query: \"coffee\", path: \"name\" } } }",
+        "Runs an Atlas Search query; must be the first stage.",
+    ),
+    op(
+        "$vectorSearch",
+        "stage",
+        "{ $vectorSearch: { index, path, queryVector, numCandidates, limit } }",
+        "Runs an Atlas Vector Search query; must be the first stage.",
+    ),
+    // Expressions and accumulators
+    op(
+        "$sum",
+        "expression",
+        "{ $sum: \"$amount\" }",
+        "Sums numbers; `{ $sum: 1 }` counts documents in $group.",
+    ),
+    op(
+        "$avg",
+        "expression",
+        "{ $avg: \"$score\" }",
+        "Averages numbers.",
+    ),
+    op(
+        "$first",
+        "expression",
+        "{ $first: \"$name\" }",
+        "First value in a group, or first array element.",
+    ),
+    op(
+        "$last",
+        "expression",
+        "{ $last: \"$name\" }",
+        "Last value in a group, or last array element.",
+    ),
+    op(
+        "$add",
+        "expression",
+        "{ $add: [\"$a\", \"$b\"] }",
+        "Adds numbers, or a number of milliseconds to a date.",
+    ),
+    op(
+        "$subtract",
+        "expression",
+        "{ $subtract: [\"$a\", \"$b\"] }",
+        "Subtracts numbers or dates.",
+    ),
+    op(
+        "$multiply",
+        "expression",
+        "{ $multiply: [\"$price\", \"$qty\"] }",
+        "Multiplies numbers.",
+    ),
+    op(
+        "$divide",
+        "expression",
+        "{ $divide: [\"$total\", \"$count\"] }",
+        "Divides numbers.",
+    ),
+    op(
+        "$concat",
+        "expression",
+        "{ $concat: [\"$first\", \" \", \"$last\"] }",
+        "Concatenates strings.",
+    ),
+    op(
+        "$cond",
+        "expression",
+        "{ $cond: { if: cond, then: a, else: b } }",
+        "Returns one of two values depending on a condition.",
+    ),
+    op(
+        "$ifNull",
+        "expression",
+        "{ $ifNull: [\"$nickname\", \"$name\"] }",
+        "Returns the first value that is not null or missing.",
+    ),
+    op(
+        "$dateToString",
+        "expression",
+        "{ $dateToString: { format: \"%Y-%m-%d\", date: \"$at\" } }",
+        "Formats a date as a string.",
+    ),
+    op(
+        "$dateTrunc",
+        "expression",
+        "{ $dateTrunc: { date: \"$at\", unit: \"day\" } }",
+        "Truncates a date to a unit.",
+    ),
+    op(
+        "$toString",
+        "expression",
+        "{ $toString: \"$_id\" }",
+        "Converts a value to a string.",
+    ),
+    op(
+        "$filter",
+        "expression",
+        "{ $filter: { input: \"$items\", cond: { $gt: [\"$$this.qty\", 0] } } }",
+        "Keeps array elements that match a condition.",
+    ),
+    op(
+        "$map",
+        "expression",
+        "{ $map: { input: \"$items\", in: \"$$this.price\" } }",
+        "Applies an expression to each array element.",
+    ),
+    op(
+        "$function",
+        "expression",
+        "{ $function: { body: function (x) { ... }, args: [\"$x\"], lang: \"js\" } }",
+        "Runs a JavaScript function.",
+    ),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find() {
+        assert_eq!(find("$gte").unwrap().kind, "query");
+        assert!(
+            find("$lookup")
+                .unwrap()
+                .to_markdown()
+                .starts_with("**$lookup** (stage operator)")
+        );
+        assert!(find("gte").is_none());
+        assert!(find("$nope").is_none());
+    }
+}
@@ -21,26 +21,41 @@
 //! ```
 
 use indicatif::{ProgressBar, ProgressStyle};
+use std::future::Future;
+use std::pin::Pin;
 use std::sync::Arc;
 use tracing::Level;
+use tracing_subscriber::fmt::writer::BoxMakeWriter;
 
 mod cli;
 mod config;
 mod connection;
+mod crash;
 mod error;
 mod executor;
 mod formatter;
+mod http;
+mod lsp;
 mod mcp;
+mod metrics;
 mod parser;
+#[cfg(feature = "plugins")]
+#[allow(dead_code)] // plugins are loaded by applications built on the library
+mod plugin;
 mod repl;
+mod rpc;
+mod serve;
 
 use cli::CliInterface;
 
 use connection::ConnectionManager;
-use error::Result;
+use error::{MongoshError, Result};
 use executor::{CommandRouter, ExecutionContext};
-use formatter::Formatter;
+use formatter::{ErrorFormatter, Formatter, LogFormatter, TerminalSink};
 
+use repl::recording::{EntryKind, Recorder};
+use repl::report::MarkdownReport;
+use repl::script::ScriptLoader;
 use repl::{ReplEngine, SharedState};
 
 /// Application entry point
@@ -70,16 +85,63 @@ async fn run() -> Result<()> {
     // Initialize logging based on verbosity
     initialize_logging(&cli);
 
+    // Write a local crash report if anything panics from here on
+    crash::install(cli.config());
+
+    // Print a saved crash report for sharing
+    if let Some(report) = &cli.args().report {
+        let path = (!report.is_empty()).then(|| std::path::Path::new(report.as_str()));
+        return crash::print_report(path);
+    }
+
     // Handle subcommands (version, completion, config)
     if cli.handle_subcommand().await? {
         return Ok(());
     }
 
+    // Dump or restore a database and exit
+    if let Some(command) = cli.backup_command() {
+        return run_backup(&cli, command).await;
+    }
+
+    // Serve Prometheus metrics in the background for the rest of the run
+    if let Some(port) = cli.args().metrics_port {
+        metrics::serve(port).await?;
+    }
+
+    // Re-run a session recording and exit
+    if let Some(file) = cli.replay_file() {
+        return run_replay_mode(&cli, file).await;
+    }
+
+    // Serve the REST bridge until stopped
+    if let Some(options) = cli.serve_options() {
+        return run_serve_mode(&cli, options).await;
+    }
+
     // Check if MCP mode is enabled
     if cli.args().mcp {
         return run_mcp_server(&cli).await;
     }
 
+    // Run a language server for script files until the editor exits
+    if cli.is_lsp() {
+        return run_lsp_mode(&cli).await;
+    }
+
+    // Answer JSON-RPC requests from an editor until stdin is closed
+    if cli.args().rpc {
+        return run_rpc_mode(&cli).await;
+    }
+
+    // Run a script file or --eval code non-interactively
+    if let Some(file) = &cli.args().file {
+        return run_script_mode(&cli, ScriptSource::File(file)).await;
+    }
+    if let Some(code) = &cli.args().eval {
+        return run_script_mode(&cli, ScriptSource::Eval(code)).await;
+    }
+
     // Print banner if not in quiet mode
     cli.print_banner();
 
@@ -111,6 +173,8 @@ async fn run_mcp_server(cli: &CliInterface) -> Result<()> {
     // Initialize shared state
     let database = cli.get_database();
     let shared_state = SharedState::with_config(database, &cli.config().display);
+    shared_state.set_query_config(cli.config().query.clone());
+    shared_state.set_safety_config(cli.config().safety.clone());
 
     // Get MCP security configuration from config file
     let security_config = cli
@@ -160,6 +224,176 @@ async fn run_interactive_mode(cli: &CliInterface) -> Result<()> {
     Ok(())
 }
 
+/// Code run by `--file` or `--eval`
+enum ScriptSource<'a> {
+    /// Script file
+    File(&'a std::path::Path),
+
+    /// Statements given on the command line
+    Eval(&'a str),
+}
+
+/// Run a script file (--file) or command line code (--eval) and exit
+///
+/// The script's arguments are exposed as `args` and `process.argv`. Ctrl+C
+/// stops the script after the running statement.
+async fn run_script_mode(cli: &CliInterface, source: ScriptSource<'_>) -> Result<()> {
+    let (conn_manager, server_version) = setup_connection(cli).await?;
+    let shared_state = initialize_shared_state(cli, server_version)?;
+    let config_path = cli.config_path().map(|p| p.to_path_buf());
+    let exec_context =
+        create_execution_context(conn_manager, shared_state.clone(), config_path).await?;
+
+    let path = match source {
+        ScriptSource::File(file) => file.to_string_lossy().to_string(),
+        ScriptSource::Eval(_) => "--eval".to_string(),
+    };
+    repl::script::set_argv(&path, &cli.args().script_args);
+
+    let cancel_token = exec_context.get_cancel_token();
+    let ctrl_c_handle = tokio::spawn(async move {
+        if tokio::signal::ctrl_c().await.is_ok() {
+            cancel_token.cancel();
+        }
+    });
+
+    let mut loader = ScriptLoader::new();
+    let result = match source {
+        ScriptSource::File(_) => {
+            run_script(cli, &exec_context, &shared_state, &mut loader, &path).await
+        }
+        ScriptSource::Eval(code) => {
+            let statements = repl::script::split_statements(code);
+            let origin = std::path::Path::new(&path);
+            run_statements(
+                cli,
+                &exec_context,
+                &shared_state,
+                &mut loader,
+                origin,
+                statements,
+            )
+            .await
+        }
+    };
+    ctrl_c_handle.abort();
+    result
+}
+
+/// Re-run the commands of a session recording and exit (`mongosh replay`)
+///
+/// Each command is echoed before its output. Failing commands are reported
+/// and the replay goes on, as the recorded session did. Ctrl+C stops it.
+async fn run_replay_mode(cli: &CliInterface, file: &std::path::Path) -> Result<()> {
+    let entries = repl::recording::load(file)?;
+    let (conn_manager, server_version) = setup_connection(cli).await?;
+    let shared_state = initialize_shared_state(cli, server_version)?;
+    let config_path = cli.config_path().map(|p| p.to_path_buf());
+    let exec_context =
+        create_execution_context(conn_manager, shared_state.clone(), config_path).await?;
+
+    let cancel_token = exec_context.get_cancel_token();
+    let ctrl_c_handle = tokio::spawn(async move {
+        if tokio::signal::ctrl_c().await.is_ok() {
+            cancel_token.cancel();
+        }
+    });
+
+    let mut parser = parser::Parser::new();
+    parser.set_environment(std::env::vars().collect());
+    for entry in entries.iter().filter(|e| e.kind == EntryKind::Input) {
+        if exec_context.get_cancel_token().is_cancelled() {
+            eprintln!("Replay interrupted");
+            break;
+        }
+
+        println!("> {}", entry.text);
+        parser.set_last_result(shared_state.get_last_result_value());
+        match parse_with_prompts(&mut parser, &entry.text) {
+            Ok(parser::Command::Exit) => break,
+            Ok(
+                parser::Command::Retry
+                | parser::Command::AiQuery(_)
+                | parser::Command::Record(_)
+                | parser::Command::Report(_),
+            ) => println!("(skipped, cannot be replayed)"),
+            Ok(parser::Command::Load(path)) => {
+                let mut loader = ScriptLoader::new();
+                let outcome =
+                    run_script(cli, &exec_context, &shared_state, &mut loader, &path).await;
+                if let Err(e) = outcome {
+                    eprintln!("{}", e);
+                }
+            }
+            Ok(command) => {
+                execute_and_display(cli, &exec_context, &shared_state, command).await;
+            }
+            Err(e) => eprintln!("{}", e),
+        }
+    }
+
+    ctrl_c_handle.abort();
+    Ok(())
+}
+
+/// Answer REST requests until stopped (`mongosh serve`)
+async fn run_serve_mode(cli: &CliInterface, options: serve::ServeOptions) -> Result<()> {
+    let (conn_manager, server_version) = setup_connection(cli).await?;
+    let shared_state = initialize_shared_state(cli, server_version)?;
+    let config_path = cli.config_path().map(|p| p.to_path_buf());
+    let exec_context = create_execution_context(conn_manager, shared_state, config_path).await?;
+
+    tokio::select! {
+        result = serve::run(exec_context, options) => result,
+        _ = tokio::signal::ctrl_c() => Ok(()),
+    }
+}
+
+/// Answer JSON-RPC requests on stdin until it is closed (`mongosh --rpc`)
+async fn run_rpc_mode(cli: &CliInterface) -> Result<()> {
+    let (conn_manager, server_version) = setup_connection(cli).await?;
+    let shared_state = initialize_shared_state(cli, server_version)?;
+    let config_path = cli.config_path().map(|p| p.to_path_buf());
+    let exec_context = create_execution_context(conn_manager, shared_state, config_path).await?;
+
+    rpc::run(exec_context).await
+}
+
+/// Run the language server on stdio (`mongosh lsp`)
+///
+/// Completion of collection names needs a connection; when the server cannot
+/// be reached the language server runs without one.
+async fn run_lsp_mode(cli: &CliInterface) -> Result<()> {
+    let (shared_state, exec_context) = match setup_connection(cli).await {
+        Ok((conn_manager, server_version)) => {
+            let shared_state = initialize_shared_state(cli, server_version)?;
+            let config_path = cli.config_path().map(|p| p.to_path_buf());
+            let exec_context =
+                create_execution_context(conn_manager, shared_state.clone(), config_path).await?;
+            (shared_state, Some(exec_context))
+        }
+        Err(e) => {
+            tracing::warn!("Language server running without a connection: {}", e);
+            (initialize_shared_state(cli, None)?, None)
+        }
+    };
+
+    lsp::run(shared_state, exec_context).await
+}
+
+/// Run a `dump` or `restore` subcommand and exit
+async fn run_backup(cli: &CliInterface, command: parser::Command) -> Result<()> {
+    let (conn_manager, server_version) = setup_connection(cli).await?;
+    let shared_state = initialize_shared_state(cli, server_version)?;
+    let config_path = cli.config_path().map(|p| p.to_path_buf());
+    let exec_context =
+        create_execution_context(conn_manager, shared_state.clone(), config_path).await?;
+
+    let result = exec_context.execute(command).await?;
+    display_result(cli, &shared_state, &result);
+    Ok(())
+}
+
 /// Setup connection to MongoDB
 async fn setup_connection(cli: &CliInterface) -> Result<(ConnectionManager, Option<String>)> {
     let uri = cli.get_connection_uri();
@@ -171,17 +405,137 @@ async fn setup_connection(cli: &CliInterface) -> Result<(ConnectionManager, Opti
 
     conn_manager.connect().await?;
 
-    let version = conn_manager.get_client().ok().and_then(|client| {
-        futures::executor::block_on(conn_manager.get_server_version(client)).ok()
-    });
+    let version = match authenticate_at_startup(&mut conn_manager).await {
+        Ok(()) => conn_manager.get_client().ok().and_then(|client| {
+            futures::executor::block_on(conn_manager.get_server_version(client)).ok()
+        }),
+        Err(e) if e.is_authentication_error() => return Err(e),
+        Err(e) => {
+            eprintln!("Warning: could not reach the server: {}", e);
+            None
+        }
+    };
 
     if let Some(ref ver) = version {
         cli.print_connection_info(ver);
+        print_startup_warnings(cli, &conn_manager).await;
     }
 
     Ok((conn_manager, version))
 }
 
+/// Print a summary of server startup warnings and recent deprecation notices
+///
+/// Skipped in quiet mode or when `display.show_startup_warnings` is off.
+/// Failures (e.g. missing `getLog` privileges) are ignored.
+async fn print_startup_warnings(cli: &CliInterface, conn_manager: &ConnectionManager) {
+    if cli.args().quiet || !cli.config().display.show_startup_warnings {
+        return;
+    }
+    let Ok(client) = conn_manager.get_client() else {
+        return;
+    };
+    let admin = client.database("admin");
+
+    let mut lines = fetch_log(&admin, "startupWarnings").await;
+    lines.extend(
+        fetch_log(&admin, "global")
+            .await
+            .into_iter()
+            .filter(|line| line.to_lowercase().contains("deprecat")),
+    );
+
+    let use_colors = cli.config().display.color_output;
+    if let Some(summary) = LogFormatter::new(use_colors).summarize("Server warnings", &lines) {
+        println!("{}", summary);
+    }
+}
+
+/// Fetch the lines of a server log, returning nothing on failure
+async fn fetch_log(admin: &mongodb::Database, name: &str) -> Vec<String> {
+    match admin.run_command(mongodb::bson::doc! { "getLog": name }).await {
+        Ok(result) => result
+            .get_array("log")
+            .map(|lines| {
+                lines
+                    .iter()
+                    .filter_map(|line| line.as_str().map(String::from))
+                    .collect()
+            })
+            .unwrap_or_default(),
+        Err(e) => {
+            tracing::debug!("getLog {} failed: {}", name, e);
+            Vec::new()
+        }
+    }
+}
+
+/// Maximum number of credential re-prompts after an authentication failure
+const MAX_AUTH_ATTEMPTS: usize = 3;
+
+/// Verify credentials right after connecting, re-prompting on rejection
+///
+/// Errors other than authentication failures (e.g. no reachable primary) are
+/// returned without prompting; the caller reports them and keeps the shell
+/// open so they can be retried.
+///
+/// # Returns
+/// * `Result<()>` - Ok once the server answered the ping
+async fn authenticate_at_startup(conn_manager: &mut ConnectionManager) -> Result<()> {
+    let mut attempts = 0;
+
+    loop {
+        let err = match conn_manager.verify_authentication().await {
+            Ok(()) => return Ok(()),
+            Err(e) if e.is_authentication_error() => e,
+            Err(e) => return Err(e),
+        };
+
+        if attempts >= MAX_AUTH_ATTEMPTS || !cli::prompt::is_interactive() {
+            return Err(err);
+        }
+        attempts += 1;
+
+        let username = conn_manager.username().await;
+        match cli::prompt::prompt_credentials(username.as_deref())? {
+            Some(credential) => {
+                conn_manager.set_credential(credential);
+                conn_manager.reconnect().await?;
+            }
+            None => return Err(err),
+        }
+    }
+}
+
+/// Offer to re-enter credentials after a command failed authentication
+///
+/// # Returns
+/// * `Result<bool>` - True once the connection is re-authenticated
+async fn reauthenticate_in_session(
+    exec_context: &ExecutionContext,
+    error: &MongoshError,
+) -> Result<bool> {
+    if !cli::prompt::is_interactive() {
+        return Ok(false);
+    }
+
+    tracing::debug!("Command failed authentication: {}", error);
+    for _ in 0..MAX_AUTH_ATTEMPTS {
+        let username = exec_context.get_current_username().await;
+        let Some(credential) = cli::prompt::prompt_credentials(username.as_deref())? else {
+            return Ok(false);
+        };
+
+        match exec_context.reauthenticate(credential).await {
+            Ok(()) => return Ok(true),
+            Err(e) if e.is_authentication_error() => eprintln!("{}", e),
+            Err(e) => return Err(e),
+        }
+    }
+
+    Ok(false)
+}
+
 /// Initialize shared state with configuration
 fn initialize_shared_state(
     cli: &CliInterface,
@@ -190,6 +544,7 @@ fn initialize_shared_state(
     let database = cli.get_database();
     let mut shared_state = SharedState::with_config(database, &cli.config().display);
     shared_state.set_connected(server_version);
+    shared_state.set_settings(cli.config().clone());
 
     if cli.args().no_color {
         shared_state.set_color_enabled(false);
@@ -241,6 +596,9 @@ async fn run_repl_loop(
     exec_context: &ExecutionContext,
     shared_state: &SharedState,
 ) -> Result<()> {
+    // Last command that failed, re-run by `retry`
+    let mut last_failed: Option<parser::Command> = None;
+
     while repl.is_running() {
         // Reset cancellation token for each command
         let mut context_clone = exec_context.clone();
@@ -251,19 +609,47 @@ async fn run_repl_loop(
             Some(_) => continue,
             None => break,
         };
+        crash::record_command(&input);
 
         let command = match repl.process_input(&input) {
             Ok(cmd) => cmd,
             Err(e) => {
+                shared_state.record(EntryKind::Input, &input);
+                shared_state.record(EntryKind::Error, &e.to_string());
                 eprintln!("{}", e);
                 continue;
             }
         };
 
+        if let parser::Command::Record(path) = &command {
+            toggle_recording(shared_state, path.as_deref());
+            continue;
+        }
+        if let parser::Command::Report(path) = &command {
+            toggle_report(shared_state, path.as_deref());
+            continue;
+        }
+        shared_state.record(EntryKind::Input, &input);
+
         if matches!(command, parser::Command::Exit) {
             break;
         }
 
+        let is_retry = matches!(command, parser::Command::Retry);
+        let command = if is_retry {
+            let Some(failed) = last_failed.clone() else {
+                eprintln!("No failed command to retry");
+                continue;
+            };
+            if !confirm_retry(&failed) {
+                println!("Retry cancelled");
+                continue;
+            }
+            failed
+        } else {
+            command
+        };
+
         // Handle AI query generation: plan → step loop → execute
         if let parser::Command::AiQuery(description) = command {
             let ai_config = cli.config().ai.clone();
@@ -280,7 +666,8 @@ async fn run_repl_loop(
             let database = shared_state.get_database();
 
             // ── Phase 1: Plan ───────────────────────────────────────
-            let spinner = make_spinner("🤖 Analyzing...");
+            let accessible = cli.config().display.accessible;
+            let spinner = make_spinner("🤖 Analyzing...", accessible);
 
             let plan = match generator.plan(&description, &database).await {
                 Ok(p) => p,
@@ -310,7 +697,7 @@ async fn run_repl_loop(
                 }
 
                 // 2b. Generate query for this step (with previous results)
-                let step_spinner = make_spinner("🤖 Generating query...");
+                let step_spinner = make_spinner("🤖 Generating query...", accessible);
                 let query = match generator
                     .generate_step(&plan, step, &previous_results, &database)
                     .await
@@ -389,7 +776,25 @@ async fn run_repl_loop(
             }
         });
 
-        execute_and_display(cli, &context_clone, shared_state, command).await;
+        let succeeded = if let parser::Command::Load(path) = &command {
+            let mut loader = ScriptLoader::new();
+            match run_script(cli, &context_clone, shared_state, &mut loader, path).await {
+                Ok(()) => true,
+                Err(e) => {
+                    shared_state.record(EntryKind::Error, &e.to_string());
+                    eprintln!("{}", e);
+                    false
+                }
+            }
+        } else {
+            execute_and_display(cli, &context_clone, shared_state, command.clone()).await
+        };
+
+        if !succeeded {
+            last_failed = Some(command);
+        } else if is_retry {
+            last_failed = None;
+        }
 
         // Cancel the Ctrl+C listener for the next command
         ctrl_c_handle.abort();
@@ -398,33 +803,284 @@ async fn run_repl_loop(
     Ok(())
 }
 
+/// Start or stop writing the session transcript (`record start <file>` / `record stop`)
+///
+/// Starting a new recording ends the active one.
+fn toggle_recording(shared_state: &SharedState, path: Option<&str>) {
+    let previous = shared_state.stop_recording();
+    if let Some(recorder) = &previous {
+        println!(
+            "Recorded {} command(s) to {}",
+            recorder.commands(),
+            recorder.path().display()
+        );
+    }
+
+    match path {
+        Some(path) => match Recorder::create(std::path::Path::new(path)) {
+            Ok(recorder) => {
+                shared_state.start_recording(recorder);
+                println!("Recording to {}, stop with `record stop`", path);
+            }
+            Err(e) => eprintln!("{}", e),
+        },
+        None if previous.is_none() => eprintln!("Not recording"),
+        None => {}
+    }
+}
+
+/// Open or close the Markdown report (`report start <file>` / `report stop`)
+///
+/// Opening a new report closes the open one.
+fn toggle_report(shared_state: &SharedState, path: Option<&str>) {
+    let previous = shared_state.stop_report();
+    if let Some(report) = &previous {
+        println!(
+            "Wrote {} command(s) to {}",
+            report.commands(),
+            report.path().display()
+        );
+    }
+
+    match path {
+        Some(path) => {
+            match MarkdownReport::create(std::path::Path::new(path), &shared_state.get_database()) {
+                Ok(report) => {
+                    shared_state.start_report(report);
+                    println!("Writing report to {}, stop with `report stop`", path);
+                }
+                Err(e) => eprintln!("{}", e),
+            }
+        }
+        None if previous.is_none() => eprintln!("No report open"),
+        None => {}
+    }
+}
+
+/// Ask before re-running a failed write that is not safe to repeat
+///
+/// # Arguments
+/// * `command` - Command about to be retried
+///
+/// # Returns
+/// * `bool` - True if the command should run again
+fn confirm_retry(command: &parser::Command) -> bool {
+    let Some(hint) = executor::retry_hint(command) else {
+        return true;
+    };
+
+    eprintln!("This write may not be safe to repeat: {}", hint);
+    if !cli::prompt::is_interactive() {
+        return true;
+    }
+    cli::prompt::read_line("Run it again anyway? (yes/no): ")
+        .is_ok_and(|answer| matches!(answer.to_lowercase().as_str(), "yes" | "y"))
+}
+
 /// Execute command and display result
+///
+/// A command failing with a network or primary change error is run once
+/// more when `connection.auto_retry_reads` / `auto_retry_writes` allow it.
+///
+/// # Returns
+/// * `bool` - True if the command succeeded
 async fn execute_and_display(
     cli: &CliInterface,
     exec_context: &ExecutionContext,
     shared_state: &SharedState,
     command: parser::Command,
-) {
+) -> bool {
     let is_config_cmd = matches!(command, parser::Command::Config(_));
+    let format_override = command.format_override();
+    let fields = command.pluck_fields().map(<[String]>::to_vec);
+    let full = command.full_values();
     let is_execute_named_query = matches!(
         command,
-        parser::Command::Config(parser::ConfigCommand::ExecuteNamedQuery { .. })
+        parser::Command::Config(
+            parser::ConfigCommand::ExecuteNamedQuery { .. } | parser::ConfigCommand::RunBookmark(_)
+        )
     );
 
-    match exec_context.execute(command).await {
+    let outcome = match exec_context.execute(command.clone()).await {
+        Err(e) if e.is_authentication_error() => {
+            match reauthenticate_in_session(exec_context, &e).await {
+                Ok(true) => exec_context.execute(command.clone()).await,
+                Ok(false) => Err(e),
+                Err(prompt_err) => Err(prompt_err),
+            }
+        }
+        Err(e)
+            if e.is_transient_error()
+                && executor::should_auto_retry(&command, &cli.config().connection) =>
+        {
+            eprintln!("{}\nRetrying once...", e);
+            exec_context.execute(command.clone()).await
+        }
+        other => other,
+    };
+
+    match outcome {
         Ok(result) => {
             if is_execute_named_query {
                 display_result(cli, shared_state, &result);
             } else if is_config_cmd {
                 if let executor::ResultData::Message(msg) = &result.data {
+                    shared_state.record(EntryKind::Output, msg);
                     println!("{}", msg);
                 }
             } else {
-                display_result(cli, shared_state, &result);
+                display_result_as(
+                    cli,
+                    shared_state,
+                    &result,
+                    format_override,
+                    fields.as_deref(),
+                    full,
+                );
+            }
+            true
+        }
+        Err(e) => {
+            let message = ErrorFormatter::new(shared_state.get_color_enabled()).format(&e);
+            shared_state.record(EntryKind::Error, &message);
+            eprintln!("{}", message);
+            if e.is_transient_error() {
+                eprintln!("Type `retry` to run it again");
+            }
+            false
+        }
+    }
+}
+
+/// Parse a statement, asking the questions of its `prompt()` calls first
+fn parse_with_prompts(parser: &mut parser::Parser, text: &str) -> Result<parser::Command> {
+    let answers = cli::prompt::answer_prompts(&parser::prompt_requests(text))?;
+    parser.set_prompt_answers(answers);
+    parser.parse(text)
+}
+
+/// Execute a script file loaded with `load()`
+///
+/// Statements run in the current session in order and stop at the first
+/// error, which is reported with the script path and line. Nested `load()`
+/// calls resolve relative to the including script.
+fn run_script<'a>(
+    cli: &'a CliInterface,
+    exec_context: &'a ExecutionContext,
+    shared_state: &'a SharedState,
+    loader: &'a mut ScriptLoader,
+    path: &'a str,
+) -> Pin<Box<dyn Future<Output = Result<()>> + 'a>> {
+    Box::pin(async move {
+        if path.ends_with(".lua") {
+            return run_lua_script(exec_context, loader, path);
+        }
+
+        let (script, statements) = loader.enter(path)?;
+        let outcome =
+            run_statements(cli, exec_context, shared_state, loader, &script, statements).await;
+        loader.leave();
+        outcome
+    })
+}
+
+/// Execute the statements of a script or of `--eval` in order
+///
+/// Stops at the first error, which is reported with `origin` and the line.
+async fn run_statements(
+    cli: &CliInterface,
+    exec_context: &ExecutionContext,
+    shared_state: &SharedState,
+    loader: &mut ScriptLoader,
+    origin: &std::path::Path,
+    statements: Vec<repl::script::Statement>,
+) -> Result<()> {
+    let mut parser = parser::Parser::new();
+    parser.set_environment(std::env::vars().collect());
+    let mut outcome = Ok(());
+
+    for statement in statements {
+        if exec_context.get_cancel_token().is_cancelled() {
+            outcome = Err(MongoshError::Generic("load(): interrupted".to_string()));
+            break;
+        }
+
+        crash::record_command(&statement.text);
+        parser.set_last_result(shared_state.get_last_result_value());
+        let step = match parse_with_prompts(&mut parser, &statement.text) {
+            Ok(parser::Command::Exit) => break,
+            Ok(parser::Command::Load(nested)) => {
+                run_script(cli, exec_context, shared_state, loader, &nested).await
+            }
+            Ok(parser::Command::AiQuery(_)) => Err(MongoshError::Generic(
+                "ai queries cannot be run from a script".to_string(),
+            )),
+            Ok(command) => {
+                let format_override = command.format_override();
+                let fields = command.pluck_fields().map(<[String]>::to_vec);
+                let full = command.full_values();
+                exec_context.execute(command).await.map(|result| {
+                    display_result_as(
+                        cli,
+                        shared_state,
+                        &result,
+                        format_override,
+                        fields.as_deref(),
+                        full,
+                    )
+                })
             }
+            Err(e) => Err(e),
+        };
+
+        if let Err(e) = step {
+            outcome = Err(MongoshError::Generic(format!(
+                "{}:{}: {}",
+                origin.display(),
+                statement.line,
+                e
+            )));
+            break;
         }
-        Err(e) => eprintln!("{}", e),
     }
+
+    outcome
+}
+
+/// Execute a Lua script loaded with `load()` or `--file`
+///
+/// Commands the script runs through `db` share the session, but their results
+/// are returned to the script instead of being displayed.
+#[cfg(feature = "lua")]
+fn run_lua_script(
+    exec_context: &ExecutionContext,
+    loader: &ScriptLoader,
+    path: &str,
+) -> Result<()> {
+    let script = loader.resolve(path);
+    let source = std::fs::read_to_string(&script).map_err(|e| {
+        MongoshError::Generic(format!(
+            "load(): cannot read '{}': {}",
+            script.display(),
+            e
+        ))
+    })?;
+
+    let runner = plugin::context_runner(exec_context.clone());
+    let lua = plugin::lua::LuaScript::new(move |input| runner(input), |line| println!("{}", line))?;
+    lua.exec(&script.display().to_string(), &source)
+}
+
+#[cfg(not(feature = "lua"))]
+fn run_lua_script(
+    _exec_context: &ExecutionContext,
+    _loader: &ScriptLoader,
+    path: &str,
+) -> Result<()> {
+    Err(MongoshError::Generic(format!(
+        "load(): cannot run '{}': Lua scripts need mongosh built with the `lua` feature",
+        path
+    )))
 }
 
 /// Display execution result with proper formatting
@@ -433,15 +1089,54 @@ fn display_result(
     shared_state: &SharedState,
     result: &executor::ExecutionResult,
 ) {
-    let mut display_config = cli.config().display.clone();
-    display_config.format = shared_state.get_format();
-    display_config.color_output = shared_state.get_color_enabled();
+    display_result_as(cli, shared_state, result, None, None, false);
+}
 
-    let formatter = Formatter::from_config(&display_config);
+/// Display execution result, optionally in a format other than the current one
+///
+/// # Arguments
+/// * `format_override` - Format requested for this command alone
+/// * `fields` - Fields to show of each document, all fields when `None`
+/// * `full` - Show whole values for this command alone (`.full()`)
+fn display_result_as(
+    cli: &CliInterface,
+    shared_state: &SharedState,
+    result: &executor::ExecutionResult,
+    format_override: Option<config::OutputFormat>,
+    fields: Option<&[String]>,
+    full: bool,
+) {
+    // Nothing to show, e.g. an export to stdout already wrote its data
+    if matches!(&result.data, executor::ResultData::Message(m) if m.is_empty()) {
+        return;
+    }
 
-    match formatter.format(result) {
-        Ok(output) => println!("{}", output),
-        Err(e) => eprintln!("Format error: {}", e),
+    let mut display_config = cli.config().display.clone();
+    display_config.format = format_override.unwrap_or_else(|| shared_state.get_format());
+    display_config.color_output = shared_state.get_color_enabled();
+    display_config.accessible = shared_state.get_accessible();
+    // Collapse, preview and date settings can be changed with `config set` during the session
+    let settings = shared_state.get_settings().display;
+    display_config.collapse_depth = settings.collapse_depth;
+    display_config.collapse_width = settings.collapse_width;
+    display_config.max_string_length = settings.max_string_length;
+    display_config.max_binary_length = settings.max_binary_length;
+    display_config.timezone = settings.timezone;
+    display_config.date_format = settings.date_format;
+    display_config.table_summary = settings.table_summary;
+
+    let formatter = Formatter::from_config(&display_config)
+        .with_full_values(full || shared_state.get_full_values())
+        .with_projection(fields.unwrap_or_default())
+        .with_redaction(&shared_state.get_redaction_config());
+
+    // Colors are stripped when stdout is redirected
+    match formatter.write_to(result, &mut TerminalSink::stdout()) {
+        Ok(output) => shared_state.record(EntryKind::Output, &output),
+        Err(e) => {
+            shared_state.record(EntryKind::Error, &format!("Format error: {}", e));
+            eprintln!("Format error: {}", e);
+        }
     }
 }
 
@@ -450,7 +1145,16 @@ fn display_result(
 /// The spinner ticks automatically in a background thread via `indicatif`'s
 /// steady-tick feature and must be stopped by calling `finish_and_clear()`
 /// once the operation it represents has completed.
-fn make_spinner(msg: &str) -> ProgressBar {
+///
+/// In accessible mode the message is printed once as a plain line and a
+/// hidden progress bar is returned, so screen readers are not flooded with
+/// animation frames.
+fn make_spinner(msg: &str, accessible: bool) -> ProgressBar {
+    if accessible {
+        eprintln!("{}", msg);
+        return ProgressBar::hidden();
+    }
+
     let pb = ProgressBar::new_spinner();
     pb.set_style(
         ProgressStyle::default_spinner()
@@ -476,10 +1180,18 @@ fn initialize_logging(cli: &CliInterface) {
         cli.config().logging.level.to_tracing_level()
     };
 
+    // Keep stdout free for protocol messages
+    let writer = if cli.stdout_is_protocol() {
+        BoxMakeWriter::new(std::io::stderr)
+    } else {
+        BoxMakeWriter::new(std::io::stdout)
+    };
+
     // Build subscriber with level filter
     let subscriber = tracing_subscriber::fmt()
         .with_max_level(level)
-        .with_target(false);
+        .with_target(false)
+        .with_writer(writer);
 
     // Configure timestamps
     if cli.config().logging.timestamps {
@@ -3,10 +3,14 @@
 //! This module provides MCP server functionality for MongoDB Shell,
 //! allowing AI models to interact with MongoDB through natural language.
 
+#[cfg(feature = "mcp")]
 pub mod server;
 pub mod security;
+#[cfg(feature = "mcp")]
 pub mod tools;
+#[cfg(feature = "mcp")]
 pub mod utils;
 
+#[cfg(feature = "mcp")]
 pub use server::MongoShellServer;
 pub use security::SecurityConfig;
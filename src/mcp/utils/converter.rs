@@ -532,6 +532,34 @@ pub fn execution_result_to_mcp_tool_result(result: ExecutionResult) -> CallToolR
                 serde_json::to_string_pretty(&output).unwrap_or_else(|_| "{}".to_string()),
             )])
         }
+        ResultData::Indexes(_)
+        | ResultData::Databases(_)
+        | ResultData::Collections(_)
+        | ResultData::Users(_) => {
+            let key = match &result.data {
+                ResultData::Indexes(_) => "indexes",
+                ResultData::Databases(_) => "databases",
+                ResultData::Collections(_) => "collections",
+                _ => "users",
+            };
+            let items: Vec<JsonValue> = result
+                .data
+                .to_documents()
+                .unwrap_or_default()
+                .iter()
+                .map(bson_document_to_json)
+                .collect();
+
+            let output = serde_json::json!({
+                key: items,
+                "count": items.len(),
+                "executionTimeMs": result.stats.execution_time_ms
+            });
+
+            CallToolResult::success(vec![Content::text(
+                serde_json::to_string_pretty(&output).unwrap_or_else(|_| "{}".to_string()),
+            )])
+        }
         ResultData::None => {
             CallToolResult::success(vec![Content::text("Operation completed successfully")])
         }
@@ -0,0 +1,280 @@
+//! Prometheus metrics for long-running use
+//!
+//! Every executed command is counted by kind and outcome and its latency is
+//! observed in a histogram; connection pool events from the driver keep gauges
+//! of open and checked out connections. `--metrics-port <PORT>` serves the
+//! values in the Prometheus text format at `http://<host>:<PORT>/metrics`, so
+//! a shell running as a job runner, or a service embedding the crate, can be
+//! scraped like any other process. Counting is a few atomic additions and
+//! happens whether or not the endpoint is running.
+
+use std::fmt::Write as _;
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+use std::time::Duration;
+
+use mongodb::event::cmap::CmapEvent;
+use tokio::net::{TcpListener, TcpStream};
+use tracing::{debug, info};
+
+use crate::error::{MongoshError, Result};
+use crate::http;
+use crate::parser::Command;
+
+/// Command kinds used as the `kind` label
+const KINDS: [&str; 5] = ["query", "admin", "utility", "config", "other"];
+
+/// Upper bounds of the latency histogram buckets, in seconds
+const BUCKETS: [f64; 11] = [
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// Counters, histogram and gauges of this process
+pub struct Metrics {
+    /// Commands by kind, then succeeded (0) or failed (1)
+    commands: [[AtomicU64; 2]; KINDS.len()],
+
+    /// Commands per latency bucket, not cumulative; the last slot is `+Inf`
+    latency_buckets: [AtomicU64; BUCKETS.len() + 1],
+
+    /// Total latency in microseconds
+    latency_sum_micros: AtomicU64,
+
+    /// Connections opened and not yet closed
+    connections_open: AtomicI64,
+
+    /// Connections checked out of the pool by an operation
+    connections_in_use: AtomicI64,
+
+    /// Configured maximum pool size
+    pool_max_size: AtomicU64,
+}
+
+static METRICS: Metrics = Metrics::new();
+
+/// Metrics of this process
+pub fn global() -> &'static Metrics {
+    &METRICS
+}
+
+impl Metrics {
+    const fn new() -> Self {
+        Self {
+            commands: [const { [const { AtomicU64::new(0) }; 2] }; KINDS.len()],
+            latency_buckets: [const { AtomicU64::new(0) }; BUCKETS.len() + 1],
+            latency_sum_micros: AtomicU64::new(0),
+            connections_open: AtomicI64::new(0),
+            connections_in_use: AtomicI64::new(0),
+            pool_max_size: AtomicU64::new(0),
+        }
+    }
+
+    /// Count an executed command and observe its latency
+    ///
+    /// # Arguments
+    /// * `kind` - Kind label from [`command_kind`]
+    /// * `success` - Whether it succeeded
+    /// * `elapsed` - Time it took
+    pub fn observe_command(&self, kind: &str, success: bool, elapsed: Duration) {
+        let kind = KINDS
+            .iter()
+            .position(|k| *k == kind)
+            .unwrap_or(KINDS.len() - 1);
+        let outcome = usize::from(!success);
+        self.commands[kind][outcome].fetch_add(1, Ordering::Relaxed);
+
+        let seconds = elapsed.as_secs_f64();
+        let bucket = BUCKETS
+            .iter()
+            .position(|bound| seconds <= *bound)
+            .unwrap_or(BUCKETS.len());
+        self.latency_buckets[bucket].fetch_add(1, Ordering::Relaxed);
+        self.latency_sum_micros
+            .fetch_add(elapsed.as_micros() as u64, Ordering::Relaxed);
+    }
+
+    /// Track connection pool events reported by the driver
+    pub fn observe_pool_event(&self, event: &CmapEvent) {
+        let (gauge, delta) = match event {
+            CmapEvent::ConnectionCreated(_) => (&self.connections_open, 1),
+            CmapEvent::ConnectionClosed(_) => (&self.connections_open, -1),
+            CmapEvent::ConnectionCheckedOut(_) => (&self.connections_in_use, 1),
+            CmapEvent::ConnectionCheckedIn(_) => (&self.connections_in_use, -1),
+            _ => return,
+        };
+        gauge.fetch_add(delta, Ordering::Relaxed);
+    }
+
+    /// Record the configured maximum pool size
+    pub fn set_pool_max_size(&self, size: u32) {
+        self.pool_max_size.store(u64::from(size), Ordering::Relaxed);
+    }
+
+    /// Render all metrics in the Prometheus text exposition format
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+
+        out.push_str("# HELP mongosh_commands_total Commands executed, by kind and outcome.\n");
+        out.push_str("# TYPE mongosh_commands_total counter\n");
+        for (kind, counts) in KINDS.iter().zip(&self.commands) {
+            for (outcome, count) in ["success", "error"].iter().zip(counts) {
+                let _ = writeln!(
+                    out,
+                    "mongosh_commands_total{{kind=\"{}\",outcome=\"{}\"}} {}",
+                    kind,
+                    outcome,
+                    count.load(Ordering::Relaxed)
+                );
+            }
+        }
+
+        out.push_str("# HELP mongosh_command_duration_seconds Command execution time.\n");
+        out.push_str("# TYPE mongosh_command_duration_seconds histogram\n");
+        let mut cumulative = 0;
+        for (i, count) in self.latency_buckets.iter().enumerate() {
+            cumulative += count.load(Ordering::Relaxed);
+            let bound = BUCKETS
+                .get(i)
+                .map_or_else(|| "+Inf".to_string(), f64::to_string);
+            let _ = writeln!(
+                out,
+                "mongosh_command_duration_seconds_bucket{{le=\"{}\"}} {}",
+                bound, cumulative
+            );
+        }
+        let sum = self.latency_sum_micros.load(Ordering::Relaxed) as f64 / 1_000_000.0;
+        let _ = writeln!(out, "mongosh_command_duration_seconds_sum {}", sum);
+        let _ = writeln!(out, "mongosh_command_duration_seconds_count {}", cumulative);
+
+        let gauges = [
+            (
+                "mongosh_pool_connections",
+                "Open connections in the driver pool.",
+                self.connections_open.load(Ordering::Relaxed),
+            ),
+            (
+                "mongosh_pool_connections_in_use",
+                "Connections checked out by running operations.",
+                self.connections_in_use.load(Ordering::Relaxed),
+            ),
+            (
+                "mongosh_pool_max_size",
+                "Configured maximum pool size.",
+                self.pool_max_size.load(Ordering::Relaxed) as i64,
+            ),
+        ];
+        for (name, help, value) in gauges {
+            let _ = writeln!(out, "# HELP {} {}", name, help);
+            let _ = writeln!(out, "# TYPE {} gauge", name);
+            let _ = writeln!(out, "{} {}", name, value);
+        }
+
+        out
+    }
+}
+
+/// `kind` label of a command
+///
+/// Pipes, `getSiblingDB()` and `watch` count as the command they wrap.
+pub fn command_kind(command: &Command) -> &'static str {
+    match command {
+        Command::Query(_) => "query",
+        Command::Admin(_) => "admin",
+        Command::Utility(_) => "utility",
+        Command::Config(_) => "config",
+        Command::Pipe(base, _) => command_kind(base),
+        Command::WithDatabase { command, .. } | Command::Watch { command, .. } => {
+            command_kind(command)
+        }
+        _ => "other",
+    }
+}
+
+/// Start serving `/metrics` on all interfaces
+///
+/// The port is bound before returning so a port in use is reported at
+/// startup; connections are then answered in the background.
+///
+/// # Arguments
+/// * `port` - TCP port
+///
+/// # Returns
+/// * `Result<()>` - Success or bind error
+pub async fn serve(port: u16) -> Result<()> {
+    let listener = TcpListener::bind(("0.0.0.0", port)).await.map_err(|e| {
+        MongoshError::Generic(format!(
+            "Failed to start metrics endpoint on port {}: {}",
+            port, e
+        ))
+    })?;
+    info!("Serving metrics on http://0.0.0.0:{}/metrics", port);
+
+    tokio::spawn(async move {
+        loop {
+            match listener.accept().await {
+                Ok((stream, _)) => {
+                    tokio::spawn(async move {
+                        if let Err(e) = respond(stream).await {
+                            debug!("Metrics request failed: {}", e);
+                        }
+                    });
+                }
+                Err(e) => debug!("Metrics connection failed: {}", e),
+            }
+        }
+    });
+    Ok(())
+}
+
+/// Answer one HTTP request
+async fn respond(mut stream: TcpStream) -> std::io::Result<()> {
+    let request = http::read_request(&mut stream).await?;
+    let (status, content_type, body) = match (request.method.as_str(), request.path.as_str()) {
+        ("GET", "/metrics" | "/") => (
+            "200 OK",
+            "text/plain; version=0.0.4; charset=utf-8",
+            global().render(),
+        ),
+        ("GET", _) => ("404 Not Found", "text/plain", "Not found\n".to_string()),
+        _ => (
+            "405 Method Not Allowed",
+            "text/plain",
+            "Method not allowed\n".to_string(),
+        ),
+    };
+    http::write_response(&mut stream, status, content_type, &body).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::{ConfigCommand, QueryCommand};
+
+    #[test]
+    fn test_render() {
+        let metrics = Metrics::new();
+        let query = Command::Query(QueryCommand::DeleteOne {
+            collection: "orders".to_string(),
+            filter: Default::default(),
+        });
+        let kind = command_kind(&query);
+        metrics.observe_command(kind, true, Duration::from_millis(3));
+        metrics.observe_command(kind, false, Duration::from_millis(300));
+        metrics.observe_command(
+            command_kind(&Command::Config(ConfigCommand::ListBookmarks)),
+            true,
+            Duration::from_secs(20),
+        );
+        metrics.set_pool_max_size(10);
+
+        let text = metrics.render();
+        assert!(text.contains("mongosh_commands_total{kind=\"query\",outcome=\"success\"} 1"));
+        assert!(text.contains("mongosh_commands_total{kind=\"query\",outcome=\"error\"} 1"));
+        assert!(text.contains("mongosh_commands_total{kind=\"config\",outcome=\"success\"} 1"));
+        assert!(text.contains("mongosh_command_duration_seconds_bucket{le=\"0.005\"} 1"));
+        assert!(text.contains("mongosh_command_duration_seconds_bucket{le=\"0.5\"} 2"));
+        assert!(text.contains("mongosh_command_duration_seconds_bucket{le=\"10\"} 2"));
+        assert!(text.contains("mongosh_command_duration_seconds_bucket{le=\"+Inf\"} 3"));
+        assert!(text.contains("mongosh_command_duration_seconds_count 3"));
+        assert!(text.contains("mongosh_pool_max_size 10"));
+    }
+}
@@ -0,0 +1,217 @@
+//! Go driver code
+
+use std::collections::BTreeSet;
+
+use bson::{Bson, Document};
+
+use super::{Arg, Call, CallOption, Method, extended_json_document, quoted};
+
+const BSON: &str = "go.mongodb.org/mongo-driver/bson";
+const PRIMITIVE: &str = "go.mongodb.org/mongo-driver/bson/primitive";
+const MONGO: &str = "go.mongodb.org/mongo-driver/mongo";
+const OPTIONS: &str = "go.mongodb.org/mongo-driver/mongo/options";
+
+/// Helper for ObjectId literals, which have no inline form in Go
+const MUST_OBJECT_ID: &str = "func mustObjectID(hex string) primitive.ObjectID {
+\tid, err := primitive.ObjectIDFromHex(hex)
+\tif err != nil {
+\t\tlog.Fatal(err)
+\t}
+\treturn id
+}";
+
+/// Render a call as Go statements with the imports they need
+pub(super) fn render(call: &Call) -> String {
+    let mut imports = BTreeSet::from(["log", BSON]);
+    let mut lines = vec![format!(
+        "coll := client.Database({}).Collection({})",
+        quoted(&call.database),
+        quoted(&call.collection)
+    )];
+
+    let mut call_args = vec!["ctx".to_string()];
+    for (position, arg) in call.args.iter().enumerate() {
+        match arg {
+            Arg::Doc(doc) => {
+                let name = arg_name(call.method, position);
+                lines.push(format!("{} := {}", name, document(doc, &mut imports)));
+                call_args.push(name.to_string());
+            }
+            Arg::Docs(docs) => {
+                let items: Vec<String> =
+                    docs.iter().map(|doc| document(doc, &mut imports)).collect();
+                let (name, literal) = if call.method == Method::Aggregate {
+                    imports.insert(MONGO);
+                    (
+                        "pipeline",
+                        format!("mongo.Pipeline{{{}}}", items.join(", ")),
+                    )
+                } else {
+                    (
+                        "documents",
+                        format!("[]interface{{}}{{{}}}", items.join(", ")),
+                    )
+                };
+                lines.push(format!("{} := {}", name, literal));
+                call_args.push(name.to_string());
+            }
+            Arg::Field(field) => call_args.push(quoted(field)),
+        }
+    }
+
+    if let Some(builder) = options_builder(call.method).filter(|_| !call.options.is_empty()) {
+        imports.insert(OPTIONS);
+        let mut opts = format!("opts := options.{}()", builder);
+        for option in &call.options {
+            opts.push_str(&match option {
+                CallOption::Projection(doc) => {
+                    format!(".SetProjection({})", document(doc, &mut imports))
+                }
+                CallOption::Sort(doc) => format!(".SetSort({})", document(doc, &mut imports)),
+                CallOption::Skip(skip) => format!(".SetSkip({})", skip),
+                CallOption::Limit(limit) => format!(".SetLimit({})", limit),
+                CallOption::Upsert => ".SetUpsert(true)".to_string(),
+            });
+        }
+        lines.push(opts);
+        call_args.push("opts".to_string());
+    }
+
+    let invocation = format!(
+        "coll.{}({})",
+        call.method.pascal_name(),
+        call_args.join(", ")
+    );
+    match call.method {
+        Method::Find | Method::Aggregate => {
+            lines.push(format!("cursor, err := {}", invocation));
+            lines.push(check_error("err != nil"));
+            lines.push("var documents []bson.M".to_string());
+            lines.push(check_error(
+                "err := cursor.All(ctx, &documents); err != nil",
+            ));
+        }
+        Method::FindOne => {
+            lines.push("var document bson.M".to_string());
+            lines.push(check_error(&format!(
+                "err := {}.Decode(&document); err != nil",
+                invocation
+            )));
+        }
+        method => {
+            lines.push(format!("{}, err := {}", binding(method), invocation));
+            lines.push(check_error("err != nil"));
+        }
+    }
+
+    let mut code = format!("{}\n\n{}", import_block(&imports), lines.join("\n"));
+    if code.contains("mustObjectID(") {
+        code.push_str("\n\n");
+        code.push_str(MUST_OBJECT_ID);
+    }
+    code
+}
+
+/// Variable name for a document argument
+fn arg_name(method: Method, position: usize) -> &'static str {
+    match (method, position) {
+        (Method::InsertOne, _) => "document",
+        (Method::UpdateOne | Method::UpdateMany, 1) => "update",
+        (Method::ReplaceOne, 1) => "replacement",
+        _ => "filter",
+    }
+}
+
+/// Options constructor for a method, e.g. `Find` for `options.Find()`
+fn options_builder(method: Method) -> Option<&'static str> {
+    match method {
+        Method::Find => Some("Find"),
+        Method::FindOne => Some("FindOne"),
+        Method::UpdateOne | Method::UpdateMany => Some("Update"),
+        Method::ReplaceOne => Some("Replace"),
+        _ => None,
+    }
+}
+
+/// Variable name for the result of a method
+fn binding(method: Method) -> &'static str {
+    match method {
+        Method::CountDocuments | Method::EstimatedDocumentCount => "count",
+        Method::Distinct => "values",
+        _ => "result",
+    }
+}
+
+/// `if <condition> { log.Fatal(err) }`
+fn check_error(condition: &str) -> String {
+    format!("if {} {{\n\tlog.Fatal(err)\n}}", condition)
+}
+
+/// Import block with the standard library before the driver packages
+fn import_block(imports: &BTreeSet<&str>) -> String {
+    let (std, driver): (Vec<&str>, Vec<&str>) =
+        imports.iter().partition(|path| !path.contains('.'));
+    let group = |paths: Vec<&str>| {
+        paths
+            .into_iter()
+            .map(|path| format!("\t{}", quoted(path)))
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    format!("import (\n{}\n\n{}\n)", group(std), group(driver))
+}
+
+/// `bson.D` literal of a document
+fn document(doc: &Document, imports: &mut BTreeSet<&'static str>) -> String {
+    let fields: Vec<String> = doc
+        .iter()
+        .map(|(key, value)| format!("{{{}, {}}}", quoted(key), self::value(value, imports)))
+        .collect();
+    format!("bson.D{{{}}}", fields.join(", "))
+}
+
+/// Go literal of a value
+fn value(value: &Bson, imports: &mut BTreeSet<&'static str>) -> String {
+    match value {
+        Bson::Document(doc) => document(doc, imports),
+        Bson::Array(items) => {
+            let items: Vec<String> = items
+                .iter()
+                .map(|item| self::value(item, imports))
+                .collect();
+            format!("bson.A{{{}}}", items.join(", "))
+        }
+        Bson::String(s) => quoted(s),
+        Bson::Int32(n) => n.to_string(),
+        Bson::Int64(n) => n.to_string(),
+        Bson::Double(f) if f.is_nan() => {
+            imports.insert("math");
+            "math.NaN()".to_string()
+        }
+        Bson::Double(f) if f.is_infinite() => {
+            imports.insert("math");
+            format!("math.Inf({})", if *f > 0.0 { 1 } else { -1 })
+        }
+        Bson::Double(f) => format!("{:?}", f),
+        Bson::Boolean(b) => b.to_string(),
+        Bson::Null | Bson::Undefined => "nil".to_string(),
+        Bson::ObjectId(id) => {
+            imports.insert(PRIMITIVE);
+            format!("mustObjectID(\"{}\")", id.to_hex())
+        }
+        Bson::DateTime(date) => {
+            imports.insert(PRIMITIVE);
+            format!("primitive.DateTime({})", date.timestamp_millis())
+        }
+        Bson::RegularExpression(regex) => {
+            imports.insert(PRIMITIVE);
+            format!(
+                "primitive.Regex{{Pattern: {}, Options: {}}}",
+                quoted(&regex.pattern),
+                quoted(&regex.options)
+            )
+        }
+        // No literal syntax; the Extended JSON form shows the intended value
+        other => self::value(&extended_json_document(other), imports),
+    }
+}
@@ -0,0 +1,104 @@
+//! Java (sync driver) code
+
+use std::collections::BTreeSet;
+
+use bson::Document;
+
+use super::{Arg, Call, CallOption, Method, extended_json, quoted};
+
+/// Render a call as Java statements with the imports they need
+pub(super) fn render(call: &Call) -> String {
+    let mut imports = BTreeSet::from(["com.mongodb.client.MongoCollection", "org.bson.Document"]);
+
+    let mut args: Vec<String> = call
+        .args
+        .iter()
+        .map(|arg| match arg {
+            Arg::Doc(doc) => document(doc),
+            Arg::Docs(docs) => {
+                imports.insert("java.util.List");
+                let docs: Vec<String> = docs.iter().map(document).collect();
+                format!("List.of({})", docs.join(", "))
+            }
+            Arg::Field(field) => quoted(field),
+        })
+        .collect();
+
+    // Cursor options are chained on the iterable, write options are passed
+    let mut chained = Vec::new();
+    for option in &call.options {
+        match option {
+            CallOption::Projection(doc) => chained.push(format!("projection({})", document(doc))),
+            CallOption::Sort(doc) => chained.push(format!("sort({})", document(doc))),
+            CallOption::Skip(skip) => chained.push(format!("skip({})", skip)),
+            CallOption::Limit(limit) => chained.push(format!("limit({})", limit)),
+            CallOption::Upsert if call.method == Method::ReplaceOne => {
+                imports.insert("com.mongodb.client.model.ReplaceOptions");
+                args.push("new ReplaceOptions().upsert(true)".to_string());
+            }
+            CallOption::Upsert => {
+                imports.insert("com.mongodb.client.model.UpdateOptions");
+                args.push("new UpdateOptions().upsert(true)".to_string());
+            }
+        }
+    }
+
+    if call.method == Method::Distinct {
+        args.push("BsonValue.class".to_string());
+    }
+
+    // findOne is find(...).first() in Java
+    let method = match call.method {
+        Method::FindOne => "find",
+        method => method.camel_name(),
+    };
+    let invocation = format!("{}({})", method, args.join(", "));
+
+    let statement = match call.method {
+        Method::Find | Method::Aggregate | Method::Distinct | Method::FindOne => {
+            let (declaration, terminal) = match call.method {
+                Method::FindOne => ("Document document", "first()"),
+                Method::Distinct => {
+                    imports.insert("org.bson.BsonValue");
+                    imports.insert("java.util.List");
+                    imports.insert("java.util.ArrayList");
+                    ("List<BsonValue> values", "into(new ArrayList<>())")
+                }
+                _ => {
+                    imports.insert("java.util.List");
+                    imports.insert("java.util.ArrayList");
+                    ("List<Document> documents", "into(new ArrayList<>())")
+                }
+            };
+            let mut chain = format!("{} = collection\n        .{}", declaration, invocation);
+            for link in chained.iter().map(String::as_str).chain([terminal]) {
+                chain.push_str("\n        .");
+                chain.push_str(link);
+            }
+            chain.push(';');
+            chain
+        }
+        Method::CountDocuments | Method::EstimatedDocumentCount => {
+            format!("long count = collection.{};", invocation)
+        }
+        _ => format!("var result = collection.{};", invocation),
+    };
+
+    let imports: Vec<String> = imports
+        .into_iter()
+        .map(|path| format!("import {};", path))
+        .collect();
+    format!(
+        "{}\n\nMongoCollection<Document> collection = client.getDatabase({}).getCollection({});\n{}",
+        imports.join("\n"),
+        quoted(&call.database),
+        quoted(&call.collection),
+        statement
+    )
+}
+
+/// `Document.parse` of the document's Extended JSON, which keeps every BSON
+/// type without needing a builder per type
+fn document(doc: &Document) -> String {
+    format!("Document.parse({})", quoted(&extended_json(doc)))
+}
@@ -0,0 +1,469 @@
+//! Driver code generation for `translate <language> <command>`
+//!
+//! A parsed query is first reduced to a [`Call`]: the collection method, its
+//! positional arguments and the options the shell chained onto it. Each
+//! language module then renders the call with the idioms of its official
+//! driver:
+//!
+//! - `rust`: `mongodb` crate with `doc!` literals
+//! - `node`: Node.js driver with object literals and `await`
+//! - `python`: PyMongo with dict literals and keyword options
+//! - `go`: Go driver with `bson.D` literals and option builders
+//! - `java`: sync Java driver with `Document.parse` of Extended JSON
+//!
+//! The `shell` module renders a call back as a shell query, which is how
+//! `sql explain` shows what a SQL statement translates to.
+//!
+//! Only the options that change which documents are returned or written are
+//! translated (projection, sort, skip, limit and upsert); session settings
+//! such as read concerns and timeouts are left to the application.
+
+mod go;
+mod java;
+mod node;
+mod python;
+mod rust;
+mod shell;
+
+use bson::{Bson, Document};
+
+use super::command::{Command, DriverLanguage, QueryCommand};
+use crate::error::{ParseError, Result};
+
+/// Collection method a query translates to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Method {
+    Find,
+    FindOne,
+    Aggregate,
+    CountDocuments,
+    EstimatedDocumentCount,
+    Distinct,
+    InsertOne,
+    InsertMany,
+    UpdateOne,
+    UpdateMany,
+    ReplaceOne,
+    DeleteOne,
+    DeleteMany,
+}
+
+impl Method {
+    /// Shell and Node.js name, e.g. `countDocuments`
+    pub(crate) fn camel_name(self) -> &'static str {
+        match self {
+            Method::Find => "find",
+            Method::FindOne => "findOne",
+            Method::Aggregate => "aggregate",
+            Method::CountDocuments => "countDocuments",
+            Method::EstimatedDocumentCount => "estimatedDocumentCount",
+            Method::Distinct => "distinct",
+            Method::InsertOne => "insertOne",
+            Method::InsertMany => "insertMany",
+            Method::UpdateOne => "updateOne",
+            Method::UpdateMany => "updateMany",
+            Method::ReplaceOne => "replaceOne",
+            Method::DeleteOne => "deleteOne",
+            Method::DeleteMany => "deleteMany",
+        }
+    }
+
+    /// Rust and Python name, e.g. `count_documents`
+    pub(crate) fn snake_name(self) -> String {
+        let mut name = String::new();
+        for c in self.camel_name().chars() {
+            if c.is_ascii_uppercase() {
+                name.push('_');
+            }
+            name.push(c.to_ascii_lowercase());
+        }
+        name
+    }
+
+    /// Go name, e.g. `CountDocuments`
+    pub(crate) fn pascal_name(self) -> String {
+        let camel = self.camel_name();
+        camel[..1].to_ascii_uppercase() + &camel[1..]
+    }
+
+    /// Whether the method returns a cursor
+    pub(crate) fn returns_cursor(self) -> bool {
+        matches!(self, Method::Find | Method::Aggregate)
+    }
+}
+
+/// Positional argument of a call
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Arg {
+    /// Filter, update or document
+    Doc(Document),
+
+    /// Pipeline or documents to insert
+    Docs(Vec<Document>),
+
+    /// Field name
+    Field(String),
+}
+
+/// Option of a call, in the order the shell applies them
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum CallOption {
+    Projection(Document),
+    Sort(Document),
+    Skip(u64),
+    Limit(i64),
+    Upsert,
+}
+
+/// A collection method call with its arguments and options
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Call {
+    pub database: String,
+    pub collection: String,
+    pub method: Method,
+    pub args: Vec<Arg>,
+    pub options: Vec<CallOption>,
+}
+
+/// Generate driver code for a parsed command
+///
+/// # Arguments
+/// * `language` - Target driver language
+/// * `database` - Database to use unless the command names one
+/// * `command` - Parsed shell or SQL command
+///
+/// # Returns
+/// * `Result<String>` - Code snippet, or an error for commands that have no
+///   driver equivalent
+pub fn translate(language: DriverLanguage, database: &str, command: &Command) -> Result<String> {
+    let call = Call::from_command(database, command)?;
+    Ok(match language {
+        DriverLanguage::Rust => rust::render(&call),
+        DriverLanguage::Node => node::render(&call),
+        DriverLanguage::Python => python::render(&call),
+        DriverLanguage::Go => go::render(&call),
+        DriverLanguage::Java => java::render(&call),
+    })
+}
+
+/// Render a parsed command as the shell query it runs as
+///
+/// # Arguments
+/// * `command` - Parsed shell or SQL command
+///
+/// # Returns
+/// * `Result<String>` - Shell query, e.g. `db.users.find({"age":{"$gt":18}}).limit(5)`
+pub fn shell_query(command: &Command) -> Result<String> {
+    // An empty database name renders as the current `db`
+    Ok(shell::render(&Call::from_command("", command)?))
+}
+
+impl Call {
+    /// Reduce a command to a single collection call
+    fn from_command(database: &str, command: &Command) -> Result<Self> {
+        match command {
+            Command::Query(query) => Self::from_query(database, query),
+            Command::WithDatabase { database, command } => Self::from_command(database, command),
+            _ => Err(unsupported("this command")),
+        }
+    }
+
+    /// Reduce a query to a single collection call
+    fn from_query(database: &str, query: &QueryCommand) -> Result<Self> {
+        let call = |collection: &String, method, args, options| Call {
+            database: database.to_string(),
+            collection: collection.clone(),
+            method,
+            args,
+            options,
+        };
+
+        Ok(match query {
+            QueryCommand::Find {
+                collection,
+                filter,
+                options,
+            }
+            | QueryCommand::FindOne {
+                collection,
+                filter,
+                options,
+            } => {
+                let method = match query {
+                    QueryCommand::Find { .. } => Method::Find,
+                    _ => Method::FindOne,
+                };
+                let mut call_options = Vec::new();
+                if let Some(projection) = &options.projection {
+                    call_options.push(CallOption::Projection(projection.clone()));
+                }
+                if let Some(sort) = &options.sort {
+                    call_options.push(CallOption::Sort(sort.clone()));
+                }
+                if let Some(skip) = options.skip {
+                    call_options.push(CallOption::Skip(skip));
+                }
+                if let Some(limit) = options.limit.filter(|_| method == Method::Find) {
+                    call_options.push(CallOption::Limit(limit));
+                }
+                call(
+                    collection,
+                    method,
+                    vec![Arg::Doc(filter.clone())],
+                    call_options,
+                )
+            }
+            QueryCommand::Aggregate {
+                collection,
+                pipeline,
+                ..
+            } => call(
+                collection,
+                Method::Aggregate,
+                vec![Arg::Docs(pipeline.clone())],
+                Vec::new(),
+            ),
+            QueryCommand::CountDocuments { collection, filter } => call(
+                collection,
+                Method::CountDocuments,
+                vec![Arg::Doc(filter.clone())],
+                Vec::new(),
+            ),
+            QueryCommand::EstimatedDocumentCount { collection } => call(
+                collection,
+                Method::EstimatedDocumentCount,
+                Vec::new(),
+                Vec::new(),
+            ),
+            QueryCommand::Distinct {
+                collection,
+                field,
+                filter,
+                ..
+            } => call(
+                collection,
+                Method::Distinct,
+                vec![
+                    Arg::Field(field.clone()),
+                    Arg::Doc(filter.clone().unwrap_or_default()),
+                ],
+                Vec::new(),
+            ),
+            QueryCommand::InsertOne {
+                collection,
+                document,
+            } => call(
+                collection,
+                Method::InsertOne,
+                vec![Arg::Doc(document.clone())],
+                Vec::new(),
+            ),
+            QueryCommand::InsertMany {
+                collection,
+                documents,
+            } => call(
+                collection,
+                Method::InsertMany,
+                vec![Arg::Docs(documents.clone())],
+                Vec::new(),
+            ),
+            QueryCommand::UpdateOne {
+                collection,
+                filter,
+                update,
+                options,
+            }
+            | QueryCommand::UpdateMany {
+                collection,
+                filter,
+                update,
+                options,
+            }
+            | QueryCommand::ReplaceOne {
+                collection,
+                filter,
+                replacement: update,
+                options,
+            } => {
+                let method = match query {
+                    QueryCommand::UpdateOne { .. } => Method::UpdateOne,
+                    QueryCommand::UpdateMany { .. } => Method::UpdateMany,
+                    _ => Method::ReplaceOne,
+                };
+                let call_options = if options.upsert {
+                    vec![CallOption::Upsert]
+                } else {
+                    Vec::new()
+                };
+                call(
+                    collection,
+                    method,
+                    vec![Arg::Doc(filter.clone()), Arg::Doc(update.clone())],
+                    call_options,
+                )
+            }
+            QueryCommand::DeleteOne { collection, filter } => call(
+                collection,
+                Method::DeleteOne,
+                vec![Arg::Doc(filter.clone())],
+                Vec::new(),
+            ),
+            QueryCommand::DeleteMany { collection, filter } => call(
+                collection,
+                Method::DeleteMany,
+                vec![Arg::Doc(filter.clone())],
+                Vec::new(),
+            ),
+            _ => return Err(unsupported(query_name(query))),
+        })
+    }
+}
+
+/// Shell name of a query without a translation
+fn query_name(query: &QueryCommand) -> &'static str {
+    match query {
+        QueryCommand::FindOneAndDelete { .. } => "findOneAndDelete",
+        QueryCommand::FindOneAndUpdate { .. } => "findOneAndUpdate",
+        QueryCommand::FindOneAndReplace { .. } => "findOneAndReplace",
+        QueryCommand::FindAndModify { .. } => "findAndModify",
+        QueryCommand::BulkWrite { .. } => "bulkWrite",
+        QueryCommand::Explain { .. } => "explain",
+        _ => "this query",
+    }
+}
+
+fn unsupported(what: &str) -> crate::error::MongoshError {
+    ParseError::InvalidCommand(format!(
+        "translate does not support {}; use find, findOne, aggregate, countDocuments, \
+         estimatedDocumentCount, distinct, insert, update, replaceOne or delete",
+        what
+    ))
+    .into()
+}
+
+/// Relaxed Extended JSON of a document, on one line
+fn extended_json(doc: &Document) -> String {
+    Bson::Document(doc.clone())
+        .into_relaxed_extjson()
+        .to_string()
+}
+
+/// Canonical Extended JSON of a value as plain BSON, for types without a
+/// literal in the target language, e.g. `{ "$numberDecimal": "1.5" }`
+fn extended_json_document(value: &Bson) -> Bson {
+    plain(value.clone().into_canonical_extjson())
+}
+
+/// JSON as BSON without interpreting `$` keys
+fn plain(json: serde_json::Value) -> Bson {
+    match json {
+        serde_json::Value::Null => Bson::Null,
+        serde_json::Value::Bool(b) => Bson::Boolean(b),
+        serde_json::Value::Number(n) => match n.as_i64() {
+            Some(n) => Bson::Int64(n),
+            None => Bson::Double(n.as_f64().unwrap_or_default()),
+        },
+        serde_json::Value::String(s) => Bson::String(s),
+        serde_json::Value::Array(items) => Bson::Array(items.into_iter().map(plain).collect()),
+        serde_json::Value::Object(map) => Bson::Document(
+            map.into_iter()
+                .map(|(key, value)| (key, plain(value)))
+                .collect(),
+        ),
+    }
+}
+
+/// Whether a key can be written without quotes in JavaScript
+fn is_identifier(key: &str) -> bool {
+    let mut chars = key.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+}
+
+/// Double-quoted string literal with `"`, `\` and control characters escaped
+fn quoted(text: &str) -> String {
+    serde_json::Value::String(text.to_string()).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+
+    fn generate(language: DriverLanguage, input: &str) -> String {
+        let command = Parser::new().parse(input).unwrap();
+        translate(language, "shop", &command).unwrap()
+    }
+
+    #[test]
+    fn test_method_names() {
+        assert_eq!(Method::CountDocuments.snake_name(), "count_documents");
+        assert_eq!(
+            Method::EstimatedDocumentCount.pascal_name(),
+            "EstimatedDocumentCount"
+        );
+        assert_eq!(Method::FindOne.camel_name(), "findOne");
+    }
+
+    #[test]
+    fn test_translate_find() {
+        let input = "db.users.find({ age: { $gt: 18 } }).sort({ name: 1 }).limit(5)";
+
+        let rust = generate(DriverLanguage::Rust, input);
+        assert!(rust.contains(".find(doc! { \"age\": { \"$gt\": 18 } })"));
+        assert!(rust.contains(".sort(doc! { \"name\": 1 })"));
+        assert!(rust.contains(".limit(5)"));
+
+        let node = generate(DriverLanguage::Node, input);
+        assert!(node.contains("client.db(\"shop\").collection(\"users\")"));
+        assert!(node.contains(".find({ age: { $gt: 18 } }, { sort: { name: 1 }, limit: 5 })"));
+        assert!(node.contains(".toArray()"));
+
+        let python = generate(DriverLanguage::Python, input);
+        assert!(python.contains("find({\"age\": {\"$gt\": 18}}, sort=[(\"name\", 1)], limit=5)"));
+
+        let go = generate(DriverLanguage::Go, input);
+        assert!(go.contains("bson.D{{\"age\", bson.D{{\"$gt\", 18}}}}"));
+        assert!(go.contains("options.Find().SetSort(bson.D{{\"name\", 1}}).SetLimit(5)"));
+
+        let java = generate(DriverLanguage::Java, input);
+        assert!(java.contains(".find(Document.parse(\"{\\\"age\\\":{\\\"$gt\\\":18}}\"))"));
+        assert!(java.contains(".limit(5)"));
+    }
+
+    #[test]
+    fn test_translate_update_with_database() {
+        let input = "db.getSiblingDB('crm').users.updateMany({ active: false }, { $set: { archived: true } }, { upsert: true })";
+        let python = generate(DriverLanguage::Python, input);
+        assert!(python.contains("client[\"crm\"][\"users\"]"));
+        assert!(python.contains("upsert=True"));
+    }
+
+    #[test]
+    fn test_shell_query() {
+        let command = Parser::new()
+            .parse("db.users.findOne({ active: true }, { sort: { age: -1 } })")
+            .unwrap();
+        // findOne with options is parsed as find(...).limit(1)
+        assert_eq!(
+            shell_query(&command).unwrap(),
+            "db.users.find({\"active\":true}).sort({\"age\":-1}).limit(1)"
+        );
+
+        let command = Parser::new()
+            .parse("db.getSiblingDB('crm')['order-items'].deleteMany({})")
+            .unwrap();
+        assert_eq!(
+            shell_query(&command).unwrap(),
+            "db.getSiblingDB(\"crm\")[\"order-items\"].deleteMany({})"
+        );
+    }
+
+    #[test]
+    fn test_translate_unsupported() {
+        let command = Parser::new().parse("show collections").unwrap();
+        assert!(translate(DriverLanguage::Rust, "shop", &command).is_err());
+    }
+}
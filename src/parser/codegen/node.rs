@@ -0,0 +1,143 @@
+//! Node.js driver code
+
+use std::collections::BTreeSet;
+
+use bson::{Bson, Document};
+
+use super::{Arg, Call, CallOption, Method, extended_json_document, is_identifier, quoted};
+
+/// Largest integer a JavaScript number holds exactly
+const MAX_SAFE_INTEGER: i64 = (1 << 53) - 1;
+
+/// Render a call as a JavaScript statement with the imports it needs
+pub(super) fn render(call: &Call) -> String {
+    let mut imports = BTreeSet::new();
+
+    let mut args: Vec<String> = call
+        .args
+        .iter()
+        .map(|arg| match arg {
+            Arg::Doc(doc) => document(doc, &mut imports),
+            Arg::Docs(docs) => array(docs.iter().map(|doc| document(doc, &mut imports))),
+            Arg::Field(field) => quoted(field),
+        })
+        .collect();
+
+    let options: Vec<String> = call
+        .options
+        .iter()
+        .map(|option| match option {
+            CallOption::Projection(doc) => format!("projection: {}", document(doc, &mut imports)),
+            CallOption::Sort(doc) => format!("sort: {}", document(doc, &mut imports)),
+            CallOption::Skip(skip) => format!("skip: {}", skip),
+            CallOption::Limit(limit) => format!("limit: {}", limit),
+            CallOption::Upsert => "upsert: true".to_string(),
+        })
+        .collect();
+    if !options.is_empty() {
+        args.push(format!("{{ {} }}", options.join(", ")));
+    }
+
+    let mut statement = format!(
+        "const {} = await client.db({}).collection({})\n  .{}({})",
+        binding(call.method),
+        quoted(&call.database),
+        quoted(&call.collection),
+        call.method.camel_name(),
+        args.join(", ")
+    );
+    if call.method.returns_cursor() {
+        statement.push_str("\n  .toArray()");
+    }
+    statement.push(';');
+
+    if imports.is_empty() {
+        return statement;
+    }
+    let imports: Vec<&str> = imports.into_iter().collect();
+    format!(
+        "const {{ {} }} = require(\"mongodb\");\n\n{}",
+        imports.join(", "),
+        statement
+    )
+}
+
+/// Variable name for the result of a method
+fn binding(method: Method) -> &'static str {
+    match method {
+        Method::Find | Method::Aggregate => "documents",
+        Method::FindOne => "document",
+        Method::CountDocuments | Method::EstimatedDocumentCount => "count",
+        Method::Distinct => "values",
+        _ => "result",
+    }
+}
+
+/// Object literal of a document
+fn document(doc: &Document, imports: &mut BTreeSet<&'static str>) -> String {
+    if doc.is_empty() {
+        return "{}".to_string();
+    }
+    let fields: Vec<String> = doc
+        .iter()
+        .map(|(key, value)| {
+            let key = if is_identifier(key) {
+                key.clone()
+            } else {
+                quoted(key)
+            };
+            format!("{}: {}", key, self::value(value, imports))
+        })
+        .collect();
+    format!("{{ {} }}", fields.join(", "))
+}
+
+/// Array literal of rendered items
+fn array(items: impl Iterator<Item = String>) -> String {
+    format!("[{}]", items.collect::<Vec<_>>().join(", "))
+}
+
+/// JavaScript literal of a value
+fn value(value: &Bson, imports: &mut BTreeSet<&'static str>) -> String {
+    match value {
+        Bson::Document(doc) => document(doc, imports),
+        Bson::Array(items) => {
+            let items: Vec<String> = items
+                .iter()
+                .map(|item| self::value(item, imports))
+                .collect();
+            array(items.into_iter())
+        }
+        Bson::String(s) => quoted(s),
+        Bson::Int32(n) => n.to_string(),
+        Bson::Int64(n) if n.abs() <= MAX_SAFE_INTEGER => n.to_string(),
+        Bson::Int64(n) => {
+            imports.insert("Long");
+            format!("Long.fromString(\"{}\")", n)
+        }
+        Bson::Double(f) if f.is_nan() => "NaN".to_string(),
+        Bson::Double(f) if f.is_infinite() && *f > 0.0 => "Infinity".to_string(),
+        Bson::Double(f) if f.is_infinite() => "-Infinity".to_string(),
+        Bson::Double(f) => f.to_string(),
+        Bson::Boolean(b) => b.to_string(),
+        Bson::Null => "null".to_string(),
+        Bson::Undefined => "undefined".to_string(),
+        Bson::ObjectId(id) => {
+            imports.insert("ObjectId");
+            format!("new ObjectId(\"{}\")", id.to_hex())
+        }
+        Bson::DateTime(date) => match date.try_to_rfc3339_string() {
+            Ok(iso) => format!("new Date(\"{}\")", iso),
+            Err(_) => format!("new Date({})", date.timestamp_millis()),
+        },
+        Bson::RegularExpression(regex) => {
+            format!("/{}/{}", regex.pattern.replace('/', "\\/"), regex.options)
+        }
+        Bson::Decimal128(decimal) => {
+            imports.insert("Decimal128");
+            format!("Decimal128.fromString(\"{}\")", decimal)
+        }
+        // No literal syntax; the Extended JSON form shows the intended value
+        other => self::value(&extended_json_document(other), imports),
+    }
+}
@@ -0,0 +1,130 @@
+//! PyMongo code
+
+use std::collections::BTreeSet;
+
+use bson::{Bson, Document};
+
+use super::{Arg, Call, CallOption, Method, extended_json_document, quoted};
+
+/// Render a call as a Python statement with the imports it needs
+pub(super) fn render(call: &Call) -> String {
+    let mut imports = BTreeSet::new();
+
+    let mut args: Vec<String> = call
+        .args
+        .iter()
+        .map(|arg| match arg {
+            Arg::Doc(doc) => document(doc, &mut imports),
+            Arg::Docs(docs) => list(docs.iter().map(|doc| document(doc, &mut imports))),
+            Arg::Field(field) => quoted(field),
+        })
+        .collect();
+    for option in &call.options {
+        args.push(match option {
+            CallOption::Projection(doc) => format!("projection={}", document(doc, &mut imports)),
+            // PyMongo takes the sort as (key, direction) pairs
+            CallOption::Sort(doc) => {
+                let pairs = doc.iter().map(|(key, value)| {
+                    format!("({}, {})", quoted(key), self::value(value, &mut imports))
+                });
+                format!("sort={}", list(pairs))
+            }
+            CallOption::Skip(skip) => format!("skip={}", skip),
+            CallOption::Limit(limit) => format!("limit={}", limit),
+            CallOption::Upsert => "upsert=True".to_string(),
+        });
+    }
+
+    let call_code = format!(
+        "client[{}][{}].{}({})",
+        quoted(&call.database),
+        quoted(&call.collection),
+        call.method.snake_name(),
+        args.join(", ")
+    );
+    let statement = if call.method.returns_cursor() {
+        format!("{} = list({})", binding(call.method), call_code)
+    } else {
+        format!("{} = {}", binding(call.method), call_code)
+    };
+
+    if imports.is_empty() {
+        return statement;
+    }
+    let imports: Vec<&str> = imports.into_iter().collect();
+    format!("{}\n\n{}", imports.join("\n"), statement)
+}
+
+/// Variable name for the result of a method
+fn binding(method: Method) -> &'static str {
+    match method {
+        Method::Find | Method::Aggregate => "documents",
+        Method::FindOne => "document",
+        Method::CountDocuments | Method::EstimatedDocumentCount => "count",
+        Method::Distinct => "values",
+        _ => "result",
+    }
+}
+
+/// Dict literal of a document
+fn document(doc: &Document, imports: &mut BTreeSet<&'static str>) -> String {
+    let fields: Vec<String> = doc
+        .iter()
+        .map(|(key, value)| format!("{}: {}", quoted(key), self::value(value, imports)))
+        .collect();
+    format!("{{{}}}", fields.join(", "))
+}
+
+/// List literal of rendered items
+fn list(items: impl Iterator<Item = String>) -> String {
+    format!("[{}]", items.collect::<Vec<_>>().join(", "))
+}
+
+/// Python literal of a value
+fn value(value: &Bson, imports: &mut BTreeSet<&'static str>) -> String {
+    match value {
+        Bson::Document(doc) => document(doc, imports),
+        Bson::Array(items) => {
+            let items: Vec<String> = items
+                .iter()
+                .map(|item| self::value(item, imports))
+                .collect();
+            list(items.into_iter())
+        }
+        Bson::String(s) => quoted(s),
+        Bson::Int32(n) => n.to_string(),
+        Bson::Int64(n) => n.to_string(),
+        Bson::Double(f) if f.is_nan() => "float(\"nan\")".to_string(),
+        Bson::Double(f) if f.is_infinite() && *f > 0.0 => "float(\"inf\")".to_string(),
+        Bson::Double(f) if f.is_infinite() => "float(\"-inf\")".to_string(),
+        Bson::Double(f) => format!("{:?}", f),
+        Bson::Boolean(true) => "True".to_string(),
+        Bson::Boolean(false) => "False".to_string(),
+        Bson::Null | Bson::Undefined => "None".to_string(),
+        Bson::ObjectId(id) => {
+            imports.insert("from bson import ObjectId");
+            format!("ObjectId(\"{}\")", id.to_hex())
+        }
+        Bson::DateTime(date) => {
+            imports.insert("from datetime import datetime, timezone");
+            format!(
+                "datetime.fromtimestamp({}, tz=timezone.utc)",
+                date.timestamp_millis() as f64 / 1000.0
+            )
+        }
+        Bson::RegularExpression(regex) => {
+            imports.insert("from bson.regex import Regex");
+            format!(
+                "Regex({}, {})",
+                quoted(&regex.pattern),
+                quoted(&regex.options)
+            )
+        }
+        Bson::Decimal128(decimal) => {
+            imports.insert("from bson.decimal128 import Decimal128");
+            format!("Decimal128(\"{}\")", decimal)
+        }
+        // No literal syntax; the Extended JSON form shows the intended value
+        other => self::value(&extended_json_document(other), imports),
+    }
+}
@@ -0,0 +1,130 @@
+//! Rust driver (`mongodb` crate) code
+
+use std::collections::BTreeSet;
+
+use bson::{Bson, Document};
+
+use super::{Arg, Call, CallOption, Method, extended_json_document, quoted};
+
+/// Render a call as a Rust statement with the imports it needs
+pub(super) fn render(call: &Call) -> String {
+    let mut imports = BTreeSet::from(["mongodb::bson::Document", "mongodb::bson::doc"]);
+
+    let args = call
+        .args
+        .iter()
+        .map(|arg| match arg {
+            Arg::Doc(doc) => format!("doc! {}", document(doc, &mut imports)),
+            Arg::Docs(docs) => {
+                let docs: Vec<String> = docs
+                    .iter()
+                    .map(|doc| format!("doc! {}", document(doc, &mut imports)))
+                    .collect();
+                format!("vec![{}]", docs.join(", "))
+            }
+            Arg::Field(field) => quoted(field),
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let mut chain = format!(
+        "client\n    .database({})\n    .collection::<Document>({})\n    .{}({})",
+        quoted(&call.database),
+        quoted(&call.collection),
+        call.method.snake_name(),
+        args
+    );
+    for option in &call.options {
+        let option = match option {
+            CallOption::Projection(doc) => {
+                format!("projection(doc! {})", document(doc, &mut imports))
+            }
+            CallOption::Sort(doc) => format!("sort(doc! {})", document(doc, &mut imports)),
+            CallOption::Skip(skip) => format!("skip({})", skip),
+            CallOption::Limit(limit) => format!("limit({})", limit),
+            CallOption::Upsert => "upsert(true)".to_string(),
+        };
+        chain.push_str("\n    .");
+        chain.push_str(&option);
+    }
+    chain.push_str("\n    .await?");
+
+    let statement = if call.method.returns_cursor() {
+        imports.insert("futures::TryStreamExt");
+        format!(
+            "let documents: Vec<Document> = {}\n    .try_collect()\n    .await?;",
+            chain
+        )
+    } else {
+        format!("let {} = {};", binding(call.method), chain)
+    };
+
+    let imports: Vec<String> = imports
+        .into_iter()
+        .map(|path| format!("use {};", path))
+        .collect();
+    format!("{}\n\n{}", imports.join("\n"), statement)
+}
+
+/// Variable name for the result of a method
+fn binding(method: Method) -> &'static str {
+    match method {
+        Method::FindOne => "document",
+        Method::CountDocuments | Method::EstimatedDocumentCount => "count",
+        Method::Distinct => "values",
+        _ => "result",
+    }
+}
+
+/// Document literal as written inside `doc!`
+fn document(doc: &Document, imports: &mut BTreeSet<&'static str>) -> String {
+    if doc.is_empty() {
+        return "{}".to_string();
+    }
+    let fields: Vec<String> = doc
+        .iter()
+        .map(|(key, value)| format!("{}: {}", quoted(key), self::value(value, imports)))
+        .collect();
+    format!("{{ {} }}", fields.join(", "))
+}
+
+/// Value literal as written inside `doc!`
+fn value(value: &Bson, imports: &mut BTreeSet<&'static str>) -> String {
+    match value {
+        Bson::Document(doc) => document(doc, imports),
+        Bson::Array(items) => {
+            let items: Vec<String> = items
+                .iter()
+                .map(|item| self::value(item, imports))
+                .collect();
+            format!("[{}]", items.join(", "))
+        }
+        Bson::String(s) => quoted(s),
+        Bson::Int32(n) => n.to_string(),
+        Bson::Int64(n) => n.to_string(),
+        Bson::Double(f) if f.is_nan() => "f64::NAN".to_string(),
+        Bson::Double(f) if f.is_infinite() && *f > 0.0 => "f64::INFINITY".to_string(),
+        Bson::Double(f) if f.is_infinite() => "f64::NEG_INFINITY".to_string(),
+        Bson::Double(f) => format!("{:?}", f),
+        Bson::Boolean(b) => b.to_string(),
+        Bson::Null | Bson::Undefined => "null".to_string(),
+        Bson::ObjectId(id) => {
+            imports.insert("mongodb::bson::oid::ObjectId");
+            format!("ObjectId::parse_str(\"{}\")?", id.to_hex())
+        }
+        Bson::DateTime(date) => {
+            imports.insert("mongodb::bson::DateTime");
+            format!("DateTime::from_millis({})", date.timestamp_millis())
+        }
+        Bson::RegularExpression(regex) => {
+            imports.insert("mongodb::bson::Regex");
+            format!(
+                "Regex {{ pattern: {}.to_string(), options: {}.to_string() }}",
+                quoted(&regex.pattern),
+                quoted(&regex.options)
+            )
+        }
+        // No literal syntax; the Extended JSON form shows the intended value
+        other => self::value(&extended_json_document(other), imports),
+    }
+}
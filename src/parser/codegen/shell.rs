@@ -0,0 +1,71 @@
+//! Shell code, as typed at the prompt
+
+use super::{Arg, Call, CallOption, Method, extended_json, is_identifier, quoted};
+
+/// Render a call as a shell query
+///
+/// The call's database is named with `getSiblingDB` unless it is empty, which
+/// stands for the current database.
+pub(super) fn render(call: &Call) -> String {
+    let mut code = String::from("db");
+    if !call.database.is_empty() {
+        code.push_str(&format!(".getSiblingDB({})", quoted(&call.database)));
+    }
+    if is_identifier(&call.collection) && !call.collection.starts_with('$') {
+        code.push('.');
+        code.push_str(&call.collection);
+    } else {
+        code.push_str(&format!("[{}]", quoted(&call.collection)));
+    }
+
+    let mut args: Vec<String> = call
+        .args
+        .iter()
+        .map(|arg| match arg {
+            Arg::Doc(doc) => extended_json(doc),
+            Arg::Docs(docs) if docs.len() > 1 => {
+                let docs: Vec<String> = docs
+                    .iter()
+                    .map(|doc| format!("  {}", extended_json(doc)))
+                    .collect();
+                format!("[\n{}\n]", docs.join(",\n"))
+            }
+            Arg::Docs(docs) => {
+                let docs: Vec<String> = docs.iter().map(extended_json).collect();
+                format!("[{}]", docs.join(", "))
+            }
+            Arg::Field(field) => quoted(field),
+        })
+        .collect();
+
+    // find chains its options; findOne and writes take an options object
+    let mut chained = String::new();
+    let mut object = Vec::new();
+    for option in &call.options {
+        match (option, call.method == Method::Find) {
+            (CallOption::Projection(doc), true) => args.push(extended_json(doc)),
+            (CallOption::Projection(doc), false) => {
+                object.push(format!("projection: {}", extended_json(doc)))
+            }
+            (CallOption::Sort(doc), true) => {
+                chained.push_str(&format!(".sort({})", extended_json(doc)))
+            }
+            (CallOption::Sort(doc), false) => object.push(format!("sort: {}", extended_json(doc))),
+            (CallOption::Skip(skip), true) => chained.push_str(&format!(".skip({})", skip)),
+            (CallOption::Skip(skip), false) => object.push(format!("skip: {}", skip)),
+            (CallOption::Limit(limit), _) => chained.push_str(&format!(".limit({})", limit)),
+            (CallOption::Upsert, _) => object.push("upsert: true".to_string()),
+        }
+    }
+    if !object.is_empty() {
+        args.push(format!("{{ {} }}", object.join(", ")));
+    }
+
+    code.push_str(&format!(
+        ".{}({}){}",
+        call.method.camel_name(),
+        args.join(", "),
+        chained
+    ));
+    code
+}
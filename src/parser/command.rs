@@ -3,9 +3,10 @@
 //! This module defines all command types that can be parsed and executed,
 //! including queries, administrative commands, utilities, and scripts.
 
-use mongodb::bson::Document;
+use bson::{Bson, Document, Timestamp};
 use serde::{Deserialize, Serialize};
 
+use crate::config::OutputFormat;
 use crate::error::ParseError;
 
 /// Query execution mode
@@ -50,16 +51,123 @@ pub enum Command {
     /// Piped command (query with post-processing)
     Pipe(Box<Command>, PipeCommand),
 
+    /// Command run against another database (db.getSiblingDB('name')...)
+    WithDatabase {
+        database: String,
+        command: Box<Command>,
+    },
+
     /// Help command with optional topic
     Help(Option<String>),
 
     /// AI query generation from natural language description
     AiQuery(String),
 
+    /// Execute a script file (load("file.js"))
+    Load(String),
+
+    /// Run commands concurrently (parallel([() => ..., ...], { concurrency }))
+    Parallel {
+        commands: Vec<Command>,
+        concurrency: usize,
+    },
+
+    /// Re-run a command every `interval` seconds until interrupted
+    /// (watch 5 db.orders.countDocuments())
+    Watch {
+        interval: u64,
+        source: String,
+        command: Box<Command>,
+    },
+
+    /// Re-run the last command that failed (retry)
+    Retry,
+
+    /// Start recording the session to a transcript (record start session.log)
+    /// or stop with `None` (record stop)
+    Record(Option<String>),
+
+    /// Start appending commands and results to a Markdown report
+    /// (report start notes.md) or close it with `None` (report stop)
+    Report(Option<String>),
+
     /// Exit/quit command
     Exit,
 }
 
+impl Command {
+    /// Output format requested for this command alone
+    ///
+    /// Set by `.format("table")`, `|> format table` or a trailing
+    /// `--format=table`.
+    pub fn format_override(&self) -> Option<OutputFormat> {
+        match self {
+            Command::Pipe(_, PipeCommand::Format(format)) => Some(*format),
+            Command::Pipe(base, PipeCommand::Pluck(_) | PipeCommand::Full) => {
+                base.format_override()
+            }
+            Command::WithDatabase { command, .. } => command.format_override(),
+            _ => None,
+        }
+    }
+
+    /// Fields to show of this command's results, all fields when `None`
+    ///
+    /// Set by `:: .name, .age`, `|> pluck name, age` or `.pluck("name", "age")`.
+    pub fn pluck_fields(&self) -> Option<&[String]> {
+        match self {
+            Command::Pipe(_, PipeCommand::Pluck(fields)) => Some(fields),
+            Command::Pipe(base, PipeCommand::Format(_) | PipeCommand::Full) => base.pluck_fields(),
+            Command::WithDatabase { command, .. } => command.pluck_fields(),
+            _ => None,
+        }
+    }
+
+    /// Whether this command's results are shown without previews or collapsing
+    ///
+    /// Set by `.full()` or `|> full`.
+    pub fn full_values(&self) -> bool {
+        match self {
+            Command::Pipe(_, PipeCommand::Full) => true,
+            Command::Pipe(base, PipeCommand::Format(_) | PipeCommand::Pluck(_)) => {
+                base.full_values()
+            }
+            Command::WithDatabase { command, .. } => command.full_values(),
+            _ => false,
+        }
+    }
+
+    /// Whether this command keeps running and printing until Ctrl+C
+    ///
+    /// True for `watch`, `oplog tail` and the monitors started with
+    /// `--follow` or `--watch`; such commands never produce a result.
+    pub fn runs_until_interrupted(&self) -> bool {
+        match self {
+            Command::Watch { .. } => true,
+            Command::Admin(admin) => matches!(
+                admin,
+                AdminCommand::OplogTail { .. }
+                    | AdminCommand::ShowLogs {
+                        follow: Some(_),
+                        ..
+                    }
+                    | AdminCommand::Top { watch: Some(_), .. }
+                    | AdminCommand::CurrentOp { watch: Some(_), .. }
+                    | AdminCommand::ReplicationLag { watch: Some(_), .. }
+            ),
+            Command::Pipe(base, _) => base.runs_until_interrupted(),
+            Command::WithDatabase { command, .. }
+            | Command::Utility(UtilityCommand::Inspect { command, .. }) => {
+                command.runs_until_interrupted()
+            }
+            Command::Parallel { commands, .. } => {
+                commands.iter().any(Command::runs_until_interrupted)
+            }
+            _ => false,
+        }
+    }
+}
+
 /// Query-related commands (CRUD operations)
 #[derive(Debug, Clone, PartialEq)]
 pub enum QueryCommand {
@@ -184,6 +292,7 @@ pub enum QueryCommand {
         collection: String,
         field: String,
         filter: Option<Document>,
+        collation: Option<Document>,
     },
 
     /// Bulk write operations
@@ -193,6 +302,42 @@ pub enum QueryCommand {
         ordered: bool,
     },
 
+    /// Update matching documents in `_id` batches, throttled to a write rate
+    BulkUpdateWithRateLimit {
+        collection: String,
+        filter: Document,
+        update: Document,
+        batch_size: u32,
+        ops_per_second: u32,
+    },
+
+    /// Report the BSON size of matching documents, largest first
+    DocSize {
+        collection: String,
+        filter: Document,
+        limit: i64,
+    },
+
+    /// Compare two collections document by document, matched on `_id`
+    Diff {
+        collection: String,
+        other: String,
+        options: DiffOptions,
+    },
+
+    /// Atlas Search query: `$search` stage body plus result limit
+    Search {
+        collection: String,
+        search: Document,
+        limit: i64,
+    },
+
+    /// Atlas Vector Search query: validated `$vectorSearch` stage body
+    VectorSearch {
+        collection: String,
+        vector_search: Document,
+    },
+
     /// Explain query execution plan
     Explain {
         collection: String,
@@ -223,6 +368,11 @@ impl QueryCommand {
             | QueryCommand::FindAndModify { collection, .. }
             | QueryCommand::Distinct { collection, .. }
             | QueryCommand::BulkWrite { collection, .. }
+            | QueryCommand::BulkUpdateWithRateLimit { collection, .. }
+            | QueryCommand::DocSize { collection, .. }
+            | QueryCommand::Diff { collection, .. }
+            | QueryCommand::Search { collection, .. }
+            | QueryCommand::VectorSearch { collection, .. }
             | QueryCommand::Explain { collection, .. } => collection,
         }
     }
@@ -236,6 +386,9 @@ impl QueryCommand {
                 | QueryCommand::Aggregate { .. }
                 | QueryCommand::CountDocuments { .. }
                 | QueryCommand::Distinct { .. }
+                | QueryCommand::DocSize { .. }
+                | QueryCommand::Search { .. }
+                | QueryCommand::VectorSearch { .. }
         )
     }
 }
@@ -347,8 +500,47 @@ pub enum AdminCommand {
     /// Show database profile information
     ShowProfile,
 
-    /// Show logs
-    ShowLogs(Option<String>),
+    /// Show logs: list available logs, or show one log with optional filters
+    ShowLogs {
+        /// Log name (global, startupWarnings, ...); `None` lists log names
+        log_type: Option<String>,
+        /// Case-insensitive text filter
+        grep: Option<String>,
+        /// Only show entries from the last N seconds
+        since: Option<u64>,
+        /// Keep polling for new entries every N seconds until Ctrl+C
+        follow: Option<u64>,
+    },
+
+    /// Show per-namespace lock time and counts from the `top` command
+    Top {
+        /// Seconds between samples until Ctrl+C; `None` shows totals since startup
+        watch: Option<u64>,
+        /// Maximum number of namespaces to show
+        limit: usize,
+    },
+
+    /// Size and time window of the oplog (rs.printOplogInfo())
+    OplogInfo,
+
+    /// Print oplog entries matching a filter as they are written (oplog tail)
+    OplogTail { filter: Document },
+
+    /// Show the lag of every replica set member behind the primary (rs.lag)
+    ReplicationLag {
+        /// Poll replSetGetStatus every N seconds until Ctrl+C
+        watch: Option<u64>,
+        /// Fail once a member is more than this many seconds behind
+        max_lag: Option<u64>,
+    },
+
+    /// List in-progress operations from `$currentOp`
+    CurrentOp {
+        /// Refresh every N seconds in an interactive monitor
+        watch: Option<u64>,
+        /// Include idle connections and sessions
+        all: bool,
+    },
 
     /// Switch to a database
     UseDatabase(String),
@@ -369,6 +561,22 @@ pub enum AdminCommand {
     /// List indexes on a collection
     ListIndexes(String),
 
+    /// Data, index and storage sizes of every database and collection
+    StorageReport,
+
+    /// Health report from serverStatus, replSetGetStatus, dbStats and
+    /// connPoolStats
+    ClusterReport,
+
+    /// Per-index usage counters from `$indexStats`
+    IndexStats(String),
+
+    /// Usage of every index in the current database, flagging indexes with
+    /// no operations since `since` (or since counting started)
+    IndexReport {
+        since: Option<bson::DateTime>,
+    },
+
     /// Drop a single index from a collection
     DropIndex { collection: String, index: String },
 
@@ -379,13 +587,25 @@ pub enum AdminCommand {
     },
 
     /// Drop a collection
-    DropCollection(String),
+    DropCollection {
+        collection: String,
+        /// Copy the collection to `<name>.__backup_YYYYMMDD` first; `None`
+        /// follows `safety.backup_before_drop`
+        backup: Option<bool>,
+    },
+
+    /// Drop the collections matching a `listCollections` filter, letting the
+    /// user pick which of the matches to drop
+    DropCollections { filter: Document },
 
     /// Rename a collection
     RenameCollection {
         collection: String,
         target: String,
         drop_target: bool,
+        /// Copy the collection (and a target that is dropped) to a backup
+        /// first; `None` follows `safety.backup_before_drop`
+        backup: Option<bool>,
     },
 
     /// Get collection statistics
@@ -393,6 +613,71 @@ pub enum AdminCommand {
         collection: String,
         scale: Option<i32>,
     },
+
+    /// Show the validator, validation level and action of a collection
+    GetValidator(String),
+
+    /// Replace the validator of a collection via collMod
+    SetValidator {
+        collection: String,
+        validator: Document,
+        level: Option<String>,
+        action: Option<String>,
+    },
+
+    /// Run the validate command on a collection
+    ValidateCollection { collection: String, full: bool },
+
+    /// Create a collection with options (capped, size, max, ...)
+    CreateCollection { name: String, options: Document },
+
+    /// Modify collection options via the collMod command
+    CollMod {
+        collection: String,
+        changes: Document,
+    },
+
+    /// Convert an existing collection to a capped collection
+    ConvertToCapped { collection: String, size: i64 },
+
+    /// Run an arbitrary server command, on the admin database if `admin` is set
+    RunCommand { command: Document, admin: bool },
+
+    /// Write collections of the current database to `<dir>/<db>/` as
+    /// mongodump-style `.bson` and `.metadata.json` files
+    Dump {
+        dir: String,
+        /// Collections to dump; all when empty
+        collections: Vec<String>,
+    },
+
+    /// Load a dump folder into the current database
+    Restore {
+        dir: String,
+        /// Collections to restore; all when empty
+        collections: Vec<String>,
+        /// Drop each collection before restoring it
+        drop: bool,
+    },
+
+    /// Restore the documents of the most recent delete from the trash
+    /// (undo last-delete, see `safety.trash`)
+    UndoLastDelete,
+
+    /// List the backups taken before drops and renames (backups)
+    ListBackups,
+
+    /// Insert the rows of a CSV file into a collection (import)
+    ImportCsv {
+        file: String,
+        /// Target collection, the file name without extension by default
+        collection: String,
+        options: CsvImportOptions,
+    },
+
+    /// Drop backups older than `older_than` seconds, or than
+    /// `safety.backup_retention_days` when `None` (backups cleanup)
+    CleanupBackups { older_than: Option<u64> },
 }
 
 /// Pipe commands for post-processing query results
@@ -401,11 +686,29 @@ pub enum PipeCommand {
     /// Export results to a file
     Export {
         format: ExportFormat,
+        /// Output file; `-` writes to stdout and `!command` pipes into a shell command
         file: Option<String>,
+        /// Explicit `{ compression: ... }` option; otherwise taken from the file extension
+        compression: Option<ExportCompression>,
     },
 
     /// Explain query execution plan
     Explain,
+
+    /// Show the results in this format instead of the current one
+    Format(OutputFormat),
+
+    /// Show only these fields of the results, fetched documents are unchanged
+    Pluck(Vec<String>),
+
+    /// Show whole strings, BinData and nested values instead of previews
+    Full,
+
+    /// Write the results to a file in the current format instead of showing them
+    Save(String),
+
+    /// Put the results on the clipboard as JSON instead of showing them
+    Copy,
 }
 
 /// Export format types
@@ -415,6 +718,155 @@ pub enum ExportFormat {
     JsonL,
     /// CSV format
     Csv,
+    /// Excel workbook (xlsx)
+    Xlsx,
+    /// Standalone HTML page with a sortable table
+    Html,
+}
+
+impl ExportFormat {
+    /// Parse a format name (`jsonl`/`json`, `csv`, `xlsx`/`excel`, `html`/`htm`)
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "jsonl" | "json" => Some(Self::JsonL),
+            "csv" => Some(Self::Csv),
+            "xlsx" | "excel" => Some(Self::Xlsx),
+            "html" | "htm" => Some(Self::Html),
+            _ => None,
+        }
+    }
+
+    /// Detect the format from a file name, ignoring a `.gz`/`.zst` suffix
+    pub fn from_path(path: &str) -> Option<Self> {
+        let mut path = std::path::Path::new(path);
+        if ExportCompression::from_path(&path.to_string_lossy()).is_some() {
+            path = std::path::Path::new(path.file_stem()?);
+        }
+        Self::from_name(&path.extension()?.to_str()?.to_lowercase())
+    }
+}
+
+/// Export options given as `{ format: ..., compression: ... }`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ExportOptions {
+    /// Output format
+    pub format: Option<ExportFormat>,
+    /// Compression of the output
+    pub compression: Option<ExportCompression>,
+}
+
+/// Compression applied to export files
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExportCompression {
+    /// gzip (`.gz`)
+    Gzip,
+    /// Zstandard (`.zst`)
+    Zstd,
+}
+
+impl ExportCompression {
+    /// Parse a compression name as given in export options
+    ///
+    /// # Arguments
+    /// * `name` - `gzip`/`gz` or `zstd`/`zst`, case-insensitive
+    ///
+    /// # Returns
+    /// * `Option<Self>` - Compression, or `None` if the name is unknown
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "gzip" | "gz" => Some(Self::Gzip),
+            "zstd" | "zst" => Some(Self::Zstd),
+            _ => None,
+        }
+    }
+
+    /// Detect compression from a file name extension (`.gz`, `.zst`)
+    pub fn from_path(path: &str) -> Option<Self> {
+        let extension = std::path::Path::new(path).extension()?.to_str()?;
+        match extension.to_lowercase().as_str() {
+            "gz" => Some(Self::Gzip),
+            "zst" => Some(Self::Zstd),
+            _ => None,
+        }
+    }
+
+    /// File name extension, without the dot
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Gzip => "gz",
+            Self::Zstd => "zst",
+        }
+    }
+}
+
+/// Languages writing numbers as `1,234.5`
+const DECIMAL_POINT_LANGUAGES: &[&str] = &[
+    "en", "ja", "zh", "ko", "he", "th", "hi", "ms", "fil", "ga", "mt",
+];
+
+/// Languages writing numbers as `1.234,5`
+const DECIMAL_COMMA_LANGUAGES: &[&str] = &[
+    "de", "es", "it", "nl", "pt", "da", "id", "tr", "el", "ro", "hr", "sl", "sr", "ca",
+];
+
+/// Languages writing numbers as `1 234,5`
+const DECIMAL_COMMA_SPACE_LANGUAGES: &[&str] = &[
+    "fr", "ru", "pl", "cs", "sk", "sv", "nb", "no", "nn", "fi", "uk", "hu", "bg", "et", "lv", "lt",
+];
+
+/// How fields and numbers are written in a CSV file read by `import`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CsvImportOptions {
+    /// Field delimiter
+    pub delimiter: char,
+    /// Decimal separator of numbers
+    pub decimal_separator: char,
+    /// Digit grouping separator accepted inside numbers, if any
+    pub thousands_separator: Option<char>,
+}
+
+impl Default for CsvImportOptions {
+    fn default() -> Self {
+        Self {
+            delimiter: ',',
+            decimal_separator: '.',
+            thousands_separator: None,
+        }
+    }
+}
+
+impl CsvImportOptions {
+    /// Number format of a locale, e.g. `de`, `fr-FR` or `en_US`
+    ///
+    /// Locales with a decimal comma use `;` as field delimiter, as spreadsheet
+    /// applications do when they export CSV.
+    ///
+    /// # Arguments
+    /// * `locale` - Language tag, optionally with a region
+    ///
+    /// # Returns
+    /// * `Option<Self>` - Options for the locale, or `None` if it is unknown
+    pub fn for_locale(locale: &str) -> Option<Self> {
+        let tag = locale.replace('_', "-").to_lowercase();
+        let (language, region) = tag.split_once('-').unwrap_or((tag.as_str(), ""));
+        let (decimal_separator, thousands_separator) = if region == "ch" || region == "li" {
+            ('.', '\'')
+        } else if DECIMAL_POINT_LANGUAGES.contains(&language) {
+            ('.', ',')
+        } else if DECIMAL_COMMA_LANGUAGES.contains(&language) {
+            (',', '.')
+        } else if DECIMAL_COMMA_SPACE_LANGUAGES.contains(&language) {
+            (',', ' ')
+        } else {
+            return None;
+        };
+
+        Some(Self {
+            delimiter: if decimal_separator == ',' { ';' } else { ',' },
+            decimal_separator,
+            thousands_separator: Some(thousands_separator),
+        })
+    }
 }
 
 /// Utility commands
@@ -422,17 +874,103 @@ pub enum ExportFormat {
 /// Utility commands for shell operations
 pub enum UtilityCommand {
     /// Print/echo a value
-    #[allow(dead_code)]
     Print(String),
 
+    /// Print a value as JSON (printjson, JSON.parse, Object.keys)
+    PrintJson(Bson),
+
+    /// Run a command and pass its result to a shell helper
+    /// (printjson(db.users.findOne()), Object.keys(db.users.findOne()))
+    Inspect {
+        helper: ValueHelper,
+        command: Box<Command>,
+        /// Pass the first document, or null, instead of all results (findOne)
+        single: bool,
+    },
+
+    /// Pause for a number of milliseconds (sleep)
+    Sleep(u64),
+
     /// Iterate through more results (it command)
     Iterate,
+
+    /// Compute the encoded BSON size of a document (Object.bsonsize)
+    BsonSize(Document),
+
+    /// Build a `$lookup` stage interactively (lookup wizard)
+    LookupWizard,
+
+    /// Write the previous result to a file in the current format (output last > file)
+    SaveLastResult(String),
+
+    /// Put the previous result on the clipboard as JSON (copy)
+    CopyLastResult,
+
+    /// Show one field of the previous result in full (expand <path>)
+    ExpandLastResult(String),
+
+    /// Show the driver code equivalent to a command (translate)
+    Translate {
+        language: DriverLanguage,
+        command: Box<Command>,
+    },
+
+    /// Show the shell query a SQL statement translates to (sql explain)
+    SqlExplain(Box<Command>),
+
+    /// Summarize what a command would do without running it (describe)
+    Describe(Box<Command>),
+
+    /// Input run by the plugin that registered its prefix
+    Plugin { prefix: String, input: String },
+}
+
+/// Shell helpers that print a value, applied to a command's result by
+/// [`UtilityCommand::Inspect`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueHelper {
+    /// printjson(value)
+    PrintJson,
+
+    /// tojson(value)
+    ToJson,
+
+    /// JSON.stringify(value)
+    Stringify,
+
+    /// Object.keys(document)
+    Keys,
+}
+
+/// Driver languages `translate` generates code for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DriverLanguage {
+    Rust,
+    Node,
+    Python,
+    Go,
+    Java,
+}
+
+impl DriverLanguage {
+    /// Parse a language name (`rust`, `node`/`js`, `python`/`py`, `go`, `java`)
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "rust" | "rs" => Some(Self::Rust),
+            "node" | "nodejs" | "js" | "javascript" => Some(Self::Node),
+            "python" | "py" => Some(Self::Python),
+            "go" | "golang" => Some(Self::Go),
+            "java" => Some(Self::Java),
+            _ => None,
+        }
+    }
 }
 
 /// Configuration commands for runtime settings
 #[derive(Debug, Clone, PartialEq)]
 pub enum ConfigCommand {
-    /// Set output format (shell, json, json-pretty, table, compact)
+    /// Set output format (shell, json, json-pretty, table, compact, extjson,
+    /// extjson-canonical, chart, map)
     SetFormat(String),
 
     /// Get current format
@@ -447,6 +985,17 @@ pub enum ConfigCommand {
     /// Show all current settings
     ShowConfig,
 
+    /// Show one setting by its dotted key (config get)
+    GetSetting(String),
+
+    /// Change one setting by its dotted key (config set)
+    SetSetting {
+        key: String,
+        value: String,
+        /// Also write the setting to the config file
+        save: bool,
+    },
+
     /// List all named queries
     ListNamedQueries,
 
@@ -459,6 +1008,39 @@ pub enum ConfigCommand {
     /// Delete a named query
     DeleteNamedQuery(String),
 
+    /// List query bookmarks (bookmark list)
+    ListBookmarks,
+
+    /// Bookmark a query, which may contain `{{name}}` placeholders
+    SaveBookmark { name: String, query: String },
+
+    /// Run a bookmarked query, prompting for its placeholders
+    RunBookmark(String),
+
+    /// Delete a query bookmark
+    DeleteBookmark(String),
+
+    /// List loaded plugins (plugin list)
+    ListPlugins,
+
+    /// Show a plugin's formats, commands and settings
+    PluginInfo(String),
+
+    /// Turn a plugin's formats and commands on or off for the session
+    SetPluginEnabled { name: String, enabled: bool },
+
+    /// Turn the read result cache on or off for the session (cache on|off)
+    SetResultCache(bool),
+
+    /// Drop every cached result (cache clear)
+    ClearResultCache,
+
+    /// Show cache settings, size and hit rate (cache stats)
+    ResultCacheStats,
+
+    /// Switch between previews and whole values for the session (show full)
+    ToggleFullValues,
+
     /// Generate AI context for the current database
     AiGenerate {
         /// Optional: generate only for this collection
@@ -469,6 +1051,12 @@ pub enum ConfigCommand {
 
     /// Show AI context status
     AiStatus,
+
+    /// Save the session state to a file
+    SaveSession(String),
+
+    /// Restore the session state from a file
+    LoadSession(String),
 }
 
 /// Options for find operations
@@ -500,6 +1088,18 @@ pub struct FindOptions {
 
     /// Read concern level
     pub read_concern: Option<Document>,
+
+    /// Return every match, ignoring `safety.max_results` (set by `.noLimit()`)
+    #[serde(default)]
+    pub no_limit: bool,
+
+    /// Read the collection as it was at this cluster time (set by `.asOf()`)
+    #[serde(default)]
+    pub as_of: Option<Timestamp>,
+
+    /// Scan this many `_id` ranges concurrently (set by `.parallel()`)
+    #[serde(default)]
+    pub parallel: Option<u32>,
 }
 
 /// Options for update operations
@@ -544,6 +1144,27 @@ pub struct AggregateOptions {
 
     /// Let variables for aggregation expressions
     pub let_vars: Option<Document>,
+
+    /// Run the pipeline over this many `_id` ranges concurrently (set by
+    /// `.parallel()`)
+    #[serde(default)]
+    pub parallel: Option<u32>,
+}
+
+/// Options for `db.collection.diff(other, options)`
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct DiffOptions {
+    /// Only compare documents matching this filter, on both sides
+    pub filter: Document,
+
+    /// Database of the other collection; the current one when unset
+    pub database: Option<String>,
+
+    /// Named datasource holding the other collection; the current connection when unset
+    pub datasource: Option<String>,
+
+    /// File receiving every difference as JSON Lines
+    pub output: Option<String>,
 }
 
 /// Options for findAndModify operations
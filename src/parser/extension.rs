@@ -0,0 +1,112 @@
+//! Commands added by plugins
+//!
+//! A plugin registers a [`CommandExtension`] under a prefix word such as
+//! `graph`. Input starting with that word that no built-in command claims is
+//! handed to the extension, which either translates it into a regular
+//! [`Command`] or leaves it to its own `CommandExtension::execute`, which runs
+//! asynchronously in the session and returns [`ResultData`](crate::executor::ResultData) that is displayed
+//! with the current output format like any other result. Registered prefixes
+//! are process-wide.
+
+use std::sync::{Arc, RwLock};
+
+use super::command::Command;
+use crate::error::{MongoshError, Result};
+#[cfg(feature = "native")]
+use crate::executor::{ExecutionContext, ResultData};
+
+/// Parses and runs the commands behind a prefix
+#[cfg_attr(feature = "native", async_trait::async_trait)]
+pub trait CommandExtension: Send + Sync {
+    /// Parse the input after the prefix
+    ///
+    /// # Arguments
+    /// * `input` - Input after the prefix word, trimmed; may be empty
+    ///
+    /// # Returns
+    /// * `Result<Option<Command>>` - A command for the shell to run, or `None`
+    ///   to run the input with `execute`
+    fn parse(&self, input: &str) -> Result<Option<Command>>;
+
+    /// Run input that [`parse`](Self::parse) left to the plugin
+    ///
+    /// # Arguments
+    /// * `input` - Input after the prefix word, trimmed
+    /// * `context` - Session the command runs in, e.g. to run queries
+    ///
+    /// # Returns
+    /// * `Result<ResultData>` - Documents, a message or any other result
+    #[cfg(feature = "native")]
+    async fn execute(&self, _input: &str, _context: &ExecutionContext) -> Result<ResultData> {
+        Err(MongoshError::NotImplemented(
+            "this plugin command only translates to shell commands".to_string(),
+        ))
+    }
+}
+
+/// Extensions registered by plugins, by lowercase prefix
+static EXTENSIONS: RwLock<Vec<(String, Arc<dyn CommandExtension>)>> = RwLock::new(Vec::new());
+
+/// Register a command prefix
+///
+/// # Arguments
+/// * `prefix` - Word the commands start with, matched case-insensitively
+/// * `extension` - Parser and executor for the commands
+///
+/// # Returns
+/// * `Result<()>` - Error if the prefix is not a single word or is taken
+#[allow(dead_code)] // library API, not used by the binary
+pub fn register_extension(prefix: &str, extension: Arc<dyn CommandExtension>) -> Result<()> {
+    let prefix = prefix.trim().to_lowercase();
+    let valid = prefix
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if prefix.is_empty() || !valid || prefix == "db" {
+        return Err(MongoshError::Generic(format!(
+            "Invalid command prefix '{}'",
+            prefix
+        )));
+    }
+
+    let mut extensions = EXTENSIONS.write().unwrap_or_else(|e| e.into_inner());
+    if extensions
+        .iter()
+        .any(|(registered, _)| *registered == prefix)
+    {
+        return Err(MongoshError::Generic(format!(
+            "Command prefix '{}' is already registered",
+            prefix
+        )));
+    }
+    extensions.push((prefix, extension));
+    Ok(())
+}
+
+/// Remove a registered prefix, e.g. when its plugin is disabled
+///
+/// # Arguments
+/// * `prefix` - Command prefix
+/// * `extension` - Extension it was registered with; a prefix registered by
+///   someone else stays
+///
+/// # Returns
+/// * `bool` - Whether the prefix was removed
+#[cfg(feature = "plugins")]
+pub(crate) fn unregister_extension(prefix: &str, extension: &Arc<dyn CommandExtension>) -> bool {
+    let mut extensions = EXTENSIONS.write().unwrap_or_else(|e| e.into_inner());
+    let before = extensions.len();
+    extensions.retain(|(registered, registered_extension)| {
+        !(registered.eq_ignore_ascii_case(prefix) && Arc::ptr_eq(registered_extension, extension))
+    });
+    extensions.len() < before
+}
+
+/// Look up the extension registered for a prefix
+pub(crate) fn find_extension(prefix: &str) -> Option<Arc<dyn CommandExtension>> {
+    EXTENSIONS
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .iter()
+        .find(|(registered, _)| registered.eq_ignore_ascii_case(prefix))
+        .map(|(_, extension)| extension.clone())
+}
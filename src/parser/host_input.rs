@@ -0,0 +1,233 @@
+//! Values the shell supplies to expressions: answers to `prompt()` and the
+//! variables read by `env()`
+//!
+//! Parsing never reads the terminal or the process environment. Before a
+//! command is parsed, the REPL or script runner lists the `prompt()` and
+//! `passwordPrompt()` calls with [`prompt_requests`], asks the user, and hands
+//! the answers to the parser with
+//! [`Parser::set_prompt_answers`](super::Parser::set_prompt_answers). The
+//! converter then takes one answer per call, in source order. Environment
+//! variables are only visible to `env()` and `process.env` after the caller
+//! opts in with [`Parser::set_environment`](super::Parser::set_environment).
+//! Callers that do neither (the REST bridge, JSON-RPC mode, embedding) get an
+//! error instead of a blocking read or a leaked secret.
+//!
+//! Like `_`, these values are set for the duration of
+//! [`Parser::parse`](super::Parser::parse) through a [`Scope`].
+
+use std::cell::RefCell;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+
+use super::mongo_lexer::{MongoLexer, MongoTokenKind};
+use crate::error::{ParseError, Result};
+
+thread_local! {
+    /// Answers for the command being parsed, `None` outside `Parser::parse`
+    static PROMPT_ANSWERS: RefCell<Option<VecDeque<String>>> = const { RefCell::new(None) };
+
+    /// Variables `env()` may read, `None` unless the caller opted in
+    static ENVIRONMENT: RefCell<Option<Arc<HashMap<String, String>>>> = const { RefCell::new(None) };
+}
+
+/// A `prompt()` or `passwordPrompt()` call found in a command
+#[derive(Debug, Clone, PartialEq)]
+pub struct PromptRequest {
+    /// Text shown before the cursor, without the trailing `: `
+    pub label: String,
+
+    /// Whether the answer must not be echoed
+    pub password: bool,
+}
+
+/// Makes prompt answers and environment variables available to the
+/// converter until dropped
+///
+/// Nested parses, such as the tasks of `parallel()`, share the values of
+/// the outermost scope.
+pub(crate) struct Scope {
+    /// Whether this scope installed the values and must clear them
+    outermost: bool,
+}
+
+impl Scope {
+    /// Install the values unless a parse is already in progress
+    ///
+    /// # Arguments
+    /// * `answers` - Answers in the order the calls appear in the command
+    /// * `environment` - Variables `env()` may read; `None` rejects `env()`
+    pub(crate) fn enter(
+        answers: Vec<String>,
+        environment: Option<Arc<HashMap<String, String>>>,
+    ) -> Self {
+        let outermost = PROMPT_ANSWERS.with_borrow_mut(|current| {
+            if current.is_some() {
+                return false;
+            }
+            *current = Some(answers.into());
+            true
+        });
+        if outermost {
+            ENVIRONMENT.set(environment);
+        }
+        Self { outermost }
+    }
+}
+
+impl Drop for Scope {
+    fn drop(&mut self) {
+        if self.outermost {
+            PROMPT_ANSWERS.set(None);
+            ENVIRONMENT.set(None);
+        }
+    }
+}
+
+/// List the `prompt()` and `passwordPrompt()` calls of a command
+///
+/// Calls whose label is not a string literal are skipped; the converter
+/// rejects them without consuming an answer.
+///
+/// # Arguments
+/// * `input` - Command text
+///
+/// # Returns
+/// * `Vec<PromptRequest>` - Calls in source order
+pub fn prompt_requests(input: &str) -> Vec<PromptRequest> {
+    let tokens = MongoLexer::tokenize(input);
+    let kind = |i: usize| tokens.get(i).map(|token| &token.kind);
+    let mut requests = Vec::new();
+
+    for i in 0..tokens.len() {
+        let password = match kind(i) {
+            Some(MongoTokenKind::Ident(name)) if name == "prompt" => false,
+            Some(MongoTokenKind::Ident(name)) if name == "passwordPrompt" => true,
+            _ => continue,
+        };
+        // `x.prompt()` is a method call, not the shell helper
+        if i > 0 && kind(i - 1) == Some(&MongoTokenKind::Dot) {
+            continue;
+        }
+
+        let label = match (kind(i + 1), kind(i + 2), kind(i + 3)) {
+            (Some(MongoTokenKind::LParen), Some(MongoTokenKind::RParen), _) => {
+                default_label(password).to_string()
+            }
+            (
+                Some(MongoTokenKind::LParen),
+                Some(MongoTokenKind::String(label)),
+                Some(MongoTokenKind::RParen),
+            ) => label.clone(),
+            _ => continue,
+        };
+        requests.push(PromptRequest { label, password });
+    }
+
+    requests
+}
+
+/// Label shown when a call gives none
+fn default_label(password: bool) -> &'static str {
+    if password {
+        "Enter password"
+    } else {
+        "Enter value"
+    }
+}
+
+/// Take the answer for the next prompt call
+///
+/// # Arguments
+/// * `fn_name` - `prompt` or `passwordPrompt`, for the error message
+pub(crate) fn next_answer(fn_name: &str) -> Result<String> {
+    PROMPT_ANSWERS
+        .with_borrow_mut(|answers| answers.as_mut().and_then(VecDeque::pop_front))
+        .ok_or_else(|| {
+            ParseError::InvalidQuery(format!(
+                "{}() is only available in the interactive shell and scripts run from a terminal",
+                fn_name
+            ))
+            .into()
+        })
+}
+
+/// Look up an environment variable for `env()` or `process.env`
+///
+/// # Arguments
+/// * `name` - Variable name
+///
+/// # Returns
+/// * `Result<Option<String>>` - Value, `None` if unset, or an error when the
+///   caller did not make the environment available
+pub(crate) fn env_var(name: &str) -> Result<Option<String>> {
+    ENVIRONMENT.with_borrow(|environment| {
+        environment
+            .as_ref()
+            .map(|vars| vars.get(name).cloned())
+            .ok_or_else(|| {
+                ParseError::InvalidQuery(
+                    "env() and process.env are only available in the interactive shell and scripts"
+                        .to_string(),
+                )
+                .into()
+            })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prompt_requests() {
+        let requests = prompt_requests(
+            "db.users.insertOne({ name: prompt('Name'), pwd: passwordPrompt(), tag: x.prompt() })",
+        );
+        assert_eq!(
+            requests,
+            vec![
+                PromptRequest {
+                    label: "Name".to_string(),
+                    password: false,
+                },
+                PromptRequest {
+                    label: "Enter password".to_string(),
+                    password: true,
+                },
+            ]
+        );
+
+        assert!(prompt_requests("print('prompt(\"x\")')").is_empty());
+        assert!(prompt_requests("prompt(42)").is_empty());
+    }
+
+    #[test]
+    fn test_answers_are_scoped() {
+        assert!(next_answer("prompt").is_err());
+        {
+            let _scope = Scope::enter(vec!["a".to_string(), "b".to_string()], None);
+            {
+                // A nested parse keeps the outer answers
+                let _inner = Scope::enter(Vec::new(), None);
+                assert_eq!(next_answer("prompt").unwrap(), "a");
+            }
+            assert_eq!(next_answer("prompt").unwrap(), "b");
+            assert!(next_answer("prompt").is_err());
+        }
+        assert!(next_answer("prompt").is_err());
+    }
+
+    #[test]
+    fn test_environment_is_opt_in() {
+        assert!(env_var("HOME").is_err());
+        {
+            let _scope = Scope::enter(Vec::new(), None);
+            assert!(env_var("HOME").is_err());
+        }
+
+        let vars = HashMap::from([("STAGE".to_string(), "prod".to_string())]);
+        let _scope = Scope::enter(Vec::new(), Some(Arc::new(vars)));
+        assert_eq!(env_var("STAGE").unwrap().as_deref(), Some("prod"));
+        assert_eq!(env_var("UNSET").unwrap(), None);
+    }
+}
@@ -0,0 +1,247 @@
+//! The `_` variable: the previous result used in a later command
+//!
+//! After a command returns data, `_` stands for that data while the next
+//! command is parsed: an array for documents and listings, a document for
+//! `findOne()`, a number for counts. It supports indexing and field access
+//! (`_[0]`, `_[0].email`, `_.length`) and `map` with a field accessor, so
+//! the ids of one query can feed another:
+//!
+//! ```text
+//! db.orders.find({ userId: { $in: _.map(d => d._id) } })
+//! ```
+//!
+//! The value is set for the duration of [`Parser::parse`](super::Parser::parse)
+//! through a [`Scope`], since expression conversion has no parser state.
+
+use std::cell::RefCell;
+
+use bson::Bson;
+
+use super::mongo_ast::{CallExpr, Expr, MemberProperty};
+use super::mongo_converter::ExpressionConverter;
+use crate::error::{ParseError, Result};
+
+thread_local! {
+    /// Value of `_` for the command being parsed
+    static LAST_RESULT: RefCell<Option<Bson>> = const { RefCell::new(None) };
+}
+
+/// Makes a value available as `_` until dropped
+pub(crate) struct Scope {
+    /// Value of `_` outside this scope
+    previous: Option<Bson>,
+}
+
+impl Scope {
+    /// Set the value of `_`
+    ///
+    /// # Arguments
+    /// * `value` - Previous result, `None` when there is none
+    pub(crate) fn enter(value: Option<Bson>) -> Self {
+        Self {
+            previous: LAST_RESULT.replace(value),
+        }
+    }
+}
+
+impl Drop for Scope {
+    fn drop(&mut self) {
+        LAST_RESULT.set(self.previous.take());
+    }
+}
+
+/// Whether an expression starts from `_`, e.g. `_`, `_[0].name` or `_.map(...)`
+pub(crate) fn is_reference(expr: &Expr) -> bool {
+    match expr {
+        Expr::Ident(name) => name == "_",
+        Expr::Member(member) => is_reference(&member.object),
+        Expr::Call(call) => is_reference(&call.callee),
+        _ => false,
+    }
+}
+
+/// Current value of `_`
+pub(crate) fn value() -> Result<Bson> {
+    LAST_RESULT.with_borrow(Clone::clone).ok_or_else(|| {
+        ParseError::InvalidQuery(
+            "_ refers to the previous result, but no command has returned data yet".to_string(),
+        )
+        .into()
+    })
+}
+
+/// Read a property of a value derived from `_`
+///
+/// Arrays support `length` and numeric indexes, documents support field
+/// names; anything missing is `null`.
+///
+/// # Arguments
+/// * `object` - Value the property is read from
+/// * `property` - Property name or computed key
+pub(crate) fn property(object: Bson, property: &MemberProperty) -> Result<Bson> {
+    let key = match property {
+        MemberProperty::Ident(name) => Bson::String(name.clone()),
+        MemberProperty::Computed(expr) => ExpressionConverter::expr_to_bson(expr)?,
+    };
+
+    Ok(match (object, key) {
+        (Bson::Array(items), Bson::String(name)) if name == "length" => {
+            Bson::Int32(items.len() as i32)
+        }
+        (Bson::Array(items), index) => index_of(&index)
+            .and_then(|index| items.into_iter().nth(index))
+            .unwrap_or(Bson::Null),
+        (Bson::Document(mut document), Bson::String(name)) => {
+            document.remove(&name).unwrap_or(Bson::Null)
+        }
+        _ => Bson::Null,
+    })
+}
+
+/// Evaluate a method call on a value derived from `_`
+///
+/// Only `map` with a field accessor such as `d => d._id` or
+/// `(d) => d.profile.city` is supported.
+///
+/// # Arguments
+/// * `call` - Call whose callee is a member of a `_` value
+pub(crate) fn call(call: &CallExpr) -> Result<Bson> {
+    let Expr::Member(member) = call.callee.as_ref() else {
+        return Err(ParseError::InvalidQuery("_ cannot be called".to_string()).into());
+    };
+
+    match &member.property {
+        MemberProperty::Ident(method) if method == "map" => {
+            let items = match ExpressionConverter::expr_to_bson(&member.object)? {
+                Bson::Array(items) => items,
+                _ => {
+                    return Err(ParseError::InvalidQuery("map() needs an array".to_string()).into());
+                }
+            };
+            let path = match call.arguments.as_slice() {
+                [Expr::Function(source)] => accessor_path(source)?,
+                _ => return Err(unsupported_map()),
+            };
+            Ok(Bson::Array(
+                items
+                    .into_iter()
+                    .map(|item| field_at(item, &path))
+                    .collect(),
+            ))
+        }
+        MemberProperty::Ident(method) => Err(ParseError::InvalidQuery(format!(
+            "Unsupported method on _: {}(). Use map(d => d.field)",
+            method
+        ))
+        .into()),
+        MemberProperty::Computed(_) => {
+            Err(ParseError::InvalidQuery("_ cannot be called".to_string()).into())
+        }
+    }
+}
+
+/// Array index from a number key
+fn index_of(key: &Bson) -> Option<usize> {
+    let index = match key {
+        Bson::Int32(n) => *n as f64,
+        Bson::Int64(n) => *n as f64,
+        Bson::Double(n) => *n,
+        _ => return None,
+    };
+    (index >= 0.0 && index.fract() == 0.0).then_some(index as usize)
+}
+
+/// Field path read by an arrow function such as `d => d.profile.city`
+///
+/// # Returns
+/// * `Result<Vec<String>>` - Path segments, empty for `d => d`
+fn accessor_path(source: &str) -> Result<Vec<String>> {
+    let (param, body) = source.split_once("=>").ok_or_else(unsupported_map)?;
+    let param = param
+        .trim()
+        .trim_start_matches('(')
+        .trim_end_matches(')')
+        .trim();
+    let body = body.trim();
+    if param.is_empty() || param.contains(',') {
+        return Err(unsupported_map());
+    }
+
+    let path = match body.strip_prefix(param) {
+        Some("") => return Ok(Vec::new()),
+        Some(rest) => rest.strip_prefix('.').ok_or_else(unsupported_map)?,
+        None => return Err(unsupported_map()),
+    };
+    let segments: Vec<String> = path.split('.').map(str::to_string).collect();
+    let is_name = |segment: &String| {
+        !segment.is_empty()
+            && segment
+                .chars()
+                .all(|c| c.is_alphanumeric() || c == '_' || c == '$')
+    };
+    if segments.iter().all(is_name) {
+        Ok(segments)
+    } else {
+        Err(unsupported_map())
+    }
+}
+
+/// Value at a field path, `null` when missing
+fn field_at(value: Bson, path: &[String]) -> Bson {
+    path.iter().fold(value, |value, segment| match value {
+        Bson::Document(mut document) => document.remove(segment).unwrap_or(Bson::Null),
+        _ => Bson::Null,
+    })
+}
+
+fn unsupported_map() -> crate::error::MongoshError {
+    ParseError::InvalidQuery(
+        "_.map() supports field accessors such as d => d._id or d => d.profile.city".to_string(),
+    )
+    .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bson::doc;
+
+    #[test]
+    fn test_accessor_path() {
+        assert_eq!(accessor_path("d => d._id").unwrap(), vec!["_id"]);
+        assert_eq!(
+            accessor_path("(u) => u.profile.city").unwrap(),
+            vec!["profile", "city"]
+        );
+        assert!(accessor_path("d => d").unwrap().is_empty());
+        assert!(accessor_path("d => x._id").is_err());
+        assert!(accessor_path("d => d._id + 1").is_err());
+        assert!(accessor_path("(a, b) => a.x").is_err());
+    }
+
+    #[test]
+    fn test_field_at() {
+        let value = Bson::Document(doc! { "profile": { "city": "Oslo" } });
+        let path = ["profile".to_string(), "city".to_string()];
+        assert_eq!(
+            field_at(value.clone(), &path),
+            Bson::String("Oslo".to_string())
+        );
+        assert_eq!(field_at(value, &["x".to_string()]), Bson::Null);
+    }
+
+    #[test]
+    fn test_scope() {
+        assert!(value().is_err());
+        {
+            let _scope = Scope::enter(Some(Bson::Int64(3)));
+            assert_eq!(value().unwrap(), Bson::Int64(3));
+            {
+                let _inner = Scope::enter(None);
+                assert!(value().is_err());
+            }
+            assert_eq!(value().unwrap(), Bson::Int64(3));
+        }
+        assert!(value().is_err());
+    }
+}
@@ -6,12 +6,16 @@
 //! # Architecture
 //!
 //! The parser is split into multiple focused modules:
+//! - `codegen`: Driver code generation for `translate`
 //! - `command`: Command type definitions (Command, QueryCommand, AdminCommand, etc.)
+//! - `extension`: Command prefixes registered by plugins
 //! - `mongo_ast`: MongoDB shell AST structures
 //! - `mongo_lexer`: MongoDB shell lexer for tokenization
 //! - `mongo_parser`: MongoDB shell parser
 //! - `mongo_operation`: Parser for db.collection.operation() syntax
 //! - `mongo_converter`: MongoDB expression to BSON converter
+//! - `last_result`: The `_` variable holding the previous result
+//! - `host_input`: Answers to `prompt()` and variables for `env()` supplied by the shell
 //! - `shell_commands`: Parser for shell commands (show, use, help, etc.)
 //! - `sql_*`: SQL query parsing modules
 //!
@@ -32,25 +36,58 @@
 //! let cmd = parser.parse("db.logs.aggregate([{ $match: {} }])").unwrap();
 //! ```
 
+mod codegen;
 mod command;
+mod extension;
+mod host_input;
+mod last_result;
 mod mongo_ast;
 mod mongo_converter;
 mod mongo_lexer;
 mod mongo_operation;
 mod mongo_parser;
 mod shell_commands;
+#[cfg(feature = "sql")]
 mod sql_context;
+#[cfg(feature = "sql")]
 mod sql_expr;
+#[cfg(feature = "sql")]
 mod sql_lexer;
+#[cfg(feature = "sql")]
 mod sql_parser;
 
 // Re-export public API
+pub use codegen::{shell_query, translate};
 pub use command::*;
+#[cfg(feature = "native")]
+pub(crate) use extension::find_extension;
+#[cfg(feature = "plugins")]
+pub(crate) use extension::unregister_extension;
+#[allow(unused_imports)]
+pub use extension::{CommandExtension, register_extension};
+pub use host_input::{PromptRequest, prompt_requests};
+#[cfg(feature = "script")]
+pub(crate) use mongo_lexer::REGEX_PRECEDING_KEYWORDS;
 pub use mongo_lexer::{MongoLexer, MongoToken, MongoTokenKind};
+#[cfg(feature = "native")]
+pub(crate) use mongo_operation::MAX_NUM_CANDIDATES;
+// Public for benchmarks; the binary goes through `Parser`
+#[allow(unused_imports)]
+pub use mongo_parser::MongoParser;
+#[cfg(feature = "sql")]
 pub use sql_lexer::{SqlLexer, Token as SqlToken, TokenKind as SqlTokenKind};
 
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use bson::Bson;
+
+use crate::config::OutputFormat;
 use crate::error::{ParseError, Result};
 
+/// Seconds between runs of `watch` when no interval is given
+const DEFAULT_WATCH_INTERVAL: u64 = 2;
+
 /// Main parser for mongosh commands
 ///
 /// This parser handles all types of MongoDB shell commands including:
@@ -58,7 +95,16 @@ use crate::error::{ParseError, Result};
 /// - Administrative commands (show, use, create, drop, etc.)
 /// - Utility commands (print, help, etc.)
 /// - Script execution
-pub struct Parser {}
+pub struct Parser {
+    /// Value of `_` in parsed expressions
+    last_result: Option<Bson>,
+
+    /// Answers for the `prompt()` calls of the next command
+    prompt_answers: Vec<String>,
+
+    /// Variables readable with `env()`, `None` until the caller opts in
+    environment: Option<Arc<HashMap<String, String>>>,
+}
 
 impl Parser {
     /// Create a new parser instance
@@ -71,7 +117,40 @@ impl Parser {
     /// let parser = Parser::new();
     /// ```
     pub fn new() -> Self {
-        Self {}
+        Self {
+            last_result: None,
+            prompt_answers: Vec::new(),
+            environment: None,
+        }
+    }
+
+    /// Set the previous result, referenced as `_` in later commands
+    ///
+    /// # Arguments
+    /// * `value` - Previous result data, `None` when there is none
+    pub fn set_last_result(&mut self, value: Option<Bson>) {
+        self.last_result = value;
+    }
+
+    /// Set the answers for the `prompt()` calls of the next command
+    ///
+    /// The answers are used by the next call to [`Parser::parse`] only.
+    ///
+    /// # Arguments
+    /// * `answers` - One answer per call listed by [`prompt_requests`], in order
+    pub fn set_prompt_answers(&mut self, answers: Vec<String>) {
+        self.prompt_answers = answers;
+    }
+
+    /// Let `env()` and `process.env` read these variables
+    ///
+    /// Without this call they fail to parse, so servers that parse commands
+    /// from other processes never expose their environment.
+    ///
+    /// # Arguments
+    /// * `vars` - Variables by name, e.g. `std::env::vars().collect()`
+    pub fn set_environment(&mut self, vars: HashMap<String, String>) {
+        self.environment = Some(Arc::new(vars));
     }
 
     /// Parse an input string into a Command
@@ -109,6 +188,68 @@ impl Parser {
             return Err(ParseError::InvalidCommand("Empty input".to_string()).into());
         }
 
+        // Expressions see the previous result as `_` while this command is parsed
+        let _last_result = last_result::Scope::enter(self.last_result.clone());
+        let _host_input = host_input::Scope::enter(
+            std::mem::take(&mut self.prompt_answers),
+            self.environment.clone(),
+        );
+
+        // Check for watch, which wraps a whole command including pipes
+        if let (keyword, rest) = Self::split_word(trimmed)
+            && keyword == "watch"
+            && !rest.is_empty()
+        {
+            return self.parse_watch(rest);
+        }
+
+        // Translate also takes the whole command, which is parsed but not run
+        if let (keyword, rest) = Self::split_word(trimmed)
+            && keyword == "translate"
+        {
+            return self.parse_translate(rest);
+        }
+
+        // Describe summarizes a whole command without running it
+        if let (keyword, rest) = Self::split_word(trimmed)
+            && keyword == "describe"
+        {
+            if rest.is_empty() {
+                return Err(
+                    ParseError::InvalidCommand("Usage: describe <command>".to_string()).into(),
+                );
+            }
+            let command = Box::new(self.parse(rest)?);
+            return Ok(Command::Utility(UtilityCommand::Describe(command)));
+        }
+
+        // `sql explain SELECT ...` shows the translation of a SQL statement
+        if let (keyword, rest) = Self::split_word(trimmed)
+            && keyword.eq_ignore_ascii_case("sql")
+        {
+            return Self::parse_sql_explain(rest);
+        }
+
+        // Bookmarks keep the rest of the line verbatim, including pipes and flags
+        if Self::split_word(trimmed).0 == "bookmark" {
+            return shell_commands::ShellCommandParser::parse(trimmed);
+        }
+
+        // Check for a trailing --format=<name> applying to this command only
+        if let Some((base_part, format_name)) = Self::split_trailing_flag(trimmed, "--format") {
+            let base_cmd = self.parse(base_part)?;
+            let format = Self::parse_output_format(format_name)?;
+            return Ok(Command::Pipe(Box::new(base_cmd), PipeCommand::Format(format)));
+        }
+
+        // A trailing --columns name,address.city picks the fields shown and
+        // their order as table columns
+        if let Some((base_part, columns)) = Self::split_trailing_flag(trimmed, "--columns") {
+            let base_cmd = self.parse(base_part)?;
+            let fields = Self::parse_field_list(columns)?;
+            return Ok(Command::Pipe(Box::new(base_cmd), PipeCommand::Pluck(fields)));
+        }
+
         // Check for pipe operator |>
         if let Some(pipe_idx) = trimmed.find("|>") {
             let base_part = trimmed[..pipe_idx].trim();
@@ -123,9 +264,16 @@ impl Parser {
             return Ok(Command::Pipe(Box::new(base_cmd), pipe_cmd));
         }
 
+        // Check for a client-side projection: <command> :: .name, .age
+        if let Some((base_part, fields_part)) = Self::split_projection(trimmed) {
+            let base_cmd = self.parse(base_part)?;
+            let fields = Self::parse_field_list(fields_part)?;
+            return Ok(Command::Pipe(Box::new(base_cmd), PipeCommand::Pluck(fields)));
+        }
+
         // Check if it's a SQL SELECT command
-        if sql_parser::SqlParser::is_sql_command(trimmed) {
-            return sql_parser::SqlParser::parse_to_command(trimmed);
+        if let Some(result) = Self::parse_sql(trimmed) {
+            return result;
         }
 
         // Check if it's a shell command (show, use, help, exit, quit)
@@ -133,58 +281,279 @@ impl Parser {
             return shell_commands::ShellCommandParser::parse(trimmed);
         }
 
+        // Check if it's a global shell function (Object.bsonsize(...))
+        if mongo_operation::GlobalOpsParser::is_global_call(trimmed) {
+            return mongo_operation::GlobalOpsParser::parse(trimmed);
+        }
+
         // Check if it's a database operation (db.collection.operation)
         if trimmed.starts_with("db.") {
             return mongo_operation::DbOperationParser::parse(trimmed);
         }
 
+        // Commands added by plugins, when no built-in command matches
+        let (prefix, rest) = Self::split_word(trimmed);
+        if let Some(extension) = extension::find_extension(prefix) {
+            return Ok(match extension.parse(rest)? {
+                Some(command) => command,
+                None => Command::Utility(UtilityCommand::Plugin {
+                    prefix: prefix.to_lowercase(),
+                    input: rest.to_string(),
+                }),
+            });
+        }
+
         // If nothing matches, it's an invalid command
         Err(ParseError::InvalidCommand(trimmed.to_string()).into())
     }
 
-    /// Parse pipe command (export or explain)
-    fn parse_pipe_command(&self, input: &str) -> Result<PipeCommand> {
-        let parts: Vec<&str> = input.split_whitespace().collect();
+    /// Parse SQL input when SQL support is built in
+    ///
+    /// # Returns
+    /// * `Option<Result<Command>>` - Parsed command, or `None` if not SQL
+    #[cfg(feature = "sql")]
+    fn parse_sql(input: &str) -> Option<Result<Command>> {
+        sql_parser::SqlParser::is_sql_command(input)
+            .then(|| sql_parser::SqlParser::parse_to_command(input))
+    }
 
-        if parts.is_empty() {
-            return Err(ParseError::InvalidCommand("Empty pipe command".to_string()).into());
-        }
+    /// SQL support is not built in; SQL input is reported as an invalid command
+    #[cfg(not(feature = "sql"))]
+    fn parse_sql(_input: &str) -> Option<Result<Command>> {
+        None
+    }
+
+    /// Parse pipe command (export, explain, format, pluck or full)
+    fn parse_pipe_command(&self, input: &str) -> Result<PipeCommand> {
+        let (name, rest) = Self::split_word(input);
 
-        match parts[0] {
+        match name {
+            "" => Err(ParseError::InvalidCommand("Empty pipe command".to_string()).into()),
             "explain" => Ok(PipeCommand::Explain),
+            "format" => Ok(PipeCommand::Format(Self::parse_output_format(rest)?)),
+            "pluck" => Ok(PipeCommand::Pluck(Self::parse_field_list(rest)?)),
+            "full" if rest.is_empty() => Ok(PipeCommand::Full),
             "export" => {
-                if parts.len() < 2 {
+                let (format_name, rest) = Self::split_word(rest);
+                if format_name.is_empty() {
                     return Err(ParseError::InvalidCommand(
-                        "export requires a format (jsonl or csv)".to_string(),
+                        "export requires a format (jsonl, csv, xlsx or html)".to_string(),
                     )
                     .into());
                 }
 
-                let format = match parts[1] {
-                    "jsonl" | "json" => ExportFormat::JsonL,
-                    "csv" => ExportFormat::Csv,
-                    other => {
-                        return Err(ParseError::InvalidCommand(format!(
-                            "Unknown export format: {}. Use jsonl or csv",
-                            other
-                        ))
-                        .into());
-                    }
+                let format = ExportFormat::from_name(format_name).ok_or_else(|| {
+                    ParseError::InvalidCommand(format!(
+                        "Unknown export format: {}. Use jsonl, csv, xlsx or html",
+                        format_name
+                    ))
+                })?;
+
+                // `!command` takes the rest of the line verbatim
+                if rest.starts_with('!') {
+                    return Ok(PipeCommand::Export {
+                        format,
+                        file: Some(rest.to_string()),
+                        compression: None,
+                    });
+                }
+
+                // Trailing `{ ... }` holds export options
+                let (target, options) = match rest.find('{') {
+                    Some(idx) => (&rest[..idx], Some(&rest[idx..])),
+                    None => (rest, None),
                 };
+                let file = target.split_whitespace().next().map(String::from);
 
-                let file = if parts.len() > 2 {
-                    Some(parts[2].to_string())
-                } else {
-                    None
+                let options = match options {
+                    Some(options) => {
+                        let expr = mongo_parser::MongoParser::parse(options)?;
+                        mongo_operation::OptionsParser::parse_export_options(&expr)?
+                    }
+                    None => ExportOptions::default(),
                 };
+                if options.format.is_some() {
+                    return Err(ParseError::InvalidCommand(
+                        "Give the export format after 'export', not in the options".to_string(),
+                    )
+                    .into());
+                }
 
-                Ok(PipeCommand::Export { format, file })
+                Ok(PipeCommand::Export {
+                    format,
+                    file,
+                    compression: options.compression,
+                })
             }
             other => Err(
                 ParseError::InvalidCommand(format!("Unknown pipe command '{}'", other)).into(),
             ),
         }
     }
+
+    /// Parse the arguments of `watch [seconds] <command>`
+    ///
+    /// # Arguments
+    /// * `input` - Input after the `watch` keyword
+    ///
+    /// # Returns
+    /// * `Result<Command>` - Watch command wrapping the parsed command
+    fn parse_watch(&mut self, input: &str) -> Result<Command> {
+        let (first, rest) = Self::split_word(input);
+        let (interval, source) = match first.parse::<u64>() {
+            Ok(0) => {
+                return Err(ParseError::InvalidCommand(
+                    "watch interval must be at least 1 second".to_string(),
+                )
+                .into());
+            }
+            Ok(seconds) => (seconds, rest),
+            Err(_) => (DEFAULT_WATCH_INTERVAL, input),
+        };
+        if source.is_empty() {
+            return Err(
+                ParseError::InvalidCommand("Usage: watch [seconds] <command>".to_string()).into(),
+            );
+        }
+
+        let command = self.parse(source)?;
+        if matches!(
+            command,
+            Command::Watch { .. }
+                | Command::Exit
+                | Command::Load(_)
+                | Command::AiQuery(_)
+                | Command::Help(_)
+                | Command::Retry
+                | Command::Record(_)
+                | Command::Report(_)
+        ) {
+            return Err(ParseError::InvalidCommand(format!("Cannot watch '{}'", source)).into());
+        }
+
+        Ok(Command::Watch {
+            interval,
+            source: source.to_string(),
+            command: Box::new(command),
+        })
+    }
+
+    /// Parse the arguments of `translate <language> <command>`
+    ///
+    /// # Arguments
+    /// * `input` - Input after the `translate` keyword
+    ///
+    /// # Returns
+    /// * `Result<Command>` - Translate command wrapping the parsed command
+    fn parse_translate(&mut self, input: &str) -> Result<Command> {
+        let (name, source) = Self::split_word(input);
+        if source.is_empty() {
+            return Err(ParseError::InvalidCommand(
+                "Usage: translate <rust|node|python|go|java> <command>".to_string(),
+            )
+            .into());
+        }
+        let language = DriverLanguage::from_name(name).ok_or_else(|| {
+            ParseError::InvalidCommand(format!(
+                "Unknown language '{}'; use rust, node, python, go or java",
+                name
+            ))
+        })?;
+
+        Ok(Command::Utility(UtilityCommand::Translate {
+            language,
+            command: Box::new(self.parse(source)?),
+        }))
+    }
+
+    /// Parse the arguments of `sql explain <statement>`
+    ///
+    /// # Arguments
+    /// * `input` - Input after the `sql` keyword
+    ///
+    /// # Returns
+    /// * `Result<Command>` - SQL explain command wrapping the parsed statement
+    fn parse_sql_explain(input: &str) -> Result<Command> {
+        let usage = || ParseError::InvalidCommand("Usage: sql explain SELECT ...".to_string());
+        let (action, statement) = Self::split_word(input);
+        if !action.eq_ignore_ascii_case("explain") || statement.is_empty() {
+            return Err(usage().into());
+        }
+        let command = Self::parse_sql(statement).ok_or_else(usage)??;
+        Ok(Command::Utility(UtilityCommand::SqlExplain(Box::new(command))))
+    }
+
+    /// Split off the first whitespace separated word
+    ///
+    /// # Returns
+    /// * `(&str, &str)` - The word and the remaining input, both trimmed
+    fn split_word(input: &str) -> (&str, &str) {
+        let input = input.trim();
+        match input.find(char::is_whitespace) {
+            Some(idx) => (&input[..idx], input[idx..].trim_start()),
+            None => (input, ""),
+        }
+    }
+
+    /// Split a trailing `--<flag>=<value>` or `--<flag> <value>` off a command
+    ///
+    /// # Arguments
+    /// * `input` - Command text
+    /// * `flag` - Flag including the dashes, e.g. `--format`
+    ///
+    /// # Returns
+    /// * `Option<(&str, &str)>` - Command text and flag value, `None` without the flag
+    fn split_trailing_flag<'a>(input: &'a str, flag: &str) -> Option<(&'a str, &'a str)> {
+        let (rest, last) = input.rsplit_once(char::is_whitespace)?;
+        if let Some(value) = last
+            .strip_prefix(flag)
+            .and_then(|value| value.strip_prefix('='))
+        {
+            return Some((rest.trim_end(), value));
+        }
+
+        let (rest, name) = rest.trim_end().rsplit_once(char::is_whitespace)?;
+        (name == flag).then(|| (rest.trim_end(), last))
+    }
+
+    /// Split `<command> :: .field, ...` at the last `::` followed by a field
+    ///
+    /// # Returns
+    /// * `Option<(&str, &str)>` - Command text and field list, `None` without a projection
+    fn split_projection(input: &str) -> Option<(&str, &str)> {
+        let idx = input.rfind("::")?;
+        let (base, fields) = (input[..idx].trim(), input[idx + 2..].trim());
+        (!base.is_empty() && fields.starts_with('.')).then_some((base, fields))
+    }
+
+    /// Parse a comma separated list of field paths, e.g. `.name, .profile.city`
+    pub(crate) fn parse_field_list(input: &str) -> Result<Vec<String>> {
+        let fields: Vec<String> = input
+            .split(',')
+            .map(|field| field.trim().trim_start_matches('.').to_string())
+            .collect();
+
+        if fields.iter().any(|field| field.is_empty()) {
+            return Err(ParseError::InvalidCommand(format!(
+                "Invalid field list: '{}'. Expected fields such as .name, .profile.city",
+                input.trim()
+            ))
+            .into());
+        }
+        Ok(fields)
+    }
+
+    /// Parse an output format name given to `--format` or `|> format`
+    pub(crate) fn parse_output_format(name: &str) -> Result<OutputFormat> {
+        OutputFormat::from_name(name.trim()).ok_or_else(|| {
+            ParseError::InvalidCommand(format!(
+                "Unknown output format: '{}'. Use one of: {}",
+                name.trim(),
+                OutputFormat::names().join(", ")
+            ))
+            .into()
+        })
+    }
 }
 
 impl Default for Parser {
@@ -193,6 +562,33 @@ impl Default for Parser {
     }
 }
 
+/// Run every parser over arbitrary input, discarding the result
+///
+/// Entry point for the cargo-fuzz targets in `fuzz/`. Input that is not
+/// valid UTF-8 is ignored; anything else must produce a command or an error,
+/// never a panic.
+///
+/// # Arguments
+///
+/// * `input` - Raw bytes from the fuzzer
+// Used by the fuzz targets and property tests, not by the binary
+#[allow(dead_code)]
+pub fn fuzz_entry(input: &[u8]) {
+    let Ok(text) = std::str::from_utf8(input) else {
+        return;
+    };
+
+    let _ = Parser::new().parse(text);
+    let _ = mongo_operation::DbOperationParser::parse(text);
+    let _ = MongoLexer::tokenize(text);
+
+    #[cfg(feature = "sql")]
+    {
+        let _ = sql_parser::SqlParser::parse_to_command(text);
+        let _ = SqlLexer::tokenize(text);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -203,6 +599,41 @@ mod tests {
         // Parser created successfully
     }
 
+    #[test]
+    fn test_parse_prompt_answers() {
+        let mut parser = Parser::new();
+        let input = "db.users.insertOne({ name: prompt('Name'), pwd: passwordPrompt() })";
+        assert_eq!(prompt_requests(input).len(), 2);
+        parser.set_prompt_answers(vec!["ada".to_string(), "s3cret".to_string()]);
+        match parser.parse(input).unwrap() {
+            Command::Query(QueryCommand::InsertOne { document, .. }) => {
+                assert_eq!(document.get_str("name").unwrap(), "ada");
+                assert_eq!(document.get_str("pwd").unwrap(), "s3cret");
+            }
+            other => panic!("Expected InsertOne, got {:?}", other),
+        }
+
+        // Answers apply to one command only
+        assert!(parser.parse(input).is_err());
+    }
+
+    #[test]
+    fn test_parse_env_needs_environment() {
+        let mut parser = Parser::new();
+        let input = "db.deploys.find({ stage: env('STAGE') })";
+        assert!(parser.parse(input).is_err());
+        assert!(parser.parse("print(env('STAGE'))").is_err());
+
+        parser.set_environment(HashMap::from([("STAGE".to_string(), "prod".to_string())]));
+        match parser.parse(input).unwrap() {
+            Command::Query(QueryCommand::Find { filter, .. }) => {
+                assert_eq!(filter.get_str("stage").unwrap(), "prod");
+            }
+            other => panic!("Expected Find, got {:?}", other),
+        }
+        assert!(parser.parse("print(env('STAGE'))").is_ok());
+    }
+
     #[test]
     fn test_parse_exit() {
         let mut parser = Parser::new();
@@ -213,6 +644,145 @@ mod tests {
         assert!(matches!(cmd, Command::Exit));
     }
 
+    #[test]
+    fn test_parse_watch() {
+        let mut parser = Parser::new();
+        let cmd = parser
+            .parse("watch 5 db.orders.countDocuments({status: 'pending'})")
+            .unwrap();
+        match cmd {
+            Command::Watch {
+                interval,
+                source,
+                command,
+            } => {
+                assert_eq!(interval, 5);
+                assert_eq!(source, "db.orders.countDocuments({status: 'pending'})");
+                assert!(matches!(*command, Command::Query(_)));
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+
+        let cmd = parser.parse("watch db.orders.find() |> explain").unwrap();
+        assert!(matches!(
+            cmd,
+            Command::Watch { interval: DEFAULT_WATCH_INTERVAL, ref command, .. }
+                if matches!(**command, Command::Pipe(..))
+        ));
+
+        assert!(parser.parse("watch 0 db.orders.find()").is_err());
+        assert!(parser.parse("watch 5").is_err());
+        assert!(parser.parse("watch 5 watch 1 db.orders.find()").is_err());
+        assert!(parser.parse("watch 5 exit").is_err());
+    }
+
+    #[test]
+    fn test_runs_until_interrupted() {
+        let mut parser = Parser::new();
+        for input in [
+            "watch 5 db.orders.countDocuments()",
+            "oplog tail",
+            "currentOp --watch",
+            "rs.lag --watch 2",
+            "top --watch 1",
+            "watch db.orders.find() |> explain",
+        ] {
+            let cmd = parser.parse(input).unwrap();
+            assert!(cmd.runs_until_interrupted(), "{}", input);
+        }
+        for input in ["db.orders.find()", "currentOp", "rs.lag", "top"] {
+            let cmd = parser.parse(input).unwrap();
+            assert!(!cmd.runs_until_interrupted(), "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_parse_translate() {
+        let mut parser = Parser::new();
+        let cmd = parser
+            .parse("translate go db.users.find({ age: { $gt: 18 } }).limit(5)")
+            .unwrap();
+        match cmd {
+            Command::Utility(UtilityCommand::Translate { language, command }) => {
+                assert_eq!(language, DriverLanguage::Go);
+                assert!(matches!(*command, Command::Query(_)));
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+
+        assert!(parser.parse("translate cobol db.users.find()").is_err());
+        assert!(parser.parse("translate rust").is_err());
+    }
+
+    struct GraphExtension;
+
+    impl CommandExtension for GraphExtension {
+        fn parse(&self, input: &str) -> Result<Option<Command>> {
+            match input.strip_prefix("nodes ") {
+                Some(label) => Ok(Some(Command::Query(QueryCommand::CountDocuments {
+                    collection: "nodes".to_string(),
+                    filter: bson::doc! { "label": label },
+                }))),
+                None => Ok(None),
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_extension() {
+        register_extension("graph-test", std::sync::Arc::new(GraphExtension)).unwrap();
+        assert!(register_extension("graph-test", std::sync::Arc::new(GraphExtension)).is_err());
+        assert!(register_extension("two words", std::sync::Arc::new(GraphExtension)).is_err());
+
+        let mut parser = Parser::new();
+        let cmd = parser.parse("graph-test nodes person").unwrap();
+        assert!(matches!(
+            cmd,
+            Command::Query(QueryCommand::CountDocuments { ref collection, .. }) if collection == "nodes"
+        ));
+
+        let cmd = parser.parse("GRAPH-TEST shortest a b").unwrap();
+        assert_eq!(
+            cmd,
+            Command::Utility(UtilityCommand::Plugin {
+                prefix: "graph-test".to_string(),
+                input: "shortest a b".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_describe() {
+        let mut parser = Parser::new();
+        let cmd = parser
+            .parse("describe db.orders.deleteMany({ status: 'x' })")
+            .unwrap();
+        assert!(matches!(
+            cmd,
+            Command::Utility(UtilityCommand::Describe(ref command))
+                if matches!(**command, Command::Query(QueryCommand::DeleteMany { .. }))
+        ));
+        assert!(parser.parse("describe").is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "sql")]
+    fn test_parse_sql_explain() {
+        let mut parser = Parser::new();
+        let cmd = parser
+            .parse("sql explain SELECT name FROM users WHERE age > 18")
+            .unwrap();
+        match cmd {
+            Command::Utility(UtilityCommand::SqlExplain(command)) => {
+                assert!(matches!(*command, Command::Query(QueryCommand::Find { .. })));
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+
+        assert!(parser.parse("sql explain db.users.find()").is_err());
+        assert!(parser.parse("sql SELECT * FROM users").is_err());
+    }
+
     #[test]
     fn test_parse_help() {
         let mut parser = Parser::new();
@@ -223,6 +793,240 @@ mod tests {
         assert!(matches!(cmd, Command::Help(Some(_))));
     }
 
+    #[test]
+    fn test_parse_pipe_export_xlsx() {
+        let parser = Parser::new();
+        let pipe = parser.parse_pipe_command("export xlsx users.xlsx").unwrap();
+        assert_eq!(
+            pipe,
+            PipeCommand::Export {
+                format: ExportFormat::Xlsx,
+                file: Some("users.xlsx".to_string()),
+                compression: None,
+            }
+        );
+        assert!(parser.parse_pipe_command("export xls").is_err());
+    }
+
+    #[test]
+    fn test_parse_pipe_export_compression() {
+        let parser = Parser::new();
+        let pipe = parser
+            .parse_pipe_command("export csv users.csv { compression: 'zstd' }")
+            .unwrap();
+        assert_eq!(
+            pipe,
+            PipeCommand::Export {
+                format: ExportFormat::Csv,
+                file: Some("users.csv".to_string()),
+                compression: Some(ExportCompression::Zstd),
+            }
+        );
+
+        let pipe = parser
+            .parse_pipe_command("export jsonl {compression: \"gzip\"}")
+            .unwrap();
+        assert!(matches!(
+            pipe,
+            PipeCommand::Export {
+                file: None,
+                compression: Some(ExportCompression::Gzip),
+                ..
+            }
+        ));
+
+        assert!(
+            parser
+                .parse_pipe_command("export csv a.csv { compression: 'lz4' }")
+                .is_err()
+        );
+        assert!(
+            parser
+                .parse_pipe_command("export csv a.csv { level: 3 }")
+                .is_err()
+        );
+        assert!(
+            parser
+                .parse_pipe_command("export csv a.csv { format: 'jsonl' }")
+                .is_err()
+        );
+        assert_eq!(
+            ExportCompression::from_path("users.jsonl.gz"),
+            Some(ExportCompression::Gzip)
+        );
+        assert_eq!(ExportCompression::from_path("users.csv"), None);
+    }
+
+    #[test]
+    fn test_fuzz_entry_rejects_without_panicking() {
+        for input in [
+            &b""[..],
+            b"\xff\xfe",
+            b"|>",
+            b"db.",
+            b"db.users.find({ a: 'unterminated",
+            b"db.users.find().limit(-1e309)",
+            b"SELECT * FROM",
+            b"SELECT \xc3\xa9 FROM users WHERE",
+            b"use \xe6\x97\xa5\xe6\x9c\xac |> export",
+        ] {
+            fuzz_entry(input);
+        }
+    }
+
+    #[test]
+    fn test_parse_pipe_export_targets() {
+        let parser = Parser::new();
+        let pipe = parser.parse_pipe_command("export jsonl -").unwrap();
+        assert!(matches!(
+            pipe,
+            PipeCommand::Export { file: Some(ref f), .. } if f == "-"
+        ));
+
+        let pipe = parser
+            .parse_pipe_command("export jsonl !jq -c '{name}'")
+            .unwrap();
+        assert_eq!(
+            pipe,
+            PipeCommand::Export {
+                format: ExportFormat::JsonL,
+                file: Some("!jq -c '{name}'".to_string()),
+                compression: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_columns_flag() {
+        let mut parser = Parser::new();
+
+        let cmd = parser
+            .parse("db.users.find() --columns name,address.city --format table")
+            .unwrap();
+        assert_eq!(cmd.format_override(), Some(OutputFormat::Table));
+        assert_eq!(
+            cmd.pluck_fields(),
+            Some(&["name".to_string(), "address.city".to_string()][..])
+        );
+
+        let cmd = parser
+            .parse("db.users.find() --format=table --columns=age")
+            .unwrap();
+        assert_eq!(cmd.format_override(), Some(OutputFormat::Table));
+        assert_eq!(cmd.pluck_fields(), Some(&["age".to_string()][..]));
+
+        assert!(parser.parse("db.users.find() --columns name,,age").is_err());
+    }
+
+    #[test]
+    fn test_parse_format_override() {
+        let mut parser = Parser::new();
+
+        let cmd = parser.parse("db.users.find() --format=json").unwrap();
+        assert_eq!(cmd.format_override(), Some(OutputFormat::Json));
+        assert!(matches!(
+            cmd,
+            Command::Pipe(ref base, _) if matches!(**base, Command::Query(QueryCommand::Find { .. }))
+        ));
+
+        let cmd = parser.parse("show dbs --format table").unwrap();
+        assert_eq!(cmd.format_override(), Some(OutputFormat::Table));
+
+        let cmd = parser.parse("db.users.find() |> format json-pretty").unwrap();
+        assert_eq!(cmd.format_override(), Some(OutputFormat::JsonPretty));
+
+        assert!(parser.parse("db.users.find() --format=yaml").is_err());
+        assert!(parser.parse("db.users.find() --formats=json").is_err());
+        assert!(parser.parse("db.users.find()").unwrap().format_override().is_none());
+        assert!(matches!(
+            parser.parse("format json").unwrap(),
+            Command::Config(ConfigCommand::SetFormat(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_bookmark_keeps_pipes() {
+        let mut parser = Parser::new();
+        assert_eq!(
+            parser
+                .parse("bookmark save names db.users.find() :: .name --format=table")
+                .unwrap(),
+            Command::Config(ConfigCommand::SaveBookmark {
+                name: "names".to_string(),
+                query: "db.users.find() :: .name --format=table".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_projection() {
+        let mut parser = Parser::new();
+
+        let cmd = parser.parse("db.users.find() :: .name, .profile.city").unwrap();
+        assert_eq!(
+            cmd.pluck_fields(),
+            Some(&["name".to_string(), "profile.city".to_string()][..])
+        );
+        assert!(matches!(
+            cmd,
+            Command::Pipe(ref base, _) if matches!(**base, Command::Query(QueryCommand::Find { .. }))
+        ));
+
+        let cmd = parser.parse("db.users.find() |> pluck name, age").unwrap();
+        assert_eq!(cmd.pluck_fields().map(<[String]>::len), Some(2));
+
+        let cmd = parser.parse("db.users.find() :: .name --format=table").unwrap();
+        assert_eq!(cmd.format_override(), Some(OutputFormat::Table));
+        assert_eq!(cmd.pluck_fields(), Some(&["name".to_string()][..]));
+
+        assert!(parser.parse("db.users.find() :: .name,").is_err());
+        assert!(parser.parse("db.users.find() |> pluck").is_err());
+        assert!(
+            parser
+                .parse("db.hosts.find({ addr: '::1' })")
+                .unwrap()
+                .pluck_fields()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_parse_last_result_reference() {
+        let mut parser = Parser::new();
+        assert!(parser.parse("db.orders.find({ userId: _[0]._id })").is_err());
+
+        parser.set_last_result(Some(Bson::Array(vec![
+            Bson::Document(bson::doc! { "_id": 1, "profile": { "city": "Oslo" } }),
+            Bson::Document(bson::doc! { "_id": 2 }),
+        ])));
+
+        let cmd = parser
+            .parse("db.orders.find({ userId: { $in: _.map(d => d._id) }, n: _.length })")
+            .unwrap();
+        match cmd {
+            Command::Query(QueryCommand::Find { filter, .. }) => {
+                assert_eq!(
+                    filter,
+                    bson::doc! { "userId": { "$in": [1, 2] }, "n": 2 }
+                );
+            }
+            other => panic!("Expected find, got {:?}", other),
+        }
+
+        let cmd = parser
+            .parse("db.users.find({ city: _[0].profile.city, other: _[1]['_id'] })")
+            .unwrap();
+        match cmd {
+            Command::Query(QueryCommand::Find { filter, .. }) => {
+                assert_eq!(filter, bson::doc! { "city": "Oslo", "other": 2 });
+            }
+            other => panic!("Expected find, got {:?}", other),
+        }
+
+        assert!(parser.parse("db.users.find({ a: _.filter(d => d.x) })").is_err());
+        assert!(parser.parse("db.users.find({ a: _.map(d => d.x + 1) })").is_err());
+    }
+
     #[test]
     fn test_parse_show_databases() {
         let mut parser = Parser::new();
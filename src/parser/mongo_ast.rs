@@ -38,6 +38,10 @@ pub enum Expr {
     Binary(Box<BinaryExpr>),
     /// Regular expression literal: /pattern/flags
     Regex(String, String),
+    /// Template literal: `text ${expr} text`
+    Template(Vec<TemplatePart>),
+    /// Function literal kept as verbatim source: function(a) { ... } or (a) => ...
+    Function(String),
 }
 
 /// Object expression: { key: value, ... }
@@ -179,6 +183,15 @@ impl UnaryExpr {
     }
 }
 
+/// Piece of a template literal
+#[derive(Debug, Clone, PartialEq)]
+pub enum TemplatePart {
+    /// Literal text with escapes resolved
+    Text(String),
+    /// Substitution: ${expr}
+    Expr(Expr),
+}
+
 /// Unary operators
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum UnaryOperator {
@@ -219,6 +232,8 @@ pub enum BinaryOperator {
     Subtract,
     /// Multiplication: a * b
     Multiply,
+    /// Division: a / b
+    Divide,
     /// Modulo: a % b
     Modulo,
 }
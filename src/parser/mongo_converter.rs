@@ -4,11 +4,26 @@
 //! It replaces the previous oxc-based implementation with a lightweight converter
 //! that works directly with our purpose-built AST.
 
-use mongodb::bson::{Bson, Decimal128, Document};
+use bson::{Binary, Bson, Decimal128, Document, Uuid, spec::BinarySubtype};
 use std::str::FromStr;
 
 use super::mongo_ast::*;
+use super::{host_input, last_result};
 use crate::error::{ParseError, Result};
+#[cfg(feature = "native")]
+use crate::repl::script;
+
+/// Without the native runtime there are no script arguments
+#[cfg(not(feature = "native"))]
+mod script {
+    pub fn args() -> Vec<String> {
+        Vec::new()
+    }
+
+    pub fn argv() -> Vec<String> {
+        vec!["mongosh".to_string()]
+    }
+}
 
 /// Converter for MongoDB expressions to BSON
 pub struct ExpressionConverter;
@@ -41,7 +56,22 @@ impl ExpressionConverter {
             // Unary expression: -5, !true
             Expr::Unary(unary) => Self::unary_to_bson(unary),
 
-            Expr::Binary(_) => Ok(Self::number_to_bson(Self::expr_to_number(expr)?)),
+            // Binary expression: string concatenation or constant arithmetic
+            Expr::Binary(binary) => Self::binary_to_bson(binary, expr),
+
+            // Template literal: `text ${expr}`
+            Expr::Template(parts) => {
+                let mut value = String::new();
+                for part in parts {
+                    match part {
+                        TemplatePart::Text(text) => value.push_str(text),
+                        TemplatePart::Expr(expr) => {
+                            value.push_str(&Self::bson_to_js_string(&Self::expr_to_bson(expr)?)?)
+                        }
+                    }
+                }
+                Ok(Bson::String(value))
+            }
 
             // New expression: new Date(), new ObjectId()
             Expr::New(new_expr) => Self::new_expression_to_bson(new_expr),
@@ -50,18 +80,14 @@ impl ExpressionConverter {
             Expr::Call(call) => Self::call_expression_to_bson(call),
 
             // Regular expression literal: /pattern/flags
-            Expr::Regex(pattern, flags) => {
-                Ok(Bson::RegularExpression(mongodb::bson::Regex {
-                    pattern: pattern.clone(),
-                    options: flags.clone(),
-                }))
-            }
+            Expr::Regex(pattern, flags) => Self::regex_to_bson(pattern, flags),
 
-            // Member expression (not supported in BSON literals)
-            Expr::Member(_) => Err(ParseError::InvalidQuery(
-                "Member expressions not supported in BSON literals".to_string(),
-            )
-            .into()),
+            // Function literal: passed through as JavaScript code for the
+            // server to evaluate ($function body, $accumulator init/accumulate/...)
+            Expr::Function(source) => Ok(Bson::JavaScriptCode(source.clone())),
+
+            // Member expression: script values (args[0], process.env.HOME) and `_[0].name`
+            Expr::Member(member) => Self::member_to_bson(member),
         }
     }
 
@@ -99,10 +125,94 @@ impl ExpressionConverter {
             "false" => Ok(Bson::Boolean(false)),
             "Infinity" => Ok(Bson::Double(f64::INFINITY)),
             "NaN" => Ok(Bson::Double(f64::NAN)),
+            "args" => Ok(Self::strings_to_bson(script::args())),
+            "_" => last_result::value(),
             _ => Err(ParseError::InvalidQuery(format!("Unknown identifier: {}", name)).into()),
         }
     }
 
+    /// Convert a member expression on a script value
+    ///
+    /// Supports `args[i]`, `args.length`, `process.argv[i]`,
+    /// `process.env.NAME` and properties of the previous result (`_[0].name`);
+    /// any other member access is rejected.
+    fn member_to_bson(member: &MemberExpr) -> Result<Bson> {
+        if last_result::is_reference(&member.object) {
+            let object = Self::expr_to_bson(&member.object)?;
+            return last_result::property(object, &member.property);
+        }
+
+        if Self::is_process_member(member, "argv") {
+            return Ok(Self::strings_to_bson(script::argv()));
+        }
+
+        let items = match member.object.as_ref() {
+            Expr::Ident(name) if name == "args" => script::args(),
+            Expr::Member(inner) if Self::is_process_member(inner, "argv") => script::argv(),
+            Expr::Member(inner) if Self::is_process_member(inner, "env") => {
+                let name = match &member.property {
+                    MemberProperty::Ident(name) => name.clone(),
+                    MemberProperty::Computed(expr) => Self::expr_to_bson(expr)?
+                        .as_str()
+                        .map(String::from)
+                        .ok_or_else(|| {
+                            ParseError::InvalidQuery(
+                                "process.env[...] expects a variable name".to_string(),
+                            )
+                        })?,
+                };
+                return Self::env_to_bson(&name, Bson::Null);
+            }
+            _ => {
+                return Err(ParseError::InvalidQuery(
+                    "Member expressions not supported in BSON literals".to_string(),
+                )
+                .into());
+            }
+        };
+
+        match &member.property {
+            MemberProperty::Ident(prop) if prop == "length" => Ok(Bson::Int32(items.len() as i32)),
+            MemberProperty::Computed(index) => {
+                let index = Self::expr_to_number(index)?;
+                if index < 0.0 || index.fract() != 0.0 {
+                    return Ok(Bson::Null);
+                }
+                Ok(items
+                    .into_iter()
+                    .nth(index as usize)
+                    .map(Bson::String)
+                    .unwrap_or(Bson::Null))
+            }
+            MemberProperty::Ident(prop) => Err(ParseError::InvalidQuery(format!(
+                "Unsupported property on script arguments: {}",
+                prop
+            ))
+            .into()),
+        }
+    }
+
+    /// Detect `process.<name>`
+    fn is_process_member(member: &MemberExpr, name: &str) -> bool {
+        matches!(
+            (member.object.as_ref(), &member.property),
+            (Expr::Ident(obj), MemberProperty::Ident(prop)) if obj == "process" && prop == name
+        )
+    }
+
+    /// Convert a list of strings to a BSON array
+    fn strings_to_bson(values: Vec<String>) -> Bson {
+        Bson::Array(values.into_iter().map(Bson::String).collect())
+    }
+
+    /// Read an environment variable supplied by the caller, or the fallback
+    /// when it is unset
+    fn env_to_bson(name: &str, fallback: Bson) -> Result<Bson> {
+        Ok(host_input::env_var(name)?
+            .map(Bson::String)
+            .unwrap_or(fallback))
+    }
+
     /// Convert unary expression to BSON (e.g., -5, +3)
     fn unary_to_bson(unary: &UnaryExpr) -> Result<Bson> {
         match unary.operator {
@@ -140,8 +250,62 @@ impl ExpressionConverter {
         }
     }
 
+    /// Evaluate a binary expression
+    ///
+    /// `+` with a string on either side concatenates like JavaScript
+    /// (`"app-" + 1` is `"app-1"`); everything else is constant arithmetic.
+    fn binary_to_bson(binary: &BinaryExpr, expr: &Expr) -> Result<Bson> {
+        if binary.operator == BinaryOperator::Add && Self::is_string_expr(expr) {
+            let left = Self::expr_to_bson(&binary.left)?;
+            let right = Self::expr_to_bson(&binary.right)?;
+            return Ok(Bson::String(
+                Self::bson_to_js_string(&left)? + &Self::bson_to_js_string(&right)?,
+            ));
+        }
+        Ok(Self::number_to_bson(Self::expr_to_number(expr)?))
+    }
+
+    /// Check whether an expression evaluates to a string
+    fn is_string_expr(expr: &Expr) -> bool {
+        match expr {
+            Expr::String(_) | Expr::Template(_) => true,
+            Expr::Binary(b) => {
+                b.operator == BinaryOperator::Add
+                    && (Self::is_string_expr(&b.left) || Self::is_string_expr(&b.right))
+            }
+            _ => false,
+        }
+    }
+
+    /// Format a value the way JavaScript converts it to a string
+    fn bson_to_js_string(value: &Bson) -> Result<String> {
+        match value {
+            Bson::String(s) => Ok(s.clone()),
+            Bson::Int32(n) => Ok(n.to_string()),
+            Bson::Int64(n) => Ok(n.to_string()),
+            Bson::Double(n) if n.is_nan() => Ok("NaN".to_string()),
+            Bson::Double(n) if n.is_infinite() => {
+                Ok(if *n > 0.0 { "Infinity" } else { "-Infinity" }.to_string())
+            }
+            Bson::Double(n) => Ok(n.to_string()),
+            Bson::Boolean(b) => Ok(b.to_string()),
+            Bson::Null => Ok("null".to_string()),
+            Bson::ObjectId(oid) => Ok(oid.to_hex()),
+            Bson::DateTime(dt) => dt
+                .try_to_rfc3339_string()
+                .map_err(|e| ParseError::InvalidQuery(format!("Invalid date: {}", e)).into()),
+            other => Err(ParseError::InvalidQuery(format!(
+                "Cannot convert {:?} to a string",
+                other.element_type()
+            ))
+            .into()),
+        }
+    }
+
     /// Recursively evaluate an expression as an f64 numeric value.
-    /// Supports number literals, unary +/-, binary arithmetic, and `Date.now()`.
+    /// Supports number literals, unary +/-, binary arithmetic, `Date.now()`,
+    /// dates (as milliseconds since epoch, like JavaScript's `valueOf`) and
+    /// numeric constructors such as `NumberLong(...)`.
     fn expr_to_number(expr: &Expr) -> Result<f64> {
         match expr {
             Expr::Number(n) => Ok(*n),
@@ -160,20 +324,30 @@ impl ExpressionConverter {
                     BinaryOperator::Add => l + r,
                     BinaryOperator::Subtract => l - r,
                     BinaryOperator::Multiply => l * r,
+                    BinaryOperator::Divide => l / r,
                     BinaryOperator::Modulo => l % r,
                 })
             }
-            Expr::Call(call) => {
-                if Self::is_date_now(call) {
-                    Ok(mongodb::bson::DateTime::now().timestamp_millis() as f64)
-                } else {
-                    Err(ParseError::InvalidQuery(
-                        "Only numeric expressions and Date.now() are allowed in arithmetic"
-                            .to_string(),
-                    )
-                    .into())
-                }
+            Expr::Call(call) if Self::is_date_now(call) => {
+                Ok(bson::DateTime::now().timestamp_millis() as f64)
+            }
+            // date.getTime() / date.valueOf()
+            Expr::Call(call) if call.arguments.is_empty() && Self::is_time_getter(call) => {
+                let Expr::Member(member) = call.callee.as_ref() else {
+                    unreachable!("is_time_getter checks for a member callee");
+                };
+                Self::expr_to_number(&member.object)
             }
+            Expr::Call(_) | Expr::New(_) => match Self::expr_to_bson(expr)? {
+                Bson::DateTime(dt) => Ok(dt.timestamp_millis() as f64),
+                Bson::Int32(n) => Ok(n as f64),
+                Bson::Int64(n) => Ok(n as f64),
+                Bson::Double(n) => Ok(n),
+                _ => Err(ParseError::InvalidQuery(
+                    "Only numbers, dates and Date.now() are allowed in arithmetic".to_string(),
+                )
+                .into()),
+            },
             _ => Err(ParseError::InvalidQuery(
                 "Arithmetic operands must be numeric literals".to_string(),
             )
@@ -190,6 +364,15 @@ impl ExpressionConverter {
         }
     }
 
+    /// Detect a `.getTime()` or `.valueOf()` member call.
+    fn is_time_getter(call: &CallExpr) -> bool {
+        matches!(
+            call.callee.as_ref(),
+            Expr::Member(member)
+                if matches!(&member.property, MemberProperty::Ident(p) if p == "getTime" || p == "valueOf")
+        )
+    }
+
     /// Detect `Date.now()` member call.
     fn is_date_now(call: &CallExpr) -> bool {
         if !call.arguments.is_empty() {
@@ -221,7 +404,7 @@ impl ExpressionConverter {
                 // new Date() or new Date(timestamp) or new Date(dateString)
                 if new_expr.arguments.is_empty() {
                     // Current time
-                    Ok(Bson::DateTime(mongodb::bson::DateTime::now()))
+                    Ok(Bson::DateTime(bson::DateTime::now()))
                 } else if let Some(arg) = new_expr.arguments.first() {
                     Self::parse_date_argument(arg)
                 } else {
@@ -231,13 +414,20 @@ impl ExpressionConverter {
             "ObjectId" => {
                 // new ObjectId() or new ObjectId("hexstring")
                 if new_expr.arguments.is_empty() {
-                    Ok(Bson::ObjectId(mongodb::bson::oid::ObjectId::new()))
+                    Ok(Bson::ObjectId(bson::oid::ObjectId::new()))
                 } else if let Some(arg) = new_expr.arguments.first() {
                     Self::parse_objectid_argument(arg)
                 } else {
                     Err(ParseError::InvalidQuery("Invalid ObjectId constructor".to_string()).into())
                 }
             }
+            // new NumberLong("..."), new UUID("..."), ... behave like the calls
+            "ISODate" | "NumberInt" | "NumberLong" | "Long" | "NumberDecimal" | "Decimal128"
+            | "UUID" | "BinData" | "Timestamp" => Self::call_expression_to_bson(&CallExpr {
+                callee: new_expr.callee.clone(),
+                arguments: new_expr.arguments.clone(),
+                span: new_expr.span.clone(),
+            }),
             _ => Err(
                 ParseError::InvalidQuery(format!("Unsupported constructor: {}", ctor_name)).into(),
             ),
@@ -247,7 +437,11 @@ impl ExpressionConverter {
     /// Convert call expression: ObjectId("..."), ISODate("...")
     fn call_expression_to_bson(call: &CallExpr) -> Result<Bson> {
         if Self::is_date_now(call) {
-            return Ok(Bson::Int64(mongodb::bson::DateTime::now().timestamp_millis()));
+            return Ok(Bson::Int64(bson::DateTime::now().timestamp_millis()));
+        }
+
+        if last_result::is_reference(&call.callee) {
+            return last_result::call(call);
         }
 
         // Get function name
@@ -263,7 +457,7 @@ impl ExpressionConverter {
         match fn_name {
             "ObjectId" => {
                 if call.arguments.is_empty() {
-                    Ok(Bson::ObjectId(mongodb::bson::oid::ObjectId::new()))
+                    Ok(Bson::ObjectId(bson::oid::ObjectId::new()))
                 } else if let Some(arg) = call.arguments.first() {
                     Self::parse_objectid_argument(arg)
                 } else {
@@ -274,7 +468,7 @@ impl ExpressionConverter {
                 if let Some(arg) = call.arguments.first() {
                     Self::parse_date_argument(arg)
                 } else {
-                    Ok(Bson::DateTime(mongodb::bson::DateTime::now()))
+                    Ok(Bson::DateTime(bson::DateTime::now()))
                 }
             }
             "NumberInt" => {
@@ -301,26 +495,163 @@ impl ExpressionConverter {
                     )
                 }
             }
+            "UUID" => match call.arguments.first() {
+                Some(arg) => Self::parse_uuid_argument(arg),
+                None => Ok(Bson::Binary(Binary::from_uuid(Uuid::new()))),
+            },
+            "BinData" => Self::parse_bindata_arguments(&call.arguments),
+            "Timestamp" => Self::parse_timestamp_arguments(&call.arguments),
+            "prompt" | "passwordPrompt" => Self::read_prompt_value(fn_name, &call.arguments),
+            "env" => match call.arguments.as_slice() {
+                [Expr::String(name)] => Self::env_to_bson(name, Bson::Null),
+                [Expr::String(name), fallback] => {
+                    Self::env_to_bson(name, Self::expr_to_bson(fallback)?)
+                }
+                _ => Err(ParseError::InvalidQuery(
+                    "env() expects a variable name and an optional default".to_string(),
+                )
+                .into()),
+            },
             _ => Err(ParseError::InvalidQuery(format!("Unsupported function: {}", fn_name)).into()),
         }
     }
 
+    /// Take the answer the shell collected for prompt("label") or passwordPrompt("label")
+    ///
+    /// The terminal is read before parsing, see [`host_input`]; passwords
+    /// typed for `{ pwd: passwordPrompt() }` never appear in the command or
+    /// history.
+    fn read_prompt_value(fn_name: &str, args: &[Expr]) -> Result<Bson> {
+        match args {
+            [] | [Expr::String(_)] => {}
+            _ => {
+                return Err(ParseError::InvalidQuery(format!(
+                    "{}() label must be a string",
+                    fn_name
+                ))
+                .into());
+            }
+        }
+
+        host_input::next_answer(fn_name).map(Bson::String)
+    }
+
     /// Parse Date argument
     fn parse_date_argument(expr: &Expr) -> Result<Bson> {
         if let Expr::String(s) = expr {
-            let datetime = mongodb::bson::DateTime::parse_rfc3339_str(s)
-                .map_err(|e| ParseError::InvalidQuery(format!("Invalid date string: {}", e)))?;
-            return Ok(Bson::DateTime(datetime));
+            return Self::parse_date_string(s).map(Bson::DateTime);
+        }
+        // Script values such as args[0] or env("SINCE") hold date strings
+        if matches!(expr, Expr::Member(_) | Expr::Call(_))
+            && let Ok(Bson::String(s)) = Self::expr_to_bson(expr)
+        {
+            return Self::parse_date_string(&s).map(Bson::DateTime);
         }
         // Numeric expression — evaluate to milliseconds since epoch.
         let millis = Self::expr_to_number(expr)? as i64;
-        Ok(Bson::DateTime(mongodb::bson::DateTime::from_millis(millis)))
+        Ok(Bson::DateTime(bson::DateTime::from_millis(millis)))
+    }
+
+    /// Convert a regex literal, mapping JavaScript flags to BSON options
+    ///
+    /// `i`, `m`, `s`, `x` and `u` are passed through; `g`, `y` and `d` only
+    /// affect JavaScript matching state and are dropped. BSON requires the
+    /// options in alphabetical order.
+    fn regex_to_bson(pattern: &str, flags: &str) -> Result<Bson> {
+        let mut options: Vec<char> = Vec::new();
+        for flag in flags.chars() {
+            match flag {
+                'i' | 'm' | 's' | 'x' | 'u' => {
+                    if !options.contains(&flag) {
+                        options.push(flag);
+                    }
+                }
+                'g' | 'y' | 'd' => {}
+                other => {
+                    return Err(ParseError::InvalidQuery(format!(
+                        "Invalid regular expression flag '{}' in /{}/{}",
+                        other, pattern, flags
+                    ))
+                    .into());
+                }
+            }
+        }
+        options.sort_unstable();
+
+        Ok(Bson::RegularExpression(bson::Regex {
+            pattern: pattern.to_string(),
+            options: options.into_iter().collect(),
+        }))
+    }
+
+    /// Parse a date string: RFC 3339, or a date / date-time without offset (UTC)
+    ///
+    /// Accepts the forms the shell accepts for `ISODate()`, e.g. `2024-01-01`,
+    /// `2024-01-01T10:30:00` and `2024-01-01T10:30:00.000Z`.
+    fn parse_date_string(s: &str) -> Result<bson::DateTime> {
+        use chrono::{NaiveDate, NaiveDateTime};
+
+        if let Ok(datetime) = bson::DateTime::parse_rfc3339_str(s) {
+            return Ok(datetime);
+        }
+
+        let naive = ["%Y-%m-%dT%H:%M:%S%.f", "%Y-%m-%d %H:%M:%S%.f", "%Y-%m-%dT%H:%M"]
+            .iter()
+            .find_map(|format| NaiveDateTime::parse_from_str(s, format).ok())
+            .or_else(|| {
+                NaiveDate::parse_from_str(s, "%Y-%m-%d")
+                    .ok()
+                    .and_then(|date| date.and_hms_opt(0, 0, 0))
+            })
+            .ok_or_else(|| ParseError::InvalidQuery(format!("Invalid date string: '{}'", s)))?;
+
+        Ok(bson::DateTime::from_millis(
+            naive.and_utc().timestamp_millis(),
+        ))
+    }
+
+    /// Parse UUID argument: a 32-digit hex string, dashes optional
+    fn parse_uuid_argument(expr: &Expr) -> Result<Bson> {
+        let Expr::String(s) = expr else {
+            return Err(ParseError::InvalidQuery("UUID argument must be string".to_string()).into());
+        };
+        let uuid = Uuid::parse_str(s)
+            .map_err(|e| ParseError::InvalidQuery(format!("Invalid UUID '{}': {}", s, e)))?;
+        Ok(Bson::Binary(Binary::from_uuid(uuid)))
+    }
+
+    /// Parse BinData(subtype, base64) arguments
+    fn parse_bindata_arguments(args: &[Expr]) -> Result<Bson> {
+        use base64::Engine as _;
+
+        let (Some(Expr::Number(subtype)), Some(Expr::String(data))) = (args.first(), args.get(1))
+        else {
+            return Err(ParseError::InvalidQuery(
+                "BinData requires a numeric subtype and a base64 string".to_string(),
+            )
+            .into());
+        };
+        if subtype.fract() != 0.0 || !(0.0..=255.0).contains(subtype) {
+            return Err(ParseError::InvalidQuery(format!(
+                "Invalid BinData subtype: {}",
+                subtype
+            ))
+            .into());
+        }
+
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(data)
+            .map_err(|e| ParseError::InvalidQuery(format!("Invalid base64 in BinData: {}", e)))?;
+        Ok(Bson::Binary(Binary {
+            subtype: BinarySubtype::from(*subtype as u8),
+            bytes,
+        }))
     }
 
     /// Parse ObjectId argument
     fn parse_objectid_argument(expr: &Expr) -> Result<Bson> {
         if let Expr::String(s) = expr {
-            let oid = mongodb::bson::oid::ObjectId::parse_str(s)
+            let oid = bson::oid::ObjectId::parse_str(s)
                 .map_err(|e| ParseError::InvalidQuery(format!("Invalid ObjectId: {}", e)))?;
             Ok(Bson::ObjectId(oid))
         } else {
@@ -362,6 +693,49 @@ impl ExpressionConverter {
         }
     }
 
+    /// Parse Timestamp(t, i) or Timestamp({ t, i }) into a BSON timestamp
+    fn parse_timestamp_arguments(args: &[Expr]) -> Result<Bson> {
+        let (time, increment) = match args {
+            [] => (0, 0),
+            [Expr::Object(obj)] => {
+                let fields = Self::object_to_bson(obj)?;
+                let part = |key: &str| fields.get(key).map_or(Ok(0), Self::timestamp_part);
+                (part("t")?, part("i")?)
+            }
+            [time, increment] => (
+                Self::timestamp_part(&Self::expr_to_bson(time)?)?,
+                Self::timestamp_part(&Self::expr_to_bson(increment)?)?,
+            ),
+            _ => {
+                return Err(ParseError::InvalidQuery(
+                    "Timestamp expects (seconds, increment) or { t, i }".to_string(),
+                )
+                .into());
+            }
+        };
+        Ok(Bson::Timestamp(bson::Timestamp {
+            time,
+            increment,
+        }))
+    }
+
+    /// Read one component of a timestamp as an unsigned 32-bit number
+    fn timestamp_part(value: &Bson) -> Result<u32> {
+        let n = match value {
+            Bson::Int32(n) => *n as i64,
+            Bson::Int64(n) => *n,
+            Bson::Double(n) if n.fract() == 0.0 => *n as i64,
+            _ => -1,
+        };
+        u32::try_from(n).map_err(|_| {
+            ParseError::InvalidQuery(format!(
+                "Timestamp parts must be integers between 0 and {}",
+                u32::MAX
+            ))
+            .into()
+        })
+    }
+
     /// Parse NumberDecimal argument and convert to Decimal128
     fn parse_decimal_argument(expr: &Expr) -> Result<Bson> {
         match expr {
@@ -485,6 +859,20 @@ mod tests {
         assert_eq!(bson.as_i64().unwrap(), 123456789);
     }
 
+    #[test]
+    fn test_timestamp() {
+        let expected = Bson::Timestamp(bson::Timestamp {
+            time: 1700000000,
+            increment: 3,
+        });
+        assert_eq!(parse_and_convert("Timestamp(1700000000, 3)"), expected);
+        assert_eq!(
+            parse_and_convert("Timestamp({ t: 1700000000, i: 3 })"),
+            expected
+        );
+        assert_eq!(parse_and_convert("new Timestamp(1700000000, 3)"), expected);
+    }
+
     #[test]
     fn test_mongo_operators() {
         let bson = parse_and_convert("{age: {$gt: 18, $lt: 65}}");
@@ -597,6 +985,34 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_function_stage_body_passthrough() {
+        let bson = parse_and_convert(
+            "{ $function: { body: function(name) { return name.toUpperCase(); }, args: ['$name'], lang: 'js' } }",
+        );
+        let stage = bson.as_document().unwrap().get_document("$function").unwrap();
+        match stage.get("body").unwrap() {
+            Bson::JavaScriptCode(code) => {
+                assert_eq!(code, "function(name) { return name.toUpperCase(); }")
+            }
+            other => panic!("Expected JavaScriptCode, got {:?}", other),
+        }
+        assert_eq!(stage.get_str("lang").unwrap(), "js");
+    }
+
+    #[test]
+    fn test_accumulator_string_and_arrow_bodies() {
+        let bson = parse_and_convert(
+            "{ $accumulator: { init: 'function() { return 0; }', accumulate: (state, v) => state + v, merge: (a, b) => a + b, lang: 'js' } }",
+        );
+        let acc = bson.as_document().unwrap().get_document("$accumulator").unwrap();
+        assert_eq!(acc.get_str("init").unwrap(), "function() { return 0; }");
+        assert!(
+            matches!(acc.get("accumulate").unwrap(), Bson::JavaScriptCode(c) if c == "(state, v) => state + v")
+        );
+        assert!(matches!(acc.get("merge").unwrap(), Bson::JavaScriptCode(c) if c == "(a, b) => a + b"));
+    }
+
     #[test]
     fn test_regex_in_query_filter() {
         let bson = parse_and_convert("{ name: { $regex: /^acme/i } }");
@@ -613,4 +1029,172 @@ mod tests {
             panic!("Expected document");
         }
     }
+
+    #[test]
+    fn test_prompt_label_must_be_string() {
+        let expr = MongoParser::parse("{ pwd: passwordPrompt(42) }").unwrap();
+        let err = ExpressionConverter::expr_to_bson(&expr).unwrap_err();
+        assert!(err.to_string().contains("label must be a string"));
+
+        let expr = MongoParser::parse("{ name: prompt({}) }").unwrap();
+        assert!(ExpressionConverter::expr_to_bson(&expr).is_err());
+    }
+
+    #[test]
+    fn test_isodate_date_only_and_local_time() {
+        let bson = parse_and_convert("ISODate('2024-01-01')");
+        let Bson::DateTime(dt) = bson else {
+            panic!("Expected DateTime");
+        };
+        assert_eq!(dt.timestamp_millis(), 1_704_067_200_000);
+
+        let bson = parse_and_convert("new Date('2024-01-01T10:30:00')");
+        assert!(matches!(bson, Bson::DateTime(_)));
+
+        let expr = MongoParser::parse("ISODate('yesterday')").unwrap();
+        assert!(ExpressionConverter::expr_to_bson(&expr).is_err());
+    }
+
+    #[test]
+    fn test_uuid_and_bindata() {
+        let bson = parse_and_convert("UUID('3b241101-e2bb-4255-8caf-4136c566a962')");
+        match bson {
+            Bson::Binary(bin) => {
+                assert_eq!(bin.subtype, BinarySubtype::Uuid);
+                assert_eq!(bin.bytes.len(), 16);
+                assert_eq!(bin.bytes[0], 0x3b);
+            }
+            other => panic!("Expected Binary, got {:?}", other),
+        }
+
+        let bson = parse_and_convert("BinData(0, 'aGVsbG8=')");
+        match bson {
+            Bson::Binary(bin) => {
+                assert_eq!(bin.subtype, BinarySubtype::Generic);
+                assert_eq!(bin.bytes, b"hello");
+            }
+            other => panic!("Expected Binary, got {:?}", other),
+        }
+
+        let expr = MongoParser::parse("BinData('x', 'aGVsbG8=')").unwrap();
+        assert!(ExpressionConverter::expr_to_bson(&expr).is_err());
+    }
+
+    #[test]
+    fn test_new_wrapper_constructors() {
+        let bson = parse_and_convert("new NumberLong('9007199254740993')");
+        assert_eq!(bson, Bson::Int64(9_007_199_254_740_993));
+
+        let bson = parse_and_convert("{ amount: NumberDecimal('10.25'), id: new UUID() }");
+        let Bson::Document(doc) = bson else {
+            panic!("Expected document");
+        };
+        assert!(matches!(doc.get("amount"), Some(Bson::Decimal128(_))));
+        assert!(matches!(doc.get("id"), Some(Bson::Binary(_))));
+    }
+
+    #[test]
+    fn test_regex_flags_normalized() {
+        let bson = parse_and_convert("/^al/gmi");
+        let Bson::RegularExpression(re) = bson else {
+            panic!("Expected RegularExpression");
+        };
+        assert_eq!(re.pattern, "^al");
+        assert_eq!(re.options, "im");
+
+        let expr = MongoParser::parse("/^al/q").unwrap();
+        assert!(ExpressionConverter::expr_to_bson(&expr).is_err());
+    }
+
+    #[test]
+    fn test_regex_in_array_and_field_value() {
+        let bson = parse_and_convert("{ name: /^al/i, tags: { $in: [/^db/, /ops$/i, 'x'] } }");
+        let Bson::Document(doc) = bson else {
+            panic!("Expected document");
+        };
+        assert!(matches!(doc.get("name"), Some(Bson::RegularExpression(_))));
+
+        let values = doc.get_document("tags").unwrap().get_array("$in").unwrap();
+        assert!(matches!(&values[0], Bson::RegularExpression(re) if re.pattern == "^db"));
+        assert!(matches!(&values[1], Bson::RegularExpression(re) if re.options == "i"));
+        assert_eq!(values[2].as_str(), Some("x"));
+    }
+
+    #[test]
+    fn test_string_concatenation_and_templates() {
+        let bson = parse_and_convert("{ source: 'app-' + 'web', shard: 'node' + (2 * 3), n: 1 + 2 + 'x' }");
+        let Bson::Document(doc) = bson else {
+            panic!("Expected document");
+        };
+        assert_eq!(doc.get_str("source").unwrap(), "app-web");
+        assert_eq!(doc.get_str("shard").unwrap(), "node6");
+        assert_eq!(doc.get_str("n").unwrap(), "3x");
+
+        let bson = parse_and_convert("`logs-${2024}-${1.5}-${true}`");
+        assert_eq!(bson, Bson::String("logs-2024-1.5-true".to_string()));
+
+        let expr = MongoParser::parse("'a' - 1").unwrap();
+        assert!(ExpressionConverter::expr_to_bson(&expr).is_err());
+    }
+
+    #[test]
+    fn test_constant_folding_with_dates() {
+        let bson = parse_and_convert("new Date(ISODate('2024-01-01T01:00:00Z') - 3600 * 1000)");
+        assert_eq!(
+            bson,
+            Bson::DateTime(bson::DateTime::from_millis(1_704_067_200_000))
+        );
+
+        let bson = parse_and_convert("new Date('2024-01-01T00:00:00Z').getTime() / 1000");
+        assert_eq!(bson, Bson::Int64(1_704_067_200));
+
+        assert_eq!(parse_and_convert("NumberInt(7) * 2"), Bson::Int64(14));
+        assert_eq!(parse_and_convert("7 / 2"), Bson::Double(3.5));
+
+        let bson = parse_and_convert("{ ts: { $gt: Date.now() - 3600 * 1000 } }");
+        let Bson::Document(doc) = bson else {
+            panic!("Expected document");
+        };
+        assert!(matches!(doc.get_document("ts").unwrap().get("$gt"), Some(Bson::Int64(_))));
+
+        let expr = MongoParser::parse("ObjectId() * 2").unwrap();
+        assert!(ExpressionConverter::expr_to_bson(&expr).is_err());
+    }
+
+    #[test]
+    fn test_script_args_and_env() {
+        let expr = MongoParser::parse("{ a: env('STAGE') }").unwrap();
+        assert!(ExpressionConverter::expr_to_bson(&expr).is_err());
+
+        let vars = std::collections::HashMap::from([("STAGE".to_string(), "prod".to_string())]);
+        let _scope = host_input::Scope::enter(Vec::new(), Some(std::sync::Arc::new(vars)));
+
+        let bson = parse_and_convert("{ a: env('STAGE'), b: env('UNSET', 5) }");
+        let doc = bson.as_document().unwrap();
+        assert_eq!(doc.get_str("a").unwrap(), "prod");
+        assert_eq!(doc.get_i64("b").unwrap(), 5);
+
+        let bson = parse_and_convert("{ a: process.env.STAGE, b: env('UNSET') }");
+        let doc = bson.as_document().unwrap();
+        assert_eq!(doc.get_str("a").unwrap(), "prod");
+        assert_eq!(doc.get("b"), Some(&Bson::Null));
+
+        // No script is running, so there are no arguments
+        let bson = parse_and_convert(
+            "{ all: args, n: args.length, first: args[0], exe: process.argv[0] }",
+        );
+        let doc = bson.as_document().unwrap();
+        assert_eq!(doc.get_array("all").unwrap().len(), 0);
+        assert_eq!(doc.get_i32("n").unwrap(), 0);
+        assert_eq!(doc.get("first"), Some(&Bson::Null));
+        assert_eq!(doc.get_str("exe").unwrap(), "mongosh");
+
+        let bson = parse_and_convert("ISODate(env('UNSET', '2024-01-01'))");
+        assert!(matches!(bson, Bson::DateTime(_)));
+
+        let expr = MongoParser::parse("{ a: db.users }").unwrap();
+        assert!(ExpressionConverter::expr_to_bson(&expr).is_err());
+        let expr = MongoParser::parse("{ a: args.first }").unwrap();
+        assert!(ExpressionConverter::expr_to_bson(&expr).is_err());
+    }
 }
@@ -12,6 +12,24 @@
 
 use std::ops::Range;
 
+/// Keywords after which a '/' starts a regex literal rather than a division
+pub(crate) const REGEX_PRECEDING_KEYWORDS: &[&str] = &[
+    "return",
+    "typeof",
+    "instanceof",
+    "in",
+    "of",
+    "new",
+    "delete",
+    "void",
+    "throw",
+    "case",
+    "do",
+    "else",
+    "yield",
+    "await",
+];
+
 /// Token types for Mongo shell syntax
 #[derive(Debug, Clone, PartialEq)]
 pub enum MongoTokenKind {
@@ -49,12 +67,16 @@ pub enum MongoTokenKind {
     Percent,
     /// Exclamation (logical NOT)
     Bang,
+    /// Slash (division, when a regex literal cannot start here)
+    Slash,
     /// String literal
     String(String),
     /// Number literal
     Number(String),
     /// Regular expression literal: /pattern/flags
     Regex(String, String),
+    /// Template literal body between backticks, with `${...}` kept verbatim
+    Template(String),
     /// End of file
     EOF,
     /// Unknown character
@@ -79,6 +101,8 @@ impl MongoToken {
 pub struct MongoLexer {
     input: Vec<char>,
     pos: usize,
+    /// Whether a '/' at the current position starts a regex literal
+    regex_allowed: bool,
 }
 
 impl MongoLexer {
@@ -87,6 +111,7 @@ impl MongoLexer {
         Self {
             input: input.chars().collect(),
             pos: 0,
+            regex_allowed: true,
         }
     }
 
@@ -98,6 +123,21 @@ impl MongoLexer {
         loop {
             let token = lexer.next_token();
             let is_eof = matches!(token.kind, MongoTokenKind::EOF);
+            // After an operand, '/' is division (e.g. inside a function body),
+            // but after a keyword such as `return` it starts a regex
+            lexer.regex_allowed = match &token.kind {
+                MongoTokenKind::Ident(name) => REGEX_PRECEDING_KEYWORDS.contains(&name.as_str()),
+                kind => !matches!(
+                    kind,
+                    MongoTokenKind::Db
+                        | MongoTokenKind::Number(_)
+                        | MongoTokenKind::String(_)
+                        | MongoTokenKind::Regex(_, _)
+                        | MongoTokenKind::Template(_)
+                        | MongoTokenKind::RParen
+                        | MongoTokenKind::RBracket
+                ),
+            };
             tokens.push(token);
             if is_eof {
                 break;
@@ -180,8 +220,13 @@ impl MongoLexer {
                 self.advance();
                 MongoToken::new(MongoTokenKind::Bang, start..self.pos)
             }
-            '/' => self.scan_regex(start),
+            '/' if self.regex_allowed => self.scan_regex(start),
+            '/' => {
+                self.advance();
+                MongoToken::new(MongoTokenKind::Slash, start..self.pos)
+            }
             '\'' | '"' => self.scan_string(ch, start),
+            '`' => self.scan_template(start),
             '0'..='9' => self.scan_number(start),
             'a'..='z' | 'A'..='Z' | '_' | '$' => self.scan_identifier(start),
             _ => {
@@ -191,6 +236,59 @@ impl MongoLexer {
         }
     }
 
+    /// Scan a template literal: `text ${expr} text`
+    ///
+    /// The body is returned raw; escapes and substitutions are handled by the
+    /// parser. Backticks inside `${...}` do not end the literal.
+    fn scan_template(&mut self, start: usize) -> MongoToken {
+        self.advance(); // Skip opening backtick
+
+        let mut body = String::new();
+        let mut depth = 0usize;
+        // Quote character of a string inside ${...}, whose braces don't count
+        let mut quote: Option<char> = None;
+
+        while !self.is_at_end() {
+            let ch = self.current_char();
+            if ch == '`' && depth == 0 {
+                break;
+            }
+            match ch {
+                _ if quote == Some(ch) => quote = None,
+                _ if quote.is_some() && ch != '\\' => {}
+                '\'' | '"' if depth > 0 => quote = Some(ch),
+                '\\' => {
+                    body.push(ch);
+                    self.advance();
+                    if !self.is_at_end() {
+                        body.push(self.current_char());
+                        self.advance();
+                    }
+                    continue;
+                }
+                '$' if depth == 0 && self.peek_char() == '{' => {
+                    body.push_str("${");
+                    self.advance();
+                    self.advance();
+                    depth = 1;
+                    continue;
+                }
+                '{' if depth > 0 => depth += 1,
+                '}' if depth > 0 => depth -= 1,
+                _ => {}
+            }
+            body.push(ch);
+            self.advance();
+        }
+
+        // Skip closing backtick if present
+        if self.current_char() == '`' {
+            self.advance();
+        }
+
+        MongoToken::new(MongoTokenKind::Template(body), start..self.pos)
+    }
+
     /// Scan a string literal
     fn scan_string(&mut self, quote: char, start: usize) -> MongoToken {
         self.advance(); // Skip opening quote
@@ -233,12 +331,19 @@ impl MongoLexer {
         self.advance(); // Skip opening '/'
 
         let mut pattern = String::new();
+        // Inside [...] a '/' does not end the literal, e.g. /[/]/
+        let mut in_class = false;
 
         while !self.is_at_end() {
             let ch = self.current_char();
-            if ch == '/' {
+            if ch == '/' && !in_class {
                 break;
             }
+            match ch {
+                '[' => in_class = true,
+                ']' => in_class = false,
+                _ => {}
+            }
             if ch == '\\' {
                 // Escape sequence inside regex
                 pattern.push(ch);
@@ -511,6 +616,21 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_tokenize_slash_after_operand() {
+        let tokens = MongoLexer::tokenize("a / b / c");
+        let slashes = tokens
+            .iter()
+            .filter(|t| matches!(t.kind, MongoTokenKind::Slash))
+            .count();
+        assert_eq!(slashes, 2);
+        assert!(
+            !tokens
+                .iter()
+                .any(|t| matches!(t.kind, MongoTokenKind::Regex(_, _)))
+        );
+    }
+
     #[test]
     fn test_tokenize_regex_in_query() {
         let tokens = MongoLexer::tokenize("{ name: { $regex: /^acme/i } }");
@@ -520,4 +640,25 @@ mod tests {
                 .any(|t| matches!(t.kind, MongoTokenKind::Regex(ref p, ref f) if p == "^acme" && f == "i"))
         );
     }
+
+    #[test]
+    fn test_tokenize_regex_slash_in_class() {
+        let tokens = MongoLexer::tokenize("/^[a/]+$/i");
+        assert!(matches!(
+            tokens[0].kind,
+            MongoTokenKind::Regex(ref p, ref f) if p == "^[a/]+$" && f == "i"
+        ));
+    }
+
+    #[test]
+    fn test_tokenize_regex_after_keyword() {
+        let tokens = MongoLexer::tokenize("return /}/.test(x)");
+        assert!(matches!(
+            tokens[1].kind,
+            MongoTokenKind::Regex(ref p, ref f) if p == "}" && f.is_empty()
+        ));
+
+        let tokens = MongoLexer::tokenize("total / count");
+        assert!(matches!(tokens[1].kind, MongoTokenKind::Slash));
+    }
 }
@@ -3,8 +3,10 @@
 //! This module contains parsers for all admin-related MongoDB operations:
 //! - Index operations (create, drop, list)
 //! - Collection operations (drop, rename, stats)
+//! - Validation rules (getValidator, setValidator, validate)
+//! - Capped collections (convertToCapped)
 
-use mongodb::bson::Document;
+use bson::Document;
 
 use crate::error::{ParseError, Result};
 use crate::parser::command::{AdminCommand, Command};
@@ -12,6 +14,12 @@ use crate::parser::mongo_ast::*;
 
 use super::args::ArgParser;
 
+/// Accepted values for the `level` option of setValidator()
+const VALIDATION_LEVELS: &[&str] = &["off", "strict", "moderate"];
+
+/// Accepted values for the `action` option of setValidator()
+const VALIDATION_ACTIONS: &[&str] = &["error", "warn", "errorAndLog"];
+
 /// Admin operation parsers
 pub struct AdminOpsParser;
 
@@ -23,6 +31,20 @@ impl AdminOpsParser {
         )))
     }
 
+    /// Parse indexStats operation: db.collection.indexStats()
+    pub fn parse_index_stats(collection: &str, args: &[Expr]) -> Result<Command> {
+        if !args.is_empty() {
+            return Err(ParseError::InvalidCommand(format!(
+                "indexStats() takes no arguments, got {}",
+                args.len()
+            ))
+            .into());
+        }
+        Ok(Command::Admin(AdminCommand::IndexStats(
+            collection.to_string(),
+        )))
+    }
+
     /// Parse createIndex operation
     pub fn parse_create_index(collection: &str, args: &[Expr]) -> Result<Command> {
         let keys = ArgParser::get_doc_arg(args, 0)?;
@@ -71,26 +93,58 @@ impl AdminOpsParser {
         }))
     }
 
-    /// Parse dropIndexes operation
-    pub fn parse_drop_indexes(collection: &str) -> Result<Command> {
-        Ok(Command::Admin(AdminCommand::DropIndex {
+    /// Parse dropIndexes operation: dropIndexes(), dropIndexes(name) or
+    /// dropIndexes([names])
+    pub fn parse_drop_indexes(collection: &str, args: &[Expr]) -> Result<Command> {
+        let indexes = if args.is_empty() {
+            None
+        } else {
+            Some(ArgParser::get_string_list_arg(args, 0)?)
+        };
+
+        Ok(Command::Admin(AdminCommand::DropIndexes {
             collection: collection.to_string(),
-            index: "*".to_string(),
+            indexes,
         }))
     }
 
-    /// Parse drop collection operation
-    pub fn parse_drop_collection(collection: &str) -> Result<Command> {
-        Ok(Command::Admin(AdminCommand::DropCollection(
-            collection.to_string(),
-        )))
+    /// Parse drop collection operation: drop() or drop({ backup: true })
+    pub fn parse_drop_collection(collection: &str, args: &[Expr]) -> Result<Command> {
+        let backup = match args {
+            [] => None,
+            [Expr::Object(_)] => Self::parse_backup_option(&ArgParser::get_doc_arg(args, 0)?)?,
+            _ => {
+                return Err(ParseError::InvalidCommand(
+                    "drop() takes at most an options document, e.g. { backup: true }".to_string(),
+                )
+                .into());
+            }
+        };
+
+        Ok(Command::Admin(AdminCommand::DropCollection {
+            collection: collection.to_string(),
+            backup,
+        }))
+    }
+
+    /// Read the `backup` option of drop() and renameCollection()
+    fn parse_backup_option(options: &Document) -> Result<Option<bool>> {
+        match options.get("backup") {
+            None => Ok(None),
+            Some(bson::Bson::Boolean(backup)) => Ok(Some(*backup)),
+            Some(_) => Err(ParseError::InvalidCommand(
+                "backup option must be a boolean".to_string(),
+            )
+            .into()),
+        }
     }
 
     /// Parse rename collection operation
     pub fn parse_rename_collection(collection: &str, args: &[Expr]) -> Result<Command> {
-        // renameCollection(target, dropTarget)
+        // renameCollection(target, dropTarget, options)
         // target is required (string)
         // dropTarget is optional (boolean, defaults to false)
+        // options is optional ({ dropTarget, backup }) and may replace dropTarget
 
         if args.is_empty() {
             return Err(ParseError::InvalidCommand(
@@ -99,10 +153,11 @@ impl AdminOpsParser {
             .into());
         }
 
-        if args.len() > 2 {
-            return Err(ParseError::InvalidCommand(
-                format!("renameCollection() expects at most 2 arguments, got {}", args.len()),
-            )
+        if args.len() > 3 {
+            return Err(ParseError::InvalidCommand(format!(
+                "renameCollection() expects at most 3 arguments, got {}",
+                args.len()
+            ))
             .into());
         }
 
@@ -117,25 +172,35 @@ impl AdminOpsParser {
             }
         };
 
-        // Parse dropTarget (optional, defaults to false)
-        let drop_target = if args.len() > 1 {
-            match &args[1] {
-                Expr::Boolean(b) => *b,
-                _ => {
-                    return Err(ParseError::InvalidCommand(
-                        "renameCollection() dropTarget must be a boolean".to_string(),
-                    )
-                    .into());
-                }
+        // Parse dropTarget (optional, defaults to false) and the options
+        let (mut drop_target, options) = match (args.get(1), args.get(2)) {
+            (None, _) => (false, None),
+            (Some(Expr::Boolean(b)), None) => (*b, None),
+            (Some(Expr::Boolean(b)), Some(Expr::Object(_))) => {
+                (*b, Some(ArgParser::get_doc_arg(args, 2)?))
+            }
+            (Some(Expr::Object(_)), None) => (false, Some(ArgParser::get_doc_arg(args, 1)?)),
+            _ => {
+                return Err(ParseError::InvalidCommand(
+                    "renameCollection() dropTarget must be a boolean, followed by an optional options document".to_string(),
+                )
+                .into());
             }
-        } else {
-            false
         };
 
+        let mut backup = None;
+        if let Some(options) = options {
+            if let Ok(value) = options.get_bool("dropTarget") {
+                drop_target = value;
+            }
+            backup = Self::parse_backup_option(&options)?;
+        }
+
         Ok(Command::Admin(AdminCommand::RenameCollection {
             collection: collection.to_string(),
             target,
             drop_target,
+            backup,
         }))
     }
 
@@ -175,6 +240,110 @@ impl AdminOpsParser {
             scale,
         }))
     }
+
+    /// Parse getValidator operation
+    pub fn parse_get_validator(collection: &str) -> Result<Command> {
+        Ok(Command::Admin(AdminCommand::GetValidator(
+            collection.to_string(),
+        )))
+    }
+
+    /// Parse setValidator operation: setValidator(schema, { level, action })
+    pub fn parse_set_validator(collection: &str, args: &[Expr]) -> Result<Command> {
+        if args.is_empty() || args.len() > 2 {
+            return Err(ParseError::InvalidCommand(format!(
+                "setValidator() expects a validator document and optional options, got {} arguments",
+                args.len()
+            ))
+            .into());
+        }
+
+        let validator = ArgParser::get_doc_arg(args, 0)?;
+        let options = ArgParser::get_doc_arg(args, 1)?;
+
+        for key in options.keys() {
+            if key != "level" && key != "action" {
+                return Err(ParseError::InvalidCommand(format!(
+                    "Unknown setValidator() option '{}'. Expected level or action",
+                    key
+                ))
+                .into());
+            }
+        }
+
+        let level = Self::get_choice(&options, "level", VALIDATION_LEVELS)?;
+        let action = Self::get_choice(&options, "action", VALIDATION_ACTIONS)?;
+
+        Ok(Command::Admin(AdminCommand::SetValidator {
+            collection: collection.to_string(),
+            validator,
+            level,
+            action,
+        }))
+    }
+
+    /// Parse validate operation: validate({ full: true }) or validate(true)
+    pub fn parse_validate(collection: &str, args: &[Expr]) -> Result<Command> {
+        let full = match args {
+            [] => false,
+            [Expr::Boolean(b)] => *b,
+            [Expr::Object(_)] => {
+                let options = ArgParser::get_doc_arg(args, 0)?;
+                options.get_bool("full").unwrap_or(false)
+            }
+            _ => {
+                return Err(ParseError::InvalidCommand(
+                    "validate() expects an options document, e.g. validate({ full: true })"
+                        .to_string(),
+                )
+                .into());
+            }
+        };
+
+        Ok(Command::Admin(AdminCommand::ValidateCollection {
+            collection: collection.to_string(),
+            full,
+        }))
+    }
+
+    /// Parse convertToCapped operation: convertToCapped(sizeInBytes)
+    pub fn parse_convert_to_capped(collection: &str, args: &[Expr]) -> Result<Command> {
+        if args.len() != 1 {
+            return Err(ParseError::InvalidCommand(
+                "convertToCapped() requires 1 argument: size in bytes".to_string(),
+            )
+            .into());
+        }
+
+        let size = ArgParser::get_number_arg(args, 0)?;
+        if size <= 0 {
+            return Err(ParseError::InvalidCommand(
+                "convertToCapped() size must be positive".to_string(),
+            )
+            .into());
+        }
+
+        Ok(Command::Admin(AdminCommand::ConvertToCapped {
+            collection: collection.to_string(),
+            size,
+        }))
+    }
+
+    /// Read an optional string option restricted to a set of values
+    fn get_choice(options: &Document, key: &str, choices: &[&str]) -> Result<Option<String>> {
+        match options.get(key) {
+            None => Ok(None),
+            Some(bson::Bson::String(value)) if choices.contains(&value.as_str()) => {
+                Ok(Some(value.clone()))
+            }
+            Some(_) => Err(ParseError::InvalidCommand(format!(
+                "setValidator() {} must be one of: {}",
+                key,
+                choices.join(", ")
+            ))
+            .into()),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -228,10 +397,33 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_parse_drop_indexes_by_name() {
+        let result = DbOperationParser::parse("db.users.dropIndexes(['a_1', 'b_1'])").unwrap();
+        assert_eq!(
+            result,
+            Command::Admin(AdminCommand::DropIndexes {
+                collection: "users".to_string(),
+                indexes: Some(vec!["a_1".to_string(), "b_1".to_string()]),
+            })
+        );
+        assert!(DbOperationParser::parse("db.users.dropIndexes([1])").is_err());
+    }
+
     #[test]
     fn test_parse_drop_collection() {
         let result = DbOperationParser::parse("db.users.drop()");
         assert!(result.is_ok());
+
+        let result = DbOperationParser::parse("db.users.drop({ backup: true })").unwrap();
+        assert_eq!(
+            result,
+            Command::Admin(AdminCommand::DropCollection {
+                collection: "users".to_string(),
+                backup: Some(true),
+            })
+        );
+        assert!(DbOperationParser::parse("db.users.drop({ backup: 'yes' })").is_err());
     }
 
     #[test]
@@ -262,6 +454,37 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_rename_collection_with_options() {
+        let result =
+            DbOperationParser::parse("db.users.renameCollection('customers', { backup: false })")
+                .unwrap();
+        assert_eq!(
+            result,
+            Command::Admin(AdminCommand::RenameCollection {
+                collection: "users".to_string(),
+                target: "customers".to_string(),
+                drop_target: false,
+                backup: Some(false),
+            })
+        );
+
+        let result = DbOperationParser::parse(
+            "db.users.renameCollection('customers', true, { backup: true })",
+        );
+        if let Ok(Command::Admin(AdminCommand::RenameCollection {
+            drop_target,
+            backup,
+            ..
+        })) = result
+        {
+            assert!(drop_target);
+            assert_eq!(backup, Some(true));
+        } else {
+            panic!("expected renameCollection");
+        }
+    }
+
     #[test]
     fn test_parse_collection_stats_no_args() {
         let result = DbOperationParser::parse("db.users.stats()");
@@ -279,4 +502,69 @@ mod tests {
         let result = DbOperationParser::parse("db.users.stats({ scale: 1024 })");
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_parse_get_validator() {
+        let result = DbOperationParser::parse("db.users.getValidator()").unwrap();
+        assert!(matches!(
+            result,
+            Command::Admin(AdminCommand::GetValidator(ref c)) if c == "users"
+        ));
+    }
+
+    #[test]
+    fn test_parse_set_validator() {
+        let result = DbOperationParser::parse(
+            "db.users.setValidator({ $jsonSchema: { required: ['email'] } }, { level: 'moderate', action: 'warn' })",
+        )
+        .unwrap();
+        match result {
+            Command::Admin(AdminCommand::SetValidator {
+                collection,
+                validator,
+                level,
+                action,
+            }) => {
+                assert_eq!(collection, "users");
+                assert!(validator.contains_key("$jsonSchema"));
+                assert_eq!(level.as_deref(), Some("moderate"));
+                assert_eq!(action.as_deref(), Some("warn"));
+            }
+            _ => panic!("Expected SetValidator command"),
+        }
+    }
+
+    #[test]
+    fn test_parse_set_validator_rejects_bad_options() {
+        assert!(DbOperationParser::parse("db.users.setValidator()").is_err());
+        assert!(DbOperationParser::parse("db.users.setValidator({}, { level: 'loose' })").is_err());
+        assert!(DbOperationParser::parse("db.users.setValidator({}, { mode: 'warn' })").is_err());
+    }
+
+    #[test]
+    fn test_parse_validate() {
+        let result = DbOperationParser::parse("db.users.validate({ full: true })").unwrap();
+        assert!(matches!(
+            result,
+            Command::Admin(AdminCommand::ValidateCollection { full: true, .. })
+        ));
+
+        let result = DbOperationParser::parse("db.users.validate()").unwrap();
+        assert!(matches!(
+            result,
+            Command::Admin(AdminCommand::ValidateCollection { full: false, .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_convert_to_capped() {
+        let result = DbOperationParser::parse("db.logs.convertToCapped(1048576)").unwrap();
+        assert!(matches!(
+            result,
+            Command::Admin(AdminCommand::ConvertToCapped { size: 1048576, .. })
+        ));
+
+        assert!(DbOperationParser::parse("db.logs.convertToCapped()").is_err());
+        assert!(DbOperationParser::parse("db.logs.convertToCapped(0)").is_err());
+    }
 }
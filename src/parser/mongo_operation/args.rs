@@ -1,9 +1,9 @@
 //! Argument extraction utilities for parsing MongoDB operation arguments
 
-use mongodb::bson::Document;
+use bson::{Bson, Document, Timestamp};
 
 use crate::error::{ParseError, Result};
-use crate::parser::command::{AggregateOptions, FindAndModifyOptions, UpdateOptions};
+use crate::parser::command::{AggregateOptions, FindAndModifyOptions, FindOptions, UpdateOptions};
 use crate::parser::mongo_ast::*;
 use crate::parser::mongo_converter::ExpressionConverter;
 
@@ -66,7 +66,7 @@ impl ArgParser {
     pub fn get_doc_arg(args: &[Expr], index: usize) -> Result<Document> {
         if let Some(expr) = args.get(index) {
             let bson = ExpressionConverter::expr_to_bson(expr)?;
-            if let mongodb::bson::Bson::Document(doc) = bson {
+            if let bson::Bson::Document(doc) = bson {
                 Ok(doc)
             } else {
                 Err(
@@ -83,10 +83,10 @@ impl ArgParser {
     pub fn get_doc_array_arg(args: &[Expr], index: usize) -> Result<Vec<Document>> {
         if let Some(expr) = args.get(index) {
             let bson = ExpressionConverter::expr_to_bson(expr)?;
-            if let mongodb::bson::Bson::Array(arr) = bson {
+            if let bson::Bson::Array(arr) = bson {
                 let mut docs = Vec::new();
                 for item in arr {
-                    if let mongodb::bson::Bson::Document(doc) = item {
+                    if let bson::Bson::Document(doc) = item {
                         docs.push(doc);
                     } else {
                         return Err(ParseError::InvalidQuery(
@@ -117,6 +117,57 @@ impl ArgParser {
         }
     }
 
+    /// Get argument at index as a list of names: a string or an array of strings
+    pub fn get_string_list_arg(args: &[Expr], index: usize) -> Result<Vec<String>> {
+        let expr = args.get(index).ok_or_else(|| {
+            ParseError::InvalidQuery(format!("Missing argument at index {}", index))
+        })?;
+        match ExpressionConverter::expr_to_bson(expr)? {
+            bson::Bson::String(s) => Ok(vec![s]),
+            bson::Bson::Array(arr) => arr
+                .into_iter()
+                .map(|item| match item {
+                    bson::Bson::String(s) => Ok(s),
+                    _ => Err(ParseError::InvalidQuery(
+                        "Array must contain only strings".to_string(),
+                    )
+                    .into()),
+                })
+                .collect(),
+            _ => Err(ParseError::InvalidQuery(format!(
+                "Argument {} must be a string or an array of strings",
+                index
+            ))
+            .into()),
+        }
+    }
+
+    /// Get argument at index as a cluster time
+    ///
+    /// Accepts `Timestamp(t, i)`, a date (`ISODate(...)`, `new Date(...)`) or
+    /// a number of seconds since the epoch; dates and numbers map to the first
+    /// operation of that second.
+    pub fn get_timestamp_arg(args: &[Expr], index: usize) -> Result<Timestamp> {
+        let expr = args.get(index).ok_or_else(|| {
+            ParseError::InvalidQuery(format!("Missing argument at index {}", index))
+        })?;
+        let seconds = match ExpressionConverter::expr_to_bson(expr)? {
+            Bson::Timestamp(ts) => return Ok(ts),
+            Bson::DateTime(date) => date.timestamp_millis().div_euclid(1000),
+            Bson::Int32(n) => n as i64,
+            Bson::Int64(n) => n,
+            Bson::Double(n) if n.fract() == 0.0 => n as i64,
+            _ => -1,
+        };
+        let time = u32::try_from(seconds).map_err(|_| {
+            ParseError::InvalidQuery(format!(
+                "Argument {} must be a Timestamp, a date or seconds since the epoch",
+                index
+            ))
+        })?;
+        Ok(Timestamp { time, increment: 0 })
+    }
+
     /// Get argument at index as number
     pub fn get_number_arg(args: &[Expr], index: usize) -> Result<i64> {
         if let Some(expr) = args.get(index) {
@@ -148,6 +199,15 @@ impl ArgParser {
         }
     }
 
+    /// Get find options from arguments, layered on top of `base`
+    pub fn get_find_options(args: &[Expr], index: usize, base: FindOptions) -> Result<FindOptions> {
+        if let Some(expr) = args.get(index) {
+            super::options::OptionsParser::parse_find_options(expr, base)
+        } else {
+            Ok(base)
+        }
+    }
+
     /// Get aggregate options from arguments
     pub fn get_aggregate_options(args: &[Expr], index: usize) -> Result<AggregateOptions> {
         if let Some(expr) = args.get(index) {
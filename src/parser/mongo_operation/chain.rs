@@ -5,15 +5,22 @@
 //! - db.collection.aggregate([...]).batchSize(100)
 //! - db.collection.explain().find()
 
-use mongodb::bson::Document;
+use bson::Document;
 
 use crate::error::{ParseError, Result};
 use crate::parser::command::{
-    AggregateOptions, Command, ExplainVerbosity, FindOptions, QueryCommand,
+    AggregateOptions, Command, ExplainVerbosity, ExportFormat, FindOptions, PipeCommand,
+    QueryCommand,
 };
 use crate::parser::mongo_ast::*;
 
 use super::args::ArgParser;
+use super::geo_ops::GeoOpsParser;
+use super::options::OptionsParser;
+use super::search_ops::SearchOpsParser;
+
+/// Most `_id` ranges `.parallel(n)` may scan at once
+const MAX_PARALLEL: i64 = 32;
 
 /// Represents a chained method call
 #[derive(Debug, Clone)]
@@ -223,10 +230,15 @@ impl ChainHandler {
                                     } else {
                                         None
                                     };
+                                    let collation = ArgParser::get_doc_arg(&base_call.arguments, 2)?
+                                        .get_document("collation")
+                                        .ok()
+                                        .cloned();
                                     QueryCommand::Distinct {
                                         collection: collection.clone(),
                                         field,
                                         filter,
+                                        collation,
                                     }
                                 }
                                 _ => {
@@ -343,6 +355,129 @@ impl ChainHandler {
 
     /// Apply a single chain method to a command
     fn apply_single_chain_method(cmd: Command, method: ChainMethod) -> Result<Command> {
+        // .export() streams the query to a file, stdout or a command
+        if method.name == "export" && matches!(cmd, Command::Query(_)) {
+            return Ok(Command::Pipe(
+                Box::new(cmd),
+                Self::parse_export_method(&method.args)?,
+            ));
+        }
+
+        // .format(), .pluck(), .table(), .chart(), .geo() and .full() only
+        // change how the query results are shown
+        let displayable = matches!(
+            cmd,
+            Command::Query(_)
+                | Command::Pipe(
+                    _,
+                    PipeCommand::Format(_) | PipeCommand::Pluck(_) | PipeCommand::Full
+                )
+        );
+
+        // .format() picks the output format for this query only
+        if method.name == "format" && displayable {
+            let name = ArgParser::get_string_arg(&method.args, 0)?;
+            return Ok(Command::Pipe(
+                Box::new(cmd),
+                PipeCommand::Format(crate::parser::Parser::parse_output_format(&name)?),
+            ));
+        }
+
+        // .pluck("name", "age") keeps only these fields of the displayed results
+        if method.name == "pluck" && displayable {
+            if method.args.is_empty() {
+                return Err(ParseError::InvalidQuery(
+                    "pluck() requires at least one field name".to_string(),
+                )
+                .into());
+            }
+            let fields = (0..method.args.len())
+                .map(|index| ArgParser::get_string_arg(&method.args, index))
+                .collect::<Result<Vec<_>>>()?;
+            return Ok(Command::Pipe(Box::new(cmd), PipeCommand::Pluck(fields)));
+        }
+
+        // .table(["name", "address.city"]) shows a table with these columns,
+        // in this order; .table() alone shows every field
+        if method.name == "table" && displayable {
+            let cmd = match method.args.len() {
+                0 => cmd,
+                1 => Command::Pipe(
+                    Box::new(cmd),
+                    PipeCommand::Pluck(ArgParser::get_string_list_arg(&method.args, 0)?),
+                ),
+                _ => Command::Pipe(
+                    Box::new(cmd),
+                    PipeCommand::Pluck(
+                        (0..method.args.len())
+                            .map(|index| ArgParser::get_string_arg(&method.args, index))
+                            .collect::<Result<Vec<_>>>()?,
+                    ),
+                ),
+            };
+            return Ok(Command::Pipe(
+                Box::new(cmd),
+                PipeCommand::Format(crate::config::OutputFormat::Table),
+            ));
+        }
+
+        // .chart() draws the results as a bar chart
+        if method.name == "chart" && displayable {
+            if !method.args.is_empty() {
+                return Err(
+                    ParseError::InvalidQuery("chart() takes no arguments".to_string()).into(),
+                );
+            }
+            return Ok(Command::Pipe(
+                Box::new(cmd),
+                PipeCommand::Format(crate::config::OutputFormat::Chart),
+            ));
+        }
+
+        // .geo() checks the GeoJSON of the query and shows the results on a map
+        if method.name == "geo" && displayable {
+            if !method.args.is_empty() {
+                return Err(
+                    ParseError::InvalidQuery("geo() takes no arguments".to_string()).into(),
+                );
+            }
+            let mut query = &cmd;
+            while let Command::Pipe(base, _) = query {
+                query = base;
+            }
+            GeoOpsParser::validate(query)?;
+            return Ok(Command::Pipe(
+                Box::new(cmd),
+                PipeCommand::Format(crate::config::OutputFormat::Map),
+            ));
+        }
+
+        // .full() shows whole values instead of previews
+        if method.name == "full" && displayable {
+            if !method.args.is_empty() {
+                return Err(
+                    ParseError::InvalidQuery("full() takes no arguments".to_string()).into(),
+                );
+            }
+            return Ok(Command::Pipe(Box::new(cmd), PipeCommand::Full));
+        }
+
+        // .save("out.json") writes the results to a file in the current format
+        if method.name == "save" && displayable {
+            let path = ArgParser::get_string_arg(&method.args, 0)?;
+            return Ok(Command::Pipe(Box::new(cmd), PipeCommand::Save(path)));
+        }
+
+        // .copy() puts the results on the clipboard
+        if method.name == "copy" && displayable {
+            if !method.args.is_empty() {
+                return Err(
+                    ParseError::InvalidQuery("copy() takes no arguments".to_string()).into(),
+                );
+            }
+            return Ok(Command::Pipe(Box::new(cmd), PipeCommand::Copy));
+        }
+
         match cmd {
             Command::Query(query_cmd) => {
                 let updated_query = Self::apply_chain_to_query(query_cmd, method)?;
@@ -355,6 +490,29 @@ impl ChainHandler {
         }
     }
 
+    /// Parse `.export(target, { format, compression })`
+    ///
+    /// The target is a file path, `-` for stdout or `!command` to pipe into a
+    /// shell command. Without a `format` option the format follows the file
+    /// extension, falling back to JSON Lines.
+    fn parse_export_method(args: &[Expr]) -> Result<PipeCommand> {
+        let target = ArgParser::get_string_arg(args, 0)?;
+        let options = match args.get(1) {
+            Some(expr) => OptionsParser::parse_export_options(expr)?,
+            None => Default::default(),
+        };
+        let format = options
+            .format
+            .or_else(|| ExportFormat::from_path(&target))
+            .unwrap_or(ExportFormat::JsonL);
+
+        Ok(PipeCommand::Export {
+            format,
+            file: Some(target),
+            compression: options.compression,
+        })
+    }
+
     /// Apply chain method to a query command
     fn apply_chain_to_query(query: QueryCommand, method: ChainMethod) -> Result<QueryCommand> {
         // Check if the method is "explain" - wrap the query in an Explain command
@@ -400,6 +558,95 @@ impl ChainHandler {
                     options: updated_options,
                 })
             }
+            QueryCommand::Distinct {
+                collection,
+                field,
+                filter,
+                collation: _,
+            } => {
+                if method.name != "collation" {
+                    return Err(ParseError::InvalidCommand(format!(
+                        "Unknown distinct() chain method: {}",
+                        method.name
+                    ))
+                    .into());
+                }
+                Ok(QueryCommand::Distinct {
+                    collection,
+                    field,
+                    filter,
+                    collation: Some(ArgParser::get_doc_arg(&method.args, 0)?),
+                })
+            }
+            QueryCommand::Search {
+                collection,
+                search,
+                limit: _,
+            } => {
+                if method.name != "limit" {
+                    return Err(ParseError::InvalidCommand(format!(
+                        "Unknown search() chain method: {}",
+                        method.name
+                    ))
+                    .into());
+                }
+                let new_limit = ArgParser::get_number_arg(&method.args, 0)?;
+                if new_limit <= 0 {
+                    return Err(ParseError::InvalidQuery(
+                        "limit() value must be positive".to_string(),
+                    )
+                    .into());
+                }
+                Ok(QueryCommand::Search {
+                    collection,
+                    search,
+                    limit: new_limit,
+                })
+            }
+            QueryCommand::DocSize {
+                collection,
+                filter,
+                limit: _,
+            } => {
+                if method.name != "limit" {
+                    return Err(ParseError::InvalidCommand(format!(
+                        "Unknown docSize() chain method: {}",
+                        method.name
+                    ))
+                    .into());
+                }
+                let new_limit = ArgParser::get_number_arg(&method.args, 0)?;
+                if new_limit <= 0 {
+                    return Err(ParseError::InvalidQuery(
+                        "limit() value must be positive".to_string(),
+                    )
+                    .into());
+                }
+                Ok(QueryCommand::DocSize {
+                    collection,
+                    filter,
+                    limit: new_limit,
+                })
+            }
+            QueryCommand::VectorSearch {
+                collection,
+                mut vector_search,
+            } => {
+                if method.name != "limit" {
+                    return Err(ParseError::InvalidCommand(format!(
+                        "Unknown vectorSearch() chain method: {}",
+                        method.name
+                    ))
+                    .into());
+                }
+                let new_limit = ArgParser::get_number_arg(&method.args, 0)?;
+                vector_search.insert("limit", new_limit);
+                SearchOpsParser::normalize_vector_search(&mut vector_search)?;
+                Ok(QueryCommand::VectorSearch {
+                    collection,
+                    vector_search,
+                })
+            }
             QueryCommand::Explain {
                 collection,
                 verbosity,
@@ -453,6 +700,24 @@ impl ChainHandler {
             "projection" => {
                 options.projection = Some(ArgParser::get_doc_arg(&method.args, 0)?);
             }
+            "noLimit" => {
+                if !method.args.is_empty() {
+                    return Err(ParseError::InvalidQuery(
+                        "noLimit() takes no arguments".to_string(),
+                    )
+                    .into());
+                }
+                options.no_limit = true;
+            }
+            "collation" => {
+                options.collation = Some(ArgParser::get_doc_arg(&method.args, 0)?);
+            }
+            "asOf" => {
+                options.as_of = Some(ArgParser::get_timestamp_arg(&method.args, 0)?);
+            }
+            "parallel" => {
+                options.parallel = Some(Self::parse_parallel(&method.args)?);
+            }
             "batchSize" => {
                 let batch_size = ArgParser::get_number_arg(&method.args, 0)?;
                 if batch_size <= 0 {
@@ -505,6 +770,12 @@ impl ChainHandler {
                 }
                 options.batch_size = Some(batch_size as u32);
             }
+            "collation" => {
+                options.collation = Some(ArgParser::get_doc_arg(&method.args, 0)?);
+            }
+            "parallel" => {
+                options.parallel = Some(Self::parse_parallel(&method.args)?);
+            }
             _ => {
                 return Err(ParseError::InvalidCommand(format!(
                     "Unknown aggregate() chain method: {}",
@@ -515,6 +786,19 @@ impl ChainHandler {
         }
         Ok(options)
     }
+
+    /// Parse the range count of `.parallel(n)`
+    fn parse_parallel(args: &[Expr]) -> Result<u32> {
+        let ranges = ArgParser::get_number_arg(args, 0)?;
+        if !(1..=MAX_PARALLEL).contains(&ranges) {
+            return Err(ParseError::InvalidQuery(format!(
+                "parallel() value must be between 1 and {}",
+                MAX_PARALLEL
+            ))
+            .into());
+        }
+        Ok(ranges as u32)
+    }
 }
 
 #[cfg(test)]
@@ -529,12 +813,230 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_parse_chained_export() {
+        let cmd = DbOperationParser::parse(
+            "db.users.find({ active: true }).limit(5).export('-', { format: 'csv' })",
+        )
+        .unwrap();
+        match cmd {
+            Command::Pipe(base, PipeCommand::Export { format, file, .. }) => {
+                assert!(matches!(*base, Command::Query(QueryCommand::Find { .. })));
+                assert_eq!(format, ExportFormat::Csv);
+                assert_eq!(file.as_deref(), Some("-"));
+            }
+            other => panic!("Expected export pipe, got {:?}", other),
+        }
+
+        // Format from the extension, compression from the options
+        let cmd = DbOperationParser::parse(
+            "db.users.find().export('users.csv.gz', { compression: 'gzip' })",
+        )
+        .unwrap();
+        assert!(matches!(
+            cmd,
+            Command::Pipe(
+                _,
+                PipeCommand::Export {
+                    format: ExportFormat::Csv,
+                    ..
+                }
+            )
+        ));
+
+        let cmd =
+            DbOperationParser::parse("db.users.find().export('report.html', { format: 'html' })")
+                .unwrap();
+        assert!(matches!(
+            cmd,
+            Command::Pipe(
+                _,
+                PipeCommand::Export {
+                    format: ExportFormat::Html,
+                    ..
+                }
+            )
+        ));
+
+        assert!(
+            DbOperationParser::parse("db.users.find().export('-', { format: 'xml' })").is_err()
+        );
+        assert!(DbOperationParser::parse("db.users.find().export('-').limit(1)").is_err());
+    }
+
+    #[test]
+    fn test_parse_chained_table() {
+        let cmd =
+            DbOperationParser::parse("db.users.find().limit(5).table(['name', 'address.city'])")
+                .unwrap();
+        assert_eq!(
+            cmd.format_override(),
+            Some(crate::config::OutputFormat::Table)
+        );
+        assert_eq!(
+            cmd.pluck_fields(),
+            Some(&["name".to_string(), "address.city".to_string()][..])
+        );
+
+        let cmd = DbOperationParser::parse("db.users.find().table('name', 'age')").unwrap();
+        assert_eq!(
+            cmd.pluck_fields(),
+            Some(&["name".to_string(), "age".to_string()][..])
+        );
+
+        let cmd = DbOperationParser::parse("db.users.find().table()").unwrap();
+        assert_eq!(
+            cmd.format_override(),
+            Some(crate::config::OutputFormat::Table)
+        );
+        assert!(cmd.pluck_fields().is_none());
+
+        assert!(DbOperationParser::parse("db.users.find().table([1])").is_err());
+    }
+
+    #[test]
+    fn test_parse_chained_chart() {
+        let cmd = DbOperationParser::parse(
+            "db.orders.aggregate([{ $group: { _id: '$status', count: { $sum: 1 } } }]).chart()",
+        )
+        .unwrap();
+        assert_eq!(
+            cmd.format_override(),
+            Some(crate::config::OutputFormat::Chart)
+        );
+        assert!(DbOperationParser::parse("db.orders.find().chart('x')").is_err());
+    }
+
+    #[test]
+    fn test_parse_chained_geo() {
+        let cmd = DbOperationParser::parse(
+            "db.places.find({ location: { $near: { $geometry: { type: 'Point', coordinates: [13.4, 52.5] }, $maxDistance: 500 } } }).limit(10).geo()",
+        )
+        .unwrap();
+        assert_eq!(cmd.format_override(), Some(crate::config::OutputFormat::Map));
+
+        let swapped = DbOperationParser::parse(
+            "db.places.find({ location: { $near: { $geometry: { type: 'Point', coordinates: [52.5, 113.4] } } } }).geo()",
+        );
+        assert!(swapped.is_err());
+        assert!(DbOperationParser::parse("db.places.insertOne({}).geo()").is_err());
+    }
+
+    #[test]
+    fn test_parse_chained_format() {
+        let cmd = DbOperationParser::parse("db.users.find().limit(5).format('table')").unwrap();
+        match cmd {
+            Command::Pipe(base, PipeCommand::Format(format)) => {
+                assert!(matches!(*base, Command::Query(QueryCommand::Find { .. })));
+                assert_eq!(format, crate::config::OutputFormat::Table);
+            }
+            other => panic!("Expected format pipe, got {:?}", other),
+        }
+
+        assert!(DbOperationParser::parse("db.users.find().format('yaml')").is_err());
+        assert!(DbOperationParser::parse("db.users.find().format()").is_err());
+    }
+
+    #[test]
+    fn test_parse_chained_pluck() {
+        let cmd =
+            DbOperationParser::parse("db.users.find().pluck('name', 'profile.city').format('table')")
+                .unwrap();
+        assert_eq!(cmd.format_override(), Some(crate::config::OutputFormat::Table));
+        assert_eq!(
+            cmd.pluck_fields(),
+            Some(&["name".to_string(), "profile.city".to_string()][..])
+        );
+
+        assert!(DbOperationParser::parse("db.users.find().pluck()").is_err());
+        assert!(DbOperationParser::parse("db.users.find().pluck(1)").is_err());
+        assert!(DbOperationParser::parse("db.users.find().pluck('name').limit(1)").is_err());
+    }
+
+    #[test]
+    fn test_parse_chained_save() {
+        let cmd = DbOperationParser::parse("db.users.find().pluck('name').save('out.json')").unwrap();
+        match cmd {
+            Command::Pipe(base, PipeCommand::Save(path)) => {
+                assert_eq!(path, "out.json");
+                assert_eq!(base.pluck_fields(), Some(&["name".to_string()][..]));
+            }
+            other => panic!("Expected save pipe, got {:?}", other),
+        }
+
+        assert!(DbOperationParser::parse("db.users.find().save()").is_err());
+        assert!(DbOperationParser::parse("db.users.find().save('a.json').limit(1)").is_err());
+    }
+
+    #[test]
+    fn test_parse_chained_copy() {
+        let cmd = DbOperationParser::parse("db.users.find().pluck('email').copy()").unwrap();
+        match cmd {
+            Command::Pipe(base, PipeCommand::Copy) => {
+                assert_eq!(base.pluck_fields(), Some(&["email".to_string()][..]));
+            }
+            other => panic!("Expected copy pipe, got {:?}", other),
+        }
+
+        assert!(DbOperationParser::parse("db.users.find().copy('x')").is_err());
+    }
+
+    #[test]
+    fn test_parse_chained_full() {
+        let cmd = DbOperationParser::parse("db.files.find().full().pluck('name', 'data')").unwrap();
+        assert!(cmd.full_values());
+        assert_eq!(
+            cmd.pluck_fields(),
+            Some(&["name".to_string(), "data".to_string()][..])
+        );
+        let plain = DbOperationParser::parse("db.files.find()").unwrap();
+        assert!(!plain.full_values());
+
+        assert!(DbOperationParser::parse("db.files.find().full(1)").is_err());
+    }
+
     #[test]
     fn test_parse_chained_skip() {
         let result = DbOperationParser::parse("db.users.find().skip(5)");
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_parse_chained_no_limit() {
+        let cmd = DbOperationParser::parse("db.users.find({ active: true }).noLimit()").unwrap();
+        match cmd {
+            Command::Query(QueryCommand::Find { options, .. }) => {
+                assert!(options.no_limit);
+                assert_eq!(options.limit, None);
+            }
+            _ => panic!("Expected Find command"),
+        }
+
+        assert!(DbOperationParser::parse("db.users.find().noLimit(5)").is_err());
+    }
+
+    #[test]
+    fn test_parse_chained_parallel() {
+        let cmd = DbOperationParser::parse("db.events.find({ type: 'click' }).parallel(8)").unwrap();
+        match cmd {
+            Command::Query(QueryCommand::Find { options, .. }) => {
+                assert_eq!(options.parallel, Some(8));
+            }
+            _ => panic!("Expected Find command"),
+        }
+
+        let cmd = DbOperationParser::parse("db.events.aggregate([{ $count: 'n' }]).parallel(4)").unwrap();
+        match cmd {
+            Command::Query(QueryCommand::Aggregate { options, .. }) => {
+                assert_eq!(options.parallel, Some(4));
+            }
+            _ => panic!("Expected Aggregate command"),
+        }
+
+        assert!(DbOperationParser::parse("db.events.find().parallel(0)").is_err());
+        assert!(DbOperationParser::parse("db.events.find().parallel(33)").is_err());
+    }
+
     #[test]
     fn test_parse_chained_sort() {
         let result = DbOperationParser::parse("db.users.find().sort({ age: -1 })");
@@ -580,6 +1082,54 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_parse_find_with_collation_chain() {
+        let result = DbOperationParser::parse(
+            "db.users.find({ name: 'alice' }).collation({ locale: 'en', strength: 2 })",
+        )
+        .unwrap();
+        match result {
+            Command::Query(QueryCommand::Find { options, .. }) => {
+                let collation = options.collation.unwrap();
+                assert_eq!(collation.get_str("locale").unwrap(), "en");
+                assert_eq!(collation.get_i64("strength").unwrap(), 2);
+            }
+            _ => panic!("Expected Find command"),
+        }
+    }
+
+    #[test]
+    fn test_parse_distinct_with_collation_chain() {
+        let result =
+            DbOperationParser::parse("db.users.distinct('city').collation({ locale: 'fr' })")
+                .unwrap();
+        assert!(matches!(
+            result,
+            Command::Query(QueryCommand::Distinct { collation: Some(_), .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_find_with_as_of() {
+        let result = DbOperationParser::parse(
+            "db.orders.find({ status: 'paid' }).sort({ _id: 1 }).asOf(Timestamp(1700000000, 4))",
+        )
+        .unwrap();
+        match result {
+            Command::Query(QueryCommand::Find { options, .. }) => {
+                assert!(options.sort.is_some());
+                assert_eq!(
+                    options.as_of,
+                    Some(bson::Timestamp {
+                        time: 1700000000,
+                        increment: 4,
+                    })
+                );
+            }
+            _ => panic!("Expected Find command"),
+        }
+    }
+
     #[test]
     fn test_parse_find_with_explain_after() {
         let result = DbOperationParser::parse("db.users.find({ age: { $gt: 18 } }).explain()");
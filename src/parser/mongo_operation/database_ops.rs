@@ -0,0 +1,456 @@
+//! Database-level operation parsers
+//!
+//! This module parses operations called directly on `db` rather than on a
+//! collection, such as `db.createCollection(name, options)`,
+//! `db.runCommand({...})`, `db.adminCommand({...})` and
+//! `db.dropCollections(/pattern/)`. It also resolves
+//! `db.getSiblingDB(name)` so the rest of the expression can be parsed as if
+//! it started from `db`.
+
+use bson::{Bson, Document, bson, doc};
+
+use crate::error::{ParseError, Result};
+use crate::parser::command::{AdminCommand, Command};
+use crate::parser::mongo_ast::*;
+use crate::parser::mongo_converter::ExpressionConverter;
+use crate::parser::shell_commands::ShellCommandParser;
+
+use super::args::ArgParser;
+
+/// Database-level operation parsers
+pub struct DatabaseOpsParser;
+
+impl DatabaseOpsParser {
+    /// Return the method name if the callee is `db.method`
+    pub fn database_method(callee: &Expr) -> Option<&str> {
+        let Expr::Member(member) = callee else {
+            return None;
+        };
+        match (member.object.as_ref(), &member.property) {
+            (Expr::Ident(object), MemberProperty::Ident(method)) if object == "db" => {
+                Some(method.as_str())
+            }
+            _ => None,
+        }
+    }
+
+    /// Replace a leading `db.getSiblingDB(name)` with `db`
+    ///
+    /// # Returns
+    /// * `Result<Option<(String, Expr)>>` - The sibling database name and the
+    ///   rewritten expression, or `None` if no `getSiblingDB` call is present
+    pub fn resolve_sibling_db(expr: &Expr) -> Result<Option<(String, Expr)>> {
+        let mut expr = expr.clone();
+        let database = Self::take_sibling_db(&mut expr)?;
+        Ok(database.map(|database| (database, expr)))
+    }
+
+    /// Walk down the callee/object chain looking for `db.getSiblingDB(name)`
+    fn take_sibling_db(expr: &mut Expr) -> Result<Option<String>> {
+        match expr {
+            Expr::Call(call) => {
+                if Self::database_method(call.callee.as_ref()) == Some("getSiblingDB") {
+                    let database = Self::parse_sibling_name(&call.arguments)?;
+                    *expr = Expr::Ident("db".to_string());
+                    return Ok(Some(database));
+                }
+                Self::take_sibling_db(&mut call.callee)
+            }
+            Expr::Member(member) => Self::take_sibling_db(&mut member.object),
+            _ => Ok(None),
+        }
+    }
+
+    /// Parse the database name argument of getSiblingDB(name)
+    fn parse_sibling_name(args: &[Expr]) -> Result<String> {
+        if args.len() != 1 {
+            return Err(ParseError::InvalidCommand(format!(
+                "getSiblingDB() expects 1 database name, got {} arguments",
+                args.len()
+            ))
+            .into());
+        }
+
+        let database = ArgParser::get_string_arg(args, 0)?;
+        if !ShellCommandParser::is_valid_db_name(&database) {
+            return Err(ParseError::InvalidCommand(format!(
+                "Invalid database name '{}'",
+                database
+            ))
+            .into());
+        }
+        Ok(database)
+    }
+
+    /// Parse a database-level operation: db.method(...)
+    pub fn parse(method: &str, args: &[Expr]) -> Result<Command> {
+        match method {
+            "createCollection" => Self::parse_create_collection(args),
+            "runCommand" => Self::parse_run_command(args),
+            "adminCommand" => Self::parse_admin_command(args),
+            "dropCollections" => Self::parse_drop_collections(args),
+            "indexReport" => Self::parse_index_report(args),
+            _ => Err(ParseError::InvalidCommand(format!(
+                "Unknown database operation 'db.{}()'",
+                method
+            ))
+            .into()),
+        }
+    }
+
+    /// Parse indexReport: db.indexReport({ since: ISODate(...) })
+    fn parse_index_report(args: &[Expr]) -> Result<Command> {
+        if args.len() > 1 {
+            return Err(ParseError::InvalidCommand(format!(
+                "indexReport() expects an optional options document, got {} arguments",
+                args.len()
+            ))
+            .into());
+        }
+
+        let mut since = None;
+        for (key, value) in ArgParser::get_doc_arg(args, 0)? {
+            match (key.as_str(), value) {
+                ("since", Bson::DateTime(date)) => since = Some(date),
+                ("since", _) => {
+                    return Err(ParseError::InvalidCommand(
+                        "indexReport() since must be a date, e.g. ISODate(\"2026-01-01\")"
+                            .to_string(),
+                    )
+                    .into());
+                }
+                (other, _) => {
+                    return Err(ParseError::InvalidCommand(format!(
+                        "Unknown indexReport() option '{}'",
+                        other
+                    ))
+                    .into());
+                }
+            }
+        }
+
+        Ok(Command::Admin(AdminCommand::IndexReport { since }))
+    }
+
+    /// Parse createCollection: db.createCollection(name, { capped, size, max, ... })
+    fn parse_create_collection(args: &[Expr]) -> Result<Command> {
+        if args.is_empty() || args.len() > 2 {
+            return Err(ParseError::InvalidCommand(format!(
+                "createCollection() expects a name and optional options, got {} arguments",
+                args.len()
+            ))
+            .into());
+        }
+
+        let name = ArgParser::get_string_arg(args, 0)?;
+        let options = ArgParser::get_doc_arg(args, 1)?;
+        Self::validate_capped_options(&options)?;
+
+        Ok(Command::Admin(AdminCommand::CreateCollection { name, options }))
+    }
+
+    /// Parse dropCollections: db.dropCollections(/pattern/) or
+    /// db.dropCollections([names])
+    ///
+    /// The argument becomes a `listCollections` filter on the name, so the
+    /// matching is done by the server.
+    fn parse_drop_collections(args: &[Expr]) -> Result<Command> {
+        if args.len() != 1 {
+            return Err(ParseError::InvalidCommand(format!(
+                "dropCollections() expects a regular expression or an array of names, got {} arguments",
+                args.len()
+            ))
+            .into());
+        }
+
+        let name = match &args[0] {
+            Expr::Regex(..) => ExpressionConverter::expr_to_bson(&args[0])?,
+            _ => bson!({ "$in": ArgParser::get_string_list_arg(args, 0)? }),
+        };
+
+        Ok(Command::Admin(AdminCommand::DropCollections {
+            filter: doc! { "name": name },
+        }))
+    }
+
+    /// Parse runCommand: db.runCommand({ command: ..., ... })
+    ///
+    /// `collMod` is modeled as its own command so it gets confirmation and
+    /// validation; any other command is passed through to the server as is.
+    fn parse_run_command(args: &[Expr]) -> Result<Command> {
+        let (name, mut command) = Self::get_command_doc("runCommand", args)?;
+
+        match name.as_str() {
+            "collMod" => {
+                let collection = match command.remove("collMod") {
+                    Some(Bson::String(collection)) => collection,
+                    _ => {
+                        return Err(ParseError::InvalidCommand(
+                            "collMod requires a collection name".to_string(),
+                        )
+                        .into());
+                    }
+                };
+                if command.is_empty() {
+                    return Err(ParseError::InvalidCommand(
+                        "collMod requires at least one option to change".to_string(),
+                    )
+                    .into());
+                }
+                Ok(Command::Admin(AdminCommand::CollMod {
+                    collection,
+                    changes: command,
+                }))
+            }
+            _ => Ok(Command::Admin(AdminCommand::RunCommand {
+                command,
+                admin: false,
+            })),
+        }
+    }
+
+    /// Parse adminCommand: db.adminCommand({ command: ..., ... })
+    fn parse_admin_command(args: &[Expr]) -> Result<Command> {
+        let (_, command) = Self::get_command_doc("adminCommand", args)?;
+        Ok(Command::Admin(AdminCommand::RunCommand {
+            command,
+            admin: true,
+        }))
+    }
+
+    /// Read the single command document argument and its command name
+    fn get_command_doc(method: &str, args: &[Expr]) -> Result<(String, Document)> {
+        if args.len() != 1 {
+            return Err(ParseError::InvalidCommand(format!(
+                "{}() expects 1 command document, got {}",
+                method,
+                args.len()
+            ))
+            .into());
+        }
+
+        let command = ArgParser::get_doc_arg(args, 0)?;
+        let Some(name) = command.keys().next().cloned() else {
+            return Err(ParseError::InvalidCommand(format!(
+                "{}() requires a non-empty command document",
+                method
+            ))
+            .into());
+        };
+
+        Ok((name, command))
+    }
+
+    /// Validate `capped`, `size` and `max` in collection options
+    fn validate_capped_options(options: &Document) -> Result<()> {
+        for key in ["size", "max"] {
+            if let Some(value) = options.get(key)
+                && !is_positive_number(value)
+            {
+                return Err(ParseError::InvalidQuery(format!(
+                    "createCollection() {} must be a positive number",
+                    key
+                ))
+                .into());
+            }
+        }
+
+        match options.get("capped") {
+            None | Some(Bson::Boolean(false)) => {
+                if options.contains_key("size") || options.contains_key("max") {
+                    return Err(ParseError::InvalidQuery(
+                        "createCollection() size and max require capped: true".to_string(),
+                    )
+                    .into());
+                }
+                Ok(())
+            }
+            Some(Bson::Boolean(true)) => {
+                if !options.contains_key("size") {
+                    return Err(ParseError::InvalidQuery(
+                        "Capped collections require a size in bytes, e.g. { capped: true, size: 1048576 }"
+                            .to_string(),
+                    )
+                    .into());
+                }
+                Ok(())
+            }
+            Some(_) => Err(ParseError::InvalidQuery(
+                "createCollection() capped must be a boolean".to_string(),
+            )
+            .into()),
+        }
+    }
+}
+
+/// Check that a BSON value is a strictly positive number
+fn is_positive_number(value: &Bson) -> bool {
+    match value {
+        Bson::Int32(n) => *n > 0,
+        Bson::Int64(n) => *n > 0,
+        Bson::Double(n) => *n > 0.0,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::mongo_operation::DbOperationParser;
+
+    #[test]
+    fn test_parse_create_collection() {
+        let cmd = DbOperationParser::parse("db.createCollection('events')").unwrap();
+        match cmd {
+            Command::Admin(AdminCommand::CreateCollection { name, options }) => {
+                assert_eq!(name, "events");
+                assert!(options.is_empty());
+            }
+            _ => panic!("Expected CreateCollection command"),
+        }
+    }
+
+    #[test]
+    fn test_parse_index_report() {
+        assert_eq!(
+            DbOperationParser::parse("db.indexReport()").unwrap(),
+            Command::Admin(AdminCommand::IndexReport { since: None })
+        );
+        assert!(matches!(
+            DbOperationParser::parse("db.indexReport({ since: ISODate('2026-01-01T00:00:00Z') })")
+                .unwrap(),
+            Command::Admin(AdminCommand::IndexReport { since: Some(_) })
+        ));
+        assert!(DbOperationParser::parse("db.indexReport({ since: 'yesterday' })").is_err());
+        assert!(DbOperationParser::parse("db.indexReport({ days: 30 })").is_err());
+        assert_eq!(
+            DbOperationParser::parse("db.orders.indexStats()").unwrap(),
+            Command::Admin(AdminCommand::IndexStats("orders".to_string()))
+        );
+        assert!(DbOperationParser::parse("db.orders.indexStats(1)").is_err());
+    }
+
+    #[test]
+    fn test_parse_create_capped_collection() {
+        let cmd = DbOperationParser::parse(
+            "db.createCollection('log', { capped: true, size: 1048576, max: 5000 })",
+        )
+        .unwrap();
+        match cmd {
+            Command::Admin(AdminCommand::CreateCollection { options, .. }) => {
+                assert!(options.get_bool("capped").unwrap());
+                assert!(options.contains_key("size"));
+                assert!(options.contains_key("max"));
+            }
+            _ => panic!("Expected CreateCollection command"),
+        }
+    }
+
+    #[test]
+    fn test_create_capped_collection_requires_size() {
+        assert!(DbOperationParser::parse("db.createCollection('log', { capped: true })").is_err());
+        assert!(DbOperationParser::parse("db.createCollection('log', { size: 1024 })").is_err());
+        assert!(
+            DbOperationParser::parse("db.createCollection('log', { capped: true, size: -1 })")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_parse_coll_mod() {
+        let cmd = DbOperationParser::parse(
+            "db.runCommand({ collMod: 'sessions', index: { keyPattern: { lastAccess: 1 }, expireAfterSeconds: 3600 } })",
+        )
+        .unwrap();
+        match cmd {
+            Command::Admin(AdminCommand::CollMod {
+                collection,
+                changes,
+            }) => {
+                assert_eq!(collection, "sessions");
+                assert!(changes.contains_key("index"));
+                assert!(!changes.contains_key("collMod"));
+            }
+            _ => panic!("Expected CollMod command"),
+        }
+
+        assert!(DbOperationParser::parse("db.runCommand({ collMod: 'sessions' })").is_err());
+    }
+
+    #[test]
+    fn test_parse_run_command_passthrough() {
+        let cmd = DbOperationParser::parse("db.runCommand({ dbStats: 1, scale: 1024 })").unwrap();
+        match cmd {
+            Command::Admin(AdminCommand::RunCommand { command, admin }) => {
+                assert!(!admin);
+                assert_eq!(command.keys().next().unwrap(), "dbStats");
+                assert!(command.contains_key("scale"));
+            }
+            _ => panic!("Expected RunCommand command"),
+        }
+
+        assert!(DbOperationParser::parse("db.runCommand({})").is_err());
+        assert!(DbOperationParser::parse("db.runCommand('ping')").is_err());
+    }
+
+    #[test]
+    fn test_parse_admin_command() {
+        let cmd = DbOperationParser::parse("db.adminCommand({ listDatabases: 1 })").unwrap();
+        match cmd {
+            Command::Admin(AdminCommand::RunCommand { command, admin }) => {
+                assert!(admin);
+                assert!(command.contains_key("listDatabases"));
+            }
+            _ => panic!("Expected RunCommand command"),
+        }
+    }
+
+    #[test]
+    fn test_parse_drop_collections() {
+        let cmd = DbOperationParser::parse("db.dropCollections(/^tmp_/i)").unwrap();
+        let expected = doc! {
+            "name": Bson::RegularExpression(bson::Regex {
+                pattern: "^tmp_".to_string(),
+                options: "i".to_string(),
+            })
+        };
+        assert_eq!(
+            cmd,
+            Command::Admin(AdminCommand::DropCollections { filter: expected })
+        );
+
+        let cmd = DbOperationParser::parse("db.dropCollections(['a', 'b'])").unwrap();
+        assert_eq!(
+            cmd,
+            Command::Admin(AdminCommand::DropCollections {
+                filter: doc! { "name": { "$in": ["a", "b"] } },
+            })
+        );
+
+        assert!(DbOperationParser::parse("db.dropCollections()").is_err());
+        assert!(DbOperationParser::parse("db.dropCollections({ name: 1 })").is_err());
+    }
+
+    #[test]
+    fn test_parse_sibling_db() {
+        let cmd = DbOperationParser::parse("db.getSiblingDB('reporting').users.find({ active: true }).limit(5)")
+            .unwrap();
+        match cmd {
+            Command::WithDatabase { database, command } => {
+                assert_eq!(database, "reporting");
+                assert!(matches!(
+                    *command,
+                    Command::Query(crate::parser::command::QueryCommand::Find { ref collection, .. })
+                        if collection == "users"
+                ));
+            }
+            _ => panic!("Expected WithDatabase command"),
+        }
+
+        let cmd = DbOperationParser::parse("db.getSiblingDB('admin').runCommand({ ping: 1 })").unwrap();
+        assert!(matches!(cmd, Command::WithDatabase { .. }));
+
+        assert!(DbOperationParser::parse("db.getSiblingDB('bad.name').users.find()").is_err());
+        assert!(DbOperationParser::parse("db.getSiblingDB().users.find()").is_err());
+    }
+}
@@ -0,0 +1,389 @@
+//! Geospatial query validation for the `.geo()` helper
+//!
+//! `db.places.find({ location: { $near: { $geometry: { type: 'Point',
+//! coordinates: [13.4, 52.5] }, $maxDistance: 500 } } }).geo()` checks the
+//! GeoJSON given to `$near`, `$nearSphere`, `$geoWithin` and `$geoIntersects`
+//! (and the `near` point of a `$geoNear` stage) before the query is sent, so
+//! swapped coordinates or an unclosed polygon ring are reported with the path
+//! of the offending value instead of a terse server error. The results are
+//! then shown on a map, see the `map` output format.
+
+use bson::{Bson, Document};
+
+use crate::error::{ParseError, Result};
+use crate::parser::command::{Command, QueryCommand};
+
+/// Query operators whose operand may hold a `$geometry`
+const GEO_OPERATORS: &[&str] = &["$near", "$nearSphere", "$geoWithin", "$geoIntersects"];
+
+/// Geospatial query validation
+pub struct GeoOpsParser;
+
+impl GeoOpsParser {
+    /// Check the geospatial parts of a query
+    ///
+    /// # Arguments
+    /// * `cmd` - Parsed command `.geo()` was applied to
+    ///
+    /// # Returns
+    /// * `Result<()>` - Error naming the invalid value, or for a command
+    ///   that is not a find, count or aggregation
+    pub fn validate(cmd: &Command) -> Result<()> {
+        match cmd {
+            Command::Query(
+                QueryCommand::Find { filter, .. }
+                | QueryCommand::FindOne { filter, .. }
+                | QueryCommand::CountDocuments { filter, .. },
+            ) => validate_filter(filter, ""),
+            Command::Query(QueryCommand::Aggregate { pipeline, .. }) => {
+                for (index, stage) in pipeline.iter().enumerate() {
+                    if let Ok(geo_near) = stage.get_document("$geoNear") {
+                        validate_geo_near(geo_near, &format!("pipeline[{}].$geoNear", index))?;
+                    }
+                    if let Ok(filter) = stage.get_document("$match") {
+                        validate_filter(filter, &format!("pipeline[{}].$match", index))?;
+                    }
+                }
+                Ok(())
+            }
+            _ => Err(invalid(
+                "geo() can only be used with find, findOne, countDocuments or aggregate",
+            )),
+        }
+    }
+}
+
+/// Walk a filter, checking the operands of geospatial operators
+fn validate_filter(filter: &Document, path: &str) -> Result<()> {
+    for (key, value) in filter {
+        let here = join(path, key);
+        match value {
+            Bson::Document(operand) if GEO_OPERATORS.contains(&key.as_str()) => {
+                validate_geo_operand(key, operand, &here)?;
+            }
+            Bson::Array(pair) if matches!(key.as_str(), "$near" | "$nearSphere") => {
+                legacy_pair(pair, &here)?;
+            }
+            Bson::Document(nested) => validate_filter(nested, &here)?,
+            Bson::Array(items) => {
+                for (index, item) in items.iter().enumerate() {
+                    if let Bson::Document(nested) = item {
+                        validate_filter(nested, &format!("{}[{}]", here, index))?;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Check the operand of `$near`, `$nearSphere`, `$geoWithin` or `$geoIntersects`
+fn validate_geo_operand(operator: &str, operand: &Document, path: &str) -> Result<()> {
+    if let Some(geometry) = operand.get("$geometry") {
+        let geometry = geometry
+            .as_document()
+            .ok_or_else(|| invalid(format!("{}.$geometry must be a GeoJSON object", path)))?;
+        validate_geometry(geometry, &join(path, "$geometry"))?;
+        if matches!(operator, "$near" | "$nearSphere")
+            && geometry.get_str("type").ok() != Some("Point")
+        {
+            return Err(invalid(format!(
+                "{}.$geometry must be a Point for {}",
+                path, operator
+            )));
+        }
+    } else if operator == "$geoIntersects" {
+        return Err(invalid(format!("{} requires a $geometry", path)));
+    }
+
+    for distance in ["$maxDistance", "$minDistance"] {
+        if let Some(value) = operand.get(distance)
+            && !number(value).is_some_and(|n| n >= 0.0)
+        {
+            return Err(invalid(format!(
+                "{}.{} must be a non-negative number",
+                path, distance
+            )));
+        }
+    }
+
+    if let Some(Bson::Array(center)) = operand.get("$centerSphere") {
+        let here = join(path, "$centerSphere");
+        match center.as_slice() {
+            [Bson::Array(point), radius] if number(radius).is_some_and(|r| r >= 0.0) => {
+                position(point, &format!("{}[0]", here))?;
+            }
+            _ => {
+                return Err(invalid(format!(
+                    "{} must be [[longitude, latitude], radius in radians]",
+                    here
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Check the `near` point of a `$geoNear` stage
+fn validate_geo_near(stage: &Document, path: &str) -> Result<()> {
+    if !stage.contains_key("distanceField") {
+        return Err(invalid(format!("{} requires a distanceField", path)));
+    }
+    match stage.get("near") {
+        Some(Bson::Document(point)) => {
+            let here = join(path, "near");
+            validate_geometry(point, &here)?;
+            if point.get_str("type").ok() != Some("Point") {
+                return Err(invalid(format!("{} must be a Point", here)));
+            }
+            Ok(())
+        }
+        Some(Bson::Array(pair)) => legacy_pair(pair, &join(path, "near")),
+        _ => Err(invalid(format!(
+            "{} requires near: a GeoJSON Point or a [x, y] pair",
+            path
+        ))),
+    }
+}
+
+/// Check a GeoJSON geometry object
+///
+/// # Arguments
+/// * `geometry` - Object with `type` and `coordinates` (or `geometries`)
+/// * `path` - Location of the object in the query, for error messages
+///
+/// # Returns
+/// * `Result<()>` - Error describing the first problem found
+pub(crate) fn validate_geometry(geometry: &Document, path: &str) -> Result<()> {
+    let kind = geometry.get_str("type").map_err(|_| {
+        invalid(format!(
+            "{}.type must be a GeoJSON type such as 'Point'",
+            path
+        ))
+    })?;
+
+    if kind == "GeometryCollection" {
+        let geometries = geometry
+            .get_array("geometries")
+            .map_err(|_| invalid(format!("{}.geometries must be an array", path)))?;
+        for (index, item) in geometries.iter().enumerate() {
+            let here = format!("{}.geometries[{}]", path, index);
+            let item = item
+                .as_document()
+                .ok_or_else(|| invalid(format!("{} must be a GeoJSON object", here)))?;
+            validate_geometry(item, &here)?;
+        }
+        return Ok(());
+    }
+
+    let coordinates = geometry
+        .get_array("coordinates")
+        .map_err(|_| invalid(format!("{}.coordinates must be an array", path)))?;
+    let here = join(path, "coordinates");
+    match kind {
+        "Point" => position(coordinates, &here),
+        "MultiPoint" => each(coordinates, &here, position),
+        "LineString" => line(coordinates, &here),
+        "MultiLineString" => each(coordinates, &here, line),
+        "Polygon" => polygon(coordinates, &here),
+        "MultiPolygon" => each(coordinates, &here, polygon),
+        other => Err(invalid(format!(
+            "{}.type '{}' is not a GeoJSON type; use Point, LineString, Polygon, MultiPoint, MultiLineString, MultiPolygon or GeometryCollection",
+            path, other
+        ))),
+    }
+}
+
+/// Check a `[longitude, latitude]` position
+fn position(items: &[Bson], path: &str) -> Result<()> {
+    let (lng, lat) = match items {
+        [lng, lat] | [lng, lat, _] => (number(lng), number(lat)),
+        _ => (None, None),
+    };
+    let (Some(lng), Some(lat)) = (lng, lat) else {
+        return Err(invalid(format!(
+            "{} must be [longitude, latitude] numbers",
+            path
+        )));
+    };
+    if !(-180.0..=180.0).contains(&lng) {
+        return Err(invalid(format!(
+            "{}: longitude {} is outside -180..180; GeoJSON lists longitude first",
+            path, lng
+        )));
+    }
+    if !(-90.0..=90.0).contains(&lat) {
+        return Err(invalid(format!(
+            "{}: latitude {} is outside -90..90; GeoJSON lists longitude first",
+            path, lat
+        )));
+    }
+    Ok(())
+}
+
+/// Check the positions of a LineString
+fn line(items: &[Bson], path: &str) -> Result<()> {
+    if items.len() < 2 {
+        return Err(invalid(format!("{} needs at least 2 positions", path)));
+    }
+    each(items, path, position)
+}
+
+/// Check the rings of a Polygon
+fn polygon(rings: &[Bson], path: &str) -> Result<()> {
+    if rings.is_empty() {
+        return Err(invalid(format!("{} needs at least one ring", path)));
+    }
+    each(rings, path, |ring, here| {
+        if ring.len() < 4 {
+            return Err(invalid(format!(
+                "{} needs at least 4 positions, the last repeating the first",
+                here
+            )));
+        }
+        each(ring, here, position)?;
+        if ring.first() != ring.last() {
+            return Err(invalid(format!(
+                "{} is not closed: the last position must equal the first",
+                here
+            )));
+        }
+        Ok(())
+    })
+}
+
+/// Apply a check to every array in `items`
+fn each(items: &[Bson], path: &str, check: impl Fn(&[Bson], &str) -> Result<()>) -> Result<()> {
+    for (index, item) in items.iter().enumerate() {
+        let here = format!("{}[{}]", path, index);
+        match item {
+            Bson::Array(inner) => check(inner, &here)?,
+            _ => return Err(invalid(format!("{} must be an array", here))),
+        }
+    }
+    Ok(())
+}
+
+/// Check a legacy `[x, y]` pair; 2d indexes allow any range
+fn legacy_pair(items: &[Bson], path: &str) -> Result<()> {
+    match items {
+        [x, y] if number(x).is_some() && number(y).is_some() => Ok(()),
+        _ => Err(invalid(format!(
+            "{} must be an [x, y] pair of numbers",
+            path
+        ))),
+    }
+}
+
+fn number(value: &Bson) -> Option<f64> {
+    match value {
+        Bson::Int32(n) => Some(*n as f64),
+        Bson::Int64(n) => Some(*n as f64),
+        Bson::Double(f) if f.is_finite() => Some(*f),
+        _ => None,
+    }
+}
+
+fn join(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
+
+fn invalid(message: impl Into<String>) -> crate::error::MongoshError {
+    ParseError::InvalidQuery(message.into()).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bson::doc;
+
+    fn find(filter: Document) -> Command {
+        Command::Query(QueryCommand::Find {
+            collection: "places".to_string(),
+            filter,
+            options: Default::default(),
+        })
+    }
+
+    #[test]
+    fn test_validate_near() {
+        let near = |coordinates: Bson| {
+            find(doc! {
+                "location": {
+                    "$near": {
+                        "$geometry": { "type": "Point", "coordinates": coordinates },
+                        "$maxDistance": 500,
+                    }
+                }
+            })
+        };
+        assert!(GeoOpsParser::validate(&near(bson::bson!([13.4, 52.5]))).is_ok());
+
+        let err = GeoOpsParser::validate(&near(bson::bson!([52.5, 113.4]))).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("location.$near.$geometry.coordinates")
+        );
+        assert!(err.to_string().contains("latitude 113.4"));
+
+        let legacy = find(doc! { "loc": { "$near": [5, 5] }, "name": "x" });
+        assert!(GeoOpsParser::validate(&legacy).is_ok());
+    }
+
+    #[test]
+    fn test_validate_within_polygon() {
+        let within = |ring: Bson| {
+            find(doc! {
+                "$and": [{
+                    "area": {
+                        "$geoWithin": {
+                            "$geometry": { "type": "Polygon", "coordinates": [ring] }
+                        }
+                    }
+                }]
+            })
+        };
+        let closed = bson::bson!([[0, 0], [3, 6], [6, 1], [0, 0]]);
+        assert!(GeoOpsParser::validate(&within(closed)).is_ok());
+
+        let open = bson::bson!([[0, 0], [3, 6], [6, 1], [1, 1]]);
+        let err = GeoOpsParser::validate(&within(open)).unwrap_err();
+        assert!(err.to_string().contains("$and[0].area.$geoWithin"));
+        assert!(err.to_string().contains("not closed"));
+
+        let sphere = find(doc! {
+            "loc": { "$geoWithin": { "$centerSphere": [[-73.9, 40.7], 0.001] } }
+        });
+        assert!(GeoOpsParser::validate(&sphere).is_ok());
+    }
+
+    #[test]
+    fn test_validate_geo_near_stage() {
+        let aggregate = |stage: Document| {
+            Command::Query(QueryCommand::Aggregate {
+                collection: "places".to_string(),
+                pipeline: vec![stage],
+                options: Default::default(),
+            })
+        };
+        let ok = doc! { "$geoNear": {
+            "near": { "type": "Point", "coordinates": [-73.99, 40.73] },
+            "distanceField": "dist",
+        }};
+        assert!(GeoOpsParser::validate(&aggregate(ok)).is_ok());
+
+        let missing = doc! { "$geoNear": { "near": [1, 2] } };
+        assert!(GeoOpsParser::validate(&aggregate(missing)).is_err());
+
+        let line = doc! { "$geoNear": {
+            "near": { "type": "LineString", "coordinates": [[1, 2], [3, 4]] },
+            "distanceField": "dist",
+        }};
+        assert!(GeoOpsParser::validate(&aggregate(line)).is_err());
+    }
+}
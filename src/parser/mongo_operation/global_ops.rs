@@ -0,0 +1,754 @@
+//! Global shell function parsers
+//!
+//! This module parses shell functions that are called on their own rather
+//! than on a collection, such as `Object.bsonsize({ ... })`, `printjson(...)`
+//! or `JSON.parse(...)`. Arguments are constant expressions, so the helpers
+//! are evaluated here and only their output is left to the executor. The
+//! value helpers (`printjson`, `tojson`, `JSON.stringify`, `Object.keys`)
+//! also take a query such as `db.users.findOne()`, which is parsed as a
+//! command and printed through the helper once it has run.
+
+use bson::{Bson, Document};
+
+use crate::error::{ParseError, Result};
+use crate::parser::command::{AdminCommand, Command, QueryCommand, UtilityCommand, ValueHelper};
+use crate::parser::mongo_ast::*;
+use crate::parser::mongo_converter::ExpressionConverter;
+use crate::parser::mongo_parser::MongoParser;
+
+use super::args::ArgParser;
+
+/// Global functions recognized at the start of an input line
+const GLOBAL_FUNCTIONS: &[&str] = &[
+    "Object.bsonsize",
+    "bsonsize",
+    "Object.keys",
+    "printjson",
+    "print",
+    "tojson",
+    "sleep",
+    "JSON.parse",
+    "JSON.stringify",
+    "load",
+    "parallel",
+    "bulkUpdateWithRateLimit",
+    "storageReport",
+    "clusterReport",
+    "rs.printOplogInfo",
+];
+
+/// Concurrency used by parallel() when none is given
+const DEFAULT_PARALLEL_CONCURRENCY: usize = 4;
+
+/// Documents per batch used by bulkUpdateWithRateLimit() when none is given
+const DEFAULT_BULK_BATCH_SIZE: u32 = 1000;
+
+/// Write rate used by bulkUpdateWithRateLimit() when none is given
+const DEFAULT_BULK_OPS_PER_SECOND: u32 = 1000;
+
+/// Global shell function parsers
+pub struct GlobalOpsParser;
+
+impl GlobalOpsParser {
+    /// Check whether input is a call to a known global shell function
+    pub fn is_global_call(input: &str) -> bool {
+        GLOBAL_FUNCTIONS.iter().any(|name| {
+            input
+                .strip_prefix(name)
+                .is_some_and(|rest| rest.trim_start().starts_with('('))
+        })
+    }
+
+    /// Parse a global shell function call
+    pub fn parse(input: &str) -> Result<Command> {
+        let call = match MongoParser::parse(input)? {
+            Expr::Call(call) => call,
+            _ => {
+                return Err(ParseError::InvalidCommand(
+                    "Expected a function call, e.g. Object.bsonsize({ a: 1 })".to_string(),
+                )
+                .into());
+            }
+        };
+
+        let name = Self::function_name(&call.callee).unwrap_or_default();
+        match name.as_str() {
+            "Object.bsonsize" | "bsonsize" => Self::parse_bson_size(&name, &call.arguments),
+            "Object.keys" => {
+                Self::parse_value_helper(input, ValueHelper::Keys, &name, &call.arguments)
+            }
+            "printjson" => {
+                Self::parse_value_helper(input, ValueHelper::PrintJson, &name, &call.arguments)
+            }
+            "print" => Self::parse_print(&call.arguments),
+            "tojson" => {
+                Self::parse_value_helper(input, ValueHelper::ToJson, &name, &call.arguments)
+            }
+            "JSON.stringify" => {
+                Self::parse_value_helper(input, ValueHelper::Stringify, &name, &call.arguments)
+            }
+            "JSON.parse" => Self::parse_json_parse(&call.arguments),
+            "sleep" => {
+                let millis = ArgParser::get_number_arg(&call.arguments, 0)?;
+                if call.arguments.len() != 1 || millis < 0 {
+                    return Err(ParseError::InvalidCommand(
+                        "sleep() expects a non-negative number of milliseconds".to_string(),
+                    )
+                    .into());
+                }
+                Ok(Command::Utility(UtilityCommand::Sleep(millis as u64)))
+            }
+            "load" => {
+                if call.arguments.len() != 1 {
+                    return Err(ParseError::InvalidCommand(
+                        "load() expects 1 file path argument".to_string(),
+                    )
+                    .into());
+                }
+                let path = ArgParser::get_string_arg(&call.arguments, 0)?;
+                Ok(Command::Load(path))
+            }
+            "parallel" => Self::parse_parallel(&call.arguments),
+            "bulkUpdateWithRateLimit" => Self::parse_bulk_update_with_rate_limit(&call.arguments),
+            "storageReport" => {
+                if !call.arguments.is_empty() {
+                    return Err(ParseError::InvalidCommand(
+                        "storageReport() takes no arguments".to_string(),
+                    )
+                    .into());
+                }
+                Ok(Command::Admin(AdminCommand::StorageReport))
+            }
+            "clusterReport" => {
+                if !call.arguments.is_empty() {
+                    return Err(ParseError::InvalidCommand(
+                        "clusterReport() takes no arguments".to_string(),
+                    )
+                    .into());
+                }
+                Ok(Command::Admin(AdminCommand::ClusterReport))
+            }
+            "rs.printOplogInfo" => {
+                if !call.arguments.is_empty() {
+                    return Err(ParseError::InvalidCommand(
+                        "rs.printOplogInfo() takes no arguments".to_string(),
+                    )
+                    .into());
+                }
+                Ok(Command::Admin(AdminCommand::OplogInfo))
+            }
+            _ => Err(ParseError::InvalidCommand(format!("Unknown function '{}'", name)).into()),
+        }
+    }
+
+    /// Parse Object.bsonsize(document)
+    fn parse_bson_size(name: &str, args: &[Expr]) -> Result<Command> {
+        if args.len() != 1 {
+            return Err(ParseError::InvalidCommand(format!(
+                "{}() expects 1 document argument, got {}",
+                name,
+                args.len()
+            ))
+            .into());
+        }
+
+        let document = ArgParser::get_doc_arg(args, 0)?;
+        Ok(Command::Utility(UtilityCommand::BsonSize(document)))
+    }
+
+    /// Parse a helper printing one value, e.g. printjson(value)
+    ///
+    /// A constant argument is printed right away; a query argument such as
+    /// `db.users.findOne()` becomes an [`UtilityCommand::Inspect`] that runs
+    /// the query and prints its result.
+    fn parse_value_helper(
+        input: &str,
+        helper: ValueHelper,
+        name: &str,
+        args: &[Expr],
+    ) -> Result<Command> {
+        if let [arg] = args
+            && let Some(source) = query_source(input, arg)
+        {
+            let command = crate::parser::Parser::new().parse(&source)?;
+            return Ok(Command::Utility(UtilityCommand::Inspect {
+                helper,
+                command: Box::new(command),
+                single: is_find_one(arg),
+            }));
+        }
+
+        let value = Self::get_value_arg(name, args)?;
+        Ok(Command::Utility(helper.apply(value)?))
+    }
+
+    /// Parse print(value, ...): strings as is, other values as JSON
+    fn parse_print(args: &[Expr]) -> Result<Command> {
+        let mut parts = Vec::with_capacity(args.len());
+        for arg in args {
+            match ExpressionConverter::expr_to_bson(arg)? {
+                Bson::String(s) => parts.push(s),
+                value => parts.push(to_json(&value, false)?),
+            }
+        }
+        Ok(Command::Utility(UtilityCommand::Print(parts.join(" "))))
+    }
+
+    /// Parse JSON.parse(text), accepting Extended JSON such as `{"$oid": ...}`
+    fn parse_json_parse(args: &[Expr]) -> Result<Command> {
+        if args.len() != 1 {
+            return Err(ParseError::InvalidCommand(
+                "JSON.parse() expects 1 string argument".to_string(),
+            )
+            .into());
+        }
+
+        let text = ArgParser::get_string_arg(args, 0)?;
+        let json: serde_json::Value = serde_json::from_str(&text)
+            .map_err(|e| ParseError::InvalidCommand(format!("JSON.parse(): {}", e)))?;
+        let value = Bson::try_from(json)
+            .map_err(|e| ParseError::InvalidCommand(format!("JSON.parse(): {}", e)))?;
+        Ok(Command::Utility(UtilityCommand::PrintJson(value)))
+    }
+
+    /// Parse parallel([() => db.a.op(...), ...], { concurrency: n })
+    ///
+    /// Each task is a function whose body is a single shell command.
+    fn parse_parallel(args: &[Expr]) -> Result<Command> {
+        let usage = || {
+            ParseError::InvalidCommand(
+                "parallel() expects an array of functions, e.g. parallel([() => db.a.find(), () => db.b.find()], { concurrency: 2 })"
+                    .to_string(),
+            )
+        };
+
+        let Some(Expr::Array(tasks)) = args.first() else {
+            return Err(usage().into());
+        };
+        if tasks.elements.is_empty() || args.len() > 2 {
+            return Err(usage().into());
+        }
+
+        let mut commands = Vec::with_capacity(tasks.elements.len());
+        for (i, task) in tasks.elements.iter().enumerate() {
+            let Expr::Function(source) = task else {
+                return Err(usage().into());
+            };
+            let body = function_body(source).ok_or_else(|| {
+                ParseError::InvalidCommand(format!(
+                    "parallel() task {} must return a single shell command",
+                    i + 1
+                ))
+            })?;
+            let command = crate::parser::Parser::new().parse(&body)?;
+            if matches!(
+                command,
+                Command::Exit
+                    | Command::Load(_)
+                    | Command::AiQuery(_)
+                    | Command::Parallel { .. }
+                    | Command::Watch { .. }
+                    | Command::Retry
+                    | Command::Record(_)
+                    | Command::Report(_)
+            ) {
+                return Err(ParseError::InvalidCommand(format!(
+                    "parallel() task {} cannot run '{}'",
+                    i + 1,
+                    body
+                ))
+                .into());
+            }
+            commands.push(command);
+        }
+
+        let concurrency = match args.get(1) {
+            None => DEFAULT_PARALLEL_CONCURRENCY,
+            Some(_) => {
+                let options = ArgParser::get_doc_arg(args, 1)?;
+                match options.get("concurrency") {
+                    None => DEFAULT_PARALLEL_CONCURRENCY,
+                    Some(value) => match value {
+                        Bson::Int32(n) if *n > 0 => *n as usize,
+                        Bson::Int64(n) if *n > 0 => *n as usize,
+                        _ => {
+                            return Err(ParseError::InvalidCommand(
+                                "parallel() concurrency must be a positive integer".to_string(),
+                            )
+                            .into());
+                        }
+                    },
+                }
+            }
+        };
+
+        Ok(Command::Parallel {
+            commands,
+            concurrency,
+        })
+    }
+
+    /// Parse bulkUpdateWithRateLimit(collection, filter, update, { batchSize, opsPerSecond })
+    ///
+    /// The collection is given by name or as `db.name`.
+    fn parse_bulk_update_with_rate_limit(args: &[Expr]) -> Result<Command> {
+        if !(3..=4).contains(&args.len()) {
+            return Err(ParseError::InvalidCommand(
+                "bulkUpdateWithRateLimit() expects (collection, filter, update, { batchSize, opsPerSecond })"
+                    .to_string(),
+            )
+            .into());
+        }
+
+        let collection = match &args[0] {
+            Expr::Member(member) if matches!(member.object.as_ref(), Expr::Ident(db) if db == "db") => {
+                match &member.property {
+                    MemberProperty::Ident(name) => name.clone(),
+                    _ => ArgParser::get_string_arg(args, 0)?,
+                }
+            }
+            _ => ArgParser::get_string_arg(args, 0)?,
+        };
+        let filter = ArgParser::get_doc_arg(args, 1)?;
+        let update = ArgParser::get_doc_arg(args, 2)?;
+
+        let options = match args.get(3) {
+            Some(_) => ArgParser::get_doc_arg(args, 3)?,
+            None => Document::new(),
+        };
+        let batch_size = positive_option(&options, "batchSize", DEFAULT_BULK_BATCH_SIZE)?;
+        let ops_per_second =
+            positive_option(&options, "opsPerSecond", DEFAULT_BULK_OPS_PER_SECOND)?;
+
+        Ok(Command::Query(QueryCommand::BulkUpdateWithRateLimit {
+            collection,
+            filter,
+            update,
+            batch_size,
+            ops_per_second,
+        }))
+    }
+
+    /// Evaluate the single value argument of a helper
+    fn get_value_arg(name: &str, args: &[Expr]) -> Result<Bson> {
+        if args.len() != 1 {
+            return Err(ParseError::InvalidCommand(format!(
+                "{}() expects 1 argument, got {}",
+                name,
+                args.len()
+            ))
+            .into());
+        }
+        ExpressionConverter::expr_to_bson(&args[0])
+    }
+
+    /// Resolve `name` or `object.name` callees to a dotted function name
+    fn function_name(callee: &Expr) -> Option<String> {
+        match callee {
+            Expr::Ident(name) => Some(name.clone()),
+            Expr::Member(member) => match (member.object.as_ref(), &member.property) {
+                (Expr::Ident(object), MemberProperty::Ident(property)) => {
+                    Some(format!("{}.{}", object, property))
+                }
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+impl ValueHelper {
+    /// Print a value through the helper
+    ///
+    /// # Arguments
+    /// * `value` - Value passed to the helper
+    ///
+    /// # Returns
+    /// * `Result<UtilityCommand>` - Command printing the helper's output
+    pub fn apply(self, value: Bson) -> Result<UtilityCommand> {
+        match self {
+            ValueHelper::PrintJson => Ok(UtilityCommand::PrintJson(value)),
+            ValueHelper::ToJson => Ok(UtilityCommand::Print(to_json(&value, true)?)),
+            ValueHelper::Stringify => Ok(UtilityCommand::Print(to_json(&value, false)?)),
+            ValueHelper::Keys => match value {
+                Bson::Document(document) => {
+                    let keys = document.keys().map(|k| Bson::String(k.clone())).collect();
+                    Ok(UtilityCommand::PrintJson(Bson::Array(keys)))
+                }
+                _ => Err(ParseError::InvalidCommand(
+                    "Object.keys() expects a document".to_string(),
+                )
+                .into()),
+            },
+        }
+    }
+}
+
+/// Source of an argument that reads from the database, e.g. `db.users.findOne()`
+///
+/// Spans count characters, not bytes, of the parsed input.
+fn query_source(input: &str, arg: &Expr) -> Option<String> {
+    let span = match arg {
+        Expr::Call(call) => &call.span,
+        Expr::Member(member) => &member.span,
+        _ => return None,
+    };
+
+    let mut root = arg;
+    loop {
+        root = match root {
+            Expr::Call(call) => &call.callee,
+            Expr::Member(member) => &member.object,
+            _ => break,
+        };
+    }
+    if !matches!(root, Expr::Ident(name) if name == "db") {
+        return None;
+    }
+
+    Some(input.chars().skip(span.start).take(span.len()).collect())
+}
+
+/// Whether an argument ends with a findOne() call, which yields one document
+fn is_find_one(arg: &Expr) -> bool {
+    match arg {
+        Expr::Call(call) => matches!(
+            call.callee.as_ref(),
+            Expr::Member(member)
+                if matches!(&member.property, MemberProperty::Ident(name) if name == "findOne")
+        ),
+        _ => false,
+    }
+}
+
+/// Read a positive integer option, falling back to a default when absent
+fn positive_option(options: &Document, key: &str, default: u32) -> Result<u32> {
+    let value = match options.get(key) {
+        None => return Ok(default),
+        Some(Bson::Int32(n)) => *n as i64,
+        Some(Bson::Int64(n)) => *n,
+        Some(Bson::Double(n)) if n.fract() == 0.0 => *n as i64,
+        Some(_) => -1,
+    };
+    u32::try_from(value).ok().filter(|n| *n > 0).ok_or_else(|| {
+        ParseError::InvalidCommand(format!("{} must be a positive integer", key)).into()
+    })
+}
+
+/// Extract the command a task function returns
+///
+/// Accepts `() => expr`, `() => { return expr; }` and
+/// `function() { return expr; }`. Returns `None` for an empty body.
+fn function_body(source: &str) -> Option<String> {
+    let body = match source.find("=>") {
+        Some(arrow) => source[arrow + 2..].trim(),
+        None => source[source.find('{')?..].trim(),
+    };
+
+    let body = match body.strip_prefix('{') {
+        Some(block) => {
+            let block = block.strip_suffix('}')?.trim();
+            block.strip_prefix("return").unwrap_or(block).trim()
+        }
+        None => body,
+    };
+
+    let body = body.trim_end_matches(';').trim();
+    (!body.is_empty()).then(|| body.to_string())
+}
+
+/// Serialize a value as relaxed Extended JSON
+fn to_json(value: &Bson, pretty: bool) -> Result<String> {
+    let json = value.clone().into_relaxed_extjson();
+    let text = if pretty {
+        serde_json::to_string_pretty(&json)
+    } else {
+        serde_json::to_string(&json)
+    };
+    text.map_err(|e| ParseError::InvalidCommand(format!("Cannot serialize value: {}", e)).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_global_call() {
+        assert!(GlobalOpsParser::is_global_call("Object.bsonsize({ a: 1 })"));
+        assert!(GlobalOpsParser::is_global_call("bsonsize ({})"));
+        assert!(!GlobalOpsParser::is_global_call("bsonsizes({})"));
+        assert!(!GlobalOpsParser::is_global_call("db.users.find()"));
+    }
+
+    #[test]
+    fn test_parse_bson_size() {
+        let cmd = GlobalOpsParser::parse("Object.bsonsize({ name: 'Alice', age: 30 })").unwrap();
+        match cmd {
+            Command::Utility(UtilityCommand::BsonSize(doc)) => {
+                assert_eq!(doc.get_str("name").unwrap(), "Alice");
+            }
+            _ => panic!("Expected BsonSize command"),
+        }
+
+        assert!(GlobalOpsParser::parse("Object.bsonsize()").is_err());
+        assert!(GlobalOpsParser::parse("Object.bsonsize(42)").is_err());
+    }
+
+    #[test]
+    fn test_parse_storage_report() {
+        assert!(GlobalOpsParser::is_global_call("storageReport()"));
+        assert_eq!(
+            GlobalOpsParser::parse("storageReport()").unwrap(),
+            Command::Admin(AdminCommand::StorageReport)
+        );
+        assert!(GlobalOpsParser::parse("storageReport('shop')").is_err());
+    }
+
+    #[test]
+    fn test_parse_cluster_report() {
+        assert!(GlobalOpsParser::is_global_call("clusterReport()"));
+        assert_eq!(
+            GlobalOpsParser::parse("clusterReport()").unwrap(),
+            Command::Admin(AdminCommand::ClusterReport)
+        );
+        assert!(GlobalOpsParser::parse("clusterReport(true)").is_err());
+    }
+
+    #[test]
+    fn test_parse_print_oplog_info() {
+        assert!(GlobalOpsParser::is_global_call("rs.printOplogInfo()"));
+        assert_eq!(
+            GlobalOpsParser::parse("rs.printOplogInfo()").unwrap(),
+            Command::Admin(AdminCommand::OplogInfo)
+        );
+        assert!(GlobalOpsParser::parse("rs.printOplogInfo(1)").is_err());
+    }
+
+    #[test]
+    fn test_is_global_call_helpers() {
+        assert!(GlobalOpsParser::is_global_call("printjson({ a: 1 })"));
+        assert!(GlobalOpsParser::is_global_call("print('hi')"));
+        assert!(GlobalOpsParser::is_global_call("JSON.parse('{}')"));
+        assert!(GlobalOpsParser::is_global_call("sleep(100)"));
+        assert!(!GlobalOpsParser::is_global_call("printer()"));
+    }
+
+    #[test]
+    fn test_parse_json_helpers() {
+        let cmd = GlobalOpsParser::parse("tojson({ n: 1 })").unwrap();
+        assert_eq!(
+            cmd,
+            Command::Utility(UtilityCommand::Print("{\n  \"n\": 1\n}".to_string()))
+        );
+
+        let cmd = GlobalOpsParser::parse("JSON.stringify({ a: [1, 'x'] })").unwrap();
+        assert_eq!(
+            cmd,
+            Command::Utility(UtilityCommand::Print("{\"a\":[1,\"x\"]}".to_string()))
+        );
+
+        let cmd = GlobalOpsParser::parse(r#"JSON.parse('{"id": {"$oid": "507f1f77bcf86cd799439011"}}')"#)
+            .unwrap();
+        match cmd {
+            Command::Utility(UtilityCommand::PrintJson(Bson::Document(doc))) => {
+                assert!(doc.get_object_id("id").is_ok());
+            }
+            other => panic!("Expected PrintJson document, got {:?}", other),
+        }
+
+        assert!(GlobalOpsParser::parse("JSON.parse('{bad')").is_err());
+    }
+
+    #[test]
+    fn test_parse_object_keys_print_and_sleep() {
+        let cmd = GlobalOpsParser::parse("Object.keys({ b: 1, a: 2 })").unwrap();
+        assert_eq!(
+            cmd,
+            Command::Utility(UtilityCommand::PrintJson(Bson::Array(vec![
+                Bson::String("b".to_string()),
+                Bson::String("a".to_string()),
+            ])))
+        );
+
+        assert!(GlobalOpsParser::parse("Object.keys(42)").is_err());
+
+        let cmd = GlobalOpsParser::parse("print('total:', 1 + 2, { ok: true })").unwrap();
+        assert_eq!(
+            cmd,
+            Command::Utility(UtilityCommand::Print("total: 3 {\"ok\":true}".to_string()))
+        );
+
+        let cmd = GlobalOpsParser::parse("sleep(250)").unwrap();
+        assert_eq!(cmd, Command::Utility(UtilityCommand::Sleep(250)));
+        assert!(GlobalOpsParser::parse("sleep(-1)").is_err());
+    }
+
+    #[test]
+    fn test_parse_load() {
+        assert!(GlobalOpsParser::is_global_call("load('setup.js')"));
+        assert!(!GlobalOpsParser::is_global_call("loader('setup.js')"));
+        assert_eq!(
+            GlobalOpsParser::parse("load(\"scripts/setup.js\")").unwrap(),
+            Command::Load("scripts/setup.js".to_string())
+        );
+        assert!(GlobalOpsParser::parse("load()").is_err());
+        assert!(GlobalOpsParser::parse("load(42)").is_err());
+    }
+
+    #[test]
+    fn test_function_body() {
+        assert_eq!(
+            function_body("() => db.users.find()").as_deref(),
+            Some("db.users.find()")
+        );
+        assert_eq!(
+            function_body("() => { return db.a.deleteMany({}); }").as_deref(),
+            Some("db.a.deleteMany({})")
+        );
+        assert_eq!(
+            function_body("function() { return db.a.countDocuments() }").as_deref(),
+            Some("db.a.countDocuments()")
+        );
+        assert_eq!(function_body("() => {}"), None);
+    }
+
+    #[test]
+    fn test_parse_parallel() {
+        let cmd = GlobalOpsParser::parse(
+            "parallel([() => db.a.updateMany({}, { $set: { x: 1 } }), function() { return db.b.countDocuments() }], { concurrency: 2 })",
+        )
+        .unwrap();
+        match cmd {
+            Command::Parallel {
+                commands,
+                concurrency,
+            } => {
+                assert_eq!(commands.len(), 2);
+                assert_eq!(concurrency, 2);
+                assert!(matches!(commands[0], Command::Query(_)));
+            }
+            other => panic!("Expected Parallel command, got {:?}", other),
+        }
+
+        let cmd = GlobalOpsParser::parse("parallel([() => db.a.find()])").unwrap();
+        assert!(matches!(
+            cmd,
+            Command::Parallel {
+                concurrency: DEFAULT_PARALLEL_CONCURRENCY,
+                ..
+            }
+        ));
+
+        assert!(GlobalOpsParser::parse("parallel([])").is_err());
+        assert!(GlobalOpsParser::parse("parallel(['db.a.find()'])").is_err());
+        assert!(GlobalOpsParser::parse("parallel([() => exit])").is_err());
+        assert!(
+            GlobalOpsParser::parse("parallel([() => db.a.find()], { concurrency: 0 })").is_err()
+        );
+    }
+    #[test]
+    fn test_parse_bulk_update_with_rate_limit() {
+        let cmd = GlobalOpsParser::parse(
+            "bulkUpdateWithRateLimit('users', { active: false }, { $set: { archived: true } }, { batchSize: 500, opsPerSecond: 200 })",
+        )
+        .unwrap();
+        assert_eq!(
+            cmd,
+            Command::Query(QueryCommand::BulkUpdateWithRateLimit {
+                collection: "users".to_string(),
+                filter: bson::doc! { "active": false },
+                update: bson::doc! { "$set": { "archived": true } },
+                batch_size: 500,
+                ops_per_second: 200,
+            })
+        );
+
+        let cmd =
+            GlobalOpsParser::parse("bulkUpdateWithRateLimit(db.users, {}, { $inc: { n: 1 } })")
+                .unwrap();
+        match cmd {
+            Command::Query(QueryCommand::BulkUpdateWithRateLimit {
+                collection,
+                batch_size,
+                ops_per_second,
+                ..
+            }) => {
+                assert_eq!(collection, "users");
+                assert_eq!(batch_size, DEFAULT_BULK_BATCH_SIZE);
+                assert_eq!(ops_per_second, DEFAULT_BULK_OPS_PER_SECOND);
+            }
+            other => panic!("Expected BulkUpdateWithRateLimit, got {:?}", other),
+        }
+
+        assert!(GlobalOpsParser::parse("bulkUpdateWithRateLimit('users', {})").is_err());
+        assert!(
+            GlobalOpsParser::parse(
+                "bulkUpdateWithRateLimit('users', {}, { $set: { a: 1 } }, { batchSize: 0 })"
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_parse_value_helper_of_query() {
+        let cmd = GlobalOpsParser::parse("printjson(db.users.findOne({ name: 'ä' }))").unwrap();
+        match cmd {
+            Command::Utility(UtilityCommand::Inspect {
+                helper,
+                command,
+                single,
+            }) => {
+                assert_eq!(helper, ValueHelper::PrintJson);
+                assert!(single);
+                match *command {
+                    Command::Query(QueryCommand::Find {
+                        collection, filter, ..
+                    }) => {
+                        assert_eq!(collection, "users");
+                        assert_eq!(filter, bson::doc! { "name": "ä" });
+                    }
+                    other => panic!("Expected Find, got {:?}", other),
+                }
+            }
+            other => panic!("Expected Inspect, got {:?}", other),
+        }
+
+        let cmd = GlobalOpsParser::parse("Object.keys(db.getSiblingDB('shop').orders.findOne())")
+            .unwrap();
+        match cmd {
+            Command::Utility(UtilityCommand::Inspect {
+                helper,
+                command,
+                single,
+            }) => {
+                assert_eq!(helper, ValueHelper::Keys);
+                assert!(single);
+                assert!(matches!(*command, Command::WithDatabase { .. }));
+            }
+            other => panic!("Expected Inspect, got {:?}", other),
+        }
+
+        let cmd = GlobalOpsParser::parse("tojson(db.users.find().limit(2))").unwrap();
+        assert!(matches!(
+            cmd,
+            Command::Utility(UtilityCommand::Inspect { single: false, .. })
+        ));
+
+        assert!(GlobalOpsParser::parse("tojson(db.users.nosuch())").is_err());
+        assert!(GlobalOpsParser::parse("tojson(db.users.find(), 1)").is_err());
+    }
+
+    #[test]
+    fn test_value_helper_apply() {
+        let value = Bson::Document(bson::doc! { "a": 1 });
+        assert_eq!(
+            ValueHelper::Stringify.apply(value.clone()).unwrap(),
+            UtilityCommand::Print("{\"a\":1}".to_string())
+        );
+        assert_eq!(
+            ValueHelper::Keys.apply(value).unwrap(),
+            UtilityCommand::PrintJson(Bson::Array(vec![Bson::String("a".to_string())]))
+        );
+        assert!(ValueHelper::Keys.apply(Bson::Int32(1)).is_err());
+    }
+}
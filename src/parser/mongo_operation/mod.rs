@@ -10,8 +10,12 @@
 mod admin_ops;
 mod args;
 mod chain;
+mod database_ops;
+mod geo_ops;
+mod global_ops;
 mod options;
 mod query_ops;
+mod search_ops;
 
 use crate::error::{ParseError, Result};
 use crate::parser::command::Command;
@@ -22,8 +26,15 @@ use chain::ChainParseResult;
 use admin_ops::AdminOpsParser;
 use args::ArgParser;
 use chain::ChainHandler;
+use database_ops::DatabaseOpsParser;
 use query_ops::QueryOpsParser;
 
+pub use global_ops::GlobalOpsParser;
+pub(crate) use options::OptionsParser;
+#[cfg(feature = "native")]
+pub(crate) use search_ops::MAX_NUM_CANDIDATES;
+use search_ops::SearchOpsParser;
+
 /// Parser for database operations
 pub struct DbOperationParser;
 
@@ -31,6 +42,16 @@ impl DbOperationParser {
     /// Parse a database operation from input
     pub fn parse(input: &str) -> Result<Command> {
         let expr = MongoParser::parse(input)?;
+
+        // db.getSiblingDB('other')... runs against another database
+        if let Some((database, expr)) = DatabaseOpsParser::resolve_sibling_db(&expr)? {
+            let command = Self::parse_expression(&expr)?;
+            return Ok(Command::WithDatabase {
+                database,
+                command: Box::new(command),
+            });
+        }
+
         Self::parse_expression(&expr)
     }
 
@@ -58,6 +79,11 @@ impl DbOperationParser {
             }
         }
 
+        // Database-level operation: db.operation(...)
+        if let Some(method) = DatabaseOpsParser::database_method(call.callee.as_ref()) {
+            return DatabaseOpsParser::parse(method, &call.arguments);
+        }
+
         // Not a chained call, parse as regular db.collection.operation()
         let (collection, operation) = ArgParser::extract_db_call_target(call.callee.as_ref())?;
         let args = &call.arguments;
@@ -83,15 +109,26 @@ impl DbOperationParser {
             "findOneAndReplace" => QueryOpsParser::parse_find_one_and_replace(&collection, args),
             "findAndModify" => QueryOpsParser::parse_find_and_modify(&collection, args),
             "distinct" => QueryOpsParser::parse_distinct(&collection, args),
+            "docSize" => QueryOpsParser::parse_doc_size(&collection, args),
+            "diff" => QueryOpsParser::parse_diff(&collection, args),
+            "asOf" => QueryOpsParser::parse_as_of(&collection, args),
+            "largestDocuments" => QueryOpsParser::parse_largest_documents(&collection, args),
             "bulkWrite" => QueryOpsParser::parse_bulk_write(&collection, args),
+            "search" => SearchOpsParser::parse_search(&collection, args),
+            "vectorSearch" => SearchOpsParser::parse_vector_search(&collection, args),
             "getIndexes" => AdminOpsParser::parse_get_indexes(&collection),
+            "indexStats" => AdminOpsParser::parse_index_stats(&collection, args),
             "createIndex" => AdminOpsParser::parse_create_index(&collection, args),
             "createIndexes" => AdminOpsParser::parse_create_indexes(&collection, args),
             "dropIndex" => AdminOpsParser::parse_drop_index(&collection, args),
-            "dropIndexes" => AdminOpsParser::parse_drop_indexes(&collection),
-            "drop" => AdminOpsParser::parse_drop_collection(&collection),
+            "dropIndexes" => AdminOpsParser::parse_drop_indexes(&collection, args),
+            "drop" => AdminOpsParser::parse_drop_collection(&collection, args),
             "renameCollection" => AdminOpsParser::parse_rename_collection(&collection, args),
             "stats" => AdminOpsParser::parse_collection_stats(&collection, args),
+            "convertToCapped" => AdminOpsParser::parse_convert_to_capped(&collection, args),
+            "getValidator" => AdminOpsParser::parse_get_validator(&collection),
+            "setValidator" => AdminOpsParser::parse_set_validator(&collection, args),
+            "validate" => AdminOpsParser::parse_validate(&collection, args),
             _ => Err(
                 ParseError::InvalidCommand(format!("Unknown operation '{}'", operation)).into(),
             ),
@@ -110,6 +147,12 @@ pub(crate) fn parse_call_expression_simple(call: &CallExpr) -> Result<Command> {
         "aggregate" => QueryOpsParser::parse_aggregate(&collection, args),
         "count" | "countDocuments" => QueryOpsParser::parse_count_documents(&collection, args),
         "distinct" => QueryOpsParser::parse_distinct(&collection, args),
+        "docSize" => QueryOpsParser::parse_doc_size(&collection, args),
+        "diff" => QueryOpsParser::parse_diff(&collection, args),
+        "asOf" => QueryOpsParser::parse_as_of(&collection, args),
+        "largestDocuments" => QueryOpsParser::parse_largest_documents(&collection, args),
+        "search" => SearchOpsParser::parse_search(&collection, args),
+        "vectorSearch" => SearchOpsParser::parse_vector_search(&collection, args),
         _ => Err(ParseError::InvalidCommand(format!(
             "Operation '{}' cannot be chained",
             operation
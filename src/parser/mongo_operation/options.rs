@@ -1,7 +1,10 @@
 //! Options parsing for MongoDB operations
 
 use crate::error::{ParseError, Result};
-use crate::parser::command::{AggregateOptions, FindAndModifyOptions, UpdateOptions};
+use crate::parser::command::{
+    AggregateOptions, ExportCompression, ExportFormat, ExportOptions, FindAndModifyOptions,
+    FindOptions, UpdateOptions,
+};
 use crate::parser::mongo_ast::*;
 use crate::parser::mongo_converter::ExpressionConverter;
 
@@ -49,6 +52,51 @@ impl OptionsParser {
         Ok(options)
     }
 
+    /// Parse find options from expression: { sort, skip, limit, collation, ... }
+    pub fn parse_find_options(expr: &Expr, mut options: FindOptions) -> Result<FindOptions> {
+        let doc = if let Expr::Object(obj) = expr {
+            ExpressionConverter::object_to_bson(obj)?
+        } else {
+            return Err(ParseError::InvalidQuery("Options must be an object".to_string()).into());
+        };
+
+        if let Ok(projection) = doc.get_document("projection") {
+            options.projection = Some(projection.clone());
+        }
+
+        if let Ok(sort) = doc.get_document("sort") {
+            options.sort = Some(sort.clone());
+        }
+
+        if let Ok(hint) = doc.get_document("hint") {
+            options.hint = Some(hint.clone());
+        }
+
+        if let Ok(collation) = doc.get_document("collation") {
+            options.collation = Some(collation.clone());
+        }
+
+        if let Ok(limit) = doc.get_i64("limit") {
+            options.limit = Some(limit);
+        } else if let Ok(limit) = doc.get_i32("limit") {
+            options.limit = Some(limit as i64);
+        }
+
+        if let Ok(skip) = doc.get_i64("skip") {
+            options.skip = Some(skip.max(0) as u64);
+        } else if let Ok(skip) = doc.get_i32("skip") {
+            options.skip = Some(skip.max(0) as u64);
+        }
+
+        if let Ok(max_time_ms) = doc.get_i64("maxTimeMS") {
+            options.max_time_ms = Some(max_time_ms as u64);
+        } else if let Ok(max_time_ms) = doc.get_i32("maxTimeMS") {
+            options.max_time_ms = Some(max_time_ms as u64);
+        }
+
+        Ok(options)
+    }
+
     /// Parse update options from expression
     pub fn parse_update_options(expr: &Expr) -> Result<UpdateOptions> {
         let doc = if let Expr::Object(obj) = expr {
@@ -66,7 +114,7 @@ impl OptionsParser {
         if let Ok(array_filters) = doc.get_array("arrayFilters") {
             let mut filters = Vec::new();
             for filter in array_filters {
-                if let mongodb::bson::Bson::Document(doc) = filter {
+                if let bson::Bson::Document(doc) = filter {
                     filters.push(doc.clone());
                 }
             }
@@ -116,4 +164,50 @@ impl OptionsParser {
 
         Ok(options)
     }
+
+    /// Parse export options: `{ format: "csv", compression: "gzip" }`
+    pub fn parse_export_options(expr: &Expr) -> Result<ExportOptions> {
+        let doc = if let Expr::Object(obj) = expr {
+            ExpressionConverter::object_to_bson(obj)?
+        } else {
+            return Err(
+                ParseError::InvalidQuery("Export options must be an object".to_string()).into(),
+            );
+        };
+
+        let mut options = ExportOptions::default();
+        for (key, value) in &doc {
+            let name = value.as_str().ok_or_else(|| {
+                ParseError::InvalidQuery(format!("Export option {} must be a string", key))
+            })?;
+            match key.as_str() {
+                "format" => {
+                    options.format = Some(ExportFormat::from_name(name).ok_or_else(|| {
+                        ParseError::InvalidQuery(format!(
+                            "Unknown export format: {}. Use jsonl, csv, xlsx or html",
+                            name
+                        ))
+                    })?);
+                }
+                "compression" => {
+                    options.compression =
+                        Some(ExportCompression::from_name(name).ok_or_else(|| {
+                            ParseError::InvalidQuery(format!(
+                                "Unknown compression: {}. Use gzip or zstd",
+                                name
+                            ))
+                        })?);
+                }
+                other => {
+                    return Err(ParseError::InvalidQuery(format!(
+                        "Unknown export option: {}",
+                        other
+                    ))
+                    .into());
+                }
+            }
+        }
+
+        Ok(options)
+    }
 }
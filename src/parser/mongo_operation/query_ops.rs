@@ -8,14 +8,17 @@
 //! - aggregate, count, distinct
 //! - findAndModify and its variants
 
-use mongodb::bson::Document;
+use bson::Document;
 
 use crate::error::{ParseError, Result};
-use crate::parser::command::{Command, ExplainVerbosity, FindOptions, QueryCommand};
+use crate::parser::command::{Command, DiffOptions, ExplainVerbosity, FindOptions, QueryCommand};
 use crate::parser::mongo_ast::*;
 
 use super::args::ArgParser;
 
+/// Number of documents reported by docSize() when no limit is given
+const DEFAULT_DOC_SIZE_LIMIT: i64 = 10;
+
 /// Query operation parsers
 pub struct QueryOpsParser;
 
@@ -57,18 +60,23 @@ impl QueryOpsParser {
         .into())
     }
 
-    /// Parse find operation: db.collection.find(filter, projection)
+    /// Parse find operation: db.collection.find(filter, projection, options)
     pub fn parse_find(collection: &str, args: &[Expr]) -> Result<Command> {
         let filter = ArgParser::get_doc_arg(args, 0)?;
         let projection = ArgParser::get_projection(args, 1)?;
+        let options = ArgParser::get_find_options(
+            args,
+            2,
+            FindOptions {
+                projection,
+                ..Default::default()
+            },
+        )?;
 
         Ok(Command::Query(QueryCommand::Find {
             collection: collection.to_string(),
             filter,
-            options: FindOptions {
-                projection,
-                ..Default::default()
-            },
+            options,
         }))
     }
 
@@ -299,7 +307,7 @@ impl QueryOpsParser {
         }))
     }
 
-    /// Parse distinct operation
+    /// Parse distinct operation: db.collection.distinct(field, filter, options)
     pub fn parse_distinct(collection: &str, args: &[Expr]) -> Result<Command> {
         let field = ArgParser::get_string_arg(args, 0)?;
         let filter = if args.len() > 1 {
@@ -307,11 +315,131 @@ impl QueryOpsParser {
         } else {
             None
         };
+        let collation = ArgParser::get_doc_arg(args, 2)?
+            .get_document("collation")
+            .ok()
+            .cloned();
 
         Ok(Command::Query(QueryCommand::Distinct {
             collection: collection.to_string(),
             field,
             filter,
+            collation,
+        }))
+    }
+
+    /// Parse asOf operation: db.collection.asOf(timestamp, filter, projection)
+    ///
+    /// Shorthand for `find(filter, projection).asOf(timestamp)`.
+    pub fn parse_as_of(collection: &str, args: &[Expr]) -> Result<Command> {
+        if args.is_empty() || args.len() > 3 {
+            return Err(ParseError::InvalidCommand(format!(
+                "asOf() expects a timestamp, an optional filter and an optional projection, got {} arguments",
+                args.len()
+            ))
+            .into());
+        }
+
+        Ok(Command::Query(QueryCommand::Find {
+            collection: collection.to_string(),
+            filter: ArgParser::get_doc_arg(args, 1)?,
+            options: FindOptions {
+                projection: ArgParser::get_projection(args, 2)?,
+                as_of: Some(ArgParser::get_timestamp_arg(args, 0)?),
+                ..Default::default()
+            },
+        }))
+    }
+
+    /// Parse diff operation: db.collection.diff(other, { filter, db, datasource, out })
+    pub fn parse_diff(collection: &str, args: &[Expr]) -> Result<Command> {
+        let other = ArgParser::get_string_arg(args, 0).map_err(|_| {
+            ParseError::InvalidQuery(
+                "diff() expects the name of the collection to compare with".to_string(),
+            )
+        })?;
+
+        let mut options = DiffOptions::default();
+        for (key, value) in ArgParser::get_doc_arg(args, 1)? {
+            let text = || {
+                value.as_str().map(String::from).ok_or_else(|| {
+                    ParseError::InvalidQuery(format!("diff() option '{}' must be a string", key))
+                })
+            };
+            match key.as_str() {
+                "filter" => {
+                    options.filter = value.as_document().cloned().ok_or_else(|| {
+                        ParseError::InvalidQuery("diff() filter must be a document".to_string())
+                    })?;
+                }
+                "db" => options.database = Some(text()?),
+                "datasource" => options.datasource = Some(text()?),
+                "out" => options.output = Some(text()?),
+                _ => {
+                    return Err(ParseError::InvalidQuery(format!(
+                        "Unknown diff() option '{}'. Expected filter, db, datasource or out",
+                        key
+                    ))
+                    .into());
+                }
+            }
+        }
+
+        if options.database.is_none() && options.datasource.is_none() && other == collection {
+            return Err(ParseError::InvalidQuery(
+                "diff() cannot compare a collection with itself".to_string(),
+            )
+            .into());
+        }
+
+        Ok(Command::Query(QueryCommand::Diff {
+            collection: collection.to_string(),
+            other,
+            options,
+        }))
+    }
+
+    /// Parse docSize operation: db.collection.docSize(filter, { limit })
+    pub fn parse_doc_size(collection: &str, args: &[Expr]) -> Result<Command> {
+        let filter = ArgParser::get_doc_arg(args, 0)?;
+        let options = ArgParser::get_doc_arg(args, 1)?;
+        let limit = match options.get("limit") {
+            None => DEFAULT_DOC_SIZE_LIMIT,
+            Some(bson::Bson::Int32(n)) if *n > 0 => *n as i64,
+            Some(bson::Bson::Int64(n)) if *n > 0 => *n,
+            Some(_) => {
+                return Err(ParseError::InvalidQuery(
+                    "docSize() limit must be a positive integer".to_string(),
+                )
+                .into());
+            }
+        };
+
+        Ok(Command::Query(QueryCommand::DocSize {
+            collection: collection.to_string(),
+            filter,
+            limit,
+        }))
+    }
+
+    /// Parse largestDocuments operation: db.collection.largestDocuments(n)
+    pub fn parse_largest_documents(collection: &str, args: &[Expr]) -> Result<Command> {
+        let limit = if args.is_empty() {
+            DEFAULT_DOC_SIZE_LIMIT
+        } else {
+            ArgParser::get_number_arg(args, 0)?
+        };
+        if limit <= 0 {
+            return Err(ParseError::InvalidQuery(
+                "largestDocuments() count must be positive".to_string(),
+            )
+            .into());
+        }
+
+        Ok(Command::Query(QueryCommand::DocSize {
+            collection: collection.to_string(),
+            filter: Document::new(),
+            limit,
         }))
     }
 
@@ -451,9 +579,9 @@ mod tests {
             let sort_doc = options.sort.unwrap();
             let purchase_date_val = sort_doc.get("purchase_date").unwrap();
             let val = match purchase_date_val {
-                mongodb::bson::Bson::Int32(v) => *v as f64,
-                mongodb::bson::Bson::Int64(v) => *v as f64,
-                mongodb::bson::Bson::Double(v) => *v,
+                bson::Bson::Int32(v) => *v as f64,
+                bson::Bson::Int64(v) => *v as f64,
+                bson::Bson::Double(v) => *v,
                 other => panic!("unexpected BSON type for purchase_date: {:?}", other),
             };
             assert_eq!(val, -1.0);
@@ -591,6 +719,39 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_parse_find_with_options_collation() {
+        let result = DbOperationParser::parse(
+            "db.users.find({ name: 'bob' }, { name: 1 }, { collation: { locale: 'en', strength: 2 }, limit: 5 })",
+        )
+        .unwrap();
+        match result {
+            Command::Query(QueryCommand::Find { options, .. }) => {
+                assert!(options.projection.is_some());
+                assert_eq!(options.limit, Some(5));
+                assert_eq!(
+                    options.collation.unwrap().get_str("locale").unwrap(),
+                    "en"
+                );
+            }
+            _ => panic!("Expected Find command"),
+        }
+    }
+
+    #[test]
+    fn test_parse_distinct_with_collation_option() {
+        let result = DbOperationParser::parse(
+            "db.users.distinct('city', {}, { collation: { locale: 'de' } })",
+        )
+        .unwrap();
+        match result {
+            Command::Query(QueryCommand::Distinct { collation, .. }) => {
+                assert_eq!(collation.unwrap().get_str("locale").unwrap(), "de");
+            }
+            _ => panic!("Expected Distinct command"),
+        }
+    }
+
     #[test]
     fn test_parse_distinct_with_filter() {
         let result = DbOperationParser::parse("db.users.distinct('city', { age: { $gte: 18 } })");
@@ -673,4 +834,104 @@ mod tests {
             assert!(upsert);
         }
     }
+
+    #[test]
+    fn test_parse_diff() {
+        let cmd = DbOperationParser::parse(
+            "db.orders.diff('orders', { db: 'shop_v2', filter: { status: 'paid' }, out: 'diff.jsonl' })",
+        )
+        .unwrap();
+        match cmd {
+            Command::Query(QueryCommand::Diff {
+                collection,
+                other,
+                options,
+            }) => {
+                assert_eq!(collection, "orders");
+                assert_eq!(other, "orders");
+                assert_eq!(options.database.as_deref(), Some("shop_v2"));
+                assert_eq!(options.filter, bson::doc! { "status": "paid" });
+                assert_eq!(options.output.as_deref(), Some("diff.jsonl"));
+                assert!(options.datasource.is_none());
+            }
+            _ => panic!("Expected Diff command"),
+        }
+
+        assert!(DbOperationParser::parse("db.orders.diff('orders_backup')").is_ok());
+        assert!(DbOperationParser::parse("db.orders.diff('orders')").is_err());
+        assert!(DbOperationParser::parse("db.orders.diff()").is_err());
+        assert!(DbOperationParser::parse("db.orders.diff('b', { limit: 1 })").is_err());
+    }
+
+    #[test]
+    fn test_parse_as_of() {
+        let at = bson::Timestamp {
+            time: 1700000000,
+            increment: 0,
+        };
+        let cmd = DbOperationParser::parse(
+            "db.orders.asOf(1700000000, { status: 'paid' }, { total: 1 })",
+        )
+        .unwrap();
+        match cmd {
+            Command::Query(QueryCommand::Find {
+                collection,
+                filter,
+                options,
+            }) => {
+                assert_eq!(collection, "orders");
+                assert_eq!(filter.get_str("status").unwrap(), "paid");
+                assert_eq!(options.as_of, Some(at));
+                assert!(options.projection.is_some());
+            }
+            _ => panic!("Expected Find command"),
+        }
+
+        let cmd =
+            DbOperationParser::parse("db.orders.asOf(ISODate('2023-11-14T22:13:20Z'))").unwrap();
+        assert!(matches!(
+            cmd,
+            Command::Query(QueryCommand::Find { ref options, .. }) if options.as_of == Some(at)
+        ));
+
+        assert!(DbOperationParser::parse("db.orders.asOf()").is_err());
+        assert!(DbOperationParser::parse("db.orders.asOf('yesterday')").is_err());
+    }
+
+    #[test]
+    fn test_parse_doc_size() {
+        let cmd = DbOperationParser::parse("db.logs.docSize({ level: 'error' })").unwrap();
+        match cmd {
+            Command::Query(QueryCommand::DocSize {
+                collection,
+                filter,
+                limit,
+            }) => {
+                assert_eq!(collection, "logs");
+                assert_eq!(filter.get_str("level").unwrap(), "error");
+                assert_eq!(limit, DEFAULT_DOC_SIZE_LIMIT);
+            }
+            _ => panic!("Expected DocSize command"),
+        }
+
+        let cmd = DbOperationParser::parse("db.logs.docSize({}, { limit: 3 })").unwrap();
+        assert!(matches!(cmd, Command::Query(QueryCommand::DocSize { limit: 3, .. })));
+
+        let cmd = DbOperationParser::parse("db.logs.docSize({}).limit(5)").unwrap();
+        assert!(matches!(cmd, Command::Query(QueryCommand::DocSize { limit: 5, .. })));
+    }
+
+    #[test]
+    fn test_parse_largest_documents() {
+        let cmd = DbOperationParser::parse("db.logs.largestDocuments(20)").unwrap();
+        match cmd {
+            Command::Query(QueryCommand::DocSize { filter, limit, .. }) => {
+                assert!(filter.is_empty());
+                assert_eq!(limit, 20);
+            }
+            _ => panic!("Expected DocSize command"),
+        }
+
+        assert!(DbOperationParser::parse("db.logs.largestDocuments(0)").is_err());
+    }
 }
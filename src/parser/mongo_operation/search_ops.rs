@@ -0,0 +1,504 @@
+//! Atlas Search helper parsers
+//!
+//! This module turns `db.collection.search(spec, options)` into a validated
+//! `$search` query. The spec is the body of the `$search` stage, e.g.
+//! `{ text: { query: 'coffee', path: 'title' }, highlight: { path: 'title' } }`.
+//!
+//! `db.collection.vectorSearch({ queryVector, path, numCandidates, limit, filter })`
+//! is parsed into a `$vectorSearch` stage body in the same way.
+
+use bson::{Bson, Document};
+
+use crate::error::{ParseError, Result};
+use crate::parser::command::{Command, QueryCommand};
+use crate::parser::mongo_ast::*;
+
+use super::args::ArgParser;
+
+/// Atlas Search operators accepted at the top level of a `$search` stage
+const SEARCH_OPERATORS: &[&str] = &[
+    "autocomplete",
+    "compound",
+    "embeddedDocument",
+    "equals",
+    "exists",
+    "geoShape",
+    "geoWithin",
+    "in",
+    "knnBeta",
+    "moreLikeThis",
+    "near",
+    "phrase",
+    "queryString",
+    "range",
+    "regex",
+    "span",
+    "text",
+    "wildcard",
+];
+
+/// Non-operator options accepted at the top level of a `$search` stage
+const SEARCH_STAGE_OPTIONS: &[&str] = &[
+    "concurrent",
+    "count",
+    "highlight",
+    "index",
+    "returnStoredSource",
+    "scoreDetails",
+    "searchAfter",
+    "searchBefore",
+    "sort",
+    "tracking",
+];
+
+/// Clauses of a `compound` operator, each holding one or more operators
+const COMPOUND_CLAUSES: &[&str] = &["must", "mustNot", "should", "filter"];
+
+/// Operators that need both a `query` and a `path`
+const QUERY_PATH_OPERATORS: &[&str] = &["autocomplete", "phrase", "regex", "text", "wildcard"];
+
+/// Number of search results returned when no limit is given
+const DEFAULT_SEARCH_LIMIT: i64 = 20;
+
+/// Fields accepted in a `$vectorSearch` stage
+const VECTOR_SEARCH_FIELDS: &[&str] = &[
+    "exact",
+    "filter",
+    "index",
+    "limit",
+    "numCandidates",
+    "path",
+    "queryVector",
+];
+
+/// Upper bound Atlas enforces on `numCandidates`
+pub(crate) const MAX_NUM_CANDIDATES: i64 = 10_000;
+
+/// Atlas Search operation parsers
+pub struct SearchOpsParser;
+
+impl SearchOpsParser {
+    /// Parse search operation: db.collection.search(spec, { limit })
+    pub fn parse_search(collection: &str, args: &[Expr]) -> Result<Command> {
+        if args.is_empty() {
+            return Err(ParseError::InvalidQuery(
+                "search() requires a search specification, e.g. search({ text: { query: 'coffee', path: 'title' } })"
+                    .to_string(),
+            )
+            .into());
+        }
+
+        let search = ArgParser::get_doc_arg(args, 0)?;
+        Self::validate_search_stage(&search)?;
+
+        let options = ArgParser::get_doc_arg(args, 1)?;
+        let limit = match options.get("limit") {
+            None => DEFAULT_SEARCH_LIMIT,
+            Some(Bson::Int64(n)) if *n > 0 => *n,
+            Some(Bson::Int32(n)) if *n > 0 => *n as i64,
+            Some(_) => {
+                return Err(ParseError::InvalidQuery(
+                    "search() limit must be a positive integer".to_string(),
+                )
+                .into());
+            }
+        };
+
+        Ok(Command::Query(QueryCommand::Search {
+            collection: collection.to_string(),
+            search,
+            limit,
+        }))
+    }
+
+    /// Parse vector search operation: db.collection.vectorSearch({ queryVector, path, ... })
+    pub fn parse_vector_search(collection: &str, args: &[Expr]) -> Result<Command> {
+        if args.is_empty() {
+            return Err(ParseError::InvalidQuery(
+                "vectorSearch() requires a specification, e.g. vectorSearch({ queryVector: [0.1, 0.2], path: 'embedding', limit: 5 })"
+                    .to_string(),
+            )
+            .into());
+        }
+
+        let mut spec = ArgParser::get_doc_arg(args, 0)?;
+        Self::normalize_vector_search(&mut spec)?;
+
+        Ok(Command::Query(QueryCommand::VectorSearch {
+            collection: collection.to_string(),
+            vector_search: spec,
+        }))
+    }
+
+    /// Validate a `$vectorSearch` stage body and fill in the default `limit`
+    ///
+    /// `numCandidates` is only checked here; when omitted the executor derives
+    /// it from the final limit, so a chained `.limit()` keeps it consistent.
+    pub fn normalize_vector_search(spec: &mut Document) -> Result<()> {
+        if let Some(key) = spec
+            .keys()
+            .find(|key| !VECTOR_SEARCH_FIELDS.contains(&key.as_str()))
+        {
+            return Err(ParseError::InvalidQuery(format!(
+                "Unknown vectorSearch field '{}'. Expected: {}",
+                key,
+                VECTOR_SEARCH_FIELDS.join(", ")
+            ))
+            .into());
+        }
+
+        match spec.get("path") {
+            Some(Bson::String(path)) if !path.is_empty() => {}
+            _ => {
+                return Err(ParseError::InvalidQuery(
+                    "vectorSearch() requires 'path' naming the embedding field".to_string(),
+                )
+                .into());
+            }
+        }
+
+        match spec.get("queryVector") {
+            Some(Bson::Array(values)) if !values.is_empty() => {
+                if !values
+                    .iter()
+                    .all(|v| matches!(v, Bson::Double(_) | Bson::Int32(_) | Bson::Int64(_)))
+                {
+                    return Err(ParseError::InvalidQuery(
+                        "vectorSearch() 'queryVector' must contain only numbers".to_string(),
+                    )
+                    .into());
+                }
+            }
+            _ => {
+                return Err(ParseError::InvalidQuery(
+                    "vectorSearch() requires a non-empty 'queryVector' array".to_string(),
+                )
+                .into());
+            }
+        }
+
+        if let Some(filter) = spec.get("filter")
+            && !matches!(filter, Bson::Document(_))
+        {
+            return Err(ParseError::InvalidQuery(
+                "vectorSearch() 'filter' must be an object".to_string(),
+            )
+            .into());
+        }
+
+        let limit = match spec.get("limit") {
+            None => DEFAULT_SEARCH_LIMIT,
+            Some(value) => positive_integer(value).ok_or_else(|| {
+                ParseError::InvalidQuery("vectorSearch() limit must be a positive integer".to_string())
+            })?,
+        };
+        spec.insert("limit", limit);
+
+        let exact = matches!(spec.get("exact"), Some(Bson::Boolean(true)));
+        if let Some(value) = spec.get("numCandidates") {
+            if exact {
+                return Err(ParseError::InvalidQuery(
+                    "vectorSearch() numCandidates cannot be combined with exact: true".to_string(),
+                )
+                .into());
+            }
+            let num_candidates = positive_integer(value).ok_or_else(|| {
+                ParseError::InvalidQuery(
+                    "vectorSearch() numCandidates must be a positive integer".to_string(),
+                )
+            })?;
+            if num_candidates < limit || num_candidates > MAX_NUM_CANDIDATES {
+                return Err(ParseError::InvalidQuery(format!(
+                    "vectorSearch() numCandidates must be between limit ({}) and {}",
+                    limit, MAX_NUM_CANDIDATES
+                ))
+                .into());
+            }
+            spec.insert("numCandidates", num_candidates);
+        }
+
+        Ok(())
+    }
+
+    /// Validate the body of a `$search` stage against known operators
+    pub fn validate_search_stage(search: &Document) -> Result<()> {
+        Self::validate_operators(search, SEARCH_STAGE_OPTIONS, "$search")
+    }
+
+    /// Validate that a document holds exactly one known operator
+    ///
+    /// # Arguments
+    /// * `body` - `$search` stage body or compound clause
+    /// * `options` - Non-operator keys accepted next to the operator
+    /// * `context` - Where the document appears, for error messages
+    fn validate_operators(body: &Document, options: &[&str], context: &str) -> Result<()> {
+        let mut operators = Vec::new();
+
+        for (key, value) in body {
+            if SEARCH_OPERATORS.contains(&key.as_str()) {
+                operators.push(key.as_str());
+                let spec = value.as_document().ok_or_else(|| {
+                    ParseError::InvalidQuery(format!(
+                        "$search operator '{}' must be an object",
+                        key
+                    ))
+                })?;
+                Self::validate_operator(key, spec)?;
+            } else if !options.contains(&key.as_str()) {
+                return Err(ParseError::InvalidQuery(format!(
+                    "Unknown {} operator or option '{}'. Known operators: {}",
+                    context,
+                    key,
+                    SEARCH_OPERATORS.join(", ")
+                ))
+                .into());
+            }
+        }
+
+        match operators.len() {
+            0 => Err(ParseError::InvalidQuery(format!(
+                "{} requires one operator ({})",
+                context,
+                SEARCH_OPERATORS.join(", ")
+            ))
+            .into()),
+            1 => Ok(()),
+            _ => Err(ParseError::InvalidQuery(format!(
+                "{} accepts a single operator, found: {}. Use 'compound' to combine them",
+                context,
+                operators.join(", ")
+            ))
+            .into()),
+        }
+    }
+
+    /// Validate a single operator body, recursing into compound clauses
+    fn validate_operator(name: &str, spec: &Document) -> Result<()> {
+        if QUERY_PATH_OPERATORS.contains(&name) {
+            for field in ["query", "path"] {
+                if !spec.contains_key(field) {
+                    return Err(ParseError::InvalidQuery(format!(
+                        "$search operator '{}' requires '{}'",
+                        name, field
+                    ))
+                    .into());
+                }
+            }
+        }
+
+        if name == "compound" {
+            Self::validate_compound(spec)?;
+        }
+
+        Ok(())
+    }
+
+    /// Validate a `compound` operator against the Atlas Search compound schema
+    ///
+    /// Clauses hold an operator object or an array of them; operators inside
+    /// a clause take no stage options such as `index` or `highlight`.
+    fn validate_compound(spec: &Document) -> Result<()> {
+        for (key, value) in spec {
+            match key.as_str() {
+                clause if COMPOUND_CLAUSES.contains(&clause) => {
+                    let items = match value {
+                        Bson::Array(items) => items.iter().collect::<Vec<_>>(),
+                        _ => vec![value],
+                    };
+                    for item in items {
+                        let doc = item.as_document().ok_or_else(|| {
+                            ParseError::InvalidQuery(format!(
+                                "compound '{}' clauses must be operator objects",
+                                clause
+                            ))
+                        })?;
+                        Self::validate_operators(doc, &[], &format!("compound '{}'", clause))?;
+                    }
+                }
+                "minimumShouldMatch" => {
+                    if !matches!(value, Bson::Int32(n) if *n >= 0)
+                        && !matches!(value, Bson::Int64(n) if *n >= 0)
+                    {
+                        return Err(ParseError::InvalidQuery(
+                            "compound 'minimumShouldMatch' must be a non-negative integer"
+                                .to_string(),
+                        )
+                        .into());
+                    }
+                }
+                "score" => {
+                    if value.as_document().is_none() {
+                        return Err(ParseError::InvalidQuery(
+                            "compound 'score' must be an object".to_string(),
+                        )
+                        .into());
+                    }
+                }
+                other => {
+                    return Err(ParseError::InvalidQuery(format!(
+                        "Unknown compound option '{}'. Expected must, mustNot, should, filter, minimumShouldMatch or score",
+                        other
+                    ))
+                    .into());
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Read a strictly positive integer from a BSON number
+fn positive_integer(value: &Bson) -> Option<i64> {
+    match value {
+        Bson::Int32(n) if *n > 0 => Some(*n as i64),
+        Bson::Int64(n) if *n > 0 => Some(*n),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::mongo_operation::DbOperationParser;
+
+    #[test]
+    fn test_parse_search_text() {
+        let cmd = DbOperationParser::parse(
+            "db.articles.search({ text: { query: 'coffee', path: 'title' } })",
+        )
+        .unwrap();
+        match cmd {
+            Command::Query(QueryCommand::Search {
+                collection,
+                search,
+                limit,
+            }) => {
+                assert_eq!(collection, "articles");
+                assert!(search.contains_key("text"));
+                assert_eq!(limit, DEFAULT_SEARCH_LIMIT);
+            }
+            _ => panic!("Expected Search command"),
+        }
+    }
+
+    #[test]
+    fn test_parse_search_with_limit_option_and_chain() {
+        let cmd = DbOperationParser::parse(
+            "db.articles.search({ text: { query: 'coffee', path: 'title' } }, { limit: 5 })",
+        )
+        .unwrap();
+        assert!(matches!(cmd, Command::Query(QueryCommand::Search { limit: 5, .. })));
+
+        let cmd = DbOperationParser::parse(
+            "db.articles.search({ text: { query: 'coffee', path: 'title' } }).limit(3)",
+        )
+        .unwrap();
+        assert!(matches!(cmd, Command::Query(QueryCommand::Search { limit: 3, .. })));
+    }
+
+    #[test]
+    fn test_search_rejects_unknown_operator() {
+        let result = DbOperationParser::parse("db.articles.search({ fuzzyText: { query: 'x' } })");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_search_requires_query_and_path() {
+        let result = DbOperationParser::parse("db.articles.search({ text: { query: 'coffee' } })");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_search_rejects_multiple_operators() {
+        let result = DbOperationParser::parse(
+            "db.articles.search({ text: { query: 'a', path: 'x' }, phrase: { query: 'b', path: 'y' } })",
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_search_validates_compound_clauses() {
+        let ok = DbOperationParser::parse(
+            "db.articles.search({ compound: { must: [{ text: { query: 'a', path: 'x' } }], filter: [{ range: { path: 'year', gte: 2000 } }] }, index: 'default' })",
+        );
+        assert!(ok.is_ok());
+
+        let bad = DbOperationParser::parse(
+            "db.articles.search({ compound: { must: [{ txt: { query: 'a', path: 'x' } }] } })",
+        );
+        assert!(bad.is_err());
+
+        let options = DbOperationParser::parse(
+            "db.articles.search({ compound: { should: [{ text: { query: 'a', path: 'x' } }, { text: { query: 'b', path: 'y' } }], minimumShouldMatch: 1, score: { boost: { value: 2 } } } })",
+        );
+        assert!(options.is_ok());
+
+        let stage_option_in_clause = DbOperationParser::parse(
+            "db.articles.search({ compound: { must: [{ text: { query: 'a', path: 'x' }, index: 'default' }] } })",
+        );
+        assert!(stage_option_in_clause.is_err());
+    }
+
+    #[test]
+    fn test_parse_vector_search_defaults() {
+        let cmd = DbOperationParser::parse(
+            "db.movies.vectorSearch({ queryVector: [0.1, 0.2, 0.3], path: 'embedding', limit: 5 })",
+        )
+        .unwrap();
+        match cmd {
+            Command::Query(QueryCommand::VectorSearch {
+                collection,
+                vector_search,
+            }) => {
+                assert_eq!(collection, "movies");
+                assert_eq!(vector_search.get_i64("limit").unwrap(), 5);
+                assert!(!vector_search.contains_key("numCandidates"));
+                assert_eq!(vector_search.get_array("queryVector").unwrap().len(), 3);
+            }
+            _ => panic!("Expected VectorSearch command"),
+        }
+    }
+
+    #[test]
+    fn test_parse_vector_search_with_filter_and_limit_chain() {
+        let cmd = DbOperationParser::parse(
+            "db.movies.vectorSearch({ queryVector: [1, 2], path: 'embedding', numCandidates: 100, filter: { year: { $gt: 2000 } } }).limit(10)",
+        )
+        .unwrap();
+        match cmd {
+            Command::Query(QueryCommand::VectorSearch { vector_search, .. }) => {
+                assert_eq!(vector_search.get_i64("limit").unwrap(), 10);
+                assert_eq!(vector_search.get_i64("numCandidates").unwrap(), 100);
+                assert!(vector_search.get_document("filter").is_ok());
+            }
+            _ => panic!("Expected VectorSearch command"),
+        }
+    }
+
+    #[test]
+    fn test_vector_search_requires_vector_and_path() {
+        assert!(DbOperationParser::parse("db.movies.vectorSearch({ path: 'embedding' })").is_err());
+        assert!(DbOperationParser::parse("db.movies.vectorSearch({ queryVector: [0.1] })").is_err());
+        assert!(
+            DbOperationParser::parse("db.movies.vectorSearch({ queryVector: ['a'], path: 'e' })")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_vector_search_rejects_too_few_candidates() {
+        let result = DbOperationParser::parse(
+            "db.movies.vectorSearch({ queryVector: [0.1], path: 'e', numCandidates: 5, limit: 10 })",
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_vector_search_exact_rejects_candidates() {
+        let result = DbOperationParser::parse(
+            "db.movies.vectorSearch({ queryVector: [0.1], path: 'e', exact: true, numCandidates: 50 })",
+        );
+        assert!(result.is_err());
+    }
+}
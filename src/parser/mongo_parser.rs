@@ -10,22 +10,37 @@
 //! - **Error-tolerant** - provides helpful error messages
 //! - **No dependencies** - uses only the mongo_lexer and mongo_ast modules
 //! - **Recursive descent** - simple and maintainable parsing strategy
+//!
+//! Syntax errors carry the span of the offending token, so they render the
+//! input line with a caret under the error position.
+
+use std::ops::Range;
 
 use super::mongo_ast::*;
 use super::mongo_lexer::{MongoLexer, MongoToken, MongoTokenKind};
 use crate::error::{ParseError, Result};
 
+/// Hint for errors where the input stops before the expression is complete
+const INCOMPLETE_INPUT_HINT: &str =
+    "the input ended early; check for a missing closing bracket, brace or quote";
+
 /// MongoDB Shell Parser
 pub struct MongoParser {
     tokens: Vec<MongoToken>,
     pos: usize,
+    /// Source characters, used to recover verbatim text from token spans
+    source: Vec<char>,
 }
 
 impl MongoParser {
     /// Create a new parser from input string
     pub fn new(input: &str) -> Self {
         let tokens = MongoLexer::tokenize(input);
-        Self { tokens, pos: 0 }
+        Self {
+            tokens,
+            pos: 0,
+            source: input.chars().collect(),
+        }
     }
 
     /// Parse the input as an expression
@@ -60,7 +75,7 @@ impl MongoParser {
         Ok(left)
     }
 
-    /// Parse multiplicative expression: a * b, a % b
+    /// Parse multiplicative expression: a * b, a / b, a % b
     fn parse_multiplicative(&mut self) -> Result<Expr> {
         let start = self.current_pos();
         let mut left = self.parse_unary()?;
@@ -68,6 +83,8 @@ impl MongoParser {
         loop {
             let op = if self.match_token(&MongoTokenKind::Star) {
                 BinaryOperator::Multiply
+            } else if self.match_token(&MongoTokenKind::Slash) {
+                BinaryOperator::Divide
             } else if self.match_token(&MongoTokenKind::Percent) {
                 BinaryOperator::Modulo
             } else {
@@ -123,20 +140,18 @@ impl MongoParser {
     fn parse_member_or_call(&mut self) -> Result<Expr> {
         let start = self.current_pos();
 
-        // Check for 'new' keyword
-        if let Some(MongoToken {
-            kind: MongoTokenKind::Ident(name),
-            ..
-        }) = self.current()
-        {
-            if name == "new" {
-                self.advance();
-                return self.parse_new_expression(start);
-            }
-        }
-
-        // Parse the base expression (primary)
-        let mut expr = self.parse_primary()?;
+        // Parse the base expression: new Ctor(args) or a primary, which may
+        // be followed by members and calls, e.g. new Date().getTime()
+        let is_new = matches!(
+            self.current(),
+            Some(MongoToken { kind: MongoTokenKind::Ident(name), .. }) if name == "new"
+        );
+        let mut expr = if is_new {
+            self.advance();
+            self.parse_new_expression(start)?
+        } else {
+            self.parse_primary()?
+        };
 
         // Handle member access and function calls
         loop {
@@ -201,6 +216,10 @@ impl MongoParser {
     fn parse_primary(&mut self) -> Result<Expr> {
         let start = self.current_pos();
 
+        if let Some(function) = self.parse_function_literal()? {
+            return Ok(function);
+        }
+
         match self.current() {
             Some(token) => match &token.kind {
                 // String literal
@@ -211,9 +230,9 @@ impl MongoParser {
                 }
                 // Number literal
                 MongoTokenKind::Number(n) => {
-                    let value = n
-                        .parse::<f64>()
-                        .map_err(|_| ParseError::SyntaxError(format!("Invalid number: {}", n)))?;
+                    let value = n.parse::<f64>().map_err(|_| {
+                        self.error_at(format!("Invalid number: {}", n), token.span.clone())
+                    })?;
                     self.advance();
                     Ok(Expr::Number(value))
                 }
@@ -248,6 +267,12 @@ impl MongoParser {
                     self.expect_token(&MongoTokenKind::RParen, "Expected ')' after expression")?;
                     Ok(expr)
                 }
+                // Template literal: `text ${expr}`
+                MongoTokenKind::Template(body) => {
+                    let body = body.clone();
+                    self.advance();
+                    Self::parse_template(&body)
+                }
                 // Regular expression literal: /pattern/flags
                 MongoTokenKind::Regex(pattern, flags) => {
                     let pattern = pattern.clone();
@@ -255,12 +280,86 @@ impl MongoParser {
                     self.advance();
                     Ok(Expr::Regex(pattern, flags))
                 }
-                _ => Err(
-                    ParseError::SyntaxError(format!("Unexpected token: {:?}", token.kind)).into(),
-                ),
+                MongoTokenKind::EOF => Err(self
+                    .error_here("Unexpected end of input")
+                    .with_hint(INCOMPLETE_INPUT_HINT)
+                    .into()),
+                MongoTokenKind::Unknown(ch) => Err(self
+                    .error_here(format!("Unexpected character '{}'", ch))
+                    .into()),
+                _ => Err(self
+                    .error_here(format!("Unexpected token: {:?}", token.kind))
+                    .into()),
             },
-            None => Err(ParseError::SyntaxError("Unexpected end of input".to_string()).into()),
+            None => Err(self
+                .error_here("Unexpected end of input")
+                .with_hint(INCOMPLETE_INPUT_HINT)
+                .into()),
+        }
+    }
+
+    /// Split a template literal body into text and `${...}` substitutions
+    fn parse_template(body: &str) -> Result<Expr> {
+        let mut parts = Vec::new();
+        let mut text = String::new();
+        let mut chars = body.chars().peekable();
+
+        while let Some(ch) = chars.next() {
+            match ch {
+                '\\' => match chars.next() {
+                    Some('n') => text.push('\n'),
+                    Some('t') => text.push('\t'),
+                    Some('r') => text.push('\r'),
+                    Some(other) => text.push(other),
+                    None => text.push('\\'),
+                },
+                '$' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    let mut source = String::new();
+                    let mut depth = 1;
+                    let mut quote: Option<char> = None;
+                    while let Some(ch) = chars.next() {
+                        match ch {
+                            '\\' => {
+                                source.push(ch);
+                                if let Some(escaped) = chars.next() {
+                                    source.push(escaped);
+                                }
+                                continue;
+                            }
+                            _ if quote == Some(ch) => quote = None,
+                            _ if quote.is_some() => {}
+                            '\'' | '"' => quote = Some(ch),
+                            '{' => depth += 1,
+                            '}' => {
+                                depth -= 1;
+                                if depth == 0 {
+                                    break;
+                                }
+                            }
+                            _ => {}
+                        }
+                        source.push(ch);
+                    }
+                    if depth != 0 {
+                        return Err(ParseError::SyntaxError(
+                            "Unterminated ${ in template literal".to_string(),
+                        )
+                        .into());
+                    }
+                    if !text.is_empty() {
+                        parts.push(TemplatePart::Text(std::mem::take(&mut text)));
+                    }
+                    parts.push(TemplatePart::Expr(Self::parse(&source)?));
+                }
+                _ => text.push(ch),
+            }
+        }
+
+        if !text.is_empty() || parts.is_empty() {
+            parts.push(TemplatePart::Text(text));
         }
+        Ok(Expr::Template(parts))
     }
 
     /// Parse object literal: { key: value, ... }
@@ -300,10 +399,10 @@ impl MongoParser {
             } else if self.check(&MongoTokenKind::RBrace) {
                 break;
             } else {
-                return Err(ParseError::SyntaxError(
-                    "Expected ',' or '}' after property".to_string(),
-                )
-                .into());
+                return Err(self
+                    .error_here("Expected ',' or '}' after property")
+                    .with_hint("separate properties with ','")
+                    .into());
             }
         }
 
@@ -322,6 +421,11 @@ impl MongoParser {
                     self.advance();
                     Ok(key)
                 }
+                // `db` is a keyword token but a valid key, e.g. `{ db: 'other' }`
+                MongoTokenKind::Db => {
+                    self.advance();
+                    Ok(PropertyKey::Ident("db".to_string()))
+                }
                 MongoTokenKind::String(s) => {
                     let key = PropertyKey::String(s.clone());
                     self.advance();
@@ -332,12 +436,14 @@ impl MongoParser {
                     self.advance();
                     Ok(key)
                 }
-                _ => Err(ParseError::SyntaxError(
-                    "Expected property key (identifier, string, or number)".to_string(),
-                )
-                .into()),
+                _ => Err(self
+                    .error_here("Expected property key (identifier, string, or number)")
+                    .into()),
             },
-            None => Err(ParseError::SyntaxError("Unexpected end of input".to_string()).into()),
+            None => Err(self
+                .error_here("Unexpected end of input")
+                .with_hint(INCOMPLETE_INPUT_HINT)
+                .into()),
         }
     }
 
@@ -368,10 +474,10 @@ impl MongoParser {
             } else if self.check(&MongoTokenKind::RBracket) {
                 break;
             } else {
-                return Err(ParseError::SyntaxError(
-                    "Expected ',' or ']' after array element".to_string(),
-                )
-                .into());
+                return Err(self
+                    .error_here("Expected ',' or ']' after array element")
+                    .with_hint("separate array elements with ','")
+                    .into());
             }
         }
 
@@ -408,6 +514,134 @@ impl MongoParser {
         Ok(arguments)
     }
 
+    /// Capture a JavaScript function literal as verbatim source text.
+    ///
+    /// Handles `function (...) { ... }`, `(a, b) => ...` and `a => ...`.
+    /// The body is never interpreted: `$function` and `$accumulator` run it
+    /// server-side, so we only need to find where it ends.
+    fn parse_function_literal(&mut self) -> Result<Option<Expr>> {
+        let end_index = match self.current().map(|t| &t.kind) {
+            Some(MongoTokenKind::Ident(name)) if name == "function" => {
+                let mut idx = self.pos + 1;
+                // Optional function name
+                if matches!(self.kind_at(idx), Some(MongoTokenKind::Ident(_))) {
+                    idx += 1;
+                }
+                let params_end = self
+                    .matching_close(idx)
+                    .ok_or_else(|| self.error_here("Expected '(' after function"))?;
+                if !matches!(self.kind_at(params_end + 1), Some(MongoTokenKind::LBrace)) {
+                    return Err(self.error_here("Expected '{' before function body").into());
+                }
+                self.matching_close(params_end + 1).ok_or_else(|| {
+                    self.error_here("Unterminated function body")
+                        .with_hint(INCOMPLETE_INPUT_HINT)
+                })?
+            }
+            Some(MongoTokenKind::LParen) => match self.matching_close(self.pos) {
+                Some(params_end) if self.is_arrow_at(params_end + 1) => {
+                    self.arrow_body_end(params_end + 3)?
+                }
+                _ => return Ok(None),
+            },
+            Some(MongoTokenKind::Ident(_)) if self.is_arrow_at(self.pos + 1) => {
+                self.arrow_body_end(self.pos + 3)?
+            }
+            _ => return Ok(None),
+        };
+
+        let start = self.current_pos();
+        let end = self.tokens[end_index].span.end;
+        self.pos = end_index + 1;
+        Ok(Some(Expr::Function(self.source[start..end].iter().collect())))
+    }
+
+    /// Find the index of the token that closes the bracket opened at `open`.
+    fn matching_close(&self, open: usize) -> Option<usize> {
+        if !matches!(
+            self.kind_at(open),
+            Some(MongoTokenKind::LParen | MongoTokenKind::LBrace | MongoTokenKind::LBracket)
+        ) {
+            return None;
+        }
+
+        let mut depth = 0usize;
+        for (idx, token) in self.tokens.iter().enumerate().skip(open) {
+            match token.kind {
+                MongoTokenKind::LParen | MongoTokenKind::LBrace | MongoTokenKind::LBracket => {
+                    depth += 1
+                }
+                MongoTokenKind::RParen | MongoTokenKind::RBrace | MongoTokenKind::RBracket => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Some(idx);
+                    }
+                }
+                MongoTokenKind::EOF => return None,
+                _ => {}
+            }
+        }
+        None
+    }
+
+    /// Check for an `=>` arrow starting at token `idx`
+    fn is_arrow_at(&self, idx: usize) -> bool {
+        match (self.tokens.get(idx), self.tokens.get(idx + 1)) {
+            (Some(eq), Some(gt)) => {
+                matches!(eq.kind, MongoTokenKind::Unknown('='))
+                    && matches!(gt.kind, MongoTokenKind::Unknown('>'))
+                    && eq.span.end == gt.span.start
+            }
+            _ => false,
+        }
+    }
+
+    /// Find the last token of an arrow function body starting at `idx`.
+    ///
+    /// A block body ends at its closing brace; an expression body runs until
+    /// a separator at the same nesting level (`,`, `;` or a closing bracket).
+    fn arrow_body_end(&self, idx: usize) -> Result<usize> {
+        if matches!(self.kind_at(idx), Some(MongoTokenKind::LBrace)) {
+            return self.matching_close(idx).ok_or_else(|| {
+                self.error_here("Unterminated arrow function body")
+                    .with_hint(INCOMPLETE_INPUT_HINT)
+                    .into()
+            });
+        }
+
+        let mut depth = 0usize;
+        let mut last = None;
+        for (i, token) in self.tokens.iter().enumerate().skip(idx) {
+            match token.kind {
+                MongoTokenKind::LParen | MongoTokenKind::LBrace | MongoTokenKind::LBracket => {
+                    depth += 1
+                }
+                MongoTokenKind::RParen | MongoTokenKind::RBrace | MongoTokenKind::RBracket
+                    if depth == 0 =>
+                {
+                    break;
+                }
+                MongoTokenKind::RParen | MongoTokenKind::RBrace | MongoTokenKind::RBracket => {
+                    depth -= 1
+                }
+                MongoTokenKind::Comma | MongoTokenKind::Semicolon if depth == 0 => break,
+                MongoTokenKind::EOF => break,
+                _ => {}
+            }
+            last = Some(i);
+        }
+
+        last.ok_or_else(|| {
+            self.error_here("Expected arrow function body after '=>'")
+                .into()
+        })
+    }
+
+    /// Get the token kind at an absolute index
+    fn kind_at(&self, idx: usize) -> Option<&MongoTokenKind> {
+        self.tokens.get(idx).map(|t| &t.kind)
+    }
+
     // Token manipulation methods
 
     /// Get current token
@@ -446,8 +680,13 @@ impl MongoParser {
         if self.check(kind) {
             self.advance();
             Ok(())
+        } else if self.check(&MongoTokenKind::EOF) || self.current().is_none() {
+            Err(self
+                .error_here(message)
+                .with_hint(INCOMPLETE_INPUT_HINT)
+                .into())
         } else {
-            Err(ParseError::SyntaxError(message.to_string()).into())
+            Err(self.error_here(message).into())
         }
     }
 
@@ -460,12 +699,30 @@ impl MongoParser {
                     self.advance();
                     Ok(name)
                 }
-                _ => Err(ParseError::SyntaxError(message.to_string()).into()),
+                _ => Err(self.error_here(message).into()),
             },
-            None => Err(ParseError::SyntaxError(message.to_string()).into()),
+            None => Err(self.error_here(message).into()),
         }
     }
 
+    /// Build a syntax error pointing at a span of the input
+    fn error_at(&self, message: impl Into<String>, span: Range<usize>) -> ParseError {
+        ParseError::at(message, self.source.iter().collect::<String>(), span)
+    }
+
+    /// Build a syntax error pointing at the current token, or at the end of
+    /// input when all tokens are consumed
+    fn error_here(&self, message: impl Into<String>) -> ParseError {
+        let span = match self.current() {
+            Some(token) => token.span.clone(),
+            None => {
+                let end = self.current_pos();
+                end..end
+            }
+        };
+        self.error_at(message, span)
+    }
+
     /// Get current position
     fn current_pos(&self) -> usize {
         if let Some(token) = self.current() {
@@ -553,6 +810,15 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_db_property_key() {
+        let expr = MongoParser::parse("{db: 'shop', filter: {}}").unwrap();
+        match expr {
+            Expr::Object(obj) => assert_eq!(obj.properties[0].key.as_string(), "db"),
+            _ => panic!("Expected object expression"),
+        }
+    }
+
     #[test]
     fn test_parse_nested_object() {
         let expr = MongoParser::parse("{user: {name: 'John'}}").unwrap();
@@ -704,9 +970,88 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_function_literal_verbatim() {
+        let src = "function(name) { return name.length > 3 ? name : '{' + name; }";
+        let expr = MongoParser::parse(src).unwrap();
+        assert!(matches!(expr, Expr::Function(ref s) if s == src));
+
+        let src = "function(s) { return /}/.test(s); }";
+        let expr = MongoParser::parse(src).unwrap();
+        assert!(matches!(expr, Expr::Function(ref s) if s == src));
+    }
+
+    #[test]
+    fn test_parse_arrow_functions() {
+        let expr = MongoParser::parse("{init: () => ({ count: 0 }), n: 1}").unwrap();
+        match expr {
+            Expr::Object(obj) => {
+                assert!(
+                    matches!(&obj.properties[0].value, Expr::Function(s) if s == "() => ({ count: 0 })")
+                );
+                assert!(matches!(obj.properties[1].value, Expr::Number(n) if n == 1.0));
+            }
+            _ => panic!("Expected object expression"),
+        }
+
+        let expr = MongoParser::parse("[x => x / 2, 3]").unwrap();
+        match expr {
+            Expr::Array(arr) => {
+                assert!(matches!(&arr.elements[0], Expr::Function(s) if s == "x => x / 2"));
+                assert_eq!(arr.elements.len(), 2);
+            }
+            _ => panic!("Expected array expression"),
+        }
+    }
+
+    #[test]
+    fn test_parse_parenthesized_expression_is_not_function() {
+        let expr = MongoParser::parse("(1 + 2) * 3").unwrap();
+        assert!(matches!(expr, Expr::Binary(_)));
+    }
+
     #[test]
     fn test_parse_complex_expression() {
         let expr = MongoParser::parse("db.users.find({age: {$gt: 18}})").unwrap();
         assert!(matches!(expr, Expr::Call(_)));
     }
+
+    #[test]
+    fn test_parse_template_literal() {
+        let expr = MongoParser::parse("`app-${1 + 1}-x`").unwrap();
+        match expr {
+            Expr::Template(parts) => {
+                assert_eq!(parts.len(), 3);
+                assert_eq!(parts[0], TemplatePart::Text("app-".to_string()));
+                assert!(matches!(parts[1], TemplatePart::Expr(Expr::Binary(_))));
+                assert_eq!(parts[2], TemplatePart::Text("-x".to_string()));
+            }
+            other => panic!("Expected Template, got {:?}", other),
+        }
+
+        let expr = MongoParser::parse("`a \\` ${'}'} b`").unwrap();
+        match expr {
+            Expr::Template(parts) => {
+                assert_eq!(parts[0], TemplatePart::Text("a ` ".to_string()));
+                assert_eq!(parts[1], TemplatePart::Expr(Expr::String("}".to_string())));
+            }
+            other => panic!("Expected Template, got {:?}", other),
+        }
+        assert!(MongoParser::parse("`${1 + }`").is_err());
+    }
+
+    #[test]
+    fn test_syntax_error_points_at_token() {
+        let err = MongoParser::parse("db.users.find({ name 'x' })").unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("Expected ':' after property key"));
+        assert!(message.contains("1 | db.users.find({ name 'x' })"));
+        assert!(message.contains(&format!("| {}^^^", " ".repeat(21))));
+
+        let err = MongoParser::parse("db.users.find({ a: 1 }").unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("Expected ')' after arguments"));
+        assert!(message.contains(&format!("| {}^", " ".repeat(22))));
+        assert!(message.contains("= hint: the input ended early"));
+    }
 }
@@ -3,13 +3,46 @@
 //! This module handles parsing of MongoDB shell-specific commands like:
 //! - show dbs, show collections, show users, etc.
 //! - use <database>
+//! - top [--watch [seconds]] [--limit n]
+//! - currentOp [--watch [seconds]] [--all]
+//! - rs.lag [--watch [seconds]] [--max-lag <duration>]
+//! - oplog tail [filter]
+//! - dump [dir], restore [dir] [--drop]
+//! - import <file.csv> [--collection name] [--locale tag] [--delimiter c] [--decimal-separator c]
+//! - undo last-delete
+//! - backups, backups cleanup [--older-than <duration>]
+//! - lookup wizard
 //! - help [topic]
+//! - retry
 //! - exit, quit
 //!
 //! These commands don't use JavaScript syntax, so they're parsed with simple string matching.
+//! Only the filter of `oplog tail` is an object literal, read with the shell expression parser.
+
+use bson::{Bson, Document};
 
 use crate::error::{ParseError, Result};
-use crate::parser::command::{AdminCommand, Command, ConfigCommand};
+use crate::parser::command::{AdminCommand, Command, ConfigCommand, CsvImportOptions};
+use crate::parser::mongo_converter::ExpressionConverter;
+use crate::parser::mongo_parser::MongoParser;
+
+/// Default interval between `top --watch` samples, in seconds
+const DEFAULT_TOP_INTERVAL: u64 = 5;
+
+/// Default refresh interval of the `currentOp --watch` monitor, in seconds
+const DEFAULT_CURRENT_OP_INTERVAL: u64 = 1;
+
+/// Default interval between `rs.lag --watch` polls, in seconds
+const DEFAULT_LAG_INTERVAL: u64 = 5;
+
+/// Default interval between `show log --follow` polls, in seconds
+const DEFAULT_LOG_FOLLOW_INTERVAL: u64 = 2;
+
+/// Default number of namespaces shown by `top`
+const DEFAULT_TOP_LIMIT: usize = 10;
+
+/// Directory used by `dump` and `restore` when none is given, as in mongodump
+const DEFAULT_DUMP_DIR: &str = "dump";
 
 /// Parser for shell-specific commands
 pub struct ShellCommandParser;
@@ -19,6 +52,14 @@ impl ShellCommandParser {
     pub fn is_shell_command(input: &str) -> bool {
         input.starts_with("show ")
             || input.starts_with("use ")
+            || input == "top"
+            || input.starts_with("top ")
+            || input == "currentOp"
+            || input.starts_with("currentOp ")
+            || input == "rs.lag"
+            || input.starts_with("rs.lag ")
+            || input == "oplog"
+            || input.starts_with("oplog ")
             || input.starts_with("help")
             || input.starts_with("config")
             || input == "format"
@@ -27,10 +68,37 @@ impl ShellCommandParser {
             || input.starts_with("color ")
             || input == "query"
             || input.starts_with("query ")
+            || input == "session"
+            || input.starts_with("session ")
+            || input == "bookmark"
+            || input.starts_with("bookmark ")
+            || input == "plugin"
+            || input.starts_with("plugin ")
+            || input == "cache"
+            || input.starts_with("cache ")
+            || input == "record"
+            || input.starts_with("record ")
+            || input == "report"
+            || input.starts_with("report ")
+            || input == "dump"
+            || input.starts_with("dump ")
+            || input == "restore"
+            || input.starts_with("restore ")
+            || input == "backups"
+            || input.starts_with("backups ")
+            || input == "import"
+            || input.starts_with("import ")
+            || input == "undo"
+            || input.starts_with("undo ")
+            || input == "lookup wizard"
+            || input == "output"
+            || input.starts_with("output ")
+            || input == "expand"
+            || input.starts_with("expand ")
             || input.starts_with("ai ")
             || input.starts_with(":ai-gen")
             || input.starts_with(":ai-status")
-            || matches!(input, "exit" | "quit" | "it")
+            || matches!(input, "exit" | "quit" | "it" | "retry" | "copy")
     }
 
     /// Parse a shell command
@@ -42,6 +110,18 @@ impl ShellCommandParser {
             return Ok(Command::Exit);
         }
 
+        // Re-run the last failed command
+        if trimmed == "retry" {
+            return Ok(Command::Retry);
+        }
+
+        // Copy the previous result to the clipboard
+        if trimmed == "copy" {
+            return Ok(Command::Utility(
+                crate::parser::command::UtilityCommand::CopyLastResult,
+            ));
+        }
+
         // Iteration command (for pagination)
         if trimmed == "it" {
             return Ok(Command::Utility(
@@ -64,6 +144,26 @@ impl ShellCommandParser {
             return Self::parse_use(trimmed);
         }
 
+        // Namespace usage
+        if trimmed == "top" || trimmed.starts_with("top ") {
+            return Self::parse_top(trimmed);
+        }
+
+        // Active operations monitor
+        if trimmed == "currentOp" || trimmed.starts_with("currentOp ") {
+            return Self::parse_current_op(trimmed);
+        }
+
+        // Replication lag monitor
+        if trimmed == "rs.lag" || trimmed.starts_with("rs.lag ") {
+            return Self::parse_rs_lag(trimmed);
+        }
+
+        // Stream oplog entries
+        if trimmed == "oplog" || trimmed.starts_with("oplog ") {
+            return Self::parse_oplog(trimmed);
+        }
+
         // Config commands
         if trimmed.starts_with("config")
             || trimmed.starts_with("format")
@@ -77,6 +177,76 @@ impl ShellCommandParser {
             return Self::parse_query(trimmed);
         }
 
+        // Session snapshots
+        if trimmed == "session" || trimmed.starts_with("session ") {
+            return Self::parse_session(trimmed);
+        }
+
+        // Query bookmarks
+        if trimmed == "bookmark" || trimmed.starts_with("bookmark ") {
+            return Self::parse_bookmark(trimmed);
+        }
+
+        // Plugin management
+        if trimmed == "plugin" || trimmed.starts_with("plugin ") {
+            return Self::parse_plugin(trimmed);
+        }
+
+        // Result cache
+        if trimmed == "cache" || trimmed.starts_with("cache ") {
+            return Self::parse_cache(trimmed);
+        }
+
+        // Session recording and Markdown reports
+        if trimmed == "record"
+            || trimmed.starts_with("record ")
+            || trimmed == "report"
+            || trimmed.starts_with("report ")
+        {
+            return Self::parse_record(trimmed);
+        }
+
+        // Dump and restore
+        if trimmed == "dump"
+            || trimmed.starts_with("dump ")
+            || trimmed == "restore"
+            || trimmed.starts_with("restore ")
+        {
+            return Self::parse_backup(trimmed);
+        }
+
+        // Copies taken before drop and renameCollection
+        if trimmed == "backups" || trimmed.starts_with("backups ") {
+            return Self::parse_backups(trimmed);
+        }
+
+        // CSV import
+        if trimmed == "import" || trimmed.starts_with("import ") {
+            return Self::parse_import(trimmed);
+        }
+
+        // Restore the last delete from the trash
+        if trimmed == "undo" || trimmed.starts_with("undo ") {
+            return Self::parse_undo(trimmed);
+        }
+
+        // Interactive $lookup builder
+        if trimmed == "lookup wizard" {
+            return Ok(Command::Utility(
+                crate::parser::command::UtilityCommand::LookupWizard,
+            ));
+        }
+
+        // Save the previous result to a file
+        if trimmed == "output" || trimmed.starts_with("output ") {
+            return Self::parse_output(trimmed);
+        }
+
+        // Show a collapsed field of the previous result
+        if trimmed == "expand" || trimmed.starts_with("expand ") {
+            return Self::parse_expand(trimmed);
+        }
+
         // AI query generation command
         if trimmed.starts_with("ai ") {
             let description = trimmed.strip_prefix("ai ").unwrap().trim().to_string();
@@ -108,20 +278,188 @@ impl ShellCommandParser {
         Ok(Command::Help(topic))
     }
 
+    /// Parse backups command: backups | backups cleanup [--older-than <duration>]
+    fn parse_backups(input: &str) -> Result<Command> {
+        let args = Self::parse_query_args(input.strip_prefix("backups").unwrap_or_default());
+        let usage = || {
+            ParseError::InvalidCommand(
+                "Usage: backups | backups cleanup [--older-than <duration>]".to_string(),
+            )
+        };
+
+        match args.as_slice() {
+            [] => Ok(Command::Admin(AdminCommand::ListBackups)),
+            [cleanup] if cleanup == "cleanup" => Ok(Command::Admin(AdminCommand::CleanupBackups {
+                older_than: None,
+            })),
+            [cleanup, flag, value] if cleanup == "cleanup" && flag == "--older-than" => {
+                let older_than = Self::parse_duration(value).ok_or_else(|| {
+                    ParseError::InvalidCommand(format!(
+                        "Invalid --older-than duration '{}', expected e.g. 12h or 7d",
+                        value
+                    ))
+                })?;
+                Ok(Command::Admin(AdminCommand::CleanupBackups {
+                    older_than: Some(older_than),
+                }))
+            }
+            _ => Err(usage().into()),
+        }
+    }
+
+    /// Parse import: import <file.csv> [--collection name] [--locale tag]
+    /// [--delimiter c] [--decimal-separator c]
+    ///
+    /// `--locale` sets the decimal and thousands separators and the matching
+    /// delimiter; `--delimiter` and `--decimal-separator` override them.
+    fn parse_import(input: &str) -> Result<Command> {
+        const USAGE: &str = "import <file.csv> [--collection name] [--locale tag] [--delimiter c] [--decimal-separator c]";
+        let args = Self::parse_query_args(input.strip_prefix("import").unwrap_or_default());
+        let mut args = args.iter();
+
+        let mut file = None;
+        let mut collection = None;
+        let mut locale = None;
+        let mut delimiter = None;
+        let mut decimal_separator = None;
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next().ok_or_else(|| {
+                    ParseError::InvalidCommand(format!(
+                        "{} requires a value. Usage: {}",
+                        arg, USAGE
+                    ))
+                })
+            };
+            match arg.as_str() {
+                "--collection" | "-c" => collection = Some(value()?.clone()),
+                "--locale" => {
+                    let tag = value()?;
+                    locale = Some(CsvImportOptions::for_locale(tag).ok_or_else(|| {
+                        ParseError::InvalidCommand(format!(
+                            "Unknown locale '{}'. Use --decimal-separator and --delimiter instead",
+                            tag
+                        ))
+                    })?);
+                }
+                "--delimiter" | "-d" => delimiter = Some(Self::parse_separator(arg, value()?)?),
+                "--decimal-separator" => {
+                    let separator = Self::parse_separator(arg, value()?)?;
+                    if !matches!(separator, '.' | ',') {
+                        return Err(ParseError::InvalidCommand(
+                            "--decimal-separator must be '.' or ','".to_string(),
+                        )
+                        .into());
+                    }
+                    decimal_separator = Some(separator);
+                }
+                other if !other.starts_with('-') && file.is_none() => {
+                    file = Some(other.to_string())
+                }
+                other => {
+                    return Err(ParseError::InvalidCommand(format!(
+                        "Unknown import option '{}'. Usage: {}",
+                        other, USAGE
+                    ))
+                    .into());
+                }
+            }
+        }
+
+        let file = file.ok_or_else(|| ParseError::InvalidCommand(format!("Usage: {}", USAGE)))?;
+        let collection = match collection {
+            Some(name) => name,
+            None => std::path::Path::new(&file)
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .filter(|stem| !stem.is_empty())
+                .map(String::from)
+                .ok_or_else(|| {
+                    ParseError::InvalidCommand(format!(
+                        "Cannot name a collection after '{}'; use --collection",
+                        file
+                    ))
+                })?,
+        };
+
+        let mut options = locale.unwrap_or_default();
+        if let Some(separator) = decimal_separator {
+            options.decimal_separator = separator;
+            if locale.is_none() && separator == ',' {
+                options.delimiter = ';';
+            }
+            // A grouping separator equal to the decimal separator would be ambiguous
+            if options.thousands_separator == Some(separator) {
+                options.thousands_separator = None;
+            }
+        }
+        if let Some(delimiter) = delimiter {
+            options.delimiter = delimiter;
+        }
+        if options.delimiter == options.decimal_separator {
+            return Err(ParseError::InvalidCommand(format!(
+                "The delimiter and the decimal separator are both '{}'",
+                options.delimiter
+            ))
+            .into());
+        }
+
+        Ok(Command::Admin(AdminCommand::ImportCsv {
+            file,
+            collection,
+            options,
+        }))
+    }
+
+    /// Read a single-character separator; `tab` and `\t` stand for a tab
+    fn parse_separator(flag: &str, value: &str) -> Result<char> {
+        if matches!(value, "tab" | "\\t") {
+            return Ok('\t');
+        }
+        let mut chars = value.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Ok(c),
+            _ => Err(ParseError::InvalidCommand(format!(
+                "{} expects a single character, got '{}'",
+                flag, value
+            ))
+            .into()),
+        }
+    }
+
+    /// Parse undo command: undo last-delete
+    fn parse_undo(input: &str) -> Result<Command> {
+        match input.strip_prefix("undo").unwrap_or_default().trim() {
+            "last-delete" => Ok(Command::Admin(AdminCommand::UndoLastDelete)),
+            _ => Err(ParseError::InvalidCommand(
+                "Usage: undo last-delete (needs safety.trash = true when deleting)".to_string(),
+            )
+            .into()),
+        }
+    }
+
     /// Parse show command
     fn parse_show(input: &str) -> Result<Command> {
         let rest = input.strip_prefix("show ").unwrap().trim();
 
+        if rest == "full" {
+            return Ok(Command::Config(ConfigCommand::ToggleFullValues));
+        }
+
         let cmd = match rest {
             "dbs" | "databases" => AdminCommand::ShowDatabases,
             "collections" | "tables" => AdminCommand::ShowCollections,
             "users" => AdminCommand::ShowUsers,
             "roles" => AdminCommand::ShowRoles,
             "profile" => AdminCommand::ShowProfile,
-            "logs" => AdminCommand::ShowLogs(None),
+            "logs" => AdminCommand::ShowLogs {
+                log_type: None,
+                grep: None,
+                since: None,
+                follow: None,
+            },
             other if other.starts_with("log ") => {
-                let log_type = other.strip_prefix("log ").unwrap().trim().to_string();
-                AdminCommand::ShowLogs(Some(log_type))
+                Self::parse_show_log(other.strip_prefix("log ").unwrap())?
             }
             _ => {
                 return Err(
@@ -133,6 +471,306 @@ impl ShellCommandParser {
         Ok(Command::Admin(cmd))
     }
 
+    /// Parse show log arguments:
+    /// <name> [--grep <text>] [--since <duration>] [--follow [seconds]]
+    fn parse_show_log(input: &str) -> Result<AdminCommand> {
+        let mut args = Self::parse_query_args(input).into_iter().peekable();
+        let log_type = args.next().ok_or_else(|| {
+            ParseError::InvalidCommand("show log requires a log name, e.g. global".to_string())
+        })?;
+
+        let mut grep = None;
+        let mut since = None;
+        let mut follow = None;
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--grep" => {
+                    grep = Some(args.next().ok_or_else(|| {
+                        ParseError::InvalidCommand("--grep requires a search text".to_string())
+                    })?);
+                }
+                "--since" => {
+                    let value = args.next().unwrap_or_default();
+                    since = Some(Self::parse_duration(&value).ok_or_else(|| {
+                        ParseError::InvalidCommand(format!(
+                            "Invalid --since duration '{}', expected e.g. 30s, 10m, 2h or 1d",
+                            value
+                        ))
+                    })?);
+                }
+                "--follow" | "-f" => {
+                    let seconds = match args.peek().and_then(|s| s.parse::<u64>().ok()) {
+                        Some(seconds) => {
+                            args.next();
+                            seconds
+                        }
+                        None => DEFAULT_LOG_FOLLOW_INTERVAL,
+                    };
+                    if seconds == 0 {
+                        return Err(ParseError::InvalidCommand(
+                            "show log --follow interval must be at least 1 second".to_string(),
+                        )
+                        .into());
+                    }
+                    follow = Some(seconds);
+                }
+                other => {
+                    return Err(ParseError::InvalidCommand(format!(
+                        "Unknown show log option '{}'. Usage: show log <name> [--grep <text>] [--since <duration>] [--follow [seconds]]",
+                        other
+                    ))
+                    .into());
+                }
+            }
+        }
+
+        Ok(AdminCommand::ShowLogs {
+            log_type: Some(log_type),
+            grep,
+            since,
+            follow,
+        })
+    }
+
+    /// Parse a duration such as `30s`, `10m`, `2h` or `1d` into seconds
+    fn parse_duration(value: &str) -> Option<u64> {
+        let split = value.find(|c: char| !c.is_ascii_digit())?;
+        let (number, unit) = value.split_at(split);
+        let number: u64 = number.parse().ok()?;
+        let multiplier = match unit {
+            "s" => 1,
+            "m" => 60,
+            "h" => 3600,
+            "d" => 86400,
+            _ => return None,
+        };
+        number.checked_mul(multiplier).filter(|secs| *secs > 0)
+    }
+
+    /// Parse top command: top [--watch [seconds]] [--limit n]
+    fn parse_top(input: &str) -> Result<Command> {
+        let mut watch = None;
+        let mut limit = DEFAULT_TOP_LIMIT;
+        let mut parts = input.split_whitespace().skip(1).peekable();
+
+        while let Some(part) = parts.next() {
+            match part {
+                "--watch" | "-w" => {
+                    let seconds = match parts.peek().and_then(|s| s.parse::<u64>().ok()) {
+                        Some(seconds) => {
+                            parts.next();
+                            seconds
+                        }
+                        None => DEFAULT_TOP_INTERVAL,
+                    };
+                    if seconds == 0 {
+                        return Err(ParseError::InvalidCommand(
+                            "top --watch interval must be at least 1 second".to_string(),
+                        )
+                        .into());
+                    }
+                    watch = Some(seconds);
+                }
+                "--limit" | "-n" => {
+                    limit = parts
+                        .next()
+                        .and_then(|s| s.parse::<usize>().ok())
+                        .filter(|n| *n > 0)
+                        .ok_or_else(|| {
+                            ParseError::InvalidCommand(
+                                "top --limit requires a positive number".to_string(),
+                            )
+                        })?;
+                }
+                other => {
+                    return Err(ParseError::InvalidCommand(format!(
+                        "Unknown top option '{}'. Usage: top [--watch [seconds]] [--limit n]",
+                        other
+                    ))
+                    .into());
+                }
+            }
+        }
+
+        Ok(Command::Admin(AdminCommand::Top { watch, limit }))
+    }
+
+    /// Parse currentOp command: currentOp [--watch [seconds]] [--all]
+    fn parse_current_op(input: &str) -> Result<Command> {
+        let mut watch = None;
+        let mut all = false;
+        let mut parts = input.split_whitespace().skip(1).peekable();
+
+        while let Some(part) = parts.next() {
+            match part {
+                "--watch" | "-w" => {
+                    let seconds = match parts.peek().and_then(|s| s.parse::<u64>().ok()) {
+                        Some(seconds) => {
+                            parts.next();
+                            seconds
+                        }
+                        None => DEFAULT_CURRENT_OP_INTERVAL,
+                    };
+                    if seconds == 0 {
+                        return Err(ParseError::InvalidCommand(
+                            "currentOp --watch interval must be at least 1 second".to_string(),
+                        )
+                        .into());
+                    }
+                    watch = Some(seconds);
+                }
+                "--all" | "-a" => all = true,
+                other => {
+                    return Err(ParseError::InvalidCommand(format!(
+                        "Unknown currentOp option '{}'. Usage: currentOp [--watch [seconds]] [--all]",
+                        other
+                    ))
+                    .into());
+                }
+            }
+        }
+
+        Ok(Command::Admin(AdminCommand::CurrentOp { watch, all }))
+    }
+
+    /// Parse rs.lag command: rs.lag [--watch [seconds]] [--max-lag <duration>]
+    fn parse_rs_lag(input: &str) -> Result<Command> {
+        let mut watch = None;
+        let mut max_lag = None;
+        let mut parts = input.split_whitespace().skip(1).peekable();
+
+        while let Some(part) = parts.next() {
+            match part {
+                "--watch" | "-w" => {
+                    let seconds = match parts.peek().and_then(|s| s.parse::<u64>().ok()) {
+                        Some(seconds) => {
+                            parts.next();
+                            seconds
+                        }
+                        None => DEFAULT_LAG_INTERVAL,
+                    };
+                    if seconds == 0 {
+                        return Err(ParseError::InvalidCommand(
+                            "rs.lag --watch interval must be at least 1 second".to_string(),
+                        )
+                        .into());
+                    }
+                    watch = Some(seconds);
+                }
+                "--max-lag" => {
+                    let value = parts.next().unwrap_or_default();
+                    let seconds = value
+                        .parse::<u64>()
+                        .ok()
+                        .or_else(|| Self::parse_duration(value))
+                        .ok_or_else(|| {
+                            ParseError::InvalidCommand(format!(
+                                "Invalid --max-lag '{}', expected seconds or a duration like 30s or 2m",
+                                value
+                            ))
+                        })?;
+                    max_lag = Some(seconds);
+                }
+                other => {
+                    return Err(ParseError::InvalidCommand(format!(
+                        "Unknown rs.lag option '{}'. Usage: rs.lag [--watch [seconds]] [--max-lag <duration>]",
+                        other
+                    ))
+                    .into());
+                }
+            }
+        }
+
+        Ok(Command::Admin(AdminCommand::ReplicationLag {
+            watch,
+            max_lag,
+        }))
+    }
+
+    /// Parse oplog command: oplog tail [filter], e.g. oplog tail {ns: 'mydb.users'}
+    fn parse_oplog(input: &str) -> Result<Command> {
+        let usage = || {
+            ParseError::InvalidCommand(
+                "Usage: oplog tail [filter], e.g. oplog tail {ns: 'mydb.users'}".to_string(),
+            )
+        };
+        let rest = input.strip_prefix("oplog").unwrap_or_default().trim_start();
+        let Some(filter) = rest.strip_prefix("tail") else {
+            return Err(usage().into());
+        };
+        if !filter.is_empty() && !filter.starts_with(char::is_whitespace) {
+            return Err(usage().into());
+        }
+
+        let filter = filter.trim();
+        let filter = if filter.is_empty() {
+            Document::new()
+        } else {
+            match ExpressionConverter::expr_to_bson(&MongoParser::parse(filter)?)? {
+                Bson::Document(filter) => filter,
+                _ => {
+                    return Err(ParseError::InvalidCommand(
+                        "oplog tail expects a filter document, e.g. {ns: 'mydb.users'}".to_string(),
+                    )
+                    .into());
+                }
+            }
+        };
+
+        Ok(Command::Admin(AdminCommand::OplogTail { filter }))
+    }
+
+    /// Parse dump/restore: dump [dir] [--collection name]...,
+    /// restore [dir] [--drop] [--collection name]...
+    fn parse_backup(input: &str) -> Result<Command> {
+        let mut parts = input.split_whitespace();
+        let action = parts.next().unwrap_or_default();
+        let usage = if action == "dump" {
+            "dump [dir] [--collection name]..."
+        } else {
+            "restore [dir] [--drop] [--collection name]..."
+        };
+
+        let mut dir = None;
+        let mut collections = Vec::new();
+        let mut drop = false;
+        while let Some(part) = parts.next() {
+            match part {
+                "--collection" | "-c" => {
+                    let name = parts.next().ok_or_else(|| {
+                        ParseError::InvalidCommand(format!(
+                            "{} requires a collection name. Usage: {}",
+                            part, usage
+                        ))
+                    })?;
+                    collections.push(name.to_string());
+                }
+                "--drop" if action == "restore" => drop = true,
+                other if !other.starts_with('-') && dir.is_none() => {
+                    dir = Some(other.to_string());
+                }
+                other => {
+                    return Err(ParseError::InvalidCommand(format!(
+                        "Unknown {} option '{}'. Usage: {}",
+                        action, other, usage
+                    ))
+                    .into());
+                }
+            }
+        }
+
+        let dir = dir.unwrap_or_else(|| DEFAULT_DUMP_DIR.to_string());
+        Ok(Command::Admin(if action == "dump" {
+            AdminCommand::Dump { dir, collections }
+        } else {
+            AdminCommand::Restore {
+                dir,
+                collections,
+                drop,
+            }
+        }))
+    }
+
     /// Parse use command
     fn parse_use(input: &str) -> Result<Command> {
         let db_name = input.strip_prefix("use ").unwrap().trim();
@@ -194,9 +832,220 @@ impl ShellCommandParser {
             return Ok(Command::Config(ConfigCommand::ShowConfig));
         }
 
+        // Handle "config get <key>" and "config set <key> <value> [--save]"
+        if let Some(rest) = trimmed.strip_prefix("config ") {
+            return Self::parse_config_setting(rest.trim());
+        }
+
         Err(ParseError::InvalidCommand(format!("Unknown config command: {}", input)).into())
     }
 
+    /// Parse config get/set: get <key> | set <key> <value> [--save]
+    fn parse_config_setting(input: &str) -> Result<Command> {
+        let usage = || {
+            ParseError::InvalidCommand(
+                "Usage: config get <key> | config set <key> <value> [--save]".to_string(),
+            )
+            .into()
+        };
+
+        let (action, rest) = input.split_once(char::is_whitespace).ok_or_else(usage)?;
+        let (key, value) = match rest.trim().split_once(char::is_whitespace) {
+            Some((key, value)) => (key, value.trim()),
+            None => (rest.trim(), ""),
+        };
+
+        match action {
+            "get" if value.is_empty() => {
+                Ok(Command::Config(ConfigCommand::GetSetting(key.to_string())))
+            }
+            "set" => {
+                let (value, save) = match value.rsplit_once(char::is_whitespace) {
+                    Some((value, "--save")) => (value.trim_end(), true),
+                    _ if value == "--save" => ("", true),
+                    _ => (value, false),
+                };
+                if value.is_empty() {
+                    return Err(ParseError::InvalidCommand(format!(
+                        "config set {} requires a value",
+                        key
+                    ))
+                    .into());
+                }
+                Ok(Command::Config(ConfigCommand::SetSetting {
+                    key: key.to_string(),
+                    value: value.to_string(),
+                    save,
+                }))
+            }
+            _ => Err(usage()),
+        }
+    }
+
+    /// Parse session commands: session save <file> | session load <file>
+    fn parse_session(input: &str) -> Result<Command> {
+        let mut parts = input.split_whitespace().skip(1);
+        let action = parts.next().unwrap_or_default();
+        let path = parts.collect::<Vec<_>>().join(" ");
+
+        match action {
+            "save" | "load" if path.is_empty() => Err(ParseError::InvalidCommand(format!(
+                "session {} requires a file path",
+                action
+            ))
+            .into()),
+            "save" => Ok(Command::Config(ConfigCommand::SaveSession(path))),
+            "load" => Ok(Command::Config(ConfigCommand::LoadSession(path))),
+            _ => Err(ParseError::InvalidCommand(
+                "Usage: session save <file> | session load <file>".to_string(),
+            )
+            .into()),
+        }
+    }
+
+    /// Parse bookmark commands:
+    /// bookmark list | bookmark save <name> <query> | bookmark run <name> | bookmark delete <name>
+    fn parse_bookmark(input: &str) -> Result<Command> {
+        let rest = input.strip_prefix("bookmark").unwrap_or_default().trim();
+        let (action, rest) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+        let (name, query) = rest
+            .trim()
+            .split_once(char::is_whitespace)
+            .map(|(name, query)| (name, query.trim()))
+            .unwrap_or((rest.trim(), ""));
+
+        match action {
+            "" | "list" if name.is_empty() => Ok(Command::Config(ConfigCommand::ListBookmarks)),
+            "save" | "run" | "delete" if name.is_empty() => Err(ParseError::InvalidCommand(
+                format!("bookmark {} requires a name", action),
+            )
+            .into()),
+            "save" if query.is_empty() => Err(ParseError::InvalidCommand(
+                "bookmark save requires <name> and <query>".to_string(),
+            )
+            .into()),
+            "save" => Ok(Command::Config(ConfigCommand::SaveBookmark {
+                name: name.to_string(),
+                query: query.to_string(),
+            })),
+            "run" if query.is_empty() => {
+                Ok(Command::Config(ConfigCommand::RunBookmark(name.to_string())))
+            }
+            "delete" if query.is_empty() => {
+                Ok(Command::Config(ConfigCommand::DeleteBookmark(name.to_string())))
+            }
+            _ => Err(ParseError::InvalidCommand(
+                "Usage: bookmark list | save <name> <query> | run <name> | delete <name>".to_string(),
+            )
+            .into()),
+        }
+    }
+
+    /// Parse plugin commands:
+    /// plugin list | plugin info <name> | plugin enable <name> | plugin disable <name>
+    fn parse_plugin(input: &str) -> Result<Command> {
+        let mut parts = input.split_whitespace().skip(1);
+        let action = parts.next().unwrap_or_default();
+        let name = parts.next().unwrap_or_default().to_string();
+        let extra = parts.next().is_some();
+
+        match action {
+            "" | "list" if name.is_empty() => Ok(Command::Config(ConfigCommand::ListPlugins)),
+            "info" | "enable" | "disable" if name.is_empty() => Err(ParseError::InvalidCommand(
+                format!("plugin {} requires a plugin name", action),
+            )
+            .into()),
+            "info" if !extra => Ok(Command::Config(ConfigCommand::PluginInfo(name))),
+            "enable" | "disable" if !extra => Ok(Command::Config(ConfigCommand::SetPluginEnabled {
+                name,
+                enabled: action == "enable",
+            })),
+            _ => Err(ParseError::InvalidCommand(
+                "Usage: plugin list | info <name> | enable <name> | disable <name>".to_string(),
+            )
+            .into()),
+        }
+    }
+
+    /// Parse result cache commands: cache on | off | clear | stats
+    fn parse_cache(input: &str) -> Result<Command> {
+        let args: Vec<&str> = input.split_whitespace().skip(1).collect();
+        let command = match args.as_slice() {
+            [] | ["stats"] => ConfigCommand::ResultCacheStats,
+            ["on"] => ConfigCommand::SetResultCache(true),
+            ["off"] => ConfigCommand::SetResultCache(false),
+            ["clear"] => ConfigCommand::ClearResultCache,
+            _ => {
+                return Err(ParseError::InvalidCommand(
+                    "Usage: cache on | off | clear | stats".to_string(),
+                )
+                .into());
+            }
+        };
+        Ok(Command::Config(command))
+    }
+
+    /// Parse recording and report commands:
+    /// record start <file> | record stop | report start <file> | report stop
+    fn parse_record(input: &str) -> Result<Command> {
+        let mut parts = input.split_whitespace();
+        let keyword = parts.next().unwrap_or_default();
+        let action = parts.next().unwrap_or_default();
+        let path = parts.collect::<Vec<_>>().join(" ");
+        let command = if keyword == "report" {
+            Command::Report
+        } else {
+            Command::Record
+        };
+
+        match action {
+            "start" if path.is_empty() => Err(ParseError::InvalidCommand(format!(
+                "{} start requires a file path",
+                keyword
+            ))
+            .into()),
+            "start" => Ok(command(Some(path))),
+            "stop" if path.is_empty() => Ok(command(None)),
+            _ => Err(ParseError::InvalidCommand(format!(
+                "Usage: {0} start <file> | {0} stop",
+                keyword
+            ))
+            .into()),
+        }
+    }
+
+    /// Parse output commands: output last > <file>
+    fn parse_output(input: &str) -> Result<Command> {
+        let path = input
+            .strip_prefix("output")
+            .map(str::trim)
+            .and_then(|rest| rest.strip_prefix("last"))
+            .filter(|rest| rest.is_empty() || !rest.starts_with(char::is_alphanumeric))
+            .map(|rest| rest.trim().trim_start_matches('>').trim())
+            .unwrap_or_default();
+
+        if path.is_empty() {
+            return Err(ParseError::InvalidCommand(
+                "Usage: output last > <file>".to_string(),
+            )
+            .into());
+        }
+        Ok(Command::Utility(
+            crate::parser::command::UtilityCommand::SaveLastResult(path.to_string()),
+        ))
+    }
+
+    /// Parse expand commands: expand <path>
+    fn parse_expand(input: &str) -> Result<Command> {
+        let path = input.strip_prefix("expand").unwrap_or_default().trim();
+        if path.is_empty() || path.split('.').any(str::is_empty) {
+            return Err(ParseError::InvalidCommand("Usage: expand <path>".to_string()).into());
+        }
+        Ok(Command::Utility(
+            crate::parser::command::UtilityCommand::ExpandLastResult(path.to_string()),
+        ))
+    }
+
     /// Parse query commands (named queries)
     fn parse_query(input: &str) -> Result<Command> {
         let trimmed = input.trim();
@@ -331,7 +1180,7 @@ impl ShellCommandParser {
     }
 
     /// Validate database name
-    fn is_valid_db_name(name: &str) -> bool {
+    pub(crate) fn is_valid_db_name(name: &str) -> bool {
         // MongoDB database name restrictions:
         // - Cannot be empty
         // - Cannot contain /\. "$*<>:|?
@@ -388,6 +1237,84 @@ mod tests {
         assert!(matches!(result, Command::Exit));
     }
 
+    #[test]
+    fn test_parse_retry() {
+        assert!(ShellCommandParser::is_shell_command("retry"));
+        let result = ShellCommandParser::parse("retry").unwrap();
+        assert!(matches!(result, Command::Retry));
+    }
+
+    #[test]
+    fn test_parse_backups() {
+        assert!(ShellCommandParser::is_shell_command("backups"));
+        assert_eq!(
+            ShellCommandParser::parse("backups").unwrap(),
+            Command::Admin(AdminCommand::ListBackups)
+        );
+        assert_eq!(
+            ShellCommandParser::parse("backups cleanup").unwrap(),
+            Command::Admin(AdminCommand::CleanupBackups { older_than: None })
+        );
+        assert_eq!(
+            ShellCommandParser::parse("backups cleanup --older-than 2d").unwrap(),
+            Command::Admin(AdminCommand::CleanupBackups {
+                older_than: Some(172_800)
+            })
+        );
+        assert!(ShellCommandParser::parse("backups cleanup --older-than soon").is_err());
+        assert!(ShellCommandParser::parse("backups drop").is_err());
+    }
+
+    #[test]
+    fn test_parse_import() {
+        assert!(ShellCommandParser::is_shell_command("import sales.csv"));
+        assert_eq!(
+            ShellCommandParser::parse("import data/sales.csv").unwrap(),
+            Command::Admin(AdminCommand::ImportCsv {
+                file: "data/sales.csv".to_string(),
+                collection: "sales".to_string(),
+                options: CsvImportOptions::default(),
+            })
+        );
+        assert_eq!(
+            ShellCommandParser::parse("import umsatz.csv --locale de-DE -c sales").unwrap(),
+            Command::Admin(AdminCommand::ImportCsv {
+                file: "umsatz.csv".to_string(),
+                collection: "sales".to_string(),
+                options: CsvImportOptions {
+                    delimiter: ';',
+                    decimal_separator: ',',
+                    thousands_separator: Some('.'),
+                },
+            })
+        );
+
+        let Command::Admin(AdminCommand::ImportCsv { options, .. }) =
+            ShellCommandParser::parse("import x.csv --decimal-separator , --delimiter tab")
+                .unwrap()
+        else {
+            panic!("Expected ImportCsv");
+        };
+        assert_eq!(options.delimiter, '\t');
+        assert_eq!(options.decimal_separator, ',');
+
+        assert!(ShellCommandParser::parse("import").is_err());
+        assert!(ShellCommandParser::parse("import x.csv --locale xx").is_err());
+        assert!(ShellCommandParser::parse("import x.csv --decimal-separator ;").is_err());
+        assert!(
+            ShellCommandParser::parse("import x.csv --delimiter , --decimal-separator ,").is_err()
+        );
+    }
+
+    #[test]
+    fn test_parse_undo() {
+        assert!(ShellCommandParser::is_shell_command("undo last-delete"));
+        let result = ShellCommandParser::parse("undo last-delete").unwrap();
+        assert_eq!(result, Command::Admin(AdminCommand::UndoLastDelete));
+        assert!(ShellCommandParser::parse("undo").is_err());
+        assert!(ShellCommandParser::parse("undo everything").is_err());
+    }
+
     #[test]
     fn test_parse_help() {
         let result = ShellCommandParser::parse("help").unwrap();
@@ -437,6 +1364,12 @@ mod tests {
         assert!(matches!(result, Command::Admin(AdminCommand::ShowUsers)));
     }
 
+    #[test]
+    fn test_parse_show_full() {
+        let result = ShellCommandParser::parse("show full").unwrap();
+        assert_eq!(result, Command::Config(ConfigCommand::ToggleFullValues));
+    }
+
     #[test]
     fn test_parse_use_database() {
         let result = ShellCommandParser::parse("use mydb").unwrap();
@@ -484,6 +1417,40 @@ mod tests {
         assert!(matches!(result, Command::Config(ConfigCommand::ShowConfig)));
     }
 
+    #[test]
+    fn test_parse_config_get_set() {
+        let result = ShellCommandParser::parse("config get display.format").unwrap();
+        assert_eq!(
+            result,
+            Command::Config(ConfigCommand::GetSetting("display.format".to_string()))
+        );
+
+        let result = ShellCommandParser::parse("config set display.page_size 50").unwrap();
+        assert_eq!(
+            result,
+            Command::Config(ConfigCommand::SetSetting {
+                key: "display.page_size".to_string(),
+                value: "50".to_string(),
+                save: false,
+            })
+        );
+
+        let result =
+            ShellCommandParser::parse("config set redaction.mask [hidden] --save").unwrap();
+        assert_eq!(
+            result,
+            Command::Config(ConfigCommand::SetSetting {
+                key: "redaction.mask".to_string(),
+                value: "[hidden]".to_string(),
+                save: true,
+            })
+        );
+
+        assert!(ShellCommandParser::parse("config set display.page_size").is_err());
+        assert!(ShellCommandParser::parse("config get").is_err());
+        assert!(ShellCommandParser::parse("config show").is_err());
+    }
+
     #[test]
     fn test_invalid_db_name() {
         assert!(ShellCommandParser::parse("use my/db").is_err());
@@ -567,4 +1534,378 @@ mod tests {
         let args = ShellCommandParser::parse_query_args("'Skelly McDermott' admin");
         assert_eq!(args, vec!["Skelly McDermott", "admin"]);
     }
+
+    #[test]
+    fn test_parse_top() {
+        assert!(ShellCommandParser::is_shell_command("top"));
+        assert!(!ShellCommandParser::is_shell_command("topology"));
+
+        let result = ShellCommandParser::parse("top").unwrap();
+        assert!(matches!(
+            result,
+            Command::Admin(AdminCommand::Top {
+                watch: None,
+                limit: DEFAULT_TOP_LIMIT
+            })
+        ));
+
+        let result = ShellCommandParser::parse("top --watch --limit 3").unwrap();
+        assert!(matches!(
+            result,
+            Command::Admin(AdminCommand::Top {
+                watch: Some(DEFAULT_TOP_INTERVAL),
+                limit: 3
+            })
+        ));
+
+        let result = ShellCommandParser::parse("top --watch 2").unwrap();
+        assert!(matches!(
+            result,
+            Command::Admin(AdminCommand::Top {
+                watch: Some(2),
+                ..
+            })
+        ));
+
+        assert!(ShellCommandParser::parse("top --watch 0").is_err());
+        assert!(ShellCommandParser::parse("top --limit").is_err());
+        assert!(ShellCommandParser::parse("top --verbose").is_err());
+    }
+
+    #[test]
+    fn test_parse_rs_lag() {
+        assert!(ShellCommandParser::is_shell_command("rs.lag"));
+        assert!(!ShellCommandParser::is_shell_command("rs.lagging"));
+
+        assert_eq!(
+            ShellCommandParser::parse("rs.lag").unwrap(),
+            Command::Admin(AdminCommand::ReplicationLag {
+                watch: None,
+                max_lag: None
+            })
+        );
+        assert_eq!(
+            ShellCommandParser::parse("rs.lag --watch --max-lag 2m").unwrap(),
+            Command::Admin(AdminCommand::ReplicationLag {
+                watch: Some(DEFAULT_LAG_INTERVAL),
+                max_lag: Some(120)
+            })
+        );
+        assert_eq!(
+            ShellCommandParser::parse("rs.lag --max-lag 30 -w 10").unwrap(),
+            Command::Admin(AdminCommand::ReplicationLag {
+                watch: Some(10),
+                max_lag: Some(30)
+            })
+        );
+
+        assert!(ShellCommandParser::parse("rs.lag --watch 0").is_err());
+        assert!(ShellCommandParser::parse("rs.lag --max-lag").is_err());
+        assert!(ShellCommandParser::parse("rs.lag --max-lag soon").is_err());
+        assert!(ShellCommandParser::parse("rs.lag --quiet").is_err());
+    }
+
+    #[test]
+    fn test_parse_oplog_tail() {
+        assert!(ShellCommandParser::is_shell_command("oplog tail"));
+        assert_eq!(
+            ShellCommandParser::parse("oplog tail").unwrap(),
+            Command::Admin(AdminCommand::OplogTail {
+                filter: Document::new()
+            })
+        );
+        assert_eq!(
+            ShellCommandParser::parse("oplog tail {ns: 'mydb.users', op: 'u'}").unwrap(),
+            Command::Admin(AdminCommand::OplogTail {
+                filter: bson::doc! { "ns": "mydb.users", "op": "u" }
+            })
+        );
+
+        assert!(ShellCommandParser::parse("oplog").is_err());
+        assert!(ShellCommandParser::parse("oplog tailx").is_err());
+        assert!(ShellCommandParser::parse("oplog tail 'mydb.users'").is_err());
+    }
+
+    #[test]
+    fn test_parse_current_op() {
+        assert!(ShellCommandParser::is_shell_command("currentOp"));
+        assert!(!ShellCommandParser::is_shell_command("currentOperations"));
+
+        let result = ShellCommandParser::parse("currentOp").unwrap();
+        assert!(matches!(
+            result,
+            Command::Admin(AdminCommand::CurrentOp {
+                watch: None,
+                all: false
+            })
+        ));
+
+        let result = ShellCommandParser::parse("currentOp --watch --all").unwrap();
+        assert!(matches!(
+            result,
+            Command::Admin(AdminCommand::CurrentOp {
+                watch: Some(DEFAULT_CURRENT_OP_INTERVAL),
+                all: true
+            })
+        ));
+
+        let result = ShellCommandParser::parse("currentOp -w 3").unwrap();
+        assert!(matches!(
+            result,
+            Command::Admin(AdminCommand::CurrentOp { watch: Some(3), .. })
+        ));
+
+        assert!(ShellCommandParser::parse("currentOp --watch 0").is_err());
+        assert!(ShellCommandParser::parse("currentOp --kill").is_err());
+    }
+
+    #[test]
+    fn test_parse_show_log() {
+        let result = ShellCommandParser::parse("show log global --grep \"slow query\" --since 10m")
+            .unwrap();
+        match result {
+            Command::Admin(AdminCommand::ShowLogs {
+                log_type,
+                grep,
+                since,
+                follow,
+            }) => {
+                assert_eq!(log_type.as_deref(), Some("global"));
+                assert_eq!(grep.as_deref(), Some("slow query"));
+                assert_eq!(since, Some(600));
+                assert_eq!(follow, None);
+            }
+            _ => panic!("Expected ShowLogs command"),
+        }
+
+        assert!(matches!(
+            ShellCommandParser::parse("show logs").unwrap(),
+            Command::Admin(AdminCommand::ShowLogs { log_type: None, .. })
+        ));
+        assert!(ShellCommandParser::parse("show log global --since 10x").is_err());
+        assert!(ShellCommandParser::parse("show log global --grep").is_err());
+
+        assert!(matches!(
+            ShellCommandParser::parse("show log global --follow").unwrap(),
+            Command::Admin(AdminCommand::ShowLogs {
+                follow: Some(DEFAULT_LOG_FOLLOW_INTERVAL),
+                ..
+            })
+        ));
+        assert!(matches!(
+            ShellCommandParser::parse("show log global -f 5 --grep index").unwrap(),
+            Command::Admin(AdminCommand::ShowLogs {
+                follow: Some(5),
+                ..
+            })
+        ));
+        assert!(ShellCommandParser::parse("show log global --follow 0").is_err());
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(ShellCommandParser::parse_duration("30s"), Some(30));
+        assert_eq!(ShellCommandParser::parse_duration("2h"), Some(7200));
+        assert_eq!(ShellCommandParser::parse_duration("1d"), Some(86400));
+        assert_eq!(ShellCommandParser::parse_duration("10"), None);
+        assert_eq!(ShellCommandParser::parse_duration("0m"), None);
+    }
+
+    #[test]
+    fn test_parse_session() {
+        assert_eq!(
+            ShellCommandParser::parse("session save sessions/incident.toml").unwrap(),
+            Command::Config(ConfigCommand::SaveSession("sessions/incident.toml".to_string()))
+        );
+        assert_eq!(
+            ShellCommandParser::parse("session load incident.toml").unwrap(),
+            Command::Config(ConfigCommand::LoadSession("incident.toml".to_string()))
+        );
+        assert!(ShellCommandParser::parse("session save").is_err());
+        assert!(ShellCommandParser::parse("session").is_err());
+        assert!(ShellCommandParser::parse("session drop x").is_err());
+    }
+
+    #[test]
+    fn test_parse_plugin() {
+        assert_eq!(
+            ShellCommandParser::parse("plugin").unwrap(),
+            Command::Config(ConfigCommand::ListPlugins)
+        );
+        assert_eq!(
+            ShellCommandParser::parse("plugin list").unwrap(),
+            Command::Config(ConfigCommand::ListPlugins)
+        );
+        assert_eq!(
+            ShellCommandParser::parse("plugin info graph").unwrap(),
+            Command::Config(ConfigCommand::PluginInfo("graph".to_string()))
+        );
+        assert_eq!(
+            ShellCommandParser::parse("plugin disable graph").unwrap(),
+            Command::Config(ConfigCommand::SetPluginEnabled {
+                name: "graph".to_string(),
+                enabled: false,
+            })
+        );
+        assert!(ShellCommandParser::parse("plugin enable").is_err());
+        assert!(ShellCommandParser::parse("plugin enable a b").is_err());
+        assert!(ShellCommandParser::parse("plugin install graph").is_err());
+    }
+
+    #[test]
+    fn test_parse_cache() {
+        assert_eq!(
+            ShellCommandParser::parse("cache").unwrap(),
+            Command::Config(ConfigCommand::ResultCacheStats)
+        );
+        assert_eq!(
+            ShellCommandParser::parse("cache on").unwrap(),
+            Command::Config(ConfigCommand::SetResultCache(true))
+        );
+        assert_eq!(
+            ShellCommandParser::parse("cache off").unwrap(),
+            Command::Config(ConfigCommand::SetResultCache(false))
+        );
+        assert_eq!(
+            ShellCommandParser::parse("cache clear").unwrap(),
+            Command::Config(ConfigCommand::ClearResultCache)
+        );
+        assert!(ShellCommandParser::parse("cache flush").is_err());
+        assert!(ShellCommandParser::parse("cache on now").is_err());
+    }
+
+    #[test]
+    fn test_parse_bookmark() {
+        assert_eq!(
+            ShellCommandParser::parse(
+                "bookmark save slow-orders db.orders.find({status:'{{status}}'}).sort({ts:-1})"
+            )
+            .unwrap(),
+            Command::Config(ConfigCommand::SaveBookmark {
+                name: "slow-orders".to_string(),
+                query: "db.orders.find({status:'{{status}}'}).sort({ts:-1})".to_string(),
+            })
+        );
+        assert_eq!(
+            ShellCommandParser::parse("bookmark run slow-orders").unwrap(),
+            Command::Config(ConfigCommand::RunBookmark("slow-orders".to_string()))
+        );
+        assert_eq!(
+            ShellCommandParser::parse("bookmark delete slow-orders").unwrap(),
+            Command::Config(ConfigCommand::DeleteBookmark("slow-orders".to_string()))
+        );
+        assert_eq!(
+            ShellCommandParser::parse("bookmark list").unwrap(),
+            Command::Config(ConfigCommand::ListBookmarks)
+        );
+        assert_eq!(
+            ShellCommandParser::parse("bookmark").unwrap(),
+            Command::Config(ConfigCommand::ListBookmarks)
+        );
+        assert!(ShellCommandParser::parse("bookmark save slow-orders").is_err());
+        assert!(ShellCommandParser::parse("bookmark run").is_err());
+        assert!(ShellCommandParser::parse("bookmark run a b").is_err());
+        assert!(ShellCommandParser::parse("bookmark rename a b").is_err());
+    }
+
+    #[test]
+    fn test_parse_record() {
+        assert_eq!(
+            ShellCommandParser::parse("record start session.log").unwrap(),
+            Command::Record(Some("session.log".to_string()))
+        );
+        assert_eq!(
+            ShellCommandParser::parse("record stop").unwrap(),
+            Command::Record(None)
+        );
+        assert!(ShellCommandParser::parse("record start").is_err());
+        assert!(ShellCommandParser::parse("record").is_err());
+        assert!(ShellCommandParser::parse("record stop now").is_err());
+        assert_eq!(
+            ShellCommandParser::parse("report start notes/incident.md").unwrap(),
+            Command::Report(Some("notes/incident.md".to_string()))
+        );
+        assert_eq!(
+            ShellCommandParser::parse("report stop").unwrap(),
+            Command::Report(None)
+        );
+        assert!(ShellCommandParser::parse("report").is_err());
+    }
+
+    #[test]
+    fn test_parse_output() {
+        let expected = Command::Utility(crate::parser::command::UtilityCommand::SaveLastResult(
+            "out/users.json".to_string(),
+        ));
+        assert_eq!(
+            ShellCommandParser::parse("output last > out/users.json").unwrap(),
+            expected
+        );
+        assert_eq!(
+            ShellCommandParser::parse("output last out/users.json").unwrap(),
+            expected
+        );
+        assert!(ShellCommandParser::parse("output last >").is_err());
+        assert!(ShellCommandParser::parse("output").is_err());
+        assert!(ShellCommandParser::parse("output first > a.json").is_err());
+        assert_eq!(
+            ShellCommandParser::parse("copy").unwrap(),
+            Command::Utility(crate::parser::command::UtilityCommand::CopyLastResult)
+        );
+    }
+
+    #[test]
+    fn test_parse_expand() {
+        assert_eq!(
+            ShellCommandParser::parse("expand 2.items.0").unwrap(),
+            Command::Utility(crate::parser::command::UtilityCommand::ExpandLastResult(
+                "2.items.0".to_string()
+            ))
+        );
+        assert!(ShellCommandParser::parse("expand").is_err());
+        assert!(ShellCommandParser::parse("expand items..0").is_err());
+    }
+
+    #[test]
+    fn test_parse_backup() {
+        assert!(ShellCommandParser::is_shell_command("dump"));
+        assert!(ShellCommandParser::is_shell_command(
+            "restore backups --drop"
+        ));
+        assert_eq!(
+            ShellCommandParser::parse("dump").unwrap(),
+            Command::Admin(AdminCommand::Dump {
+                dir: "dump".to_string(),
+                collections: vec![],
+            })
+        );
+        assert_eq!(
+            ShellCommandParser::parse("dump backups --collection orders -c users").unwrap(),
+            Command::Admin(AdminCommand::Dump {
+                dir: "backups".to_string(),
+                collections: vec!["orders".to_string(), "users".to_string()],
+            })
+        );
+        assert_eq!(
+            ShellCommandParser::parse("restore backups/shop --drop").unwrap(),
+            Command::Admin(AdminCommand::Restore {
+                dir: "backups/shop".to_string(),
+                collections: vec![],
+                drop: true,
+            })
+        );
+        assert!(ShellCommandParser::parse("dump --drop").is_err());
+        assert!(ShellCommandParser::parse("restore --collection").is_err());
+        assert!(ShellCommandParser::parse("dump a b").is_err());
+    }
+
+    #[test]
+    fn test_parse_lookup_wizard() {
+        assert!(ShellCommandParser::is_shell_command("lookup wizard"));
+        assert!(!ShellCommandParser::is_shell_command("lookup"));
+        assert_eq!(
+            ShellCommandParser::parse("lookup wizard").unwrap(),
+            Command::Utility(crate::parser::command::UtilityCommand::LookupWizard)
+        );
+    }
 }
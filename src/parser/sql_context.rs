@@ -508,6 +508,9 @@ pub enum SqlExpr {
     /// IS NULL / IS NOT NULL
     IsNull { expr: Box<SqlExpr>, negated: bool },
 
+    /// IS MISSING / IS NOT MISSING (field absent vs present)
+    IsMissing { expr: Box<SqlExpr>, negated: bool },
+
     /// Typed literal: DATE '2026-02-15', TIMESTAMP '2026-02-15 16:00:00'
     TypedLiteral {
         type_name: String, // "DATE", "TIMESTAMP", "TIME"
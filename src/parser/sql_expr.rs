@@ -3,7 +3,7 @@
 //! This module converts SQL expressions and AST nodes into MongoDB BSON
 //! documents for use in queries and aggregation pipelines.
 
-use mongodb::bson::{Document, doc};
+use bson::{Document, doc};
 
 use super::sql_context::{
     ArithmeticOperator, ArrayIndex, ArraySlice, FieldPath, SliceIndex, SqlColumn, SqlExpr,
@@ -111,6 +111,8 @@ impl SqlExprConverter {
 
             SqlExpr::IsNull { expr, negated } => Self::is_null_to_filter(expr, *negated),
 
+            SqlExpr::IsMissing { expr, negated } => Self::is_missing_to_filter(expr, *negated),
+
             SqlExpr::TypedLiteral { .. } => {
                 Err(ParseError::InvalidCommand("Cannot use typed literal as filter".to_string()).into())
             }
@@ -135,23 +137,23 @@ impl SqlExprConverter {
         left: &SqlExpr,
         op: &ArithmeticOperator,
         right: &SqlExpr,
-    ) -> Result<mongodb::bson::Bson> {
+    ) -> Result<bson::Bson> {
         let left_expr = Self::expr_to_aggregate_value(left)?;
         let right_expr = Self::expr_to_aggregate_value(right)?;
 
-        Ok(mongodb::bson::Bson::Document(doc! {
+        Ok(bson::Bson::Document(doc! {
             op.to_mongo_operator(): [left_expr, right_expr]
         }))
     }
 
     /// Convert SQL expression to aggregation pipeline value
-    pub fn expr_to_aggregate_value(expr: &SqlExpr) -> Result<mongodb::bson::Bson> {
+    pub fn expr_to_aggregate_value(expr: &SqlExpr) -> Result<bson::Bson> {
         match expr {
             SqlExpr::Literal(lit) => Ok(Self::literal_to_bson(lit)),
 
             SqlExpr::FieldPath(path) => {
                 if let Some(path_str) = path.to_mongodb_path() {
-                    Ok(mongodb::bson::Bson::String(format!("${}", path_str)))
+                    Ok(bson::Bson::String(format!("${}", path_str)))
                 } else {
                     Err(ParseError::InvalidCommand(
                         "Complex field paths require special handling".to_string(),
@@ -184,7 +186,7 @@ impl SqlExprConverter {
     }
 
     /// Convert function call to aggregation expression
-    fn function_to_aggregate(name: &str, args: &[SqlExpr]) -> Result<mongodb::bson::Bson> {
+    fn function_to_aggregate(name: &str, args: &[SqlExpr]) -> Result<bson::Bson> {
         let upper_name = name.to_uppercase();
         match upper_name.as_str() {
             "ROUND" => {
@@ -198,9 +200,9 @@ impl SqlExprConverter {
                 let place = if args.len() == 2 {
                     Self::expr_to_aggregate_value(&args[1])?
                 } else {
-                    mongodb::bson::Bson::Int32(0)
+                    bson::Bson::Int32(0)
                 };
-                Ok(mongodb::bson::Bson::Document(doc! {
+                Ok(bson::Bson::Document(doc! {
                     "$round": [value, place]
                 }))
             }
@@ -212,7 +214,7 @@ impl SqlExprConverter {
                     .into());
                 }
                 let value = Self::expr_to_aggregate_value(&args[0])?;
-                Ok(mongodb::bson::Bson::Document(doc! { "$abs": value }))
+                Ok(bson::Bson::Document(doc! { "$abs": value }))
             }
             "CEIL" | "CEILING" => {
                 if args.len() != 1 {
@@ -222,7 +224,7 @@ impl SqlExprConverter {
                     .into());
                 }
                 let value = Self::expr_to_aggregate_value(&args[0])?;
-                Ok(mongodb::bson::Bson::Document(doc! { "$ceil": value }))
+                Ok(bson::Bson::Document(doc! { "$ceil": value }))
             }
             "FLOOR" => {
                 if args.len() != 1 {
@@ -232,7 +234,7 @@ impl SqlExprConverter {
                     .into());
                 }
                 let value = Self::expr_to_aggregate_value(&args[0])?;
-                Ok(mongodb::bson::Bson::Document(doc! { "$floor": value }))
+                Ok(bson::Bson::Document(doc! { "$floor": value }))
             }
             "TRUNC" | "TRUNCATE" => {
                 if args.is_empty() || args.len() > 2 {
@@ -245,9 +247,9 @@ impl SqlExprConverter {
                 let place = if args.len() == 2 {
                     Self::expr_to_aggregate_value(&args[1])?
                 } else {
-                    mongodb::bson::Bson::Int32(0)
+                    bson::Bson::Int32(0)
                 };
-                Ok(mongodb::bson::Bson::Document(doc! {
+                Ok(bson::Bson::Document(doc! {
                     "$trunc": [value, place]
                 }))
             }
@@ -262,8 +264,8 @@ impl SqlExprConverter {
                 // Get the string value and convert to ObjectId
                 match &args[0] {
                     SqlExpr::Literal(SqlLiteral::String(s)) => {
-                        match mongodb::bson::oid::ObjectId::parse_str(s) {
-                            Ok(oid) => Ok(mongodb::bson::Bson::ObjectId(oid)),
+                        match bson::oid::ObjectId::parse_str(s) {
+                            Ok(oid) => Ok(bson::Bson::ObjectId(oid)),
                             Err(e) => Err(ParseError::InvalidCommand(format!(
                                 "Invalid ObjectId string '{}': {}", s, e
                             )).into()),
@@ -274,16 +276,34 @@ impl SqlExprConverter {
                     ).into()),
                 }
             }
+            "YEAR" | "MONTH" | "DAY" | "HOUR" => {
+                if args.len() != 1 {
+                    return Err(ParseError::InvalidCommand(format!(
+                        "{} requires exactly 1 argument",
+                        upper_name
+                    ))
+                    .into());
+                }
+                let operator = match upper_name.as_str() {
+                    "YEAR" => "$year",
+                    "MONTH" => "$month",
+                    "DAY" => "$dayOfMonth",
+                    _ => "$hour",
+                };
+                let value = Self::expr_to_aggregate_value(&args[0])?;
+                Ok(bson::Bson::Document(doc! { operator: value }))
+            }
+            "DATE_TRUNC" => Self::date_trunc_to_aggregate(args),
             // Aggregate functions - convert to MongoDB aggregation operators
             "COUNT" => {
                 // COUNT(*) or COUNT(field) - in expression context, return $sum: 1 or $sum with condition
                 if args.is_empty() {
                     // COUNT(*) - just returns count, represented as literal in expression
-                    Ok(mongodb::bson::Bson::Document(doc! { "$sum": 1 }))
+                    Ok(bson::Bson::Document(doc! { "$sum": 1 }))
                 } else {
                     let field = Self::expr_to_aggregate_value(&args[0])?;
-                    Ok(mongodb::bson::Bson::Document(doc! {
-                        "$sum": { "$cond": [{ "$ne": [field, mongodb::bson::Bson::Null] }, 1, 0] }
+                    Ok(bson::Bson::Document(doc! {
+                        "$sum": { "$cond": [{ "$ne": [field, bson::Bson::Null] }, 1, 0] }
                     }))
                 }
             }
@@ -294,7 +314,7 @@ impl SqlExprConverter {
                     ).into());
                 }
                 let value = Self::expr_to_aggregate_value(&args[0])?;
-                Ok(mongodb::bson::Bson::Document(doc! { "$sum": value }))
+                Ok(bson::Bson::Document(doc! { "$sum": value }))
             }
             "AVG" => {
                 if args.len() != 1 {
@@ -303,7 +323,7 @@ impl SqlExprConverter {
                     ).into());
                 }
                 let value = Self::expr_to_aggregate_value(&args[0])?;
-                Ok(mongodb::bson::Bson::Document(doc! { "$avg": value }))
+                Ok(bson::Bson::Document(doc! { "$avg": value }))
             }
             "MIN" => {
                 if args.len() != 1 {
@@ -312,7 +332,7 @@ impl SqlExprConverter {
                     ).into());
                 }
                 let value = Self::expr_to_aggregate_value(&args[0])?;
-                Ok(mongodb::bson::Bson::Document(doc! { "$min": value }))
+                Ok(bson::Bson::Document(doc! { "$min": value }))
             }
             "MAX" => {
                 if args.len() != 1 {
@@ -321,7 +341,7 @@ impl SqlExprConverter {
                     ).into());
                 }
                 let value = Self::expr_to_aggregate_value(&args[0])?;
-                Ok(mongodb::bson::Bson::Document(doc! { "$max": value }))
+                Ok(bson::Bson::Document(doc! { "$max": value }))
             }
             _ => Err(ParseError::InvalidCommand(format!(
                 "Function {} not supported in aggregation",
@@ -345,7 +365,7 @@ impl SqlExprConverter {
         // Return as $expr for use in comparisons
         Ok(doc! {
             "$expr": {
-                "$ne": [arith_expr, mongodb::bson::Bson::Null]
+                "$ne": [arith_expr, bson::Bson::Null]
             }
         })
     }
@@ -481,7 +501,7 @@ impl SqlExprConverter {
             }
         };
 
-        let bson_values: Result<Vec<mongodb::bson::Bson>> =
+        let bson_values: Result<Vec<bson::Bson>> =
             values.iter().map(|v| Self::expr_to_bson_value(v)).collect();
 
         Ok(doc! { column: { "$in": bson_values? } })
@@ -546,25 +566,11 @@ impl SqlExprConverter {
     }
 
     /// Convert IS NULL expression to filter
+    ///
+    /// Like SQL, `IS NULL` also matches documents where the field is absent;
+    /// use `IS MISSING` to match only absent fields.
     fn is_null_to_filter(expr: &SqlExpr, negated: bool) -> Result<Document> {
-        let column = match expr {
-            SqlExpr::FieldPath(path) => {
-                if let Some(path_str) = path.to_mongodb_path() {
-                    path_str
-                } else {
-                    return Err(ParseError::InvalidCommand(
-                        "Complex field paths in IS NULL require aggregation pipeline".to_string(),
-                    )
-                    .into());
-                }
-            }
-            _ => {
-                return Err(ParseError::InvalidCommand(
-                    "IS NULL expression must have field path on left side".to_string(),
-                )
-                .into());
-            }
-        };
+        let column = Self::is_operand_column(expr, "IS NULL")?;
 
         if negated {
             Ok(doc! { column: { "$ne": null } })
@@ -573,14 +579,38 @@ impl SqlExprConverter {
         }
     }
 
+    /// Convert IS MISSING expression to filter
+    fn is_missing_to_filter(expr: &SqlExpr, negated: bool) -> Result<Document> {
+        let column = Self::is_operand_column(expr, "IS MISSING")?;
+        Ok(doc! { column: { "$exists": negated } })
+    }
+
+    /// Resolve the field path on the left side of an IS predicate
+    fn is_operand_column(expr: &SqlExpr, predicate: &str) -> Result<String> {
+        match expr {
+            SqlExpr::FieldPath(path) => path.to_mongodb_path().ok_or_else(|| {
+                ParseError::InvalidCommand(format!(
+                    "Complex field paths in {} require aggregation pipeline",
+                    predicate
+                ))
+                .into()
+            }),
+            _ => Err(ParseError::InvalidCommand(format!(
+                "{} expression must have field path on left side",
+                predicate
+            ))
+            .into()),
+        }
+    }
+
     /// Convert SQL expression to BSON value
-    fn expr_to_bson_value(expr: &SqlExpr) -> Result<mongodb::bson::Bson> {
+    fn expr_to_bson_value(expr: &SqlExpr) -> Result<bson::Bson> {
         match expr {
             SqlExpr::Literal(lit) => Ok(Self::literal_to_bson(lit)),
             SqlExpr::FieldPath(path) => {
                 // Field path reference as value - use MongoDB path syntax
                 if let Some(path_str) = path.to_mongodb_path() {
-                    Ok(mongodb::bson::Bson::String(format!("${}", path_str)))
+                    Ok(bson::Bson::String(format!("${}", path_str)))
                 } else {
                     // Complex path requires aggregation expression
                     Self::field_path_to_bson(path)
@@ -601,7 +631,7 @@ impl SqlExprConverter {
     }
 
     /// Convert SQL function call to BSON value
-    fn function_to_bson(name: &str, args: &[SqlExpr]) -> Result<mongodb::bson::Bson> {
+    fn function_to_bson(name: &str, args: &[SqlExpr]) -> Result<bson::Bson> {
         match name.to_uppercase().as_str() {
             "OBJECTID" => {
                 // ObjectId expects a single string argument
@@ -624,8 +654,8 @@ impl SqlExprConverter {
                 };
 
                 // Parse the hex string into an ObjectId
-                match mongodb::bson::oid::ObjectId::parse_str(&id_str) {
-                    Ok(oid) => Ok(mongodb::bson::Bson::ObjectId(oid)),
+                match bson::oid::ObjectId::parse_str(&id_str) {
+                    Ok(oid) => Ok(bson::Bson::ObjectId(oid)),
                     Err(e) => Err(ParseError::InvalidCommand(format!(
                         "Invalid ObjectId string '{}': {}",
                         id_str, e
@@ -655,8 +685,8 @@ impl SqlExprConverter {
                 };
 
                 // Parse ISO 8601 date string using MongoDB's built-in parser
-                mongodb::bson::DateTime::parse_rfc3339_str(&date_str)
-                    .map(mongodb::bson::Bson::DateTime)
+                bson::DateTime::parse_rfc3339_str(&date_str)
+                    .map(bson::Bson::DateTime)
                     .map_err(|e| ParseError::InvalidCommand(format!(
                         "Invalid date string '{}': {}. Expected ISO 8601 format (e.g., '2026-02-15T16:00:00.000Z')",
                         date_str, e
@@ -667,7 +697,7 @@ impl SqlExprConverter {
     }
 
     /// Convert typed literal to BSON (DATE '...', TIMESTAMP '...')
-    fn typed_literal_to_bson(type_name: &str, value: &str) -> Result<mongodb::bson::Bson> {
+    fn typed_literal_to_bson(type_name: &str, value: &str) -> Result<bson::Bson> {
         match type_name.to_uppercase().as_str() {
             "DATE" | "TIMESTAMP" => Self::parse_datetime_string(value),
             "TIME" => Err(ParseError::InvalidCommand(
@@ -680,12 +710,12 @@ impl SqlExprConverter {
     }
 
     /// Parse datetime string with multiple format support
-    fn parse_datetime_string(value: &str) -> Result<mongodb::bson::Bson> {
-        use mongodb::bson::DateTime as BsonDateTime;
+    fn parse_datetime_string(value: &str) -> Result<bson::Bson> {
+        use bson::DateTime as BsonDateTime;
 
         // Fast path: try RFC 3339 directly (most common case)
         if let Ok(dt) = BsonDateTime::parse_rfc3339_str(value) {
-            return Ok(mongodb::bson::Bson::DateTime(dt));
+            return Ok(bson::Bson::DateTime(dt));
         }
 
         // Analyze string characteristics once
@@ -714,7 +744,7 @@ impl SqlExprConverter {
         };
 
         BsonDateTime::parse_rfc3339_str(&iso_str)
-            .map(mongodb::bson::Bson::DateTime)
+            .map(bson::Bson::DateTime)
             .map_err(|_| Self::datetime_parse_error(value))
     }
 
@@ -743,10 +773,10 @@ impl SqlExprConverter {
     }
 
     /// Convert current time function to BSON (CURRENT_TIMESTAMP, NOW())
-    fn current_time_to_bson(kind: &str) -> Result<mongodb::bson::Bson> {
+    fn current_time_to_bson(kind: &str) -> Result<bson::Bson> {
         match kind.to_uppercase().as_str() {
             "CURRENT_TIMESTAMP" | "CURRENT_DATE" | "CURRENT_TIME" | "NOW" => {
-                Ok(mongodb::bson::Bson::DateTime(mongodb::bson::DateTime::now()))
+                Ok(bson::Bson::DateTime(bson::DateTime::now()))
             }
             _ => Err(ParseError::InvalidCommand(format!(
                 "Unsupported current time function: {}",
@@ -757,26 +787,63 @@ impl SqlExprConverter {
     }
 
     /// Convert SQL literal to BSON value (public version for use outside this module)
-    pub fn literal_to_bson_public(lit: &SqlLiteral) -> mongodb::bson::Bson {
+    pub fn literal_to_bson_public(lit: &SqlLiteral) -> bson::Bson {
         Self::literal_to_bson(lit)
     }
 
     /// Convert SQL literal to BSON value
-    fn literal_to_bson(lit: &SqlLiteral) -> mongodb::bson::Bson {
+    fn literal_to_bson(lit: &SqlLiteral) -> bson::Bson {
         match lit {
-            SqlLiteral::String(s) => mongodb::bson::Bson::String(s.clone()),
+            SqlLiteral::String(s) => bson::Bson::String(s.clone()),
             SqlLiteral::Number(n) => {
                 if n.fract() == 0.0 && *n >= i64::MIN as f64 && *n <= i64::MAX as f64 {
-                    mongodb::bson::Bson::Int64(*n as i64)
+                    bson::Bson::Int64(*n as i64)
                 } else {
-                    mongodb::bson::Bson::Double(*n)
+                    bson::Bson::Double(*n)
                 }
             }
-            SqlLiteral::Boolean(b) => mongodb::bson::Bson::Boolean(*b),
-            SqlLiteral::Null => mongodb::bson::Bson::Null,
+            SqlLiteral::Boolean(b) => bson::Bson::Boolean(*b),
+            SqlLiteral::Null => bson::Bson::Null,
         }
     }
 
+    /// Convert DATE_TRUNC('unit', date) to a $dateTrunc expression
+    fn date_trunc_to_aggregate(args: &[SqlExpr]) -> Result<bson::Bson> {
+        const UNITS: [&str; 8] = [
+            "year", "quarter", "month", "week", "day", "hour", "minute", "second",
+        ];
+
+        if args.len() != 2 {
+            return Err(ParseError::InvalidCommand(
+                "DATE_TRUNC requires 2 arguments: DATE_TRUNC('unit', date)".to_string(),
+            )
+            .into());
+        }
+
+        let unit = match &args[0] {
+            SqlExpr::Literal(SqlLiteral::String(unit)) => unit.to_lowercase(),
+            _ => {
+                return Err(ParseError::InvalidCommand(
+                    "DATE_TRUNC unit must be a string literal such as 'month'".to_string(),
+                )
+                .into());
+            }
+        };
+        if !UNITS.contains(&unit.as_str()) {
+            return Err(ParseError::InvalidCommand(format!(
+                "Unsupported DATE_TRUNC unit '{}'. Expected one of: {}",
+                unit,
+                UNITS.join(", ")
+            ))
+            .into());
+        }
+
+        let date = Self::expr_to_aggregate_value(&args[1])?;
+        Ok(bson::Bson::Document(doc! {
+            "$dateTrunc": { "date": date, "unit": unit }
+        }))
+    }
+
     /// Build a MongoDB aggregate expression for a SQL aggregate function
     pub fn build_aggregate_expr(
         func: &str,
@@ -843,12 +910,12 @@ impl SqlExprConverter {
         // Build _id field from GROUP BY columns
         if group_by.len() == 1 {
             // Single field grouping
-            group_doc.insert("_id", format!("${}", group_by[0]));
+            group_doc.insert("_id", Self::group_key_value(&group_by[0], columns)?);
         } else {
             // Multiple field grouping
             let mut id_doc = Document::new();
             for field in group_by {
-                id_doc.insert(field.clone(), format!("${}", field));
+                id_doc.insert(field.clone(), Self::group_key_value(field, columns)?);
             }
             group_doc.insert("_id", id_doc);
         }
@@ -873,13 +940,41 @@ impl SqlExprConverter {
         Ok(group_doc)
     }
 
+    /// Resolve a GROUP BY key to the value grouped on
+    ///
+    /// A key naming a SELECT expression, by alias or by its text (e.g.
+    /// `GROUP BY month` or `GROUP BY YEAR(created_at)`), groups on that
+    /// expression; any other key is a document field.
+    fn group_key_value(key: &str, columns: &[SqlColumn]) -> Result<bson::Bson> {
+        let expr = columns.iter().find_map(|col| match col {
+            SqlColumn::Expression { expr, alias } => {
+                let matches = match alias {
+                    Some(alias) => alias == key,
+                    None => expr.to_display_string().eq_ignore_ascii_case(key),
+                };
+                matches.then_some(expr)
+            }
+            _ => None,
+        });
+
+        match expr {
+            Some(expr) => Self::expr_to_aggregate_value(expr),
+            None if key.contains('(') => Err(ParseError::InvalidCommand(format!(
+                "GROUP BY expression {} must also appear in the SELECT list",
+                key
+            ))
+            .into()),
+            None => Ok(bson::Bson::String(format!("${}", key))),
+        }
+    }
+
     /// Convert FieldPath to BSON for aggregation expressions
-    pub fn field_path_to_bson(path: &FieldPath) -> Result<mongodb::bson::Bson> {
+    pub fn field_path_to_bson(path: &FieldPath) -> Result<bson::Bson> {
         match path {
-            FieldPath::Simple(name) => Ok(mongodb::bson::Bson::String(format!("${}", name))),
+            FieldPath::Simple(name) => Ok(bson::Bson::String(format!("${}", name))),
             FieldPath::Nested { base, field } => {
                 if let Some(base_str) = base.to_mongodb_path() {
-                    Ok(mongodb::bson::Bson::String(format!(
+                    Ok(bson::Bson::String(format!(
                         "${}.{}",
                         base_str, field
                     )))
@@ -907,7 +1002,7 @@ impl SqlExprConverter {
                     ArrayIndex::Negative(idx) => -*idx,
                 };
 
-                Ok(mongodb::bson::Bson::Document(doc! {
+                Ok(bson::Bson::Document(doc! {
                     "$arrayElemAt": [base_path, index_value]
                 }))
             }
@@ -928,7 +1023,7 @@ impl SqlExprConverter {
     }
 
     /// Build $slice expression for array slicing
-    fn build_slice_expr(base_path: &str, slice: &ArraySlice) -> mongodb::bson::Bson {
+    fn build_slice_expr(base_path: &str, slice: &ArraySlice) -> bson::Bson {
         let start = match &slice.start {
             Some(SliceIndex::Positive(n)) => *n,
             Some(SliceIndex::Negative(n)) => -*n,
@@ -940,7 +1035,7 @@ impl SqlExprConverter {
             (Some(SliceIndex::Positive(s)), Some(SliceIndex::Positive(e))) => e - s,
             (None, None) => {
                 // Full slice - return the array as-is
-                return mongodb::bson::Bson::String(base_path.to_string());
+                return bson::Bson::String(base_path.to_string());
             }
             _ => {
                 // Complex slice with negative indices - use conditional logic
@@ -952,11 +1047,11 @@ impl SqlExprConverter {
         if slice.step.is_some() && slice.step != Some(1) {
             // Step not equal to 1 requires more complex aggregation
             // For now, just do basic slice
-            mongodb::bson::Bson::Document(doc! {
+            bson::Bson::Document(doc! {
                 "$slice": [base_path, start, count]
             })
         } else {
-            mongodb::bson::Bson::Document(doc! {
+            bson::Bson::Document(doc! {
                 "$slice": [base_path, start, count]
             })
         }
@@ -983,10 +1078,10 @@ mod tests {
         let projection = SqlExprConverter::columns_to_projection(&columns).unwrap();
         assert!(projection.is_some());
         let proj = projection.unwrap();
-        assert_eq!(proj.get("name"), Some(&mongodb::bson::Bson::Int32(1)));
-        assert_eq!(proj.get("age"), Some(&mongodb::bson::Bson::Int32(1)));
+        assert_eq!(proj.get("name"), Some(&bson::Bson::Int32(1)));
+        assert_eq!(proj.get("age"), Some(&bson::Bson::Int32(1)));
         // _id should be excluded when not explicitly requested
-        assert_eq!(proj.get("_id"), Some(&mongodb::bson::Bson::Int32(0)));
+        assert_eq!(proj.get("_id"), Some(&bson::Bson::Int32(0)));
     }
 
     #[test]
@@ -998,8 +1093,8 @@ mod tests {
         let projection = SqlExprConverter::columns_to_projection(&columns).unwrap();
         assert!(projection.is_some());
         let proj = projection.unwrap();
-        assert_eq!(proj.get("_id"), Some(&mongodb::bson::Bson::Int32(1)));
-        assert_eq!(proj.get("name"), Some(&mongodb::bson::Bson::Int32(1)));
+        assert_eq!(proj.get("_id"), Some(&bson::Bson::Int32(1)));
+        assert_eq!(proj.get("name"), Some(&bson::Bson::Int32(1)));
     }
 
     #[test]
@@ -1008,7 +1103,7 @@ mod tests {
         let right = SqlExpr::Literal(SqlLiteral::Number(18.0));
         let filter =
             SqlExprConverter::binary_op_to_filter(&left, &SqlOperator::Eq, &right).unwrap();
-        assert_eq!(filter.get("age"), Some(&mongodb::bson::Bson::Int64(18)));
+        assert_eq!(filter.get("age"), Some(&bson::Bson::Int64(18)));
     }
 
     #[test]
@@ -1018,7 +1113,7 @@ mod tests {
         let filter =
             SqlExprConverter::binary_op_to_filter(&left, &SqlOperator::Gt, &right).unwrap();
         let age_doc = filter.get_document("age").unwrap();
-        assert_eq!(age_doc.get("$gt"), Some(&mongodb::bson::Bson::Int64(18)));
+        assert_eq!(age_doc.get("$gt"), Some(&bson::Bson::Int64(18)));
     }
 
     #[test]
@@ -1053,35 +1148,35 @@ mod tests {
     fn test_is_null_to_filter() {
         let expr = SqlExpr::FieldPath(FieldPath::simple("name".to_string()));
         let filter = SqlExprConverter::is_null_to_filter(&expr, false).unwrap();
-        assert_eq!(filter.get("name"), Some(&mongodb::bson::Bson::Null));
+        assert_eq!(filter.get("name"), Some(&bson::Bson::Null));
     }
 
     #[test]
     fn test_literal_to_bson_string() {
         let lit = SqlLiteral::String("hello".to_string());
         let bson = SqlExprConverter::literal_to_bson(&lit);
-        assert_eq!(bson, mongodb::bson::Bson::String("hello".to_string()));
+        assert_eq!(bson, bson::Bson::String("hello".to_string()));
     }
 
     #[test]
     fn test_literal_to_bson_number_int() {
         let lit = SqlLiteral::Number(42.0);
         let bson = SqlExprConverter::literal_to_bson(&lit);
-        assert_eq!(bson, mongodb::bson::Bson::Int64(42));
+        assert_eq!(bson, bson::Bson::Int64(42));
     }
 
     #[test]
     fn test_literal_to_bson_number_float() {
         let lit = SqlLiteral::Number(3.14);
         let bson = SqlExprConverter::literal_to_bson(&lit);
-        assert_eq!(bson, mongodb::bson::Bson::Double(3.14));
+        assert_eq!(bson, bson::Bson::Double(3.14));
     }
 
     #[test]
     fn test_literal_to_bson_bool() {
         let lit = SqlLiteral::Boolean(true);
         let bson = SqlExprConverter::literal_to_bson(&lit);
-        assert_eq!(bson, mongodb::bson::Bson::Boolean(true));
+        assert_eq!(bson, bson::Bson::Boolean(true));
     }
 
     #[test]
@@ -1108,7 +1203,7 @@ mod tests {
         let result = SqlExprConverter::function_to_bson("ISODate", &args);
         assert!(result.is_ok());
         match result.unwrap() {
-            mongodb::bson::Bson::DateTime(_) => {} // Success
+            bson::Bson::DateTime(_) => {} // Success
             _ => panic!("Expected DateTime type"),
         }
     }
@@ -1121,7 +1216,7 @@ mod tests {
         let result = SqlExprConverter::function_to_bson("DATE", &args);
         assert!(result.is_ok());
         match result.unwrap() {
-            mongodb::bson::Bson::DateTime(_) => {} // Success
+            bson::Bson::DateTime(_) => {} // Success
             _ => panic!("Expected DateTime type"),
         }
     }
@@ -1134,7 +1229,7 @@ mod tests {
         let result = SqlExprConverter::function_to_bson("ISODate", &args);
         assert!(result.is_ok());
         match result.unwrap() {
-            mongodb::bson::Bson::DateTime(_) => {} // Success
+            bson::Bson::DateTime(_) => {} // Success
             _ => panic!("Expected DateTime type"),
         }
     }
@@ -1161,4 +1256,29 @@ mod tests {
         let result = SqlExprConverter::function_to_bson("ISODate", &args);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_date_part_functions() {
+        let field = SqlExpr::FieldPath(FieldPath::simple("created_at".to_string()));
+        let year = SqlExprConverter::function_to_aggregate("year", &[field.clone()]).unwrap();
+        assert_eq!(year, bson::Bson::Document(doc! { "$year": "$created_at" }));
+
+        let trunc = SqlExprConverter::function_to_aggregate(
+            "DATE_TRUNC",
+            &[SqlExpr::Literal(SqlLiteral::String("Month".to_string())), field.clone()],
+        )
+        .unwrap();
+        assert_eq!(
+            trunc,
+            bson::Bson::Document(doc! {
+                "$dateTrunc": { "date": "$created_at", "unit": "month" }
+            })
+        );
+
+        assert!(SqlExprConverter::function_to_aggregate(
+            "DATE_TRUNC",
+            &[SqlExpr::Literal(SqlLiteral::String("fortnight".to_string())), field],
+        )
+        .is_err());
+    }
 }
@@ -145,6 +145,38 @@ impl SqlLexer {
         tokens
     }
 
+    /// Find a string literal that runs to the end of input without its
+    /// closing quote
+    ///
+    /// Tokenizing never fails, so the parser uses this to point at the
+    /// literal instead of silently reading the rest of the query as text.
+    ///
+    /// # Arguments
+    /// * `input` - Tokenized input
+    /// * `tokens` - Tokens returned by `tokenize`
+    ///
+    /// # Returns
+    /// * `Option<Range<usize>>` - Span of the first unterminated literal
+    pub fn unterminated_string(input: &str, tokens: &[Token]) -> Option<Range<usize>> {
+        let chars: Vec<char> = input.chars().collect();
+        tokens
+            .iter()
+            .filter(|token| matches!(token.kind, TokenKind::String(_)))
+            .find(|token| {
+                let quote = chars.get(token.span.start).copied();
+                let mut i = token.span.start + 1;
+                while i < token.span.end {
+                    match chars.get(i).copied() {
+                        Some('\\') => i += 2,
+                        c if c == quote => return false,
+                        _ => i += 1,
+                    }
+                }
+                true
+            })
+            .map(|token| token.span.clone())
+    }
+
     /// Get the next token
     fn next_token(&mut self) -> Token {
         self.skip_whitespace();
@@ -302,28 +334,55 @@ impl SqlLexer {
         Token::new(TokenKind::String(value), start..self.pos)
     }
 
-    /// Scan a number (integer or decimal)
+    /// Scan a number (integer, decimal or scientific notation)
+    ///
+    /// Underscores between digits are accepted as separators (`1_000_000`)
+    /// and dropped from the token value.
     fn scan_number(&mut self, start: usize) -> Token {
         let mut value = String::new();
 
-        while !self.is_at_end() && self.current_char().is_ascii_digit() {
-            value.push(self.current_char());
-            self.advance();
-        }
+        self.scan_digits(&mut value);
 
         // Handle decimal point
         if self.current_char() == '.' && self.peek_char().is_ascii_digit() {
             value.push('.');
             self.advance();
-            while !self.is_at_end() && self.current_char().is_ascii_digit() {
-                value.push(self.current_char());
+            self.scan_digits(&mut value);
+        }
+
+        // Handle exponent: e10, E-3, e+6
+        if matches!(self.current_char(), 'e' | 'E') {
+            let next = self.peek_char();
+            let after_sign = self.input.get(self.pos + 2).copied().unwrap_or('\0');
+            if next.is_ascii_digit() || (matches!(next, '+' | '-') && after_sign.is_ascii_digit())
+            {
+                value.push('e');
                 self.advance();
+                if matches!(self.current_char(), '+' | '-') {
+                    value.push(self.current_char());
+                    self.advance();
+                }
+                self.scan_digits(&mut value);
             }
         }
 
         Token::new(TokenKind::Number(value), start..self.pos)
     }
 
+    /// Scan a run of digits, skipping underscores placed between digits
+    fn scan_digits(&mut self, value: &mut String) {
+        while !self.is_at_end() {
+            let ch = self.current_char();
+            let is_separator = ch == '_' && !value.is_empty() && self.peek_char().is_ascii_digit();
+            if ch.is_ascii_digit() {
+                value.push(ch);
+            } else if !is_separator {
+                break;
+            }
+            self.advance();
+        }
+    }
+
     /// Scan an identifier or keyword
     fn scan_identifier(&mut self, start: usize) -> Token {
         let mut value = String::new();
@@ -518,6 +577,17 @@ mod tests {
         assert!(matches!(tokens[7].kind, TokenKind::Number(ref s) if s == "18"));
     }
 
+    #[test]
+    fn test_unterminated_string() {
+        let input = "SELECT * FROM users WHERE name = 'Jo\\'hn";
+        let tokens = SqlLexer::tokenize(input);
+        assert_eq!(SqlLexer::unterminated_string(input, &tokens), Some(33..40));
+
+        let input = "SELECT * FROM users WHERE name = 'it\\'s' AND a = \"\"";
+        let tokens = SqlLexer::tokenize(input);
+        assert_eq!(SqlLexer::unterminated_string(input, &tokens), None);
+    }
+
     #[test]
     fn test_tokenize_partial_input() {
         let tokens = SqlLexer::tokenize("SELECT * FR");
@@ -594,4 +664,23 @@ mod tests {
                 .any(|t| matches!(t.kind, TokenKind::Unknown('@')))
         );
     }
+
+    #[test]
+    fn test_tokenize_number_separators_and_exponent() {
+        let number = |input: &str| match &SqlLexer::tokenize(input)[0].kind {
+            TokenKind::Number(s) => s.clone(),
+            other => panic!("Expected number, got {:?}", other),
+        };
+
+        assert_eq!(number("1_000_000"), "1000000");
+        assert_eq!(number("1_234.5_6"), "1234.56");
+        assert_eq!(number("1e6"), "1e6");
+        assert_eq!(number("2.5E-3"), "2.5e-3");
+        assert_eq!(number("4e+2"), "4e+2");
+
+        // Trailing underscores and bare exponents are not part of the number
+        let tokens = SqlLexer::tokenize("10_ 3e");
+        assert!(matches!(tokens[0].kind, TokenKind::Number(ref s) if s == "10"));
+        assert!(tokens.iter().any(|t| matches!(t.kind, TokenKind::Number(ref s) if s == "3")));
+    }
 }
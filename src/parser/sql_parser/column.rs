@@ -30,6 +30,12 @@ impl super::SqlParser {
                     let saved_pos = self.pos;
                     self.advance();
 
+                    // Function call such as YEAR(created_at)
+                    if self.check_token(&TokenKind::LParen) {
+                        self.pos = saved_pos;
+                        return self.parse_expression_column();
+                    }
+
                     // Parse field path (supports nested fields and array access)
                     let path = match self.parse_field_path_continuation(FieldPath::simple(name)) {
                         Ok(p) => p,
@@ -7,7 +7,7 @@
 //! - Handling GROUP BY, ORDER BY, LIMIT, OFFSET
 //! - EXPLAIN query wrapping
 
-use mongodb::bson::{Document, doc};
+use bson::{Document, doc};
 
 use super::super::command::{AggregateOptions, Command, FindOptions, QueryCommand};
 use super::super::sql_context::{SqlColumn, SqlExpr, SqlSelect};
@@ -121,7 +121,9 @@ impl super::SqlParser {
                 self.expr_has_complex_paths(expr)
                     || values.iter().any(|v| self.expr_has_complex_paths(v))
             }
-            SqlExpr::Like { expr, .. } | SqlExpr::IsNull { expr, .. } => {
+            SqlExpr::Like { expr, .. }
+            | SqlExpr::IsNull { expr, .. }
+            | SqlExpr::IsMissing { expr, .. } => {
                 self.expr_has_complex_paths(expr)
             }
             _ => false,
@@ -281,7 +283,7 @@ impl super::SqlParser {
         } else if has_aggregates {
             // No GROUP BY but has aggregates: aggregate over entire collection (e.g., SELECT COUNT(*) FROM ...)
             let mut group_doc = Document::new();
-            group_doc.insert("_id", mongodb::bson::Bson::Null); // Group all documents together
+            group_doc.insert("_id", bson::Bson::Null); // Group all documents together
 
             // Add aggregate functions - collect intermediate results for expressions
             let mut expr_columns: Vec<(&SqlExpr, Option<&String>)> = Vec::new();
@@ -518,7 +520,7 @@ impl super::SqlParser {
     }
 
     /// Build expression for $project stage that references $group results
-    pub(super) fn build_post_group_expr(expr: &SqlExpr) -> Result<mongodb::bson::Bson> {
+    pub(super) fn build_post_group_expr(expr: &SqlExpr) -> Result<bson::Bson> {
         match expr {
             SqlExpr::Function { name, args } => {
                 let upper = name.to_uppercase();
@@ -529,7 +531,7 @@ impl super::SqlParser {
                     } else {
                         format!("_agg_{}_{}", upper.to_lowercase(), args.len())
                     };
-                    Ok(mongodb::bson::Bson::String(format!("${}", field_name)))
+                    Ok(bson::Bson::String(format!("${}", field_name)))
                 } else {
                     SqlExprConverter::expr_to_aggregate_value(expr)
                 }
@@ -537,7 +539,7 @@ impl super::SqlParser {
             SqlExpr::ArithmeticOp { left, op, right } => {
                 let left_expr = Self::build_post_group_expr(left)?;
                 let right_expr = Self::build_post_group_expr(right)?;
-                Ok(mongodb::bson::Bson::Document(doc! {
+                Ok(bson::Bson::Document(doc! {
                     op.to_mongo_operator(): [left_expr, right_expr]
                 }))
             }
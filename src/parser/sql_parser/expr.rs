@@ -3,6 +3,7 @@
 //! This module handles parsing of:
 //! - Logical expressions (AND, OR)
 //! - Comparison expressions (=, !=, >, <, >=, <=)
+//! - IS [NOT] NULL and IS [NOT] MISSING predicates
 //! - Arithmetic expressions (+, -, *, /, %)
 //! - Literals (numbers, strings, booleans, null)
 //! - Function calls
@@ -98,6 +99,11 @@ impl super::SqlParser {
 
     /// Parse comparison operator and right side, given the left side
     pub(super) fn parse_comparison_with_left(&mut self, left: SqlExpr) -> ParseResult<SqlExpr> {
+        // IS [NOT] NULL / IS [NOT] MISSING
+        if self.match_keyword(&TokenKind::Is) {
+            return self.parse_is_predicate(left);
+        }
+
         // Check for comparison operator
         let op = if self.match_token(&TokenKind::Eq) {
             SqlOperator::Eq
@@ -180,6 +186,37 @@ impl super::SqlParser {
         })
     }
 
+    /// Parse the rest of an IS predicate: [NOT] NULL or [NOT] MISSING
+    fn parse_is_predicate(&mut self, left: SqlExpr) -> ParseResult<SqlExpr> {
+        let negated = self.match_keyword(&TokenKind::Not);
+
+        if self.is_at_eof() {
+            self.expected = vec![Expected::Keyword("NULL"), Expected::Keyword("MISSING")];
+            return ParseResult::Partial(left, self.expected.clone());
+        }
+
+        match self.peek_kind() {
+            Some(TokenKind::Null) => {
+                self.advance();
+                ParseResult::Ok(SqlExpr::IsNull {
+                    expr: Box::new(left),
+                    negated,
+                })
+            }
+            Some(TokenKind::Ident(name)) if name.eq_ignore_ascii_case("MISSING") => {
+                self.advance();
+                ParseResult::Ok(SqlExpr::IsMissing {
+                    expr: Box::new(left),
+                    negated,
+                })
+            }
+            _ => ParseResult::Error(ParseError::new(
+                "Expected NULL or MISSING after IS".to_string(),
+                self.current_position()..self.current_position(),
+            )),
+        }
+    }
+
     /// Parse an arithmetic expression with operator precedence (Pratt parser)
     pub(super) fn parse_arithmetic_expr(&mut self, min_bp: u8) -> ParseResult<SqlExpr> {
         // Parse left-hand side (atom: literal, field, or parenthesized expression)
@@ -68,6 +68,13 @@ impl SqlParser {
     /// Parse SQL and convert to Command
     pub fn parse_to_command(input: &str) -> Result<Command> {
         let tokens = SqlLexer::tokenize(input);
+        if let Some(span) = SqlLexer::unterminated_string(input, &tokens) {
+            return Err(
+                crate::error::ParseError::at("Unterminated string literal", input, span)
+                    .with_hint("close the string with the quote it starts with")
+                    .into(),
+            );
+        }
         let mut parser = Self::new(tokens);
 
         // Check if this is an EXPLAIN statement
@@ -145,11 +152,18 @@ impl SqlParser {
                     Ok(cmd)
                 }
             }
-            ParseResult::Error(err) => Err(crate::error::ParseError::InvalidCommand(format!(
-                "SQL parse error: {}",
-                err.message
-            ))
-            .into()),
+            ParseResult::Error(err) => {
+                let error = crate::error::ParseError::at(
+                    format!("SQL parse error: {}", err.message),
+                    input,
+                    err.span,
+                );
+                Err(match err.hint {
+                    Some(hint) => error.with_hint(hint),
+                    None => error,
+                }
+                .into())
+            }
         }
     }
 
@@ -453,30 +467,45 @@ impl SqlParser {
         loop {
             if let Some(TokenKind::Ident(name)) = self.peek_kind() {
                 let name = name.clone();
+                let saved_pos = self.pos;
                 self.advance();
 
-                // Parse field path (supports nested fields and array access)
-                let path = match self
-                    .parse_field_path_continuation(super::sql_context::FieldPath::simple(name))
-                {
-                    Ok(p) => p,
-                    Err(err) => {
+                // Function call such as YEAR(created_at), matched against the
+                // SELECT list by its text when the pipeline is built
+                if self.check_token(&TokenKind::LParen) {
+                    self.pos = saved_pos;
+                    match self.parse_value_atom() {
+                        ParseResult::Ok(expr) => columns.push(expr.to_display_string()),
+                        ParseResult::Partial(_, exp) => {
+                            self.expected = exp.clone();
+                            return ParseResult::Partial(columns, exp);
+                        }
+                        ParseResult::Error(err) => return ParseResult::Error(err),
+                    }
+                } else {
+                    // Parse field path (supports nested fields and array access)
+                    let path = match self
+                        .parse_field_path_continuation(super::sql_context::FieldPath::simple(name))
+                    {
+                        Ok(p) => p,
+                        Err(err) => {
+                            return ParseResult::Error(ParseError::new(
+                                err.to_user_message(),
+                                self.current_position()..self.current_position(),
+                            ));
+                        }
+                    };
+
+                    // For GROUP BY, we need to convert path to string for now
+                    // TODO: Update GROUP BY to use FieldPath directly
+                    if let Some(path_str) = path.to_mongodb_path() {
+                        columns.push(path_str);
+                    } else {
                         return ParseResult::Error(ParseError::new(
-                            err.to_user_message(),
+                            "Array access in GROUP BY requires aggregation pipeline".to_string(),
                             self.current_position()..self.current_position(),
                         ));
                     }
-                };
-
-                // For GROUP BY, we need to convert path to string for now
-                // TODO: Update GROUP BY to use FieldPath directly
-                if let Some(path_str) = path.to_mongodb_path() {
-                    columns.push(path_str);
-                } else {
-                    return ParseResult::Error(ParseError::new(
-                        "Array access in GROUP BY requires aggregation pipeline".to_string(),
-                        self.current_position()..self.current_position(),
-                    ));
                 }
             } else if self.is_at_eof() {
                 self.expected = vec![Expected::ColumnName];
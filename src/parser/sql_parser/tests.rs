@@ -142,6 +142,22 @@ mod tests {
         assert!(err_msg.contains("WHERE clause must appear before"));
     }
 
+    #[test]
+    fn test_parse_error_shows_source_span() {
+        let input = "SELECT * FROM tasks ORDER BY created_at WHERE status='active'";
+        let err_msg = SqlParser::parse_to_command(input).unwrap_err().to_string();
+        let position = input.find("WHERE").unwrap();
+        assert!(err_msg.contains(&format!("1 | {}", input)));
+        assert!(err_msg.contains(&format!("  | {}^\n", " ".repeat(position))));
+        assert!(err_msg.contains("= hint: Move the WHERE clause before"));
+
+        let err_msg = SqlParser::parse_to_command("SELECT * FROM users WHERE name = 'Jo")
+            .unwrap_err()
+            .to_string();
+        assert!(err_msg.contains("Unterminated string literal"));
+        assert!(err_msg.contains(&format!("  | {}^^^", " ".repeat(33))));
+    }
+
     #[test]
     fn test_correct_clause_order_accepted() {
         // This should be accepted - correct order
@@ -196,7 +212,7 @@ mod tests {
 
             // The value should be an ObjectId
             let value = filter.get("group_id").unwrap();
-            assert!(matches!(value, mongodb::bson::Bson::ObjectId(_)));
+            assert!(matches!(value, bson::Bson::ObjectId(_)));
         } else {
             panic!("Expected Find command");
         }
@@ -1082,4 +1098,75 @@ mod tests {
             panic!("Expected Aggregate command for DISTINCT nested field");
         }
     }
+
+    #[test]
+    fn test_parse_is_null_and_is_missing() {
+        let filter_of = |sql: &str| match SqlParser::parse_to_command(sql) {
+            Ok(Command::Query(QueryCommand::Find { filter, .. })) => filter,
+            other => panic!("Expected Find command, got {:?}", other),
+        };
+
+        let filter = filter_of("SELECT * FROM users WHERE email IS NULL");
+        assert_eq!(filter, bson::doc! { "email": null });
+
+        let filter = filter_of("SELECT * FROM users WHERE email IS NOT NULL");
+        assert_eq!(filter, bson::doc! { "email": { "$ne": null } });
+
+        let filter = filter_of("SELECT * FROM users WHERE email IS MISSING");
+        assert_eq!(filter, bson::doc! { "email": { "$exists": false } });
+
+        let filter = filter_of("SELECT * FROM users WHERE profile.phone is not missing AND age > 18");
+        let conditions = filter.get_array("$and").unwrap();
+        assert_eq!(
+            conditions[0].as_document().unwrap(),
+            &bson::doc! { "profile.phone": { "$exists": true } }
+        );
+
+        assert!(SqlParser::parse_to_command("SELECT * FROM users WHERE email IS 5").is_err());
+    }
+
+    #[test]
+    fn test_group_by_date_functions() {
+        let pipeline_of = |sql: &str| match SqlParser::parse_to_command(sql) {
+            Ok(Command::Query(QueryCommand::Aggregate { pipeline, .. })) => pipeline,
+            other => panic!("Expected Aggregate command, got {:?}", other),
+        };
+
+        let pipeline = pipeline_of(
+            "SELECT DATE_TRUNC('month', created_at) AS month, COUNT(*) AS orders FROM orders GROUP BY month ORDER BY month",
+        );
+        let group = pipeline[0].get_document("$group").unwrap();
+        assert_eq!(
+            group.get_document("_id").unwrap(),
+            &bson::doc! { "$dateTrunc": { "date": "$created_at", "unit": "month" } }
+        );
+        let project = pipeline[1].get_document("$project").unwrap();
+        assert_eq!(project.get_str("month").unwrap(), "$_id");
+
+        let pipeline = pipeline_of(
+            "SELECT YEAR(created_at), MONTH(created_at), SUM(total) FROM orders GROUP BY YEAR(created_at), MONTH(created_at)",
+        );
+        let id = pipeline[0]
+            .get_document("$group")
+            .unwrap()
+            .get_document("_id")
+            .unwrap();
+        assert_eq!(
+            id.get_document("YEAR(created_at)").unwrap(),
+            &bson::doc! { "$year": "$created_at" }
+        );
+        assert!(id.contains_key("MONTH(created_at)"));
+
+        let pipeline = pipeline_of("SELECT name, YEAR(created_at) AS y FROM users");
+        let project = pipeline[0].get_document("$project").unwrap();
+        assert_eq!(
+            project.get_document("y").unwrap(),
+            &bson::doc! { "$year": "$created_at" }
+        );
+
+        assert!(
+            SqlParser::parse_to_command("SELECT COUNT(*) FROM orders GROUP BY YEAR(created_at)")
+                .is_err()
+        );
+    }
 }
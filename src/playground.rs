@@ -0,0 +1,160 @@
+//! Offline query playground
+//!
+//! Everything a browser playground needs without a server: checking that a
+//! command parses, showing the shell query a SQL statement translates to and
+//! rendering documents the way the shell prints them. Only the parser and the
+//! formatter are used, so with
+//!
+//! ```text
+//! cargo build --target wasm32-unknown-unknown --no-default-features --features wasm
+//! ```
+//!
+//! the module compiles to WebAssembly; a `wasm-bindgen` wrapper exposes these
+//! functions to JavaScript.
+
+use std::ops::Range;
+
+use bson::{Bson, Document};
+use serde_json::Value as JsonValue;
+
+use crate::config::{DisplayConfig, OutputFormat};
+use crate::error::{ExecutionError, MongoshError, ParseError, Result};
+use crate::executor::{ExecutionResult, ExecutionStats, ResultData};
+use crate::formatter::Formatter;
+use crate::parser::{Command, Parser, UtilityCommand, shell_query};
+
+/// Why a command does not parse
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Problem {
+    /// Error message
+    pub message: String,
+
+    /// How to fix it, when known
+    pub hint: Option<String>,
+
+    /// Character range of the offending text in the input, when known
+    pub span: Option<Range<usize>>,
+}
+
+/// Check that a shell or SQL command parses
+///
+/// # Arguments
+/// * `input` - Command as typed in the shell
+///
+/// # Returns
+/// * `Option<Problem>` - `None` when the command parses
+pub fn validate(input: &str) -> Option<Problem> {
+    let error = Parser::new().parse(input).err()?;
+    Some(match error {
+        MongoshError::Parse(ParseError::Located {
+            message,
+            source,
+            span,
+            hint,
+        }) => {
+            // The span is relative to the parsed part, e.g. the command before a pipe
+            let base = input
+                .find(source.as_str())
+                .map_or(0, |byte| input[..byte].chars().count());
+            Problem {
+                message,
+                hint,
+                span: Some(base + span.start..base + span.end),
+            }
+        }
+        other => Problem {
+            message: other.to_string(),
+            hint: None,
+            span: None,
+        },
+    })
+}
+
+/// Translate a SQL query to the shell query it runs as
+///
+/// # Arguments
+/// * `sql` - SQL query, e.g. `SELECT name FROM users WHERE age > 30`
+///
+/// # Returns
+/// * `Result<String>` - `db.<collection>.find(...)` or `.aggregate(...)`
+pub fn sql_to_shell(sql: &str) -> Result<String> {
+    match Parser::new().parse(&format!("sql explain {}", sql))? {
+        Command::Utility(UtilityCommand::SqlExplain(command)) => shell_query(&command),
+        _ => Err(ParseError::InvalidQuery("Not a SQL query".to_string()).into()),
+    }
+}
+
+/// Render documents as the shell would print them
+///
+/// # Arguments
+/// * `json` - Document or array of documents as (Extended) JSON
+/// * `format` - Output format, e.g. `OutputFormat::Table`
+///
+/// # Returns
+/// * `Result<String>` - Formatted text without colors
+pub fn format_documents(json: &str, format: OutputFormat) -> Result<String> {
+    let value: JsonValue = serde_json::from_str(json)
+        .map_err(|e| ParseError::InvalidQuery(format!("Invalid JSON: {}", e)))?;
+    let documents = match Bson::try_from(value)
+        .map_err(|e| ParseError::InvalidQuery(format!("Invalid Extended JSON: {}", e)))?
+    {
+        Bson::Document(doc) => vec![doc],
+        Bson::Array(items) => items
+            .into_iter()
+            .map(|item| match item {
+                Bson::Document(doc) => Ok(doc),
+                other => Err(ExecutionError::InvalidOperation(format!(
+                    "Expected documents, found {}",
+                    other
+                ))),
+            })
+            .collect::<std::result::Result<Vec<Document>, _>>()?,
+        _ => {
+            return Err(ExecutionError::InvalidOperation(
+                "Expected a document or an array of documents".to_string(),
+            )
+            .into());
+        }
+    };
+
+    let display = DisplayConfig {
+        format,
+        color_output: false,
+        show_timing: false,
+        ..DisplayConfig::default()
+    };
+    let result = ExecutionResult {
+        success: true,
+        data: ResultData::Documents(documents),
+        stats: ExecutionStats::default(),
+        error: None,
+    };
+    Formatter::from_config(&display).format(&result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate() {
+        assert!(validate("db.users.find({ age: { $gt: 30 } })").is_none());
+        let problem = validate("db.users.find({ age: })").unwrap();
+        assert!(!problem.message.is_empty());
+    }
+
+    #[test]
+    fn test_sql_to_shell() {
+        let shell = sql_to_shell("SELECT name FROM users WHERE age > 30 LIMIT 5").unwrap();
+        assert!(shell.starts_with("db.users.find({\"age\":{\"$gt\":30}}"));
+        assert!(shell.ends_with(".limit(5)"));
+        assert!(sql_to_shell("db.users.find()").is_err());
+    }
+
+    #[test]
+    fn test_format_documents() {
+        let output = format_documents(r#"[{"name": "Ada"}]"#, OutputFormat::Json).unwrap();
+        assert!(output.contains("\"name\":\"Ada\""));
+        assert!(format_documents("[1, 2]", OutputFormat::Shell).is_err());
+    }
+}
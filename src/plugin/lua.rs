@@ -0,0 +1,234 @@
+//! Lua automation scripts
+//!
+//! With the `lua` feature, `load("report.lua")` and `mongosh --file report.lua`
+//! run a Lua 5.4 script instead of shell statements. Scripts get the same
+//! `db` and `print` bindings as shell scripts:
+//!
+//! ```lua
+//! local pending = db.orders:countDocuments({ status = "pending" })
+//! print("pending orders:", pending)
+//!
+//! for _, user in ipairs(db.users:find({ age = { ["$gt"] = 30 } })) do
+//!     print(user.name, user.age)
+//! end
+//! ```
+//!
+//! `db.<collection>:<method>(...)` runs `db.<collection>.<method>(...)` with
+//! the arguments converted to JSON and returns the result as Lua values:
+//! documents become tables, counts numbers. `find` returns the first batch of
+//! documents, as the shell displays it. Lua tables have no key order, so a
+//! sort on several fields needs an aggregation with one `$sort` per field.
+
+use std::sync::Arc;
+
+use mlua::{Error as LuaError, Lua, LuaSerdeExt, Table, Value, Variadic};
+use serde_json::Value as JsonValue;
+
+use crate::error::{MongoshError, Result};
+
+/// Runs a shell command for a script, returning its result as JSON
+type Runner = dyn Fn(&str) -> Result<JsonValue> + Send + Sync;
+
+/// A Lua interpreter with the shell bindings installed
+pub struct LuaScript {
+    lua: Lua,
+}
+
+impl LuaScript {
+    /// Create an interpreter
+    ///
+    /// # Arguments
+    /// * `run` - Runs a shell command, returning its result as relaxed
+    ///   Extended JSON
+    /// * `print` - Writes a line printed by the script
+    ///
+    /// # Returns
+    /// * `Result<Self>` - Interpreter with `db` and `print` defined
+    pub fn new<R, P>(run: R, print: P) -> Result<Self>
+    where
+        R: Fn(&str) -> Result<JsonValue> + Send + Sync + 'static,
+        P: Fn(&str) + Send + Sync + 'static,
+    {
+        let lua = Lua::new();
+        let db = database(&lua, Arc::new(run))?;
+        let print = print_function(&lua, print)?;
+        let globals = lua.globals();
+        globals.set("db", db).map_err(lua_error)?;
+        globals.set("print", print).map_err(lua_error)?;
+        Ok(Self { lua })
+    }
+
+    /// Run a script
+    ///
+    /// # Arguments
+    /// * `name` - Script name shown in error messages, e.g. its path
+    /// * `source` - Lua source
+    ///
+    /// # Returns
+    /// * `Result<()>` - Error raised by the script or a command it ran
+    pub fn exec(&self, name: &str, source: &str) -> Result<()> {
+        self.lua
+            .load(source)
+            .set_name(name)
+            .exec()
+            .map_err(lua_error)
+    }
+}
+
+/// The `db` table: indexing it yields a collection
+fn database(lua: &Lua, run: Arc<Runner>) -> Result<Table> {
+    let db = lua.create_table().map_err(lua_error)?;
+    let index = lua
+        .create_function(move |lua, (_, name): (Table, String)| collection(lua, &name, run.clone()))
+        .map_err(lua_error)?;
+    let meta = lua.create_table().map_err(lua_error)?;
+    meta.set("__index", index).map_err(lua_error)?;
+    db.set_metatable(Some(meta));
+    Ok(db)
+}
+
+/// A collection table: indexing it yields a method running a shell command
+fn collection(lua: &Lua, name: &str, run: Arc<Runner>) -> mlua::Result<Table> {
+    let target = if is_identifier(name) {
+        format!("db.{}", name)
+    } else {
+        format!("db[{}]", JsonValue::String(name.to_string()))
+    };
+
+    let collection = lua.create_table()?;
+    let meta = lua.create_table()?;
+    meta.set(
+        "__index",
+        lua.create_function(move |lua, (this, method): (Table, String)| {
+            let call = format!("{}.{}", target, method);
+            let run = run.clone();
+            lua.create_function(move |lua, args: Variadic<Value>| {
+                // `db.users:find(...)` passes the collection first
+                let skip = matches!(args.first(), Some(Value::Table(t)) if *t == this);
+                let args = args
+                    .into_iter()
+                    .skip(skip as usize)
+                    .map(|value| {
+                        lua.from_value::<JsonValue>(value)
+                            .map(|json| json.to_string())
+                    })
+                    .collect::<mlua::Result<Vec<_>>>()?;
+
+                let command = format!("{}({})", call, args.join(", "));
+                let result = run(&command).map_err(|e| LuaError::RuntimeError(e.to_string()))?;
+                lua.to_value(&result)
+            })
+        })?,
+    )?;
+    collection.set_metatable(Some(meta));
+    Ok(collection)
+}
+
+/// `print(...)`: strings as is, tables as JSON, separated by spaces
+fn print_function<P>(lua: &Lua, print: P) -> Result<mlua::Function>
+where
+    P: Fn(&str) + Send + Sync + 'static,
+{
+    lua.create_function(move |lua, values: Variadic<Value>| {
+        let parts = values
+            .into_iter()
+            .map(|value| match value {
+                Value::Nil => Ok("nil".to_string()),
+                Value::Boolean(b) => Ok(b.to_string()),
+                Value::Integer(i) => Ok(i.to_string()),
+                Value::Number(n) => Ok(n.to_string()),
+                Value::String(s) => Ok(s.to_string_lossy().to_string()),
+                other => lua
+                    .from_value::<JsonValue>(other)
+                    .map(|json| json.to_string()),
+            })
+            .collect::<mlua::Result<Vec<_>>>()?;
+        print(&parts.join(" "));
+        Ok(())
+    })
+    .map_err(lua_error)
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+}
+
+fn lua_error(e: LuaError) -> MongoshError {
+    MongoshError::Generic(e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::sync::Mutex;
+
+    /// Interpreter recording the commands it runs and the lines it prints
+    fn script(reply: JsonValue) -> (LuaScript, Arc<Mutex<Vec<String>>>) {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let commands = log.clone();
+        let lines = log.clone();
+        let script = LuaScript::new(
+            move |command| {
+                commands.lock().unwrap().push(command.to_string());
+                Ok(reply.clone())
+            },
+            move |line| lines.lock().unwrap().push(format!("> {}", line)),
+        )
+        .unwrap();
+        (script, log)
+    }
+
+    #[test]
+    fn test_lua_collection_methods() {
+        let (script, log) = script(json!([{ "name": "Ada", "age": 36 }]));
+        script
+            .exec(
+                "test.lua",
+                r#"
+                local users = db.users:find({ age = { ["$gt"] = 30 } })
+                print(users[1].name, users[1].age)
+                db["order-items"]:countDocuments()
+                "#,
+            )
+            .unwrap();
+
+        assert_eq!(
+            *log.lock().unwrap(),
+            vec![
+                "db.users.find({\"age\":{\"$gt\":30}})",
+                "> Ada 36",
+                "db[\"order-items\"].countDocuments()",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lua_dot_call_keeps_arguments() {
+        let (script, log) = script(json!({ "deletedCount": 1 }));
+        script
+            .exec("test.lua", r#"db.users.deleteOne({ name = "Ada" })"#)
+            .unwrap();
+        assert_eq!(
+            log.lock().unwrap()[0],
+            "db.users.deleteOne({\"name\":\"Ada\"})"
+        );
+    }
+
+    #[test]
+    fn test_lua_errors() {
+        let script = LuaScript::new(
+            |_| Err(MongoshError::Generic("connection refused".to_string())),
+            |_| {},
+        )
+        .unwrap();
+        let error = script.exec("report.lua", "db.users:find()").unwrap_err();
+        assert!(error.to_string().contains("connection refused"));
+
+        assert!(script.exec("report.lua", "local x = ").is_err());
+    }
+}
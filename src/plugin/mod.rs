@@ -0,0 +1,469 @@
+//! Plugin extension points
+//!
+//! A plugin is a Rust type implementing [`Plugin`] that an application built
+//! on this library registers once at startup with [`register`]. Every hook
+//! has a default that adds nothing, so a plugin only implements the extension
+//! points it uses.
+//!
+//! ```no_run
+//! use std::sync::Arc;
+//!
+//! use bson::Document;
+//! use mongosh::formatter::DocumentFormatter;
+//! use mongosh::plugin::{FormatterRegistry, Plugin};
+//!
+//! struct Lines;
+//!
+//! impl DocumentFormatter for Lines {
+//!     fn format_documents(&self, documents: &[Document]) -> mongosh::Result<String> {
+//!         let lines: Vec<String> = documents.iter().map(|doc| doc.to_string()).collect();
+//!         Ok(lines.join("\n"))
+//!     }
+//! }
+//!
+//! struct LinesPlugin;
+//!
+//! impl Plugin for LinesPlugin {
+//!     fn name(&self) -> &str {
+//!         "lines"
+//!     }
+//!
+//!     fn register_formatters(&self, formatters: &mut FormatterRegistry) {
+//!         formatters.add("lines", Arc::new(Lines));
+//!     }
+//! }
+//!
+//! mongosh::plugin::register(&LinesPlugin).unwrap();
+//! // `format lines` now selects the new format
+//! ```
+//!
+//! Plugins can also add commands under a prefix word with
+//! [`Plugin::register_commands`]: the parser hands input such as
+//! `graph neighbors alice` to the plugin whenever no built-in command matches.
+//!
+//! Applications that read a [`Config`](crate::Config) load plugins with
+//! [`load`] instead, which passes each plugin its `[plugins.config.<name>]`
+//! table through [`Plugin::configure`] and leaves the plugins listed in
+//! `plugins.disabled` turned off. In the shell, `plugin list`, `plugin info`,
+//! `plugin enable` and `plugin disable` manage the loaded plugins.
+//!
+//! With the `wasm-plugins` feature, [`wasm::WasmPlugin`] loads such a plugin
+//! from a `.wasm` module instead, sandboxed without filesystem or network
+//! access. The `lua` feature adds [`lua::LuaScript`], which runs Lua
+//! automation scripts against the shell's `db` and `print` bindings.
+
+use std::sync::{Arc, RwLock};
+
+#[cfg(feature = "native")]
+use serde_json::Value as JsonValue;
+
+use crate::config::PluginConfig;
+use crate::error::{MongoshError, Result};
+#[cfg(feature = "native")]
+use crate::executor::{ExecutionContext, ResultData};
+use crate::formatter::{DocumentFormatter, register_format, unregister_format};
+use crate::parser::{CommandExtension, register_extension, unregister_extension};
+
+#[cfg(feature = "lua")]
+pub mod lua;
+#[cfg(feature = "wasm-plugins")]
+pub mod wasm;
+
+/// Runs a shell command for a plugin or script, returning its result as JSON
+#[cfg(feature = "native")]
+pub type CommandRunner = Arc<dyn Fn(&str) -> Result<JsonValue> + Send + Sync>;
+
+/// Runner executing commands in a session
+///
+/// Results are returned as relaxed Extended JSON, messages as strings. The
+/// runner blocks on the session from synchronous plugin code, so it must be
+/// called on a multi-threaded tokio runtime, as the shell does.
+///
+/// # Arguments
+/// * `context` - Execution context of the session the commands run in
+///
+/// # Returns
+/// * `CommandRunner` - Runner for plugins and scripts
+#[cfg(feature = "native")]
+pub fn context_runner(context: ExecutionContext) -> CommandRunner {
+    Arc::new(move |input| {
+        let mut parser = crate::parser::Parser::new();
+        parser.set_last_result(context.shared_state.get_last_result_value());
+        let command = parser.parse(input)?;
+        let result = tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(context.execute(command))
+        })?;
+        if !result.success {
+            return Err(MongoshError::Generic(
+                result.error.unwrap_or_else(|| "Command failed".to_string()),
+            ));
+        }
+        Ok(match (result.data.to_bson(), result.data) {
+            (Some(value), _) => value.into_relaxed_extjson(),
+            (None, ResultData::Message(message)) => JsonValue::String(message),
+            (None, _) => JsonValue::Null,
+        })
+    })
+}
+
+/// Extension of the shell provided by an application
+pub trait Plugin: Send + Sync {
+    /// Plugin name, used in error messages
+    fn name(&self) -> &str;
+
+    /// Apply the plugin's settings, before its extensions are registered
+    ///
+    /// # Arguments
+    /// * `settings` - The `[plugins.config.<name>]` table, empty if absent
+    ///
+    /// # Returns
+    /// * `Result<()>` - Error to refuse loading, e.g. for an invalid setting
+    fn configure(&self, _settings: &toml::Table) -> Result<()> {
+        Ok(())
+    }
+
+    /// Add output formats, selectable with `format <name>`
+    ///
+    /// # Arguments
+    /// * `formatters` - Registry collecting the plugin's formats
+    fn register_formatters(&self, _formatters: &mut FormatterRegistry) {}
+
+    /// Add commands starting with a prefix word, e.g. `graph ...`
+    ///
+    /// # Arguments
+    /// * `commands` - Registry collecting the plugin's command prefixes
+    fn register_commands(&self, _commands: &mut CommandRegistry) {}
+}
+
+/// Output formats a plugin adds
+#[derive(Default)]
+pub struct FormatterRegistry {
+    formats: Vec<(String, Arc<dyn DocumentFormatter>)>,
+}
+
+impl FormatterRegistry {
+    /// Add an output format
+    ///
+    /// # Arguments
+    /// * `name` - Format name as typed after `format`, e.g. `yaml`
+    /// * `formatter` - Formatter rendering the documents
+    pub fn add(&mut self, name: &str, formatter: Arc<dyn DocumentFormatter>) {
+        self.formats.push((name.to_string(), formatter));
+    }
+}
+
+/// Command prefixes a plugin adds
+#[derive(Default)]
+pub struct CommandRegistry {
+    prefixes: Vec<(String, Arc<dyn CommandExtension>)>,
+}
+
+impl CommandRegistry {
+    /// Add a command prefix
+    ///
+    /// # Arguments
+    /// * `prefix` - Word the commands start with, e.g. `graph`
+    /// * `extension` - Parser and executor for the commands
+    pub fn add(&mut self, prefix: &str, extension: Arc<dyn CommandExtension>) {
+        self.prefixes.push((prefix.to_string(), extension));
+    }
+}
+
+/// A loaded plugin and the extensions it added
+struct Loaded {
+    name: String,
+    enabled: bool,
+    settings: toml::Table,
+    formats: Vec<(String, Arc<dyn DocumentFormatter>)>,
+    commands: Vec<(String, Arc<dyn CommandExtension>)>,
+}
+
+impl Loaded {
+    /// Register the extensions; none stay registered if one clashes
+    fn activate(&self) -> Result<()> {
+        let registered = self
+            .formats
+            .iter()
+            .try_for_each(|(name, formatter)| register_format(name, formatter.clone()).map(drop))
+            .and_then(|_| {
+                self.commands.iter().try_for_each(|(prefix, extension)| {
+                    register_extension(prefix, extension.clone())
+                })
+            });
+        if registered.is_err() {
+            self.deactivate();
+        }
+        registered
+    }
+
+    /// Remove the extensions from the shell
+    fn deactivate(&self) {
+        for (name, formatter) in &self.formats {
+            unregister_format(name, formatter);
+        }
+        for (prefix, extension) in &self.commands {
+            unregister_extension(prefix, extension);
+        }
+    }
+}
+
+/// Plugins loaded in this process, in load order
+static PLUGINS: RwLock<Vec<Loaded>> = RwLock::new(Vec::new());
+
+/// A loaded plugin, as shown by `plugin list` and `plugin info`
+#[derive(Debug, Clone, PartialEq)]
+pub struct PluginInfo {
+    /// Plugin name
+    pub name: String,
+
+    /// Whether its formats and commands are available
+    pub enabled: bool,
+
+    /// Output formats the plugin adds
+    pub formats: Vec<String>,
+
+    /// Command prefixes the plugin adds
+    pub commands: Vec<String>,
+
+    /// Settings the plugin was configured with
+    pub settings: toml::Table,
+}
+
+/// Register a plugin's extensions
+///
+/// # Arguments
+/// * `plugin` - Plugin to register
+///
+/// # Returns
+/// * `Result<()>` - Error if an extension clashes with an existing one
+pub fn register(plugin: &dyn Plugin) -> Result<()> {
+    load(plugin, &PluginConfig::default())
+}
+
+/// Configure a plugin and register its extensions unless it is disabled
+///
+/// # Arguments
+/// * `plugin` - Plugin to load
+/// * `config` - The `[plugins]` section of the configuration
+///
+/// # Returns
+/// * `Result<()>` - Error if a plugin of the same name is loaded, the plugin
+///   rejects its settings or an extension clashes with an existing one
+pub fn load(plugin: &dyn Plugin, config: &PluginConfig) -> Result<()> {
+    let name = plugin.name();
+    let clash = |e: MongoshError| MongoshError::Generic(format!("Plugin '{}': {}", name, e));
+
+    let mut plugins = PLUGINS.write().unwrap_or_else(|e| e.into_inner());
+    if plugins.iter().any(|loaded| loaded.name == name) {
+        return Err(MongoshError::Generic(format!(
+            "Plugin '{}' is already loaded",
+            name
+        )));
+    }
+
+    let settings = config.config.get(name).cloned().unwrap_or_default();
+    plugin.configure(&settings).map_err(clash)?;
+
+    let mut formatters = FormatterRegistry::default();
+    plugin.register_formatters(&mut formatters);
+    let mut commands = CommandRegistry::default();
+    plugin.register_commands(&mut commands);
+
+    let loaded = Loaded {
+        name: name.to_string(),
+        enabled: config.is_enabled(name),
+        settings,
+        formats: formatters.formats,
+        commands: commands.prefixes,
+    };
+    if loaded.enabled {
+        loaded.activate().map_err(clash)?;
+    }
+    plugins.push(loaded);
+    Ok(())
+}
+
+/// Loaded plugins, in load order
+pub fn plugins() -> Vec<PluginInfo> {
+    PLUGINS
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .iter()
+        .map(|loaded| PluginInfo {
+            name: loaded.name.clone(),
+            enabled: loaded.enabled,
+            formats: loaded
+                .formats
+                .iter()
+                .map(|(name, _)| name.clone())
+                .collect(),
+            commands: loaded
+                .commands
+                .iter()
+                .map(|(prefix, _)| prefix.clone())
+                .collect(),
+            settings: loaded.settings.clone(),
+        })
+        .collect()
+}
+
+/// Turn a loaded plugin's extensions on or off
+///
+/// # Arguments
+/// * `name` - Plugin name
+/// * `enabled` - Whether its formats and commands are available
+///
+/// # Returns
+/// * `Result<()>` - Error if no such plugin is loaded or, when enabling, an
+///   extension now clashes with another one
+pub fn set_enabled(name: &str, enabled: bool) -> Result<()> {
+    let mut plugins = PLUGINS.write().unwrap_or_else(|e| e.into_inner());
+    let loaded = plugins
+        .iter_mut()
+        .find(|loaded| loaded.name == name)
+        .ok_or_else(|| MongoshError::Generic(format!("No plugin named '{}' is loaded", name)))?;
+
+    if loaded.enabled != enabled {
+        if enabled {
+            loaded.activate()?;
+        } else {
+            loaded.deactivate();
+        }
+        loaded.enabled = enabled;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{DisplayConfig, OutputFormat};
+    use crate::executor::{ExecutionResult, ExecutionStats, ResultData};
+    use crate::formatter::Formatter;
+    use bson::{Document, doc};
+
+    struct Yaml;
+
+    impl DocumentFormatter for Yaml {
+        fn format_documents(&self, documents: &[Document]) -> Result<String> {
+            let mut output = String::new();
+            for doc in documents {
+                for (i, (key, value)) in doc.iter().enumerate() {
+                    let marker = if i == 0 { "- " } else { "  " };
+                    output.push_str(&format!("{}{}: {}\n", marker, key, value));
+                }
+            }
+            Ok(output.trim_end().to_string())
+        }
+    }
+
+    struct YamlPlugin;
+
+    impl Plugin for YamlPlugin {
+        fn name(&self) -> &str {
+            "yaml"
+        }
+
+        fn register_formatters(&self, formatters: &mut FormatterRegistry) {
+            formatters.add("yaml-test", Arc::new(Yaml));
+        }
+    }
+
+    /// Plugin adding a `shout-test` prefix that upper-cases its input, or
+    /// lower-cases it when configured with `quiet = true`
+    #[derive(Default)]
+    struct ShoutPlugin {
+        quiet: std::sync::atomic::AtomicBool,
+    }
+
+    struct Shout;
+
+    impl CommandExtension for Shout {
+        fn parse(&self, _input: &str) -> Result<Option<crate::parser::Command>> {
+            Ok(None)
+        }
+    }
+
+    impl Plugin for ShoutPlugin {
+        fn name(&self) -> &str {
+            "shout"
+        }
+
+        fn configure(&self, settings: &toml::Table) -> Result<()> {
+            let quiet = settings
+                .get("quiet")
+                .map(|value| {
+                    value.as_bool().ok_or_else(|| {
+                        MongoshError::Generic("quiet must be true or false".to_string())
+                    })
+                })
+                .transpose()?
+                .unwrap_or(false);
+            self.quiet
+                .store(quiet, std::sync::atomic::Ordering::Relaxed);
+            Ok(())
+        }
+
+        fn register_commands(&self, commands: &mut CommandRegistry) {
+            commands.add("shout-test", Arc::new(Shout));
+        }
+    }
+
+    #[test]
+    fn test_load_with_config() {
+        let mut config = PluginConfig::default();
+        config.disabled.push("shout".to_string());
+        config
+            .config
+            .insert("shout".to_string(), toml::toml! { quiet = "yes" });
+        assert!(load(&ShoutPlugin::default(), &config).is_err());
+
+        config
+            .config
+            .insert("shout".to_string(), toml::toml! { quiet = true });
+        let plugin = ShoutPlugin::default();
+        load(&plugin, &config).unwrap();
+        assert!(plugin.quiet.load(std::sync::atomic::Ordering::Relaxed));
+
+        // Loaded disabled: the prefix is not registered until enabled
+        let info = plugins().into_iter().find(|p| p.name == "shout").unwrap();
+        assert!(!info.enabled);
+        assert_eq!(info.commands, vec!["shout-test"]);
+        let registered = || crate::parser::Parser::new().parse("shout-test hi").is_ok();
+        assert!(!registered());
+
+        set_enabled("shout", true).unwrap();
+        assert!(registered());
+        set_enabled("shout", false).unwrap();
+        assert!(!registered());
+
+        assert!(set_enabled("missing", true).is_err());
+        assert!(load(&plugin, &config).is_err());
+    }
+
+    #[test]
+    fn test_register_formatters() {
+        register(&YamlPlugin).unwrap();
+        let format = OutputFormat::from_name("yaml-test").unwrap();
+        assert!(OutputFormat::names().contains(&"yaml-test"));
+
+        let display = DisplayConfig {
+            format,
+            color_output: false,
+            show_timing: false,
+            ..DisplayConfig::default()
+        };
+        let result = ExecutionResult {
+            success: true,
+            data: ResultData::Documents(vec![doc! { "name": "Ada", "age": 36 }]),
+            stats: ExecutionStats::default(),
+            error: None,
+        };
+        let output = Formatter::from_config(&display).format(&result).unwrap();
+        assert_eq!(output, "- name: \"Ada\"\n  age: 36");
+
+        // A second registration of the same format is rejected
+        let error = register(&YamlPlugin).unwrap_err();
+        assert!(error.to_string().contains("Plugin 'yaml'"));
+    }
+}
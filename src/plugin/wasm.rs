@@ -0,0 +1,455 @@
+//! Plugins shipped as WebAssembly modules
+//!
+//! A [`WasmPlugin`] runs a `.wasm` module in a wasmtime sandbox. The module
+//! gets no WASI, so it cannot touch the filesystem, the network, the clock or
+//! the environment; the only way out is the `mongosh` import module:
+//!
+//! - `execute(ptr, len) -> i64` runs a shell command and returns
+//!   `{"result": ...}` (relaxed Extended JSON) or `{"error": "..."}`; it is
+//!   refused unless [`WasmCapabilities::runner`] is set
+//! - `log(ptr, len)` writes a line to the shell's log
+//!
+//! The module exports its `memory` and:
+//!
+//! - `mongosh_alloc(len) -> ptr` reserving `len` bytes for the host to write
+//! - `mongosh_prefix() -> i64` naming the command prefix, e.g. `graph`
+//! - `mongosh_run(ptr, len) -> i64` running the input after the prefix and
+//!   replying `{"output": "..."}`, `{"documents": [...]}` or `{"error": "..."}`
+//!
+//! Strings are UTF-8; an `i64` string is `ptr << 32 | len`. Every command
+//! runs with a fuel budget, so a plugin stuck in a loop fails instead of
+//! hanging the shell.
+//!
+//! ```no_run
+//! use mongosh::MongoshSession;
+//! use mongosh::plugin::context_runner;
+//! use mongosh::plugin::wasm::{WasmCapabilities, WasmPlugin};
+//!
+//! # async fn example() -> mongosh::Result<()> {
+//! let session = MongoshSession::connect("mongodb://localhost:27017/shop").await?;
+//! let capabilities = WasmCapabilities {
+//!     runner: Some(context_runner(session.context().clone())),
+//!     ..WasmCapabilities::default()
+//! };
+//! let plugin = WasmPlugin::load("plugins/graph.wasm", capabilities)?;
+//! mongosh::plugin::register(&plugin)?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fs;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use bson::Bson;
+use serde::Deserialize;
+use serde_json::{Value as JsonValue, json};
+use wasmtime::{
+    AsContext, AsContextMut, Caller, Config as EngineConfig, Engine, Extern, Linker, Memory,
+    Module, Store, StoreLimits, StoreLimitsBuilder, TypedFunc,
+};
+
+use super::{CommandRegistry, CommandRunner, Plugin};
+use crate::error::{MongoshError, Result};
+use crate::executor::{ExecutionContext, ResultData};
+use crate::parser::{Command, CommandExtension};
+
+/// Fuel per command when none is configured
+const DEFAULT_FUEL: u64 = 1_000_000_000;
+
+/// Memory limit when none is configured
+const DEFAULT_MAX_MEMORY: usize = 64 * 1024 * 1024;
+
+/// What a WASM plugin is allowed to do
+#[derive(Clone)]
+pub struct WasmCapabilities {
+    /// Runs shell commands for the plugin; `None` refuses `execute`
+    pub runner: Option<CommandRunner>,
+
+    /// Fuel for each command, roughly the number of instructions it may run
+    pub fuel: u64,
+
+    /// Largest linear memory the plugin may grow to, in bytes
+    pub max_memory: usize,
+}
+
+impl Default for WasmCapabilities {
+    fn default() -> Self {
+        Self {
+            runner: None,
+            fuel: DEFAULT_FUEL,
+            max_memory: DEFAULT_MAX_MEMORY,
+        }
+    }
+}
+
+/// A plugin loaded from a WebAssembly module
+pub struct WasmPlugin {
+    name: String,
+    prefix: String,
+    guest: Arc<Mutex<Guest>>,
+}
+
+impl WasmPlugin {
+    /// Load a plugin from a `.wasm` file, named after the file
+    ///
+    /// # Arguments
+    /// * `path` - Path of the module
+    /// * `capabilities` - What the plugin may do
+    ///
+    /// # Returns
+    /// * `Result<Self>` - Instantiated plugin, or an error if the file cannot
+    ///   be read or the module lacks an export or imports anything else
+    pub fn load(path: impl AsRef<Path>, capabilities: WasmCapabilities) -> Result<Self> {
+        let path = path.as_ref();
+        let bytes = fs::read(path).map_err(|e| {
+            MongoshError::Generic(format!("Cannot read plugin '{}': {}", path.display(), e))
+        })?;
+        let name = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_else(|| path.display().to_string());
+        Self::from_bytes(&name, &bytes, capabilities)
+    }
+
+    /// Load a plugin from module bytes (binary or text format)
+    ///
+    /// # Arguments
+    /// * `name` - Plugin name, used in messages
+    /// * `bytes` - Module
+    /// * `capabilities` - What the plugin may do
+    ///
+    /// # Returns
+    /// * `Result<Self>` - Instantiated plugin
+    pub fn from_bytes(name: &str, bytes: &[u8], capabilities: WasmCapabilities) -> Result<Self> {
+        let fail = |e: wasmtime::Error| plugin_error(name, e);
+
+        let mut config = EngineConfig::new();
+        config.consume_fuel(true);
+        let engine = Engine::new(&config).map_err(fail)?;
+        let module = Module::new(&engine, bytes).map_err(fail)?;
+
+        let state = HostState {
+            plugin: name.to_string(),
+            runner: capabilities.runner,
+            limits: StoreLimitsBuilder::new()
+                .memory_size(capabilities.max_memory)
+                .instances(1)
+                .build(),
+        };
+        let mut store = Store::new(&engine, state);
+        store.limiter(|state| &mut state.limits);
+        store.set_fuel(capabilities.fuel).map_err(fail)?;
+
+        let instance = host_linker(&engine)
+            .map_err(fail)?
+            .instantiate(&mut store, &module)
+            .map_err(fail)?;
+        let memory = instance
+            .get_memory(&mut store, "memory")
+            .ok_or_else(|| plugin_error(name, "module does not export its memory"))?;
+        let alloc = instance
+            .get_typed_func::<i32, i32>(&mut store, "mongosh_alloc")
+            .map_err(fail)?;
+        let run = instance
+            .get_typed_func::<(i32, i32), i64>(&mut store, "mongosh_run")
+            .map_err(fail)?;
+        let prefix = instance
+            .get_typed_func::<(), i64>(&mut store, "mongosh_prefix")
+            .and_then(|prefix| prefix.call(&mut store, ()))
+            .and_then(|packed| read_string(memory, &store, packed))
+            .map_err(fail)?;
+
+        Ok(Self {
+            name: name.to_string(),
+            prefix,
+            guest: Arc::new(Mutex::new(Guest {
+                store,
+                memory,
+                alloc,
+                run,
+                fuel: capabilities.fuel,
+            })),
+        })
+    }
+
+    /// Command prefix the plugin handles
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+}
+
+impl Plugin for WasmPlugin {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn register_commands(&self, commands: &mut CommandRegistry) {
+        commands.add(&self.prefix, Arc::new(WasmCommands(self.guest.clone())));
+    }
+}
+
+/// Store data seen by the host functions
+struct HostState {
+    plugin: String,
+    runner: Option<CommandRunner>,
+    limits: StoreLimits,
+}
+
+/// An instantiated module and its entry points
+struct Guest {
+    store: Store<HostState>,
+    memory: Memory,
+    alloc: TypedFunc<i32, i32>,
+    run: TypedFunc<(i32, i32), i64>,
+    fuel: u64,
+}
+
+impl Guest {
+    fn run(&mut self, input: &str) -> Result<ResultData> {
+        let plugin = self.store.data().plugin.clone();
+        let fail = |e: wasmtime::Error| plugin_error(&plugin, e);
+
+        self.store.set_fuel(self.fuel).map_err(fail)?;
+        let (ptr, len) =
+            write_string(&mut self.store, self.memory, &self.alloc, input).map_err(fail)?;
+        let packed = self.run.call(&mut self.store, (ptr, len)).map_err(fail)?;
+        let reply = read_string(self.memory, &self.store, packed).map_err(fail)?;
+
+        let reply: Reply = serde_json::from_str(&reply)
+            .map_err(|e| plugin_error(&plugin, format!("invalid reply: {}", e)))?;
+        match reply {
+            Reply::Output(text) => Ok(ResultData::Message(text)),
+            Reply::Documents(values) => {
+                let documents = values
+                    .into_iter()
+                    .map(|value| match Bson::try_from(value) {
+                        Ok(Bson::Document(doc)) => Ok(doc),
+                        _ => Err(plugin_error(&plugin, "reply documents must be objects")),
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(ResultData::Documents(documents))
+            }
+            Reply::Error(message) => Err(plugin_error(&plugin, message)),
+        }
+    }
+}
+
+/// Reply of `mongosh_run`
+#[derive(Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Reply {
+    Output(String),
+    Documents(Vec<JsonValue>),
+    Error(String),
+}
+
+/// Commands behind a WASM plugin's prefix
+struct WasmCommands(Arc<Mutex<Guest>>);
+
+#[async_trait]
+impl CommandExtension for WasmCommands {
+    fn parse(&self, _input: &str) -> Result<Option<Command>> {
+        // Run the module only when the command executes, not while the
+        // editor or language server parses input
+        Ok(None)
+    }
+
+    async fn execute(&self, input: &str, _context: &ExecutionContext) -> Result<ResultData> {
+        // Commands the module runs go through its runner, not this context
+        self.0.lock().unwrap_or_else(|e| e.into_inner()).run(input)
+    }
+}
+
+/// Linker providing the `mongosh` import module and nothing else
+fn host_linker(engine: &Engine) -> wasmtime::Result<Linker<HostState>> {
+    let mut linker = Linker::new(engine);
+
+    linker.func_wrap(
+        "mongosh",
+        "execute",
+        |mut caller: Caller<'_, HostState>, ptr: i32, len: i32| -> wasmtime::Result<i64> {
+            let (memory, alloc) = caller_exports(&mut caller)?;
+            let input = read_string(memory, &caller, pack(ptr, len))?;
+            let reply = match &caller.data().runner {
+                Some(runner) => match runner(&input) {
+                    Ok(result) => json!({ "result": result }),
+                    Err(e) => json!({ "error": e.to_string() }),
+                },
+                None => json!({ "error": "command execution is not enabled for this plugin" }),
+            };
+            let (ptr, len) = write_string(&mut caller, memory, &alloc, &reply.to_string())?;
+            Ok(pack(ptr, len))
+        },
+    )?;
+
+    linker.func_wrap(
+        "mongosh",
+        "log",
+        |mut caller: Caller<'_, HostState>, ptr: i32, len: i32| -> wasmtime::Result<()> {
+            let (memory, _) = caller_exports(&mut caller)?;
+            let line = read_string(memory, &caller, pack(ptr, len))?;
+            tracing::info!("[{}] {}", caller.data().plugin, line);
+            Ok(())
+        },
+    )?;
+
+    Ok(linker)
+}
+
+/// The module's memory and allocator, seen from a host function
+fn caller_exports(
+    caller: &mut Caller<'_, HostState>,
+) -> wasmtime::Result<(Memory, TypedFunc<i32, i32>)> {
+    let memory = caller
+        .get_export("memory")
+        .and_then(Extern::into_memory)
+        .ok_or_else(|| wasmtime::Error::msg("module does not export its memory"))?;
+    let alloc = caller
+        .get_export("mongosh_alloc")
+        .and_then(Extern::into_func)
+        .ok_or_else(|| wasmtime::Error::msg("module does not export mongosh_alloc"))?
+        .typed::<i32, i32>(&*caller)?;
+    Ok((memory, alloc))
+}
+
+fn pack(ptr: i32, len: i32) -> i64 {
+    (((ptr as u32 as u64) << 32) | len as u32 as u64) as i64
+}
+
+/// Read a string the module returned as `ptr << 32 | len`
+fn read_string(memory: Memory, store: impl AsContext, packed: i64) -> wasmtime::Result<String> {
+    let ptr = ((packed as u64) >> 32) as usize;
+    let len = (packed as u64 & 0xffff_ffff) as usize;
+    let bytes = memory
+        .data(&store)
+        .get(ptr..ptr.saturating_add(len))
+        .ok_or_else(|| wasmtime::Error::msg("string out of bounds of the module's memory"))?;
+    Ok(String::from_utf8(bytes.to_vec())?)
+}
+
+/// Copy a string into memory allocated by the module
+fn write_string(
+    mut store: impl AsContextMut,
+    memory: Memory,
+    alloc: &TypedFunc<i32, i32>,
+    text: &str,
+) -> wasmtime::Result<(i32, i32)> {
+    let len = i32::try_from(text.len())?;
+    let ptr = alloc.call(&mut store, len)?;
+    memory.write(&mut store, ptr as u32 as usize, text.as_bytes())?;
+    Ok((ptr, len))
+}
+
+fn plugin_error(plugin: &str, error: impl std::fmt::Display) -> MongoshError {
+    MongoshError::Generic(format!("Plugin '{}': {}", plugin, error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Bump allocator shared by the test modules
+    const ALLOC: &str = r#"
+        (memory (export "memory") 1)
+        (global $next (mut i32) (i32.const 1024))
+        (func (export "mongosh_alloc") (param $len i32) (result i32)
+            (local $ptr i32)
+            (local.set $ptr (global.get $next))
+            (global.set $next (i32.add (global.get $next) (local.get $len)))
+            (local.get $ptr))
+    "#;
+
+    fn guest(body: &str) -> String {
+        format!("(module {} {})", body, ALLOC)
+    }
+
+    fn load(name: &str, body: &str) -> Result<WasmPlugin> {
+        WasmPlugin::from_bytes(name, guest(body).as_bytes(), WasmCapabilities::default())
+    }
+
+    #[test]
+    fn test_wasm_plugin_output() {
+        let plugin = load(
+            "pong",
+            r#"
+            (data (i32.const 0) "ping")
+            (data (i32.const 16) "{\"output\":\"pong\"}")
+            (func (export "mongosh_prefix") (result i64)
+                (i64.const 4))
+            (func (export "mongosh_run") (param i32 i32) (result i64)
+                (i64.or (i64.shl (i64.const 16) (i64.const 32)) (i64.const 17)))
+            "#,
+        )
+        .unwrap();
+        assert_eq!(plugin.prefix(), "ping");
+
+        let data = plugin.guest.lock().unwrap().run("").unwrap();
+        assert!(matches!(data, ResultData::Message(ref text) if text == "pong"));
+    }
+
+    #[test]
+    fn test_wasm_plugin_execute_requires_runner() {
+        // The module passes the host's reply to `execute` straight back
+        let plugin = load(
+            "probe",
+            r#"
+            (import "mongosh" "execute" (func $execute (param i32 i32) (result i64)))
+            (data (i32.const 0) "probe")
+            (data (i32.const 16) "show dbs")
+            (func (export "mongosh_prefix") (result i64)
+                (i64.const 5))
+            (func (export "mongosh_run") (param i32 i32) (result i64)
+                (call $execute (i32.const 16) (i32.const 8)))
+            "#,
+        )
+        .unwrap();
+
+        let error = plugin.guest.lock().unwrap().run("").unwrap_err();
+        assert!(error.to_string().contains("not enabled"));
+    }
+
+    #[test]
+    fn test_wasm_plugin_without_wasi() {
+        let error = load(
+            "fs",
+            r#"
+            (import "wasi_snapshot_preview1" "fd_write"
+                (func (param i32 i32 i32 i32) (result i32)))
+            (func (export "mongosh_prefix") (result i64)
+                (i64.const 0))
+            (func (export "mongosh_run") (param i32 i32) (result i64)
+                (i64.const 0))
+            "#,
+        )
+        .err()
+        .unwrap();
+        assert!(error.to_string().starts_with("Plugin 'fs'"));
+    }
+
+    #[test]
+    fn test_wasm_plugin_fuel() {
+        let plugin = WasmPlugin::from_bytes(
+            "spin",
+            guest(
+                r#"
+                (data (i32.const 0) "spin")
+                (func (export "mongosh_prefix") (result i64)
+                    (i64.const 4))
+                (func (export "mongosh_run") (param i32 i32) (result i64)
+                    (loop $forever (br $forever))
+                    (i64.const 0))
+                "#,
+            )
+            .as_bytes(),
+            WasmCapabilities {
+                fuel: 10_000,
+                ..WasmCapabilities::default()
+            },
+        )
+        .unwrap();
+
+        assert!(plugin.guest.lock().unwrap().run("").is_err());
+    }
+}
@@ -486,6 +486,10 @@ pub fn summarize_result(
             let text = items.join("\n");
             (truncate_chars(&text, MAX_STEP_RESULT_CHARS), items.len())
         }
+        ResultData::Indexes(_)
+        | ResultData::Databases(_)
+        | ResultData::Collections(_)
+        | ResultData::Users(_) => summarize_docs(&result.data.to_documents().unwrap_or_default()),
         ResultData::None => ("(no data)".to_string(), 0),
         ResultData::Stream(_) => ("(streaming result)".to_string(), 0),
     };
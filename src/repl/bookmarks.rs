@@ -0,0 +1,215 @@
+//! Query bookmarks for `bookmark save`, `bookmark run` and `bookmark list`
+//!
+//! Bookmarks are kept in `~/.mongosh/bookmarks.toml` as `name = "query"`
+//! entries, so they are shared by every datasource and survive config file
+//! edits. A query may contain `{{name}}` placeholders; `bookmark run` asks
+//! for a value for each one and substitutes it verbatim, so a placeholder
+//! inside quotes (`{status: '{{status}}'}`) becomes a string and one outside
+//! quotes (`.limit({{n}})`) a number or expression.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::{ConfigError, MongoshError, Result};
+
+/// Saved query bookmarks
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BookmarkStore {
+    /// Query text by bookmark name
+    #[serde(default)]
+    pub bookmarks: BTreeMap<String, String>,
+}
+
+impl BookmarkStore {
+    /// Default location of the bookmark file
+    ///
+    /// # Returns
+    /// * `PathBuf` - `~/.mongosh/bookmarks.toml`
+    pub fn default_path() -> PathBuf {
+        dirs::home_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join(".mongosh")
+            .join("bookmarks.toml")
+    }
+
+    /// Read bookmarks from a file
+    ///
+    /// # Arguments
+    /// * `path` - Bookmark file
+    ///
+    /// # Returns
+    /// * `Result<Self>` - Bookmarks, empty when the file does not exist yet
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(path).map_err(|e| {
+            MongoshError::Config(ConfigError::Generic(format!(
+                "Failed to read bookmark file '{}': {}",
+                path.display(),
+                e
+            )))
+        })?;
+
+        toml::from_str(&content).map_err(|e| {
+            MongoshError::Config(ConfigError::Generic(format!(
+                "Failed to parse bookmark file '{}': {}",
+                path.display(),
+                e
+            )))
+        })
+    }
+
+    /// Write bookmarks to a file, creating its directory if needed
+    ///
+    /// # Arguments
+    /// * `path` - Destination file
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or error
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let content = toml::to_string_pretty(self).map_err(|e| {
+            MongoshError::Config(ConfigError::Generic(format!(
+                "Failed to serialize bookmarks: {}",
+                e
+            )))
+        })?;
+
+        if let Some(parent) = path.parent()
+            && !parent.as_os_str().is_empty()
+        {
+            fs::create_dir_all(parent).map_err(|e| {
+                MongoshError::Config(ConfigError::Generic(format!(
+                    "Failed to create directory '{}': {}",
+                    parent.display(),
+                    e
+                )))
+            })?;
+        }
+
+        fs::write(path, content).map_err(|e| {
+            MongoshError::Config(ConfigError::Generic(format!(
+                "Failed to write bookmark file '{}': {}",
+                path.display(),
+                e
+            )))
+        })
+    }
+}
+
+/// Names of the `{{name}}` placeholders in a query, in order of first use
+///
+/// # Arguments
+/// * `query` - Bookmarked query text
+///
+/// # Returns
+/// * `Vec<String>` - Distinct placeholder names
+pub fn placeholders(query: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    let mut rest = query;
+
+    while let Some(start) = rest.find("{{") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            break;
+        };
+        let name = after[..end].trim();
+        if is_placeholder_name(name) && !names.iter().any(|n| n == name) {
+            names.push(name.to_string());
+        }
+        rest = &after[end + 2..];
+    }
+
+    names
+}
+
+/// Replace `{{name}}` placeholders with their values
+///
+/// # Arguments
+/// * `query` - Bookmarked query text
+/// * `values` - Value for each placeholder name
+///
+/// # Returns
+/// * `String` - Query ready to parse; unknown placeholders are left as is
+pub fn fill(query: &str, values: &HashMap<String, String>) -> String {
+    let mut filled = String::with_capacity(query.len());
+    let mut rest = query;
+
+    while let Some(start) = rest.find("{{") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            break;
+        };
+        filled.push_str(&rest[..start]);
+        match values.get(after[..end].trim()) {
+            Some(value) => filled.push_str(value),
+            None => filled.push_str(&rest[start..start + 2 + end + 2]),
+        }
+        rest = &after[end + 2..];
+    }
+
+    filled.push_str(rest);
+    filled
+}
+
+/// Whether text between `{{` and `}}` names a placeholder
+fn is_placeholder_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '-')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_placeholders() {
+        assert_eq!(
+            placeholders("db.orders.find({status: '{{status}}', n: {{ n }}}).limit({{n}})"),
+            vec!["status", "n"]
+        );
+        assert!(placeholders("db.orders.find({a: {b: 1}})").is_empty());
+        assert!(placeholders("db.orders.find({a: {{b: 1}}})").is_empty());
+    }
+
+    #[test]
+    fn test_fill() {
+        let values = HashMap::from([
+            ("status".to_string(), "pending".to_string()),
+            ("n".to_string(), "5".to_string()),
+        ]);
+        assert_eq!(
+            fill(
+                "db.orders.find({status: '{{status}}'}).limit({{ n }}) {{other}}",
+                &values
+            ),
+            "db.orders.find({status: 'pending'}).limit(5) {{other}}"
+        );
+    }
+
+    #[test]
+    fn test_store_round_trip() {
+        let path = std::env::temp_dir()
+            .join(format!("mongosh-bookmarks-{}", std::process::id()))
+            .join("bookmarks.toml");
+        assert_eq!(
+            BookmarkStore::load(&path).unwrap(),
+            BookmarkStore::default()
+        );
+
+        let mut store = BookmarkStore::default();
+        store.bookmarks.insert(
+            "slow-orders".to_string(),
+            "db.orders.find({status: '{{status}}'}).sort({ts: -1})".to_string(),
+        );
+        store.save(&path).unwrap();
+        let loaded = BookmarkStore::load(&path).unwrap();
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+        assert_eq!(loaded, store);
+    }
+}
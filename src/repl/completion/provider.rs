@@ -198,8 +198,20 @@ impl CandidateProvider for MongoCandidateProvider {
             "estimatedDocumentCount".to_string(),
             "distinct".to_string(),
             "aggregate".to_string(),
+            "search".to_string(),
+            "vectorSearch".to_string(),
+            "docSize".to_string(),
+            "diff".to_string(),
+            "asOf".to_string(),
+            "largestDocuments".to_string(),
+            "getValidator".to_string(),
+            "setValidator".to_string(),
+            "validate".to_string(),
+            "convertToCapped".to_string(),
             "createIndex".to_string(),
             "dropIndex".to_string(),
+            "dropIndexes".to_string(),
+            "indexStats".to_string(),
             "drop".to_string(),
             "rename".to_string(),
         ];
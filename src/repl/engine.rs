@@ -6,10 +6,11 @@ use reedline::{
 
 use std::sync::Arc;
 
+use crate::cli::prompt::answer_prompts;
 use crate::config::{AiConfig, HistoryConfig};
 use crate::error::{MongoshError, Result};
 use crate::executor::ExecutionContext;
-use crate::parser::{Command, Parser};
+use crate::parser::{Command, Parser, prompt_requests};
 
 use super::ai_completion::AiCompletionService;
 use super::completer::MongoCompleter;
@@ -184,10 +185,15 @@ impl ReplEngine {
             .with_partial_completions(true) // Allow partial completion
             .use_kitty_keyboard_enhancement(false); // Disable for better compatibility
 
+        // Commands are typed by the user who started the shell, so env() may
+        // read its environment
+        let mut parser = Parser::new();
+        parser.set_environment(std::env::vars().collect());
+
         Ok(Self {
             editor,
             shared_state,
-            parser: Parser::new(),
+            parser,
             running: true,
         })
     }
@@ -240,6 +246,9 @@ impl ReplEngine {
     /// # Returns
     /// * `Result<Command>` - Parsed command or error
     pub fn process_input(&mut self, input: &str) -> Result<Command> {
+        self.parser.set_last_result(self.shared_state.get_last_result_value());
+        let answers = answer_prompts(&prompt_requests(input))?;
+        self.parser.set_prompt_answers(answers);
         self.parser.parse(input)
     }
 
@@ -97,6 +97,21 @@ impl MongoHighlighter {
         "db",
         "show",
         "use",
+        "top",
+        "currentOp",
+        "dump",
+        "restore",
+        "import",
+        "undo",
+        "backups",
+        "oplog",
+        "watch",
+        "translate",
+        "describe",
+        "plugin",
+        "cache",
+        "expand",
+        "retry",
         "exit",
         "quit",
         "help",
@@ -134,17 +149,30 @@ impl MongoHighlighter {
         "countDocuments",
         "estimatedDocumentCount",
         "distinct",
+        "search",
+        "vectorSearch",
+        "docSize",
+        "diff",
+        "asOf",
+        "largestDocuments",
+        "getValidator",
+        "setValidator",
+        "validate",
+        "convertToCapped",
         "createIndex",
         "createIndexes",
         "dropIndex",
         "dropIndexes",
         "drop",
+        "dropCollections",
         "renameCollection",
         "stats",
         "dataSize",
         "storageSize",
         "totalIndexSize",
         "getIndexes",
+        "indexStats",
+        "indexReport",
         "explain",
     ];
 
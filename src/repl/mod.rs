@@ -11,6 +11,11 @@
 //! - `hinter`        : Inline hints for reedline
 //! - `validator`     : Line validation for reedline
 //! - `completion`    : Intelligent completion system for MongoDB shell and SQL
+//! - `script`        : Script files executed with `load()`
+//! - `session`       : Session snapshots for `session save` / `session load`
+//! - `bookmarks`     : Query bookmarks for `bookmark save` / `bookmark run`
+//! - `recording`     : Session transcripts for `record start` / `mongosh replay`
+//! - `report`        : Markdown reports for `report start` / `report stop`
 //!
 //! External code should typically depend on `ReplEngine` and `SharedState`.
 //! More specialized types (e.g. completer, highlighter, validator)
@@ -20,17 +25,30 @@
 pub mod ai_completion;
 pub mod ai_context;
 pub mod ai_query;
+pub mod bookmarks;
+#[cfg(feature = "repl")]
 mod completer;
+#[cfg(feature = "repl")]
 pub mod completion;
 mod cursor_state;
+#[cfg(feature = "repl")]
 mod engine;
+#[cfg(feature = "repl")]
 mod highlighter;
+#[cfg(feature = "repl")]
 mod hinter;
+#[cfg(feature = "repl")]
 mod prompt;
+pub mod recording;
+pub mod report;
+pub mod script;
+pub mod session;
 mod shared_state;
+#[cfg(feature = "repl")]
 mod validator;
 
 pub use cursor_state::CursorState;
+#[cfg(feature = "repl")]
 pub use engine::ReplEngine;
 pub use shared_state::SharedState;
 
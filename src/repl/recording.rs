@@ -0,0 +1,282 @@
+//! Session recording for `record start` / `record stop` and `mongosh replay`
+//!
+//! A recording is a plain text transcript of every command typed and the
+//! output it printed, each stamped with the UTC time it happened:
+//!
+//! ```text
+//! # mongosh session recorded 2026-10-16T09:30:00.000Z
+//! [2026-10-16T09:30:02.118Z] > db.orders.find({status: 'pending'}).limit(1)
+//! [2026-10-16T09:30:02.164Z] < [
+//! |   { _id: 1, status: 'pending' }
+//! | ]
+//! [2026-10-16T09:30:05.402Z] ! Collection 'order' not found
+//! ```
+//!
+//! `>` marks input, `<` output and `!` an error; lines starting with `| `
+//! continue the entry above, so recorded output can never be mistaken for a
+//! command. Colors are stripped before writing.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use chrono::{SecondsFormat, Utc};
+
+use crate::error::{MongoshError, Result};
+use crate::formatter::strip_ansi;
+
+/// What a transcript entry holds
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntryKind {
+    /// A command as typed
+    Input,
+
+    /// Output printed by a command
+    Output,
+
+    /// Error printed by a command
+    Error,
+}
+
+impl EntryKind {
+    /// Marker written after the timestamp
+    fn marker(self) -> char {
+        match self {
+            EntryKind::Input => '>',
+            EntryKind::Output => '<',
+            EntryKind::Error => '!',
+        }
+    }
+
+    fn from_marker(marker: char) -> Option<Self> {
+        match marker {
+            '>' => Some(EntryKind::Input),
+            '<' => Some(EntryKind::Output),
+            '!' => Some(EntryKind::Error),
+            _ => None,
+        }
+    }
+}
+
+/// One timestamped transcript entry
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    /// RFC 3339 UTC timestamp
+    pub time: String,
+
+    /// Input, output or error
+    pub kind: EntryKind,
+
+    /// Entry text, possibly several lines
+    pub text: String,
+}
+
+impl Entry {
+    /// Render the entry as transcript lines, ending with a newline
+    fn to_transcript(&self) -> String {
+        let mut lines = self.text.lines();
+        let mut rendered = format!(
+            "[{}] {} {}\n",
+            self.time,
+            self.kind.marker(),
+            lines.next().unwrap_or_default()
+        );
+        for line in lines {
+            rendered.push_str("| ");
+            rendered.push_str(line);
+            rendered.push('\n');
+        }
+        rendered
+    }
+}
+
+/// Writes a transcript while a recording is active
+#[derive(Debug)]
+pub struct Recorder {
+    /// Transcript file
+    path: PathBuf,
+
+    /// Open transcript, written through on every entry
+    file: File,
+
+    /// Number of commands recorded so far
+    commands: usize,
+}
+
+impl Recorder {
+    /// Start a new transcript, replacing an existing file
+    ///
+    /// # Arguments
+    /// * `path` - Transcript file
+    ///
+    /// # Returns
+    /// * `Result<Self>` - Recorder or error
+    pub fn create(path: &Path) -> Result<Self> {
+        if let Some(parent) = path.parent()
+            && !parent.as_os_str().is_empty()
+        {
+            fs::create_dir_all(parent).map_err(|e| write_error(path, e))?;
+        }
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(path)
+            .map_err(|e| write_error(path, e))?;
+        writeln!(file, "# mongosh session recorded {}", now()).map_err(|e| write_error(path, e))?;
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            file,
+            commands: 0,
+        })
+    }
+
+    /// Transcript file being written
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Number of commands recorded so far
+    pub fn commands(&self) -> usize {
+        self.commands
+    }
+
+    /// Append an entry stamped with the current time
+    ///
+    /// # Arguments
+    /// * `kind` - Input, output or error
+    /// * `text` - Text as shown on screen; colors are removed
+    pub fn record(&mut self, kind: EntryKind, text: &str) -> Result<()> {
+        let entry = Entry {
+            time: now(),
+            kind,
+            text: strip_ansi(text),
+        };
+        self.file
+            .write_all(entry.to_transcript().as_bytes())
+            .map_err(|e| write_error(&self.path, e))?;
+        if kind == EntryKind::Input {
+            self.commands += 1;
+        }
+        Ok(())
+    }
+}
+
+/// Read a transcript file
+///
+/// # Arguments
+/// * `path` - Transcript written by `record start`
+///
+/// # Returns
+/// * `Result<Vec<Entry>>` - Entries in recorded order
+pub fn load(path: &Path) -> Result<Vec<Entry>> {
+    let content = fs::read_to_string(path).map_err(|e| {
+        MongoshError::Generic(format!(
+            "Failed to read recording '{}': {}",
+            path.display(),
+            e
+        ))
+    })?;
+    Ok(parse(&content))
+}
+
+/// Parse transcript text; comments and unrecognized lines are skipped
+///
+/// # Arguments
+/// * `content` - Transcript text
+///
+/// # Returns
+/// * `Vec<Entry>` - Entries in recorded order
+pub fn parse(content: &str) -> Vec<Entry> {
+    let mut entries: Vec<Entry> = Vec::new();
+
+    for line in content.lines() {
+        if let Some(rest) = line.strip_prefix('|') {
+            if let Some(entry) = entries.last_mut() {
+                entry.text.push('\n');
+                entry.text.push_str(rest.strip_prefix(' ').unwrap_or(rest));
+            }
+            continue;
+        }
+
+        let Some((time, rest)) = line
+            .strip_prefix('[')
+            .and_then(|line| line.split_once("] "))
+        else {
+            continue;
+        };
+        let mut chars = rest.chars();
+        let Some(kind) = chars.next().and_then(EntryKind::from_marker) else {
+            continue;
+        };
+        let text = chars.as_str();
+        entries.push(Entry {
+            time: time.to_string(),
+            kind,
+            text: text.strip_prefix(' ').unwrap_or(text).to_string(),
+        });
+    }
+
+    entries
+}
+
+/// Current UTC time with millisecond precision
+fn now() -> String {
+    Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+fn write_error(path: &Path, e: std::io::Error) -> MongoshError {
+    MongoshError::Generic(format!(
+        "Failed to write recording '{}': {}",
+        path.display(),
+        e
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let content = "# mongosh session recorded 2026-10-16T09:30:00.000Z\n\
+            [2026-10-16T09:30:02.118Z] > db.orders.find()\n\
+            [2026-10-16T09:30:02.164Z] < [\n\
+            |   { _id: 1 }\n\
+            | ]\n\
+            [2026-10-16T09:30:05.402Z] ! not found\n\
+            garbage\n";
+        let entries = parse(content);
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].kind, EntryKind::Input);
+        assert_eq!(entries[0].time, "2026-10-16T09:30:02.118Z");
+        assert_eq!(entries[0].text, "db.orders.find()");
+        assert_eq!(entries[1].kind, EntryKind::Output);
+        assert_eq!(entries[1].text, "[\n  { _id: 1 }\n]");
+        assert_eq!(entries[2].kind, EntryKind::Error);
+        assert_eq!(entries[2].text, "not found");
+    }
+
+    #[test]
+    fn test_record_round_trip() {
+        let path =
+            std::env::temp_dir().join(format!("mongosh-recording-{}.log", std::process::id()));
+        let mut recorder = Recorder::create(&path).unwrap();
+        recorder
+            .record(EntryKind::Input, "db.users.find(\n  {age: 1}\n)")
+            .unwrap();
+        recorder
+            .record(EntryKind::Output, "\u{1b}[32m[ { _id: 1 } ]\u{1b}[0m")
+            .unwrap();
+        assert_eq!(recorder.commands(), 1);
+        drop(recorder);
+
+        let entries = load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].text, "db.users.find(\n  {age: 1}\n)");
+        assert_eq!(entries[1].text, "[ { _id: 1 } ]");
+    }
+}
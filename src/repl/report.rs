@@ -0,0 +1,182 @@
+//! Markdown reports for `report start` / `report stop`
+//!
+//! While a report is open every command is appended to a Markdown file as a
+//! numbered cell: the command in a fenced `javascript` block followed by its
+//! formatted result in a `text` block, or its error as a quote. The file is
+//! written through after each cell, so an investigation can be shared as is
+//! even if the shell exits unexpectedly.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use chrono::Utc;
+
+use super::recording::EntryKind;
+use crate::error::{MongoshError, Result};
+use crate::formatter::strip_ansi;
+
+/// Writes a Markdown report while one is open
+#[derive(Debug)]
+pub struct MarkdownReport {
+    /// Report file
+    path: PathBuf,
+
+    /// Open report, written through on every cell
+    file: File,
+
+    /// Number of commands written so far
+    commands: usize,
+}
+
+impl MarkdownReport {
+    /// Start a report, appending to an existing file
+    ///
+    /// # Arguments
+    /// * `path` - Markdown file
+    /// * `database` - Current database, named in the report header
+    ///
+    /// # Returns
+    /// * `Result<Self>` - Report or error
+    pub fn create(path: &Path, database: &str) -> Result<Self> {
+        if let Some(parent) = path.parent()
+            && !parent.as_os_str().is_empty()
+        {
+            fs::create_dir_all(parent).map_err(|e| write_error(path, e))?;
+        }
+
+        let is_new = !fs::metadata(path).is_ok_and(|meta| meta.len() > 0);
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| write_error(path, e))?;
+
+        let header = format!(
+            "{}Session started {} on database `{}`\n\n",
+            if is_new {
+                "# mongosh report\n\n"
+            } else {
+                "\n---\n\n"
+            },
+            timestamp(),
+            database
+        );
+        file.write_all(header.as_bytes())
+            .map_err(|e| write_error(path, e))?;
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            file,
+            commands: 0,
+        })
+    }
+
+    /// Report file being written
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Number of commands written so far
+    pub fn commands(&self) -> usize {
+        self.commands
+    }
+
+    /// Append a command, its output or its error
+    ///
+    /// # Arguments
+    /// * `kind` - Input, output or error
+    /// * `text` - Text as shown on screen; colors are removed
+    pub fn append(&mut self, kind: EntryKind, text: &str) -> Result<()> {
+        let text = strip_ansi(text);
+        let cell = match kind {
+            EntryKind::Input => {
+                self.commands += 1;
+                format!(
+                    "### [{}] {}\n\n{}",
+                    self.commands,
+                    timestamp(),
+                    fenced("javascript", &text)
+                )
+            }
+            EntryKind::Output => fenced("text", &text),
+            EntryKind::Error => {
+                let quoted: Vec<String> = text.lines().map(|line| format!("> {}", line)).collect();
+                format!("> **Error**\n>\n{}\n\n", quoted.join("\n"))
+            }
+        };
+
+        self.file
+            .write_all(cell.as_bytes())
+            .map_err(|e| write_error(&self.path, e))
+    }
+}
+
+/// Fenced code block, with a fence longer than any backtick run in the text
+fn fenced(language: &str, text: &str) -> String {
+    let longest = text
+        .split(|c| c != '`')
+        .map(str::len)
+        .max()
+        .unwrap_or_default();
+    let fence = "`".repeat(longest.max(2) + 1);
+    format!("{}{}\n{}\n{}\n\n", fence, language, text.trim_end(), fence)
+}
+
+/// Current UTC time for cell headings
+fn timestamp() -> String {
+    Utc::now().format("%Y-%m-%d %H:%M:%S UTC").to_string()
+}
+
+fn write_error(path: &Path, e: std::io::Error) -> MongoshError {
+    MongoshError::Generic(format!(
+        "Failed to write report '{}': {}",
+        path.display(),
+        e
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fenced() {
+        assert_eq!(fenced("text", "a\n"), "```text\na\n```\n\n");
+        assert_eq!(
+            fenced("text", "x ```js``` y"),
+            "````text\nx ```js``` y\n````\n\n"
+        );
+    }
+
+    #[test]
+    fn test_report() {
+        let path = std::env::temp_dir().join(format!("mongosh-report-{}.md", std::process::id()));
+        let mut report = MarkdownReport::create(&path, "shop").unwrap();
+        report
+            .append(EntryKind::Input, "db.orders.countDocuments()")
+            .unwrap();
+        report
+            .append(EntryKind::Output, "\u{1b}[33m42\u{1b}[0m")
+            .unwrap();
+        report.append(EntryKind::Input, "db.order.find()").unwrap();
+        report
+            .append(EntryKind::Error, "Collection not found")
+            .unwrap();
+        assert_eq!(report.commands(), 2);
+        drop(report);
+        MarkdownReport::create(&path, "shop").unwrap();
+
+        let content = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert!(content.starts_with("# mongosh report\n\nSession started "));
+        assert!(content.contains("on database `shop`"));
+        assert!(content.contains("### [1] "));
+        assert!(
+            content.contains("```javascript\ndb.orders.countDocuments()\n```\n\n```text\n42\n```")
+        );
+        assert!(content.contains("> **Error**\n>\n> Collection not found\n"));
+        assert_eq!(content.matches("# mongosh report").count(), 1);
+        assert!(content.contains("\n---\n\nSession started "));
+    }
+}
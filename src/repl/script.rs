@@ -0,0 +1,402 @@
+//! Script files executed with `load()`
+//!
+//! A script is a file of shell statements, one per line or separated by
+//! semicolons. Statements may span lines while brackets are open or when the
+//! next line continues a method chain (`.sort(...)`). Comments are skipped;
+//! like the expression lexer, a `/` where an operand is expected starts a
+//! regex literal, so `//` inside `/https?:\/\//` is not a comment.
+//!
+//! `load()` calls inside a script resolve relative to the including file's
+//! directory; at the top level they resolve against the working directory.
+//!
+//! A script run with `mongosh --file script.js -- a b` sees its arguments as
+//! `args` (`["a", "b"]`) and `process.argv` (`["mongosh", "script.js", "a", "b"]`).
+//!
+//! The loader and statement splitter are built with the `script` feature;
+//! `args` and `process.argv` are always available to the expression parser.
+
+use std::sync::OnceLock;
+#[cfg(feature = "script")]
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+#[cfg(feature = "script")]
+use crate::error::{MongoshError, Result};
+#[cfg(feature = "script")]
+use crate::parser::REGEX_PRECEDING_KEYWORDS;
+
+/// Command line seen by scripts as `process.argv`
+static ARGV: OnceLock<Vec<String>> = OnceLock::new();
+
+/// Record the script and its arguments for `args` and `process.argv`
+///
+/// Only the first call has an effect.
+///
+/// # Arguments
+/// * `script` - Script path as given on the command line
+/// * `args` - Arguments given after `--`
+pub fn set_argv(script: &str, args: &[String]) {
+    let mut argv = vec!["mongosh".to_string(), script.to_string()];
+    argv.extend(args.iter().cloned());
+    let _ = ARGV.set(argv);
+}
+
+/// Get `process.argv`: `mongosh`, the script path, then the script arguments
+pub fn argv() -> Vec<String> {
+    ARGV.get()
+        .cloned()
+        .unwrap_or_else(|| vec!["mongosh".to_string()])
+}
+
+/// Get `args`: the arguments given to the script after `--`
+pub fn args() -> Vec<String> {
+    argv().into_iter().skip(2).collect()
+}
+
+/// A statement read from a script
+#[cfg(feature = "script")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Statement {
+    /// 1-based line on which the statement starts
+    pub line: usize,
+
+    /// Statement source text
+    pub text: String,
+}
+
+/// Tracks the chain of scripts being loaded
+#[cfg(feature = "script")]
+#[derive(Debug, Default)]
+pub struct ScriptLoader {
+    /// Canonical paths of the scripts currently executing, outermost first
+    stack: Vec<PathBuf>,
+}
+
+#[cfg(feature = "script")]
+impl ScriptLoader {
+    /// Create a loader with no script executing
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Resolve a `load()` path against the including script
+    ///
+    /// # Arguments
+    /// * `path` - Path as written in the `load()` call
+    ///
+    /// # Returns
+    /// * `PathBuf` - Path relative to the current script's directory
+    pub fn resolve(&self, path: &str) -> PathBuf {
+        let path = Path::new(path);
+        match self.stack.last().and_then(|current| current.parent()) {
+            Some(dir) if path.is_relative() => dir.join(path),
+            _ => path.to_path_buf(),
+        }
+    }
+
+    /// Start executing a script
+    ///
+    /// # Arguments
+    /// * `path` - Path as written in the `load()` call
+    ///
+    /// # Returns
+    /// * `Result<(PathBuf, Vec<Statement>)>` - Canonical path and statements,
+    ///   or an error if the file cannot be read or is already being loaded
+    pub fn enter(&mut self, path: &str) -> Result<(PathBuf, Vec<Statement>)> {
+        let resolved = self.resolve(path);
+        let canonical = resolved.canonicalize().map_err(|e| {
+            MongoshError::Generic(format!(
+                "load(): cannot open '{}': {}",
+                resolved.display(),
+                e
+            ))
+        })?;
+
+        if self.stack.contains(&canonical) {
+            let chain: Vec<String> = self
+                .stack
+                .iter()
+                .chain(std::iter::once(&canonical))
+                .map(|p| p.display().to_string())
+                .collect();
+            return Err(MongoshError::Generic(format!(
+                "load(): recursive include: {}",
+                chain.join(" -> ")
+            )));
+        }
+
+        let source = fs::read_to_string(&canonical).map_err(|e| {
+            MongoshError::Generic(format!(
+                "load(): cannot read '{}': {}",
+                canonical.display(),
+                e
+            ))
+        })?;
+
+        self.stack.push(canonical.clone());
+        Ok((canonical, split_statements(&source)))
+    }
+
+    /// Finish executing the innermost script
+    pub fn leave(&mut self) {
+        self.stack.pop();
+    }
+}
+
+/// Split script source into statements
+///
+/// # Arguments
+/// * `source` - Script source
+///
+/// # Returns
+/// * `Vec<Statement>` - Non-empty statements in order
+#[cfg(feature = "script")]
+pub fn split_statements(source: &str) -> Vec<Statement> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut statements = Vec::new();
+    let mut current = String::new();
+    let mut start_line = 1;
+    let mut line = 1;
+    let mut depth = 0usize;
+    let mut quote: Option<char> = None;
+    // Whether a '/' here starts a regex literal, and the word before it
+    let mut regex_allowed = true;
+    let mut word = String::new();
+    let mut i = 0;
+
+    let mut flush = |current: &mut String, start_line: usize| {
+        let text = current.trim();
+        if !text.is_empty() {
+            statements.push(Statement {
+                line: start_line,
+                text: text.to_string(),
+            });
+        }
+        current.clear();
+    };
+
+    while i < chars.len() {
+        let c = chars[i];
+
+        if let Some(q) = quote {
+            current.push(c);
+            if c == '\\' && i + 1 < chars.len() {
+                current.push(chars[i + 1]);
+                i += 1;
+            } else if c == q {
+                quote = None;
+                regex_allowed = false;
+            }
+            if c == '\n' {
+                line += 1;
+            }
+            i += 1;
+            continue;
+        }
+
+        match c {
+            '/' if chars.get(i + 1) == Some(&'/') => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+                continue;
+            }
+            '/' if chars.get(i + 1) == Some(&'*') => {
+                i += 2;
+                while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                    if chars[i] == '\n' {
+                        line += 1;
+                    }
+                    i += 1;
+                }
+                i += 2;
+                continue;
+            }
+            '/' if regex_allowed => {
+                let end = regex_end(&chars, i);
+                if current.trim().is_empty() {
+                    current.clear();
+                    start_line = line;
+                }
+                current.extend(&chars[i..end]);
+                regex_allowed = false;
+                i = end;
+                continue;
+            }
+            '\'' | '"' | '`' => quote = Some(c),
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth = depth.saturating_sub(1),
+            ';' if depth == 0 => {
+                flush(&mut current, start_line);
+                regex_allowed = true;
+                word.clear();
+                i += 1;
+                continue;
+            }
+            '\n' => {
+                line += 1;
+                if depth == 0 && !continues_chain(&chars[i + 1..]) {
+                    flush(&mut current, start_line);
+                    regex_allowed = true;
+                    word.clear();
+                    i += 1;
+                    continue;
+                }
+            }
+            _ => {}
+        }
+
+        // After an operand '/' divides, after an operator or keyword it
+        // starts a regex, as in the lexer
+        if c.is_alphanumeric() || c == '_' || c == '$' {
+            word.push(c);
+            regex_allowed = REGEX_PRECEDING_KEYWORDS.contains(&word.as_str());
+        } else {
+            word.clear();
+            if !c.is_whitespace() {
+                regex_allowed = !matches!(c, ')' | ']');
+            }
+        }
+
+        if current.trim().is_empty() {
+            current.clear();
+            start_line = line;
+        }
+        if !(c.is_whitespace() && current.is_empty()) {
+            current.push(c);
+        }
+        i += 1;
+    }
+    flush(&mut current, start_line);
+
+    statements
+}
+
+/// Find the end of the regex literal starting at `start`
+///
+/// A `/` inside a character class or after a backslash does not close it.
+/// An unterminated literal ends at the line break.
+#[cfg(feature = "script")]
+fn regex_end(chars: &[char], start: usize) -> usize {
+    let mut in_class = false;
+    let mut i = start + 1;
+    while i < chars.len() && chars[i] != '\n' {
+        match chars[i] {
+            '\\' => i += 1,
+            '[' => in_class = true,
+            ']' => in_class = false,
+            '/' if !in_class => return i + 1,
+            _ => {}
+        }
+        i += 1;
+    }
+    i.min(chars.len())
+}
+
+/// Check whether the next non-blank text continues a method chain
+#[cfg(feature = "script")]
+fn continues_chain(rest: &[char]) -> bool {
+    rest.iter().find(|c| !c.is_whitespace()) == Some(&'.')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_args_without_script() {
+        assert_eq!(argv()[0], "mongosh");
+        assert!(args().is_empty());
+    }
+
+    #[cfg(feature = "script")]
+    fn texts(source: &str) -> Vec<(usize, String)> {
+        split_statements(source)
+            .into_iter()
+            .map(|s| (s.line, s.text))
+            .collect()
+    }
+
+    #[test]
+    #[cfg(feature = "script")]
+    fn test_split_lines_and_semicolons() {
+        let source = "use test\n\ndb.users.find(); db.users.countDocuments()\n";
+        assert_eq!(
+            texts(source),
+            vec![
+                (1, "use test".to_string()),
+                (3, "db.users.find()".to_string()),
+                (3, "db.users.countDocuments()".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "script")]
+    fn test_split_multiline_statements() {
+        let source = "db.users.insertOne({\n  name: 'a;b',\n  // note; ignored\n  age: 1\n})\ndb.users.find()\n  .sort({ age: 1 })\n  .limit(2)\n";
+        assert_eq!(
+            texts(source),
+            vec![
+                (
+                    1,
+                    "db.users.insertOne({\n  name: 'a;b',\n  \n  age: 1\n})".to_string()
+                ),
+                (
+                    6,
+                    "db.users.find()\n  .sort({ age: 1 })\n  .limit(2)".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "script")]
+    fn test_split_skips_comments() {
+        let source = "// setup\n/* block\ncomment */\nprint('x // y')\n";
+        assert_eq!(texts(source), vec![(4, "print('x // y')".to_string())]);
+    }
+
+    #[test]
+    #[cfg(feature = "script")]
+    fn test_split_keeps_regex_literals() {
+        let source = "db.links.find({ url: /^https?:\\/\\// }) // web\ndb.a.find({ p: /[/;]/i }); db.a.find({ n: { $gt: 4 / 2 } })\n";
+        assert_eq!(
+            texts(source),
+            vec![
+                (1, "db.links.find({ url: /^https?:\\/\\// })".to_string()),
+                (2, "db.a.find({ p: /[/;]/i })".to_string()),
+                (2, "db.a.find({ n: { $gt: 4 / 2 } })".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "script")]
+    fn test_loader_resolves_relative_and_detects_recursion() {
+        let dir = std::env::temp_dir().join(format!("mongosh-load-{}", std::process::id()));
+        fs::create_dir_all(dir.join("lib")).unwrap();
+        fs::write(dir.join("main.js"), "load('lib/util.js')\n").unwrap();
+        fs::write(dir.join("lib/util.js"), "load('../main.js')\n").unwrap();
+
+        let mut loader = ScriptLoader::new();
+        let (main, statements) = loader.enter(dir.join("main.js").to_str().unwrap()).unwrap();
+        assert_eq!(statements[0].text, "load('lib/util.js')");
+        assert_eq!(
+            loader.resolve("lib/util.js"),
+            main.parent().unwrap().join("lib/util.js")
+        );
+
+        loader.enter("lib/util.js").unwrap();
+        let err = loader.enter("../main.js").unwrap_err();
+        assert!(err.to_string().contains("recursive include"));
+
+        loader.leave();
+        loader.leave();
+        assert!(loader.enter("missing.js").is_err());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
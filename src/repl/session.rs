@@ -0,0 +1,365 @@
+//! Session snapshots for `session save` and `session load`
+//!
+//! A snapshot records where an investigation was left off: the datasource and
+//! database in use, the runtime display and query settings, the previous
+//! result (`_`), named queries and bookmarks. It is written as TOML so it can
+//! be read and edited like the config file, and carries no credentials, so it
+//! can be copied to another machine that has the same datasource names
+//! configured.
+//!
+//! Files carry a format `version`. Files written before it was recorded read
+//! as version 1; settings they lack are left as they are when loaded.
+
+use mongodb::bson::{Bson, Document};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use crate::config::{OutputFormat, QueryConfig, SafetyConfig};
+use crate::error::{ConfigError, MongoshError, Result};
+use crate::executor::ResultData;
+use crate::formatter::Redactor;
+use crate::repl::SharedState;
+
+/// Session file format written by this version
+///
+/// 2 added `version`, the accessible, collapse and timezone display
+/// settings, `last_result`, `named_queries` and `bookmarks`.
+pub const SESSION_VERSION: u32 = 2;
+
+/// Saved session state
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionState {
+    /// Format of the file, 1 when it predates versioning
+    #[serde(default = "first_version")]
+    pub version: u32,
+
+    /// Named datasource in use, empty when connected by URI
+    #[serde(default)]
+    pub datasource: String,
+
+    /// Current database
+    pub database: String,
+
+    /// Output format
+    pub format: OutputFormat,
+
+    /// Color output
+    pub color: bool,
+
+    /// Screen reader friendly output
+    #[serde(default)]
+    pub accessible: Option<bool>,
+
+    /// Nesting depth below which documents and arrays are collapsed
+    #[serde(default)]
+    pub collapse_depth: Option<usize>,
+
+    /// Entries above which documents and arrays are collapsed
+    #[serde(default)]
+    pub collapse_width: Option<usize>,
+
+    /// Time zone dates are shown in
+    #[serde(default)]
+    pub timezone: Option<String>,
+
+    /// Previous result, referenced as `_`, as canonical Extended JSON
+    #[serde(default)]
+    pub last_result: Option<String>,
+
+    /// Query execution settings
+    #[serde(default)]
+    pub query: QueryConfig,
+
+    /// Safety limits
+    #[serde(default)]
+    pub safety: SafetyConfig,
+
+    /// Named queries by name (`query save`)
+    #[serde(default)]
+    pub named_queries: BTreeMap<String, String>,
+
+    /// Query bookmarks by name (`bookmark save`)
+    #[serde(default)]
+    pub bookmarks: BTreeMap<String, String>,
+}
+
+impl SessionState {
+    /// Capture the current session
+    ///
+    /// Named queries and bookmarks live in their own files; callers fill
+    /// them in. The previous result is masked according to `[redaction]`.
+    ///
+    /// # Arguments
+    /// * `state` - Shared REPL state
+    /// * `datasource` - Named datasource in use
+    ///
+    /// # Returns
+    /// * `Self` - Snapshot of the session
+    pub fn capture(state: &SharedState, datasource: String) -> Self {
+        let display = state.get_settings().display;
+        let last_result = state.get_last_result().and_then(|data| {
+            let redacted = Redactor::from_config(&state.get_redaction_config())
+                .and_then(|redactor| redactor.redact_data(&data));
+            redacted
+                .as_ref()
+                .unwrap_or(&data)
+                .to_bson()
+                .and_then(|value| serde_json::to_string(&value.into_canonical_extjson()).ok())
+        });
+
+        Self {
+            version: SESSION_VERSION,
+            datasource,
+            database: state.get_database(),
+            format: state.get_format(),
+            color: state.get_color_enabled(),
+            accessible: Some(display.accessible),
+            collapse_depth: Some(display.collapse_depth),
+            collapse_width: Some(display.collapse_width),
+            timezone: Some(display.timezone),
+            last_result,
+            query: state.get_query_config(),
+            safety: state.get_safety_config(),
+            named_queries: BTreeMap::new(),
+            bookmarks: BTreeMap::new(),
+        }
+    }
+
+    /// Restore the settings and previous result of this snapshot
+    ///
+    /// The datasource is not switched here since that needs a connection,
+    /// and named queries and bookmarks are not written to their files;
+    /// callers do both.
+    ///
+    /// # Arguments
+    /// * `state` - Shared REPL state to update
+    pub fn apply(&self, state: &SharedState) {
+        state.set_database(self.database.clone());
+
+        let mut settings = state.get_settings();
+        settings.display.format = self.format;
+        settings.display.color_output = self.color;
+        if let Some(accessible) = self.accessible {
+            settings.display.accessible = accessible;
+        }
+        if let Some(depth) = self.collapse_depth {
+            settings.display.collapse_depth = depth;
+        }
+        if let Some(width) = self.collapse_width {
+            settings.display.collapse_width = width;
+        }
+        if let Some(timezone) = &self.timezone {
+            settings.display.timezone = timezone.clone();
+        }
+        settings.query = self.query.clone();
+        settings.safety = self.safety.clone();
+        state.set_settings(settings);
+
+        if let Some(data) = self.last_result.as_deref().and_then(result_data) {
+            state.set_last_result(data);
+        }
+    }
+
+    /// Write the snapshot to a file
+    ///
+    /// # Arguments
+    /// * `path` - Destination file
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or error
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let content = toml::to_string_pretty(self).map_err(|e| {
+            MongoshError::Config(ConfigError::Generic(format!(
+                "Failed to serialize session: {}",
+                e
+            )))
+        })?;
+
+        fs::write(path, content).map_err(|e| {
+            MongoshError::Config(ConfigError::Generic(format!(
+                "Failed to write session file '{}': {}",
+                path.display(),
+                e
+            )))
+        })
+    }
+
+    /// Read a snapshot from a file
+    ///
+    /// # Arguments
+    /// * `path` - Session file
+    ///
+    /// # Returns
+    /// * `Result<Self>` - Snapshot or error
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path).map_err(|e| {
+            MongoshError::Config(ConfigError::Generic(format!(
+                "Failed to read session file '{}': {}",
+                path.display(),
+                e
+            )))
+        })?;
+
+        let session: Self = toml::from_str(&content).map_err(|e| {
+            MongoshError::Config(ConfigError::Generic(format!(
+                "Failed to parse session file '{}': {}",
+                path.display(),
+                e
+            )))
+        })?;
+
+        if session.version > SESSION_VERSION {
+            return Err(MongoshError::Config(ConfigError::Generic(format!(
+                "Session file '{}' has format version {}, this version reads up to {}",
+                path.display(),
+                session.version,
+                SESSION_VERSION
+            ))));
+        }
+        Ok(session)
+    }
+}
+
+/// Version of session files written before `version` was recorded
+fn first_version() -> u32 {
+    1
+}
+
+/// Previous result restored from Extended JSON
+///
+/// Only documents and lists of documents or strings come back; other
+/// values cannot be told apart from the results they were derived from.
+fn result_data(json: &str) -> Option<ResultData> {
+    let value: serde_json::Value = serde_json::from_str(json).ok()?;
+    match Bson::try_from(value).ok()? {
+        Bson::Document(document) => Some(ResultData::Document(document)),
+        Bson::Array(items) => {
+            let documents: Option<Vec<Document>> = items
+                .iter()
+                .map(|item| item.as_document().cloned())
+                .collect();
+            if let Some(documents) = documents {
+                return Some(ResultData::Documents(documents));
+            }
+            let strings: Option<Vec<String>> = items
+                .iter()
+                .map(|item| item.as_str().map(str::to_string))
+                .collect();
+            strings.map(ResultData::List)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::RedactionConfig;
+    use mongodb::bson::doc;
+
+    #[test]
+    fn test_session_round_trip() {
+        let state = SharedState::new("analytics".to_string());
+        state.set_format(OutputFormat::Table);
+        state.set_color_enabled(false);
+        state.set_query_config(QueryConfig {
+            keyset_pagination: true,
+            ..QueryConfig::default()
+        });
+        let mut settings = state.get_settings();
+        settings.display.accessible = true;
+        settings.display.timezone = "Europe/Berlin".to_string();
+        state.set_settings(settings);
+        state.set_last_result(ResultData::Documents(vec![doc! { "n": 1_i64 }]));
+
+        let mut session = SessionState::capture(&state, "prod".to_string());
+        session.bookmarks.insert(
+            "late".to_string(),
+            "db.orders.find({ late: true })".to_string(),
+        );
+        assert_eq!(session.version, SESSION_VERSION);
+        let path =
+            std::env::temp_dir().join(format!("mongosh-session-{}.toml", std::process::id()));
+        session.save(&path).unwrap();
+        let loaded = SessionState::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(loaded, session);
+
+        let restored = SharedState::new("test".to_string());
+        loaded.apply(&restored);
+        assert_eq!(restored.get_database(), "analytics");
+        assert_eq!(restored.get_format(), OutputFormat::Table);
+        assert!(!restored.get_color_enabled());
+        assert!(restored.get_query_config().keyset_pagination);
+        assert!(restored.get_accessible());
+        assert_eq!(restored.get_settings().display.timezone, "Europe/Berlin");
+        assert_eq!(
+            restored.get_last_result_value(),
+            Some(Bson::Array(vec![Bson::Document(doc! { "n": 1_i64 })]))
+        );
+    }
+
+    #[test]
+    fn test_session_capture_redacts_last_result() {
+        let state = SharedState::new("test".to_string());
+        state.set_redaction_config(RedactionConfig {
+            enabled: true,
+            fields: vec!["*.password".to_string()],
+            mask: "***".to_string(),
+        });
+        state.set_last_result(ResultData::Documents(vec![
+            doc! { "user": "ada", "password": "hunter2" },
+        ]));
+
+        let last_result = SessionState::capture(&state, "prod".to_string())
+            .last_result
+            .unwrap();
+        assert!(!last_result.contains("hunter2"));
+        assert!(last_result.contains("***"));
+    }
+
+    #[test]
+    fn test_session_version_1_loads() {
+        let path =
+            std::env::temp_dir().join(format!("mongosh-v1-session-{}.toml", std::process::id()));
+        fs::write(
+            &path,
+            "datasource = \"prod\"\ndatabase = \"shop\"\nformat = \"json\"\ncolor = true\n",
+        )
+        .unwrap();
+        let loaded = SessionState::load(&path);
+        fs::remove_file(&path).unwrap();
+        let loaded = loaded.unwrap();
+        assert_eq!(loaded.version, 1);
+        assert_eq!(loaded.timezone, None);
+        assert!(loaded.bookmarks.is_empty());
+
+        let state = SharedState::new("test".to_string());
+        state.set_accessible(true);
+        loaded.apply(&state);
+        assert_eq!(state.get_database(), "shop");
+        assert!(state.get_accessible());
+    }
+
+    #[test]
+    fn test_session_load_errors() {
+        assert!(SessionState::load(Path::new("/nonexistent/session.toml")).is_err());
+
+        let path =
+            std::env::temp_dir().join(format!("mongosh-bad-session-{}.toml", std::process::id()));
+        fs::write(&path, "database = 1\n").unwrap();
+        let result = SessionState::load(&path);
+        assert!(result.is_err());
+
+        fs::write(
+            &path,
+            "version = 99\ndatabase = \"shop\"\nformat = \"json\"\ncolor = true\n",
+        )
+        .unwrap();
+        let result = SessionState::load(&path);
+        fs::remove_file(&path).unwrap();
+        assert!(result.is_err());
+    }
+}
@@ -1,8 +1,14 @@
+use mongodb::bson::Bson;
 use std::sync::{Arc, RwLock};
 use tokio::sync::Mutex;
 
-use crate::config::{DisplayConfig, OutputFormat};
+use crate::config::{
+    Config, DisplayConfig, OutputFormat, QueryConfig, RedactionConfig, SafetyConfig,
+};
+use crate::executor::{PageBookmark, ResultCache, ResultData};
 use crate::repl::CursorState;
+use crate::repl::recording::{EntryKind, Recorder};
+use crate::repl::report::MarkdownReport;
 
 /// Shared state between REPL and execution context.
 #[derive(Debug, Clone)]
@@ -22,9 +28,42 @@ pub struct SharedState {
     /// Color output setting
     pub color_enabled: Arc<RwLock<bool>>,
 
+    /// Screen reader friendly output setting
+    pub accessible: Arc<RwLock<bool>>,
+
+    /// Show whole values instead of previews and collapsed values (`show full`)
+    pub full_values: Arc<RwLock<bool>>,
+
+    /// Query execution settings
+    pub query_config: Arc<RwLock<QueryConfig>>,
+
+    /// Safety limits
+    pub safety_config: Arc<RwLock<SafetyConfig>>,
+
+    /// Masking of sensitive fields
+    pub redaction_config: Arc<RwLock<RedactionConfig>>,
+
+    /// Full configuration as loaded and changed by `config set`
+    pub settings: Arc<RwLock<Config>>,
+
+    /// Data of the most recent result, referenced as `_` and saved by `output last`
+    last_result: Arc<RwLock<Option<ResultData>>>,
+
+    /// Transcript being written by `record start`, if any
+    recorder: Arc<RwLock<Option<Recorder>>>,
+
+    /// Markdown report being written by `report start`, if any
+    report: Arc<RwLock<Option<MarkdownReport>>>,
+
     /// Cursor state for pagination
     /// Uses Mutex because cursor needs mutable access and is not Clone
     cursor_state: Arc<Mutex<Option<CursorState>>>,
+
+    /// Results of recent reads, used when `query.result_cache` is on
+    result_cache: Arc<RwLock<ResultCache>>,
+
+    /// End of the last keyset page, used when `query.keyset_pagination` is on
+    page_bookmark: Arc<RwLock<Option<PageBookmark>>>,
 }
 
 impl SharedState {
@@ -46,7 +85,21 @@ impl SharedState {
             server_version: Arc::new(RwLock::new(None)),
             output_format: Arc::new(RwLock::new(display_config.format)),
             color_enabled: Arc::new(RwLock::new(display_config.color_output)),
+            accessible: Arc::new(RwLock::new(display_config.accessible)),
+            full_values: Arc::new(RwLock::new(false)),
+            query_config: Arc::new(RwLock::new(QueryConfig::default())),
+            safety_config: Arc::new(RwLock::new(SafetyConfig::default())),
+            redaction_config: Arc::new(RwLock::new(RedactionConfig::default())),
+            settings: Arc::new(RwLock::new(Config {
+                display: display_config.clone(),
+                ..Config::default()
+            })),
+            last_result: Arc::new(RwLock::new(None)),
+            recorder: Arc::new(RwLock::new(None)),
+            report: Arc::new(RwLock::new(None)),
             cursor_state: Arc::new(Mutex::new(None)),
+            result_cache: Arc::new(RwLock::new(ResultCache::default())),
+            page_bookmark: Arc::new(RwLock::new(None)),
         }
     }
 
@@ -86,6 +139,82 @@ impl SharedState {
         cursor.is_some()
     }
 
+    /// Get the data of the most recent result.
+    pub fn get_last_result(&self) -> Option<ResultData> {
+        self.last_result.read().unwrap().clone()
+    }
+
+    /// Get the most recent result as the value of `_`.
+    pub fn get_last_result_value(&self) -> Option<Bson> {
+        self.last_result
+            .read()
+            .unwrap()
+            .as_ref()
+            .and_then(ResultData::to_bson)
+    }
+
+    /// Keep the data of a result for `_` and `output last`.
+    pub fn set_last_result(&self, data: ResultData) {
+        *self.last_result.write().unwrap() = Some(data);
+    }
+
+    /// Get where the last keyset page ended.
+    pub fn get_page_bookmark(&self) -> Option<PageBookmark> {
+        self.page_bookmark.read().unwrap().clone()
+    }
+
+    /// Set where the last keyset page ended, `None` to forget it.
+    pub fn set_page_bookmark(&self, bookmark: Option<PageBookmark>) {
+        *self.page_bookmark.write().unwrap() = bookmark;
+    }
+
+    /// Get the result cache for a lookup or an update.
+    pub fn result_cache(&self) -> std::sync::RwLockWriteGuard<'_, ResultCache> {
+        self.result_cache.write().unwrap()
+    }
+
+    /// Start writing a session transcript, replacing any active recording.
+    pub fn start_recording(&self, recorder: Recorder) {
+        *self.recorder.write().unwrap() = Some(recorder);
+    }
+
+    /// Stop the active recording and return it.
+    pub fn stop_recording(&self) -> Option<Recorder> {
+        self.recorder.write().unwrap().take()
+    }
+
+    /// Start writing a Markdown report, replacing any open report.
+    pub fn start_report(&self, report: MarkdownReport) {
+        *self.report.write().unwrap() = Some(report);
+    }
+
+    /// Close the open report and return it.
+    pub fn stop_report(&self) -> Option<MarkdownReport> {
+        self.report.write().unwrap().take()
+    }
+
+    /// Append to the active recording and report; does nothing when neither is open.
+    ///
+    /// A failed write stops that recording or report so the error is reported
+    /// only once.
+    pub fn record(&self, kind: EntryKind, text: &str) {
+        let mut recorder = self.recorder.write().unwrap();
+        if let Some(active) = recorder.as_mut()
+            && let Err(e) = active.record(kind, text)
+        {
+            eprintln!("{}; recording stopped", e);
+            *recorder = None;
+        }
+
+        let mut report = self.report.write().unwrap();
+        if let Some(active) = report.as_mut()
+            && let Err(e) = active.append(kind, text)
+        {
+            eprintln!("{}; report closed", e);
+            *report = None;
+        }
+    }
+
     /// Get current database name.
     pub fn get_database(&self) -> String {
         self.current_database.read().unwrap().clone()
@@ -116,6 +245,99 @@ impl SharedState {
         *self.color_enabled.write().unwrap() = enabled;
     }
 
+    /// Get accessible output setting.
+    pub fn get_accessible(&self) -> bool {
+        *self.accessible.read().unwrap()
+    }
+
+    /// Set accessible output.
+    pub fn set_accessible(&self, enabled: bool) {
+        *self.accessible.write().unwrap() = enabled;
+    }
+
+    /// Get whether whole values are shown instead of previews.
+    pub fn get_full_values(&self) -> bool {
+        *self.full_values.read().unwrap()
+    }
+
+    /// Show whole values instead of previews and collapsed values.
+    pub fn set_full_values(&self, enabled: bool) {
+        *self.full_values.write().unwrap() = enabled;
+    }
+
+    /// Get query execution settings.
+    pub fn get_query_config(&self) -> QueryConfig {
+        self.query_config.read().unwrap().clone()
+    }
+
+    /// Set query execution settings.
+    pub fn set_query_config(&self, config: QueryConfig) {
+        *self.query_config.write().unwrap() = config;
+    }
+
+    /// Get safety limits.
+    pub fn get_safety_config(&self) -> SafetyConfig {
+        self.safety_config.read().unwrap().clone()
+    }
+
+    /// Set safety limits.
+    pub fn set_safety_config(&self, config: SafetyConfig) {
+        *self.safety_config.write().unwrap() = config;
+    }
+
+    /// Get sensitive field masking settings.
+    pub fn get_redaction_config(&self) -> RedactionConfig {
+        self.redaction_config.read().unwrap().clone()
+    }
+
+    /// Set sensitive field masking settings.
+    pub fn set_redaction_config(&self, config: RedactionConfig) {
+        *self.redaction_config.write().unwrap() = config;
+    }
+
+    /// Get the full configuration, including settings changed at runtime.
+    pub fn get_settings(&self) -> Config {
+        let mut config = self.settings.read().unwrap().clone();
+        config.display.format = self.get_format();
+        config.display.color_output = self.get_color_enabled();
+        config.display.accessible = self.get_accessible();
+        config.query = self.get_query_config();
+        config.safety = self.get_safety_config();
+        config.redaction = self.get_redaction_config();
+        config
+    }
+
+    /// Replace the full configuration and apply the settings read at runtime.
+    pub fn set_settings(&self, config: Config) {
+        self.set_format(config.display.format);
+        self.set_color_enabled(config.display.color_output);
+        self.set_accessible(config.display.accessible);
+        self.set_query_config(config.query.clone());
+        self.set_safety_config(config.safety.clone());
+        self.set_redaction_config(config.redaction.clone());
+        *self.settings.write().unwrap() = config;
+    }
+
+    /// Check whether a change to `key` takes effect without a restart.
+    pub fn is_live_setting(key: &str) -> bool {
+        const LIVE_KEYS: &[&str] = &[
+            "display.format",
+            "display.color_output",
+            "display.accessible",
+            "display.collapse_depth",
+            "display.collapse_width",
+            "display.max_string_length",
+            "display.max_binary_length",
+            "display.timezone",
+            "display.date_format",
+            "display.table_summary",
+        ];
+        const LIVE_SECTIONS: &[&str] = &["query", "safety", "redaction"];
+
+        let section = key.split('.').next().unwrap_or_default();
+        LIVE_KEYS.contains(&key) || LIVE_SECTIONS.contains(&section)
+    }
+
     /// Check if connected.
     pub fn is_connected(&self) -> bool {
         *self.connected.read().unwrap()
@@ -0,0 +1,492 @@
+//! JSON-RPC over stdio for editor integrations (`mongosh --rpc`)
+//!
+//! Another process, typically an editor extension, drives the shell's parser,
+//! completion and executor by writing JSON-RPC 2.0 requests to stdin, one per
+//! line, and reading one response per line from stdout:
+//!
+//! ```text
+//! --> {"jsonrpc": "2.0", "id": 1, "method": "complete", "params": {"text": "db.us", "pos": 5}}
+//! <-- {"jsonrpc": "2.0", "id": 1, "result": {"start": 3, "items": [{"label": "users", ...}]}}
+//! --> {"jsonrpc": "2.0", "id": 2, "method": "execute", "params": {"text": "db.users.find()"}}
+//! --> {"jsonrpc": "2.0", "id": 3, "method": "cancel", "params": {"id": 2}}
+//! ```
+//!
+//! Methods:
+//!
+//! - `complete {text, pos?}` - completion candidates at `pos` (a character
+//!   offset, the end of `text` by default) and the offset they replace from
+//! - `execute {text, database?, format?}` - run a command; the result has the
+//!   shape of a `mongosh serve` response body
+//! - `cancel {id}` - cancel a running `execute` request, which then fails
+//!   with code -32800
+//! - `listCollections {database?}` - collection names of a database
+//!
+//! Requests run concurrently, so responses may arrive out of order. The
+//! session is shared by all requests, like a REPL: `use` switches the database
+//! for the requests that follow. Logs go to stderr, stdout only carries
+//! responses. The process exits when stdin is closed and running requests
+//! have answered.
+//!
+//! Stdin carries requests, so nothing can be confirmed: operations that
+//! would ask first fail with a "needs confirmation" error, and monitors that
+//! run until Ctrl+C (`watch`, `oplog tail`, `--follow`) are refused.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use serde_json::{Value as JsonValue, json};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::sync::mpsc::{self, UnboundedSender};
+use tokio_util::sync::CancellationToken;
+use tracing::debug;
+
+use crate::config::OutputFormat;
+use crate::error::{MongoshError, Result};
+use crate::executor::{ConfirmationMode, ExecutionContext};
+use crate::parser::{Command, Parser};
+use crate::repl::completion::{CompletionEngine, MongoCandidateProvider};
+use crate::serve;
+
+/// Invalid JSON was received
+pub(crate) const PARSE_ERROR: i64 = -32700;
+
+/// The message is not a valid request object
+pub(crate) const INVALID_REQUEST: i64 = -32600;
+
+/// The method does not exist
+pub(crate) const METHOD_NOT_FOUND: i64 = -32601;
+
+/// Missing or mistyped parameters
+pub(crate) const INVALID_PARAMS: i64 = -32602;
+
+/// The command text could not be parsed
+const SYNTAX_ERROR: i64 = -32001;
+
+/// The command ran and failed
+pub(crate) const COMMAND_FAILED: i64 = -32000;
+
+/// The request was cancelled, as in the Language Server Protocol
+const REQUEST_CANCELLED: i64 = -32800;
+
+/// Error answered to a request
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct RpcError {
+    pub(crate) code: i64,
+    pub(crate) message: String,
+}
+
+impl RpcError {
+    pub(crate) fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+/// A parsed request; `id` is `None` for notifications, which get no response
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Request {
+    pub(crate) id: Option<JsonValue>,
+    pub(crate) method: String,
+    pub(crate) params: JsonValue,
+}
+
+/// State shared by all requests
+#[derive(Clone)]
+struct Server {
+    /// Session the commands run in
+    context: ExecutionContext,
+
+    /// Completion over the session's database
+    completion: Arc<CompletionEngine>,
+
+    /// Cancellation tokens of running `execute` requests, by serialized id
+    running: Arc<Mutex<HashMap<String, CancellationToken>>>,
+
+    /// Responses waiting to be written to stdout
+    responses: UnboundedSender<JsonValue>,
+}
+
+/// Answer requests from stdin until it is closed
+///
+/// # Arguments
+/// * `context` - Execution context shared by all requests
+///
+/// # Returns
+/// * `Result<()>` - Success or an error reading stdin
+pub async fn run(context: ExecutionContext) -> Result<()> {
+    let (responses, mut outgoing) = mpsc::unbounded_channel::<JsonValue>();
+    let writer = tokio::spawn(async move {
+        let mut stdout = tokio::io::stdout();
+        while let Some(response) = outgoing.recv().await {
+            let line = format!("{}\n", response);
+            if stdout.write_all(line.as_bytes()).await.is_err() || stdout.flush().await.is_err() {
+                break;
+            }
+        }
+    });
+
+    let provider = MongoCandidateProvider::new(
+        context.shared_state.clone(),
+        Some(Arc::new(context.clone())),
+    );
+    let server = Server {
+        context: context.with_confirmation(ConfirmationMode::Refuse),
+        completion: Arc::new(CompletionEngine::new(Arc::new(provider))),
+        running: Arc::new(Mutex::new(HashMap::new())),
+        responses,
+    };
+
+    let mut lines = BufReader::new(tokio::io::stdin()).lines();
+    while let Some(line) = lines
+        .next_line()
+        .await
+        .map_err(|e| MongoshError::Generic(format!("Failed to read request: {}", e)))?
+    {
+        if !line.trim().is_empty() {
+            server.handle(&line);
+        }
+    }
+
+    // The writer stops once running requests have answered and dropped their senders
+    drop(server);
+    let _ = writer.await;
+    Ok(())
+}
+
+impl Server {
+    /// Start answering one request line
+    fn handle(&self, line: &str) {
+        let request = match parse_request(line) {
+            Ok(request) => request,
+            Err(error) => {
+                self.send(Some(JsonValue::Null), Err(error));
+                return;
+            }
+        };
+        debug!("RPC request: {}", request.method);
+
+        // Register before spawning so a `cancel` on the next line finds it
+        let mut context = self.context.clone();
+        let mut key = None;
+        if request.method == "execute" {
+            context.reset_cancel_token();
+            if let Some(id) = &request.id {
+                let id = id.to_string();
+                self.running
+                    .lock()
+                    .unwrap()
+                    .insert(id.clone(), context.get_cancel_token());
+                key = Some(id);
+            }
+        }
+
+        let server = self.clone();
+        tokio::spawn(async move {
+            let response = server.call(&context, &request).await;
+            if let Some(key) = key {
+                server.running.lock().unwrap().remove(&key);
+            }
+            server.send(request.id, response);
+        });
+    }
+
+    /// Run a request's method
+    async fn call(
+        &self,
+        context: &ExecutionContext,
+        request: &Request,
+    ) -> std::result::Result<JsonValue, RpcError> {
+        let params = &request.params;
+        match request.method.as_str() {
+            "complete" => {
+                let text = string_param(params, "text")?.unwrap_or_default();
+                let pos = match params.get("pos") {
+                    None | Some(JsonValue::Null) => text.chars().count(),
+                    Some(pos) => pos.as_u64().ok_or_else(|| {
+                        RpcError::new(INVALID_PARAMS, "\"pos\" must be a non-negative integer")
+                    })? as usize,
+                };
+                // Collection names may be fetched, which blocks
+                let engine = self.completion.clone();
+                tokio::task::spawn_blocking(move || {
+                    let (start, pairs) = engine.complete(&text, byte_offset(&text, pos));
+                    let items: Vec<JsonValue> = pairs
+                        .into_iter()
+                        .map(|pair| {
+                            json!({
+                                "label": pair.display,
+                                "insertText": pair.replacement,
+                                "detail": pair.description,
+                            })
+                        })
+                        .collect();
+                    json!({ "start": char_offset(&text, start), "items": items })
+                })
+                .await
+                .map_err(|e| RpcError::new(COMMAND_FAILED, e.to_string()))
+            }
+            "execute" => execute(context, params).await,
+            "cancel" => {
+                let id = params
+                    .get("id")
+                    .filter(|id| !id.is_null())
+                    .ok_or_else(|| RpcError::new(INVALID_PARAMS, "\"id\" is required"))?;
+                let token = self.running.lock().unwrap().get(&id.to_string()).cloned();
+                if let Some(token) = &token {
+                    token.cancel();
+                }
+                Ok(json!({ "cancelled": token.is_some() }))
+            }
+            "listCollections" => {
+                let context = match string_param(params, "database")? {
+                    Some(database) => context.with_database(database),
+                    None => context.clone(),
+                };
+                let database = context
+                    .get_database()
+                    .await
+                    .map_err(|e| RpcError::new(COMMAND_FAILED, e.to_string()))?;
+                let mut names = database
+                    .list_collection_names()
+                    .await
+                    .map_err(|e| RpcError::new(COMMAND_FAILED, e.to_string()))?;
+                names.sort();
+                Ok(json!(names))
+            }
+            method => Err(RpcError::new(
+                METHOD_NOT_FOUND,
+                format!("Unknown method '{}'", method),
+            )),
+        }
+    }
+
+    /// Queue the response to a request; notifications are not answered
+    fn send(&self, id: Option<JsonValue>, response: std::result::Result<JsonValue, RpcError>) {
+        let Some(id) = id else {
+            return;
+        };
+        let _ = self.responses.send(response_message(id, response));
+    }
+}
+
+/// Parse, check and execute the command of an `execute` request
+async fn execute(
+    context: &ExecutionContext,
+    params: &JsonValue,
+) -> std::result::Result<JsonValue, RpcError> {
+    let text = string_param(params, "text")?
+        .filter(|text| !text.trim().is_empty())
+        .ok_or_else(|| RpcError::new(INVALID_PARAMS, "\"text\" is required"))?;
+    let format =
+        match string_param(params, "format")? {
+            Some(name) => Some(OutputFormat::from_name(&name).ok_or_else(|| {
+                RpcError::new(INVALID_PARAMS, format!("Unknown format '{}'", name))
+            })?),
+            None => None,
+        };
+
+    let mut parser = Parser::new();
+    parser.set_last_result(context.shared_state.get_last_result_value());
+    let command = parser
+        .parse(&text)
+        .map_err(|e| RpcError::new(SYNTAX_ERROR, e.to_string()))?;
+    if matches!(
+        command,
+        Command::Exit
+            | Command::Retry
+            | Command::AiQuery(_)
+            | Command::Load(_)
+            | Command::Record(_)
+            | Command::Report(_)
+    ) || command.runs_until_interrupted()
+    {
+        return Err(RpcError::new(
+            COMMAND_FAILED,
+            "This command is only available in the interactive shell",
+        ));
+    }
+
+    let format = command.format_override().or(format);
+    let fields = command.pluck_fields().map(<[String]>::to_vec);
+    let command = match string_param(params, "database")? {
+        Some(database) => Command::WithDatabase {
+            database,
+            command: Box::new(command),
+        },
+        None => command,
+    };
+
+    let cancel_token = context.get_cancel_token();
+    let result = tokio::select! {
+        result = context.execute(command) => result,
+        _ = cancel_token.cancelled() => {
+            return Err(RpcError::new(REQUEST_CANCELLED, "Request cancelled"));
+        }
+    };
+    match result {
+        Ok(result) if result.success => Ok(serve::result_body(
+            context,
+            &result,
+            format,
+            fields.as_deref(),
+        )),
+        Ok(result) => Err(RpcError::new(
+            COMMAND_FAILED,
+            result.error.as_deref().unwrap_or("Command failed"),
+        )),
+        Err(e) => Err(RpcError::new(COMMAND_FAILED, e.to_string())),
+    }
+}
+
+/// Parse one request line
+pub(crate) fn parse_request(line: &str) -> std::result::Result<Request, RpcError> {
+    let value: JsonValue = serde_json::from_str(line)
+        .map_err(|e| RpcError::new(PARSE_ERROR, format!("Invalid JSON: {}", e)))?;
+    let object = value
+        .as_object()
+        .ok_or_else(|| RpcError::new(INVALID_REQUEST, "Request must be a JSON object"))?;
+
+    if object.get("jsonrpc").and_then(JsonValue::as_str) != Some("2.0") {
+        return Err(RpcError::new(
+            INVALID_REQUEST,
+            "\"jsonrpc\" must be \"2.0\"",
+        ));
+    }
+    let method = object
+        .get("method")
+        .and_then(JsonValue::as_str)
+        .ok_or_else(|| RpcError::new(INVALID_REQUEST, "\"method\" must be a string"))?;
+    let id = object.get("id").cloned();
+    if let Some(id) = &id
+        && !(id.is_string() || id.is_number() || id.is_null())
+    {
+        return Err(RpcError::new(
+            INVALID_REQUEST,
+            "\"id\" must be a string or a number",
+        ));
+    }
+    let params = match object.get("params") {
+        None | Some(JsonValue::Null) => json!({}),
+        Some(params @ JsonValue::Object(_)) => params.clone(),
+        Some(_) => {
+            return Err(RpcError::new(
+                INVALID_PARAMS,
+                "\"params\" must be an object",
+            ));
+        }
+    };
+
+    Ok(Request {
+        id,
+        method: method.to_string(),
+        params,
+    })
+}
+
+/// Response message for a request id
+pub(crate) fn response_message(
+    id: JsonValue,
+    response: std::result::Result<JsonValue, RpcError>,
+) -> JsonValue {
+    match response {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(error) => json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": { "code": error.code, "message": error.message },
+        }),
+    }
+}
+
+/// Optional string parameter
+pub(crate) fn string_param(
+    params: &JsonValue,
+    name: &str,
+) -> std::result::Result<Option<String>, RpcError> {
+    match params.get(name) {
+        None | Some(JsonValue::Null) => Ok(None),
+        Some(JsonValue::String(s)) => Ok(Some(s.clone())),
+        Some(_) => Err(RpcError::new(
+            INVALID_PARAMS,
+            format!("\"{}\" must be a string", name),
+        )),
+    }
+}
+
+/// Byte index of a character offset, clamped to the end of the text
+fn byte_offset(text: &str, chars: usize) -> usize {
+    text.char_indices()
+        .nth(chars)
+        .map_or(text.len(), |(index, _)| index)
+}
+
+/// Character offset of a byte index
+fn char_offset(text: &str, bytes: usize) -> usize {
+    text.get(..bytes)
+        .map_or_else(|| text.chars().count(), |prefix| prefix.chars().count())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_request() {
+        let request = parse_request(
+            r#"{"jsonrpc": "2.0", "id": 7, "method": "complete", "params": {"text": "db."}}"#,
+        )
+        .unwrap();
+        assert_eq!(request.id, Some(json!(7)));
+        assert_eq!(request.method, "complete");
+        assert_eq!(request.params, json!({ "text": "db." }));
+
+        let notification =
+            parse_request(r#"{"jsonrpc": "2.0", "method": "cancel", "params": {"id": "a"}}"#)
+                .unwrap();
+        assert_eq!(notification.id, None);
+
+        let code = |line: &str| parse_request(line).unwrap_err().code;
+        assert_eq!(code("{not json"), PARSE_ERROR);
+        assert_eq!(code("[1]"), INVALID_REQUEST);
+        assert_eq!(code(r#"{"id": 1, "method": "execute"}"#), INVALID_REQUEST);
+        assert_eq!(code(r#"{"jsonrpc": "2.0", "id": 1}"#), INVALID_REQUEST);
+        assert_eq!(
+            code(r#"{"jsonrpc": "2.0", "id": {}, "method": "execute"}"#),
+            INVALID_REQUEST
+        );
+        assert_eq!(
+            code(r#"{"jsonrpc": "2.0", "id": 1, "method": "execute", "params": [1]}"#),
+            INVALID_PARAMS
+        );
+    }
+
+    #[test]
+    fn test_response_message() {
+        assert_eq!(
+            response_message(json!(1), Ok(json!(["users"]))),
+            json!({ "jsonrpc": "2.0", "id": 1, "result": ["users"] })
+        );
+        assert_eq!(
+            response_message(
+                json!("a"),
+                Err(RpcError::new(REQUEST_CANCELLED, "Request cancelled"))
+            ),
+            json!({
+                "jsonrpc": "2.0",
+                "id": "a",
+                "error": { "code": -32800, "message": "Request cancelled" },
+            })
+        );
+    }
+
+    #[test]
+    fn test_offsets() {
+        let text = "db.café.fi";
+        assert_eq!(byte_offset(text, 7), 8);
+        assert_eq!(byte_offset(text, 100), text.len());
+        assert_eq!(char_offset(text, 8), 7);
+        assert_eq!(char_offset(text, text.len()), 10);
+    }
+}
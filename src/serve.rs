@@ -0,0 +1,524 @@
+//! REST bridge for `mongosh serve`
+//!
+//! `POST /query` runs one shell or SQL command and answers with its result as
+//! Extended JSON (relaxed), so internal tools can query through mongosh without
+//! a driver. The body is either the command text itself or a JSON object:
+//!
+//! ```text
+//! curl -H 'Content-Type: text/plain' -d 'db.orders.find({status: "pending"}).limit(5)' \
+//!     localhost:8080/query
+//! curl -H 'Content-Type: application/json' \
+//!     -d '{"command": "SELECT name FROM users", "database": "shop", "format": "table"}' \
+//!     localhost:8080/query
+//! ```
+//!
+//! `database` runs the command against another database without switching
+//! the shared session, and `format` adds the text the shell would print as
+//! `output`. Only queries and allow-listed admin and utility commands run;
+//! commands that only make sense in the REPL (`use`, `load()`,
+//! monitors, files and clipboard) are refused, and so are writes unless the
+//! server was started with `--allow-writes`. `GET /health` answers
+//! `{"ok": true}`.
+//!
+//! Browsers must not reach the bridge from other sites: requests carrying an
+//! `Origin` header and bodies that are neither `text/plain` nor
+//! `application/json` are refused. With `--token` (or `MONGOSH_SERVE_TOKEN`)
+//! `POST /query` also needs an `Authorization: Bearer <token>` header.
+
+use std::sync::Arc;
+
+use serde_json::{Value as JsonValue, json};
+use tokio::net::{TcpListener, TcpStream};
+use tracing::{debug, info};
+
+use crate::config::{DisplayConfig, OutputFormat};
+use crate::error::{MongoshError, Result};
+use crate::executor::{self, ConfirmationMode, ExecutionContext, ExecutionResult};
+use crate::formatter::{FieldProjection, Formatter, Redactor};
+use crate::http;
+use crate::parser::{AdminCommand, Command, Parser, PipeCommand, QueryCommand, UtilityCommand};
+
+/// Where and how `mongosh serve` listens
+#[derive(Debug, Clone)]
+pub struct ServeOptions {
+    /// Address to bind, e.g. `127.0.0.1`
+    pub bind: String,
+
+    /// TCP port
+    pub port: u16,
+
+    /// Whether commands that modify data are run
+    pub allow_writes: bool,
+
+    /// Bearer token `POST /query` must present, any client when `None`
+    pub token: Option<String>,
+}
+
+/// Body of a `POST /query` request
+#[derive(Debug, Clone, PartialEq)]
+struct QueryRequest {
+    /// Shell or SQL command
+    command: String,
+
+    /// Database to run the command against, the session's when `None`
+    database: Option<String>,
+
+    /// Format of the `output` text, no text when `None`
+    format: Option<OutputFormat>,
+}
+
+/// Serve requests until the process is stopped
+///
+/// # Arguments
+/// * `context` - Execution context shared by all requests
+/// * `options` - Address and write permission
+///
+/// # Returns
+/// * `Result<()>` - Bind error; otherwise never returns
+pub async fn run(context: ExecutionContext, options: ServeOptions) -> Result<()> {
+    let listener = TcpListener::bind((options.bind.as_str(), options.port))
+        .await
+        .map_err(|e| {
+            MongoshError::Generic(format!(
+                "Failed to listen on {}:{}: {}",
+                options.bind, options.port, e
+            ))
+        })?;
+    info!("Serving REST bridge on {}:{}", options.bind, options.port);
+    println!(
+        "Listening on http://{}:{} (POST /query{})",
+        options.bind,
+        options.port,
+        if options.allow_writes {
+            ", writes allowed"
+        } else {
+            ", read-only"
+        }
+    );
+
+    // Nobody can answer a prompt; --allow-writes is the consent for writes
+    let context = context.with_confirmation(if options.allow_writes {
+        ConfirmationMode::Confirmed
+    } else {
+        ConfirmationMode::Refuse
+    });
+    let options = Arc::new(options);
+    loop {
+        let (stream, _) = match listener.accept().await {
+            Ok(connection) => connection,
+            Err(e) => {
+                debug!("REST bridge connection failed: {}", e);
+                continue;
+            }
+        };
+        let context = context.clone();
+        let options = options.clone();
+        tokio::spawn(async move {
+            if let Err(e) = respond(stream, &context, &options).await {
+                debug!("REST bridge request failed: {}", e);
+            }
+        });
+    }
+}
+
+/// Answer one HTTP request
+async fn respond(
+    mut stream: TcpStream,
+    context: &ExecutionContext,
+    options: &ServeOptions,
+) -> std::io::Result<()> {
+    let (status, body) = match http::read_request(&mut stream).await {
+        Ok(request) if request.header("origin").is_some() => (
+            "403 Forbidden",
+            error_body("Cross-origin requests are not allowed"),
+        ),
+        Ok(request) => match (request.method.as_str(), request.path.as_str()) {
+            ("POST", "/query") => {
+                if let Err((status, message)) = check_request(&request, options.token.as_deref()) {
+                    (status, error_body(message))
+                } else {
+                    run_query(context, &request.body, options.allow_writes).await
+                }
+            }
+            ("GET", "/health") => ("200 OK", json!({ "ok": true })),
+            (_, "/query" | "/health") => {
+                ("405 Method Not Allowed", error_body("Method not allowed"))
+            }
+            _ => ("404 Not Found", error_body("Not found")),
+        },
+        Err(e) if e.kind() == std::io::ErrorKind::InvalidData => {
+            ("400 Bad Request", error_body(&e.to_string()))
+        }
+        Err(e) => return Err(e),
+    };
+
+    http::write_response(&mut stream, status, "application/json", &body.to_string()).await
+}
+
+/// Check the token and body type of a `POST /query` request
+///
+/// Only `text/plain` and `application/json` bodies are accepted, so HTML
+/// forms cannot post commands; a missing `Content-Type` is fine for scripts.
+///
+/// # Returns
+/// * `Result<(), (&str, &str)>` - HTTP status and message of a refused request
+fn check_request(
+    request: &http::Request,
+    token: Option<&str>,
+) -> std::result::Result<(), (&'static str, &'static str)> {
+    if let Some(token) = token {
+        let presented = request
+            .header("authorization")
+            .and_then(|value| value.strip_prefix("Bearer "));
+        if !presented.is_some_and(|presented| same_token(presented.trim(), token)) {
+            return Err(("401 Unauthorized", "Missing or invalid token"));
+        }
+    }
+
+    if let Some(content_type) = request.header("content-type") {
+        let media_type = content_type.split(';').next().unwrap_or_default().trim();
+        if !media_type.eq_ignore_ascii_case("text/plain")
+            && !media_type.eq_ignore_ascii_case("application/json")
+        {
+            return Err((
+                "415 Unsupported Media Type",
+                "Send the command as text/plain or application/json",
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Compare tokens in time independent of where they differ
+fn same_token(presented: &str, expected: &str) -> bool {
+    presented.len() == expected.len()
+        && presented
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+/// Parse, check and execute the command of a `POST /query` request
+///
+/// # Returns
+/// * `(&str, JsonValue)` - HTTP status and response body
+async fn run_query(
+    context: &ExecutionContext,
+    body: &[u8],
+    allow_writes: bool,
+) -> (&'static str, JsonValue) {
+    let request = match parse_request(body) {
+        Ok(request) => request,
+        Err(message) => return ("400 Bad Request", error_body(&message)),
+    };
+    let command = match Parser::new().parse(&request.command) {
+        Ok(command) => command,
+        Err(e) => return ("400 Bad Request", error_body(&e.to_string())),
+    };
+    if let Err(message) = check_allowed(&command, allow_writes) {
+        return ("403 Forbidden", error_body(&message));
+    }
+
+    let format = command.format_override().or(request.format);
+    let fields = command.pluck_fields().map(<[String]>::to_vec);
+    let command = match request.database {
+        Some(database) => Command::WithDatabase {
+            database,
+            command: Box::new(command),
+        },
+        None => command,
+    };
+
+    match context.execute(command).await {
+        Ok(result) if result.success => {
+            let mut body = result_body(context, &result, format, fields.as_deref());
+            body["ok"] = json!(true);
+            ("200 OK", body)
+        }
+        Ok(result) => (
+            "422 Unprocessable Entity",
+            error_body(result.error.as_deref().unwrap_or("Command failed")),
+        ),
+        Err(e) => ("500 Internal Server Error", error_body(&e.to_string())),
+    }
+}
+
+/// Read the command from a raw text or JSON body
+fn parse_request(body: &[u8]) -> std::result::Result<QueryRequest, String> {
+    let text = std::str::from_utf8(body).map_err(|_| "Body is not valid UTF-8".to_string())?;
+    let text = text.trim();
+
+    let request = if text.starts_with('{') {
+        let value: JsonValue =
+            serde_json::from_str(text).map_err(|e| format!("Invalid JSON body: {}", e))?;
+        let field = |name: &str| -> std::result::Result<Option<String>, String> {
+            match value.get(name) {
+                None | Some(JsonValue::Null) => Ok(None),
+                Some(JsonValue::String(s)) => Ok(Some(s.clone())),
+                Some(_) => Err(format!("\"{}\" must be a string", name)),
+            }
+        };
+        let format = match field("format")? {
+            Some(name) => Some(
+                OutputFormat::from_name(&name)
+                    .ok_or_else(|| format!("Unknown format '{}'", name))?,
+            ),
+            None => None,
+        };
+        QueryRequest {
+            command: field("command")?.unwrap_or_default(),
+            database: field("database")?,
+            format,
+        }
+    } else {
+        QueryRequest {
+            command: text.to_string(),
+            database: None,
+            format: None,
+        }
+    };
+
+    if request.command.trim().is_empty() {
+        return Err("No command given".to_string());
+    }
+    Ok(request)
+}
+
+/// Refuse commands that need a terminal, touch local files, reach other
+/// datasources or change the shared session, and writes when they are not
+/// allowed
+///
+/// Admin and utility commands are allow-listed, so commands added later stay
+/// local until they are added here.
+fn check_allowed(command: &Command, allow_writes: bool) -> std::result::Result<(), String> {
+    let local_only = match command {
+        // A diff can write a local file or connect to another datasource
+        Command::Query(QueryCommand::Diff { options, .. }) => {
+            options.output.is_some() || options.datasource.is_some()
+        }
+        Command::Query(_) | Command::Help(_) => false,
+        Command::Admin(admin) => !matches!(
+            admin,
+            AdminCommand::ShowDatabases
+                | AdminCommand::ShowCollections
+                | AdminCommand::ShowUsers
+                | AdminCommand::ShowRoles
+                | AdminCommand::ShowProfile
+                | AdminCommand::ShowLogs { follow: None, .. }
+                | AdminCommand::Top { watch: None, .. }
+                | AdminCommand::OplogInfo
+                | AdminCommand::ReplicationLag { watch: None, .. }
+                | AdminCommand::CurrentOp { watch: None, .. }
+                | AdminCommand::ListIndexes(_)
+                | AdminCommand::StorageReport
+                | AdminCommand::ClusterReport
+                | AdminCommand::IndexStats(_)
+                | AdminCommand::IndexReport { .. }
+                | AdminCommand::CollectionStats { .. }
+                | AdminCommand::GetValidator(_)
+                | AdminCommand::ValidateCollection { .. }
+                | AdminCommand::ListBackups
+                | AdminCommand::CreateIndex { .. }
+                | AdminCommand::CreateIndexes { .. }
+                | AdminCommand::DropIndex { .. }
+                | AdminCommand::DropIndexes { .. }
+                | AdminCommand::DropCollection { .. }
+                | AdminCommand::DropCollections { .. }
+                | AdminCommand::RenameCollection { .. }
+                | AdminCommand::SetValidator { .. }
+                | AdminCommand::CreateCollection { .. }
+                | AdminCommand::CollMod { .. }
+                | AdminCommand::ConvertToCapped { .. }
+                | AdminCommand::RunCommand { .. }
+        ),
+        Command::Utility(UtilityCommand::Inspect { command, .. }) => {
+            return check_allowed(command, allow_writes);
+        }
+        Command::Utility(utility) => !matches!(
+            utility,
+            UtilityCommand::Print(_)
+                | UtilityCommand::PrintJson(_)
+                | UtilityCommand::BsonSize(_)
+                | UtilityCommand::Sleep(_)
+                | UtilityCommand::Translate { .. }
+                | UtilityCommand::SqlExplain(_)
+                | UtilityCommand::Describe(_)
+                | UtilityCommand::ExpandLastResult(_)
+        ),
+        Command::Pipe(base, pipe) => {
+            check_allowed(base, allow_writes)?;
+            !matches!(
+                pipe,
+                PipeCommand::Explain
+                    | PipeCommand::Format(_)
+                    | PipeCommand::Pluck(_)
+                    | PipeCommand::Full
+            )
+        }
+        Command::WithDatabase { command, .. } => return check_allowed(command, allow_writes),
+        Command::Parallel { commands, .. } => {
+            return commands
+                .iter()
+                .try_for_each(|command| check_allowed(command, allow_writes));
+        }
+        _ => true,
+    } || command.runs_until_interrupted();
+
+    if local_only {
+        Err("This command is only available in the interactive shell".to_string())
+    } else if !allow_writes && !executor::is_read_only(command) {
+        Err("Writes are disabled; start the server with --allow-writes".to_string())
+    } else {
+        Ok(())
+    }
+}
+
+/// JSON of a successful command's result, shared with the `--rpc` mode
+///
+/// Holds `result` as relaxed Extended JSON, `stats`, the `message` of a
+/// message result and, when `format` is given, the text the shell would print
+/// as `output`.
+pub(crate) fn result_body(
+    context: &ExecutionContext,
+    result: &ExecutionResult,
+    format: Option<OutputFormat>,
+    fields: Option<&[String]>,
+) -> JsonValue {
+    let redaction = context.shared_state.get_redaction_config();
+    let projected = fields
+        .and_then(FieldProjection::new)
+        .and_then(|projection| projection.project_data(&result.data));
+    let data = projected.as_ref().unwrap_or(&result.data);
+    let redacted =
+        Redactor::from_config(&redaction).and_then(|redactor| redactor.redact_data(data));
+    let data = redacted.as_ref().unwrap_or(data);
+
+    let mut body = json!({
+        "result": data.to_bson().map(|value| value.into_relaxed_extjson()),
+        "stats": {
+            "executionTimeMs": result.stats.execution_time_ms,
+            "documentsReturned": result.stats.documents_returned,
+            "documentsAffected": result.stats.documents_affected,
+        },
+    });
+    if let executor::ResultData::Message(message) = &result.data {
+        body["message"] = json!(message);
+    }
+    if let Some(format) = format {
+        let display = DisplayConfig {
+            format,
+            color_output: false,
+            ..context.shared_state.get_settings().display
+        };
+        let formatter = Formatter::from_config(&display)
+            .with_projection(fields.unwrap_or_default())
+            .with_redaction(&redaction);
+        if let Ok(output) = formatter.format(result) {
+            body["output"] = json!(output);
+        }
+    }
+    body
+}
+
+fn error_body(message: &str) -> JsonValue {
+    json!({ "ok": false, "error": message })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mongodb::bson::doc;
+
+    #[test]
+    fn test_parse_request() {
+        assert_eq!(
+            parse_request(b" db.users.find() \n").unwrap(),
+            QueryRequest {
+                command: "db.users.find()".to_string(),
+                database: None,
+                format: None,
+            }
+        );
+        assert_eq!(
+            parse_request(
+                br#"{"command": "show collections", "database": "shop", "format": "table"}"#
+            )
+            .unwrap(),
+            QueryRequest {
+                command: "show collections".to_string(),
+                database: Some("shop".to_string()),
+                format: Some(OutputFormat::Table),
+            }
+        );
+        assert!(parse_request(b"").is_err());
+        assert!(parse_request(br#"{"database": "shop"}"#).is_err());
+        assert!(parse_request(br#"{"command": 1}"#).is_err());
+        assert!(parse_request(br#"{"command": "x", "format": "yaml"}"#).is_err());
+        assert!(parse_request(b"{not json").is_err());
+    }
+
+    #[test]
+    fn test_check_allowed() {
+        let parse = |input: &str| Parser::new().parse(input).unwrap();
+
+        assert!(check_allowed(&parse("db.users.find()"), false).is_ok());
+        assert!(check_allowed(&parse("show dbs"), false).is_ok());
+        assert!(check_allowed(&parse("use shop"), true).is_err());
+        assert!(check_allowed(&parse("copy"), true).is_err());
+        assert!(check_allowed(&parse("format table"), true).is_err());
+        assert!(check_allowed(&parse("top"), false).is_ok());
+        assert!(check_allowed(&parse("top --watch 5"), true).is_err());
+        assert!(check_allowed(&parse("currentOp"), false).is_ok());
+        assert!(check_allowed(&parse("currentOp --watch"), true).is_err());
+        assert!(check_allowed(&parse("oplog tail"), true).is_err());
+        assert!(check_allowed(&parse("rs.lag"), false).is_ok());
+        assert!(check_allowed(&parse("rs.lag --watch"), true).is_err());
+        assert!(check_allowed(&parse("undo last-delete"), true).is_err());
+        assert!(check_allowed(&parse("watch 5 db.users.find()"), true).is_err());
+        assert!(check_allowed(&parse("db.a.diff('b')"), false).is_ok());
+        assert!(check_allowed(&parse("db.a.diff('b', { out: 'a.jsonl' })"), true).is_err());
+        assert!(check_allowed(&parse("db.a.diff('b', { datasource: 'prod' })"), true).is_err());
+
+        let delete = Command::Query(QueryCommand::DeleteOne {
+            collection: "users".to_string(),
+            filter: doc! { "_id": 1 },
+        });
+        assert!(check_allowed(&delete, false).is_err());
+        assert!(check_allowed(&delete, true).is_ok());
+    }
+
+    #[test]
+    fn test_check_request() {
+        let request = |headers: &[(&str, &str)]| http::Request {
+            method: "POST".to_string(),
+            path: "/query".to_string(),
+            headers: headers
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+            body: Vec::new(),
+        };
+
+        assert!(check_request(&request(&[]), None).is_ok());
+        assert!(
+            check_request(
+                &request(&[("content-type", "text/plain; charset=utf-8")]),
+                None
+            )
+            .is_ok()
+        );
+        assert!(
+            check_request(
+                &request(&[("content-type", "application/x-www-form-urlencoded")]),
+                None
+            )
+            .is_err()
+        );
+
+        let authorized = request(&[("authorization", "Bearer s3cret")]);
+        assert!(check_request(&authorized, Some("s3cret")).is_ok());
+        assert!(check_request(&authorized, Some("other")).is_err());
+        assert!(check_request(&request(&[]), Some("s3cret")).is_err());
+    }
+}
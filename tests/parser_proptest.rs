@@ -0,0 +1,240 @@
+//! Property tests for the shell and SQL parsers
+//!
+//! Commands are generated, rendered to the text a user would type and parsed
+//! back; the result must equal the generated command. Arbitrary input must
+//! produce a command or an error, never a panic.
+
+use bson::{Bson, Document, doc};
+use mongosh::parser::{AdminCommand, Command, FindOptions, Parser, QueryCommand, fuzz_entry};
+use proptest::prelude::*;
+
+/// Collection names; the prefix keeps them clear of `db` method names
+fn collection_name() -> impl Strategy<Value = String> {
+    "c_[a-z0-9_]{0,10}"
+}
+
+/// Field names; the prefix keeps them clear of operators and SQL keywords
+fn field_name() -> impl Strategy<Value = String> {
+    "f_[a-z0-9_]{0,8}"
+}
+
+/// BSON values the shell syntax can express as literals
+fn value() -> impl Strategy<Value = Bson> {
+    let leaf = prop_oneof![
+        (-1_000_000i64..1_000_000).prop_map(Bson::Int64),
+        "[a-zA-Z0-9 _.:-]{0,16}".prop_map(Bson::String),
+        any::<bool>().prop_map(Bson::Boolean),
+        Just(Bson::Null),
+    ];
+    leaf.prop_recursive(3, 24, 4, |inner| {
+        prop_oneof![
+            prop::collection::vec(inner.clone(), 0..4).prop_map(Bson::Array),
+            prop::collection::vec((field_name(), inner), 0..4)
+                .prop_map(|fields| Bson::Document(fields.into_iter().collect())),
+        ]
+    })
+}
+
+/// Documents with unique field names
+fn document() -> impl Strategy<Value = Document> {
+    prop::collection::vec((field_name(), value()), 0..5)
+        .prop_map(|fields| fields.into_iter().collect())
+}
+
+/// Sort specifications with ascending or descending fields
+fn sort_spec() -> impl Strategy<Value = Document> {
+    prop::collection::vec((field_name(), prop_oneof![Just(1i64), Just(-1i64)]), 1..3).prop_map(
+        |fields| {
+            fields
+                .into_iter()
+                .map(|(field, direction)| (field, Bson::Int64(direction)))
+                .collect()
+        },
+    )
+}
+
+/// Commands covered by the shell round trip
+fn command() -> impl Strategy<Value = Command> {
+    prop_oneof![
+        (
+            collection_name(),
+            document(),
+            prop::option::of(sort_spec()),
+            prop::option::of(0u64..10_000),
+            prop::option::of(0i64..10_000),
+        )
+            .prop_map(|(collection, filter, sort, skip, limit)| {
+                Command::Query(QueryCommand::Find {
+                    collection,
+                    filter,
+                    options: FindOptions {
+                        sort,
+                        skip,
+                        limit,
+                        ..Default::default()
+                    },
+                })
+            }),
+        (collection_name(), document()).prop_map(|(collection, document)| {
+            Command::Query(QueryCommand::InsertOne {
+                collection,
+                document,
+            })
+        }),
+        (collection_name(), document()).prop_map(|(collection, filter)| {
+            Command::Query(QueryCommand::DeleteMany { collection, filter })
+        }),
+        (collection_name(), document()).prop_map(|(collection, filter)| {
+            Command::Query(QueryCommand::CountDocuments { collection, filter })
+        }),
+        collection_name().prop_map(|name| Command::Admin(AdminCommand::DropCollection {
+            collection: name,
+            backup: None,
+        })),
+        "[a-z][a-z0-9_]{0,10}".prop_map(|name| Command::Admin(AdminCommand::UseDatabase(name))),
+        Just(Command::Admin(AdminCommand::ShowCollections)),
+        Just(Command::Admin(AdminCommand::ShowDatabases)),
+    ]
+}
+
+/// Render a value as a shell literal
+fn render_value(value: &Bson) -> String {
+    match value {
+        Bson::Int64(n) => n.to_string(),
+        Bson::String(s) => format!("'{}'", s),
+        Bson::Boolean(b) => b.to_string(),
+        Bson::Null => "null".to_string(),
+        Bson::Array(items) => format!(
+            "[{}]",
+            items
+                .iter()
+                .map(render_value)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        Bson::Document(doc) => render_document(doc),
+        other => panic!("no literal for {:?}", other),
+    }
+}
+
+/// Render a document as a shell object literal
+fn render_document(doc: &Document) -> String {
+    let fields: Vec<String> = doc
+        .iter()
+        .map(|(key, value)| format!("'{}': {}", key, render_value(value)))
+        .collect();
+    format!("{{ {} }}", fields.join(", "))
+}
+
+/// Render a command as shell input
+fn render(command: &Command) -> String {
+    match command {
+        Command::Query(QueryCommand::Find {
+            collection,
+            filter,
+            options,
+        }) => {
+            let mut text = format!("db.{}.find({})", collection, render_document(filter));
+            if let Some(sort) = &options.sort {
+                text.push_str(&format!(".sort({})", render_document(sort)));
+            }
+            if let Some(skip) = options.skip {
+                text.push_str(&format!(".skip({})", skip));
+            }
+            if let Some(limit) = options.limit {
+                text.push_str(&format!(".limit({})", limit));
+            }
+            text
+        }
+        Command::Query(QueryCommand::InsertOne {
+            collection,
+            document,
+        }) => format!("db.{}.insertOne({})", collection, render_document(document)),
+        Command::Query(QueryCommand::DeleteMany { collection, filter }) => {
+            format!("db.{}.deleteMany({})", collection, render_document(filter))
+        }
+        Command::Query(QueryCommand::CountDocuments { collection, filter }) => {
+            format!(
+                "db.{}.countDocuments({})",
+                collection,
+                render_document(filter)
+            )
+        }
+        Command::Admin(AdminCommand::DropCollection {
+            collection: name, ..
+        }) => format!("db.{}.drop()", name),
+        Command::Admin(AdminCommand::UseDatabase(name)) => format!("use {}", name),
+        Command::Admin(AdminCommand::ShowCollections) => "show collections".to_string(),
+        Command::Admin(AdminCommand::ShowDatabases) => "show dbs".to_string(),
+        other => panic!("no rendering for {:?}", other),
+    }
+}
+
+proptest! {
+    #[test]
+    fn shell_round_trip(command in command()) {
+        let text = render(&command);
+        let parsed = Parser::new().parse(&text);
+        prop_assert_eq!(parsed.ok(), Some(command), "input: {}", text);
+    }
+
+    #[cfg(feature = "sql")]
+    #[test]
+    fn sql_round_trip(
+        collection in collection_name(),
+        field in field_name(),
+        value in 0i64..1_000_000,
+        descending in any::<bool>(),
+        limit in prop::option::of(0u64..10_000),
+        offset in prop::option::of(0u64..10_000),
+    ) {
+        let mut text = format!(
+            "SELECT * FROM {} WHERE {} = {} ORDER BY {} {}",
+            collection,
+            field,
+            value,
+            field,
+            if descending { "DESC" } else { "ASC" }
+        );
+        if let Some(limit) = limit {
+            text.push_str(&format!(" LIMIT {}", limit));
+        }
+        if let Some(offset) = offset {
+            text.push_str(&format!(" OFFSET {}", offset));
+        }
+
+        let expected = Command::Query(QueryCommand::Find {
+            collection,
+            filter: doc! { field.clone(): value },
+            options: FindOptions {
+                sort: Some(doc! { field: if descending { -1 } else { 1 } }),
+                limit: limit.map(|l| l as i64),
+                skip: offset,
+                ..Default::default()
+            },
+        });
+        let parsed = Parser::new().parse(&text);
+        prop_assert_eq!(parsed.ok(), Some(expected), "input: {}", text);
+    }
+
+    #[test]
+    fn arbitrary_input_never_panics(input in "\\PC{0,64}") {
+        fuzz_entry(input.as_bytes());
+    }
+
+    #[test]
+    fn shell_like_input_never_panics(
+        parts in prop::collection::vec(
+            prop_oneof![
+                Just("db."), Just("users"), Just(".find("), Just(")"), Just("({"),
+                Just("})"), Just("["), Just("]"), Just(", "), Just("'"), Just("\""),
+                Just("$gt: "), Just("1"), Just("-"), Just("|> "), Just("export csv"),
+                Just("SELECT "), Just("* FROM "), Just(" WHERE "), Just(" = "),
+                Just("ObjectId("), Just("new Date("), Just("=> "), Just(";"),
+            ],
+            0..24,
+        )
+    ) {
+        fuzz_entry(parts.concat().as_bytes());
+    }
+}